@@ -25,6 +25,8 @@ pub enum CachedDist {
 pub struct CachedRegistryDist {
     pub filename: WheelFilename,
     pub path: PathBuf,
+    /// The SHA-256 digest of the archive from which the wheel was unzipped, if known.
+    pub sha256: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -33,47 +35,64 @@ pub struct CachedDirectUrlDist {
     pub url: VerbatimUrl,
     pub path: PathBuf,
     pub editable: bool,
+    /// The SHA-256 digest of the archive from which the wheel was unzipped, if known.
+    pub sha256: Option<String>,
 }
 
 impl CachedDist {
-    /// Initialize a [`CachedDist`] from a [`Dist`].
-    pub fn from_remote(remote: Dist, filename: WheelFilename, path: PathBuf) -> Self {
+    /// Initialize a [`CachedDist`] from a [`Dist`], along with the SHA-256 digest of the archive
+    /// from which it was unzipped, if known.
+    pub fn from_remote(
+        remote: Dist,
+        filename: WheelFilename,
+        path: PathBuf,
+        sha256: Option<String>,
+    ) -> Self {
         match remote {
-            Dist::Built(BuiltDist::Registry(_dist)) => {
-                Self::Registry(CachedRegistryDist { filename, path })
-            }
+            Dist::Built(BuiltDist::Registry(_dist)) => Self::Registry(CachedRegistryDist {
+                filename,
+                path,
+                sha256,
+            }),
             Dist::Built(BuiltDist::DirectUrl(dist)) => Self::Url(CachedDirectUrlDist {
                 filename,
                 url: dist.url,
                 path,
                 editable: false,
+                sha256,
             }),
             Dist::Built(BuiltDist::Path(dist)) => Self::Url(CachedDirectUrlDist {
                 filename,
                 url: dist.url,
                 path,
                 editable: false,
+                sha256,
+            }),
+            Dist::Source(SourceDist::Registry(_dist)) => Self::Registry(CachedRegistryDist {
+                filename,
+                path,
+                sha256,
             }),
-            Dist::Source(SourceDist::Registry(_dist)) => {
-                Self::Registry(CachedRegistryDist { filename, path })
-            }
             Dist::Source(SourceDist::DirectUrl(dist)) => Self::Url(CachedDirectUrlDist {
                 filename,
                 url: dist.url,
                 path,
                 editable: false,
+                sha256,
             }),
             Dist::Source(SourceDist::Git(dist)) => Self::Url(CachedDirectUrlDist {
                 filename,
                 url: dist.url,
                 path,
                 editable: false,
+                sha256,
             }),
             Dist::Source(SourceDist::Path(dist)) => Self::Url(CachedDirectUrlDist {
                 filename,
                 url: dist.url,
                 path,
                 editable: dist.editable,
+                sha256,
             }),
         }
     }
@@ -98,12 +117,25 @@ impl CachedDist {
                         editable: dist.editable,
                     })))
                 } else {
-                    DirectUrl::try_from(dist.url.raw()).map(Some)
+                    let mut direct_url = DirectUrl::try_from(dist.url.raw())?;
+                    if let DirectUrl::Archive(archive) = &mut direct_url {
+                        archive.sha256.clone_from(&dist.sha256);
+                    }
+                    Ok(Some(direct_url))
                 }
             }
         }
     }
 
+    /// Return the SHA-256 digest of the archive from which the distribution was unzipped, if known
+    /// (e.g., if it was downloaded, rather than built or read from a local file).
+    pub fn sha256(&self) -> Option<&str> {
+        match self {
+            Self::Registry(dist) => dist.sha256.as_deref(),
+            Self::Url(dist) => dist.sha256.as_deref(),
+        }
+    }
+
     pub fn editable(&self) -> bool {
         match self {
             Self::Registry(_) => false,
@@ -127,6 +159,7 @@ impl CachedDirectUrlDist {
             url,
             path,
             editable: false,
+            sha256: None,
         }
     }
 }
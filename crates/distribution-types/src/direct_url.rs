@@ -1,6 +1,9 @@
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Error, Result};
+use sha2::{Digest, Sha256};
 use url::Url;
 
 use uv_git::{GitSha, GitUrl};
@@ -46,6 +49,8 @@ pub struct DirectGitUrl {
 pub struct DirectArchiveUrl {
     pub url: Url,
     pub subdirectory: Option<PathBuf>,
+    /// The SHA-256 digest of the archive, as computed when it was downloaded, if known.
+    pub sha256: Option<String>,
 }
 
 impl TryFrom<&Url> for DirectGitUrl {
@@ -69,6 +74,7 @@ impl From<&Url> for DirectArchiveUrl {
         Self {
             url: url.clone(),
             subdirectory: get_subdirectory(url),
+            sha256: None,
         }
     }
 }
@@ -87,6 +93,46 @@ fn get_subdirectory(url: &Url) -> Option<PathBuf> {
     Some(PathBuf::from(subdirectory))
 }
 
+/// If the URL includes a hash, extract it, as in:
+///   `https://files.example.com/tqdm-4.66.1-py3-none-any.whl#sha256=dedc...`
+fn get_hash(url: &Url) -> Option<(&str, &str)> {
+    let fragment = url.fragment()?;
+    fragment.split('&').find_map(|fragment| {
+        let (algorithm, digest) = fragment.split_once('=')?;
+        matches!(algorithm, "md5" | "sha256" | "sha384" | "sha512").then_some((algorithm, digest))
+    })
+}
+
+/// Return the URL without its fragment (e.g., `#subdirectory=...` or `#sha256=...`), which
+/// `direct_url.json` represents separately.
+fn without_fragment(url: &Url) -> String {
+    let mut url = url.clone();
+    url.set_fragment(None);
+    url.to_string()
+}
+
+/// Compute the hex-encoded SHA-256 digest of a local file.
+fn sha256_file(path: &Path) -> io::Result<String> {
+    let mut file = fs_err::File::open(path)?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Build the [`pypi_types::ArchiveInfo`] for an archive with the given hash, if known.
+fn archive_info(hash: Option<(&str, &str)>) -> pypi_types::ArchiveInfo {
+    match hash {
+        Some((algorithm, digest)) => pypi_types::ArchiveInfo {
+            hash: Some(format!("{algorithm}={digest}")),
+            hashes: Some(HashMap::from([(algorithm.to_string(), digest.to_string())])),
+        },
+        None => pypi_types::ArchiveInfo {
+            hash: None,
+            hashes: None,
+        },
+    }
+}
+
 /// Return the Git reference of the given URL, if it exists.
 pub fn git_reference(url: &Url) -> Result<Option<GitSha>, Error> {
     let DirectGitUrl { url, .. } = DirectGitUrl::try_from(url)?;
@@ -131,6 +177,34 @@ impl TryFrom<&LocalFileUrl> for pypi_types::DirectUrl {
     type Error = Error;
 
     fn try_from(value: &LocalFileUrl) -> Result<Self, Self::Error> {
+        // Local archives (as opposed to source trees) are recorded along with their hash.
+        if !value.editable {
+            if let Ok(path) = value.url.to_file_path() {
+                if !path.exists() {
+                    return Err(Error::msg(format!(
+                        "Local archive or directory does not exist: {}",
+                        path.display()
+                    )));
+                }
+                if path.is_file() {
+                    let digest = match get_hash(&value.url) {
+                        Some((algorithm, digest)) => (algorithm.to_string(), digest.to_string()),
+                        None => (
+                            "sha256".to_string(),
+                            sha256_file(&path).with_context(|| {
+                                format!("Failed to hash archive: {}", path.display())
+                            })?,
+                        ),
+                    };
+                    return Ok(Self::ArchiveUrl {
+                        url: without_fragment(&value.url),
+                        archive_info: archive_info(Some((&digest.0, &digest.1))),
+                        subdirectory: get_subdirectory(&value.url),
+                    });
+                }
+            }
+        }
+
         Ok(Self::LocalDirectory {
             url: value.url.to_string(),
             dir_info: pypi_types::DirInfo {
@@ -144,12 +218,12 @@ impl TryFrom<&DirectArchiveUrl> for pypi_types::DirectUrl {
     type Error = Error;
 
     fn try_from(value: &DirectArchiveUrl) -> Result<Self, Self::Error> {
+        // Prefer the hash in the URL, if any, over the digest computed when it was downloaded.
+        let hash = get_hash(&value.url)
+            .or_else(|| value.sha256.as_deref().map(|digest| ("sha256", digest)));
         Ok(Self::ArchiveUrl {
-            url: value.url.to_string(),
-            archive_info: pypi_types::ArchiveInfo {
-                hash: None,
-                hashes: None,
-            },
+            url: without_fragment(&value.url),
+            archive_info: archive_info(hash),
             subdirectory: value.subdirectory.clone(),
         })
     }
@@ -246,4 +320,56 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn direct_url_json_archive_hash() -> Result<()> {
+        let url = Url::parse(
+            "https://files.example.com/tqdm-4.66.1-py3-none-any.whl#sha256=dedc0b3d7a5e2c8f6e4c0f1a32b7c0d6c8b3f2e1a4d5c6b7a8f9e0d1c2b3a4f5",
+        )?;
+        let direct_url = pypi_types::DirectUrl::try_from(&DirectUrl::try_from(&url)?)?;
+        let pypi_types::DirectUrl::ArchiveUrl {
+            url, archive_info, ..
+        } = direct_url
+        else {
+            panic!("Expected an archive URL");
+        };
+        assert_eq!(
+            url,
+            "https://files.example.com/tqdm-4.66.1-py3-none-any.whl"
+        );
+        assert_eq!(
+            archive_info.hash.as_deref(),
+            Some("sha256=dedc0b3d7a5e2c8f6e4c0f1a32b7c0d6c8b3f2e1a4d5c6b7a8f9e0d1c2b3a4f5")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn direct_url_json_downloaded_hash() -> Result<()> {
+        let url = Url::parse("https://files.example.com/tqdm-4.66.1-py3-none-any.whl")?;
+        let DirectUrl::Archive(mut archive) = DirectUrl::try_from(&url)? else {
+            panic!("Expected an archive URL");
+        };
+        archive.sha256 =
+            Some("dedc0b3d7a5e2c8f6e4c0f1a32b7c0d6c8b3f2e1a4d5c6b7a8f9e0d1c2b3a4f5".to_string());
+        let direct_url = pypi_types::DirectUrl::try_from(&DirectUrl::Archive(archive))?;
+        let pypi_types::DirectUrl::ArchiveUrl { archive_info, .. } = direct_url else {
+            panic!("Expected an archive URL");
+        };
+        assert_eq!(
+            archive_info.hash.as_deref(),
+            Some("sha256=dedc0b3d7a5e2c8f6e4c0f1a32b7c0d6c8b3f2e1a4d5c6b7a8f9e0d1c2b3a4f5")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn direct_url_json_missing_archive() -> Result<()> {
+        let path = std::env::temp_dir()
+            .join("uv-missing-archive")
+            .join("tqdm-4.66.1-py3-none-any.whl");
+        let url = Url::from_file_path(path).expect("path is absolute");
+        assert!(pypi_types::DirectUrl::try_from(&DirectUrl::try_from(&url)?).is_err());
+        Ok(())
+    }
 }
@@ -43,7 +43,8 @@ impl File {
     /// `TryFrom` instead of `From` to filter out files with invalid requires python version specifiers
     pub fn try_from(file: pypi_types::File, base: &Url) -> Result<Self, FileConversionError> {
         Ok(Self {
-            dist_info_metadata: file.dist_info_metadata,
+            // Per PEP 714, `core-metadata` supersedes `dist-info-metadata`.
+            dist_info_metadata: file.core_metadata.or(file.dist_info_metadata),
             filename: file.filename,
             hashes: file.hashes,
             requires_python: file
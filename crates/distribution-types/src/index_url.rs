@@ -117,6 +117,7 @@ impl FromStr for FlatIndexLocation {
     /// - `file:../ferris/`
     /// - `../ferris/`
     /// - `https://download.pytorch.org/whl/torch_stable.html`
+    /// - `s3://wheels/nightly/`
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         // Expand environment variables.
         let expanded = expand_env_vars(s);
@@ -142,6 +143,12 @@ impl FromStr for FlatIndexLocation {
                     Ok(Self::Url(url))
                 }
 
+                // Ex) `s3://wheels/nightly/` or `gs://wheels/nightly/`
+                None if matches!(scheme, "s3" | "gs") => {
+                    let url = Url::parse(expanded.as_ref())?;
+                    Ok(Self::Url(url))
+                }
+
                 // Ex) `C:\Users\ferris\wheel-0.42.0.tar.gz`
                 None => {
                     let path = PathBuf::from(expanded.as_ref());
@@ -179,6 +186,7 @@ impl Display for FlatIndexLocation {
 #[derive(Debug, Clone)]
 pub struct IndexLocations {
     index: Option<IndexUrl>,
+    index_mirrors: Vec<Url>,
     extra_index: Vec<IndexUrl>,
     flat_index: Vec<FlatIndexLocation>,
     no_index: bool,
@@ -189,6 +197,7 @@ impl Default for IndexLocations {
     fn default() -> Self {
         Self {
             index: Some(DEFAULT_INDEX_URL.clone()),
+            index_mirrors: Vec::new(),
             extra_index: Vec::new(),
             flat_index: Vec::new(),
             no_index: false,
@@ -206,18 +215,31 @@ impl IndexLocations {
     ) -> Self {
         Self {
             index,
+            index_mirrors: Vec::new(),
             extra_index,
             flat_index,
             no_index,
         }
     }
 
+    /// Set the mirrors of the primary index, to which requests fail over if the index can't be
+    /// reached.
+    #[must_use]
+    pub fn with_index_mirrors(self, index_mirrors: Vec<Url>) -> Self {
+        Self {
+            index_mirrors,
+            ..self
+        }
+    }
+
     /// Combine a set of index locations.
     ///
     /// If either the current or the other index locations have `no_index` set, the result will
     /// have `no_index` set.
     ///
-    /// If the current index location has an `index` set, it will be preserved.
+    /// If the current index location has an `index` set, it will be preserved. Otherwise, if the
+    /// other index locations replace the primary index, the current mirrors are dropped, since
+    /// they mirror the default index rather than the replacement.
     #[must_use]
     pub fn combine(
         self,
@@ -226,8 +248,14 @@ impl IndexLocations {
         flat_index: Vec<FlatIndexLocation>,
         no_index: bool,
     ) -> Self {
+        let index_mirrors = if self.index.is_none() && index.is_some() {
+            Vec::new()
+        } else {
+            self.index_mirrors
+        };
         Self {
             index: self.index.or(index),
+            index_mirrors,
             extra_index: self.extra_index.into_iter().chain(extra_index).collect(),
             flat_index: self.flat_index.into_iter().chain(flat_index).collect(),
             no_index: self.no_index || no_index,
@@ -252,6 +280,17 @@ impl<'a> IndexLocations {
         }
     }
 
+    /// Return the mirrors of the primary [`IndexUrl`].
+    ///
+    /// If `--no-index` is set, return an empty slice.
+    pub fn index_mirrors(&'a self) -> &'a [Url] {
+        if self.no_index {
+            &[]
+        } else {
+            &self.index_mirrors
+        }
+    }
+
     /// Return an iterator over the extra [`IndexUrl`] entries.
     pub fn extra_index(&'a self) -> impl Iterator<Item = &'a IndexUrl> + 'a {
         if self.no_index {
@@ -275,6 +314,7 @@ impl<'a> IndexLocations {
     pub fn index_urls(&'a self) -> IndexUrls {
         IndexUrls {
             index: self.index.clone(),
+            index_mirrors: self.index_mirrors.clone(),
             extra_index: self.extra_index.clone(),
             no_index: self.no_index,
         }
@@ -284,6 +324,7 @@ impl<'a> IndexLocations {
     pub fn urls(&'a self) -> impl Iterator<Item = &'a Url> + 'a {
         self.indexes()
             .map(IndexUrl::url)
+            .chain(self.index_mirrors())
             .chain(self.flat_index.iter().filter_map(|index| match index {
                 FlatIndexLocation::Path(_) => None,
                 FlatIndexLocation::Url(url) => Some(url),
@@ -297,6 +338,7 @@ impl<'a> IndexLocations {
 #[derive(Debug, Clone)]
 pub struct IndexUrls {
     index: Option<IndexUrl>,
+    index_mirrors: Vec<Url>,
     extra_index: Vec<IndexUrl>,
     no_index: bool,
 }
@@ -306,6 +348,7 @@ impl Default for IndexUrls {
     fn default() -> Self {
         Self {
             index: Some(DEFAULT_INDEX_URL.clone()),
+            index_mirrors: Vec::new(),
             extra_index: Vec::new(),
             no_index: false,
         }
@@ -318,7 +361,7 @@ impl<'a> IndexUrls {
     /// If `--no-index` is set, return `None`.
     ///
     /// If no index is provided, use the `PyPI` index.
-    fn index(&'a self) -> Option<&'a IndexUrl> {
+    pub fn index(&'a self) -> Option<&'a IndexUrl> {
         if self.no_index {
             None
         } else {
@@ -329,6 +372,17 @@ impl<'a> IndexUrls {
         }
     }
 
+    /// Return the mirrors of the primary [`IndexUrl`].
+    ///
+    /// If `--no-index` is set, return an empty slice.
+    pub fn index_mirrors(&'a self) -> &'a [Url] {
+        if self.no_index {
+            &[]
+        } else {
+            &self.index_mirrors
+        }
+    }
+
     /// Return an iterator over the extra [`IndexUrl`] entries.
     fn extra_index(&'a self) -> impl Iterator<Item = &'a IndexUrl> + 'a {
         if self.no_index {
@@ -351,6 +405,7 @@ impl From<IndexLocations> for IndexUrls {
     fn from(locations: IndexLocations) -> Self {
         Self {
             index: locations.index,
+            index_mirrors: locations.index_mirrors,
             extra_index: locations.extra_index,
             no_index: locations.no_index,
         }
@@ -383,5 +438,36 @@ mod test {
                 Url::parse("https://download.pytorch.org/whl/torch_stable.html").unwrap()
             )
         );
+        assert_eq!(
+            FlatIndexLocation::from_str("s3://wheels/nightly/").unwrap(),
+            FlatIndexLocation::Url(Url::parse("s3://wheels/nightly/").unwrap())
+        );
+    }
+
+    #[test]
+    fn combine_index_mirrors() {
+        let mirror = Url::parse("https://mirror.example.com/simple/").unwrap();
+        let private = IndexUrl::from_str("https://private.example.com/simple").unwrap();
+
+        // Mirrors of the default index don't apply to an index set by a requirements file.
+        let locations = IndexLocations::new(None, Vec::new(), Vec::new(), false)
+            .with_index_mirrors(vec![mirror.clone()])
+            .combine(Some(private.clone()), Vec::new(), Vec::new(), false);
+        assert_eq!(locations.index(), Some(&private));
+        assert!(locations.index_mirrors().is_empty());
+
+        // Mirrors of an explicit index are retained, since that index takes precedence.
+        let index = IndexUrl::from_str("https://index.example.com/simple").unwrap();
+        let locations = IndexLocations::new(Some(index.clone()), Vec::new(), Vec::new(), false)
+            .with_index_mirrors(vec![mirror.clone()])
+            .combine(Some(private), Vec::new(), Vec::new(), false);
+        assert_eq!(locations.index(), Some(&index));
+        assert_eq!(locations.index_mirrors(), &[mirror.clone()]);
+
+        // Files that don't set an index leave the mirrors in place.
+        let locations = IndexLocations::new(None, Vec::new(), Vec::new(), false)
+            .with_index_mirrors(vec![mirror.clone()])
+            .combine(None, Vec::new(), Vec::new(), false);
+        assert_eq!(locations.index_mirrors(), &[mirror]);
     }
 }
@@ -2,9 +2,9 @@ use std::fmt::{Display, Formatter};
 
 use pep440_rs::VersionSpecifiers;
 use platform_tags::{IncompatibleTag, TagCompatibility, TagPriority};
-use pypi_types::{Hashes, Yanked};
+use pypi_types::{DistInfoMetadata, Hashes, Yanked};
 
-use crate::{Dist, InstalledDist, ResolvedDistRef};
+use crate::{Dist, InstalledDist, ResolvedDistRef, SourceDist};
 
 /// A collection of distributions that have been filtered by relevance.
 #[derive(Debug, Default, Clone)]
@@ -77,6 +77,12 @@ impl Display for IncompatibleDist {
                         reason.trim().trim_end_matches('.')
                     ),
                 },
+                IncompatibleWheel::MissingHash => {
+                    f.write_str("no wheels are available with a hash and hashes are required")
+                }
+                IncompatibleWheel::MismatchedHash => {
+                    f.write_str("no wheels are available with a matching hash")
+                }
                 IncompatibleWheel::ExcludeNewer(ts) => match ts {
                     Some(_) => f.write_str("it was published after the exclude newer time"),
                     None => f.write_str("it has no publish time"),
@@ -97,6 +103,12 @@ impl Display for IncompatibleDist {
                         reason.trim().trim_end_matches('.')
                     ),
                 },
+                IncompatibleSource::MissingHash => f.write_str(
+                    "no source distribution is available with a hash and hashes are required",
+                ),
+                IncompatibleSource::MismatchedHash => {
+                    f.write_str("no source distribution is available with a matching hash")
+                }
                 IncompatibleSource::ExcludeNewer(ts) => match ts {
                     Some(_) => f.write_str("it was published after the exclude newer time"),
                     None => f.write_str("it has no publish time"),
@@ -122,6 +134,8 @@ pub enum IncompatibleWheel {
     Tag(IncompatibleTag),
     RequiresPython(VersionSpecifiers),
     Yanked(Yanked),
+    MissingHash,
+    MismatchedHash,
     NoBinary,
 }
 
@@ -136,6 +150,8 @@ pub enum IncompatibleSource {
     ExcludeNewer(Option<i64>),
     RequiresPython(VersionSpecifiers),
     Yanked(Yanked),
+    MissingHash,
+    MismatchedHash,
     NoBuild,
 }
 
@@ -317,7 +333,16 @@ impl<'a> CompatibleDist<'a> {
     /// Avoid building source distributions we don't need.
     pub fn prefetchable(&self) -> bool {
         match *self {
-            CompatibleDist::SourceDist(_) => false,
+            // Source distributions are only prefetchable if their metadata can be fetched without
+            // building them, i.e., if the index serves it directly (PEP 658).
+            CompatibleDist::SourceDist(dist) => match dist {
+                Dist::Source(SourceDist::Registry(sdist)) => sdist
+                    .file
+                    .dist_info_metadata
+                    .as_ref()
+                    .is_some_and(DistInfoMetadata::is_available),
+                _ => false,
+            },
             CompatibleDist::InstalledDist(_)
             | CompatibleDist::CompatibleWheel(_, _)
             | CompatibleDist::IncompatibleWheel { .. } => true,
@@ -381,18 +406,30 @@ impl IncompatibleSource {
             Self::ExcludeNewer(timestamp_self) => match other {
                 // Smaller timestamps are closer to the cut-off time
                 Self::ExcludeNewer(timestamp_other) => timestamp_other < timestamp_self,
-                Self::NoBuild | Self::RequiresPython(_) | Self::Yanked(_) => true,
+                Self::NoBuild
+                | Self::RequiresPython(_)
+                | Self::Yanked(_)
+                | Self::MissingHash
+                | Self::MismatchedHash => true,
             },
             Self::RequiresPython(_) => match other {
                 Self::ExcludeNewer(_) => false,
                 // Version specifiers cannot be reasonably compared
                 Self::RequiresPython(_) => false,
-                Self::NoBuild | Self::Yanked(_) => true,
+                Self::NoBuild | Self::Yanked(_) | Self::MissingHash | Self::MismatchedHash => true,
             },
             Self::Yanked(_) => match other {
                 Self::ExcludeNewer(_) | Self::RequiresPython(_) => false,
                 // Yanks with a reason are more helpful for errors
                 Self::Yanked(yanked_other) => matches!(yanked_other, Yanked::Reason(_)),
+                Self::NoBuild | Self::MissingHash | Self::MismatchedHash => true,
+            },
+            Self::MissingHash | Self::MismatchedHash => match other {
+                Self::ExcludeNewer(_)
+                | Self::RequiresPython(_)
+                | Self::Yanked(_)
+                | Self::MissingHash
+                | Self::MismatchedHash => false,
                 Self::NoBuild => true,
             },
             Self::NoBuild => false,
@@ -412,23 +449,41 @@ impl IncompatibleWheel {
                         timestamp_other < timestamp_self
                     }
                 },
-                Self::NoBinary | Self::RequiresPython(_) | Self::Tag(_) | Self::Yanked(_) => true,
+                Self::NoBinary
+                | Self::RequiresPython(_)
+                | Self::Tag(_)
+                | Self::Yanked(_)
+                | Self::MissingHash
+                | Self::MismatchedHash => true,
             },
             Self::Tag(tag_self) => match other {
                 Self::ExcludeNewer(_) => false,
                 Self::Tag(tag_other) => tag_other > tag_self,
-                Self::NoBinary | Self::RequiresPython(_) | Self::Yanked(_) => true,
+                Self::NoBinary
+                | Self::RequiresPython(_)
+                | Self::Yanked(_)
+                | Self::MissingHash
+                | Self::MismatchedHash => true,
             },
             Self::RequiresPython(_) => match other {
                 Self::ExcludeNewer(_) | Self::Tag(_) => false,
                 // Version specifiers cannot be reasonably compared
                 Self::RequiresPython(_) => false,
-                Self::NoBinary | Self::Yanked(_) => true,
+                Self::NoBinary | Self::Yanked(_) | Self::MissingHash | Self::MismatchedHash => true,
             },
             Self::Yanked(_) => match other {
                 Self::ExcludeNewer(_) | Self::Tag(_) | Self::RequiresPython(_) => false,
                 // Yanks with a reason are more helpful for errors
                 Self::Yanked(yanked_other) => matches!(yanked_other, Yanked::Reason(_)),
+                Self::NoBinary | Self::MissingHash | Self::MismatchedHash => true,
+            },
+            Self::MissingHash | Self::MismatchedHash => match other {
+                Self::ExcludeNewer(_)
+                | Self::Tag(_)
+                | Self::RequiresPython(_)
+                | Self::Yanked(_)
+                | Self::MissingHash
+                | Self::MismatchedHash => false,
                 Self::NoBinary => true,
             },
             Self::NoBinary => false,
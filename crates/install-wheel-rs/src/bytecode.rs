@@ -0,0 +1,164 @@
+//! Record bytecode compiled after installation in the `RECORD` of its distribution.
+
+use std::path::Path;
+
+use data_encoding::BASE64URL_NOPAD;
+use fs_err as fs;
+use fs_err::File;
+use rustc_hash::FxHashSet;
+use sha2::{Digest, Sha256};
+
+use crate::record::RecordEntry;
+use crate::wheel::read_record_file;
+use crate::Error;
+
+/// Add the `.pyc` files compiled for the Python modules of a distribution to its `RECORD`, such
+/// that they're tracked (and hashed) like every other installed file.
+///
+/// For each `.py` file in the `RECORD`, any `__pycache__/{module}.{tag}.pyc` (or
+/// `__pycache__/{module}.{tag}.opt-{level}.pyc`) file alongside it is added, if it isn't present
+/// already.
+///
+/// Returns the number of added entries.
+pub fn record_bytecode(site_packages: &Path, dist_info: &Path) -> Result<usize, Error> {
+    let record_path = dist_info.join("RECORD");
+    let mut record = read_record_file(&mut File::open(&record_path)?)?;
+
+    let recorded: FxHashSet<String> = record.iter().map(|entry| entry.path.clone()).collect();
+    let mut added = Vec::new();
+    for entry in &record {
+        // Ignore files outside `site-packages`, like scripts.
+        if entry.path.starts_with("..") {
+            continue;
+        }
+        let Some(module) = entry.path.strip_suffix(".py") else {
+            continue;
+        };
+        let (parent, stem) = match module.rsplit_once('/') {
+            Some((parent, stem)) => (Some(parent), stem),
+            None => (None, module),
+        };
+        let pycache = match parent {
+            Some(parent) => format!("{parent}/__pycache__"),
+            None => "__pycache__".to_string(),
+        };
+
+        let entries = match fs::read_dir(site_packages.join(&pycache)) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(err) => return Err(err.into()),
+        };
+        for pyc in entries {
+            let pyc = pyc?;
+            let file_name = pyc.file_name();
+            let Some(file_name) = file_name.to_str() else {
+                continue;
+            };
+            if !is_bytecode_for(file_name, stem) {
+                continue;
+            }
+            let path = format!("{pycache}/{file_name}");
+            if recorded.contains(&path) {
+                continue;
+            }
+            let contents = fs::read(pyc.path())?;
+            let hash = Sha256::new().chain_update(&contents).finalize();
+            added.push(RecordEntry {
+                path,
+                hash: Some(format!("sha256={}", BASE64URL_NOPAD.encode(&hash))),
+                size: Some(contents.len() as u64),
+            });
+        }
+    }
+
+    if added.is_empty() {
+        return Ok(0);
+    }
+
+    let count = added.len();
+    record.extend(added);
+    record.sort();
+    let mut record_writer = csv::WriterBuilder::new()
+        .has_headers(false)
+        .escape(b'"')
+        .from_path(&record_path)?;
+    for entry in record {
+        record_writer.serialize(entry)?;
+    }
+    record_writer.flush()?;
+
+    Ok(count)
+}
+
+/// Returns `true` if the given file in `__pycache__` contains the bytecode of the module with the
+/// given stem, e.g., `cli.cpython-312.pyc` or `cli.cpython-312.opt-1.pyc` for `cli`.
+fn is_bytecode_for(file_name: &str, stem: &str) -> bool {
+    let Some(tag) = file_name
+        .strip_prefix(stem)
+        .and_then(|rest| rest.strip_prefix('.'))
+        .and_then(|rest| rest.strip_suffix(".pyc"))
+    else {
+        return false;
+    };
+    match tag.split_once('.') {
+        None => !tag.is_empty(),
+        Some((tag, optimization)) => !tag.is_empty() && optimization.starts_with("opt-"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fs_err as fs;
+    use indoc::indoc;
+
+    use super::{is_bytecode_for, record_bytecode};
+
+    #[test]
+    fn bytecode_file_names() {
+        assert!(is_bytecode_for("cli.cpython-312.pyc", "cli"));
+        assert!(is_bytecode_for("cli.cpython-312.opt-1.pyc", "cli"));
+        assert!(!is_bytecode_for("cli_utils.cpython-312.pyc", "cli"));
+        assert!(!is_bytecode_for("cli.utils.cpython-312.pyc", "cli"));
+        assert!(!is_bytecode_for("cli.pyc", "cli"));
+    }
+
+    #[test]
+    fn record() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let site_packages = temp_dir.path();
+        let write = |path: &str, contents: &str| {
+            let path = site_packages.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        };
+        write("tqdm/__init__.py", "");
+        write("tqdm/__pycache__/__init__.cpython-312.pyc", "bytecode");
+        write("tqdm/__pycache__/other.cpython-312.pyc", "bytecode");
+        write("top.py", "");
+        write("__pycache__/top.cpython-312.pyc", "bytecode");
+        write(
+            "tqdm-4.66.1.dist-info/RECORD",
+            "tqdm/__init__.py,sha256=47DEQpj8HBSa-_TImW-5JCeuQeRkm5NMpJWZG3hSuFU,0\n\
+             top.py,sha256=47DEQpj8HBSa-_TImW-5JCeuQeRkm5NMpJWZG3hSuFU,0\n\
+             ../../../bin/tqdm.py,,\n\
+             tqdm-4.66.1.dist-info/RECORD,,\n",
+        );
+
+        let dist_info = site_packages.join("tqdm-4.66.1.dist-info");
+        assert_eq!(record_bytecode(site_packages, &dist_info).unwrap(), 2);
+        assert_eq!(
+            fs::read_to_string(dist_info.join("RECORD")).unwrap(),
+            indoc! {"
+                ../../../bin/tqdm.py,,
+                __pycache__/top.cpython-312.pyc,sha256=jolhhxJ3JqraL25AfXCjkENVR2jdQ2f00Nz0V5IMBDI,8
+                top.py,sha256=47DEQpj8HBSa-_TImW-5JCeuQeRkm5NMpJWZG3hSuFU,0
+                tqdm-4.66.1.dist-info/RECORD,,
+                tqdm/__init__.py,sha256=47DEQpj8HBSa-_TImW-5JCeuQeRkm5NMpJWZG3hSuFU,0
+                tqdm/__pycache__/__init__.cpython-312.pyc,sha256=jolhhxJ3JqraL25AfXCjkENVR2jdQ2f00Nz0V5IMBDI,8
+            "}
+        );
+
+        // Recording is idempotent.
+        assert_eq!(record_bytecode(site_packages, &dist_info).unwrap(), 0);
+    }
+}
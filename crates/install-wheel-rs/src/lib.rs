@@ -8,13 +8,18 @@ use platform_info::PlatformInfoError;
 use thiserror::Error;
 use zip::result::ZipError;
 
+pub use bytecode::record_bytecode;
 use pep440_rs::Version;
 use platform_tags::{Arch, Os};
 use pypi_types::Scheme;
+pub use record::{
+    read_installed_files, verify_installed_files, InstalledFile, InstalledFileStatus,
+};
 pub use uninstall::{uninstall_wheel, Uninstall};
 use uv_fs::Simplified;
 use uv_normalize::PackageName;
 
+mod bytecode;
 pub mod linker;
 pub mod metadata;
 mod record;
@@ -35,6 +40,21 @@ pub struct Layout {
     pub scheme: Scheme,
 }
 
+/// How to locate the Python interpreter from the scripts (and entrypoint launchers) installed
+/// into an environment.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum ShebangMode {
+    /// Use the absolute path to the interpreter (e.g., `#!/path/to/.venv/bin/python`).
+    #[default]
+    Absolute,
+    /// Locate the interpreter relative to the script itself, such that the environment can be
+    /// moved as long as the interpreter stays at the same relative location.
+    Relative,
+    /// Locate the interpreter on the `PATH` at runtime (e.g., `#!/usr/bin/env python3`).
+    Env,
+}
+
 /// Note: The caller is responsible for adding the path of the wheel we're installing.
 #[derive(Error, Debug)]
 pub enum Error {
@@ -1,13 +1,15 @@
 //! Like `wheel.rs`, but for installing wheels that have already been unzipped, rather than
 //! reading from a zip file.
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::Mutex;
 use std::time::SystemTime;
 
 use fs_err as fs;
 use fs_err::{DirEntry, File};
 use reflink_copy as reflink;
+use rustc_hash::FxHashSet;
 use tempfile::tempdir_in;
 use tracing::{debug, instrument};
 
@@ -15,13 +17,14 @@ use distribution_filename::WheelFilename;
 use pep440_rs::Version;
 use pypi_types::DirectUrl;
 use uv_normalize::PackageName;
+use uv_warnings::warn_user_once;
 
 use crate::script::{scripts_from_ini, Script};
 use crate::wheel::{
     extra_dist_info, install_data, parse_metadata, parse_wheel_file, read_record_file,
     write_script_entrypoints, LibKind,
 };
-use crate::{Error, Layout};
+use crate::{Error, Layout, ShebangMode};
 
 /// Install the given wheel to the given venv
 ///
@@ -38,6 +41,7 @@ pub fn install_wheel(
     direct_url: Option<&DirectUrl>,
     installer: Option<&str>,
     link_mode: LinkMode,
+    shebang: ShebangMode,
 ) -> Result<(), Error> {
     let dist_info_prefix = find_dist_info(&wheel)?;
     let metadata = dist_info_metadata(&dist_info_prefix, &wheel)?;
@@ -87,8 +91,22 @@ pub fn install_wheel(
     debug!(name, "Writing entrypoints");
     let (console_scripts, gui_scripts) =
         parse_scripts(&wheel, &dist_info_prefix, None, layout.python_version.1)?;
-    write_script_entrypoints(layout, site_packages, &console_scripts, &mut record, false)?;
-    write_script_entrypoints(layout, site_packages, &gui_scripts, &mut record, true)?;
+    write_script_entrypoints(
+        layout,
+        site_packages,
+        &console_scripts,
+        &mut record,
+        false,
+        shebang,
+    )?;
+    write_script_entrypoints(
+        layout,
+        site_packages,
+        &gui_scripts,
+        &mut record,
+        true,
+        shebang,
+    )?;
 
     // 2.a Unpacked archive includes distribution-1.0.dist-info/ and (if there is data) distribution-1.0.data/.
     // 2.b Move each subtree of distribution-1.0.data/ onto its destination path. Each subdirectory of distribution-1.0.data/ is a key into a dict of destination directories, such as distribution-1.0.data/(purelib|platlib|headers|scripts|data). The initially supported paths are taken from distutils.command.install.
@@ -103,6 +121,7 @@ pub fn install_wheel(
             &console_scripts,
             &gui_scripts,
             &mut record,
+            shebang,
         )?;
         // 2.c If applicable, update scripts starting with #!python to point to the correct interpreter.
         // Script are unsupported through data
@@ -201,7 +220,7 @@ fn parse_scripts(
     scripts_from_ini(extras, python_minor, ini)
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
 pub enum LinkMode {
     /// Clone (i.e., copy-on-write) packages from the wheel into the site packages.
@@ -210,11 +229,20 @@ pub enum LinkMode {
     Copy,
     /// Hard link packages from the wheel into the site packages.
     Hardlink,
+    /// Symbolically link packages from the wheel into the site packages.
+    ///
+    /// Symlinked packages are broken by `uv cache clean` and `uv cache prune`, so this mode is
+    /// only suitable for environments that are recreated along with the cache.
+    Symlink,
 }
 
 impl Default for LinkMode {
     fn default() -> Self {
-        if cfg!(any(target_os = "macos", target_os = "ios")) {
+        if cfg!(any(
+            target_os = "macos",
+            target_os = "ios",
+            target_os = "linux"
+        )) {
             Self::Clone
         } else {
             Self::Hardlink
@@ -222,6 +250,11 @@ impl Default for LinkMode {
     }
 }
 
+/// The link modes that failed for each `site-packages` directory (e.g., as the filesystem doesn't
+/// support reflinks, or the cache is on a different device), shared across all installations in
+/// the process, such that each mode is only attempted once per directory.
+static UNSUPPORTED: Mutex<Option<FxHashSet<(PathBuf, LinkMode)>>> = Mutex::new(None);
+
 impl LinkMode {
     /// Extract a wheel by linking all of its files into site packages.
     #[instrument(skip_all)]
@@ -230,10 +263,73 @@ impl LinkMode {
         site_packages: impl AsRef<Path>,
         wheel: impl AsRef<Path>,
     ) -> Result<usize, Error> {
-        match self {
+        let site_packages = site_packages.as_ref();
+        match self.supported(site_packages) {
             Self::Clone => clone_wheel_files(site_packages, wheel),
             Self::Copy => copy_wheel_files(site_packages, wheel),
             Self::Hardlink => hardlink_wheel_files(site_packages, wheel),
+            Self::Symlink => symlink_wheel_files(site_packages, wheel),
+        }
+    }
+
+    /// Return the mode used to link files into the given `site-packages` directory; that is, this
+    /// mode, or the mode it fell back to after failing earlier in the process.
+    #[must_use]
+    pub fn effective(self, site_packages: &Path) -> Self {
+        self.supported(site_packages)
+    }
+
+    /// The mode to fall back to if this mode isn't supported.
+    fn fallback(self) -> Self {
+        match self {
+            Self::Clone => Self::Hardlink,
+            Self::Hardlink | Self::Symlink | Self::Copy => Self::Copy,
+        }
+    }
+
+    /// Return the first mode in the fallback chain of this mode that hasn't failed for the
+    /// given `site-packages` directory.
+    fn supported(self, site_packages: &Path) -> Self {
+        let unsupported = UNSUPPORTED.lock().unwrap();
+        let Some(unsupported) = unsupported.as_ref() else {
+            return self;
+        };
+        let mut mode = self;
+        while mode != Self::Copy && unsupported.contains(&(site_packages.to_path_buf(), mode)) {
+            mode = mode.fallback();
+        }
+        mode
+    }
+
+    /// Mark this mode as unsupported for the given `site-packages` directory, returning the mode
+    /// to fall back to.
+    fn mark_unsupported(self, site_packages: &Path, err: &std::io::Error) -> Self {
+        UNSUPPORTED
+            .lock()
+            .unwrap()
+            .get_or_insert_with(FxHashSet::default)
+            .insert((site_packages.to_path_buf(), self));
+        let fallback = self.fallback();
+        debug!(
+            "Failed to {self} files into {} ({err}); falling back to {fallback}",
+            site_packages.display()
+        );
+        if fallback == Self::Copy {
+            warn_user_once!(
+                "Failed to {self} files; falling back to full copy. This may lead to degraded performance. If the cache and target directories are on different filesystems, `--link-mode={self}` may not be supported. If this is intentional, set `--link-mode=copy` to suppress this warning."
+            );
+        }
+        fallback
+    }
+}
+
+impl std::fmt::Display for LinkMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Clone => write!(f, "clone"),
+            Self::Copy => write!(f, "copy"),
+            Self::Hardlink => write!(f, "hardlink"),
+            Self::Symlink => write!(f, "symlink"),
         }
     }
 }
@@ -242,27 +338,32 @@ impl LinkMode {
 /// via copy-on-write, which is similar to a hard link, but allows the files to be modified
 /// independently (that is, the file is copied upon modification).
 ///
-/// This method uses `clonefile` on macOS, and `reflink` on Linux.
+/// This method uses `clonefile` on macOS (APFS), and `FICLONE` on Linux (e.g., btrfs and XFS),
+/// such that cloning even large wheels is nearly instantaneous and uses no additional disk space.
+/// On filesystems without copy-on-write support, the first clone fails, and we fall back to
+/// hard-linking (or copying) instead.
 fn clone_wheel_files(
     site_packages: impl AsRef<Path>,
     wheel: impl AsRef<Path>,
 ) -> Result<usize, Error> {
-    let mut count = 0usize;
     let mut attempt = Attempt::default();
 
-    // On macOS, directly can be recursively copied with a single `clonefile` call.
-    // So we only need to iterate over the top-level of the directory, and copy each file or
-    // subdirectory unless the subdirectory exists already in which case we'll need to recursively
-    // merge its contents with the existing directory.
-    for entry in fs::read_dir(wheel.as_ref())? {
-        clone_recursive(
-            site_packages.as_ref(),
-            wheel.as_ref(),
-            &entry?,
-            &mut attempt,
-        )?;
-        count += 1;
-    }
+    let result = if cfg!(any(target_os = "macos", target_os = "ios")) {
+        clone_wheel_directories(site_packages.as_ref(), wheel.as_ref(), &mut attempt)
+    } else {
+        clone_wheel_files_individually(site_packages.as_ref(), wheel.as_ref(), &mut attempt)
+    };
+    let count = match result {
+        Ok(count) => count,
+        Err(Error::Reflink { err, .. }) if attempt == Attempt::Initial => {
+            // If the first clone fails, cloning isn't supported (e.g., by the filesystem), so
+            // fall back for the entire wheel.
+            return LinkMode::Clone
+                .mark_unsupported(site_packages.as_ref(), &err)
+                .link_wheel_files(site_packages, wheel);
+        }
+        Err(err) => return Err(err),
+    };
 
     // The directory mtime is not updated when cloning and the mtime is used by CPython's
     // import mechanisms to determine if it should look for new packages in a directory.
@@ -292,9 +393,10 @@ fn clone_wheel_files(
     Ok(count)
 }
 
-// Hard linking / reflinking might not be supported but we (afaik) can't detect this ahead of time,
-// so we'll try hard linking / reflinking the first file - if this succeeds we'll know later
-// errors are not due to lack of os/fs support. If it fails, we'll switch to copying for the rest of the
+// Hard linking / reflinking / symlinking might not be supported but we (afaik) can't detect this
+// ahead of time, so we'll try linking the first file - if this succeeds we'll know later errors are
+// not due to lack of os/fs support. If it fails, we'll mark the mode as unsupported for the
+// `site-packages` directory (see `LinkMode::mark_unsupported`) and fall back for the rest of the
 // install.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 enum Attempt {
@@ -304,6 +406,74 @@ enum Attempt {
     UseCopyFallback,
 }
 
+/// Clone the top-level entries of a wheel into site packages.
+///
+/// On macOS, directories can be recursively copied with a single `clonefile` call. So we only
+/// need to iterate over the top-level of the directory, and copy each file or subdirectory unless
+/// the subdirectory exists already in which case we'll need to recursively merge its contents with
+/// the existing directory.
+fn clone_wheel_directories(
+    site_packages: &Path,
+    wheel: &Path,
+    attempt: &mut Attempt,
+) -> Result<usize, Error> {
+    let mut count = 0usize;
+    for entry in fs::read_dir(wheel)? {
+        clone_recursive(site_packages, wheel, &entry?, attempt)?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// Clone each file of a wheel into site packages.
+///
+/// On Linux (and Windows), only files can be cloned (via `FICLONE` and block cloning,
+/// respectively), so we walk the wheel, create each directory, and clone each file.
+fn clone_wheel_files_individually(
+    site_packages: &Path,
+    wheel: &Path,
+    attempt: &mut Attempt,
+) -> Result<usize, Error> {
+    let mut count = 0usize;
+    for entry in walkdir::WalkDir::new(wheel) {
+        let entry = entry?;
+        let from = entry.path();
+        let to = site_packages.join(from.strip_prefix(wheel).unwrap());
+
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&to)?;
+            continue;
+        }
+
+        if let Err(err) = reflink::reflink(from, &to) {
+            if err.kind() != std::io::ErrorKind::AlreadyExists {
+                return Err(Error::Reflink {
+                    from: from.to_path_buf(),
+                    to,
+                    err,
+                });
+            }
+
+            // If the file already exists, overwrite it.
+            debug!("File already exists, overwriting: {}", to.display());
+            let tempdir = tempdir_in(site_packages)?;
+            let tempfile = tempdir.path().join(entry.file_name());
+            if let Err(err) = reflink::reflink(from, &tempfile) {
+                return Err(Error::Reflink {
+                    from: from.to_path_buf(),
+                    to,
+                    err,
+                });
+            }
+            fs::rename(&tempfile, &to)?;
+        }
+
+        *attempt = Attempt::Subsequent;
+        count += 1;
+    }
+    Ok(count)
+}
+
 /// Recursively clone the contents of `from` into `to`.
 fn clone_recursive(
     site_packages: &Path,
@@ -317,68 +487,24 @@ fn clone_recursive(
 
     debug!("Cloning {} to {}", from.display(), to.display());
 
-    match attempt {
-        Attempt::Initial => {
-            if let Err(err) = reflink::reflink(&from, &to) {
-                if matches!(err.kind(), std::io::ErrorKind::AlreadyExists) {
-                    // If cloning/copying fails and the directory exists already, it must be merged recursively.
-                    if entry.file_type()?.is_dir() {
-                        for entry in fs::read_dir(from)? {
-                            clone_recursive(site_packages, wheel, &entry?, attempt)?;
-                        }
-                    } else {
-                        // If file already exists, overwrite it.
-                        let tempdir = tempdir_in(site_packages)?;
-                        let tempfile = tempdir.path().join(from.file_name().unwrap());
-                        if reflink::reflink(&from, &tempfile).is_ok() {
-                            fs::rename(&tempfile, to)?;
-                        } else {
-                            debug!("Failed to clone {} to temporary location {} - attempting to copy files as a fallback", from.display(), tempfile.display());
-                            *attempt = Attempt::UseCopyFallback;
-                            fs::copy(&from, &to)?;
-                        }
-                    }
-                } else {
-                    debug!(
-                        "Failed to clone {} to {} - attempting to copy files as a fallback",
-                        from.display(),
-                        to.display()
-                    );
-                    // switch to copy fallback
-                    *attempt = Attempt::UseCopyFallback;
-                    clone_recursive(site_packages, wheel, entry, attempt)?;
-                }
-            }
-        }
-        Attempt::Subsequent => {
-            if let Err(err) = reflink::reflink(&from, &to) {
-                if matches!(err.kind(), std::io::ErrorKind::AlreadyExists) {
-                    // If cloning/copying fails and the directory exists already, it must be merged recursively.
-                    if entry.file_type()?.is_dir() {
-                        for entry in fs::read_dir(from)? {
-                            clone_recursive(site_packages, wheel, &entry?, attempt)?;
-                        }
-                    } else {
-                        // If file already exists, overwrite it.
-                        let tempdir = tempdir_in(site_packages)?;
-                        let tempfile = tempdir.path().join(from.file_name().unwrap());
-                        reflink::reflink(&from, &tempfile)?;
-                        fs::rename(&tempfile, to)?;
-                    }
-                } else {
-                    return Err(Error::Reflink { from, to, err });
-                }
-            }
-        }
-        Attempt::UseCopyFallback => {
+    if let Err(err) = reflink::reflink(&from, &to) {
+        if matches!(err.kind(), std::io::ErrorKind::AlreadyExists) {
+            // If cloning fails and the directory exists already, it must be merged recursively.
             if entry.file_type()?.is_dir() {
-                fs::create_dir_all(&to)?;
                 for entry in fs::read_dir(from)? {
                     clone_recursive(site_packages, wheel, &entry?, attempt)?;
                 }
             } else {
-                fs::copy(&from, &to)?;
+                // If file already exists, overwrite it.
+                let tempdir = tempdir_in(site_packages)?;
+                let tempfile = tempdir.path().join(from.file_name().unwrap());
+                if let Err(err) = reflink::reflink(&from, &tempfile) {
+                    return Err(Error::Reflink { from, to, err });
+                }
+                fs::rename(&tempfile, to)?;
             }
+        } else {
+            return Err(Error::Reflink { from, to, err });
         }
     }
 
@@ -460,13 +586,15 @@ fn hardlink_wheel_files(
                         // Removing and recreating would lead to race conditions.
                         let tempdir = tempdir_in(&site_packages)?;
                         let tempfile = tempdir.path().join(entry.file_name());
-                        if fs::hard_link(path, &tempfile).is_ok() {
-                            fs_err::rename(&tempfile, &out_path)?;
-                        } else {
+                        if let Err(err) = fs::hard_link(path, &tempfile) {
+                            LinkMode::Hardlink.mark_unsupported(site_packages.as_ref(), &err);
                             fs::copy(path, &out_path)?;
                             attempt = Attempt::UseCopyFallback;
+                        } else {
+                            fs_err::rename(&tempfile, &out_path)?;
                         }
                     } else {
+                        LinkMode::Hardlink.mark_unsupported(site_packages.as_ref(), &err);
                         fs::copy(path, &out_path)?;
                         attempt = Attempt::UseCopyFallback;
                     }
@@ -500,3 +628,118 @@ fn hardlink_wheel_files(
 
     Ok(count)
 }
+
+/// Extract a wheel by symbolically linking all of its files into site packages.
+fn symlink_wheel_files(
+    site_packages: impl AsRef<Path>,
+    wheel: impl AsRef<Path>,
+) -> Result<usize, Error> {
+    let mut attempt = Attempt::default();
+    let mut count = 0usize;
+
+    // Walk over the directory.
+    for entry in walkdir::WalkDir::new(&wheel) {
+        let entry = entry?;
+        let path = entry.path();
+
+        let relative = path.strip_prefix(&wheel).unwrap();
+        let out_path = site_packages.as_ref().join(relative);
+
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&out_path)?;
+            continue;
+        }
+
+        // The `RECORD` file is modified during installation, so we copy it instead of linking.
+        if path.ends_with("RECORD") {
+            fs::copy(path, &out_path)?;
+            count += 1;
+            continue;
+        }
+
+        // Fallback to copying if symlinks aren't supported for this installation.
+        match attempt {
+            Attempt::Initial | Attempt::Subsequent => {
+                if let Err(err) = symlink_replace(path, &out_path, site_packages.as_ref()) {
+                    if attempt == Attempt::Subsequent {
+                        return Err(err.into());
+                    }
+                    LinkMode::Symlink.mark_unsupported(site_packages.as_ref(), &err);
+                    fs::copy(path, &out_path)?;
+                    attempt = Attempt::UseCopyFallback;
+                } else {
+                    attempt = Attempt::Subsequent;
+                }
+            }
+            Attempt::UseCopyFallback => {
+                fs::copy(path, &out_path)?;
+            }
+        }
+
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+/// Create a symlink at `to` pointing to `from`, replacing any existing file.
+fn symlink_replace(from: &Path, to: &Path, site_packages: &Path) -> std::io::Result<()> {
+    match symlink_file(from, to) {
+        Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => {
+            debug!("File already exists, overwriting: {}", to.display());
+            // Removing and recreating would lead to race conditions.
+            let tempdir = tempdir_in(site_packages)?;
+            let tempfile = tempdir.path().join(from.file_name().unwrap());
+            symlink_file(from, &tempfile)?;
+            fs_err::rename(&tempfile, to)
+        }
+        result => result,
+    }
+}
+
+#[cfg(unix)]
+fn symlink_file(from: &Path, to: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(from, to)
+}
+
+#[cfg(windows)]
+fn symlink_file(from: &Path, to: &Path) -> std::io::Result<()> {
+    std::os::windows::fs::symlink_file(from, to)
+}
+
+#[cfg(test)]
+mod tests {
+    use fs_err as fs;
+
+    use super::LinkMode;
+
+    /// Distributions that share a namespace package (and ship `.pth` files) are merged into the
+    /// same `site-packages` directory.
+    #[test]
+    fn test_link_namespace_packages() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let site_packages = temp_dir.path().join("site-packages");
+        fs::create_dir_all(&site_packages).unwrap();
+
+        for name in ["a", "b"] {
+            let wheel = temp_dir.path().join(format!("wheel-{name}"));
+            fs::create_dir_all(wheel.join("zope").join(name)).unwrap();
+            fs::write(wheel.join("zope").join("__init__.py"), "").unwrap();
+            fs::write(wheel.join("zope").join(name).join("__init__.py"), "").unwrap();
+            fs::write(wheel.join(format!("zope.{name}-nspkg.pth")), "import zope").unwrap();
+            LinkMode::Copy
+                .link_wheel_files(&site_packages, &wheel)
+                .unwrap();
+        }
+
+        for path in [
+            "zope/__init__.py",
+            "zope/a/__init__.py",
+            "zope/b/__init__.py",
+            "zope.a-nspkg.pth",
+            "zope.b-nspkg.pth",
+        ] {
+            assert!(site_packages.join(path).is_file(), "{path}");
+        }
+    }
+}
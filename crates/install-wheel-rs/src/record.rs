@@ -1,5 +1,11 @@
+use std::path::Path;
+
+use fs_err::File;
 use serde::{Deserialize, Serialize};
 
+use crate::wheel::{copy_and_hash, read_record_file};
+use crate::Error;
+
 /// Line in a RECORD file
 /// <https://www.python.org/dev/peps/pep-0376/#record>
 ///
@@ -11,6 +17,84 @@ use serde::{Deserialize, Serialize};
 pub(crate) struct RecordEntry {
     pub(crate) path: String,
     pub(crate) hash: Option<String>,
-    #[allow(dead_code)]
     pub(crate) size: Option<u64>,
 }
+
+/// Read the paths of the files installed for a distribution, as listed in the `RECORD` of its
+/// `.dist-info` directory.
+///
+/// The paths are relative to the directory containing the `.dist-info` directory (e.g.,
+/// `site-packages`). Returns `None` if the distribution doesn't have a `RECORD`.
+pub fn read_installed_files(dist_info: &Path) -> Result<Option<Vec<String>>, Error> {
+    let mut file = match File::open(dist_info.join("RECORD")) {
+        Ok(file) => file,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+    let record = read_record_file(&mut file)?;
+    Ok(Some(record.into_iter().map(|entry| entry.path).collect()))
+}
+
+/// The state of a file listed in the `RECORD` of an installed distribution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstalledFileStatus {
+    /// The file matches the hash (and size) recorded at install time, or can't be verified (e.g.,
+    /// the `RECORD` itself, which is listed without a hash).
+    Unchanged,
+    /// The file exists, but its contents differ from those recorded at install time.
+    Modified,
+    /// The file no longer exists.
+    Missing,
+}
+
+/// A file listed in the `RECORD` of an installed distribution, along with its current state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstalledFile {
+    /// The path to the file, relative to the directory containing the `.dist-info` directory.
+    pub path: String,
+    /// The state of the file, as compared to the `RECORD`.
+    pub status: InstalledFileStatus,
+}
+
+/// Re-hash each file listed in the `RECORD` of an installed distribution, to detect files that
+/// were modified or removed after installation.
+///
+/// Returns `None` if the distribution doesn't have a `RECORD`.
+pub fn verify_installed_files(dist_info: &Path) -> Result<Option<Vec<InstalledFile>>, Error> {
+    let mut file = match File::open(dist_info.join("RECORD")) {
+        Ok(file) => file,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+    let record = read_record_file(&mut file)?;
+    let root = dist_info.parent().unwrap_or(dist_info);
+
+    let mut files = Vec::with_capacity(record.len());
+    for entry in record {
+        let path = root.join(&entry.path);
+        let status = if !path.is_file() {
+            InstalledFileStatus::Missing
+        } else if let Some(expected) = entry
+            .hash
+            .as_deref()
+            .and_then(|hash| hash.strip_prefix("sha256="))
+        {
+            let mut reader = File::open(&path)?;
+            let (size, actual) = copy_and_hash(&mut reader, &mut std::io::sink())?;
+            let size_matches = entry.size.map_or(true, |expected| expected == size);
+            if size_matches && actual.strip_prefix("sha256=") == Some(expected) {
+                InstalledFileStatus::Unchanged
+            } else {
+                InstalledFileStatus::Modified
+            }
+        } else {
+            // Files without a (supported) hash can only be checked for existence.
+            InstalledFileStatus::Unchanged
+        };
+        files.push(InstalledFile {
+            path: entry.path,
+            status,
+        });
+    }
+    Ok(Some(files))
+}
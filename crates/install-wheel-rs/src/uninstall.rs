@@ -1,4 +1,4 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashSet};
 use std::path::{Component, Path, PathBuf};
 
 use fs_err as fs;
@@ -32,9 +32,19 @@ pub fn uninstall_wheel(dist_info: &Path) -> Result<Uninstall, Error> {
     let mut dir_count = 0usize;
 
     // Uninstall the files, keeping track of any directories that are left empty.
+    //
+    // Namespace packages that predate PEP 420 (e.g., `zope` or `sphinxcontrib`) are spread across
+    // multiple distributions, each of which ships the same `__init__.py`. Removing it on behalf of
+    // one distribution would break the others, so those files are deferred until all other files
+    // have been removed.
     let mut visited = BTreeSet::new();
+    let mut deferred = Vec::new();
     for entry in &record {
         let path = site_packages.join(&entry.path);
+        if path.file_name().is_some_and(|name| name == "__init__.py") {
+            deferred.push(path);
+            continue;
+        }
         match fs::remove_file(&path) {
             Ok(()) => {
                 debug!("Removed file: {}", path.display());
@@ -55,6 +65,35 @@ pub fn uninstall_wheel(dist_info: &Path) -> Result<Uninstall, Error> {
         }
     }
 
+    // Remove any deferred `__init__.py` files, unless the package is shared with (and the file is
+    // recorded by) another installed distribution.
+    let mut shared = None;
+    for path in deferred {
+        let parent = path.parent().unwrap_or(site_packages);
+        if has_other_entries(parent)? {
+            let shared = match shared.as_mut() {
+                Some(shared) => shared,
+                None => shared.insert(recorded_by_others(site_packages, dist_info)?),
+            };
+            if shared.contains(&normalize_path(&path)) {
+                debug!(
+                    "Retaining shared namespace package file: {}",
+                    path.display()
+                );
+                continue;
+            }
+        }
+        match fs::remove_file(&path) {
+            Ok(()) => {
+                debug!("Removed file: {}", path.display());
+                file_count += 1;
+                visited.insert(normalize_path(parent));
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err.into()),
+        }
+    }
+
     // If any directories were left empty, remove them. Iterate in reverse order such that we visit
     // the deepest directories first.
     for path in visited.iter().rev() {
@@ -118,6 +157,48 @@ pub fn uninstall_wheel(dist_info: &Path) -> Result<Uninstall, Error> {
     })
 }
 
+/// Returns `true` if the directory contains any entries other than `__init__.py` and
+/// `__pycache__`.
+fn has_other_entries(path: &Path) -> Result<bool, Error> {
+    let read_dir = match fs::read_dir(path) {
+        Ok(read_dir) => read_dir,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+        Err(err) => return Err(err.into()),
+    };
+    for entry in read_dir {
+        let name = entry?.file_name();
+        if name != "__init__.py" && name != "__pycache__" {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Collect the (normalized) paths recorded by every distribution in `site-packages` other than the
+/// one being uninstalled.
+fn recorded_by_others(site_packages: &Path, dist_info: &Path) -> Result<HashSet<PathBuf>, Error> {
+    let mut paths = HashSet::new();
+    for entry in fs::read_dir(site_packages)? {
+        let path = entry?.path();
+        if path == dist_info
+            || !path
+                .extension()
+                .is_some_and(|extension| extension == "dist-info")
+        {
+            continue;
+        }
+        let mut record_file = match fs::File::open(path.join("RECORD")) {
+            Ok(record_file) => record_file,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(err) => return Err(err.into()),
+        };
+        for entry in read_record_file(&mut record_file)? {
+            paths.insert(normalize_path(&site_packages.join(&entry.path)));
+        }
+    }
+    Ok(paths)
+}
+
 #[derive(Debug, Default)]
 pub struct Uninstall {
     /// The number of files that were removed during the uninstallation.
@@ -155,3 +236,71 @@ fn normalize_path(path: &Path) -> PathBuf {
     }
     ret
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use fs_err as fs;
+
+    use super::uninstall_wheel;
+
+    /// Create a distribution in `site-packages` with the given files, listed in its `RECORD`.
+    fn install(site_packages: &Path, name: &str, files: &[&str]) {
+        let dist_info = format!("{name}-1.0.dist-info");
+        let mut record = String::new();
+        for file in files {
+            let path = site_packages.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, "").unwrap();
+            record.push_str(&format!("{file},,\n"));
+        }
+        fs::create_dir_all(site_packages.join(&dist_info)).unwrap();
+        record.push_str(&format!("{dist_info}/RECORD,,\n"));
+        fs::write(site_packages.join(&dist_info).join("RECORD"), record).unwrap();
+    }
+
+    /// A namespace package `__init__.py` shared across distributions is retained until the last
+    /// of those distributions is uninstalled.
+    #[test]
+    fn test_uninstall_shared_namespace_package() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let site_packages = temp_dir.path();
+        install(
+            site_packages,
+            "zope.a",
+            &["zope/__init__.py", "zope/a/__init__.py", "zope.a-nspkg.pth"],
+        );
+        install(
+            site_packages,
+            "zope.b",
+            &["zope/__init__.py", "zope/b/__init__.py", "zope.b-nspkg.pth"],
+        );
+
+        uninstall_wheel(&site_packages.join("zope.a-1.0.dist-info")).unwrap();
+        assert!(site_packages.join("zope/__init__.py").is_file());
+        assert!(site_packages.join("zope/b/__init__.py").is_file());
+        assert!(site_packages.join("zope.b-nspkg.pth").is_file());
+        assert!(!site_packages.join("zope/a").exists());
+        assert!(!site_packages.join("zope.a-nspkg.pth").exists());
+
+        uninstall_wheel(&site_packages.join("zope.b-1.0.dist-info")).unwrap();
+        assert!(!site_packages.join("zope").exists());
+        assert!(!site_packages.join("zope.b-nspkg.pth").exists());
+    }
+
+    /// A package `__init__.py` that isn't recorded by any other distribution is removed, even if
+    /// the package directory contains unrecorded files.
+    #[test]
+    fn test_uninstall_unshared_package() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let site_packages = temp_dir.path();
+        install(site_packages, "foo", &["foo/__init__.py", "foo/bar.py"]);
+        fs::write(site_packages.join("foo/generated.txt"), "").unwrap();
+
+        uninstall_wheel(&site_packages.join("foo-1.0.dist-info")).unwrap();
+        assert!(!site_packages.join("foo/__init__.py").exists());
+        assert!(!site_packages.join("foo/bar.py").exists());
+        assert!(site_packages.join("foo/generated.txt").is_file());
+    }
+}
@@ -5,7 +5,7 @@ use std::{env, io, iter};
 
 use data_encoding::BASE64URL_NOPAD;
 use fs_err as fs;
-use fs_err::{DirEntry, File};
+use fs_err::File;
 use mailparse::MailHeaderMap;
 use rustc_hash::FxHashMap;
 use sha2::{Digest, Sha256};
@@ -19,7 +19,7 @@ use uv_fs::Simplified;
 
 use crate::record::RecordEntry;
 use crate::script::Script;
-use crate::{Error, Layout};
+use crate::{Error, Layout, ShebangMode};
 
 const LAUNCHER_MAGIC_NUMBER: [u8; 4] = [b'U', b'V', b'U', b'V'];
 
@@ -90,7 +90,10 @@ pub(crate) fn read_scripts_from_section(
 /// <https://github.com/richo/hashing-copy/blob/d8dd2fdb63c6faf198de0c9e5713d6249cbb5323/src/lib.rs#L10-L52>
 /// which in turn got it from std
 /// <https://doc.rust-lang.org/1.58.0/src/std/io/copy.rs.html#128-156>
-fn copy_and_hash(reader: &mut impl Read, writer: &mut impl Write) -> io::Result<(u64, String)> {
+pub(crate) fn copy_and_hash(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+) -> io::Result<(u64, String)> {
     // TODO: Do we need to support anything besides sha256?
     let mut hasher = Sha256::new();
     // Same buf size as std. Note that this number is important for performance
@@ -143,6 +146,79 @@ fn format_shebang(executable: impl AsRef<Path>, os_name: &str) -> String {
     format!("#!{executable}")
 }
 
+/// Format a shebang that locates the Python executable relative to the scripts directory, such
+/// that the scripts remain valid if the environment is moved.
+///
+/// Like the `/bin/sh` fallback in [`format_shebang`], the script re-executes itself with the
+/// interpreter found next to the (resolved) script path.
+fn format_relative_shebang(executable: &Path, scripts: &Path, os_name: &str) -> String {
+    if os_name == "posix" {
+        if let Some(relative) = pathdiff::diff_paths(executable, scripts) {
+            let relative = format!("'{}'", relative.to_string_lossy().replace('\'', r#"'"'"'"#));
+            return format!(
+                "#!/bin/sh\n'''exec' \"$(dirname -- \"$(realpath -- \"$0\")\")\"/{relative} \"$0\" \"$@\"\n' '''"
+            );
+        }
+    }
+    format_shebang(executable, os_name)
+}
+
+/// Format a shebang that locates the Python executable on the `PATH` at runtime.
+fn format_env_shebang(executable: &Path, os_name: &str) -> String {
+    if os_name == "posix" {
+        if let Some(name) = executable.file_name().and_then(|name| name.to_str()) {
+            if !name.contains(' ') {
+                return format!("#!/usr/bin/env {name}");
+            }
+        }
+    }
+    format_shebang(executable, os_name)
+}
+
+/// Format the shebang for scripts installed into the given [`Layout`].
+fn layout_shebang(layout: &Layout, shebang: ShebangMode) -> String {
+    match shebang {
+        ShebangMode::Absolute => format_shebang(&layout.sys_executable, &layout.os_name),
+        ShebangMode::Relative => format_relative_shebang(
+            &layout.sys_executable,
+            &layout.scheme.scripts,
+            &layout.os_name,
+        ),
+        ShebangMode::Env => format_env_shebang(&layout.sys_executable, &layout.os_name),
+    }
+}
+
+/// Return the Python executable that a Windows launcher should start.
+///
+/// Like `distlib`, GUI scripts are launched with `pythonw.exe` (if it exists alongside the
+/// interpreter), such that no console window is opened.
+///
+/// <https://github.com/pypa/pip/blob/fd0ea6bc5e8cb95e518c23d901c26ca14db17f89/src/pip/_vendor/distlib/scripts.py#L376-L383>
+fn windows_launcher_python(python_executable: &Path, is_gui: bool) -> PathBuf {
+    if is_gui {
+        let pythonw = python_executable
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| {
+                let (stem, extension) = name.rsplit_once('.').unwrap_or((name, ""));
+                let suffix = stem.strip_prefix("python")?;
+                if suffix.starts_with('w') {
+                    return None;
+                }
+                let pythonw = if extension.is_empty() {
+                    format!("pythonw{suffix}")
+                } else {
+                    format!("pythonw{suffix}.{extension}")
+                };
+                Some(python_executable.with_file_name(pythonw))
+            });
+        if let Some(pythonw) = pythonw.filter(|pythonw| pythonw.is_file()) {
+            return pythonw;
+        }
+    }
+    python_executable.to_path_buf()
+}
+
 /// A Windows script is a minimal .exe launcher binary with the python entrypoint script appended as
 /// stored zip file. The launcher will look for `python[w].exe` adjacent to it in the same directory
 /// to start the embedded script.
@@ -224,6 +300,7 @@ pub(crate) fn write_script_entrypoints(
     entrypoints: &[Script],
     record: &mut Vec<RecordEntry>,
     is_gui: bool,
+    shebang: ShebangMode,
 ) -> Result<(), Error> {
     for entrypoint in entrypoints {
         let entrypoint_absolute = if cfg!(windows) {
@@ -253,17 +330,19 @@ pub(crate) fn write_script_entrypoints(
             })?;
 
         // Generate the launcher script.
-        let launcher_python_script = get_script_launcher(
-            entrypoint,
-            &format_shebang(&layout.sys_executable, &layout.os_name),
-        );
+        let launcher_python_script =
+            get_script_launcher(entrypoint, &layout_shebang(layout, shebang));
 
         // If necessary, wrap the launcher script in a Windows launcher binary.
         if cfg!(windows) {
             write_file_recorded(
                 site_packages,
                 &entrypoint_relative,
-                &windows_script_launcher(&launcher_python_script, is_gui, &layout.sys_executable)?,
+                &windows_script_launcher(
+                    &launcher_python_script,
+                    is_gui,
+                    windows_launcher_python(&layout.sys_executable, is_gui),
+                )?,
                 record,
             )?;
         } else {
@@ -427,20 +506,19 @@ pub(crate) fn move_folder_recorded(
 /// Installs a single script (not an entrypoint)
 ///
 /// Has to deal with both binaries files (just move) and scripts (rewrite the shebang if applicable)
+///
+/// The `relative` path is the path of the script within the `scripts` directory of the wheel
+/// (e.g., `tool` or `tools/tool`), which is preserved within the scripts directory of the
+/// environment.
 fn install_script(
     layout: &Layout,
     site_packages: &Path,
     record: &mut [RecordEntry],
-    file: &DirEntry,
+    path: &Path,
+    relative: &Path,
+    shebang: ShebangMode,
 ) -> Result<(), Error> {
-    if !file.file_type()?.is_file() {
-        return Err(Error::InvalidWheel(format!(
-            "Wheel contains entry in scripts directory that is not a file: {}",
-            file.path().display()
-        )));
-    }
-
-    let script_absolute = layout.scheme.scripts.join(file.file_name());
+    let script_absolute = layout.scheme.scripts.join(relative);
     let script_relative =
         pathdiff::diff_paths(&script_absolute, site_packages).ok_or_else(|| {
             Error::Io(io::Error::new(
@@ -451,9 +529,11 @@ fn install_script(
                 ),
             ))
         })?;
+    if let Some(parent) = script_absolute.parent() {
+        fs::create_dir_all(parent)?;
+    }
 
-    let path = file.path();
-    let mut script = File::open(&path)?;
+    let mut script = BufReader::new(File::open(path)?);
 
     // https://sphinx-locales.github.io/peps/pep-0427/#recommended-installer-features
     // > In wheel, scripts are packaged in {distribution}-{version}.data/scripts/.
@@ -464,21 +544,28 @@ fn install_script(
     // > The b'#!pythonw' convention is allowed. b'#!pythonw' indicates a GUI script
     // > instead of a console script.
     let placeholder_python = b"#!python";
-    // scripts might be binaries, so we read an exact number of bytes instead of the first line as string
-    let mut start = vec![0; placeholder_python.len()];
-    script.read_exact(&mut start)?;
+    // scripts might be binaries, so we read an exact number of bytes instead of the first line as
+    // string (and scripts might be shorter than the placeholder, so we can't use `read_exact`)
+    let mut start = Vec::with_capacity(placeholder_python.len());
+    script
+        .by_ref()
+        .take(placeholder_python.len() as u64)
+        .read_to_end(&mut start)?;
     let size_and_encoded_hash = if start == placeholder_python {
-        let start = format_shebang(&layout.sys_executable, &layout.os_name)
-            .as_bytes()
-            .to_vec();
+        // Like pip, replace the entire first line (e.g., `#!pythonw` or `#!python3`), since the
+        // rewritten shebang may span multiple lines. The shebang always ends with `\n`, as a
+        // trailing `\r` would be treated as part of the interpreter path.
+        let mut rest_of_line = Vec::new();
+        script.read_until(b'\n', &mut rest_of_line)?;
+        let start = format!("{}\n", layout_shebang(layout, shebang)).into_bytes();
         let mut target = File::create(&script_absolute)?;
         let size_and_encoded_hash = copy_and_hash(&mut start.chain(script), &mut target)?;
-        fs::remove_file(&path)?;
+        fs::remove_file(path)?;
         Some(size_and_encoded_hash)
     } else {
         // reading and writing is slow especially for large binaries, so we move them instead
         drop(script);
-        fs::rename(&path, &script_absolute)?;
+        fs::rename(path, &script_absolute)?;
         None
     };
     #[cfg(unix)]
@@ -525,6 +612,7 @@ pub(crate) fn install_data(
     console_scripts: &[Script],
     gui_scripts: &[Script],
     record: &mut [RecordEntry],
+    shebang: ShebangMode,
 ) -> Result<(), Error> {
     for entry in fs::read_dir(data_dir)? {
         let entry = entry?;
@@ -536,26 +624,49 @@ pub(crate) fn install_data(
                 move_folder_recorded(&path, &layout.scheme.data, site_packages, record)?;
             }
             Some("scripts") => {
-                for file in fs::read_dir(path)? {
+                // Scripts may be nested in subdirectories, which are preserved.
+                for file in WalkDir::new(&path).min_depth(1) {
                     let file = file?;
+                    if file.file_type().is_dir() {
+                        continue;
+                    }
+                    if !file.file_type().is_file() {
+                        return Err(Error::InvalidWheel(format!(
+                            "Wheel contains entry in scripts directory that is not a file: {}",
+                            file.path().display()
+                        )));
+                    }
 
                     // Couldn't find any docs for this, took it directly from
                     // https://github.com/pypa/pip/blob/b5457dfee47dd9e9f6ec45159d9d410ba44e5ea1/src/pip/_internal/operations/install/wheel.py#L565-L583
-                    let name = file.file_name().to_string_lossy().to_string();
-                    let match_name = name
-                        .strip_suffix(".exe")
-                        .or_else(|| name.strip_suffix("-script.py"))
-                        .or_else(|| name.strip_suffix(".pya"))
-                        .unwrap_or(&name);
-                    if console_scripts
-                        .iter()
-                        .chain(gui_scripts)
-                        .any(|script| script.name == match_name)
-                    {
-                        continue;
+                    if file.depth() == 1 {
+                        let name = file.file_name().to_string_lossy().to_string();
+                        let match_name = name
+                            .strip_suffix(".exe")
+                            .or_else(|| name.strip_suffix("-script.py"))
+                            .or_else(|| name.strip_suffix(".pya"))
+                            .unwrap_or(&name);
+                        if console_scripts
+                            .iter()
+                            .chain(gui_scripts)
+                            .any(|script| script.name == match_name)
+                        {
+                            continue;
+                        }
                     }
 
-                    install_script(layout, site_packages, record, &file)?;
+                    let relative = file
+                        .path()
+                        .strip_prefix(&path)
+                        .expect("Prefix must no change");
+                    install_script(
+                        layout,
+                        site_packages,
+                        record,
+                        file.path(),
+                        relative,
+                        shebang,
+                    )?;
                 }
             }
             Some("headers") => {
@@ -734,9 +845,16 @@ mod test {
     use crate::Error;
     use indoc::{formatdoc, indoc};
 
-    use crate::wheel::format_shebang;
+    use crate::wheel::{
+        format_env_shebang, format_relative_shebang, format_shebang, windows_launcher_python,
+    };
 
-    use super::{parse_key_value_file, parse_wheel_file, read_record_file, relative_to, Script};
+    use super::{
+        install_script, parse_key_value_file, parse_wheel_file, read_record_file, relative_to,
+        Script,
+    };
+    use crate::record::RecordEntry;
+    use crate::{Layout, ShebangMode};
 
     #[test]
     fn test_parse_key_value_file() {
@@ -897,6 +1015,125 @@ mod test {
         assert_eq!(format_shebang(executable, os_name), "#!/bin/sh\n'''exec' '/usr/bin/path/to/a/very/long/executable/executable/executable/executable/executable/executable/executable/executable/name/python3' \"$0\" \"$@\"\n' '''");
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn test_install_script() -> Result<(), Error> {
+        let temp_dir = tempfile::tempdir()?;
+        let site_packages = temp_dir.path().join("site-packages");
+        let scripts = temp_dir.path().join("bin");
+        let layout = Layout {
+            sys_executable: Path::new("/usr/bin/python3").to_path_buf(),
+            python_version: (3, 12),
+            os_name: "posix".to_string(),
+            scheme: pypi_types::Scheme {
+                purelib: site_packages.clone(),
+                platlib: site_packages.clone(),
+                scripts: scripts.clone(),
+                data: temp_dir.path().to_path_buf(),
+                include: temp_dir.path().join("include"),
+            },
+        };
+
+        let data_scripts = site_packages.join("pkg-1.0.data/scripts");
+        fs_err::create_dir_all(data_scripts.join("tools"))?;
+        fs_err::write(data_scripts.join("gui"), "#!pythonw -u\r\nprint()\r\n")?;
+        fs_err::write(data_scripts.join("tools/short"), "#!/x")?;
+        let mut record = vec![
+            RecordEntry {
+                path: "pkg-1.0.data/scripts/gui".to_string(),
+                hash: None,
+                size: None,
+            },
+            RecordEntry {
+                path: "pkg-1.0.data/scripts/tools/short".to_string(),
+                hash: None,
+                size: None,
+            },
+        ];
+
+        // The entire placeholder line is replaced, terminating the shebang with `\n`.
+        install_script(
+            &layout,
+            &site_packages,
+            &mut record,
+            &data_scripts.join("gui"),
+            Path::new("gui"),
+            ShebangMode::Absolute,
+        )?;
+        assert_eq!(
+            fs_err::read_to_string(scripts.join("gui"))?,
+            "#!/usr/bin/python3\nprint()\r\n"
+        );
+        assert_eq!(record[0].path, "../bin/gui");
+        assert!(record[0].hash.is_some());
+
+        // Scripts shorter than the placeholder, and in subdirectories, are moved as-is.
+        install_script(
+            &layout,
+            &site_packages,
+            &mut record,
+            &data_scripts.join("tools/short"),
+            Path::new("tools/short"),
+            ShebangMode::Absolute,
+        )?;
+        assert_eq!(fs_err::read_to_string(scripts.join("tools/short"))?, "#!/x");
+        assert_eq!(record[1].path, "../bin/tools/short");
+        assert!(record[1].hash.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_windows_launcher_python() -> Result<(), Error> {
+        let temp_dir = tempfile::tempdir()?;
+        let python = temp_dir.path().join("python.exe");
+        fs_err::write(&python, "")?;
+
+        // Console scripts always use the interpreter itself.
+        assert_eq!(windows_launcher_python(&python, false), python);
+
+        // GUI scripts fall back to the interpreter if `pythonw.exe` doesn't exist...
+        assert_eq!(windows_launcher_python(&python, true), python);
+
+        // ...but otherwise use `pythonw.exe`.
+        let pythonw = temp_dir.path().join("pythonw.exe");
+        fs_err::write(&pythonw, "")?;
+        assert_eq!(windows_launcher_python(&python, true), pythonw);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_relocatable_shebang() {
+        // A relative shebang locates the interpreter next to the script.
+        let executable = Path::new("/home/ferris/.venv/bin/python");
+        let scripts = Path::new("/home/ferris/.venv/bin");
+        assert_eq!(
+            format_relative_shebang(executable, scripts, "posix"),
+            "#!/bin/sh\n'''exec' \"$(dirname -- \"$(realpath -- \"$0\")\")\"/'python' \"$0\" \"$@\"\n' '''"
+        );
+
+        // ...or elsewhere, relative to the scripts directory.
+        let scripts = Path::new("/home/ferris/.venv/lib/bin");
+        assert_eq!(
+            format_relative_shebang(executable, scripts, "posix"),
+            "#!/bin/sh\n'''exec' \"$(dirname -- \"$(realpath -- \"$0\")\")\"/'../../bin/python' \"$0\" \"$@\"\n' '''"
+        );
+
+        // An `env` shebang locates the interpreter on the `PATH`.
+        let executable = Path::new("/usr/bin/python3.12");
+        assert_eq!(
+            format_env_shebang(executable, "posix"),
+            "#!/usr/bin/env python3.12"
+        );
+
+        // Except on Windows, where the absolute path is retained.
+        assert_eq!(
+            format_env_shebang(executable, "nt"),
+            "#!/usr/bin/python3.12"
+        );
+    }
+
     #[test]
     fn test_empty_value() -> Result<(), Error> {
         let wheel = indoc! {r"
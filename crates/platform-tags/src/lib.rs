@@ -1,5 +1,8 @@
 pub use platform::{Arch, Os, Platform, PlatformError};
-pub use tags::{IncompatibleTag, TagCompatibility, TagPriority, Tags, TagsError};
+pub use tags::{
+    compatible_tags, expand_platform_tags, IncompatibleTag, TagCompatibility, TagPriority, Tags,
+    TagsError,
+};
 
 mod platform;
 mod tags;
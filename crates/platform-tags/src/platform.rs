@@ -34,6 +34,64 @@ impl Platform {
     pub fn arch(&self) -> Arch {
         self.arch
     }
+
+    /// Parse a platform tag (e.g., `manylinux_2_28_aarch64`, `macosx_11_0_arm64`, or
+    /// `win_amd64`) into the [`Platform`] it describes.
+    ///
+    /// Returns `None` for tags that don't map to a single operating system and architecture.
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        match tag {
+            "win32" => return Some(Self::new(Os::Windows, Arch::X86)),
+            "win_amd64" => return Some(Self::new(Os::Windows, Arch::X86_64)),
+            "win_arm64" => return Some(Self::new(Os::Windows, Arch::Aarch64)),
+            _ => {}
+        }
+
+        if let Some(rest) = tag.strip_prefix("manylinux") {
+            // Legacy aliases, as defined in PEP 600.
+            let (major, minor, arch) = if let Some(arch) = rest.strip_prefix("2014_") {
+                (2, 17, arch)
+            } else if let Some(arch) = rest.strip_prefix("2010_") {
+                (2, 12, arch)
+            } else if let Some(arch) = rest.strip_prefix("1_") {
+                (2, 5, arch)
+            } else {
+                parse_version_arch(rest.strip_prefix('_')?)?
+            };
+            return Some(Self::new(
+                Os::Manylinux { major, minor },
+                Arch::from_tag(arch)?,
+            ));
+        }
+
+        if let Some(rest) = tag.strip_prefix("musllinux_") {
+            let (major, minor, arch) = parse_version_arch(rest)?;
+            return Some(Self::new(
+                Os::Musllinux { major, minor },
+                Arch::from_tag(arch)?,
+            ));
+        }
+
+        if let Some(rest) = tag.strip_prefix("macosx_") {
+            let (major, minor, arch) = parse_version_arch(rest)?;
+            let arch = match arch {
+                "arm64" => Arch::Aarch64,
+                "x86_64" => Arch::X86_64,
+                // Multi-architecture tags (e.g., `universal2`) don't describe a single platform.
+                _ => return None,
+            };
+            return Some(Self::new(Os::Macos { major, minor }, arch));
+        }
+
+        None
+    }
+}
+
+/// Split `{major}_{minor}_{arch}` into its components.
+fn parse_version_arch(tag: &str) -> Option<(u16, u16, &str)> {
+    let (major, rest) = tag.split_once('_')?;
+    let (minor, arch) = rest.split_once('_')?;
+    Some((major.parse().ok()?, minor.parse().ok()?, arch))
 }
 
 /// All supported operating systems.
@@ -102,6 +160,21 @@ impl fmt::Display for Arch {
 }
 
 impl Arch {
+    /// Parse the architecture component of a platform tag (e.g., `aarch64` or `x86_64`).
+    pub fn from_tag(arch: &str) -> Option<Self> {
+        match arch {
+            "aarch64" | "arm64" => Some(Self::Aarch64),
+            "armv6l" => Some(Self::Armv6L),
+            "armv7l" => Some(Self::Armv7L),
+            "ppc64le" => Some(Self::Powerpc64Le),
+            "ppc64" => Some(Self::Powerpc64),
+            "i686" | "i386" => Some(Self::X86),
+            "x86_64" | "amd64" => Some(Self::X86_64),
+            "s390x" => Some(Self::S390X),
+            _ => None,
+        }
+    }
+
     /// Returns the oldest possible `manylinux` tag for this architecture, if it supports
     /// `manylinux`.
     pub fn get_minimum_manylinux_minor(&self) -> Option<u16> {
@@ -93,25 +93,52 @@ impl Tags {
         python_version: (u8, u8),
         implementation_name: &str,
         implementation_version: (u8, u8),
+    ) -> Result<Self, TagsError> {
+        Self::from_platform_tags(
+            &compatible_tags(platform)?,
+            python_version,
+            implementation_name,
+            implementation_version,
+            None,
+        )
+    }
+
+    /// Returns the compatible tags for the given Python implementation (e.g., `cpython`), version,
+    /// and platform tags (e.g., `manylinux_2_17_aarch64`), in lieu of those of a [`Platform`], as
+    /// when targeting an environment other than the current one.
+    ///
+    /// If ABI tags are provided (e.g., `cp312`), they're used in place of the implementation's own
+    /// ABI tag.
+    pub fn from_platform_tags(
+        platform_tags: &[String],
+        python_version: (u8, u8),
+        implementation_name: &str,
+        implementation_version: (u8, u8),
+        abi_tags: Option<&[String]>,
     ) -> Result<Self, TagsError> {
         let implementation = Implementation::from_str(implementation_name)?;
-        let platform_tags = compatible_tags(platform)?;
 
         let mut tags = Vec::with_capacity(5 * platform_tags.len());
 
         // 1. This exact c api version
-        for platform_tag in &platform_tags {
-            tags.push((
-                implementation.language_tag(python_version),
-                implementation.abi_tag(python_version, implementation_version),
-                platform_tag.clone(),
-            ));
+        let abi_tags = abi_tags.map_or_else(
+            || vec![implementation.abi_tag(python_version, implementation_version)],
+            <[String]>::to_vec,
+        );
+        for abi_tag in &abi_tags {
+            for platform_tag in platform_tags {
+                tags.push((
+                    implementation.language_tag(python_version),
+                    abi_tag.clone(),
+                    platform_tag.clone(),
+                ));
+            }
         }
         // 2. abi3 and no abi (e.g. executable binary)
         if matches!(implementation, Implementation::CPython) {
             // For some reason 3.2 is the minimum python for the cp abi
             for minor in (2..=python_version.1).rev() {
-                for platform_tag in &platform_tags {
+                for platform_tag in platform_tags {
                     tags.push((
                         implementation.language_tag((python_version.0, minor)),
                         "abi3".to_string(),
@@ -120,7 +147,7 @@ impl Tags {
                 }
                 // Only include `none` tags for the current CPython version
                 if minor == python_version.1 {
-                    for platform_tag in &platform_tags {
+                    for platform_tag in platform_tags {
                         tags.push((
                             implementation.language_tag((python_version.0, minor)),
                             "none".to_string(),
@@ -132,7 +159,7 @@ impl Tags {
         }
         // 3. no abi (e.g. executable binary)
         for minor in (0..=python_version.1).rev() {
-            for platform_tag in &platform_tags {
+            for platform_tag in platform_tags {
                 tags.push((
                     format!("py{}{}", python_version.0, minor),
                     "none".to_string(),
@@ -141,7 +168,7 @@ impl Tags {
             }
             // After the matching version emit `none` tags for the major version i.e. `py3`
             if minor == python_version.1 {
-                for platform_tag in &platform_tags {
+                for platform_tag in platform_tags {
                     tags.push((
                         format!("py{}", python_version.0),
                         "none".to_string(),
@@ -176,6 +203,23 @@ impl Tags {
         Ok(Self::new(tags))
     }
 
+    /// Returns the tags as `(python_tag, abi_tag, platform_tag)` triples, from high to low
+    /// priority, such that they can be passed back to [`Tags::new`].
+    pub fn to_vec(&self) -> Vec<(String, String, String)> {
+        let mut tags = BTreeSet::new();
+        for (python_tag, abi_tags) in self.map.iter() {
+            for (abi_tag, platform_tags) in abi_tags {
+                for (platform_tag, priority) in platform_tags {
+                    tags.insert((
+                        priority,
+                        (python_tag.clone(), abi_tag.clone(), platform_tag.clone()),
+                    ));
+                }
+            }
+        }
+        tags.into_iter().rev().map(|(_, tag)| tag).collect()
+    }
+
     /// Returns true when there exists at least one tag for this platform
     /// whose individual components all appear in each of the slices given.
     ///
@@ -274,16 +318,8 @@ impl TryFrom<usize> for TagPriority {
 impl std::fmt::Display for Tags {
     /// Display tags from high to low priority
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let mut tags = BTreeSet::new();
-        for (python_tag, abi_tags) in self.map.iter() {
-            for (abi_tag, platform_tags) in abi_tags {
-                for (platform_tag, priority) in platform_tags {
-                    tags.insert((priority, format!("{python_tag}-{abi_tag}-{platform_tag}")));
-                }
-            }
-        }
-        for (_, tag) in tags.iter().rev() {
-            writeln!(f, "{tag}")?;
+        for (python_tag, abi_tag, platform_tag) in self.to_vec() {
+            writeln!(f, "{python_tag}-{abi_tag}-{platform_tag}")?;
         }
         Ok(())
     }
@@ -359,6 +395,32 @@ impl FromStr for Implementation {
     }
 }
 
+/// Expand each of the given platform tags (e.g., `manylinux_2_28_aarch64`) into the full set of
+/// platform tags it's compatible with (e.g., `manylinux_2_27_aarch64`, ..., `manylinux2014_aarch64`),
+/// in decreasing priority.
+///
+/// Tags that don't describe a recognized [`Platform`] are retained verbatim. Wheels tagged
+/// `linux_*` are built for a specific host, rather than for a portable target, so they're only
+/// included if requested explicitly.
+pub fn expand_platform_tags(platform_tags: &[String]) -> Result<Vec<String>, PlatformError> {
+    let mut expanded = Vec::with_capacity(platform_tags.len());
+    for platform_tag in platform_tags {
+        let compatible = match Platform::from_tag(platform_tag) {
+            Some(platform) => compatible_tags(&platform)?
+                .into_iter()
+                .filter(|tag| tag == platform_tag || !tag.starts_with("linux_"))
+                .collect(),
+            None => vec![platform_tag.clone()],
+        };
+        for tag in compatible {
+            if !expanded.contains(&tag) {
+                expanded.push(tag);
+            }
+        }
+    }
+    Ok(expanded)
+}
+
 /// Returns the compatible tags for the current [`Platform`] (e.g., `manylinux_2_17`,
 /// `macosx_11_0_arm64`, or `win_amd64`).
 ///
@@ -366,7 +428,7 @@ impl FromStr for Implementation {
 /// and "any".
 ///
 /// Bit of a mess, needs to be cleaned up.
-fn compatible_tags(platform: &Platform) -> Result<Vec<String>, PlatformError> {
+pub fn compatible_tags(platform: &Platform) -> Result<Vec<String>, PlatformError> {
     let os = platform.os();
     let arch = platform.arch();
 
@@ -2007,4 +2069,129 @@ mod tests {
         "###
         );
     }
+
+    /// Check the tags computed for an explicit set of platform and ABI tags.
+    #[test]
+    fn test_platform_tags_override() {
+        let tags = Tags::from_platform_tags(
+            &["manylinux2014_aarch64".to_string()],
+            (3, 12),
+            "cpython",
+            (3, 12),
+            None,
+        )
+        .unwrap();
+        let tag = |s: &str| vec![s.to_string()];
+        assert!(tags.is_compatible(&tag("cp312"), &tag("cp312"), &tag("manylinux2014_aarch64")));
+        assert!(tags.is_compatible(&tag("cp38"), &tag("abi3"), &tag("manylinux2014_aarch64")));
+        assert!(tags.is_compatible(&tag("py3"), &tag("none"), &tag("any")));
+        assert!(!tags.is_compatible(&tag("cp312"), &tag("cp312"), &tag("manylinux2014_x86_64")));
+
+        // An explicit ABI replaces that of the implementation.
+        let tags = Tags::from_platform_tags(
+            &["manylinux2014_aarch64".to_string()],
+            (3, 12),
+            "cpython",
+            (3, 12),
+            Some(&["cp312d".to_string()]),
+        )
+        .unwrap();
+        assert!(tags.is_compatible(&tag("cp312"), &tag("cp312d"), &tag("manylinux2014_aarch64")));
+        assert!(!tags.is_compatible(&tag("cp312"), &tag("cp312"), &tag("manylinux2014_aarch64")));
+    }
+
+    #[test]
+    fn test_expand_platform_tags() {
+        let tags = expand_platform_tags(&[
+            "manylinux_2_20_aarch64".to_string(),
+            "win_amd64".to_string(),
+            "custom_platform".to_string(),
+        ])
+        .unwrap();
+        assert_debug_snapshot!(
+            tags,
+            @r###"
+        [
+            "manylinux_2_20_aarch64",
+            "manylinux_2_19_aarch64",
+            "manylinux_2_18_aarch64",
+            "manylinux_2_17_aarch64",
+            "manylinux2014_aarch64",
+            "win_amd64",
+            "custom_platform",
+        ]
+        "###
+        );
+
+        // Legacy aliases are expanded to the equivalent `manylinux` tags.
+        let tags = expand_platform_tags(&["manylinux2014_x86_64".to_string()]).unwrap();
+        assert_eq!(
+            tags.first().map(String::as_str),
+            Some("manylinux_2_17_x86_64")
+        );
+        assert!(tags.contains(&"manylinux1_x86_64".to_string()));
+
+        // Host-specific `linux_*` tags are only included if requested explicitly.
+        let tags = expand_platform_tags(&["musllinux_1_2_x86_64".to_string()]).unwrap();
+        assert!(!tags.contains(&"linux_x86_64".to_string()));
+        let tags = expand_platform_tags(&[
+            "manylinux_2_17_x86_64".to_string(),
+            "linux_x86_64".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(tags.last().map(String::as_str), Some("linux_x86_64"));
+    }
+
+    #[test]
+    fn test_platform_from_tag() {
+        assert_eq!(
+            Platform::from_tag("manylinux_2_28_x86_64"),
+            Some(Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 28
+                },
+                Arch::X86_64
+            ))
+        );
+        assert_eq!(
+            Platform::from_tag("macosx_11_0_arm64"),
+            Some(Platform::new(
+                Os::Macos {
+                    major: 11,
+                    minor: 0
+                },
+                Arch::Aarch64
+            ))
+        );
+        assert_eq!(
+            Platform::from_tag("win32"),
+            Some(Platform::new(Os::Windows, Arch::X86))
+        );
+        assert_eq!(Platform::from_tag("macosx_11_0_universal2"), None);
+        assert_eq!(Platform::from_tag("manylinux_2_x86_64"), None);
+    }
+
+    #[test]
+    fn test_tags_round_trip() {
+        let tags = Tags::from_platform_tags(
+            &["manylinux2014_aarch64".to_string()],
+            (3, 12),
+            "cpython",
+            (3, 12),
+            None,
+        )
+        .unwrap();
+        let triples = tags.to_vec();
+        assert_eq!(
+            triples.first(),
+            Some(&(
+                "cp312".to_string(),
+                "cp312".to_string(),
+                "manylinux2014_aarch64".to_string()
+            ))
+        );
+        assert_eq!(Tags::new(triples.clone()).to_vec(), triples);
+        assert_eq!(Tags::new(triples).to_string(), tags.to_string());
+    }
 }
@@ -37,6 +37,10 @@ fn sorted_simple_json_files<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<File>
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct File {
+    /// The availability of the core metadata as a separate file, per PEP 658, under the name
+    /// introduced by [PEP 714](https://peps.python.org/pep-0714/). Takes precedence over
+    /// `dist-info-metadata`.
+    pub core_metadata: Option<DistInfoMetadata>,
     // Non-PEP 691-compliant alias used by PyPI.
     #[serde(alias = "data-dist-info-metadata")]
     pub dist_info_metadata: Option<DistInfoMetadata>,
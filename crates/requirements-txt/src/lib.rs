@@ -301,6 +301,8 @@ pub struct RequirementEntry {
     pub hashes: Vec<String>,
     /// Editable installation, see e.g. <https://stackoverflow.com/q/35064426/3549270>
     pub editable: bool,
+    /// The (1-indexed) line in the requirements file on which the requirement was declared.
+    pub line: usize,
 }
 
 impl Display for RequirementEntry {
@@ -316,13 +318,27 @@ impl Display for RequirementEntry {
     }
 }
 
+/// A constraint included with `-c`, along with the constraints file that declared it.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct ConstraintEntry {
+    /// The PEP 508 constraint.
+    pub requirement: Requirement,
+    /// The constraints file that declared the constraint, as passed to `-c` (e.g.,
+    /// `constraints.txt` or a URL).
+    pub source: String,
+    /// The (1-indexed) line in the constraints file on which the constraint was declared.
+    pub line: usize,
+    /// Hashes of the downloadable packages.
+    pub hashes: Vec<String>,
+}
+
 /// Parsed and flattened requirements.txt with requirements and constraints
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct RequirementsTxt {
     /// The actual requirements with the hashes.
     pub requirements: Vec<RequirementEntry>,
-    /// Constraints included with `-c`.
-    pub constraints: Vec<Requirement>,
+    /// Constraints included with `-c`, including those from nested constraints files.
+    pub constraints: Vec<ConstraintEntry>,
     /// Editables with `-e`.
     pub editables: Vec<EditableRequirement>,
     /// The index URL, specified with `--index-url`.
@@ -489,7 +505,12 @@ impl RequirementsTxt {
                     for entry in sub_constraints.requirements {
                         match entry.requirement {
                             RequirementsTxtRequirement::Pep508(requirement) => {
-                                data.constraints.push(requirement);
+                                data.constraints.push(ConstraintEntry {
+                                    requirement,
+                                    source: filename.to_string(),
+                                    line: entry.line,
+                                    hashes: entry.hashes,
+                                });
                             }
                             RequirementsTxtRequirement::Unnamed(_) => {
                                 return Err(RequirementsTxtParserError::UnnamedConstraint {
@@ -499,6 +520,7 @@ impl RequirementsTxt {
                             }
                         }
                     }
+                    // Constraints from nested constraints files retain their original source.
                     data.constraints.extend(sub_constraints.constraints);
                 }
                 RequirementsTxtStatement::RequirementEntry(requirement_entry) => {
@@ -671,6 +693,7 @@ fn parse_entry(
             requirement,
             hashes,
             editable: false,
+            line: calculate_row_column(content, start).0,
         })
     } else if let Some(char) = s.peek() {
         let (line, column) = calculate_row_column(content, s.cursor());
@@ -1744,6 +1767,7 @@ mod test {
                     ),
                     hashes: [],
                     editable: false,
+                    line: 1,
                 },
             ],
             constraints: [],
@@ -1799,6 +1823,7 @@ mod test {
                     ),
                     hashes: [],
                     editable: false,
+                    line: 1,
                 },
             ],
             constraints: [],
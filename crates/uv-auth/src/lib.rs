@@ -1,11 +1,13 @@
 mod keyring;
 mod middleware;
+mod provider;
 mod store;
 
 pub use keyring::KeyringProvider;
 pub use middleware::AuthMiddleware;
 use once_cell::sync::Lazy;
-pub use store::AuthenticationStore;
+pub use provider::{CredentialProvider, KeyringSubprocessProvider};
+pub use store::{AuthenticationStore, BasicAuthData, Credential};
 
 use url::Url;
 
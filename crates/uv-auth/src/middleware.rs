@@ -1,37 +1,61 @@
 use std::path::Path;
+use std::sync::Arc;
 
 use netrc::Netrc;
-use reqwest::{header::HeaderValue, Request, Response};
+use reqwest::{header::HeaderValue, Request, Response, StatusCode};
 use reqwest_middleware::{Middleware, Next};
 use task_local_extensions::Extensions;
-use tracing::{debug, warn};
+use tracing::debug;
 
 use crate::{
-    keyring::{get_keyring_subprocess_auth, KeyringProvider},
+    keyring::KeyringProvider,
+    provider::{CredentialProvider, KeyringSubprocessProvider},
     store::Credential,
     GLOBAL_AUTH_STORE,
 };
 
-/// A middleware that adds basic authentication to requests based on the netrc file and the keyring.
+/// A middleware that adds basic authentication to requests based on the netrc file, falling back
+/// to a [`CredentialProvider`] (e.g., the keyring) if the server responds with a `401`.
 ///
 /// Netrc support Based on: <https://github.com/gribouille/netrc>.
 pub struct AuthMiddleware {
     nrc: Option<Netrc>,
-    keyring_provider: KeyringProvider,
+    credential_provider: Option<Arc<dyn CredentialProvider>>,
 }
 
 impl AuthMiddleware {
     pub fn new(keyring_provider: KeyringProvider) -> Self {
         Self {
             nrc: Netrc::new().ok(),
-            keyring_provider,
+            credential_provider: Self::keyring(keyring_provider),
         }
     }
 
     pub fn from_netrc_file(file: &Path, keyring_provider: KeyringProvider) -> Self {
         Self {
             nrc: Netrc::from_file(file).ok(),
-            keyring_provider,
+            credential_provider: Self::keyring(keyring_provider),
+        }
+    }
+
+    /// Use the given [`CredentialProvider`] when a request is rejected as unauthorized, in lieu
+    /// of the configured keyring provider.
+    #[must_use]
+    pub fn with_credential_provider(
+        self,
+        credential_provider: Arc<dyn CredentialProvider>,
+    ) -> Self {
+        Self {
+            credential_provider: Some(credential_provider),
+            ..self
+        }
+    }
+
+    /// Return the [`CredentialProvider`] for the given [`KeyringProvider`], if any.
+    fn keyring(keyring_provider: KeyringProvider) -> Option<Arc<dyn CredentialProvider>> {
+        match keyring_provider {
+            KeyringProvider::Disabled => None,
+            KeyringProvider::Subprocess => Some(Arc::new(KeyringSubprocessProvider)),
         }
     }
 }
@@ -41,7 +65,7 @@ impl Middleware for AuthMiddleware {
     async fn handle(
         &self,
         mut req: Request,
-        _extensions: &mut Extensions,
+        extensions: &mut Extensions,
         next: Next<'_>,
     ) -> reqwest_middleware::Result<Response> {
         let url = req.url().clone();
@@ -50,7 +74,7 @@ impl Middleware for AuthMiddleware {
         // This gives in-URL credentials precedence over the netrc file.
         if req.headers().contains_key(reqwest::header::AUTHORIZATION) {
             debug!("Request already has an authorization header: {url}");
-            return next.run(req, _extensions).await;
+            return next.run(req, extensions).await;
         }
 
         // Try auth strategies in order of precedence:
@@ -76,32 +100,41 @@ impl Middleware for AuthMiddleware {
                 basic_auth(auth.username(), auth.password()),
             );
             GLOBAL_AUTH_STORE.set(&url, Some(auth));
-        } else if matches!(self.keyring_provider, KeyringProvider::Subprocess) {
-            // If we have keyring support enabled, we check there as well
-            match get_keyring_subprocess_auth(&url) {
-                Ok(Some(auth)) => {
-                    req.headers_mut().insert(
-                        reqwest::header::AUTHORIZATION,
-                        basic_auth(auth.username(), auth.password()),
-                    );
-                    GLOBAL_AUTH_STORE.set(&url, Some(auth));
-                }
-                Ok(None) => {
-                    debug!("No keyring credentials found for {url}");
-                }
-                Err(e) => {
-                    warn!("Failed to get keyring credentials for {url}: {e}");
-                }
-            }
         }
 
-        // If we still don't have any credentials, we save the URL so we don't have to check netrc or keyring again
-        if !req.headers().contains_key(reqwest::header::AUTHORIZATION) {
-            debug!("No credentials found for: {url}");
-            GLOBAL_AUTH_STORE.set(&url, None);
+        if req.headers().contains_key(reqwest::header::AUTHORIZATION) {
+            return next.run(req, extensions).await;
+        }
+
+        // If we still don't have any credentials, we save the URL so we don't have to check netrc
+        // again.
+        debug!("No credentials found for: {url}");
+        GLOBAL_AUTH_STORE.set(&url, None);
+
+        let Some(credential_provider) = self.credential_provider.as_ref() else {
+            return next.run(req, extensions).await;
+        };
+
+        // Send the request without credentials, retaining a copy to retry with credentials from
+        // the provider if the server rejects it. Requests with streaming bodies can't be copied.
+        let Some(mut retry) = req.try_clone() else {
+            return next.run(req, extensions).await;
+        };
+        let response = next.clone().run(req, extensions).await?;
+        if response.status() != StatusCode::UNAUTHORIZED {
+            return Ok(response);
         }
 
-        next.run(req, _extensions).await
+        debug!("Request for {url} was unauthorized; querying credential provider");
+        let Some(auth) = credential_provider.credentials(&url) else {
+            return Ok(response);
+        };
+        retry.headers_mut().insert(
+            reqwest::header::AUTHORIZATION,
+            basic_auth(auth.username(), auth.password()),
+        );
+        GLOBAL_AUTH_STORE.set(&url, Some(auth));
+        next.run(retry, extensions).await
     }
 }
 
@@ -181,4 +214,51 @@ mod tests {
         assert_eq!(status, 200);
         Ok(())
     }
+
+    #[derive(Debug)]
+    struct StaticProvider;
+
+    impl CredentialProvider for StaticProvider {
+        fn credentials(&self, _url: &url::Url) -> Option<Credential> {
+            Some(Credential::Basic(crate::BasicAuthData {
+                username: "myuser".to_string(),
+                password: Some("mypassword".to_string()),
+            }))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_credential_provider_on_unauthorized() -> Result<(), Box<dyn std::error::Error>> {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/hello"))
+            .and(basic_auth("myuser", "mypassword"))
+            .respond_with(ResponseTemplate::new(200))
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/hello"))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&server)
+            .await;
+
+        // Use an empty netrc file, such that the provider is the only source of credentials.
+        let netrc_file = NamedTempFile::new()?;
+
+        let status = ClientBuilder::new(Client::builder().build()?)
+            .with(
+                AuthMiddleware::from_netrc_file(netrc_file.path(), KeyringProvider::Disabled)
+                    .with_credential_provider(Arc::new(StaticProvider)),
+            )
+            .build()
+            .get(format!("{}/hello", &server.uri()))
+            .send()
+            .await?
+            .status();
+
+        assert_eq!(status, 200);
+        Ok(())
+    }
 }
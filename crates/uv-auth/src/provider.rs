@@ -0,0 +1,35 @@
+use std::fmt::Debug;
+
+use tracing::{debug, warn};
+use url::Url;
+
+use crate::keyring::get_keyring_subprocess_auth;
+use crate::store::Credential;
+
+/// A source of credentials, consulted when a request is rejected as unauthorized (i.e., with a
+/// `401` status) and no other credentials are known for the URL.
+pub trait CredentialProvider: Debug + Send + Sync {
+    /// Return the credentials to use for the given URL, if any.
+    fn credentials(&self, url: &Url) -> Option<Credential>;
+}
+
+/// A [`CredentialProvider`] that retrieves credentials by invoking the `keyring` CLI, matching
+/// `pip`'s `--keyring-provider subprocess`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct KeyringSubprocessProvider;
+
+impl CredentialProvider for KeyringSubprocessProvider {
+    fn credentials(&self, url: &Url) -> Option<Credential> {
+        match get_keyring_subprocess_auth(url) {
+            Ok(Some(auth)) => Some(auth),
+            Ok(None) => {
+                debug!("No keyring credentials found for {url}");
+                None
+            }
+            Err(err) => {
+                warn!("Failed to get keyring credentials for {url}: {err}");
+                None
+            }
+        }
+    }
+}
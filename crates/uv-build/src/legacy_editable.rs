@@ -0,0 +1,258 @@
+//! Editable installs of legacy `setup.py` projects (i.e., `--legacy-setup-py` builds), which lack
+//! the PEP 660 `build_editable` hook.
+//!
+//! Like `setup.py develop`, the project is exposed via a `.pth` file pointing at the directory
+//! containing its `.egg-info`; unlike `setup.py develop`, that `.pth` file is packaged into an
+//! editable wheel, such that it's installed (and uninstalled) like any other wheel.
+
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use base64::prelude::BASE64_URL_SAFE_NO_PAD;
+use base64::Engine;
+use fs_err as fs;
+use sha2::{Digest, Sha256};
+use zip::write::FileOptions;
+use zip::ZipWriter;
+
+/// Find the `.egg-info` directory written by `setup.py egg_info`, which is placed in the package
+/// root (e.g., `src/`, for projects with `package_dir={"": "src"}`).
+///
+/// If there are multiple candidates (e.g., stale `.egg-info` directories from earlier builds), the
+/// most recently modified is used.
+pub(crate) fn find_egg_info(source_tree: &Path) -> io::Result<Option<PathBuf>> {
+    let mut candidates = Vec::new();
+    for directory in std::iter::once(source_tree.to_path_buf()).chain(subdirectories(source_tree)?)
+    {
+        for entry in fs::read_dir(&directory)? {
+            let entry = entry?;
+            if entry.file_type()?.is_dir()
+                && entry
+                    .path()
+                    .extension()
+                    .is_some_and(|extension| extension == "egg-info")
+            {
+                let modified = entry.metadata()?.modified()?;
+                candidates.push((modified, entry.path()));
+            }
+        }
+    }
+    Ok(candidates
+        .into_iter()
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, path)| path))
+}
+
+/// Return the non-hidden subdirectories of the given directory.
+fn subdirectories(directory: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut subdirectories = Vec::new();
+    for entry in fs::read_dir(directory)? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() && !entry.file_name().to_string_lossy().starts_with('.') {
+            subdirectories.push(entry.path());
+        }
+    }
+    Ok(subdirectories)
+}
+
+/// Write an editable wheel for the project described by the given `.egg-info` directory into
+/// `wheel_dir`, returning the wheel's filename.
+pub(crate) fn build_editable(egg_info: &Path, wheel_dir: &Path) -> io::Result<String> {
+    let pkg_info = fs::read_to_string(egg_info.join("PKG-INFO"))?;
+    let (Some(name), Some(version)) = (header(&pkg_info, "Name"), header(&pkg_info, "Version"))
+    else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "Missing `Name` or `Version` in {}",
+                egg_info.join("PKG-INFO").display()
+            ),
+        ));
+    };
+
+    // Use the escaped name and version, as in the wheel filename.
+    let name = name.replace(['-', '.'], "_");
+    let version = version.replace('-', "_");
+    let dist_info = format!("{name}-{version}.dist-info");
+    let filename = format!("{name}-{version}-py3-none-any.whl");
+
+    let requires = match fs::read_to_string(egg_info.join("requires.txt")) {
+        Ok(requires) => Some(requires),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+        Err(err) => return Err(err),
+    };
+    let metadata = to_metadata(&pkg_info, requires.as_deref());
+
+    // The directory containing the `.egg-info` is the root of the importable packages.
+    let root = egg_info
+        .parent()
+        .expect("`.egg-info` directory has a parent");
+
+    let mut files: Vec<(String, Vec<u8>)> = vec![
+        (
+            format!("__editable__.{name}-{version}.pth"),
+            format!("{}\n", root.display()).into_bytes(),
+        ),
+        (format!("{dist_info}/METADATA"), metadata.into_bytes()),
+        (
+            format!("{dist_info}/WHEEL"),
+            b"Wheel-Version: 1.0\nGenerator: uv\nRoot-Is-Purelib: true\nTag: py3-none-any\n"
+                .to_vec(),
+        ),
+    ];
+    for extra in ["entry_points.txt", "top_level.txt"] {
+        match fs::read(egg_info.join(extra)) {
+            Ok(contents) => files.push((format!("{dist_info}/{extra}"), contents)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err),
+        }
+    }
+
+    let mut record = String::new();
+    for (path, contents) in &files {
+        let digest = BASE64_URL_SAFE_NO_PAD.encode(Sha256::digest(contents));
+        record.push_str(&format!("{path},sha256={digest},{}\n", contents.len()));
+    }
+    record.push_str(&format!("{dist_info}/RECORD,,\n"));
+    files.push((format!("{dist_info}/RECORD"), record.into_bytes()));
+
+    let mut archive = ZipWriter::new(fs::File::create(wheel_dir.join(&filename))?);
+    for (path, contents) in files {
+        archive.start_file(path, FileOptions::default())?;
+        archive.write_all(&contents)?;
+    }
+    archive.finish()?;
+
+    Ok(filename)
+}
+
+/// Return the value of the first header with the given name in a `PKG-INFO` file.
+fn header<'a>(pkg_info: &'a str, name: &str) -> Option<&'a str> {
+    pkg_info
+        .lines()
+        .take_while(|line| !line.is_empty())
+        .find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            key.eq_ignore_ascii_case(name).then_some(value.trim())
+        })
+}
+
+/// Convert a `PKG-INFO` file to a `METADATA` file, adding the `Requires-Dist` and
+/// `Provides-Extra` headers for the dependencies in `requires.txt`, if they're absent (as with
+/// older versions of `setuptools`).
+///
+/// See: <https://setuptools.pypa.io/en/latest/deprecated/python_eggs.html#requires-txt>
+fn to_metadata(pkg_info: &str, requires: Option<&str>) -> String {
+    let Some(requires) = requires else {
+        return pkg_info.to_string();
+    };
+    if header(pkg_info, "Requires-Dist").is_some() {
+        return pkg_info.to_string();
+    }
+
+    let mut headers = Vec::new();
+    let mut section: Option<(Option<&str>, Option<&str>)> = None;
+    for line in requires.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        // Ex) `[extra]`, `[:python_version < "3.8"]`, or `[extra:sys_platform == "win32"]`
+        if let Some(inner) = line
+            .strip_prefix('[')
+            .and_then(|line| line.strip_suffix(']'))
+        {
+            let (extra, marker) = match inner.split_once(':') {
+                Some((extra, marker)) => (extra.trim(), Some(marker.trim())),
+                None => (inner.trim(), None),
+            };
+            let extra = (!extra.is_empty()).then_some(extra);
+            if let Some(extra) = extra {
+                headers.push(format!("Provides-Extra: {extra}"));
+            }
+            section = Some((extra, marker));
+            continue;
+        }
+
+        let requirement = match section {
+            None | Some((None, None)) => line.to_string(),
+            Some((None, Some(marker))) => format!("{line} ; {marker}"),
+            Some((Some(extra), None)) => format!("{line} ; extra == \"{extra}\""),
+            Some((Some(extra), Some(marker))) => {
+                format!("{line} ; ({marker}) and extra == \"{extra}\"")
+            }
+        };
+        headers.push(format!("Requires-Dist: {requirement}"));
+    }
+
+    // Insert the headers before the body, if any.
+    let (head, body) = match pkg_info.split_once("\n\n") {
+        Some((head, body)) => (head, Some(body)),
+        None => (pkg_info.trim_end_matches('\n'), None),
+    };
+    let mut metadata = head.to_string();
+    for header in headers {
+        metadata.push('\n');
+        metadata.push_str(&header);
+    }
+    metadata.push('\n');
+    if let Some(body) = body {
+        metadata.push('\n');
+        metadata.push_str(body);
+    }
+    metadata
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::to_metadata;
+
+    #[test]
+    fn requires_txt() {
+        let pkg_info = indoc! {"
+            Metadata-Version: 2.1
+            Name: legacy
+            Version: 0.1.0
+
+            A legacy project.
+        "};
+        let requires = indoc! {r#"
+            anyio>=4
+
+            [:python_version < "3.11"]
+            tomli
+
+            [socks]
+            pysocks
+
+            [test:sys_platform == "win32"]
+            colorama
+        "#};
+        insta::assert_snapshot!(to_metadata(pkg_info, Some(requires)), @r###"
+        Metadata-Version: 2.1
+        Name: legacy
+        Version: 0.1.0
+        Requires-Dist: anyio>=4
+        Requires-Dist: tomli ; python_version < "3.11"
+        Provides-Extra: socks
+        Requires-Dist: pysocks ; extra == "socks"
+        Provides-Extra: test
+        Requires-Dist: colorama ; (sys_platform == "win32") and extra == "test"
+
+        A legacy project.
+        "###);
+    }
+
+    #[test]
+    fn requires_dist_present() {
+        let pkg_info = indoc! {"
+            Metadata-Version: 2.1
+            Name: legacy
+            Version: 0.1.0
+            Requires-Dist: anyio>=4
+        "};
+        assert_eq!(to_metadata(pkg_info, Some("anyio>=4\n")), pkg_info);
+    }
+}
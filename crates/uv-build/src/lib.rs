@@ -34,6 +34,8 @@ use uv_types::{
     BuildContext, BuildIsolation, BuildKind, ConfigSettings, SetupPyStrategy, SourceBuildTrait,
 };
 
+mod legacy_editable;
+
 /// e.g. `pygraphviz/graphviz_wrap.c:3020:10: fatal error: graphviz/cgraph.h: No such file or directory`
 static MISSING_HEADER_RE: Lazy<Regex> = Lazy::new(|| {
     Regex::new(
@@ -74,8 +76,6 @@ pub enum Error {
     InvalidSourceDist(String),
     #[error("Invalid `pyproject.toml`")]
     InvalidPyprojectToml(#[from] toml::de::Error),
-    #[error("Editable installs with setup.py legacy builds are unsupported, please specify a build backend in pyproject.toml")]
-    EditableSetupPy,
     #[error("Failed to install requirements from {0}")]
     RequirementsInstall(&'static str, #[source] anyhow::Error),
     #[error("Failed to create temporary virtualenv")]
@@ -415,6 +415,7 @@ impl SourceBuild {
                 interpreter.clone(),
                 uv_virtualenv::Prompt::None,
                 false,
+                false,
                 Vec::new(),
             )?,
             BuildIsolation::Shared(venv) => venv.clone(),
@@ -730,8 +731,8 @@ impl SourceBuild {
             fs_err::rename(from, to)?;
             Ok(filename)
         } else {
-            if self.build_kind != BuildKind::Wheel {
-                return Err(Error::EditableSetupPy);
+            if self.build_kind == BuildKind::Editable {
+                return self.setup_py_editable(&wheel_dir).await;
             }
             // We checked earlier that setup.py exists.
             let python_interpreter = self.venv.python_executable();
@@ -774,6 +775,42 @@ impl SourceBuild {
         }
     }
 
+    /// Build an editable wheel for a legacy `setup.py` project, which lacks the PEP 660 hooks, by
+    /// generating its `.egg-info` in place (as `setup.py develop` would) and packaging a `.pth`
+    /// file that points to the directory containing it.
+    async fn setup_py_editable(&self, wheel_dir: &Path) -> Result<String, Error> {
+        let python_interpreter = self.venv.python_executable();
+        let span = info_span!(
+            "run_python_script",
+            script="setup.py egg_info",
+            python_version = %self.venv.interpreter().python_version()
+        );
+        let output = Command::new(python_interpreter)
+            .args(["setup.py", "egg_info"])
+            .current_dir(self.source_tree.simplified())
+            .output()
+            .instrument(span)
+            .await
+            .map_err(|err| Error::CommandFailed(python_interpreter.to_path_buf(), err))?;
+        if !output.status.success() {
+            return Err(Error::from_command_output(
+                "Failed building editable through setup.py".to_string(),
+                &output,
+                &self.package_id,
+            ));
+        }
+
+        let Some(egg_info) = legacy_editable::find_egg_info(&self.source_tree)? else {
+            return Err(Error::from_command_output(
+                "Expected an `.egg-info` directory after invoking setup.py egg_info".to_string(),
+                &output,
+                &self.package_id,
+            ));
+        };
+        debug!("Building editable wheel from: {}", egg_info.display());
+        Ok(legacy_editable::build_editable(&egg_info, wheel_dir)?)
+    }
+
     async fn pep517_build(
         &self,
         wheel_dir: &Path,
@@ -0,0 +1,213 @@
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::{Deserialize, Serialize};
+
+use uv_fs::directories;
+use uv_normalize::PackageName;
+
+use crate::wheel::WheelCacheKind;
+use crate::{Cache, CacheBucket};
+
+/// The shards accessed by the current process, to be recorded in the [`AccessLog`] on exit.
+///
+/// Filesystem access times are disabled (or coarsened) on most systems, so uv tracks accesses
+/// itself: whenever a shard or entry is computed via [`Cache::shard`] or [`Cache::entry`], its
+/// directory is recorded here, and the set is flushed to the per-bucket logs once the command
+/// completes.
+#[derive(Debug, Default)]
+pub(crate) struct AccessTracker(Mutex<FxHashSet<(CacheBucket, PathBuf)>>);
+
+impl AccessTracker {
+    /// Record an access to the given directory within a bucket.
+    pub(crate) fn record(&self, bucket: CacheBucket, dir: &Path) {
+        if let Ok(mut accessed) = self.0.lock() {
+            accessed.insert((bucket, dir.to_path_buf()));
+        }
+    }
+
+    /// Write the recorded accesses to the [`AccessLog`] for each bucket, for any shards that exist
+    /// in the cache at `root`.
+    pub(crate) fn flush(&self, root: &Path) -> io::Result<()> {
+        let accessed = match self.0.lock() {
+            Ok(mut accessed) => std::mem::take(&mut *accessed),
+            Err(_) => return Ok(()),
+        };
+
+        let mut by_bucket: FxHashMap<CacheBucket, Vec<PathBuf>> = FxHashMap::default();
+        for (bucket, dir) in accessed {
+            if root.join(bucket.to_str()).join(&dir).exists() {
+                by_bucket.entry(bucket).or_default().push(dir);
+            }
+        }
+        if by_bucket.is_empty() {
+            return Ok(());
+        }
+
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        fs_err::create_dir_all(root.join(AccessLog::DIR))?;
+        for (bucket, dirs) in by_bucket {
+            // Guard against concurrent updates from other uv processes.
+            let path = AccessLog::path(root, bucket);
+            let _lock = uv_fs::LockedFile::acquire(path.with_extension("lock"), bucket)?;
+
+            let mut log = AccessLog::read(root, bucket)?;
+            for dir in dirs {
+                log.0.insert(key(&dir), now);
+            }
+            log.write(root, bucket)?;
+        }
+        Ok(())
+    }
+}
+
+/// A compact log of the last access to each shard in a [`CacheBucket`], keyed by the shard's path
+/// relative to the bucket root, and stored as `MessagePack`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct AccessLog(BTreeMap<String, u64>);
+
+impl AccessLog {
+    /// The name of the directory containing the access logs, relative to the cache root.
+    pub(crate) const DIR: &'static str = ".access";
+
+    /// Return the path to the access log for the given bucket.
+    fn path(root: &Path, bucket: CacheBucket) -> PathBuf {
+        root.join(Self::DIR)
+            .join(format!("{}.msgpack", bucket.to_str()))
+    }
+
+    /// Read the access log for the given bucket, returning an empty log if it doesn't exist or
+    /// can't be deserialized.
+    pub(crate) fn read(root: &Path, bucket: CacheBucket) -> io::Result<Self> {
+        match fs_err::read(Self::path(root, bucket)) {
+            Ok(data) => Ok(rmp_serde::from_slice(&data).unwrap_or_default()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Write the access log for the given bucket.
+    fn write(&self, root: &Path, bucket: CacheBucket) -> io::Result<()> {
+        let data = rmp_serde::to_vec(self).map_err(io::Error::other)?;
+        uv_fs::write_atomic_sync(Self::path(root, bucket), data)
+    }
+
+    /// Return the time of the last access to the given shard, if any.
+    pub(crate) fn last_access(&self, dir: &Path) -> Option<SystemTime> {
+        self.0
+            .get(&key(dir))
+            .map(|seconds| SystemTime::UNIX_EPOCH + Duration::from_secs(*seconds))
+    }
+}
+
+/// Return the platform-independent key for a shard directory.
+fn key(dir: &Path) -> String {
+    dir.components()
+        .filter_map(|component| match component {
+            Component::Normal(component) => Some(component.to_string_lossy()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Return the names of all packages in the cache, along with the time at which each was last
+/// used, ordered from least- to most-recently used.
+///
+/// Packages are collected from every bucket that's partitioned by package name, for both PyPI
+/// and alternate indexes. The time of last use is taken from the [`AccessLog`], falling back to
+/// the modification time of the package's entry (which is updated whenever it's written).
+pub(crate) fn packages_by_recency(cache: &Cache) -> Vec<(PackageName, SystemTime)> {
+    let mut packages: FxHashMap<PackageName, SystemTime> = FxHashMap::default();
+    for bucket in [
+        CacheBucket::Wheels,
+        CacheBucket::BuiltWheels,
+        CacheBucket::WheelMetadata,
+        CacheBucket::Simple,
+    ] {
+        let log = AccessLog::read(cache.root(), bucket).unwrap_or_default();
+        let root = cache.bucket(bucket);
+        for (name, path) in package_shards(bucket, &root) {
+            let modified = fs_err::metadata(&path)
+                .and_then(|metadata| metadata.modified())
+                .ok();
+            let accessed = path
+                .strip_prefix(&root)
+                .ok()
+                .and_then(|dir| log.last_access(dir));
+            let Some(last_used) = modified.max(accessed) else {
+                continue;
+            };
+            packages
+                .entry(name)
+                .and_modify(|existing| *existing = (*existing).max(last_used))
+                .or_insert(last_used);
+        }
+    }
+
+    let mut packages = packages.into_iter().collect::<Vec<_>>();
+    packages.sort_by_key(|(_, last_used)| *last_used);
+    packages
+}
+
+/// Return the entries in a bucket that are keyed by package name, along with the name of each
+/// package.
+fn package_shards(bucket: CacheBucket, root: &Path) -> Vec<(PackageName, PathBuf)> {
+    /// Parse a package name from a file or directory name.
+    fn package_name(path: &Path, extension: Option<&str>) -> Option<PackageName> {
+        let name = match extension {
+            Some(extension) => {
+                if path.extension().and_then(|ext| ext.to_str()) != Some(extension) {
+                    return None;
+                }
+                path.file_stem()?
+            }
+            None => path.file_name()?,
+        };
+        PackageName::new(name.to_str()?.to_string()).ok()
+    }
+
+    let (parents, extension) = match bucket {
+        // Wheels and their metadata are stored in a directory per package, either directly under
+        // the `pypi` shard, or under a shard per index or URL.
+        CacheBucket::Wheels | CacheBucket::WheelMetadata => (
+            std::iter::once(root.join(WheelCacheKind::Pypi))
+                .chain(directories(root.join(WheelCacheKind::Index)))
+                .chain(directories(root.join(WheelCacheKind::Url)))
+                .collect::<Vec<_>>(),
+            None,
+        ),
+        // Source distributions from registries are built in a directory per package; those from
+        // URLs, paths, and Git repositories aren't keyed by name.
+        CacheBucket::BuiltWheels => (
+            std::iter::once(root.join(WheelCacheKind::Pypi))
+                .chain(directories(root.join(WheelCacheKind::Index)))
+                .collect(),
+            None,
+        ),
+        // Simple API responses are stored in a file per package, under a shard per index.
+        CacheBucket::Simple => (directories(root).collect(), Some("rkyv")),
+        _ => return Vec::new(),
+    };
+
+    let mut shards = Vec::new();
+    for parent in parents {
+        let Ok(entries) = fs_err::read_dir(&parent) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if let Some(name) = package_name(&path, extension) {
+                shards.push((name, path));
+            }
+        }
+    }
+    shards
+}
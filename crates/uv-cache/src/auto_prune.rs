@@ -0,0 +1,138 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use tracing::debug;
+
+use uv_normalize::PackageName;
+
+use crate::removal::{rm_rf, Removal, RemovalMode};
+use crate::{access, Cache, CacheBucket};
+
+/// An opt-in policy for automatically pruning the cache at the end of a command.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AutoPrune {
+    /// Prune the cache after every `interval` commands.
+    pub interval: Option<u64>,
+    /// Evict the least-recently-used packages whenever the cache exceeds the given size, in bytes.
+    pub max_size: Option<u64>,
+}
+
+impl AutoPrune {
+    /// The number of commands between collections, if only a maximum size is configured.
+    const DEFAULT_INTERVAL: u64 = 20;
+
+    /// The maximum amount of time to spend on a single collection.
+    const BUDGET: Duration = Duration::from_secs(2);
+
+    /// The name of the file used to count commands since the last collection, relative to the
+    /// cache root.
+    pub(crate) const STATE: &'static str = ".auto-prune";
+
+    /// Returns `true` if automatic pruning is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.interval.is_some() || self.max_size.is_some()
+    }
+
+    /// Count a command against the collection interval, returning `true` if a collection is due.
+    pub(crate) fn is_due(self, cache: &Cache) -> io::Result<bool> {
+        if !self.is_enabled() {
+            return Ok(false);
+        }
+
+        // Count the commands since the last collection.
+        let state = cache.root().join(Self::STATE);
+        let count = fs_err::read_to_string(&state)
+            .ok()
+            .and_then(|count| count.trim().parse::<u64>().ok())
+            .unwrap_or(0)
+            + 1;
+        if count < self.interval.unwrap_or(Self::DEFAULT_INTERVAL) {
+            uv_fs::write_atomic_sync(&state, count.to_string())?;
+            return Ok(false);
+        }
+        uv_fs::write_atomic_sync(&state, "0")?;
+        Ok(true)
+    }
+
+    /// Run a bounded, incremental collection on the cache.
+    ///
+    /// Dangling archives are removed first; then, if the cache exceeds the configured maximum
+    /// size, unpinned packages are evicted in least-recently-used order until it fits. The
+    /// collection stops early once its time budget is exhausted, to be resumed by a subsequent
+    /// collection.
+    pub(crate) fn run(self, cache: &Cache) -> io::Result<Removal> {
+        let mut summary = Removal::default();
+
+        let deadline = Instant::now() + Self::BUDGET;
+
+        // First, remove any dangling archives.
+        if cache.bucket(CacheBucket::Archive).is_dir() {
+            for path in cache.dangling_archives()? {
+                if Instant::now() >= deadline {
+                    debug!("Automatic pruning exhausted its time budget");
+                    return Ok(summary);
+                }
+                debug!("Removing dangling cache entry: {}", path.display());
+                summary += rm_rf(path)?;
+            }
+        }
+
+        // Second, evict the least-recently-used packages until the cache fits.
+        let Some(max_size) = self.max_size else {
+            return Ok(summary);
+        };
+        let pins = cache.pins()?;
+        let mut size = disk_usage(cache.root())?;
+        for (name, _) in access::packages_by_recency(cache) {
+            // Never evict pinned packages.
+            if pins.contains(&name) {
+                continue;
+            }
+            if size <= max_size {
+                break;
+            }
+            if Instant::now() >= deadline {
+                debug!("Automatic pruning exhausted its time budget");
+                break;
+            }
+
+            // Remove the package's entries, along with the archives they link to.
+            let archives = package_archives(cache, &name);
+            let mut removal = cache.remove(&name, RemovalMode::Remove)?;
+            for archive in archives {
+                removal += rm_rf(archive)?;
+            }
+
+            debug!("Evicted least-recently-used package from the cache: {name}");
+            size = size.saturating_sub(removal.total_bytes);
+            summary += removal;
+        }
+
+        Ok(summary)
+    }
+}
+
+/// Return the archives linked to by the entries for the given package in the wheel buckets,
+/// including those for alternate indexes, direct URLs, local paths, and Git dependencies.
+pub(crate) fn package_archives(cache: &Cache, name: &PackageName) -> Vec<PathBuf> {
+    [CacheBucket::Wheels, CacheBucket::BuiltWheels]
+        .into_iter()
+        .flat_map(|bucket| bucket.package_entries(cache, name))
+        .flat_map(|entry| walkdir::WalkDir::new(entry).into_iter().flatten())
+        .filter(|entry| entry.file_type().is_symlink())
+        .filter_map(|entry| entry.path().canonicalize().ok())
+        .collect()
+}
+
+/// Return the total size of the files within a directory, without following symlinks.
+fn disk_usage(path: &Path) -> io::Result<u64> {
+    let mut total = 0;
+    for entry in walkdir::WalkDir::new(path) {
+        let entry = entry?;
+        if entry.file_type().is_file() {
+            total += entry.metadata()?.len();
+        }
+    }
+    Ok(total)
+}
@@ -4,7 +4,7 @@ use std::path::PathBuf;
 use clap::Parser;
 use directories::ProjectDirs;
 
-use crate::Cache;
+use crate::{ArchiveCompression, AutoPrune, Cache, CacheFsMode};
 
 #[derive(Parser, Debug, Clone)]
 pub struct CacheArgs {
@@ -18,6 +18,16 @@ pub struct CacheArgs {
     )]
     no_cache: bool,
 
+    /// Read from the cache, but direct all writes to a temporary cache that's discarded on exit,
+    /// leaving the cache directory unmodified.
+    #[arg(
+        global = true,
+        long,
+        conflicts_with = "no_cache",
+        env = "UV_CACHE_READ_ONLY"
+    )]
+    cache_read_only: bool,
+
     /// Path to the cache directory.
     ///
     /// Defaults to `$HOME/Library/Caches/uv` on macOS, `$XDG_CACHE_HOME/uv` or `$HOME/.cache/uv` on
@@ -25,6 +35,55 @@ pub struct CacheArgs {
     /// on Windows.
     #[arg(global = true, long, env = "UV_CACHE_DIR")]
     cache_dir: Option<PathBuf>,
+
+    /// The compression to apply to unzipped wheels stored in the cache.
+    ///
+    /// Compressed entries take up less space on disk, but must be decompressed prior to
+    /// installation. Existing entries are unaffected, such that caches may contain a mix of
+    /// compressed and uncompressed entries.
+    #[arg(
+        global = true,
+        long,
+        value_enum,
+        default_value_t,
+        env = "UV_CACHE_COMPRESSION"
+    )]
+    cache_compression: ArchiveCompression,
+
+    /// The strategy used to persist entries to the cache directory.
+    ///
+    /// By default, uv detects whether the cache directory is on a network filesystem (e.g., NFS or
+    /// SMB), on which symlinks and atomic renames are unreliable, and falls back to copying
+    /// entries into place under a lock file.
+    #[arg(
+        global = true,
+        long,
+        value_enum,
+        default_value_t,
+        env = "UV_CACHE_FS_MODE"
+    )]
+    cache_fs_mode: CacheFsMode,
+
+    /// Automatically prune the cache after every N commands.
+    ///
+    /// Pruning is incremental and bounded in time: dangling entries are removed first, followed by
+    /// the least-recently-used packages if the cache exceeds `--cache-max-size`.
+    #[arg(global = true, long, env = "UV_CACHE_AUTO_PRUNE_INTERVAL")]
+    cache_auto_prune_interval: Option<u64>,
+
+    /// The maximum size of the cache (e.g., `500M` or `10G`), beyond which the least-recently-used
+    /// packages are evicted during automatic pruning.
+    #[arg(global = true, long, value_parser = parse_size, env = "UV_CACHE_MAX_SIZE")]
+    cache_max_size: Option<u64>,
+
+    /// Strictly follow the HTTP caching semantics of RFC 9111 when reusing cached responses.
+    ///
+    /// By default, uv favors cached responses where the RFC leaves room for interpretation (e.g.,
+    /// reusing stale responses when offline). In strict mode, `no-store`, `must-revalidate`,
+    /// `Vary`, and the rules for heuristic freshness are fully respected, such that indexes that
+    /// rely on cache headers for invalidation behave as expected.
+    #[arg(global = true, long, env = "UV_STRICT_HTTP_CACHE")]
+    strict_http_cache: bool,
 }
 
 impl TryFrom<CacheArgs> for Cache {
@@ -33,19 +92,78 @@ impl TryFrom<CacheArgs> for Cache {
     /// Prefer, in order:
     /// 1. A temporary cache directory, if the user requested `--no-cache`.
     /// 2. The specific cache directory specified by the user via `--cache-dir` or `UV_CACHE_DIR`.
-    /// 3. The system-appropriate cache directory.
-    /// 4. A `.uv_cache` directory in the current working directory.
+    /// 3. A project-local `.uv-cache` directory in the current working directory or any of its
+    ///    ancestors, layered over the system-appropriate cache directory.
+    /// 4. The system-appropriate cache directory.
+    /// 5. A `.uv_cache` directory in the current working directory.
+    ///
+    /// With `--cache-read-only`, the selected cache directory is overlaid with a temporary cache, to
+    /// which all writes are directed. The selected cache directory is never created or modified.
     ///
     /// Returns an absolute cache dir.
     fn try_from(value: CacheArgs) -> Result<Self, Self::Error> {
-        if value.no_cache {
+        let project_dirs = ProjectDirs::from("", "", "uv");
+        let cache = if value.no_cache {
             Self::temp()
-        } else if let Some(cache_dir) = value.cache_dir {
-            Self::from_path(cache_dir)
-        } else if let Some(project_dirs) = ProjectDirs::from("", "", "uv") {
-            Self::from_path(project_dirs.cache_dir())
         } else {
-            Self::from_path(".uv_cache")
-        }
+            let (root, fallback) = if let Some(cache_dir) = value.cache_dir {
+                (cache_dir, None)
+            } else if let Some(local_cache_dir) = find_local_cache_dir()? {
+                (
+                    local_cache_dir,
+                    project_dirs
+                        .as_ref()
+                        .map(|project_dirs| project_dirs.cache_dir().to_path_buf()),
+                )
+            } else if let Some(project_dirs) = project_dirs.as_ref() {
+                (project_dirs.cache_dir().to_path_buf(), None)
+            } else {
+                (PathBuf::from(".uv_cache"), None)
+            };
+            if value.cache_read_only {
+                // Avoid `Cache::from_path`, which initializes the cache directory.
+                Self::temp_overlay(root)
+            } else {
+                let cache = Self::from_path(root)?;
+                match fallback {
+                    Some(fallback) => cache.with_fallback(fallback),
+                    None => Ok(cache),
+                }
+            }
+        }?;
+        Ok(cache
+            .with_compression(value.cache_compression)
+            .with_fs_mode(value.cache_fs_mode)
+            .with_auto_prune(AutoPrune {
+                interval: value.cache_auto_prune_interval,
+                max_size: value.cache_max_size,
+            })
+            .with_strict_http(value.strict_http_cache))
     }
 }
+
+/// Parse a size in bytes, with an optional binary suffix (e.g., `512K`, `500M`, or `10G`).
+fn parse_size(input: &str) -> Result<u64, String> {
+    let input = input.trim();
+    let (digits, multiplier) = match input.char_indices().last() {
+        Some((index, 'K' | 'k')) => (&input[..index], 1024),
+        Some((index, 'M' | 'm')) => (&input[..index], 1024 * 1024),
+        Some((index, 'G' | 'g')) => (&input[..index], 1024 * 1024 * 1024),
+        _ => (input, 1),
+    };
+    digits
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .and_then(|size| size.checked_mul(multiplier))
+        .ok_or_else(|| format!("Invalid size: `{input}`"))
+}
+
+/// Find the nearest `.uv-cache` directory in the current working directory or its ancestors.
+fn find_local_cache_dir() -> io::Result<Option<PathBuf>> {
+    let cwd = std::env::current_dir()?;
+    Ok(cwd
+        .ancestors()
+        .map(|dir| dir.join(".uv-cache"))
+        .find(|dir| dir.is_dir()))
+}
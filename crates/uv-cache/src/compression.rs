@@ -0,0 +1,175 @@
+use std::io;
+use std::path::Path;
+
+/// The compression to apply to new entries in the archive bucket.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum ArchiveCompression {
+    /// Store unzipped wheels as plain directories, which can be linked directly into an
+    /// environment.
+    #[default]
+    None,
+    /// Store unzipped wheels as zstd-compressed tarballs, which must be decompressed prior to
+    /// installation.
+    Zstd,
+}
+
+/// The on-disk format of an individual entry in the archive bucket.
+///
+/// The format is recorded per-entry (via the file extension), such that caches containing a mix
+/// of compressed and uncompressed entries remain usable regardless of the configured
+/// [`ArchiveCompression`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    /// An unzipped wheel, stored as a directory.
+    Directory,
+    /// An unzipped wheel, stored as a zstd-compressed tarball.
+    TarZstd,
+}
+
+impl ArchiveFormat {
+    /// The file extension used for [`ArchiveFormat::TarZstd`] entries.
+    pub(crate) const TAR_ZSTD_EXTENSION: &'static str = "tar.zst";
+
+    /// Determine the format of the archive entry at the given (canonicalized) path.
+    ///
+    /// Entries that were hard-linked or copied into place (rather than symlinked) don't carry the
+    /// archive's extension, so any entry that's a file, rather than a directory, is assumed to be
+    /// compressed.
+    pub fn from_path(path: impl AsRef<Path>) -> Self {
+        if path
+            .as_ref()
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.ends_with(Self::TAR_ZSTD_EXTENSION))
+            || path.as_ref().is_file()
+        {
+            Self::TarZstd
+        } else {
+            Self::Directory
+        }
+    }
+
+    /// Returns `true` if the archive entry must be decompressed prior to use.
+    pub fn is_compressed(self) -> bool {
+        matches!(self, Self::TarZstd)
+    }
+}
+
+/// Compress the directory at `source` into a zstd-compressed tarball at `target`.
+pub(crate) fn compress(source: &Path, target: &Path) -> io::Result<()> {
+    let parent = target.parent().expect("Cache entry to have parent");
+    let temp_file = tempfile::NamedTempFile::new_in(parent)?;
+
+    let encoder = zstd::Encoder::new(temp_file.as_file(), zstd::DEFAULT_COMPRESSION_LEVEL)?;
+    let mut builder = tar::Builder::new(encoder);
+    builder.follow_symlinks(false);
+    builder.append_dir_all(".", source)?;
+    builder.into_inner()?.finish()?;
+
+    temp_file.persist(target).map_err(|err| err.error)?;
+    Ok(())
+}
+
+/// Decompress the zstd-compressed tarball at `source` into the directory at `target`.
+pub fn decompress(source: impl AsRef<Path>, target: impl AsRef<Path>) -> io::Result<()> {
+    let file = fs_err::File::open(source.as_ref())?;
+    let decoder = zstd::Decoder::new(file)?;
+    let mut archive = tar::Archive::new(decoder);
+    archive.set_preserve_permissions(true);
+    archive.unpack(target.as_ref())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Result;
+    use std::path::Path;
+
+    use crate::{decompress, ArchiveCompression, ArchiveFormat, Cache, CacheBucket};
+
+    /// Populate the given directory with the contents of a minimal unzipped wheel.
+    fn unzipped_wheel(path: &Path) -> Result<()> {
+        fs_err::create_dir_all(path.join("foo"))?;
+        fs_err::create_dir_all(path.join("foo-1.0.0.dist-info"))?;
+        fs_err::write(path.join("foo").join("__init__.py"), "print('foo')\n")?;
+        fs_err::write(
+            path.join("foo-1.0.0.dist-info").join("METADATA"),
+            "Metadata-Version: 2.1\nName: foo\nVersion: 1.0.0\n",
+        )?;
+        Ok(())
+    }
+
+    /// Assert that the given directory contains the files written by [`unzipped_wheel`].
+    fn assert_unzipped_wheel(path: &Path) -> Result<()> {
+        assert_eq!(
+            fs_err::read_to_string(path.join("foo").join("__init__.py"))?,
+            "print('foo')\n"
+        );
+        assert_eq!(
+            fs_err::read_to_string(path.join("foo-1.0.0.dist-info").join("METADATA"))?,
+            "Metadata-Version: 2.1\nName: foo\nVersion: 1.0.0\n"
+        );
+        Ok(())
+    }
+
+    /// Persist an unzipped wheel with the given compression, and read it back.
+    async fn round_trip(compression: ArchiveCompression, format: ArchiveFormat) -> Result<()> {
+        let cache = Cache::temp()?.with_compression(compression);
+
+        let temp_dir = tempfile::tempdir_in(cache.root())?;
+        unzipped_wheel(temp_dir.path())?;
+
+        let entry = cache.entry(CacheBucket::Wheels, "foo", "foo-1.0.0-py3-none-any");
+        let archive = cache.persist(temp_dir.into_path(), entry.path()).await?;
+        assert_eq!(ArchiveFormat::from_path(archive), format);
+        assert_eq!(ArchiveFormat::from_path(entry.path()), format);
+
+        if format.is_compressed() {
+            let target = tempfile::tempdir()?;
+            decompress(entry.path(), target.path())?;
+            assert_unzipped_wheel(target.path())?;
+        } else {
+            assert_unzipped_wheel(entry.path())?;
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn round_trip_none() -> Result<()> {
+        round_trip(ArchiveCompression::None, ArchiveFormat::Directory).await
+    }
+
+    #[tokio::test]
+    async fn round_trip_zstd() -> Result<()> {
+        round_trip(ArchiveCompression::Zstd, ArchiveFormat::TarZstd).await
+    }
+
+    /// The permissions of executables are preserved across compression.
+    #[cfg(unix)]
+    #[test]
+    fn preserve_permissions() -> Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let source = tempfile::tempdir()?;
+        unzipped_wheel(source.path())?;
+        let script = source.path().join("foo").join("run.sh");
+        fs_err::write(&script, "#!/bin/sh\n")?;
+        fs_err::set_permissions(&script, std::fs::Permissions::from_mode(0o755))?;
+
+        let archive = tempfile::tempdir()?;
+        let tarball = archive.path().join("foo.tar.zst");
+        super::compress(source.path(), &tarball)?;
+
+        let target = tempfile::tempdir()?;
+        decompress(&tarball, target.path())?;
+        assert_unzipped_wheel(target.path())?;
+        let mode = fs_err::metadata(target.path().join("foo").join("run.sh"))?
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o111, 0o111);
+
+        Ok(())
+    }
+}
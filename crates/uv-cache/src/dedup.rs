@@ -0,0 +1,168 @@
+use std::fs::Permissions;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use rustc_hash::FxHashMap;
+use sha2::{Digest, Sha256};
+use tracing::debug;
+
+/// Deduplicate identical files within a directory tree (e.g., across entries in the archive
+/// bucket), replacing duplicates with hard links or, if hard links aren't supported, reflinks to a
+/// single canonical copy.
+///
+/// Links are staged in a temporary directory within `scratch`, which must be on the same
+/// filesystem as `path`, but outside of it.
+pub(crate) fn dedup(
+    path: impl AsRef<Path>,
+    scratch: impl AsRef<Path>,
+) -> io::Result<Deduplication> {
+    let mut deduplication = Deduplication::default();
+    let temp_dir = tempfile::tempdir_in(scratch)?;
+    deduplication.dedup(path.as_ref(), temp_dir.path())?;
+    Ok(deduplication)
+}
+
+#[derive(Debug, Default)]
+pub struct Deduplication {
+    /// The number of files that were replaced with a link to an identical file.
+    pub num_files: u64,
+    /// The total number of bytes saved.
+    ///
+    /// Note: this is a measure of the exact byte size of the linked files (as opposed to the block
+    /// size), and so will under-count the savings in general.
+    pub total_bytes: u64,
+}
+
+impl Deduplication {
+    /// Recursively deduplicate all files within a directory.
+    fn dedup(&mut self, root: &Path, temp_dir: &Path) -> io::Result<()> {
+        // Group candidate files by size, to avoid hashing files that can't possibly be identical.
+        let mut by_size: FxHashMap<u64, Vec<(PathBuf, Permissions)>> = FxHashMap::default();
+        for entry in walkdir::WalkDir::new(root) {
+            let entry = entry?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let metadata = entry.metadata()?;
+            if metadata.len() == 0 {
+                continue;
+            }
+            by_size
+                .entry(metadata.len())
+                .or_default()
+                .push((entry.into_path(), metadata.permissions()));
+        }
+
+        for (size, paths) in by_size {
+            if paths.len() < 2 {
+                continue;
+            }
+
+            // Within each size class, group files by content hash. Linked files share their
+            // permissions, so files that differ in permissions (e.g., an executable script and an
+            // identical data file) are kept apart.
+            let mut by_digest: FxHashMap<[u8; 32], Vec<(PathBuf, Permissions)>> =
+                FxHashMap::default();
+            for (path, permissions) in paths {
+                let digest = sha256(&path)?;
+                let originals = by_digest.entry(digest).or_default();
+                let Some((original, _)) = originals
+                    .iter()
+                    .find(|(_, original)| *original == permissions)
+                else {
+                    originals.push((path, permissions));
+                    continue;
+                };
+
+                // If the files are already linked, there's nothing to do.
+                if same_file::is_same_file(original, &path)? {
+                    continue;
+                }
+
+                debug!(
+                    "Deduplicating {} against {}",
+                    path.display(),
+                    original.display()
+                );
+                link(original, &path, temp_dir)?;
+                self.num_files += 1;
+                self.total_bytes += size;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl std::ops::AddAssign for Deduplication {
+    fn add_assign(&mut self, other: Self) {
+        self.num_files += other.num_files;
+        self.total_bytes += other.total_bytes;
+    }
+}
+
+/// Compute the SHA-256 digest of the file at the given path.
+fn sha256(path: &Path) -> io::Result<[u8; 32]> {
+    let mut hasher = Sha256::new();
+    let mut file = fs_err::File::open(path)?;
+    io::copy(&mut file, &mut hasher)?;
+    Ok(hasher.finalize().into())
+}
+
+/// Atomically replace `dst` with a link to `src`, preferring a hard link and falling back to a
+/// reflink.
+///
+/// Hard links are preferred as they're detectable on subsequent passes, whereas reflinked files
+/// are indistinguishable from copies.
+///
+/// The link is staged in `temp_dir`, such that the tree being deduplicated is never modified other
+/// than by the final rename.
+fn link(src: &Path, dst: &Path, temp_dir: &Path) -> io::Result<()> {
+    let temp_file = temp_dir.join("link");
+    if let Err(err) = fs_err::hard_link(src, &temp_file) {
+        debug!("Failed to hard link ({err}); falling back to reflink");
+        reflink_copy::reflink(src, &temp_file)?;
+    }
+    fs_err::rename(&temp_file, dst)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    /// Identical files are only linked if their permissions match.
+    #[cfg(unix)]
+    #[test]
+    fn dedup_permissions() -> io::Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let root = tempfile::tempdir()?;
+        let scratch = tempfile::tempdir()?;
+        let archive = root.path().join("archive");
+        for entry in ["a", "b", "c"] {
+            fs_err::create_dir_all(archive.join(entry))?;
+            fs_err::write(archive.join(entry).join("script"), "#!/bin/sh\n")?;
+        }
+        fs_err::set_permissions(
+            archive.join("c").join("script"),
+            std::fs::Permissions::from_mode(0o755),
+        )?;
+
+        let deduplication = super::dedup(&archive, scratch.path())?;
+        assert_eq!(deduplication.num_files, 1);
+        assert!(same_file::is_same_file(
+            archive.join("a").join("script"),
+            archive.join("b").join("script")
+        )?);
+        assert!(!same_file::is_same_file(
+            archive.join("a").join("script"),
+            archive.join("c").join("script")
+        )?);
+
+        // Only the archive itself is modified.
+        assert_eq!(fs_err::read_dir(scratch.path())?.count(), 0);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,141 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+use tracing::debug;
+
+use crate::removal::rm_rf;
+
+/// The strategy used to persist entries to the cache directory.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum CacheFsMode {
+    /// Detect whether the cache directory is on a network filesystem, and select the appropriate
+    /// strategy.
+    #[default]
+    Auto,
+    /// Persist entries via atomic renames into the archive bucket, linked into place via
+    /// symlinks.
+    Local,
+    /// Persist entries by copying them into place under a lock file, avoiding symlinks and atomic
+    /// renames, which are unreliable on network filesystems (e.g., NFS or SMB).
+    Network,
+}
+
+impl CacheFsMode {
+    /// Resolve [`CacheFsMode::Auto`] to a concrete mode for the cache directory at `root`.
+    pub(crate) fn resolve(self, root: &Path) -> Self {
+        match self {
+            Self::Auto => {
+                if is_network_filesystem(root) {
+                    debug!(
+                        "Detected network filesystem for cache directory: {}",
+                        root.display()
+                    );
+                    Self::Network
+                } else {
+                    Self::Local
+                }
+            }
+            mode => mode,
+        }
+    }
+}
+
+/// Filesystem types for which symlinks and atomic renames are unreliable.
+#[cfg(target_os = "linux")]
+const NETWORK_FILESYSTEMS: &[&str] = &["nfs", "nfs4", "cifs", "smb3", "smbfs", "9p", "fuse.sshfs"];
+
+/// Returns `true` if the given (canonicalized) path resides on a network filesystem.
+///
+/// Detection is based on the mount table, and is only supported on Linux; on other platforms,
+/// [`CacheFsMode::Network`] must be requested explicitly.
+#[cfg(target_os = "linux")]
+fn is_network_filesystem(path: &Path) -> bool {
+    let mounts = match fs_err::read_to_string("/proc/self/mounts") {
+        Ok(mounts) => mounts,
+        Err(err) => {
+            debug!("Failed to read mount table: {err}");
+            return false;
+        }
+    };
+
+    // Find the most specific mount point containing the path.
+    mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let _device = fields.next()?;
+            let mount_point = fields.next()?;
+            let fs_type = fields.next()?;
+            Some((unescape_mount_point(mount_point), fs_type))
+        })
+        .filter(|(mount_point, _)| path.starts_with(mount_point))
+        .max_by_key(|(mount_point, _)| mount_point.len())
+        .is_some_and(|(_, fs_type)| NETWORK_FILESYSTEMS.contains(&fs_type))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn is_network_filesystem(_path: &Path) -> bool {
+    false
+}
+
+/// Unescape the octal escapes (e.g., `\040` for a space) used in the mount table.
+#[cfg(target_os = "linux")]
+fn unescape_mount_point(mount_point: &str) -> String {
+    let mut unescaped = String::with_capacity(mount_point.len());
+    let mut chars = mount_point.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            let digits: String = chars.by_ref().take(3).collect();
+            if let Ok(byte) = u8::from_str_radix(&digits, 8) {
+                unescaped.push(char::from(byte));
+                continue;
+            }
+            unescaped.push(c);
+            unescaped.push_str(&digits);
+        } else {
+            unescaped.push(c);
+        }
+    }
+    unescaped
+}
+
+/// Persist a temporary directory to the given path by copying it into place, rather than moving
+/// it into the artifact store and symlinking it.
+///
+/// Compression is not applied to entries persisted in this way.
+pub(crate) fn persist_copy(temp_dir: &Path, path: &Path) -> io::Result<PathBuf> {
+    let parent = path.parent().expect("Cache entry to have parent");
+    fs_err::create_dir_all(parent)?;
+
+    // Guard against concurrent writers, since the copy is not atomic.
+    let mut lock = path.as_os_str().to_owned();
+    lock.push(".lock");
+    let _lock = uv_fs::LockedFile::acquire(PathBuf::from(lock), path.display())?;
+
+    // Replace any existing entry (e.g., a symlink created in local mode, or a partial copy).
+    rm_rf(path)?;
+    copy_dir_all(temp_dir, path)?;
+    rm_rf(temp_dir)?;
+
+    debug!("Persisted cache entry by copy: {}", path.display());
+    Ok(path.to_path_buf())
+}
+
+/// Recursively copy the directory at `source` to `target`, following any symlinks.
+pub(crate) fn copy_dir_all(source: &Path, target: &Path) -> io::Result<()> {
+    for entry in walkdir::WalkDir::new(source).follow_links(true) {
+        let entry = entry?;
+        let relative = entry
+            .path()
+            .strip_prefix(source)
+            .expect("walkdir starts with root");
+        let destination = target.join(relative);
+        if entry.file_type().is_dir() {
+            fs_err::create_dir_all(&destination)?;
+        } else {
+            fs_err::copy(entry.path(), &destination)?;
+        }
+    }
+    Ok(())
+}
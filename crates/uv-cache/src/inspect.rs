@@ -0,0 +1,241 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use distribution_filename::WheelFilename;
+use pep440_rs::Version;
+use pypi_types::Metadata23;
+use uv_fs::directories;
+use uv_normalize::PackageName;
+
+use crate::access::AccessLog;
+use crate::{Cache, CacheBucket, WheelCacheKind};
+
+/// A description of an entry in the cache, as returned by [`Cache::entries`].
+#[derive(Debug, Clone)]
+pub struct CacheEntryInfo {
+    /// The bucket containing the entry.
+    pub bucket: CacheBucket,
+    /// The path to the entry.
+    pub path: PathBuf,
+    /// The package to which the entry belongs, if known.
+    pub name: Option<PackageName>,
+    /// The version of the package, if known.
+    pub version: Option<Version>,
+    /// The kind of source from which the entry originated, for buckets that are partitioned by
+    /// source (e.g., [`CacheBucket::Wheels`]).
+    pub kind: Option<WheelCacheKind>,
+    /// The total size of the entry, in bytes, including any archive it links to.
+    pub size: u64,
+    /// The time at which the entry was last modified.
+    pub modified: Option<SystemTime>,
+    /// The time at which the entry was last accessed, as recorded in the cache's access log.
+    pub accessed: Option<SystemTime>,
+}
+
+/// The location of an entry within a bucket, prior to inspection.
+struct Candidate {
+    path: PathBuf,
+    kind: Option<WheelCacheKind>,
+    name: Option<PackageName>,
+}
+
+/// Return an iterator over the entries in the given bucket.
+///
+/// The bucket layout is traversed eagerly, but each entry is only inspected (e.g., to compute its
+/// size) as the iterator is advanced.
+pub(crate) fn entries(
+    cache: &Cache,
+    bucket: CacheBucket,
+) -> impl Iterator<Item = io::Result<CacheEntryInfo>> {
+    let root = cache.bucket(bucket);
+    let log = AccessLog::read(cache.root(), bucket).unwrap_or_default();
+    let candidates = candidates(&root, bucket);
+    candidates
+        .into_iter()
+        .map(move |candidate| inspect(&root, bucket, &log, candidate))
+}
+
+/// Enumerate the entries in a bucket, following the layout documented on [`CacheBucket`].
+fn candidates(root: &Path, bucket: CacheBucket) -> Vec<Candidate> {
+    let mut candidates = Vec::new();
+    match bucket {
+        CacheBucket::Wheels | CacheBucket::WheelMetadata => {
+            // Wheels are stored per-package, either directly under the `pypi` shard, or under a
+            // shard per index or URL.
+            for (kind, shard) in shards(root, &[WheelCacheKind::Index, WheelCacheKind::Url]) {
+                for package in directories(shard) {
+                    let name = package_name(&package);
+                    for entry in children(&package) {
+                        candidates.push(Candidate {
+                            path: entry,
+                            kind: Some(kind),
+                            name: name.clone(),
+                        });
+                    }
+                }
+            }
+        }
+        CacheBucket::BuiltWheels => {
+            // Built wheels are stored per-revision, at varying depths; each revision contains the
+            // metadata of the source distribution from which it was built.
+            for kind in [
+                WheelCacheKind::Pypi,
+                WheelCacheKind::Index,
+                WheelCacheKind::Url,
+                WheelCacheKind::Path,
+                WheelCacheKind::Editable,
+                WheelCacheKind::Git,
+            ] {
+                for entry in walkdir::WalkDir::new(root.join(kind))
+                    .into_iter()
+                    .flatten()
+                    .filter(|entry| entry.file_type().is_dir())
+                    .filter(|entry| entry.path().join("metadata.msgpack").is_file())
+                {
+                    candidates.push(Candidate {
+                        path: entry.into_path(),
+                        kind: Some(kind),
+                        name: None,
+                    });
+                }
+            }
+        }
+        CacheBucket::Simple => {
+            // Index responses are stored per-package, either directly under the `pypi` shard, or
+            // under a shard per index.
+            for (kind, shard) in shards(root, &[WheelCacheKind::Url]) {
+                for entry in children(&shard) {
+                    let name = entry
+                        .file_stem()
+                        .and_then(|stem| stem.to_str())
+                        .and_then(|stem| PackageName::new(stem.to_string()).ok());
+                    candidates.push(Candidate {
+                        path: entry,
+                        kind: Some(kind),
+                        name,
+                    });
+                }
+            }
+        }
+        CacheBucket::FlatIndex
+        | CacheBucket::Git
+        | CacheBucket::Interpreter
+        | CacheBucket::Resolutions
+        | CacheBucket::Archive => {
+            // These buckets aren't partitioned by package.
+            for entry in children(root) {
+                candidates.push(Candidate {
+                    path: entry,
+                    kind: None,
+                    name: None,
+                });
+            }
+        }
+    }
+    candidates
+}
+
+/// Inspect a single entry, computing its size, timestamps, and (where possible) version.
+fn inspect(
+    root: &Path,
+    bucket: CacheBucket,
+    log: &AccessLog,
+    candidate: Candidate,
+) -> io::Result<CacheEntryInfo> {
+    let Candidate {
+        path,
+        kind,
+        mut name,
+    } = candidate;
+
+    let mut version = None;
+    match bucket {
+        CacheBucket::Wheels | CacheBucket::WheelMetadata => {
+            // Entries are named after the wheel, with or without an extension (e.g., the unzipped
+            // wheel, alongside its `.http` or `.rev` pointer).
+            let stem = path.file_name().and_then(|name| name.to_str()).map(|name| {
+                [".msgpack", ".whl", ".http", ".rev"]
+                    .into_iter()
+                    .find_map(|extension| name.strip_suffix(extension))
+                    .unwrap_or(name)
+                    .to_string()
+            });
+            if let Some(filename) = stem.and_then(|stem| WheelFilename::from_stem(&stem).ok()) {
+                version = Some(filename.version);
+            }
+        }
+        CacheBucket::BuiltWheels => {
+            if let Some(metadata) = fs_err::read(path.join("metadata.msgpack"))
+                .ok()
+                .and_then(|metadata| rmp_serde::from_slice::<Metadata23>(&metadata).ok())
+            {
+                name = Some(metadata.name);
+                version = Some(metadata.version);
+            }
+        }
+        _ => {}
+    }
+
+    let metadata = fs_err::symlink_metadata(&path)?;
+    let modified = metadata.modified().ok();
+    let accessed = path
+        .strip_prefix(root)
+        .ok()
+        .into_iter()
+        .flat_map(Path::ancestors)
+        .filter_map(|dir| log.last_access(dir))
+        .max();
+
+    Ok(CacheEntryInfo {
+        bucket,
+        size: disk_usage(&path)?,
+        path,
+        name,
+        version,
+        kind,
+        modified,
+        accessed,
+    })
+}
+
+/// Return the shards within a bucket: the `pypi` shard, followed by a shard for every index or
+/// URL under each of the given kinds.
+fn shards(root: &Path, kinds: &[WheelCacheKind]) -> Vec<(WheelCacheKind, PathBuf)> {
+    let mut shards = vec![(WheelCacheKind::Pypi, root.join(WheelCacheKind::Pypi))];
+    for kind in kinds {
+        shards.extend(directories(root.join(kind)).map(|shard| (*kind, shard)));
+    }
+    shards
+}
+
+/// Return the name of the package for a package directory.
+fn package_name(path: &Path) -> Option<PackageName> {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .and_then(|name| PackageName::new(name.to_string()).ok())
+}
+
+/// Return the entries directly within a directory, excluding hidden files (e.g., lock files and
+/// marker files).
+fn children(path: &Path) -> impl Iterator<Item = PathBuf> {
+    path.read_dir()
+        .ok()
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+        .map(|entry| entry.path())
+}
+
+/// Return the total size of the files within an entry, following links into the archive bucket.
+fn disk_usage(path: &Path) -> io::Result<u64> {
+    let mut total = 0;
+    for entry in walkdir::WalkDir::new(path).follow_links(true) {
+        let entry = entry?;
+        if entry.file_type().is_file() {
+            total += entry.metadata()?.len();
+        }
+    }
+    Ok(total)
+}
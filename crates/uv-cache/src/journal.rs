@@ -0,0 +1,188 @@
+use std::io;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use fs2::FileExt;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::link::link_archive;
+
+/// A write-ahead journal for [`crate::Cache::persist`].
+///
+/// Persisting an entry is a two-phase operation: the entry is first moved into the archive bucket,
+/// and then symlinked into place. If uv is interrupted between the two phases, the archive would
+/// otherwise be leaked until the next `uv cache prune`. To avoid this, a record of the operation is
+/// written to the journal before the first phase, and removed once the second phase completes. Any
+/// records left behind are recovered on the next cache access.
+///
+/// Each record is locked by the process that owns it until the operation completes. As the lock is
+/// released when that process exits, a record that can't be locked belongs to an operation that's
+/// still in flight, and is left alone by recovery.
+#[derive(Debug, Clone)]
+pub(crate) struct Journal {
+    /// The directory containing the journal records.
+    dir: PathBuf,
+}
+
+/// A single in-flight persist operation, as recorded in the [`Journal`].
+#[derive(Debug, Serialize, Deserialize)]
+struct Record {
+    /// The path to the entry in the archive bucket.
+    archive: PathBuf,
+    /// The path at which the archive is to be symlinked.
+    link: PathBuf,
+}
+
+/// A handle to a [`Record`] in the [`Journal`], to be committed once the persist completes.
+///
+/// The record remains locked for as long as the handle is held.
+#[derive(Debug)]
+#[must_use]
+pub(crate) struct PendingRecord(fs_err::File);
+
+impl Journal {
+    /// The name of the journal directory, relative to the cache root.
+    pub(crate) const DIR: &'static str = ".journal";
+
+    /// Return the [`Journal`] for the cache at `root`.
+    pub(crate) fn new(root: &Path) -> Self {
+        Self {
+            dir: root.join(Self::DIR),
+        }
+    }
+
+    /// Record the intent to move an entry to `archive` and symlink it at `link`.
+    pub(crate) fn begin(&self, archive: &Path, link: &Path) -> io::Result<PendingRecord> {
+        let record = Record {
+            archive: archive.to_path_buf(),
+            link: link.to_path_buf(),
+        };
+        let path = self.dir.join(format!("{}.msgpack", nanoid::nanoid!()));
+        fs_err::create_dir_all(&self.dir)?;
+
+        // Lock the record before it's moved into place, such that it's never visible to recovery
+        // while unlocked.
+        let mut temp_file = tempfile::NamedTempFile::new_in(&self.dir)?;
+        temp_file.as_file().lock_exclusive()?;
+        temp_file.write_all(&rmp_serde::to_vec(&record).map_err(io::Error::other)?)?;
+        let file = temp_file.persist(&path).map_err(|err| err.error)?;
+        Ok(PendingRecord(fs_err::File::from_parts(file, path)))
+    }
+
+    /// Recover any persist operations that were interrupted.
+    ///
+    /// Operations for which the archive exists, but the symlink doesn't, are rolled forward by
+    /// creating the symlink. Operations for which the archive doesn't exist never got past the
+    /// first phase, and are rolled back by discarding the record. Records that are locked by a
+    /// running process are skipped, as are records that fail to recover, which are left in place
+    /// to be retried.
+    pub(crate) fn recover(&self) -> io::Result<()> {
+        let entries = match fs_err::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(err),
+        };
+
+        for entry in entries {
+            let path = entry?.path();
+            if let Err(err) = Self::recover_record(&path) {
+                warn!(
+                    "Failed to recover interrupted persist at {}: {err}",
+                    path.display()
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Recover the persist operation for the [`Record`] at `path`, unless it's still in flight.
+    fn recover_record(path: &Path) -> io::Result<()> {
+        let mut file = match fs_err::File::open(path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(err),
+        };
+        if file.file().try_lock_exclusive().is_err() {
+            debug!("Skipping in-flight persist: {}", path.display());
+            return Ok(());
+        }
+
+        let mut data = Vec::new();
+        file.read_to_end(&mut data)?;
+        let record = match rmp_serde::from_slice::<Record>(&data) {
+            Ok(record) => record,
+            Err(err) => {
+                warn!(
+                    "Discarding broken journal record at {}: {err}",
+                    path.display()
+                );
+                return remove_record(path);
+            }
+        };
+
+        if record.archive.exists() && fs_err::symlink_metadata(&record.link).is_err() {
+            debug!(
+                "Rolling forward interrupted persist: {}",
+                record.link.display()
+            );
+            fs_err::create_dir_all(record.link.parent().expect("Cache entry to have parent"))?;
+            link_archive(&record.archive, &record.link)?;
+        } else if !record.archive.exists() {
+            debug!(
+                "Rolling back interrupted persist: {}",
+                record.link.display()
+            );
+        }
+
+        remove_record(path)
+    }
+}
+
+impl PendingRecord {
+    /// Mark the persist operation as complete, removing its record from the [`Journal`].
+    pub(crate) fn commit(self) -> io::Result<()> {
+        remove_record(self.0.path())
+    }
+}
+
+/// Remove a journal record, ignoring records that were already removed (e.g., by a concurrent
+/// recovery that ran before the record was locked).
+fn remove_record(path: &Path) -> io::Result<()> {
+    match fs_err::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use super::Journal;
+
+    /// Records that are still held by their owner are skipped, and recovered once released.
+    #[test]
+    fn recover_in_flight() -> io::Result<()> {
+        let root = tempfile::tempdir()?;
+        let journal = Journal::new(root.path());
+        let archive = root.path().join("archive-v0").join("entry");
+        let link = root.path().join("wheels-v0").join("entry");
+        fs_err::create_dir_all(&archive)?;
+
+        // The persist is in flight, so recovery leaves it alone.
+        let record = journal.begin(&archive, &link)?;
+        journal.recover()?;
+        assert!(fs_err::symlink_metadata(&link).is_err());
+
+        // Once the owner releases the record (e.g., by exiting), the persist is rolled forward.
+        drop(record);
+        journal.recover()?;
+        assert!(fs_err::symlink_metadata(&link).is_ok());
+        assert_eq!(fs_err::read_dir(root.path().join(Journal::DIR))?.count(), 0);
+
+        Ok(())
+    }
+}
@@ -5,92 +5,166 @@ use std::io::Write;
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::{Duration, SystemTime};
 
 use fs_err as fs;
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
 use tempfile::{tempdir, TempDir};
-use tracing::debug;
+use tracing::{debug, warn};
+use url::Url;
 
+use cache_key::{digest, CanonicalUrl};
 use distribution_types::InstalledDist;
 use pypi_types::Metadata23;
-use uv_fs::directories;
+use uv_fs::{directories, LockedFile, Simplified};
 use uv_normalize::PackageName;
 
+use crate::access::{AccessLog, AccessTracker};
+pub use crate::auto_prune::AutoPrune;
 pub use crate::by_timestamp::CachedByTimestamp;
 #[cfg(feature = "clap")]
 pub use crate::cli::CacheArgs;
-use crate::removal::{rm_rf, Removal};
+pub use crate::compression::{decompress, ArchiveCompression, ArchiveFormat};
+pub use crate::dedup::Deduplication;
+pub use crate::fs_mode::CacheFsMode;
+pub use crate::inspect::CacheEntryInfo;
+use crate::journal::Journal;
+pub use crate::metrics::CacheMetrics;
+pub use crate::migrate::Migrated;
+pub use crate::pins::Pins;
+use crate::removal::rm_rf;
+pub use crate::removal::{Removal, RemovalMode};
 pub use crate::timestamp::Timestamp;
-pub use crate::wheel::WheelCache;
-use crate::wheel::WheelCacheKind;
+pub use crate::wheel::{WheelCache, WheelCacheKind};
 
+mod access;
+mod auto_prune;
 mod by_timestamp;
 #[cfg(feature = "clap")]
 mod cli;
+mod compression;
+mod dedup;
+mod fs_mode;
+mod inspect;
+mod journal;
+mod link;
+mod metrics;
+mod migrate;
+mod pins;
 mod removal;
 mod timestamp;
 mod wheel;
 
 /// A [`CacheEntry`] which may or may not exist yet.
 #[derive(Debug, Clone)]
-pub struct CacheEntry(PathBuf);
+pub struct CacheEntry {
+    /// The path to the entry in the primary cache, to which all writes are directed.
+    path: PathBuf,
+    /// The path to the same entry in the fallback cache, if any.
+    fallback: Option<PathBuf>,
+}
 
 impl CacheEntry {
     /// Create a new [`CacheEntry`] from a directory and a file name.
     pub fn new(dir: impl Into<PathBuf>, file: impl AsRef<Path>) -> Self {
-        Self(dir.into().join(file))
+        Self {
+            path: dir.into().join(file),
+            fallback: None,
+        }
     }
 
     /// Create a new [`CacheEntry`] from a path.
     pub fn from_path(path: impl Into<PathBuf>) -> Self {
-        Self(path.into())
+        Self {
+            path: path.into(),
+            fallback: None,
+        }
     }
 
     /// Convert the [`CacheEntry`] into a [`PathBuf`].
     #[inline]
     pub fn into_path_buf(self) -> PathBuf {
-        self.0
+        self.path
     }
 
     /// Return the path to the [`CacheEntry`].
     #[inline]
     pub fn path(&self) -> &Path {
-        &self.0
+        &self.path
+    }
+
+    /// Return the path from which the [`CacheEntry`] should be read.
+    ///
+    /// If the entry doesn't exist in the primary cache, but does exist in the fallback cache,
+    /// returns the path to the fallback entry. Otherwise, returns [`CacheEntry::path`].
+    pub fn read_path(&self) -> &Path {
+        match &self.fallback {
+            Some(fallback) if !self.path.exists() && fallback.exists() => fallback,
+            _ => &self.path,
+        }
     }
 
     /// Return the cache entry's parent directory.
     #[inline]
     pub fn dir(&self) -> &Path {
-        self.0.parent().expect("Cache entry has no parent")
+        self.path.parent().expect("Cache entry has no parent")
     }
 
     /// Create a new [`CacheEntry`] with the given file name.
     #[must_use]
     pub fn with_file(&self, file: impl AsRef<Path>) -> Self {
-        Self(self.dir().join(file))
+        let file = file.as_ref();
+        Self {
+            path: self.dir().join(file),
+            fallback: self.fallback.as_ref().map(|fallback| {
+                fallback
+                    .parent()
+                    .expect("Cache entry has no parent")
+                    .join(file)
+            }),
+        }
     }
 }
 
 /// A subdirectory within the cache.
 #[derive(Debug, Clone)]
-pub struct CacheShard(PathBuf);
+pub struct CacheShard {
+    /// The path to the shard in the primary cache.
+    path: PathBuf,
+    /// The path to the same shard in the fallback cache, if any.
+    fallback: Option<PathBuf>,
+}
 
 impl CacheShard {
     /// Return a [`CacheEntry`] within this shard.
     pub fn entry(&self, file: impl AsRef<Path>) -> CacheEntry {
-        CacheEntry::new(&self.0, file)
+        let file = file.as_ref();
+        CacheEntry {
+            path: self.path.join(file),
+            fallback: self.fallback.as_ref().map(|fallback| fallback.join(file)),
+        }
     }
 
     /// Return a [`CacheShard`] within this shard.
     #[must_use]
     pub fn shard(&self, dir: impl AsRef<Path>) -> Self {
-        Self(self.0.join(dir.as_ref()))
+        let dir = dir.as_ref();
+        Self {
+            path: self.path.join(dir),
+            fallback: self.fallback.as_ref().map(|fallback| fallback.join(dir)),
+        }
+    }
+
+    /// Return the directories from which the [`CacheShard`] should be read: the shard in the
+    /// primary cache, followed by the same shard in the fallback cache, if any.
+    pub fn read_paths(&self) -> impl Iterator<Item = &Path> {
+        std::iter::once(self.path.as_path()).chain(self.fallback.as_deref())
     }
 }
 
 impl AsRef<Path> for CacheShard {
     fn as_ref(&self) -> &Path {
-        &self.0
+        &self.path
     }
 }
 
@@ -98,7 +172,7 @@ impl Deref for CacheShard {
     type Target = Path;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.path
     }
 }
 
@@ -107,22 +181,74 @@ impl Deref for CacheShard {
 pub struct Cache {
     /// The cache directory.
     root: PathBuf,
+    /// A cache directory to consult for entries that are missing from `root` (e.g., the global
+    /// cache, when layering a project-local cache over it). Entries are never written to the
+    /// fallback.
+    fallback: Option<PathBuf>,
     /// The refresh strategy to use when reading from the cache.
     refresh: Refresh,
+    /// The compression to apply to new entries in the archive bucket.
+    compression: ArchiveCompression,
+    /// The strategy used to persist entries to the cache directory.
+    fs_mode: CacheFsMode,
+    /// The policy for automatically pruning the cache.
+    auto_prune: AutoPrune,
+    /// Whether to strictly follow the HTTP caching semantics of RFC 9111 for cached responses.
+    strict_http: bool,
+    /// The shards accessed by the current process.
+    accesses: Arc<AccessTracker>,
     /// A temporary cache directory, if the user requested `--no-cache`.
     ///
     /// Included to ensure that the temporary directory exists for the length of the operation, but
     /// is dropped at the end as appropriate.
-    _temp_dir_drop: Option<Arc<TempDir>>,
+    temp_dir_drop: Option<Arc<TempDir>>,
 }
 
 impl Cache {
+    /// The name of the file used to lock the cache, relative to the cache root.
+    const LOCK: &'static str = ".lock";
+
     /// A persistent cache directory at `root`.
     pub fn from_path(root: impl Into<PathBuf>) -> Result<Self, io::Error> {
+        let root = Self::init(root)?;
+
+        // Recover any persist operations that were interrupted in a previous invocation. Failures
+        // are non-fatal, since the records are left in place to be retried.
+        if let Err(err) = Journal::new(&root).recover() {
+            warn!(
+                "Failed to recover interrupted cache operations at {}: {err}",
+                root.user_display()
+            );
+        }
+
+        // Carry over entries from any outdated buckets with a registered migration. Failures are
+        // non-fatal, since the outdated buckets are otherwise left in place for `uv cache prune`.
+        if migrate::is_pending(&root) {
+            let result =
+                LockedFile::acquire(root.join(Self::LOCK), root.user_display()).and_then(|lock| {
+                    let migrated = migrate::migrate(&root);
+                    drop(lock);
+                    migrated
+                });
+            match result {
+                Ok(migrated) => debug!(
+                    "Migrated {} cache entries ({} discarded)",
+                    migrated.num_entries, migrated.num_discarded
+                ),
+                Err(err) => warn!("Failed to migrate cache at {}: {err}", root.user_display()),
+            }
+        }
+
         Ok(Self {
-            root: Self::init(root)?,
+            root,
+            fallback: None,
             refresh: Refresh::None,
-            _temp_dir_drop: None,
+            compression: ArchiveCompression::default(),
+            fs_mode: CacheFsMode::Local,
+            auto_prune: AutoPrune::default(),
+            strict_http: false,
+            accesses: Arc::default(),
+            temp_dir_drop: None,
         })
     }
 
@@ -131,17 +257,93 @@ impl Cache {
         let temp_dir = tempdir()?;
         Ok(Self {
             root: Self::init(temp_dir.path())?,
+            fallback: None,
             refresh: Refresh::None,
-            _temp_dir_drop: Some(Arc::new(temp_dir)),
+            compression: ArchiveCompression::default(),
+            fs_mode: CacheFsMode::Local,
+            auto_prune: AutoPrune::default(),
+            strict_http: false,
+            accesses: Arc::default(),
+            temp_dir_drop: Some(Arc::new(temp_dir)),
         })
     }
 
+    /// Create a temporary cache directory, layered over the cache directory at `base`.
+    ///
+    /// Entries missing from the temporary cache are read from `base`, but all writes are directed
+    /// to the temporary cache, which is discarded on drop, such that `base` is never modified.
+    /// Unlike [`Cache::from_path`], `base` is neither initialized nor recovered from interrupted
+    /// operations; if it doesn't exist, the temporary cache is used on its own.
+    pub fn temp_overlay(base: impl Into<PathBuf>) -> Result<Self, io::Error> {
+        Self::temp()?.with_fallback(base)
+    }
+
     /// Set the [`Refresh`] policy for the cache.
     #[must_use]
     pub fn with_refresh(self, refresh: Refresh) -> Self {
         Self { refresh, ..self }
     }
 
+    /// Layer the cache over the cache at `fallback`, such that entries missing from this cache are
+    /// read from the fallback. Writes are always directed to this cache.
+    pub fn with_fallback(self, fallback: impl Into<PathBuf>) -> Result<Self, io::Error> {
+        let fallback = match fs::canonicalize(fallback.into()) {
+            Ok(fallback) => fallback,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(self),
+            Err(err) => return Err(err),
+        };
+        if fallback == self.root {
+            return Ok(self);
+        }
+        Ok(Self {
+            fallback: Some(fallback),
+            ..self
+        })
+    }
+
+    /// Set the [`ArchiveCompression`] to use for new entries in the archive bucket.
+    #[must_use]
+    pub fn with_compression(self, compression: ArchiveCompression) -> Self {
+        Self {
+            compression,
+            ..self
+        }
+    }
+
+    /// Set the [`CacheFsMode`] to use when persisting entries, resolving [`CacheFsMode::Auto`]
+    /// based on the filesystem of the cache directory.
+    #[must_use]
+    pub fn with_fs_mode(self, fs_mode: CacheFsMode) -> Self {
+        let fs_mode = fs_mode.resolve(&self.root);
+        Self { fs_mode, ..self }
+    }
+
+    /// Set the [`AutoPrune`] policy for the cache.
+    #[must_use]
+    pub fn with_auto_prune(self, auto_prune: AutoPrune) -> Self {
+        Self { auto_prune, ..self }
+    }
+
+    /// Strictly follow the HTTP caching semantics of RFC 9111 (e.g., never reusing a stale
+    /// `must-revalidate` response, even when offline).
+    #[must_use]
+    pub fn with_strict_http(self, strict_http: bool) -> Self {
+        Self {
+            strict_http,
+            ..self
+        }
+    }
+
+    /// Returns `true` if the HTTP caching semantics of RFC 9111 should be strictly followed.
+    pub fn strict_http(&self) -> bool {
+        self.strict_http
+    }
+
+    /// Return the [`Refresh`] policy for the cache.
+    pub fn refresh(&self) -> &Refresh {
+        &self.refresh
+    }
+
     /// Return the root of the cache.
     pub fn root(&self) -> &Path {
         &self.root
@@ -152,9 +354,34 @@ impl Cache {
         self.root.join(cache_bucket.to_str())
     }
 
+    /// Return the fallback cache directory, if any.
+    pub fn fallback(&self) -> Option<&Path> {
+        self.fallback.as_deref()
+    }
+
     /// Compute an entry in the cache.
     pub fn shard(&self, cache_bucket: CacheBucket, dir: impl AsRef<Path>) -> CacheShard {
-        CacheShard(self.bucket(cache_bucket).join(dir.as_ref()))
+        let dir = dir.as_ref();
+        self.accesses.record(cache_bucket, dir);
+        CacheShard {
+            path: self.bucket(cache_bucket).join(dir),
+            fallback: self
+                .fallback
+                .as_ref()
+                .map(|fallback| fallback.join(cache_bucket.to_str()).join(dir)),
+        }
+    }
+
+    /// Return an iterator over the entries in the given bucket, along with a description of each
+    /// (e.g., the package name and version, the source kind, its size, and its timestamps).
+    ///
+    /// Entries are enumerated according to the layout documented on [`CacheBucket`], such that
+    /// callers needn't traverse the cache directory themselves.
+    pub fn entries(
+        &self,
+        bucket: CacheBucket,
+    ) -> impl Iterator<Item = Result<CacheEntryInfo, io::Error>> {
+        inspect::entries(self, bucket)
     }
 
     /// Compute an entry in the cache.
@@ -164,15 +391,29 @@ impl Cache {
         dir: impl AsRef<Path>,
         file: impl AsRef<Path>,
     ) -> CacheEntry {
-        CacheEntry::new(self.bucket(cache_bucket).join(dir), file)
+        self.shard(cache_bucket, dir).entry(file)
     }
 
     /// Returns `true` if a cache entry must be revalidated given the [`Refresh`] policy.
     pub fn must_revalidate(&self, package: &PackageName) -> bool {
         match &self.refresh {
             Refresh::None => false,
-            Refresh::All(_) => true,
-            Refresh::Packages(packages, _) => packages.contains(package),
+            Refresh::All(_) | Refresh::Revalidate(..) => true,
+            Refresh::Packages(packages, ..) => packages.contains(package),
+        }
+    }
+
+    /// Returns `true` if a cache entry for a direct URL dependency must be revalidated given the
+    /// [`Refresh`] policy.
+    pub fn must_revalidate_url(&self, url: &Url) -> bool {
+        match &self.refresh {
+            Refresh::None => false,
+            Refresh::All(_) | Refresh::Revalidate(..) => true,
+            Refresh::Packages(_, urls, _) => {
+                let url = CanonicalUrl::new(url);
+                urls.iter()
+                    .any(|candidate| CanonicalUrl::new(candidate) == url)
+            }
         }
     }
 
@@ -182,25 +423,38 @@ impl Cache {
         entry: &CacheEntry,
         package: Option<&PackageName>,
     ) -> io::Result<Freshness> {
-        // Grab the cutoff timestamp, if it's relevant.
-        let timestamp = match &self.refresh {
+        // Grab the cutoff timestamp, if it's relevant, along with the freshness to report for
+        // entries created before the cutoff.
+        let (timestamp, outdated) = match &self.refresh {
             Refresh::None => return Ok(Freshness::Fresh),
-            Refresh::All(timestamp) => timestamp,
-            Refresh::Packages(packages, timestamp) => {
-                if package.map_or(true, |package| packages.contains(package)) {
-                    timestamp
+            Refresh::All(timestamp) => (timestamp, Freshness::Stale),
+            Refresh::Revalidate(packages, urls, timestamp) => {
+                if package.is_some_and(|package| packages.contains(package))
+                    || urls.iter().any(|url| self.is_url_entry(entry, url))
+                {
+                    (timestamp, Freshness::Stale)
+                } else {
+                    (timestamp, Freshness::Revalidate)
+                }
+            }
+            Refresh::Packages(packages, urls, timestamp) => {
+                if (!packages.is_empty()
+                    && package.map_or(true, |package| packages.contains(package)))
+                    || urls.iter().any(|url| self.is_url_entry(entry, url))
+                {
+                    (timestamp, Freshness::Stale)
                 } else {
                     return Ok(Freshness::Fresh);
                 }
             }
         };
 
-        match fs::metadata(entry.path()) {
+        match fs::metadata(entry.read_path()) {
             Ok(metadata) => {
                 if Timestamp::from_metadata(&metadata) >= *timestamp {
                     Ok(Freshness::Fresh)
                 } else {
-                    Ok(Freshness::Stale)
+                    Ok(outdated)
                 }
             }
             Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Freshness::Missing),
@@ -208,26 +462,84 @@ impl Cache {
         }
     }
 
+    /// Returns `true` if the [`CacheEntry`] is stored under one of the shards for the given direct
+    /// URL (i.e., the [`WheelCacheKind::Url`] or [`WheelCacheKind::Git`] shards in the
+    /// [`CacheBucket::Wheels`] and [`CacheBucket::BuiltWheels`] buckets).
+    ///
+    /// Git entries are matched on the URL without its reference, such that refreshing any
+    /// reference to a repository (e.g., a branch) refreshes the wheels built from every commit.
+    fn is_url_entry(&self, entry: &CacheEntry, url: &Url) -> bool {
+        let digest = digest(&CanonicalUrl::new(url));
+        let shards = [
+            self.bucket(CacheBucket::Wheels)
+                .join(WheelCacheKind::Url)
+                .join(&digest),
+            self.bucket(CacheBucket::BuiltWheels)
+                .join(WheelCacheKind::Url)
+                .join(&digest),
+            self.bucket(CacheBucket::BuiltWheels)
+                .join(WheelCacheKind::Git)
+                .join(cache_key::digest(&CanonicalUrl::new(
+                    &wheel::without_git_reference(url),
+                ))),
+        ];
+        shards.iter().any(|shard| entry.path().starts_with(shard))
+    }
+
     /// Persist a temporary directory to the artifact store.
     pub async fn persist(
         &self,
         temp_dir: impl AsRef<Path>,
         path: impl AsRef<Path>,
     ) -> io::Result<PathBuf> {
+        // On network filesystems, symlinks and atomic renames are unreliable, so copy the entry
+        // into place under a lock instead.
+        if self.fs_mode == CacheFsMode::Network {
+            return fs_mode::persist_copy(temp_dir.as_ref(), path.as_ref());
+        }
+
         // Create a unique ID for the artifact.
         // TODO(charlie): Support content-addressed persistence via SHAs.
         let id = nanoid::nanoid!();
 
-        // Move the temporary directory into the directory store.
-        let archive_entry = self.entry(CacheBucket::Archive, "", id);
+        let archive_entry = match self.compression {
+            ArchiveCompression::None => self.entry(CacheBucket::Archive, "", id),
+            ArchiveCompression::Zstd => self.entry(
+                CacheBucket::Archive,
+                "",
+                format!("{id}.{}", ArchiveFormat::TAR_ZSTD_EXTENSION),
+            ),
+        };
+
+        // Record the operation in the journal, such that it can be recovered if interrupted
+        // before the symlink is created.
+        let record = Journal::new(&self.root).begin(archive_entry.path(), path.as_ref())?;
+
         fs_err::create_dir_all(archive_entry.dir())?;
-        uv_fs::rename_with_retry(temp_dir.as_ref(), archive_entry.path()).await?;
+        match self.compression {
+            ArchiveCompression::None => {
+                // Move the temporary directory into the directory store.
+                uv_fs::rename_with_retry(temp_dir.as_ref(), archive_entry.path()).await?;
+            }
+            ArchiveCompression::Zstd => {
+                // Compress the temporary directory into the directory store.
+                let source = temp_dir.as_ref().to_path_buf();
+                let target = archive_entry.path().to_path_buf();
+                tokio::task::spawn_blocking(move || compression::compress(&source, &target))
+                    .await
+                    .map_err(io::Error::other)??;
+                rm_rf(temp_dir.as_ref())?;
+            }
+        }
 
-        // Create a symlink to the directory store.
+        // Link the entry into place, falling back to a hard link or copy where symlinks aren't
+        // available.
         fs_err::create_dir_all(path.as_ref().parent().expect("Cache entry to have parent"))?;
-        uv_fs::replace_symlink(archive_entry.path(), path.as_ref())?;
+        let entry = link::link_archive(archive_entry.path(), path.as_ref())?;
+
+        record.commit()?;
 
-        Ok(archive_entry.into_path_buf())
+        Ok(entry)
     }
 
     /// Initialize a directory for use as a cache.
@@ -279,24 +591,95 @@ impl Cache {
         fs::canonicalize(root)
     }
 
-    /// Clear the cache, removing all entries.
-    pub fn clear(&self) -> Result<Removal, io::Error> {
-        rm_rf(&self.root)
+    /// Clear the cache, removing all entries except those for pinned packages.
+    ///
+    /// If no packages are pinned, the cache directory itself is removed.
+    pub fn clear(&self, mode: RemovalMode) -> Result<Removal, io::Error> {
+        let pins = self.pins()?;
+        if pins.is_empty() {
+            return self.clear_all(mode);
+        }
+
+        // Protect the entries for each pinned package, along with any archives they link to.
+        let mut protected = FxHashSet::default();
+        for name in pins.iter() {
+            for bucket in CacheBucket::iter() {
+                for path in bucket.package_entries(self, name) {
+                    // Skip buckets that can't be partitioned by package.
+                    if path == self.bucket(bucket) || !path.exists() {
+                        continue;
+                    }
+                    for entry in walkdir::WalkDir::new(&path) {
+                        let entry = entry?;
+                        if entry.file_type().is_symlink() {
+                            protected.insert(entry.path().canonicalize()?);
+                        }
+                    }
+                    protected.insert(path);
+                }
+            }
+        }
+        for marker in ["CACHEDIR.TAG", ".gitignore", Self::LOCK, Pins::FILE] {
+            protected.insert(self.root.join(marker));
+        }
+
+        debug!(
+            "Preserving cache entries for pinned packages: {}",
+            pins.iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        clear_except(&self.root, &protected, mode)
+    }
+
+    /// Clear the cache, removing all entries, including those for pinned packages.
+    pub fn clear_all(&self, mode: RemovalMode) -> Result<Removal, io::Error> {
+        mode.rm_rf(&self.root)
+    }
+
+    /// Return the set of packages pinned in the cache.
+    pub fn pins(&self) -> Result<Pins, io::Error> {
+        Pins::read(&self.root)
+    }
+
+    /// Pin a package in the cache, protecting its entries against eviction.
+    ///
+    /// Returns `false` if the package was already pinned.
+    pub fn pin(&self, name: PackageName) -> Result<bool, io::Error> {
+        let mut pins = self.pins()?;
+        let inserted = pins.insert(name);
+        if inserted {
+            pins.write(&self.root)?;
+        }
+        Ok(inserted)
+    }
+
+    /// Unpin a package in the cache.
+    ///
+    /// Returns `false` if the package wasn't pinned.
+    pub fn unpin(&self, name: &PackageName) -> Result<bool, io::Error> {
+        let mut pins = self.pins()?;
+        let removed = pins.remove(name);
+        if removed {
+            pins.write(&self.root)?;
+        }
+        Ok(removed)
     }
 
     /// Remove a package from the cache.
     ///
     /// Returns the number of entries removed from the cache.
-    pub fn remove(&self, name: &PackageName) -> Result<Removal, io::Error> {
+    pub fn remove(&self, name: &PackageName, mode: RemovalMode) -> Result<Removal, io::Error> {
         let mut summary = Removal::default();
         for bucket in CacheBucket::iter() {
-            summary += bucket.remove(self, name)?;
+            summary += bucket.remove(self, name, mode)?;
         }
         Ok(summary)
     }
 
     /// Run the garbage collector on the cache, removing any dangling entries.
-    pub fn prune(&self) -> Result<Removal, io::Error> {
+    pub fn prune(&self, mode: RemovalMode) -> Result<Removal, io::Error> {
         let mut summary = Removal::default();
 
         // First, remove any top-level directories that are unused. These typically represent
@@ -308,6 +691,11 @@ impl Cache {
             if entry.file_name() == "CACHEDIR.TAG"
                 || entry.file_name() == ".gitignore"
                 || entry.file_name() == ".git"
+                || entry.file_name() == Journal::DIR
+                || entry.file_name() == AutoPrune::STATE
+                || entry.file_name() == Self::LOCK
+                || entry.file_name() == Pins::FILE
+                || entry.file_name() == AccessLog::DIR
             {
                 continue;
             }
@@ -317,46 +705,199 @@ impl Cache {
                 if CacheBucket::iter().all(|bucket| entry.file_name() != bucket.to_str()) {
                     let path = entry.path();
                     debug!("Removing dangling cache entry: {}", path.display());
-                    summary += rm_rf(path)?;
+                    summary += mode.rm_rf(path)?;
                 }
             } else {
                 // If the file is not a marker file, remove it.
                 let path = entry.path();
                 debug!("Removing dangling cache entry: {}", path.display());
-                summary += rm_rf(path)?;
+                summary += mode.rm_rf(path)?;
             }
         }
 
         // Second, remove any unused archives (by searching for archives that are not symlinked).
         // TODO(charlie): Remove any unused source distributions. This requires introspecting the
         // cache contents, e.g., reading and deserializing the manifests.
+        for path in self.dangling_archives()? {
+            debug!("Removing dangling cache entry: {}", path.display());
+            summary += mode.rm_rf(path)?;
+        }
+
+        Ok(summary)
+    }
+
+    /// Remove any registry packages that haven't been used within the given duration, as recorded
+    /// in the access log. Pinned packages are never removed.
+    pub fn prune_unused(
+        &self,
+        unused_for: Duration,
+        mode: RemovalMode,
+    ) -> Result<Removal, io::Error> {
+        let cutoff = SystemTime::now()
+            .checked_sub(unused_for)
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        let pins = self.pins()?;
+
+        let mut summary = Removal::default();
+        for (name, last_used) in access::packages_by_recency(self) {
+            if last_used >= cutoff {
+                break;
+            }
+            if pins.contains(&name) {
+                continue;
+            }
+
+            // Remove the package's entries, along with the archives they link to.
+            debug!("Removing unused package from the cache: {name}");
+            let archives = auto_prune::package_archives(self, &name);
+            summary += self.remove(&name, mode)?;
+            for archive in archives {
+                summary += mode.rm_rf(archive)?;
+            }
+        }
+        Ok(summary)
+    }
+
+    /// Record the shards accessed by the current process in the cache's access log.
+    pub fn flush_access_log(&self) -> Result<(), io::Error> {
+        if !self.root.exists() {
+            return Ok(());
+        }
+        self.accesses.flush(&self.root)
+    }
+
+    /// Count the current command against the configured [`AutoPrune`] policy, returning `true` if
+    /// a collection is due.
+    ///
+    /// Temporary caches are never collected.
+    pub fn auto_prune_due(&self) -> Result<bool, io::Error> {
+        if self.temp_dir_drop.is_some() || !self.root.exists() {
+            return Ok(false);
+        }
+        self.auto_prune.is_due(self)
+    }
+
+    /// Run a bounded, incremental collection on the cache under the configured [`AutoPrune`]
+    /// policy, holding the cache lock for its duration.
+    pub fn auto_prune(&self) -> Result<Removal, io::Error> {
+        if !self.auto_prune.is_enabled() || !self.root.exists() {
+            return Ok(Removal::default());
+        }
+        let _lock = self.lock()?;
+        self.auto_prune.run(self)
+    }
+
+    /// Return the configured [`AutoPrune`] policy.
+    pub fn auto_prune_policy(&self) -> AutoPrune {
+        self.auto_prune
+    }
+
+    /// Acquire an exclusive lock on the cache, to serialize operations that remove entries from
+    /// it (e.g., `uv cache clean` and `uv cache prune`).
+    pub fn lock(&self) -> Result<LockedFile, io::Error> {
+        LockedFile::acquire(self.root.join(Self::LOCK), self.root.user_display())
+    }
+
+    /// Return the entries in the archive bucket that aren't symlinked from any other bucket.
+    fn dangling_archives(&self) -> Result<Vec<PathBuf>, io::Error> {
         let mut references = FxHashSet::default();
 
+        // Compressed archives may be hard-linked into place, rather than symlinked, in which case
+        // they can only be identified by file identity. To avoid comparing against every file in
+        // the cache, index the archive files by size.
+        let mut archive_files: FxHashMap<u64, Vec<PathBuf>> = FxHashMap::default();
+        for entry in fs::read_dir(self.bucket(CacheBucket::Archive))? {
+            let entry = entry?;
+            let metadata = entry.metadata()?;
+            if metadata.is_file() {
+                archive_files
+                    .entry(metadata.len())
+                    .or_default()
+                    .push(entry.path().canonicalize()?);
+            }
+        }
+
         for bucket in CacheBucket::iter() {
+            if bucket == CacheBucket::Archive {
+                continue;
+            }
             let bucket = self.bucket(bucket);
             if bucket.is_dir() {
                 for entry in walkdir::WalkDir::new(bucket) {
                     let entry = entry?;
+                    // Note: on Windows, junctions are reported as symlinks.
                     if entry.file_type().is_symlink() {
                         references.insert(entry.path().canonicalize()?);
+                    } else if entry.file_type().is_file() && !archive_files.is_empty() {
+                        let Some(candidates) = archive_files.get(&entry.metadata()?.len()) else {
+                            continue;
+                        };
+                        for candidate in candidates {
+                            if same_file::is_same_file(candidate, entry.path())? {
+                                references.insert(candidate.clone());
+                            }
+                        }
                     }
                 }
             }
         }
 
+        let mut dangling = Vec::new();
         for entry in fs::read_dir(self.bucket(CacheBucket::Archive))? {
             let entry = entry?;
             let path = entry.path().canonicalize()?;
             if !references.contains(&path) {
-                debug!("Removing dangling cache entry: {}", path.display());
-                summary += rm_rf(path)?;
+                dangling.push(path);
             }
         }
 
-        Ok(summary)
+        Ok(dangling)
+    }
+
+    /// Migrate entries from outdated cache buckets into their current versions, for any buckets
+    /// with a registered forward-compatible migration.
+    ///
+    /// Outdated buckets without a registered migration are left in place, to be removed by
+    /// [`Cache::prune`].
+    pub fn migrate(&self) -> Result<Migrated, io::Error> {
+        let _lock = self.lock()?;
+        migrate::migrate(&self.root)
+    }
+
+    /// Deduplicate identical files across entries in the archive bucket, replacing duplicates with
+    /// hard links (or reflinks) to a single copy, holding the cache lock for its duration.
+    pub fn dedup(&self) -> Result<Deduplication, io::Error> {
+        let archive = self.bucket(CacheBucket::Archive);
+        if !archive.is_dir() {
+            return Ok(Deduplication::default());
+        }
+        let _lock = self.lock()?;
+        dedup::dedup(archive, &self.root)
     }
 }
 
+/// Recursively remove the contents of `dir`, preserving the `protected` paths and their ancestors.
+fn clear_except(
+    dir: &Path,
+    protected: &FxHashSet<PathBuf>,
+    mode: RemovalMode,
+) -> Result<Removal, io::Error> {
+    let mut summary = Removal::default();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if protected.contains(&path) {
+            continue;
+        }
+        if entry.file_type()?.is_dir() && protected.iter().any(|p| p.starts_with(&path)) {
+            summary += clear_except(&path, protected, mode)?;
+        } else {
+            summary += mode.rm_rf(path)?;
+        }
+    }
+    Ok(summary)
+}
+
 /// The different kinds of data in the cache are stored in different bucket, which in our case
 /// are subdirectories of the cache root.
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
@@ -481,6 +1022,10 @@ pub enum CacheBucket {
     ///  * `built-wheels-v0/<digest(index-url)>/foo/foo-1.0.0.zip/{manifest.msgpack, metadata.msgpack, foo-1.0.0-py3-none-any.whl, ...other wheels}`
     ///  * `built-wheels-v0/url/<digest(url)>/foo/foo-1.0.0.zip/{manifest.msgpack, metadata.msgpack, foo-1.0.0-py3-none-any.whl, ...other wheels}`
     ///  * `built-wheels-v0/git/<digest(url)>/<git sha>/foo/foo-1.0.0.zip/{metadata.msgpack, foo-1.0.0-py3-none-any.whl, ...other wheels}`
+    ///  * `built-wheels-v0/editable/<digest(url)>/manifest.msgpack`, alongside a
+    ///    `<revision>/{metadata.msgpack, foo-1.0.0-py3-none-any.whl}` directory per build of the
+    ///    editable project (as for local source trees, keyed on the [`ArchiveTimestamp`] of every
+    ///    file in the project).
     ///
     /// But the url filename does not need to be a valid source dist filename
     /// (<https://github.com/search?q=path%3A**%2Frequirements.txt+master.zip&type=code>),
@@ -527,12 +1072,17 @@ pub enum CacheBucket {
     /// needed to invalidate the cache. The `metadata.msgpack` contains the metadata of the source
     /// distribution.
     BuiltWheels,
-    /// Flat index responses, a format very similar to the simple metadata API.
+    /// Flat index responses, a format very similar to the simple metadata API, along with scans of
+    /// `--find-links` directories.
     ///
     /// Cache structure:
-    ///  * `flat-index-v0/index/<digest(flat_index_url)>.msgpack`
+    ///  * `flat-index-v1/html/<digest(flat_index_url)>.msgpack`
+    ///  * `flat-index-v1/directory/<digest(flat_index_path)>.msgpack`
     ///
-    /// The response is stored as `Vec<File>`.
+    /// Each response is stored as the list of files linked from the page, along with the URLs of
+    /// any pages nested beneath it. Each directory scan is stored as the list of files found in the
+    /// directory and its nested directories, along with the timestamp of every scanned directory,
+    /// such that the scan is invalidated if any of the directories change.
     FlatIndex,
     /// Git repositories.
     Git,
@@ -542,7 +1092,7 @@ pub enum CacheBucket {
     /// without the shim itself changing, we only cache when the path equals `sys.executable`, i.e.
     /// the path we're running is the python executable itself and not a shim.
     ///
-    /// Cache structure: `interpreter-v0/<digest(path)>.msgpack`
+    /// Cache structure: `interpreter-v3/<digest(path)>.msgpack`
     ///
     /// # Example
     ///
@@ -581,8 +1131,40 @@ pub enum CacheBucket {
     ///  * `simple-v0/pypi/<package_name>.rkyv`
     ///  * `simple-v0/<digest(index_url)>/<package_name>.rkyv`
     ///
-    /// The response is parsed into `uv_client::SimpleMetadata` before storage.
+    /// The response is parsed into `uv_client::SimpleMetadata` before storage, alongside the HTTP
+    /// cache policy of the response (including its `ETag` and `Last-Modified` validators). When an
+    /// entry must be revalidated (e.g., with `--refresh`), those validators are sent back to the
+    /// index as `If-None-Match` and `If-Modified-Since`, such that an unchanged page costs a
+    /// `304 Not Modified` rather than a full download.
+    ///
+    /// If the index reports that a package doesn't exist, an empty negative cache entry is stored
+    /// in its place (e.g., `simple-v7/pypi/<package_name>.missing`), which is honored for a short
+    /// period to avoid re-querying the index on every resolution.
     Simple,
+    /// Wheel and source distribution metadata served by an index via [PEP 658], stored separately
+    /// from the distributions themselves.
+    ///
+    /// Resolution only requires the metadata, so `pip compile` can populate this bucket with small
+    /// `.metadata` downloads; the wheels are fetched into [`CacheBucket::Wheels`] later, once
+    /// they're installed. Entries are keyed like those in [`CacheBucket::Wheels`].
+    ///
+    /// Cache structure:
+    ///  * `wheel-metadata-v0/pypi/foo/foo-1.0.0-py3-none-any.msgpack`
+    ///  * `wheel-metadata-v0/index/<digest(index-url)>/foo/foo-1.0.0-py3-none-any.msgpack`
+    ///  * `wheel-metadata-v0/pypi/foo/foo-1.0.0.tar.gz.msgpack`
+    ///
+    /// [PEP 658]: https://peps.python.org/pep-0658/
+    WheelMetadata,
+    /// The output of previous `pip compile` invocations.
+    ///
+    /// Cache structure: `resolutions-v1/<digest(inputs)>.msgpack`
+    ///
+    /// The inputs include the requirements, constraints, overrides, and preferences, along with
+    /// the target markers, index configuration, resolver options, and the files in any local
+    /// `--find-links` directories. Resolutions are only cached when bounded by `--exclude-newer`;
+    /// those involving editables, source trees, direct URL requirements, or remote `--find-links`
+    /// locations are never cached, as their contents may change without any change to the inputs.
+    Resolutions,
     /// A cache of unzipped wheels, stored as directories. This is used internally within the cache.
     /// When other buckets need to store directories, they should persist them to
     /// [`CacheBucket::Archive`], and then symlink them into the appropriate bucket. This ensures
@@ -595,11 +1177,13 @@ impl CacheBucket {
     fn to_str(self) -> &'static str {
         match self {
             Self::BuiltWheels => "built-wheels-v2",
-            Self::FlatIndex => "flat-index-v0",
+            Self::FlatIndex => "flat-index-v1",
             Self::Git => "git-v0",
-            Self::Interpreter => "interpreter-v0",
-            Self::Simple => "simple-v6",
+            Self::Interpreter => "interpreter-v3",
+            Self::Simple => "simple-v7",
+            Self::Resolutions => "resolutions-v1",
             Self::Wheels => "wheels-v0",
+            Self::WheelMetadata => "wheel-metadata-v0",
             Self::Archive => "archive-v0",
         }
     }
@@ -607,7 +1191,24 @@ impl CacheBucket {
     /// Remove a package from the cache bucket.
     ///
     /// Returns the number of entries removed from the cache.
-    fn remove(self, cache: &Cache, name: &PackageName) -> Result<Removal, io::Error> {
+    fn remove(
+        self,
+        cache: &Cache,
+        name: &PackageName,
+        mode: RemovalMode,
+    ) -> Result<Removal, io::Error> {
+        let mut summary = Removal::default();
+        for path in self.package_entries(cache, name) {
+            summary += mode.rm_rf(path)?;
+        }
+        Ok(summary)
+    }
+
+    /// Return the paths to the entries for a package in the cache bucket, some of which may not
+    /// exist.
+    ///
+    /// For buckets that can't be partitioned by package, returns the entire bucket.
+    fn package_entries(self, cache: &Cache, name: &PackageName) -> Vec<PathBuf> {
         /// Returns `true` if the [`Path`] represents a built wheel for the given package.
         fn is_match(path: &Path, name: &PackageName) -> bool {
             let Ok(metadata) = fs_err::read(path.join("metadata.msgpack")) else {
@@ -619,37 +1220,37 @@ impl CacheBucket {
             metadata.name == *name
         }
 
-        let mut summary = Removal::default();
+        let mut entries = Vec::new();
         match self {
-            Self::Wheels => {
+            Self::Wheels | Self::WheelMetadata => {
                 // For `pypi` wheels, we expect a directory per package (indexed by name).
                 let root = cache.bucket(self).join(WheelCacheKind::Pypi);
-                summary += rm_rf(root.join(name.to_string()))?;
+                entries.push(root.join(name.to_string()));
 
                 // For alternate indices, we expect a directory for every index, followed by a
                 // directory per package (indexed by name).
                 let root = cache.bucket(self).join(WheelCacheKind::Index);
                 for directory in directories(root) {
-                    summary += rm_rf(directory.join(name.to_string()))?;
+                    entries.push(directory.join(name.to_string()));
                 }
 
                 // For direct URLs, we expect a directory for every URL, followed by a
                 // directory per package (indexed by name).
                 let root = cache.bucket(self).join(WheelCacheKind::Url);
                 for directory in directories(root) {
-                    summary += rm_rf(directory.join(name.to_string()))?;
+                    entries.push(directory.join(name.to_string()));
                 }
             }
             Self::BuiltWheels => {
                 // For `pypi` wheels, we expect a directory per package (indexed by name).
                 let root = cache.bucket(self).join(WheelCacheKind::Pypi);
-                summary += rm_rf(root.join(name.to_string()))?;
+                entries.push(root.join(name.to_string()));
 
                 // For alternate indices, we expect a directory for every index, followed by a
                 // directory per package (indexed by name).
                 let root = cache.bucket(self).join(WheelCacheKind::Index);
                 for directory in directories(root) {
-                    summary += rm_rf(directory.join(name.to_string()))?;
+                    entries.push(directory.join(name.to_string()));
                 }
 
                 // For direct URLs, we expect a directory for every URL, followed by a
@@ -658,7 +1259,7 @@ impl CacheBucket {
                 let root = cache.bucket(self).join(WheelCacheKind::Url);
                 for url in directories(root) {
                     if directories(&url).any(|version| is_match(&version, name)) {
-                        summary += rm_rf(url)?;
+                        entries.push(url);
                     }
                 }
 
@@ -668,7 +1269,15 @@ impl CacheBucket {
                 let root = cache.bucket(self).join(WheelCacheKind::Path);
                 for path in directories(root) {
                     if directories(&path).any(|version| is_match(&version, name)) {
-                        summary += rm_rf(path)?;
+                        entries.push(path);
+                    }
+                }
+
+                // For editable builds, we expect the same layout as for local dependencies.
+                let root = cache.bucket(self).join(WheelCacheKind::Editable);
+                for path in directories(root) {
+                    if directories(&path).any(|version| is_match(&version, name)) {
+                        entries.push(path);
                     }
                 }
 
@@ -679,28 +1288,31 @@ impl CacheBucket {
                 for repository in directories(root) {
                     for sha in directories(repository) {
                         if is_match(&sha, name) {
-                            summary += rm_rf(sha)?;
+                            entries.push(sha);
                         }
                     }
                 }
             }
             Self::Simple => {
-                // For `pypi` wheels, we expect a rkyv file per package, indexed by name.
+                // For `pypi` wheels, we expect a rkyv file per package, indexed by name, along
+                // with an optional negative cache entry.
                 let root = cache.bucket(self).join(WheelCacheKind::Pypi);
-                summary += rm_rf(root.join(format!("{name}.rkyv")))?;
+                entries.push(root.join(format!("{name}.rkyv")));
+                entries.push(root.join(format!("{name}.missing")));
 
                 // For alternate indices, we expect a directory for every index, followed by a
                 // MsgPack file per package, indexed by name.
                 let root = cache.bucket(self).join(WheelCacheKind::Url);
                 for directory in directories(root) {
-                    summary += rm_rf(directory.join(format!("{name}.rkyv")))?;
+                    entries.push(directory.join(format!("{name}.rkyv")));
+                    entries.push(directory.join(format!("{name}.missing")));
                 }
             }
             Self::FlatIndex => {
                 // We can't know if the flat index includes a package, so we just remove the entire
                 // cache entry.
                 let root = cache.bucket(self);
-                summary += rm_rf(root)?;
+                entries.push(root);
             }
             Self::Git => {
                 // Nothing to do.
@@ -708,11 +1320,17 @@ impl CacheBucket {
             Self::Interpreter => {
                 // Nothing to do.
             }
+            Self::Resolutions => {
+                // We can't know if a resolution includes a package without deserializing it, so
+                // we remove the entire bucket.
+                let root = cache.bucket(self);
+                entries.push(root);
+            }
             Self::Archive => {
                 // Nothing to do.
             }
         }
-        Ok(summary)
+        entries
     }
 
     /// Return an iterator over all cache buckets.
@@ -724,6 +1342,8 @@ impl CacheBucket {
             CacheBucket::Git,
             CacheBucket::Interpreter,
             CacheBucket::Simple,
+            CacheBucket::WheelMetadata,
+            CacheBucket::Resolutions,
             CacheBucket::Archive,
         ]
         .iter()
@@ -795,6 +1415,44 @@ impl ArchiveTimestamp {
         }
     }
 
+    /// Return the modification timestamp for a source tree, as the latest modification time of
+    /// any file in the tree.
+    ///
+    /// Unlike [`ArchiveTimestamp::from_path`], this accounts for files that are read by the build
+    /// backend beyond the `pyproject.toml`, `setup.py`, and `setup.cfg` (e.g., a `requirements.txt`
+    /// referenced by dynamic metadata). Build outputs (like `build` and `*.egg-info` directories),
+    /// bytecode caches, and virtual environments nested in the tree are ignored.
+    ///
+    /// If the path is to a directory with no entrypoint, returns `None`.
+    pub fn from_source_tree(path: impl AsRef<Path>) -> Result<Option<Self>, io::Error> {
+        let mut timestamp = match Self::from_path(path.as_ref())? {
+            Some(Self::Approximate(timestamp)) => timestamp,
+            exact_or_none => return Ok(exact_or_none),
+        };
+
+        let mut walker = walkdir::WalkDir::new(path.as_ref()).into_iter();
+        while let Some(entry) = walker.next() {
+            let entry = entry.map_err(io::Error::from)?;
+            if entry.file_type().is_dir() {
+                let name = entry.file_name().to_string_lossy();
+                if entry.depth() > 0
+                    && (name == "build"
+                        || name == "__pycache__"
+                        || name.ends_with(".egg-info")
+                        || entry.path().join("pyvenv.cfg").is_file())
+                {
+                    walker.skip_current_dir();
+                }
+                continue;
+            }
+            if entry.file_type().is_file() {
+                timestamp = max(timestamp, Timestamp::from_metadata(&entry.metadata()?));
+            }
+        }
+
+        Ok(Some(Self::Approximate(timestamp)))
+    }
+
     /// Return the modification timestamp for a file.
     pub fn from_file(path: impl AsRef<Path>) -> Result<Self, io::Error> {
         let metadata = fs_err::metadata(path.as_ref())?;
@@ -858,6 +1516,9 @@ pub enum Freshness {
     Fresh,
     /// The cache entry is stale according to the [`Refresh`] policy.
     Stale,
+    /// The cache entry should be revalidated against the remote, if possible, according to the
+    /// [`Refresh`] policy.
+    Revalidate,
     /// The cache entry does not exist.
     Missing,
 }
@@ -867,6 +1528,15 @@ impl Freshness {
         matches!(self, Self::Fresh)
     }
 
+    /// Returns `true` if the cache entry is fresh, or only needs to be revalidated against the
+    /// remote.
+    ///
+    /// Entries derived from local state (e.g., interpreter metadata or directory scans) can't be
+    /// revalidated against a remote, and so are trusted as-is when revalidation is requested.
+    pub const fn is_fresh_or_revalidate(self) -> bool {
+        matches!(self, Self::Fresh | Self::Revalidate)
+    }
+
     pub const fn is_stale(self) -> bool {
         matches!(self, Self::Stale)
     }
@@ -877,19 +1547,38 @@ impl Freshness {
 pub enum Refresh {
     /// Don't refresh any entries.
     None,
-    /// Refresh entries linked to the given packages, if created before the given timestamp.
-    Packages(Vec<PackageName>, Timestamp),
+    /// Refresh entries linked to the given packages or direct URLs, if created before the given
+    /// timestamp.
+    ///
+    /// Git URLs are matched regardless of their reference, so refreshing a branch or tag refreshes
+    /// the wheels built from any commit in the repository.
+    Packages(Vec<PackageName>, Vec<Url>, Timestamp),
     /// Refresh all entries created before the given timestamp.
     All(Timestamp),
+    /// Revalidate all entries created before the given timestamp, rather than discarding them,
+    /// other than those linked to the given packages or direct URLs, which are refreshed.
+    ///
+    /// Entries derived from HTTP responses are revalidated against the remote via conditional
+    /// requests, using the cache policy (e.g., `ETag` or `Last-Modified`) stored alongside the
+    /// entry, and reused if the remote reports that they're unchanged. Entries derived from local
+    /// state (e.g., built wheels for local source trees) are trusted as-is.
+    Revalidate(Vec<PackageName>, Vec<Url>, Timestamp),
 }
 
 impl Refresh {
     /// Determine the refresh strategy to use based on the command-line arguments.
-    pub fn from_args(refresh: bool, refresh_package: Vec<PackageName>) -> Self {
+    pub fn from_args(
+        refresh: bool,
+        revalidate: bool,
+        refresh_package: Vec<PackageName>,
+        refresh_url: Vec<Url>,
+    ) -> Self {
         if refresh {
             Self::All(Timestamp::now())
-        } else if !refresh_package.is_empty() {
-            Self::Packages(refresh_package, Timestamp::now())
+        } else if revalidate {
+            Self::Revalidate(refresh_package, refresh_url, Timestamp::now())
+        } else if !refresh_package.is_empty() || !refresh_url.is_empty() {
+            Self::Packages(refresh_package, refresh_url, Timestamp::now())
         } else {
             Self::None
         }
@@ -900,3 +1589,135 @@ impl Refresh {
         matches!(self, Self::None)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use std::time::{Duration, SystemTime};
+
+    use filetime::FileTime;
+    use url::Url;
+
+    use uv_normalize::PackageName;
+
+    use crate::{Cache, CacheBucket, Freshness, Refresh, RemovalMode, Timestamp, WheelCache};
+
+    /// Packages passed to `--refresh-package` are refreshed in full when combined with
+    /// `--revalidate`, while all other entries are only revalidated.
+    #[test]
+    fn revalidate_refresh_package() -> std::io::Result<()> {
+        let cache = Cache::temp()?;
+        let entry = cache.entry(CacheBucket::Simple, "pypi", "foo.rkyv");
+        fs_err::create_dir_all(entry.dir())?;
+        fs_err::write(entry.path(), "")?;
+        std::thread::sleep(Duration::from_millis(10));
+
+        let foo = PackageName::from_str("foo").unwrap();
+        let bar = PackageName::from_str("bar").unwrap();
+        let cache = cache.with_refresh(Refresh::Revalidate(
+            vec![foo.clone()],
+            Vec::new(),
+            Timestamp::now(),
+        ));
+
+        assert_eq!(cache.freshness(&entry, Some(&foo))?, Freshness::Stale);
+        assert_eq!(cache.freshness(&entry, Some(&bar))?, Freshness::Revalidate);
+        assert_eq!(cache.freshness(&entry, None)?, Freshness::Revalidate);
+        assert!(!Freshness::Revalidate.is_fresh());
+        assert!(Freshness::Revalidate.is_fresh_or_revalidate());
+
+        Ok(())
+    }
+
+    /// `--refresh-package` and `--refresh-url` may be combined.
+    #[test]
+    fn refresh_package_and_url() -> std::io::Result<()> {
+        let foo = PackageName::from_str("foo").unwrap();
+        let bar = PackageName::from_str("bar").unwrap();
+        let url = Url::parse("https://example.com/baz-1.0.0-py3-none-any.whl").unwrap();
+        let other = Url::parse("https://example.com/qux-1.0.0-py3-none-any.whl").unwrap();
+
+        let cache = Cache::temp()?.with_refresh(Refresh::from_args(
+            false,
+            false,
+            vec![foo.clone()],
+            vec![url.clone()],
+        ));
+
+        assert!(cache.must_revalidate(&foo));
+        assert!(!cache.must_revalidate(&bar));
+        assert!(cache.must_revalidate_url(&url));
+        assert!(!cache.must_revalidate_url(&other));
+
+        Ok(())
+    }
+
+    /// Refreshing a Git URL with a branch reference refreshes the wheels built from the commit to
+    /// which it resolved, but not those built from other repositories or subdirectories.
+    #[test]
+    fn refresh_git_branch() -> std::io::Result<()> {
+        let precise = Url::parse(
+            "git+https://github.com/pypa/sample@f2aba6b4eff8e14e1b0e4e5e7b7b7a2e0b2f3e1d",
+        )
+        .unwrap();
+        let subdirectory = Url::parse(
+            "git+https://github.com/pypa/sample@f2aba6b4eff8e14e1b0e4e5e7b7b7a2e0b2f3e1d#subdirectory=pkg",
+        )
+        .unwrap();
+        let other = Url::parse(
+            "git+https://github.com/pypa/other@f2aba6b4eff8e14e1b0e4e5e7b7b7a2e0b2f3e1d",
+        )
+        .unwrap();
+
+        let cache = Cache::temp()?;
+        let entries = [&precise, &subdirectory, &other].map(|url| {
+            cache.entry(
+                CacheBucket::BuiltWheels,
+                WheelCache::Git(url, "f2aba6b4eff8e14e").root(),
+                "manifest.msgpack",
+            )
+        });
+        for entry in &entries {
+            fs_err::create_dir_all(entry.dir())?;
+            fs_err::write(entry.path(), "")?;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+
+        let branch = Url::parse("git+https://github.com/pypa/sample.git@main").unwrap();
+        let cache = cache.with_refresh(Refresh::from_args(false, false, Vec::new(), vec![branch]));
+
+        let [precise, subdirectory, other] = entries;
+        assert_eq!(cache.freshness(&precise, None)?, Freshness::Stale);
+        assert_eq!(cache.freshness(&subdirectory, None)?, Freshness::Fresh);
+        assert_eq!(cache.freshness(&other, None)?, Freshness::Fresh);
+
+        Ok(())
+    }
+
+    /// Packages that haven't been used within the given duration are removed, while those that
+    /// were recently accessed are retained, even if their entries were written long ago.
+    #[test]
+    fn prune_unused() -> std::io::Result<()> {
+        let cache = Cache::temp()?;
+        let foo = cache.bucket(CacheBucket::Wheels).join("pypi").join("foo");
+        let bar = cache.bucket(CacheBucket::Wheels).join("pypi").join("bar");
+        for dir in [&foo, &bar] {
+            fs_err::create_dir_all(dir)?;
+            fs_err::write(dir.join("foo-1.0.0-py3-none-any.http"), "")?;
+            let written = SystemTime::now() - Duration::from_secs(60 * 60 * 24 * 30);
+            filetime::set_file_mtime(dir, FileTime::from_system_time(written))?;
+        }
+
+        // Access `bar`, and record the access in the log.
+        cache.shard(CacheBucket::Wheels, "pypi/bar");
+        cache.flush_access_log()?;
+
+        let removal =
+            cache.prune_unused(Duration::from_secs(60 * 60 * 24 * 7), RemovalMode::Remove)?;
+        assert_eq!(removal.num_files, 1);
+        assert!(!foo.exists());
+        assert!(bar.exists());
+
+        Ok(())
+    }
+}
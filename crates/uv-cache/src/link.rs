@@ -0,0 +1,57 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+use tracing::debug;
+
+use crate::fs_mode::copy_dir_all;
+use crate::removal::rm_rf;
+
+/// Link an entry in the archive bucket into place at `link`.
+///
+/// Symlinks (junctions, on Windows) are preferred. Where they can't be created (e.g., on Windows
+/// without Developer Mode, where junctions are unavailable for files, or on filesystems without
+/// link support), compressed archives are hard-linked into place, and uncompressed archives are
+/// copied into place, at which point the archive itself is no longer needed and is removed.
+///
+/// Returns the path at which the entry can be read.
+pub(crate) fn link_archive(archive: &Path, link: &Path) -> io::Result<PathBuf> {
+    let err = match uv_fs::replace_symlink(archive, link) {
+        Ok(()) => return Ok(archive.to_path_buf()),
+        Err(err) => err,
+    };
+
+    if archive.is_file() {
+        debug!(
+            "Failed to symlink cache entry ({err}); falling back to hard link: {}",
+            link.display()
+        );
+        rm_rf(link)?;
+        match fs_err::hard_link(archive, link) {
+            Ok(()) => return Ok(archive.to_path_buf()),
+            Err(err) => debug!(
+                "Failed to hard link cache entry ({err}); falling back to copy: {}",
+                link.display()
+            ),
+        }
+    } else {
+        debug!(
+            "Failed to symlink cache entry ({err}); falling back to copy: {}",
+            link.display()
+        );
+        rm_rf(link)?;
+    }
+
+    // Copy into a temporary location and then move into place, such that an interrupted copy is
+    // never mistaken for a complete entry.
+    let temp_dir = tempfile::tempdir_in(link.parent().expect("Cache entry to have parent"))?;
+    let temp_entry = temp_dir.path().join("entry");
+    if archive.is_file() {
+        fs_err::copy(archive, &temp_entry)?;
+    } else {
+        copy_dir_all(archive, &temp_entry)?;
+    }
+    fs_err::rename(&temp_entry, link)?;
+
+    rm_rf(archive)?;
+    Ok(link.to_path_buf())
+}
@@ -0,0 +1,72 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use serde::Serialize;
+
+/// Process-wide counters for cache activity.
+static HITS: AtomicU64 = AtomicU64::new(0);
+static MISSES: AtomicU64 = AtomicU64::new(0);
+static REVALIDATIONS: AtomicU64 = AtomicU64::new(0);
+static BYTES_SERVED: AtomicU64 = AtomicU64::new(0);
+
+/// A snapshot of the cache activity for the current process.
+///
+/// Counters are recorded by the cached HTTP client, as it consults the entries for cached
+/// responses (e.g., Simple API pages and wheel metadata), and by the installer, as it looks up
+/// unzipped wheels in the cache. They can be read at any point via [`CacheMetrics::snapshot`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct CacheMetrics {
+    /// The number of lookups that were served from the cache.
+    pub hits: u64,
+    /// The number of lookups for which no cache entry existed.
+    pub misses: u64,
+    /// The number of cache entries that had to be revalidated against the remote.
+    pub revalidations: u64,
+    /// The total number of bytes read from the cache to serve hits.
+    ///
+    /// Unzipped wheels are linked into the environment rather than read, so they don't contribute
+    /// to this count.
+    pub bytes_served: u64,
+}
+
+impl CacheMetrics {
+    /// Record a cache hit, serving the given number of bytes.
+    pub fn record_hit(bytes: u64) {
+        HITS.fetch_add(1, Ordering::Relaxed);
+        BYTES_SERVED.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// Record a cache miss.
+    pub fn record_miss() {
+        MISSES.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record the revalidation of a stale cache entry.
+    pub fn record_revalidation() {
+        REVALIDATIONS.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Return a snapshot of the current counters.
+    pub fn snapshot() -> Self {
+        Self {
+            hits: HITS.load(Ordering::Relaxed),
+            misses: MISSES.load(Ordering::Relaxed),
+            revalidations: REVALIDATIONS.load(Ordering::Relaxed),
+            bytes_served: BYTES_SERVED.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Returns `true` if no cache activity has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.hits == 0 && self.misses == 0 && self.revalidations == 0
+    }
+}
+
+impl std::fmt::Display for CacheMetrics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses, {} revalidations ({} bytes served)",
+            self.hits, self.misses, self.revalidations, self.bytes_served
+        )
+    }
+}
@@ -0,0 +1,251 @@
+use std::io;
+use std::path::Path;
+
+use tracing::debug;
+
+use crate::removal::rm_rf;
+use crate::CacheBucket;
+
+/// A forward-compatible migration from an outdated version of a [`CacheBucket`].
+///
+/// When a bucket version is bumped (e.g., `built-wheels-v2` to `built-wheels-v3`), a migration can
+/// be registered in [`MIGRATIONS`] to carry over any entries whose serialized format can be
+/// upgraded, rather than discarding the entire bucket.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Migration {
+    /// The name of the outdated bucket directory (e.g., `built-wheels-v2`).
+    pub(crate) from: &'static str,
+    /// The bucket into which entries should be migrated.
+    pub(crate) to: CacheBucket,
+    /// The strategy used to upgrade individual entries.
+    pub(crate) kind: MigrationKind,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum MigrationKind {
+    /// The on-disk format is unchanged, so entries can be moved as-is.
+    Move,
+    /// Entries must be rewritten using the given conversion, which receives the path of the entry
+    /// relative to the bucket root and its contents. Returns `None` if the entry can't be
+    /// converted, in which case it's discarded.
+    Convert(fn(&Path, &[u8]) -> Option<Vec<u8>>),
+}
+
+/// The registered migrations, in order of application.
+pub(crate) const MIGRATIONS: &[Migration] = &[
+    // `simple-v7` adds negative entries for missing packages; the index responses are unchanged.
+    Migration {
+        from: "simple-v6",
+        to: CacheBucket::Simple,
+        kind: MigrationKind::Move,
+    },
+    // `resolutions-v1` records the diagnostics of each cached resolution, and keys them on the
+    // resolver limits; the decisions used by `--incremental` are unchanged.
+    Migration {
+        from: "resolutions-v0",
+        to: CacheBucket::Resolutions,
+        kind: MigrationKind::Convert(convert_resolutions_v0),
+    },
+];
+
+/// Carry over the decisions from a `resolutions-v0` bucket.
+///
+/// Cached resolutions are discarded, as their keys omit inputs that now affect the output, such
+/// that they can't be carried over to the current bucket.
+fn convert_resolutions_v0(path: &Path, contents: &[u8]) -> Option<Vec<u8>> {
+    path.starts_with("decisions").then(|| contents.to_vec())
+}
+
+/// Returns `true` if the cache at `root` contains any outdated bucket with a registered migration.
+pub(crate) fn is_pending(root: &Path) -> bool {
+    MIGRATIONS
+        .iter()
+        .any(|migration| root.join(migration.from).is_dir())
+}
+
+/// Apply the registered [`MIGRATIONS`] to the cache at `root`.
+///
+/// The caller is expected to hold the cache lock.
+pub(crate) fn migrate(root: &Path) -> io::Result<Migrated> {
+    let mut summary = Migrated::default();
+    for migration in MIGRATIONS {
+        let from = root.join(migration.from);
+        if !from.is_dir() {
+            continue;
+        }
+        debug!(
+            "Migrating cache bucket `{}` to `{}`",
+            migration.from, migration.to
+        );
+        summary += migration.run(&from, &root.join(migration.to.to_str()))?;
+    }
+    Ok(summary)
+}
+
+#[derive(Debug, Default)]
+pub struct Migrated {
+    /// The number of outdated buckets that were migrated.
+    pub num_buckets: u64,
+    /// The number of entries that were carried over into the current bucket.
+    pub num_entries: u64,
+    /// The number of entries that couldn't be converted, and were discarded.
+    pub num_discarded: u64,
+}
+
+impl std::ops::AddAssign for Migrated {
+    fn add_assign(&mut self, other: Self) {
+        self.num_buckets += other.num_buckets;
+        self.num_entries += other.num_entries;
+        self.num_discarded += other.num_discarded;
+    }
+}
+
+impl Migration {
+    /// Migrate the entries in the outdated bucket at `from` into the current bucket at `to`,
+    /// removing the outdated bucket once complete.
+    ///
+    /// Entries that already exist in the current bucket are left untouched.
+    pub(crate) fn run(&self, from: &Path, to: &Path) -> io::Result<Migrated> {
+        let mut migrated = Migrated {
+            num_buckets: 1,
+            ..Migrated::default()
+        };
+
+        for entry in walkdir::WalkDir::new(from) {
+            let entry = entry?;
+            if entry.file_type().is_dir() {
+                continue;
+            }
+
+            let relative = entry
+                .path()
+                .strip_prefix(from)
+                .expect("walkdir starts with root");
+            let target = to.join(relative);
+            if target.exists() {
+                continue;
+            }
+            fs_err::create_dir_all(target.parent().expect("Cache entry to have parent"))?;
+
+            // Symlinks point into the archive bucket, which is shared across versions, so they can
+            // always be moved as-is.
+            if entry.file_type().is_symlink() {
+                fs_err::rename(entry.path(), &target)?;
+                migrated.num_entries += 1;
+                continue;
+            }
+
+            match self.kind {
+                MigrationKind::Move => {
+                    fs_err::rename(entry.path(), &target)?;
+                    migrated.num_entries += 1;
+                }
+                MigrationKind::Convert(convert) => {
+                    let contents = fs_err::read(entry.path())?;
+                    if let Some(contents) = convert(relative, &contents) {
+                        uv_fs::write_atomic_sync(&target, contents)?;
+                        migrated.num_entries += 1;
+                    } else {
+                        debug!("Discarding unconvertible entry: {}", entry.path().display());
+                        migrated.num_discarded += 1;
+                    }
+                }
+            }
+        }
+
+        rm_rf(from)?;
+
+        Ok(migrated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::CacheBucket;
+
+    use super::{convert_resolutions_v0, Migration, MigrationKind, MIGRATIONS};
+
+    #[test]
+    fn move_entries() -> std::io::Result<()> {
+        let root = tempfile::tempdir()?;
+        let from = root.path().join("simple-v5");
+        let to = root.path().join(CacheBucket::Simple.to_str());
+        fs_err::create_dir_all(from.join("pypi"))?;
+        fs_err::write(from.join("pypi").join("foo.msgpack"), "foo")?;
+        fs_err::write(from.join("pypi").join("bar.msgpack"), "old")?;
+        fs_err::create_dir_all(to.join("pypi"))?;
+        fs_err::write(to.join("pypi").join("bar.msgpack"), "new")?;
+
+        let migration = Migration {
+            from: "simple-v5",
+            to: CacheBucket::Simple,
+            kind: MigrationKind::Move,
+        };
+        let migrated = migration.run(&from, &to)?;
+
+        assert_eq!(migrated.num_buckets, 1);
+        assert_eq!(migrated.num_entries, 1);
+        assert_eq!(migrated.num_discarded, 0);
+        assert!(!from.exists());
+        assert_eq!(
+            fs_err::read_to_string(to.join("pypi").join("foo.msgpack"))?,
+            "foo"
+        );
+        // Existing entries in the current bucket take precedence.
+        assert_eq!(
+            fs_err::read_to_string(to.join("pypi").join("bar.msgpack"))?,
+            "new"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn convert_entries() -> std::io::Result<()> {
+        fn convert(_: &Path, contents: &[u8]) -> Option<Vec<u8>> {
+            (contents != b"invalid").then(|| contents.to_ascii_uppercase())
+        }
+
+        let root = tempfile::tempdir()?;
+        let from = root.path().join("simple-v5");
+        let to = root.path().join(CacheBucket::Simple.to_str());
+        fs_err::create_dir_all(&from)?;
+        fs_err::write(from.join("foo.msgpack"), "foo")?;
+        fs_err::write(from.join("bar.msgpack"), "invalid")?;
+
+        let migration = Migration {
+            from: "simple-v5",
+            to: CacheBucket::Simple,
+            kind: MigrationKind::Convert(convert),
+        };
+        let migrated = migration.run(&from, &to)?;
+
+        assert_eq!(migrated.num_entries, 1);
+        assert_eq!(migrated.num_discarded, 1);
+        assert!(!from.exists());
+        assert_eq!(fs_err::read_to_string(to.join("foo.msgpack"))?, "FOO");
+        assert!(!to.join("bar.msgpack").exists());
+        Ok(())
+    }
+
+    #[test]
+    fn resolutions_v0() {
+        assert_eq!(
+            convert_resolutions_v0(Path::new("decisions/1234.msgpack"), b"decisions"),
+            Some(b"decisions".to_vec())
+        );
+        assert_eq!(
+            convert_resolutions_v0(Path::new("1234.msgpack"), b"resolution"),
+            None
+        );
+    }
+
+    /// Every migration must be from an outdated bucket, or the current bucket would be removed.
+    #[test]
+    fn outdated_buckets() {
+        for migration in MIGRATIONS {
+            assert_ne!(migration.from, migration.to.to_str());
+        }
+    }
+}
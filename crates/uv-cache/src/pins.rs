@@ -0,0 +1,72 @@
+use std::collections::BTreeSet;
+use std::io;
+use std::path::Path;
+
+use uv_normalize::PackageName;
+
+/// The set of packages pinned in the cache.
+///
+/// The entries for pinned packages are protected against eviction: they're skipped by automatic
+/// pruning, and preserved by `uv cache clean` unless `--force` is provided. Pins are stored as a
+/// newline-delimited list of package names in the cache root.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Pins(BTreeSet<PackageName>);
+
+impl Pins {
+    /// The name of the file containing the pinned packages, relative to the cache root.
+    pub(crate) const FILE: &'static str = ".pins";
+
+    /// Read the pinned packages for the cache at `root`.
+    ///
+    /// Lines that aren't valid package names are ignored.
+    pub(crate) fn read(root: &Path) -> io::Result<Self> {
+        let contents = match fs_err::read_to_string(root.join(Self::FILE)) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => return Err(err),
+        };
+        Ok(Self(
+            contents
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .filter_map(|line| PackageName::new(line.to_string()).ok())
+                .collect(),
+        ))
+    }
+
+    /// Write the pinned packages to the cache at `root`.
+    pub(crate) fn write(&self, root: &Path) -> io::Result<()> {
+        let mut contents = String::new();
+        for name in &self.0 {
+            contents.push_str(name.as_ref());
+            contents.push('\n');
+        }
+        uv_fs::write_atomic_sync(root.join(Self::FILE), contents)
+    }
+
+    /// Add a package to the set. Returns `false` if the package was already pinned.
+    pub(crate) fn insert(&mut self, name: PackageName) -> bool {
+        self.0.insert(name)
+    }
+
+    /// Remove a package from the set. Returns `false` if the package wasn't pinned.
+    pub(crate) fn remove(&mut self, name: &PackageName) -> bool {
+        self.0.remove(name)
+    }
+
+    /// Returns `true` if the given package is pinned.
+    pub fn contains(&self, name: &PackageName) -> bool {
+        self.0.contains(name)
+    }
+
+    /// Returns `true` if no packages are pinned.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterate over the pinned packages, in sorted order.
+    pub fn iter(&self) -> impl Iterator<Item = &PackageName> {
+        self.0.iter()
+    }
+}
@@ -13,6 +13,34 @@ pub(crate) fn rm_rf(path: impl AsRef<Path>) -> io::Result<Removal> {
     Ok(removal)
 }
 
+/// Compute the [`Removal`] that would result from removing a file or directory and all its
+/// contents, without modifying the filesystem.
+pub(crate) fn preview(path: impl AsRef<Path>) -> io::Result<Removal> {
+    let mut removal = Removal::default();
+    removal.preview(path.as_ref())?;
+    Ok(removal)
+}
+
+/// Whether to remove cache entries, or to only report what would be removed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum RemovalMode {
+    /// Remove the entries from disk.
+    #[default]
+    Remove,
+    /// Walk the entries and sum what would be removed, without touching disk.
+    Preview,
+}
+
+impl RemovalMode {
+    /// Remove (or preview the removal of) a file or directory and all its contents.
+    pub(crate) fn rm_rf(self, path: impl AsRef<Path>) -> io::Result<Removal> {
+        match self {
+            Self::Remove => rm_rf(path),
+            Self::Preview => preview(path),
+        }
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct Removal {
     /// The number of files removed.
@@ -70,6 +98,38 @@ impl Removal {
 
         Ok(())
     }
+
+    /// Recursively walk a file or directory, summing the files, directories, and bytes that
+    /// [`Removal::rm_rf`] would remove.
+    fn preview(&mut self, path: &Path) -> io::Result<()> {
+        let metadata = match fs_err::symlink_metadata(path) {
+            Ok(metadata) => metadata,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(err),
+        };
+
+        if !metadata.is_dir() {
+            self.num_files += 1;
+            self.total_bytes += metadata.len();
+            return Ok(());
+        }
+
+        for entry in walkdir::WalkDir::new(path) {
+            let entry = entry?;
+            if cfg!(windows) && entry.file_type().is_symlink() {
+                self.num_files += 1;
+            } else if entry.file_type().is_dir() {
+                self.num_dirs += 1;
+            } else {
+                self.num_files += 1;
+                if let Ok(metadata) = entry.metadata() {
+                    self.total_bytes += metadata.len();
+                }
+            }
+        }
+
+        Ok(())
+    }
 }
 
 impl std::ops::AddAssign for Removal {
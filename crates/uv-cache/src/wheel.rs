@@ -14,7 +14,9 @@ pub enum WheelCache<'a> {
     Url(&'a Url),
     /// A path dependency, which we key by URL.
     Path(&'a Url),
-    /// A Git dependency, which we key by URL and SHA.
+    /// An editable dependency, which we key by URL.
+    Editable(&'a Url),
+    /// A Git dependency, which we key by URL (without the reference) and SHA.
     ///
     /// Note that this variant only exists for source distributions; wheels can't be delivered
     /// through Git.
@@ -35,9 +37,12 @@ impl<'a> WheelCache<'a> {
             WheelCache::Path(url) => WheelCacheKind::Path
                 .root()
                 .join(digest(&CanonicalUrl::new(url))),
+            WheelCache::Editable(url) => WheelCacheKind::Editable
+                .root()
+                .join(digest(&CanonicalUrl::new(url))),
             WheelCache::Git(url, sha) => WheelCacheKind::Git
                 .root()
-                .join(digest(&CanonicalUrl::new(url)))
+                .join(digest(&CanonicalUrl::new(&without_git_reference(url))))
                 .join(sha),
         }
     }
@@ -48,8 +53,25 @@ impl<'a> WheelCache<'a> {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-pub(crate) enum WheelCacheKind {
+/// Strip the reference (i.e., a branch, tag, or commit) from a Git URL, retaining any other
+/// components, like the subdirectory.
+///
+/// Git dependencies are keyed by the URL without the reference, such that all references to a
+/// repository share a shard, with a directory per commit.
+pub(crate) fn without_git_reference(url: &Url) -> Url {
+    let mut url = url.clone();
+    if url.scheme().starts_with("git+") {
+        if let Some((prefix, _reference)) = url.path().rsplit_once('@') {
+            let prefix = prefix.to_string();
+            url.set_path(&prefix);
+        }
+    }
+    url
+}
+
+/// The kind of source from which a wheel cache entry originated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WheelCacheKind {
     /// A cache of data from PyPI.
     Pypi,
     /// A cache of data from an alternative index.
@@ -58,6 +80,8 @@ pub(crate) enum WheelCacheKind {
     Url,
     /// A cache of data from a local path.
     Path,
+    /// A cache of editable builds of a local path.
+    Editable,
     /// A cache of data from a Git repository.
     Git,
 }
@@ -69,6 +93,7 @@ impl WheelCacheKind {
             Self::Index => "index",
             Self::Url => "url",
             Self::Path => "path",
+            Self::Editable => "editable",
             Self::Git => "git",
         }
     }
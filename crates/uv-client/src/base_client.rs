@@ -1,30 +1,45 @@
+use futures::stream::BoxStream;
+use futures::TryStreamExt;
+use hyper::body::Bytes;
 use pep508_rs::MarkerEnvironment;
 use platform_tags::Platform;
-use reqwest::{Client, ClientBuilder};
+use reqwest::{Client, ClientBuilder, NoProxy, Proxy, Response};
 use reqwest_middleware::ClientWithMiddleware;
-use reqwest_retry::policies::ExponentialBackoff;
-use reqwest_retry::RetryTransientMiddleware;
 use std::env;
 use std::fmt::Debug;
+use std::io;
 use std::ops::Deref;
 use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
 use tracing::debug;
-use uv_auth::{AuthMiddleware, KeyringProvider};
+use url::Url;
+use uv_auth::{AuthMiddleware, CredentialProvider, KeyringProvider};
 use uv_fs::Simplified;
 use uv_version::version;
 use uv_warnings::warn_user_once;
 
+use crate::connection::with_read_timeout;
+use crate::dns::CachingResolver;
 use crate::linehaul::LineHaul;
 use crate::middleware::OfflineMiddleware;
+use crate::mirror::MirrorMiddleware;
+use crate::object_store::ObjectStoreMiddleware;
+use crate::retry::RetryMiddleware;
 use crate::tls::Roots;
-use crate::{tls, Connectivity};
+use crate::trace::{HttpTrace, TraceMiddleware};
+use crate::{tls, ConnectionOptions, Connectivity, RetryPolicy};
 
 /// A builder for an [`BaseClient`].
 #[derive(Debug, Clone)]
 pub struct BaseClientBuilder<'a> {
     keyring_provider: KeyringProvider,
+    credential_provider: Option<Arc<dyn CredentialProvider>>,
     native_tls: bool,
-    retries: u32,
+    retry_policy: RetryPolicy,
+    proxy: Option<Url>,
+    connection_options: ConnectionOptions,
+    index_mirrors: Option<(Url, Vec<Url>)>,
     connectivity: Connectivity,
     client: Option<Client>,
     markers: Option<&'a MarkerEnvironment>,
@@ -41,9 +56,13 @@ impl BaseClientBuilder<'_> {
     pub fn new() -> Self {
         Self {
             keyring_provider: KeyringProvider::default(),
+            credential_provider: None,
             native_tls: false,
             connectivity: Connectivity::Online,
-            retries: 3,
+            retry_policy: RetryPolicy::default(),
+            proxy: None,
+            connection_options: ConnectionOptions::from_env(),
+            index_mirrors: None,
             client: None,
             markers: None,
             platform: None,
@@ -58,6 +77,39 @@ impl<'a> BaseClientBuilder<'a> {
         self
     }
 
+    /// Use the given [`CredentialProvider`] when a request is rejected as unauthorized, in lieu
+    /// of the keyring provider.
+    #[must_use]
+    pub fn credential_provider(mut self, credential_provider: Arc<dyn CredentialProvider>) -> Self {
+        self.credential_provider = Some(credential_provider);
+        self
+    }
+
+    /// Send all requests through the given proxy, rather than those configured via the
+    /// `HTTP_PROXY`, `HTTPS_PROXY`, and `ALL_PROXY` environment variables.
+    ///
+    /// In either case, hosts listed in `NO_PROXY` are accessed directly.
+    #[must_use]
+    pub fn proxy(mut self, proxy: Option<Url>) -> Self {
+        self.proxy = proxy;
+        self
+    }
+
+    /// Configure the connection pool and timeouts, in lieu of those read from the environment.
+    #[must_use]
+    pub fn connection_options(mut self, connection_options: ConnectionOptions) -> Self {
+        self.connection_options = connection_options;
+        self
+    }
+
+    /// Fail over requests for the given index to its mirrors, if the index can't be reached or
+    /// responds with a server error.
+    #[must_use]
+    pub fn index_mirrors(mut self, index: Url, mirrors: Vec<Url>) -> Self {
+        self.index_mirrors = Some((index, mirrors));
+        self
+    }
+
     #[must_use]
     pub fn connectivity(mut self, connectivity: Connectivity) -> Self {
         self.connectivity = connectivity;
@@ -66,7 +118,13 @@ impl<'a> BaseClientBuilder<'a> {
 
     #[must_use]
     pub fn retries(mut self, retries: u32) -> Self {
-        self.retries = retries;
+        self.retry_policy.retries = retries;
+        self
+    }
+
+    #[must_use]
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
         self
     }
 
@@ -94,22 +152,39 @@ impl<'a> BaseClientBuilder<'a> {
         self
     }
 
-    pub fn is_offline(&self) -> bool {
-        matches!(self.connectivity, Connectivity::Offline)
-    }
-
-    pub fn build(&self) -> BaseClient {
-        // Create user agent.
-        let mut user_agent_string = format!("uv/{}", version());
+    /// The `User-Agent` sent with each request: `uv/<version>`, followed by a JSON blob of
+    /// installer metadata (the Python version, platform, and whether uv appears to be running in
+    /// CI) in the [Linehaul](https://github.com/pypi/linehaul-cloud-function) format used by pip,
+    /// if markers were provided.
+    ///
+    /// The metadata can be omitted by setting `UV_NO_USER_AGENT_METADATA`, in which case the
+    /// `User-Agent` is just `uv/<version>`.
+    pub fn user_agent(&self) -> String {
+        let mut user_agent = format!("uv/{}", version());
 
         // Add linehaul metadata.
         if let Some(markers) = self.markers {
-            let linehaul = LineHaul::new(markers, self.platform);
-            if let Ok(output) = serde_json::to_string(&linehaul) {
-                user_agent_string += &format!(" {}", output);
+            if env::var_os("UV_NO_USER_AGENT_METADATA").is_some_and(|value| !value.is_empty()) {
+                debug!("Omitting installer metadata from User-Agent");
+            } else {
+                let linehaul = LineHaul::new(markers, self.platform);
+                if let Ok(output) = serde_json::to_string(&linehaul) {
+                    user_agent += &format!(" {output}");
+                }
             }
         }
 
+        user_agent
+    }
+
+    pub fn is_offline(&self) -> bool {
+        matches!(self.connectivity, Connectivity::Offline)
+    }
+
+    pub fn build(&self) -> BaseClient {
+        let user_agent_string = self.user_agent();
+        debug!("Using User-Agent: {user_agent_string}");
+
         // Timeout options, matching https://doc.rust-lang.org/nightly/cargo/reference/config.html#httptimeout
         // `UV_REQUEST_TIMEOUT` is provided for backwards compatibility with v0.1.6
         let default_timeout = 5 * 60;
@@ -140,19 +215,53 @@ impl<'a> BaseClientBuilder<'a> {
                 }
                 path_exists
             });
+            let options = &self.connection_options;
+            debug!("Using connection options: {options:?}");
+
             // Load the TLS configuration.
-            let tls = tls::load(if self.native_tls || ssl_cert_file_exists {
-                Roots::Native
-            } else {
-                Roots::Webpki
-            })
+            let tls = tls::load(
+                if self.native_tls || ssl_cert_file_exists {
+                    Roots::Native
+                } else {
+                    Roots::Webpki
+                },
+                options.http2,
+            )
             .expect("Failed to load TLS configuration.");
 
             let client_core = ClientBuilder::new()
                 .user_agent(user_agent_string)
-                .pool_max_idle_per_host(20)
+                .pool_max_idle_per_host(options.max_idle_per_host)
+                .pool_idle_timeout(options.idle_timeout)
                 .timeout(std::time::Duration::from_secs(timeout))
-                .use_preconfigured_tls(tls);
+                .use_preconfigured_tls(tls)
+                .dns_resolver(Arc::new(CachingResolver::new(
+                    options.dns_cache_ttl,
+                    options.prefer_ipv4,
+                )));
+
+            let client_core = if let Some(connect_timeout) = options.connect_timeout {
+                client_core.connect_timeout(connect_timeout)
+            } else {
+                client_core
+            };
+
+            let client_core = if options.http2 {
+                client_core
+            } else {
+                client_core.http1_only()
+            };
+
+            // Configure an explicit proxy, which replaces any proxies read from the environment.
+            let client_core = if let Some(proxy) = &self.proxy {
+                debug!("Using proxy: {proxy}");
+                let proxy = Proxy::all(proxy.as_str())
+                    .expect("Failed to configure proxy.")
+                    .no_proxy(NoProxy::from_env());
+                client_core.proxy(proxy)
+            } else {
+                client_core
+            };
 
             client_core.build().expect("Failed to build HTTP client.")
         });
@@ -163,13 +272,34 @@ impl<'a> BaseClientBuilder<'a> {
                 let client = reqwest_middleware::ClientBuilder::new(client.clone());
 
                 // Initialize the retry strategy.
-                let retry_policy =
-                    ExponentialBackoff::builder().build_with_max_retries(self.retries);
-                let retry_strategy = RetryTransientMiddleware::new_with_policy(retry_policy);
-                let client = client.with(retry_strategy);
+                let client = client.with(RetryMiddleware::new(self.retry_policy.clone()));
+
+                // Initialize the mirror middleware, prior to authentication, such that
+                // credentials are determined for the mirror rather than the index.
+                let client = match self.index_mirrors.clone() {
+                    Some((index, mirrors)) => client.with(MirrorMiddleware::new(index, mirrors)),
+                    None => client,
+                };
 
                 // Initialize the authentication middleware to set headers.
-                let client = client.with(AuthMiddleware::new(self.keyring_provider));
+                let auth = AuthMiddleware::new(self.keyring_provider);
+                let auth = match self.credential_provider.clone() {
+                    Some(credential_provider) => auth.with_credential_provider(credential_provider),
+                    None => auth,
+                };
+                let client = client.with(auth);
+
+                // Initialize the trace middleware last, such that every attempt is recorded as
+                // sent over the network.
+                let client = if HttpTrace::is_enabled() {
+                    client.with(TraceMiddleware)
+                } else {
+                    client
+                };
+
+                // Resolve `s3://` and `gs://` URLs after tracing, such that traces record the
+                // bucket URL rather than a presigned URL.
+                let client = client.with(ObjectStoreMiddleware::from_env());
 
                 client.build()
             }
@@ -182,6 +312,7 @@ impl<'a> BaseClientBuilder<'a> {
             connectivity: self.connectivity,
             client,
             timeout,
+            read_timeout: self.connection_options.read_timeout,
         }
     }
 }
@@ -195,6 +326,8 @@ pub struct BaseClient {
     connectivity: Connectivity,
     /// Configured client timeout, in seconds.
     timeout: u64,
+    /// Configured read timeout, if any.
+    read_timeout: Option<Duration>,
 }
 
 impl BaseClient {
@@ -212,6 +345,27 @@ impl BaseClient {
     pub fn connectivity(&self) -> Connectivity {
         self.connectivity
     }
+
+    /// Stream the body of the given response, failing if no data is received within the
+    /// configured read timeout.
+    ///
+    /// Request timeouts are reported in terms of the configured client timeout.
+    pub fn bytes_stream(&self, response: Response) -> BoxStream<'static, io::Result<Bytes>> {
+        let timeout = self.timeout;
+        let stream = response.bytes_stream().map_err(move |err| {
+            if err.is_timeout() {
+                io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    format!(
+                        "Failed to download distribution due to network timeout. Try increasing UV_HTTP_TIMEOUT (current value: {timeout}s)."
+                    ),
+                )
+            } else {
+                io::Error::new(io::ErrorKind::Other, err)
+            }
+        });
+        with_read_timeout(stream, self.read_timeout)
+    }
 }
 
 // To avoid excessively verbose call chains, as the [`BaseClient`] is often nested within other client types.
@@ -5,15 +5,17 @@ use reqwest::{Request, Response};
 use rkyv::util::AlignedVec;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use task_local_extensions::Extensions;
 use tracing::{debug, info_span, instrument, trace, warn, Instrument};
 
-use uv_cache::{CacheEntry, Freshness};
+use uv_cache::{CacheEntry, CacheMetrics, Freshness};
 use uv_fs::write_atomic;
 
 use crate::BaseClient;
 use crate::{
     httpcache::{AfterResponse, BeforeRequest, CachePolicy, CachePolicyBuilder},
     rkyvutil::OwnedArchive,
+    trace::{CacheDisposition, HttpTrace},
     Error, ErrorKind,
 };
 
@@ -136,7 +138,7 @@ impl From<Freshness> for CacheControl {
     fn from(value: Freshness) -> Self {
         match value {
             Freshness::Fresh => Self::None,
-            Freshness::Stale => Self::MustRevalidate,
+            Freshness::Stale | Freshness::Revalidate => Self::MustRevalidate,
             Freshness::Missing => Self::None,
         }
     }
@@ -158,16 +160,30 @@ impl From<Freshness> for CacheControl {
 /// Again unlike `http-cache`, the caller gets full control over the cache key with the assumption
 /// that it's a file.
 #[derive(Debug, Clone)]
-pub struct CachedClient(BaseClient);
+pub struct CachedClient {
+    client: BaseClient,
+    /// Whether to strictly follow the HTTP caching semantics of RFC 9111.
+    strict: bool,
+}
 
 impl CachedClient {
     pub fn new(client: BaseClient) -> Self {
-        Self(client)
+        Self {
+            client,
+            strict: false,
+        }
+    }
+
+    /// Strictly follow the HTTP caching semantics of RFC 9111, rather than favoring cached
+    /// responses where the RFC leaves room for interpretation.
+    #[must_use]
+    pub fn with_strict(self, strict: bool) -> Self {
+        Self { strict, ..self }
     }
 
     /// The base client
     pub fn uncached(&self) -> BaseClient {
-        self.0.clone()
+        self.client.clone()
     }
 
     /// Make a cached request with a custom response transformation
@@ -232,6 +248,7 @@ impl CachedClient {
             Some(cached) => self.send_cached(req, cache_control, cached).boxed().await?,
             None => {
                 debug!("No cache entry for: {}", req.url());
+                CacheMetrics::record_miss();
                 let (response, cache_policy) = self.fresh_request(req).await?;
                 CachedResponse::ModifiedOrNew {
                     response,
@@ -240,18 +257,22 @@ impl CachedClient {
             }
         };
         match cached_response {
-            CachedResponse::FreshCache(cached) => match Payload::from_aligned_bytes(cached.data) {
-                Ok(payload) => Ok(payload),
-                Err(err) => {
-                    warn!(
-                        "Broken fresh cache entry (for payload) at {}, removing: {err}",
-                        cache_entry.path().display()
-                    );
-                    self.resend_and_heal_cache(fresh_req, cache_entry, response_callback)
-                        .await
+            CachedResponse::FreshCache(cached) => {
+                CacheMetrics::record_hit(cached.data.len() as u64);
+                match Payload::from_aligned_bytes(cached.data) {
+                    Ok(payload) => Ok(payload),
+                    Err(err) => {
+                        warn!(
+                            "Broken fresh cache entry (for payload) at {}, removing: {err}",
+                            cache_entry.path().display()
+                        );
+                        self.resend_and_heal_cache(fresh_req, cache_entry, response_callback)
+                            .await
+                    }
                 }
-            },
+            }
             CachedResponse::NotModified { cached, new_policy } => {
+                CacheMetrics::record_hit(cached.data.len() as u64);
                 let refresh_cache =
                     info_span!("refresh_cache", file = %cache_entry.path().display());
                 async {
@@ -332,6 +353,11 @@ impl CachedClient {
             .await
             .map_err(|err| CachedClientError::Callback(err))?;
         let Some(cache_policy) = cache_policy else {
+            // In strict mode, a response that can't be stored (e.g., due to `no-store`) also
+            // invalidates any previously cached response.
+            if self.strict {
+                let _ = fs_err::tokio::remove_file(&cache_entry.path()).await;
+            }
             return Ok(data.into_target());
         };
         async {
@@ -351,19 +377,20 @@ impl CachedClient {
 
     #[instrument(name="read_and_parse_cache", skip_all, fields(file = %cache_entry.path().display()))]
     async fn read_cache(cache_entry: &CacheEntry) -> Option<DataWithCachePolicy> {
-        match DataWithCachePolicy::from_path_async(cache_entry.path()).await {
+        let path = cache_entry.read_path();
+        match DataWithCachePolicy::from_path_async(path).await {
             Ok(data) => Some(data),
             Err(err) => {
                 // When we know the cache entry doesn't exist, then things are
                 // normal and we shouldn't emit a WARN.
                 if err.is_file_not_exists() {
-                    trace!("No cache entry exists for {}", cache_entry.path().display());
+                    trace!("No cache entry exists for {}", path.display());
                 } else {
-                    warn!(
-                        "Broken cache policy entry at {}, removing: {err}",
-                        cache_entry.path().display()
-                    );
-                    let _ = fs_err::tokio::remove_file(&cache_entry.path()).await;
+                    warn!("Broken cache policy entry at {}: {err}", path.display());
+                    // Never remove entries from the fallback cache, which is read-only.
+                    if path == cache_entry.path() {
+                        let _ = fs_err::tokio::remove_file(path).await;
+                    }
                 }
                 None
             }
@@ -390,19 +417,38 @@ impl CachedClient {
                 );
             }
         }
-        Ok(match cached.cache_policy.before_request(&mut req) {
+        let before_request = cached.cache_policy.before_request(&mut req, self.strict);
+        Ok(match before_request {
             BeforeRequest::Fresh => {
                 debug!("Found fresh response for: {}", req.url());
+                HttpTrace::record_cached(&req, cached.cache_policy.status(), CacheDisposition::Hit);
                 CachedResponse::FreshCache(cached)
             }
             BeforeRequest::Stale(new_cache_policy_builder) => match cache_control {
                 CacheControl::None | CacheControl::MustRevalidate => {
                     debug!("Found stale response for: {}", req.url());
+                    CacheMetrics::record_revalidation();
+                    self.send_cached_handle_stale(req, cached, new_cache_policy_builder)
+                        .await?
+                }
+                CacheControl::AllowStale
+                    if self.strict && !cached.cache_policy.allows_disconnected_stale() =>
+                {
+                    debug!(
+                        "Found stale response that must be revalidated for: {}",
+                        req.url()
+                    );
+                    CacheMetrics::record_revalidation();
                     self.send_cached_handle_stale(req, cached, new_cache_policy_builder)
                         .await?
                 }
                 CacheControl::AllowStale => {
                     debug!("Found stale (but allowed) response for: {}", req.url());
+                    HttpTrace::record_cached(
+                        &req,
+                        cached.cache_policy.status(),
+                        CacheDisposition::Stale,
+                    );
                     CachedResponse::FreshCache(cached)
                 }
             },
@@ -429,9 +475,11 @@ impl CachedClient {
     ) -> Result<CachedResponse, Error> {
         let url = req.url().clone();
         debug!("Sending revalidation request for: {url}");
+        let mut extensions = Extensions::new();
+        extensions.insert(CacheDisposition::Revalidate);
         let response = self
-            .0
-            .execute(req)
+            .client
+            .execute_with_extensions(req, &mut extensions)
             .instrument(info_span!("revalidation_request", url = url.as_str()))
             .await
             .map_err(ErrorKind::from)?
@@ -468,9 +516,11 @@ impl CachedClient {
     ) -> Result<(Response, Option<Box<CachePolicy>>), Error> {
         trace!("Sending fresh {} request for {}", req.method(), req.url());
         let cache_policy_builder = CachePolicyBuilder::new(&req);
+        let mut extensions = Extensions::new();
+        extensions.insert(CacheDisposition::Miss);
         let response = self
-            .0
-            .execute(req)
+            .client
+            .execute_with_extensions(req, &mut extensions)
             .await
             .map_err(ErrorKind::from)?
             .error_for_status()
@@ -0,0 +1,186 @@
+use std::env;
+use std::io;
+use std::str::FromStr;
+use std::time::Duration;
+
+use futures::stream::BoxStream;
+use futures::{Stream, StreamExt};
+
+use uv_warnings::warn_user_once;
+
+/// Settings for the connections opened by the HTTP client.
+///
+/// The defaults suit typical connections to public indexes; very fast internal networks benefit
+/// from larger pools, while very slow links benefit from more patient timeouts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectionOptions {
+    /// The maximum number of idle connections to keep open to each host.
+    pub max_idle_per_host: usize,
+    /// How long an idle connection is kept open for reuse, or `None` to keep idle connections open
+    /// indefinitely.
+    pub idle_timeout: Option<Duration>,
+    /// The timeout for establishing a connection, or `None` to only bound the connection by the
+    /// overall request timeout.
+    pub connect_timeout: Option<Duration>,
+    /// The maximum time to wait for more data while reading a response body, or `None` to only
+    /// bound the read by the overall request timeout.
+    pub read_timeout: Option<Duration>,
+    /// Whether to negotiate HTTP/2 with servers that support it. If disabled (the default), only
+    /// HTTP/1.1 is used.
+    pub http2: bool,
+    /// How long to reuse the resolved addresses of each host, or `None` to resolve the host for
+    /// every connection.
+    pub dns_cache_ttl: Option<Duration>,
+    /// Whether to attempt IPv4 addresses before IPv6 addresses when connecting, for networks on
+    /// which IPv6 is advertised but broken.
+    pub prefer_ipv4: bool,
+}
+
+impl Default for ConnectionOptions {
+    fn default() -> Self {
+        Self {
+            max_idle_per_host: 20,
+            idle_timeout: Some(Duration::from_secs(90)),
+            connect_timeout: None,
+            read_timeout: None,
+            http2: false,
+            dns_cache_ttl: Some(Duration::from_secs(300)),
+            prefer_ipv4: false,
+        }
+    }
+}
+
+impl ConnectionOptions {
+    /// Read the connection settings from the environment, via `UV_HTTP_MAX_IDLE_PER_HOST`,
+    /// `UV_HTTP_IDLE_TIMEOUT`, `UV_HTTP_CONNECT_TIMEOUT`, `UV_HTTP_READ_TIMEOUT`, `UV_HTTP2`,
+    /// `UV_DNS_CACHE_TTL`, and `UV_PREFER_IPV4`, falling back to the defaults for any that are
+    /// unset or invalid.
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        Self {
+            max_idle_per_host: parse_env::<usize>("UV_HTTP_MAX_IDLE_PER_HOST", "integer")
+                .unwrap_or(default.max_idle_per_host),
+            idle_timeout: parse_env::<u64>("UV_HTTP_IDLE_TIMEOUT", "integer number of seconds")
+                .map_or(default.idle_timeout, |seconds| {
+                    Some(Duration::from_secs(seconds))
+                }),
+            connect_timeout: parse_env::<u64>(
+                "UV_HTTP_CONNECT_TIMEOUT",
+                "integer number of seconds",
+            )
+            .map_or(default.connect_timeout, |seconds| {
+                Some(Duration::from_secs(seconds))
+            }),
+            read_timeout: parse_env::<u64>("UV_HTTP_READ_TIMEOUT", "integer number of seconds")
+                .map_or(default.read_timeout, |seconds| {
+                    Some(Duration::from_secs(seconds))
+                }),
+            http2: parse_bool_env("UV_HTTP2").unwrap_or(default.http2),
+            // A TTL of zero disables the cache.
+            dns_cache_ttl: parse_env::<u64>("UV_DNS_CACHE_TTL", "integer number of seconds")
+                .map_or(default.dns_cache_ttl, |seconds| {
+                    (seconds > 0).then_some(Duration::from_secs(seconds))
+                }),
+            prefer_ipv4: parse_bool_env("UV_PREFER_IPV4").unwrap_or(default.prefer_ipv4),
+        }
+    }
+}
+
+/// Fail the given stream with an [`io::ErrorKind::TimedOut`] error if the next item isn't
+/// received within the given timeout.
+pub(crate) fn with_read_timeout<'a, T: Send + 'a>(
+    stream: impl Stream<Item = io::Result<T>> + Send + 'a,
+    timeout: Option<Duration>,
+) -> BoxStream<'a, io::Result<T>> {
+    let Some(timeout) = timeout else {
+        return stream.boxed();
+    };
+    futures::stream::unfold(Some(stream.boxed()), move |stream| async move {
+        let mut stream = stream?;
+        match tokio::time::timeout(timeout, stream.next()).await {
+            Ok(item) => item.map(|item| (item, Some(stream))),
+            Err(_) => Some((
+                Err(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    format!(
+                        "Failed to read response as no data was received for {}s. Try increasing UV_HTTP_READ_TIMEOUT (current value: {}s).",
+                        timeout.as_secs(),
+                        timeout.as_secs()
+                    ),
+                )),
+                None,
+            )),
+        }
+    })
+    .boxed()
+}
+
+/// Parse the given environment variable, warning if it's set to an invalid value.
+fn parse_env<T: FromStr>(name: &str, expected: &str) -> Option<T> {
+    let value = env::var(name).ok()?;
+    match value.parse::<T>() {
+        Ok(value) => Some(value),
+        Err(_) => {
+            warn_user_once!("Ignoring invalid value from environment for {name}. Expected {expected}, got \"{value}\".");
+            None
+        }
+    }
+}
+
+/// Parse the given environment variable as a boolean, warning if it's set to an invalid value.
+fn parse_bool_env(name: &str) -> Option<bool> {
+    let value = env::var(name).ok()?;
+    match value.to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "0" | "false" | "no" | "off" => Some(false),
+        _ => {
+            warn_user_once!("Ignoring invalid value from environment for {name}. Expected boolean, got \"{value}\".");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::time::Duration;
+
+    use futures::{stream, StreamExt};
+
+    use crate::connection::{with_read_timeout, ConnectionOptions};
+
+    #[test]
+    fn http2_opt_in() {
+        assert!(!ConnectionOptions::default().http2);
+    }
+
+    #[tokio::test]
+    async fn read_timeout_complete() {
+        let items = with_read_timeout(
+            stream::iter([Ok(1), Ok(2)]),
+            Some(Duration::from_millis(100)),
+        )
+        .collect::<Vec<io::Result<i32>>>()
+        .await;
+        assert_eq!(
+            items.into_iter().map(Result::unwrap).collect::<Vec<_>>(),
+            [1, 2]
+        );
+    }
+
+    #[tokio::test]
+    async fn read_timeout_stalled() {
+        let items = with_read_timeout(
+            stream::iter([Ok(1)]).chain(stream::pending()),
+            Some(Duration::from_millis(100)),
+        )
+        .collect::<Vec<io::Result<i32>>>()
+        .await;
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].as_ref().unwrap(), &1);
+        assert_eq!(
+            items[1].as_ref().unwrap_err().kind(),
+            io::ErrorKind::TimedOut
+        );
+    }
+}
@@ -0,0 +1,128 @@
+use std::net::SocketAddr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use hyper::client::connect::dns::Name;
+use reqwest::dns::{Addrs, Resolve, Resolving};
+use rustc_hash::FxHashMap;
+use tracing::trace;
+
+/// The resolved addresses for a host, along with the time at which they were resolved.
+type CacheEntry = (Vec<SocketAddr>, Instant);
+
+/// The resolved addresses for each host, shared across every client in the process.
+static CACHE: Mutex<Option<FxHashMap<String, CacheEntry>>> = Mutex::new(None);
+
+/// A DNS resolver that caches the addresses for each host for a fixed period, such that batches of
+/// requests to the same index don't re-resolve its hostname, and optionally orders IPv4 addresses
+/// ahead of IPv6 addresses.
+///
+/// When connecting, the addresses of the first family are attempted first, with a fallback to the
+/// other family after a short delay ("Happy Eyeballs"). Preferring IPv4 thus avoids stalling on
+/// networks where IPv6 is advertised but broken.
+#[derive(Debug, Clone)]
+pub(crate) struct CachingResolver {
+    /// How long to reuse resolved addresses, or `None` to resolve every connection.
+    ttl: Option<Duration>,
+    /// Whether to attempt IPv4 addresses before IPv6 addresses.
+    prefer_ipv4: bool,
+}
+
+impl CachingResolver {
+    pub(crate) fn new(ttl: Option<Duration>, prefer_ipv4: bool) -> Self {
+        Self { ttl, prefer_ipv4 }
+    }
+
+    /// Return the cached addresses for the host, if they haven't expired.
+    fn cached(&self, host: &str) -> Option<Vec<SocketAddr>> {
+        let ttl = self.ttl?;
+        let cache = CACHE.lock().unwrap();
+        let (addrs, resolved) = cache.as_ref()?.get(host)?;
+        (resolved.elapsed() < ttl).then(|| addrs.clone())
+    }
+
+    fn insert(&self, host: String, addrs: Vec<SocketAddr>) {
+        if self.ttl.is_some() {
+            CACHE
+                .lock()
+                .unwrap()
+                .get_or_insert_with(FxHashMap::default)
+                .insert(host, (addrs, Instant::now()));
+        }
+    }
+
+    /// Order the addresses by family, if IPv4 is preferred, retaining the resolver's order within
+    /// each family.
+    fn sort(&self, mut addrs: Vec<SocketAddr>) -> Vec<SocketAddr> {
+        if self.prefer_ipv4 {
+            addrs.sort_by_key(SocketAddr::is_ipv6);
+        }
+        addrs
+    }
+}
+
+impl Resolve for CachingResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let resolver = self.clone();
+        Box::pin(async move {
+            let host = name.as_str().to_string();
+            if let Some(addrs) = resolver.cached(&host) {
+                trace!("Using cached addresses for {host}");
+                return Ok(Box::new(resolver.sort(addrs).into_iter()) as Addrs);
+            }
+
+            // The port is replaced with the port of the request URL by the connector.
+            let addrs = tokio::net::lookup_host((host.as_str(), 0))
+                .await?
+                .collect::<Vec<_>>();
+            trace!("Resolved {host} to {addrs:?}");
+            resolver.insert(host, addrs.clone());
+            Ok(Box::new(resolver.sort(addrs).into_iter()) as Addrs)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+    use std::time::Duration;
+
+    use super::CachingResolver;
+
+    #[test]
+    fn prefer_ipv4() {
+        let addrs: Vec<SocketAddr> = vec![
+            "[2001:db8::1]:0".parse().unwrap(),
+            "192.0.2.1:0".parse().unwrap(),
+            "[2001:db8::2]:0".parse().unwrap(),
+            "192.0.2.2:0".parse().unwrap(),
+        ];
+
+        let resolver = CachingResolver::new(None, false);
+        assert_eq!(resolver.sort(addrs.clone()), addrs);
+
+        let resolver = CachingResolver::new(None, true);
+        assert_eq!(
+            resolver.sort(addrs.clone()),
+            vec![addrs[1], addrs[3], addrs[0], addrs[2]]
+        );
+    }
+
+    #[test]
+    fn ttl() {
+        let addrs: Vec<SocketAddr> = vec!["192.0.2.1:0".parse().unwrap()];
+
+        // Without a TTL, nothing is cached.
+        let resolver = CachingResolver::new(None, false);
+        resolver.insert("uncached.example.com".to_string(), addrs.clone());
+        assert_eq!(resolver.cached("uncached.example.com"), None);
+
+        let resolver = CachingResolver::new(Some(Duration::from_secs(60)), false);
+        resolver.insert("cached.example.com".to_string(), addrs.clone());
+        assert_eq!(resolver.cached("cached.example.com"), Some(addrs.clone()));
+
+        // Expired entries are ignored.
+        let resolver = CachingResolver::new(Some(Duration::ZERO), false);
+        assert_eq!(resolver.cached("cached.example.com"), None);
+    }
+}
@@ -1,10 +1,11 @@
 use std::collections::btree_map::Entry;
 use std::collections::BTreeMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use futures::{FutureExt, StreamExt};
 use reqwest::Response;
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::{Deserialize, Serialize};
 use tracing::{debug, info_span, instrument, warn, Instrument};
 use url::Url;
 
@@ -18,7 +19,8 @@ use pep440_rs::Version;
 use pep508_rs::VerbatimUrl;
 use platform_tags::Tags;
 use pypi_types::Hashes;
-use uv_cache::{Cache, CacheBucket};
+use uv_cache::{Cache, CacheBucket, Freshness, Timestamp};
+use uv_fs::write_atomic_sync;
 use uv_normalize::PackageName;
 use uv_types::{NoBinary, NoBuild};
 
@@ -35,6 +37,9 @@ pub enum FlatIndexError {
     FindLinksUrl(Url, #[source] Error),
 }
 
+/// The maximum depth to which nested pages are followed from a `--find-links` URL.
+const MAX_PAGE_DEPTH: usize = 8;
+
 #[derive(Debug, Default, Clone)]
 pub struct FlatIndexEntries {
     /// The list of `--find-links` entries.
@@ -101,7 +106,8 @@ impl<'a> FlatIndexClient<'a> {
         let mut fetches = futures::stream::iter(indexes)
             .map(|index| async move {
                 let entries = match index {
-                    FlatIndexLocation::Path(path) => Self::read_from_directory(path)
+                    FlatIndexLocation::Path(path) => self
+                        .read_from_directory(path)
                         .map_err(|err| FlatIndexError::FindLinksDirectory(path.clone(), err))?,
                     FlatIndexLocation::Url(url) => self
                         .read_from_url(url)
@@ -130,7 +136,73 @@ impl<'a> FlatIndexClient<'a> {
     }
 
     /// Read a flat remote index from a `--find-links` URL.
+    ///
+    /// Any pages nested beneath the URL (e.g., `./cu121/` linked from `https://example.com/wheels/`)
+    /// are read too, such that the index can be split across subpaths.
     async fn read_from_url(&self, url: &Url) -> Result<FlatIndexEntries, Error> {
+        let index_url = IndexUrl::Url(VerbatimUrl::from_url(url.clone()));
+
+        // Read the root page, propagating any errors.
+        let Some(page) = self.read_page(url).await? else {
+            return Ok(FlatIndexEntries::offline());
+        };
+
+        let mut visited = FxHashSet::default();
+        visited.insert(url.clone());
+
+        let mut subpages = page.subpages().collect::<Vec<_>>();
+        let mut files = page.files;
+        let mut offline = false;
+        let mut depth = 0;
+        loop {
+            subpages.retain(|subpage| visited.insert(subpage.clone()));
+            if subpages.is_empty() {
+                break;
+            }
+            if depth == MAX_PAGE_DEPTH {
+                warn!("Not following pages nested more than {MAX_PAGE_DEPTH} levels deep in `--find-links` URL: {url}");
+                break;
+            }
+            depth += 1;
+
+            // Read the nested pages. Unlike the root page, a nested page that can't be read is
+            // skipped, rather than failing the entire index.
+            let mut fetches = futures::stream::iter(std::mem::take(&mut subpages))
+                .map(|subpage| async move {
+                    let result = self.read_page(&subpage).await;
+                    (subpage, result)
+                })
+                .buffered(16);
+            while let Some((subpage, result)) = fetches.next().await {
+                match result {
+                    Ok(Some(page)) => {
+                        subpages.extend(page.subpages());
+                        files.extend(page.files);
+                    }
+                    Ok(None) => offline = true,
+                    Err(err) => warn!("Skipping nested `--find-links` page {subpage}: {err}"),
+                }
+            }
+        }
+
+        let files = files
+            .into_iter()
+            .filter_map(|file| {
+                Some((
+                    DistFilename::try_from_normalized_filename(&file.filename)?,
+                    file,
+                    index_url.clone(),
+                ))
+            })
+            .collect();
+        let mut entries = FlatIndexEntries::from_entries(files);
+        entries.offline = offline;
+        Ok(entries)
+    }
+
+    /// Read a single page of a flat remote index, returning `None` if the page could not be
+    /// fetched due to a lack of network connectivity.
+    async fn read_page(&self, url: &Url) -> Result<Option<FlatIndexPage>, Error> {
         let cache_entry = self.cache.entry(
             CacheBucket::FlatIndex,
             "html",
@@ -163,9 +235,16 @@ impl<'a> FlatIndexClient<'a> {
                 let SimpleHtml { base, files } = SimpleHtml::parse(&text, &url)
                     .map_err(|err| Error::from_html_err(err, url.clone()))?;
 
+                let mut subpages = Vec::new();
                 let files: Vec<File> = files
                     .into_iter()
                     .filter_map(|file| {
+                        // Links to directories nested beneath this page are read as part of the
+                        // index, rather than as files.
+                        if let Some(subpage) = nested_page(&url, base.as_url(), &file.url) {
+                            subpages.push(subpage.to_string());
+                            return None;
+                        }
                         match File::try_from(file, base.as_url()) {
                             Ok(file) => Some(file),
                             Err(err) => {
@@ -176,7 +255,7 @@ impl<'a> FlatIndexClient<'a> {
                         }
                     })
                     .collect();
-                Ok::<Vec<File>, CachedClientError<Error>>(files)
+                Ok::<FlatIndexPage, CachedClientError<Error>>(FlatIndexPage { files, subpages })
             }
             .boxed()
             .instrument(info_span!("parse_flat_index_html", url = % url))
@@ -192,45 +271,79 @@ impl<'a> FlatIndexClient<'a> {
             )
             .await;
         match response {
-            Ok(files) => {
-                let index_url = IndexUrl::Url(VerbatimUrl::from_url(url.clone()));
-                let files = files
-                    .into_iter()
-                    .filter_map(|file| {
-                        Some((
-                            DistFilename::try_from_normalized_filename(&file.filename)?,
-                            file,
-                            index_url.clone(),
-                        ))
-                    })
-                    .collect();
-                Ok(FlatIndexEntries::from_entries(files))
-            }
-            Err(CachedClientError::Client(err)) if err.is_offline() => {
-                Ok(FlatIndexEntries::offline())
-            }
+            Ok(page) => Ok(Some(page)),
+            Err(CachedClientError::Client(err)) if err.is_offline() => Ok(None),
             Err(err) => Err(err.into()),
         }
     }
 
-    /// Read a flat remote index from a `--find-links` directory.
-    fn read_from_directory(path: &PathBuf) -> Result<FlatIndexEntries, std::io::Error> {
+    /// Read a flat remote index from a `--find-links` directory, including any nested directories.
+    ///
+    /// The scan is cached, and reused for as long as none of the scanned directories have been
+    /// modified.
+    fn read_from_directory(&self, path: &Path) -> Result<FlatIndexEntries, std::io::Error> {
         // Absolute paths are required for the URL conversion.
         let path = fs_err::canonicalize(path)?;
         let index_url = IndexUrl::Url(VerbatimUrl::from_path(&path));
 
-        let mut dists = Vec::new();
-        for entry in fs_err::read_dir(path)? {
-            let entry = entry?;
-            let metadata = entry.metadata()?;
-            if !metadata.is_file() {
-                continue;
+        let cache_entry = self.cache.entry(
+            CacheBucket::FlatIndex,
+            "directory",
+            format!("{}.msgpack", cache_key::digest(&path)),
+        );
+
+        // Read from the cache, if none of the scanned directories have changed.
+        let cached = if self
+            .cache
+            .freshness(&cache_entry, None)
+            .is_ok_and(Freshness::is_fresh_or_revalidate)
+        {
+            match fs_err::read(cache_entry.read_path()) {
+                Ok(data) => match rmp_serde::from_slice::<DirectoryScan>(&data) {
+                    Ok(scan) if scan.is_current() => Some(scan),
+                    Ok(_) => {
+                        debug!(
+                            "Ignoring stale cached scan of `--find-links` directory: {}",
+                            path.display()
+                        );
+                        None
+                    }
+                    Err(err) => {
+                        warn!(
+                            "Broken cache entry at {}, removing: {err}",
+                            cache_entry.path().display()
+                        );
+                        let _ = fs_err::remove_file(cache_entry.path());
+                        None
+                    }
+                },
+                Err(_) => None,
             }
+        } else {
+            None
+        };
+
+        let scan = if let Some(scan) = cached {
+            scan
+        } else {
+            let scan = DirectoryScan::scan(&path)?;
+            fs_err::create_dir_all(cache_entry.dir())?;
+            write_atomic_sync(
+                cache_entry.path(),
+                rmp_serde::to_vec(&scan).map_err(std::io::Error::other)?,
+            )?;
+            scan
+        };
 
-            let Ok(filename) = entry.file_name().into_string() else {
-                warn!(
-                    "Skipping non-UTF-8 filename in `--find-links` directory: {}",
-                    entry.file_name().to_string_lossy()
+        let mut dists = Vec::new();
+        for path in scan.files {
+            let Some(filename) = path.file_name().and_then(|filename| filename.to_str()) else {
+                continue;
+            };
+            let Some(dist_filename) = DistFilename::try_from_normalized_filename(filename) else {
+                debug!(
+                    "Ignoring `--find-links` entry (expected a wheel or source distribution filename): {}",
+                    path.display()
                 );
                 continue;
             };
@@ -242,20 +355,120 @@ impl<'a> FlatIndexClient<'a> {
                 requires_python: None,
                 size: None,
                 upload_time_utc_ms: None,
-                url: FileLocation::Path(entry.path().clone()),
+                url: FileLocation::Path(path.clone()),
                 yanked: None,
             };
+            dists.push((dist_filename, file, index_url.clone()));
+        }
+        Ok(FlatIndexEntries::from_entries(dists))
+    }
+}
 
-            let Some(filename) = DistFilename::try_from_normalized_filename(&filename) else {
-                debug!(
-                    "Ignoring `--find-links` entry (expected a wheel or source distribution filename): {}",
-                    entry.path().display()
-                );
-                continue;
+/// A single page of a flat remote index.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct FlatIndexPage {
+    /// The files linked from the page.
+    files: Vec<File>,
+    /// The URLs of the pages nested beneath the page, to be read as part of the same index.
+    subpages: Vec<String>,
+}
+
+impl FlatIndexPage {
+    /// Return the URLs of the pages nested beneath the page.
+    fn subpages(&self) -> impl Iterator<Item = Url> + '_ {
+        self.subpages
+            .iter()
+            .filter_map(|subpage| Url::parse(subpage).ok())
+    }
+}
+
+/// Returns the URL of the nested page referenced by `href`, if it links to a directory beneath
+/// the page at `url` (as in `<a href="cu121/">`), or `None` if it links to anything else (e.g., a
+/// file, or a parent directory).
+fn nested_page(url: &Url, base: &Url, href: &str) -> Option<Url> {
+    let mut subpage = base.join(href).ok()?;
+    subpage.set_fragment(None);
+    subpage.set_query(None);
+    if !subpage.path().ends_with('/') {
+        return None;
+    }
+
+    // The nested page must be strictly beneath the directory containing the current page.
+    let parent = url.join("./").ok()?;
+    // Compare the components of the origin directly, as non-HTTP URLs (like `s3://`) have opaque
+    // origins, which never compare as equal.
+    if subpage.scheme() != parent.scheme()
+        || subpage.host_str() != parent.host_str()
+        || subpage.port_or_known_default() != parent.port_or_known_default()
+        || subpage.path().len() <= parent.path().len()
+        || !subpage.path().starts_with(parent.path())
+    {
+        return None;
+    }
+
+    Some(subpage)
+}
+
+/// The distributions found in a `--find-links` directory and its nested directories.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct DirectoryScan {
+    /// Every directory that was scanned, along with its timestamp at the time of the scan.
+    directories: Vec<(PathBuf, Timestamp)>,
+    /// The files found in the scanned directories.
+    files: Vec<PathBuf>,
+}
+
+impl DirectoryScan {
+    /// Scan the given directory, recursively, skipping any hidden directories.
+    fn scan(root: &Path) -> Result<Self, std::io::Error> {
+        let mut directories = Vec::new();
+        let mut files = Vec::new();
+
+        let walker = walkdir::WalkDir::new(root)
+            .follow_links(true)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| {
+                entry.depth() == 0
+                    || !entry.file_type().is_dir()
+                    || !entry.file_name().to_string_lossy().starts_with('.')
+            });
+        for entry in walker {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(err) if err.loop_ancestor().is_some() => {
+                    warn!("Skipping symlink cycle in `--find-links` directory: {err}");
+                    continue;
+                }
+                Err(err) => return Err(err.into()),
             };
-            dists.push((filename, file, index_url.clone()));
+
+            if entry.file_type().is_dir() {
+                // Record the timestamp before the directory's contents are read, such that any
+                // concurrent modifications invalidate the scan.
+                let metadata = entry.metadata().map_err(std::io::Error::from)?;
+                let timestamp = Timestamp::from_metadata(&metadata);
+                directories.push((entry.into_path(), timestamp));
+            } else if entry.file_type().is_file() {
+                if entry.file_name().to_str().is_none() {
+                    warn!(
+                        "Skipping non-UTF-8 filename in `--find-links` directory: {}",
+                        entry.file_name().to_string_lossy()
+                    );
+                    continue;
+                }
+                files.push(entry.into_path());
+            }
         }
-        Ok(FlatIndexEntries::from_entries(dists))
+
+        Ok(Self { directories, files })
+    }
+
+    /// Returns `true` if none of the scanned directories have been modified since the scan.
+    fn is_current(&self) -> bool {
+        self.directories.iter().all(|(directory, timestamp)| {
+            Timestamp::from_path(directory).is_ok_and(|current| current == *timestamp)
+        })
     }
 }
 
@@ -427,3 +640,84 @@ impl From<FlatDistributions> for BTreeMap<Version, PrioritizedDist> {
         distributions.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use url::Url;
+
+    use super::{nested_page, DirectoryScan};
+
+    #[test]
+    fn nested_pages() {
+        let url = Url::parse("https://example.com/wheels/index.html").unwrap();
+        let base = url.clone();
+
+        // Relative and absolute links to nested directories are followed.
+        assert_eq!(
+            nested_page(&url, &base, "cu121/").unwrap().as_str(),
+            "https://example.com/wheels/cu121/"
+        );
+        assert_eq!(
+            nested_page(&url, &base, "https://example.com/wheels/cpu/nightly/#top")
+                .unwrap()
+                .as_str(),
+            "https://example.com/wheels/cpu/nightly/"
+        );
+
+        // Files, parent directories, and other hosts are not.
+        assert!(nested_page(&url, &base, "torch-2.2.0-cp312-none-any.whl").is_none());
+        assert!(nested_page(&url, &base, "../").is_none());
+        assert!(nested_page(&url, &base, "./").is_none());
+        assert!(nested_page(&url, &base, "/other/").is_none());
+        assert!(nested_page(&url, &base, "https://mirror.example.com/wheels/cu121/").is_none());
+
+        // Object store listings link to nested prefixes within the same bucket.
+        let url = Url::parse("s3://bucket/wheels/").unwrap();
+        assert_eq!(
+            nested_page(&url, &url, "s3://bucket/wheels/nightly/")
+                .unwrap()
+                .as_str(),
+            "s3://bucket/wheels/nightly/"
+        );
+        assert!(nested_page(&url, &url, "s3://other/wheels/nightly/").is_none());
+    }
+
+    #[test]
+    fn directory_scan() {
+        let root = tempfile::tempdir().unwrap();
+        fs_err::create_dir_all(root.path().join("nested").join("deeper")).unwrap();
+        fs_err::create_dir_all(root.path().join(".hidden")).unwrap();
+        fs_err::write(root.path().join("a-1.0.0.tar.gz"), "").unwrap();
+        fs_err::write(
+            root.path()
+                .join("nested")
+                .join("deeper")
+                .join("b-1.0.0.tar.gz"),
+            "",
+        )
+        .unwrap();
+        fs_err::write(root.path().join(".hidden").join("c-1.0.0.tar.gz"), "").unwrap();
+
+        let scan = DirectoryScan::scan(root.path()).unwrap();
+        let files = scan
+            .files
+            .iter()
+            .map(|path| path.strip_prefix(root.path()).unwrap().to_path_buf())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            files,
+            vec![
+                PathBuf::from("a-1.0.0.tar.gz"),
+                Path::new("nested").join("deeper").join("b-1.0.0.tar.gz"),
+            ]
+        );
+        assert!(scan.is_current());
+
+        // Adding a file to a nested directory invalidates the scan.
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        fs_err::write(root.path().join("nested").join("d-1.0.0.tar.gz"), "").unwrap();
+        assert!(!scan.is_current());
+    }
+}
@@ -180,21 +180,10 @@ impl SimpleHtml {
             None
         };
 
-        // Extract the `data-dist-info-metadata` field, which should be set on
-        // the `data-dist-info-metadata` attribute.
-        let dist_info_metadata = if let Some(dist_info_metadata) =
-            link.attributes().get("data-dist-info-metadata").flatten()
-        {
-            let dist_info_metadata = std::str::from_utf8(dist_info_metadata.as_bytes())?;
-            let dist_info_metadata = html_escape::decode_html_entities(dist_info_metadata);
-            match dist_info_metadata.as_ref() {
-                "true" => Some(DistInfoMetadata::Bool(true)),
-                "false" => Some(DistInfoMetadata::Bool(false)),
-                fragment => Some(DistInfoMetadata::Hashes(Self::parse_hash(fragment)?)),
-            }
-        } else {
-            None
-        };
+        // Extract the `core-metadata` field, which should be set on the `data-core-metadata`
+        // attribute (PEP 714), falling back to the legacy `data-dist-info-metadata` attribute.
+        let core_metadata = Self::parse_dist_info_metadata(link, "data-core-metadata")?;
+        let dist_info_metadata = Self::parse_dist_info_metadata(link, "data-dist-info-metadata")?;
 
         // Extract the `yanked` field, which should be set on the `data-yanked`
         // attribute.
@@ -207,6 +196,7 @@ impl SimpleHtml {
         };
 
         Ok(File {
+            core_metadata,
             dist_info_metadata,
             yanked,
             requires_python,
@@ -217,6 +207,24 @@ impl SimpleHtml {
             upload_time: None,
         })
     }
+
+    /// Parse the availability of the core metadata from the given attribute of an anchor tag
+    /// (e.g., `data-core-metadata="sha256=..."`), if present.
+    fn parse_dist_info_metadata(
+        link: &HTMLTag,
+        attribute: &str,
+    ) -> Result<Option<DistInfoMetadata>, Error> {
+        let Some(dist_info_metadata) = link.attributes().get(attribute).flatten() else {
+            return Ok(None);
+        };
+        let dist_info_metadata = std::str::from_utf8(dist_info_metadata.as_bytes())?;
+        let dist_info_metadata = html_escape::decode_html_entities(dist_info_metadata);
+        match dist_info_metadata.as_ref() {
+            "true" => Ok(Some(DistInfoMetadata::Bool(true))),
+            "false" => Ok(Some(DistInfoMetadata::Bool(false))),
+            fragment => Ok(Some(DistInfoMetadata::Hashes(Self::parse_hash(fragment)?))),
+        }
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -293,6 +301,7 @@ mod tests {
             ),
             files: [
                 File {
+                    core_metadata: None,
                     dist_info_metadata: None,
                     filename: "Jinja2-3.1.2-py3-none-any.whl",
                     hashes: Hashes {
@@ -349,6 +358,7 @@ mod tests {
             ),
             files: [
                 File {
+                    core_metadata: None,
                     dist_info_metadata: None,
                     filename: "Jinja2-3.1.2-py3-none-any.whl",
                     hashes: Hashes {
@@ -408,6 +418,7 @@ mod tests {
             ),
             files: [
                 File {
+                    core_metadata: None,
                     dist_info_metadata: None,
                     filename: "Jinja2-3.1.2-py3-none-any.whl",
                     hashes: Hashes {
@@ -464,6 +475,7 @@ mod tests {
             ),
             files: [
                 File {
+                    core_metadata: None,
                     dist_info_metadata: None,
                     filename: "Jinja2-3.1.2+233fca715f49-py3-none-any.whl",
                     hashes: Hashes {
@@ -520,6 +532,7 @@ mod tests {
             ),
             files: [
                 File {
+                    core_metadata: None,
                     dist_info_metadata: None,
                     filename: "torchtext-0.17.0+cpu-cp39-cp39-win_amd64.whl",
                     hashes: Hashes {
@@ -574,6 +587,7 @@ mod tests {
             ),
             files: [
                 File {
+                    core_metadata: None,
                     dist_info_metadata: None,
                     filename: "Jinja2-3.1.2-py3-none-any.whl",
                     hashes: Hashes {
@@ -662,6 +676,7 @@ mod tests {
             ),
             files: [
                 File {
+                    core_metadata: None,
                     dist_info_metadata: None,
                     filename: "Jinja2-3.1.2-py3-none-any.whl",
                     hashes: Hashes {
@@ -751,6 +766,7 @@ mod tests {
             ),
             files: [
                 File {
+                    core_metadata: None,
                     dist_info_metadata: None,
                     filename: "jaxlib-0.1.52+cuda100-cp36-none-manylinux2010_x86_64.whl",
                     hashes: Hashes {
@@ -766,6 +782,7 @@ mod tests {
                     yanked: None,
                 },
                 File {
+                    core_metadata: None,
                     dist_info_metadata: None,
                     filename: "jaxlib-0.1.52+cuda100-cp37-none-manylinux2010_x86_64.whl",
                     hashes: Hashes {
@@ -830,6 +847,7 @@ mod tests {
             ),
             files: [
                 File {
+                    core_metadata: None,
                     dist_info_metadata: None,
                     filename: "Flask-0.1.tar.gz",
                     hashes: Hashes {
@@ -847,6 +865,7 @@ mod tests {
                     yanked: None,
                 },
                 File {
+                    core_metadata: None,
                     dist_info_metadata: None,
                     filename: "Flask-0.10.1.tar.gz",
                     hashes: Hashes {
@@ -864,6 +883,7 @@ mod tests {
                     yanked: None,
                 },
                 File {
+                    core_metadata: None,
                     dist_info_metadata: None,
                     filename: "flask-3.0.1.tar.gz",
                     hashes: Hashes {
@@ -930,6 +950,7 @@ mod tests {
             ),
             files: [
                 File {
+                    core_metadata: None,
                     dist_info_metadata: None,
                     filename: "Jinja2-3.1.2-py3-none-any.whl",
                     hashes: Hashes {
@@ -961,4 +982,41 @@ mod tests {
         }
         "###);
     }
+
+    #[test]
+    fn parse_core_metadata() {
+        let text = r#"
+<!DOCTYPE html>
+<html>
+  <body>
+    <h1>Links for jinja2</h1>
+    <a href="/whl/Jinja2-3.1.2-py3-none-any.whl" data-core-metadata="sha256=6088930bfe239f0e6710546ab9c19c9ef35e29792895fed6e6e31a023a182a61" data-dist-info-metadata="true">Jinja2-3.1.2-py3-none-any.whl</a><br/>
+  </body>
+</html>
+        "#;
+        let base = Url::parse("https://download.pytorch.org/whl/jinja2/").unwrap();
+        let result = SimpleHtml::parse(text, &base).unwrap();
+        let file = &result.files[0];
+        insta::assert_debug_snapshot!((&file.core_metadata, &file.dist_info_metadata), @r###"
+        (
+            Some(
+                Hashes(
+                    Hashes {
+                        md5: None,
+                        sha256: Some(
+                            "6088930bfe239f0e6710546ab9c19c9ef35e29792895fed6e6e31a023a182a61",
+                        ),
+                        sha384: None,
+                        sha512: None,
+                    },
+                ),
+            ),
+            Some(
+                Bool(
+                    true,
+                ),
+            ),
+        )
+        "###);
+    }
 }
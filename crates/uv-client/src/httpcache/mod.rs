@@ -81,6 +81,23 @@ only store the headers listed in the response.
 Also, since we aren't a proxy, there are a host of proxy-specific rules for
 managing headers and data that we needn't care about.
 
+# Strict mode
+
+By default, a few of the decisions below are more lenient than RFC 9111
+requires, in favor of avoiding network requests. Callers can opt in to strict
+compliance (e.g., for private indexes that rely on cache headers for
+invalidation), in which case:
+
+* A cached response whose `Vary` header doesn't match the new request is never
+reused, not even after a successful revalidation of the cached variant.
+* A heuristic freshness lifetime is only assigned to responses with a
+heuristically cacheable status code (or a `public` directive), as per [RFC 9111
+S4.2.2].
+* A stale response with `must-revalidate` or `no-cache` is never reused without
+successful validation, even when disconnected from the origin server.
+
+[RFC 9111 S4.2.2]: https://www.rfc-editor.org/rfc/rfc9111.html#section-4.2.2
+
 # Zero-copy deserialization
 
 As mentioned above, we would really like our fast path (that is, a cached
@@ -145,6 +162,13 @@ use self::control::CacheControl;
 
 mod control;
 
+/// The status codes that are defined as heuristically cacheable, as per [RFC
+/// 9110 S15.1].
+///
+/// [RFC 9110 S15.1]: https://www.rfc-editor.org/rfc/rfc9110#section-15.1
+const HEURISTICALLY_CACHEABLE_STATUS_CODES: &[u16] =
+    &[200, 203, 204, 206, 300, 301, 308, 404, 405, 410, 414, 501];
+
 /// Knobs to configure uv's cache behavior.
 ///
 /// At time of writing, we don't expose any way of modifying these since I
@@ -307,8 +331,12 @@ impl ArchivedCachePolicy {
     /// In the case of (2), the given request is modified in place such that
     /// it is suitable as a revalidation request.
     ///
+    /// When `strict` is set, the semantics of [RFC 9111] are followed without
+    /// any of the lenience described in the module documentation.
+    ///
+    /// [RFC 9111]: https://www.rfc-editor.org/rfc/rfc9111.html
     /// [RFC 9111 S4]: https://www.rfc-editor.org/rfc/rfc9111.html#section-4
-    pub fn before_request(&self, request: &mut reqwest::Request) -> BeforeRequest {
+    pub fn before_request(&self, request: &mut reqwest::Request, strict: bool) -> BeforeRequest {
         let now = SystemTime::now();
         // If the response was never storable, then we just bail out
         // completely.
@@ -346,14 +374,18 @@ impl ArchivedCachePolicy {
         // "request header fields nominated by the stored response (if any)
         // match those presented, and..."
         //
-        // We don't support the `Vary` header, so if it was set, we
-        // conservatively require revalidation.
+        // By default, we conservatively require revalidation of the cached
+        // response. In strict mode, the cached response is a different variant
+        // altogether, so we omit the validators to request a new response
+        // rather than risk an HTTP 304 for the cached variant.
         if !self.vary.matches(request.headers()) {
             tracing::trace!(
                 "request {} does not match cached request because of the 'Vary' header",
                 request.url(),
             );
-            self.set_revalidation_headers(request);
+            if !strict {
+                self.set_revalidation_headers(request);
+            }
             return BeforeRequest::Stale(self.new_cache_policy_builder(request));
         }
         // "the stored response does not contain the no-cache directive, unless
@@ -366,7 +398,7 @@ impl ArchivedCachePolicy {
         //
         // "fresh, or..."
         // "allowed to be served stale, or..."
-        if self.is_fresh(now, request) {
+        if self.is_fresh(now, request, strict) {
             return BeforeRequest::Fresh;
         }
         // "successfully validated."
@@ -550,13 +582,6 @@ impl ArchivedCachePolicy {
     ///
     /// [RFC 9111 S3]: https://www.rfc-editor.org/rfc/rfc9111.html#section-3
     pub fn is_storable(&self) -> bool {
-        // In the absence of other signals, we are limited to caching responses
-        // with a code that is heuristically cacheable as per [RFC 9110 S15.1].
-        //
-        // [RFC 9110 S15.1]: https://www.rfc-editor.org/rfc/rfc9110#section-15.1
-        const HEURISTICALLY_CACHEABLE_STATUS_CODES: &[u16] =
-            &[200, 203, 204, 206, 300, 301, 308, 404, 405, 410, 414, 501];
-
         // N.B. This routine could be "simpler", but we bias toward
         // following the flow of logic as closely as possible as written
         // in RFC 9111 S3.
@@ -737,13 +762,32 @@ impl ArchivedCachePolicy {
             || self.response.headers.cc.s_maxage_seconds.is_some()
     }
 
+    /// Returns the status code of the cached response.
+    pub fn status(&self) -> u16 {
+        self.response.status
+    }
+
+    /// Returns true if a stale response may be reused when the origin server
+    /// can't be reached, as per [RFC 9111 S4.2.4].
+    ///
+    /// As per [RFC 9111 S5.2.2.2] and [RFC 9111 S5.2.2.4], responses with
+    /// `must-revalidate` or `no-cache` must never be reused without successful
+    /// validation, even when disconnected.
+    ///
+    /// [RFC 9111 S4.2.4]: https://www.rfc-editor.org/rfc/rfc9111.html#section-4.2.4
+    /// [RFC 9111 S5.2.2.2]: https://www.rfc-editor.org/rfc/rfc9111.html#section-5.2.2.2
+    /// [RFC 9111 S5.2.2.4]: https://www.rfc-editor.org/rfc/rfc9111.html#section-5.2.2.4
+    pub fn allows_disconnected_stale(&self) -> bool {
+        !self.response.headers.cc.must_revalidate && !self.response.headers.cc.no_cache
+    }
+
     /// Returns true if the response is considered fresh as per [RFC 9111
     /// S4.2]. If the response is not fresh, then it considered stale and ought
     /// to be revalidated with the origin server.
     ///
     /// [RFC 9111 S4.2]: https://www.rfc-editor.org/rfc/rfc9111.html#section-4.2
-    fn is_fresh(&self, now: SystemTime, request: &reqwest::Request) -> bool {
-        let freshness_lifetime = self.freshness_lifetime().as_secs();
+    fn is_fresh(&self, now: SystemTime, request: &reqwest::Request, strict: bool) -> bool {
+        let freshness_lifetime = self.freshness_lifetime(strict).as_secs();
         let age = self.age(now).as_secs();
 
         // Per RFC 8246, the `immutable` directive means that a reload from an
@@ -821,7 +865,7 @@ impl ArchivedCachePolicy {
             }
         }
         if age > freshness_lifetime {
-            let allows_stale = self.allows_stale(now);
+            let allows_stale = self.allows_stale(now, strict);
             if !allows_stale {
                 tracing::trace!(
                     "request {} does not have a fresh cache because \
@@ -842,7 +886,7 @@ impl ArchivedCachePolicy {
     /// 9111 S4.2.4].
     ///
     /// [RFC 9111 S4.2.4]: https://www.rfc-editor.org/rfc/rfc9111.html#section-4.2.4
-    fn allows_stale(&self, now: SystemTime) -> bool {
+    fn allows_stale(&self, now: SystemTime, strict: bool) -> bool {
         // As per [RFC 9111 S5.2.2.2], if `must-revalidate` is present, then
         // caches cannot reuse a stale response without talking to the server
         // first. Note that RFC 9111 doesn't seem to say anything about the
@@ -868,7 +912,7 @@ impl ArchivedCachePolicy {
             let stale_amount = self
                 .age(now)
                 .as_secs()
-                .saturating_sub(self.freshness_lifetime().as_secs());
+                .saturating_sub(self.freshness_lifetime(strict).as_secs());
             if stale_amount <= max_stale {
                 tracing::trace!(
                     "cached request {} has a cached response that allows staleness \
@@ -930,8 +974,12 @@ impl ArchivedCachePolicy {
     /// this returns `0`. That is, the response will be considered stale in all
     /// cases.
     ///
+    /// In strict mode, a heuristic freshness lifetime is only assigned to
+    /// responses that permit one, as per [RFC 9111 S4.2.2].
+    ///
     /// [RFC 9111 S4.2.1]: https://www.rfc-editor.org/rfc/rfc9111.html#section-4.2.1
-    fn freshness_lifetime(&self) -> Duration {
+    /// [RFC 9111 S4.2.2]: https://www.rfc-editor.org/rfc/rfc9111.html#section-4.2.2
+    fn freshness_lifetime(&self, strict: bool) -> Duration {
         if self.config.shared {
             if let Some(&s_maxage) = self.response.headers.cc.s_maxage_seconds.as_ref() {
                 return Duration::from_secs(s_maxage);
@@ -943,7 +991,21 @@ impl ArchivedCachePolicy {
         if let Some(&expires) = self.response.headers.expires_unix_timestamp.as_ref() {
             return Duration::from_secs(expires.saturating_sub(self.response.header_date()));
         }
-        if let Some(&last_modified) = self.response.headers.last_modified_unix_timestamp.as_ref() {
+        // "a cache MUST NOT use heuristics to determine freshness when an
+        // explicit expiration time is present in the stored response. ...
+        // Caches can use heuristic freshness only for responses with a status
+        // code that is defined as heuristically cacheable, or marked as
+        // explicitly cacheable (e.g., with a public response directive)."
+        let heuristically_cacheable = !strict
+            || self.response.headers.cc.public
+            || HEURISTICALLY_CACHEABLE_STATUS_CODES.contains(&self.response.status);
+        if let Some(&last_modified) = self
+            .response
+            .headers
+            .last_modified_unix_timestamp
+            .as_ref()
+            .filter(|_| heuristically_cacheable)
+        {
             let interval = self.response.header_date().saturating_sub(last_modified);
             let percent = u64::from(self.config.heuristic_percent);
             return Duration::from_secs(interval.saturating_mul(percent).saturating_div(100));
@@ -1371,3 +1433,128 @@ fn parse_seconds(value: &[u8]) -> Option<u64> {
     }
     std::str::from_utf8(value).ok()?.parse().ok()
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use super::{unix_timestamp_to_rfc2822, BeforeRequest, CachePolicyBuilder};
+
+    fn request(accept: &str) -> reqwest::Request {
+        let mut request = reqwest::Request::new(
+            reqwest::Method::GET,
+            "https://pypi.org/simple/flask/".parse().unwrap(),
+        );
+        request
+            .headers_mut()
+            .insert("accept", accept.parse().unwrap());
+        request
+    }
+
+    fn response(status: u16, headers: &[(&str, &str)]) -> reqwest::Response {
+        let mut builder = http::Response::builder().status(status);
+        for (name, value) in headers {
+            builder = builder.header(*name, *value);
+        }
+        reqwest::Response::from(builder.body("").unwrap())
+    }
+
+    fn http_date(ago: Duration) -> String {
+        let time = SystemTime::now() - ago;
+        let seconds = time
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        unix_timestamp_to_rfc2822(seconds).unwrap()
+    }
+
+    #[test]
+    fn strict_vary() {
+        let policy = CachePolicyBuilder::new(&request("text/html")).build(&response(
+            200,
+            &[
+                ("cache-control", "max-age=600"),
+                ("etag", "\"abc\""),
+                ("vary", "accept"),
+            ],
+        ));
+        let policy = policy.to_archived();
+
+        // By default, a mismatched variant is revalidated.
+        let mut req = request("application/json");
+        assert!(matches!(
+            policy.before_request(&mut req, false),
+            BeforeRequest::Stale(_)
+        ));
+        assert!(req.headers().contains_key("if-none-match"));
+
+        // In strict mode, a mismatched variant is never reused.
+        let mut req = request("application/json");
+        assert!(matches!(
+            policy.before_request(&mut req, true),
+            BeforeRequest::Stale(_)
+        ));
+        assert!(!req.headers().contains_key("if-none-match"));
+
+        // A matching variant is fresh in either mode.
+        assert!(matches!(
+            policy.before_request(&mut request("text/html"), true),
+            BeforeRequest::Fresh
+        ));
+    }
+
+    #[test]
+    fn strict_heuristic_freshness() {
+        // Date the response an hour ago, such that it's stale without a freshness lifetime.
+        let date = http_date(Duration::from_secs(60 * 60));
+        let last_modified = http_date(Duration::from_secs(10 * 24 * 60 * 60));
+
+        // A `302` isn't heuristically cacheable, but is storable as a private response.
+        let policy = CachePolicyBuilder::new(&request("text/html")).build(&response(
+            302,
+            &[
+                ("cache-control", "private"),
+                ("date", &date),
+                ("last-modified", &last_modified),
+            ],
+        ));
+        let policy = policy.to_archived();
+        assert!(policy.is_storable());
+        assert!(matches!(
+            policy.before_request(&mut request("text/html"), false),
+            BeforeRequest::Fresh
+        ));
+        assert!(matches!(
+            policy.before_request(&mut request("text/html"), true),
+            BeforeRequest::Stale(_)
+        ));
+
+        // A `200` is heuristically cacheable in either mode.
+        let policy = CachePolicyBuilder::new(&request("text/html")).build(&response(
+            200,
+            &[("date", &date), ("last-modified", &last_modified)],
+        ));
+        let policy = policy.to_archived();
+        assert!(matches!(
+            policy.before_request(&mut request("text/html"), true),
+            BeforeRequest::Fresh
+        ));
+    }
+
+    #[test]
+    fn disconnected_stale() {
+        let policy = CachePolicyBuilder::new(&request("text/html"))
+            .build(&response(200, &[("cache-control", "max-age=0")]));
+        assert!(policy.to_archived().allows_disconnected_stale());
+
+        let policy = CachePolicyBuilder::new(&request("text/html")).build(&response(
+            200,
+            &[("cache-control", "max-age=0, must-revalidate")],
+        ));
+        assert!(!policy.to_archived().allows_disconnected_stale());
+
+        let policy = CachePolicyBuilder::new(&request("text/html"))
+            .build(&response(200, &[("cache-control", "no-cache")]));
+        assert!(!policy.to_archived().allows_disconnected_stale());
+    }
+}
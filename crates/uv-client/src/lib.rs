@@ -1,5 +1,6 @@
 pub use base_client::{BaseClient, BaseClientBuilder};
 pub use cached_client::{CacheControl, CachedClient, CachedClientError, DataWithCachePolicy};
+pub use connection::ConnectionOptions;
 pub use error::{BetterReqwestError, Error, ErrorKind};
 pub use flat_index::{FlatDistributions, FlatIndex, FlatIndexClient, FlatIndexError};
 pub use linehaul::LineHaul;
@@ -7,17 +8,25 @@ pub use registry_client::{
     Connectivity, RegistryClient, RegistryClientBuilder, SimpleMetadata, SimpleMetadatum,
     VersionFiles,
 };
+pub use retry::{IndexRetryStatus, RetryPolicy};
 pub use rkyvutil::OwnedArchive;
+pub use trace::{CacheDisposition, HttpTrace, HttpTraceEntry, HttpTraceFormat};
 
 mod base_client;
 mod cached_client;
+mod connection;
+mod dns;
 mod error;
 mod flat_index;
 mod html;
 mod httpcache;
 mod linehaul;
 mod middleware;
+mod mirror;
+mod object_store;
 mod registry_client;
 mod remote_metadata;
+mod retry;
 mod rkyvutil;
 mod tls;
+mod trace;
@@ -0,0 +1,118 @@
+use reqwest::header::AUTHORIZATION;
+use reqwest::{Request, Response};
+use reqwest_middleware::{Middleware, Next};
+use task_local_extensions::Extensions;
+use tracing::warn;
+use url::Url;
+
+/// A middleware that fails over requests for an index to its mirrors, if the index can't be
+/// reached or responds with a server error.
+///
+/// Requests are rewritten after the cache key has been determined, such that cached responses
+/// remain tied to the canonical index URL, regardless of which mirror served them.
+pub(crate) struct MirrorMiddleware {
+    index: Url,
+    mirrors: Vec<Url>,
+}
+
+impl MirrorMiddleware {
+    pub(crate) fn new(mut index: Url, mirrors: Vec<Url>) -> Self {
+        // Credentials are stripped from request URLs, so strip them from the index too.
+        let _ = index.set_username("");
+        let _ = index.set_password(None);
+        Self { index, mirrors }
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for MirrorMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        if index_path(req.url(), &self.index).is_none() {
+            return next.run(req, extensions).await;
+        }
+
+        // Requests with streaming bodies can't be cloned, and so can't be failed over.
+        let Some(attempt) = req.try_clone() else {
+            return next.run(req, extensions).await;
+        };
+
+        let mut result = next.clone().run(attempt, extensions).await;
+        for mirror in &self.mirrors {
+            if !is_unavailable(&result) {
+                return result;
+            }
+            let Some(url) = rewrite(req.url(), &self.index, mirror) else {
+                continue;
+            };
+            let Some(mut attempt) = req.try_clone() else {
+                return result;
+            };
+            warn!(
+                "Failed to reach {}; falling back to mirror: {url}",
+                req.url()
+            );
+
+            // Credentials for the index must not be sent to its mirrors.
+            attempt.headers_mut().remove(AUTHORIZATION);
+            *attempt.url_mut() = url;
+            result = next.clone().run(attempt, extensions).await;
+        }
+        result
+    }
+}
+
+/// Returns `true` if the result indicates that the server is unavailable, such that the request
+/// should be sent to a mirror instead.
+fn is_unavailable(result: &reqwest_middleware::Result<Response>) -> bool {
+    match result {
+        Ok(response) => response.status().is_server_error(),
+        Err(reqwest_middleware::Error::Reqwest(err)) => err.is_connect() || err.is_timeout(),
+        Err(reqwest_middleware::Error::Middleware(_)) => false,
+    }
+}
+
+/// Return the remainder of a URL relative to the given index, or `None` if the URL isn't under
+/// the index.
+fn index_path<'a>(url: &'a Url, index: &Url) -> Option<&'a str> {
+    let path = url
+        .as_str()
+        .strip_prefix(index.as_str().trim_end_matches('/'))?;
+    (path.is_empty() || path.starts_with(['/', '?', '#'])).then_some(path)
+}
+
+/// Rewrite a URL under the given index to the same path under the given mirror, or return `None`
+/// if the URL isn't under the index.
+fn rewrite(url: &Url, index: &Url, mirror: &Url) -> Option<Url> {
+    let path = index_path(url, index)?;
+    Url::parse(&format!("{}{path}", mirror.as_str().trim_end_matches('/'))).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use super::rewrite;
+
+    #[test]
+    fn rewrite_url() {
+        let index = Url::parse("https://pypi.org/simple").unwrap();
+        let mirror = Url::parse("https://mirror.example.com/pypi/simple/").unwrap();
+
+        let url = Url::parse("https://pypi.org/simple/flask/").unwrap();
+        assert_eq!(
+            rewrite(&url, &index, &mirror).unwrap().as_str(),
+            "https://mirror.example.com/pypi/simple/flask/"
+        );
+
+        // URLs outside the index are left untouched.
+        let url = Url::parse("https://pypi.org/simpler/flask/").unwrap();
+        assert_eq!(rewrite(&url, &index, &mirror), None);
+        let url = Url::parse("https://files.pythonhosted.org/packages/flask.whl").unwrap();
+        assert_eq!(rewrite(&url, &index, &mirror), None);
+    }
+}
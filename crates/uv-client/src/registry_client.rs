@@ -2,6 +2,8 @@ use std::collections::BTreeMap;
 use std::fmt::Debug;
 use std::path::Path;
 use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
 
 use async_http_range_reader::AsyncHttpRangeReader;
 use futures::{FutureExt, TryStreamExt};
@@ -10,27 +12,33 @@ use reqwest::{Client, Response, StatusCode};
 use serde::{Deserialize, Serialize};
 use tokio::io::AsyncReadExt;
 use tokio_util::compat::{FuturesAsyncReadCompatExt, TokioAsyncReadCompatExt};
-use tracing::{info_span, instrument, trace, warn, Instrument};
+use tracing::{debug, info_span, instrument, trace, warn, Instrument};
 use url::Url;
 
 use distribution_filename::{DistFilename, SourceDistFilename, WheelFilename};
-use distribution_types::{BuiltDist, File, FileLocation, IndexUrl, IndexUrls, Name};
+use distribution_types::{
+    BuiltDist, File, FileLocation, IndexUrl, IndexUrls, Name, RegistrySourceDist,
+};
 use install_wheel_rs::metadata::{find_archive_dist_info, is_metadata_entry};
 use pep440_rs::Version;
 use pep508_rs::MarkerEnvironment;
 use platform_tags::Platform;
 use pypi_types::{Metadata23, SimpleJson};
-use uv_auth::KeyringProvider;
-use uv_cache::{Cache, CacheBucket, WheelCache};
+use uv_auth::{CredentialProvider, KeyringProvider};
+use uv_cache::{Cache, CacheBucket, CacheEntry, WheelCache};
 use uv_normalize::PackageName;
 use uv_types::IndexStrategy;
 
 use crate::base_client::{BaseClient, BaseClientBuilder};
 use crate::cached_client::CacheControl;
 use crate::html::SimpleHtml;
+use crate::object_store::is_object_store_url;
 use crate::remote_metadata::wheel_metadata_from_remote_zip;
 use crate::rkyvutil::OwnedArchive;
-use crate::{CachedClient, CachedClientError, Error, ErrorKind};
+use crate::{CachedClient, CachedClientError, ConnectionOptions, Error, ErrorKind, RetryPolicy};
+
+/// The duration for which a package that was missing from an index is assumed to remain missing.
+const NEGATIVE_CACHE_TTL: Duration = Duration::from_secs(5 * 60);
 
 /// A builder for an [`RegistryClient`].
 #[derive(Debug, Clone)]
@@ -38,8 +46,11 @@ pub struct RegistryClientBuilder<'a> {
     index_urls: IndexUrls,
     index_strategy: IndexStrategy,
     keyring_provider: KeyringProvider,
+    credential_provider: Option<Arc<dyn CredentialProvider>>,
     native_tls: bool,
-    retries: u32,
+    retry_policy: RetryPolicy,
+    proxy: Option<Url>,
+    connection_options: ConnectionOptions,
     connectivity: Connectivity,
     cache: Cache,
     client: Option<Client>,
@@ -53,10 +64,13 @@ impl RegistryClientBuilder<'_> {
             index_urls: IndexUrls::default(),
             index_strategy: IndexStrategy::default(),
             keyring_provider: KeyringProvider::default(),
+            credential_provider: None,
             native_tls: false,
             cache,
             connectivity: Connectivity::Online,
-            retries: 3,
+            retry_policy: RetryPolicy::default(),
+            proxy: None,
+            connection_options: ConnectionOptions::from_env(),
             client: None,
             markers: None,
             platform: None,
@@ -83,6 +97,12 @@ impl<'a> RegistryClientBuilder<'a> {
         self
     }
 
+    #[must_use]
+    pub fn credential_provider(mut self, credential_provider: Arc<dyn CredentialProvider>) -> Self {
+        self.credential_provider = Some(credential_provider);
+        self
+    }
+
     #[must_use]
     pub fn connectivity(mut self, connectivity: Connectivity) -> Self {
         self.connectivity = connectivity;
@@ -91,7 +111,25 @@ impl<'a> RegistryClientBuilder<'a> {
 
     #[must_use]
     pub fn retries(mut self, retries: u32) -> Self {
-        self.retries = retries;
+        self.retry_policy.retries = retries;
+        self
+    }
+
+    #[must_use]
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    #[must_use]
+    pub fn proxy(mut self, proxy: Option<Url>) -> Self {
+        self.proxy = proxy;
+        self
+    }
+
+    #[must_use]
+    pub fn connection_options(mut self, connection_options: ConnectionOptions) -> Self {
+        self.connection_options = connection_options;
         self
     }
 
@@ -141,8 +179,21 @@ impl<'a> RegistryClientBuilder<'a> {
             builder = builder.platform(platform)
         }
 
+        if let Some(index) = self.index_urls.index() {
+            let mirrors = self.index_urls.index_mirrors();
+            if !mirrors.is_empty() {
+                builder = builder.index_mirrors(index.url().clone(), mirrors.to_vec());
+            }
+        }
+
+        if let Some(credential_provider) = self.credential_provider {
+            builder = builder.credential_provider(credential_provider)
+        }
+
         let client = builder
-            .retries(self.retries)
+            .retry_policy(self.retry_policy)
+            .proxy(self.proxy)
+            .connection_options(self.connection_options)
             .connectivity(self.connectivity)
             .native_tls(self.native_tls)
             .keyring_provider(self.keyring_provider)
@@ -152,7 +203,7 @@ impl<'a> RegistryClientBuilder<'a> {
         let connectivity = client.connectivity();
 
         // Wrap in the cache middleware.
-        let client = CachedClient::new(client);
+        let client = CachedClient::new(client).with_strict(self.cache.strict_http());
 
         RegistryClient {
             index_urls: self.index_urls,
@@ -226,6 +277,13 @@ impl RegistryClient {
 
         let mut results = Vec::new();
         for index in it {
+            // If the index recently reported that the package doesn't exist, skip it.
+            let missing_entry = self.simple_cache_entry(package_name, index, "missing");
+            if self.is_known_missing(&missing_entry, package_name)? {
+                debug!("Skipping known-missing package {package_name} on index: {index}");
+                continue;
+            }
+
             match self.simple_single_index(package_name, index).await? {
                 Ok(metadata) => {
                     results.push((index.clone(), metadata));
@@ -241,6 +299,14 @@ impl RegistryClient {
                         if err.status() == Some(StatusCode::NOT_FOUND)
                             || err.status() == Some(StatusCode::FORBIDDEN)
                         {
+                            // Record a negative cache entry, to avoid re-querying the index for
+                            // the same package in subsequent resolutions. The entry is only an
+                            // optimization, so failing to write it shouldn't fail the request.
+                            if self.connectivity == Connectivity::Online {
+                                if let Err(err) = write_missing_entry(&missing_entry).await {
+                                    warn!("Failed to cache missing package {package_name}: {err}");
+                                }
+                            }
                             continue;
                         }
                         return Err(ErrorKind::from(err).into());
@@ -263,6 +329,54 @@ impl RegistryClient {
         Ok(results)
     }
 
+    /// Return the [`CacheEntry`] for a package in the simple API cache, with the given extension.
+    fn simple_cache_entry(
+        &self,
+        package_name: &PackageName,
+        index: &IndexUrl,
+        extension: &str,
+    ) -> CacheEntry {
+        self.cache.entry(
+            CacheBucket::Simple,
+            Path::new(&match index {
+                IndexUrl::Pypi(_) => "pypi".to_string(),
+                IndexUrl::Url(url) => cache_key::digest(&cache_key::CanonicalUrl::new(url)),
+            }),
+            format!("{package_name}.{extension}"),
+        )
+    }
+
+    /// Returns `true` if the negative cache entry indicates that the package was recently found to
+    /// be missing from the index.
+    ///
+    /// Negative entries are only honored for [`NEGATIVE_CACHE_TTL`], and are subject to the
+    /// cache's refresh policy.
+    fn is_known_missing(
+        &self,
+        missing_entry: &CacheEntry,
+        package_name: &PackageName,
+    ) -> Result<bool, Error> {
+        if !self
+            .cache
+            .freshness(missing_entry, Some(package_name))
+            .map_err(ErrorKind::Io)?
+            .is_fresh()
+        {
+            return Ok(false);
+        }
+        let Some(metadata) =
+            uv_fs::metadata_if_exists(missing_entry.read_path()).map_err(ErrorKind::Io)?
+        else {
+            return Ok(false);
+        };
+        let age = metadata
+            .modified()
+            .map_err(ErrorKind::Io)?
+            .elapsed()
+            .unwrap_or_default();
+        Ok(age < NEGATIVE_CACHE_TTL)
+    }
+
     async fn simple_single_index(
         &self,
         package_name: &PackageName,
@@ -280,14 +394,7 @@ impl RegistryClient {
 
         trace!("Fetching metadata for {package_name} from {url}");
 
-        let cache_entry = self.cache.entry(
-            CacheBucket::Simple,
-            Path::new(&match index {
-                IndexUrl::Pypi(_) => "pypi".to_string(),
-                IndexUrl::Url(url) => cache_key::digest(&cache_key::CanonicalUrl::new(url)),
-            }),
-            format!("{package_name}.rkyv"),
-        );
+        let cache_entry = self.simple_cache_entry(package_name, index, "rkyv");
         let cache_control = match self.connectivity {
             Connectivity::Online => CacheControl::from(
                 self.cache
@@ -361,7 +468,8 @@ impl RegistryClient {
     /// Fetch the metadata for a remote wheel file.
     ///
     /// For a remote wheel, we try the following ways to fetch the metadata:
-    /// 1. From a [PEP 658](https://peps.python.org/pep-0658/) data-dist-info-metadata url
+    /// 1. From a [PEP 658](https://peps.python.org/pep-0658/) data-dist-info-metadata url (falling
+    ///    back to the following if the metadata file can't be fetched)
     /// 2. From a remote wheel by partial zip reading
     /// 3. From a (temp) download of a remote wheel (this is a fallback, the webserver should support range requests)
     #[instrument(skip_all, fields(% built_dist))]
@@ -415,6 +523,100 @@ impl RegistryClient {
         Ok(metadata)
     }
 
+    /// Fetch the core metadata for a registry source distribution, if the index serves it
+    /// separately via [PEP 658](https://peps.python.org/pep-0658/).
+    ///
+    /// Returns `None` if the index doesn't advertise a metadata file for the source distribution,
+    /// or if the metadata can't be used for resolution (e.g., because `Requires-Dist` is marked as
+    /// dynamic), in which case the source distribution must be built instead.
+    #[instrument(skip_all, fields(% sdist))]
+    pub async fn sdist_metadata(
+        &self,
+        sdist: &RegistrySourceDist,
+    ) -> Result<Option<Metadata23>, Error> {
+        if !sdist
+            .file
+            .dist_info_metadata
+            .as_ref()
+            .is_some_and(pypi_types::DistInfoMetadata::is_available)
+        {
+            return Ok(None);
+        }
+
+        let mut url = match &sdist.file.url {
+            FileLocation::RelativeUrl(base, url) => pypi_types::base_url_join_relative(base, url)
+                .map_err(ErrorKind::JoinRelativeError)?,
+            FileLocation::AbsoluteUrl(url) => Url::parse(url).map_err(ErrorKind::UrlParseError)?,
+            FileLocation::Path(_) => return Ok(None),
+        };
+        url.set_path(&format!("{}.metadata", url.path()));
+
+        // Store the metadata alongside that of the wheels, keyed by the source distribution's
+        // filename.
+        let cache_entry = self.cache.entry(
+            CacheBucket::WheelMetadata,
+            WheelCache::Index(&sdist.index).wheel_dir(sdist.filename.name.as_ref()),
+            format!("{}.msgpack", sdist.file.filename),
+        );
+        let cache_control = match self.connectivity {
+            Connectivity::Online => CacheControl::from(
+                self.cache
+                    .freshness(&cache_entry, Some(&sdist.filename.name))
+                    .map_err(ErrorKind::Io)?,
+            ),
+            Connectivity::Offline => CacheControl::AllowStale,
+        };
+
+        let response_callback = |response: Response| async {
+            let bytes = response.bytes().await.map_err(ErrorKind::from)?;
+
+            // Unlike wheel metadata, source distribution metadata may omit fields that are only
+            // known after a build, in which case we fall back to building.
+            let metadata = match info_span!("parse_pkg_info")
+                .in_scope(|| Metadata23::parse_pkg_info(bytes.as_ref()))
+            {
+                Ok(metadata) => Some(metadata),
+                Err(err) => {
+                    debug!("Ignoring PEP 658 metadata for {sdist}: {err}");
+                    None
+                }
+            };
+            Ok::<Option<Metadata23>, Error>(metadata)
+        };
+        let req = self
+            .uncached_client()
+            .get(url)
+            .build()
+            .map_err(ErrorKind::from)?;
+        let metadata: Option<Metadata23> = match self
+            .cached_client()
+            .get_serde(req, &cache_entry, cache_control, response_callback)
+            .await
+        {
+            Ok(metadata) => metadata,
+            Err(err) => {
+                // The metadata file is an optimization; if it can't be fetched, fall back to
+                // building the source distribution.
+                warn!(
+                    "Failed to fetch PEP 658 metadata for {sdist}; building instead: {}",
+                    Error::from(err)
+                );
+                None
+            }
+        };
+
+        if let Some(metadata) = &metadata {
+            if metadata.name != sdist.filename.name {
+                return Err(Error::from(ErrorKind::NameMismatch {
+                    metadata: metadata.name.clone(),
+                    given: sdist.filename.name.clone(),
+                }));
+            }
+        }
+
+        Ok(metadata)
+    }
+
     /// Fetch the metadata from a wheel file.
     async fn wheel_metadata_registry(
         &self,
@@ -432,8 +634,10 @@ impl RegistryClient {
             let mut url = url.clone();
             url.set_path(&format!("{}.metadata", url.path()));
 
+            // Store the metadata separately from the wheel, such that resolution can be
+            // served from small `.metadata` downloads alone.
             let cache_entry = self.cache.entry(
-                CacheBucket::Wheels,
+                CacheBucket::WheelMetadata,
                 WheelCache::Index(index).wheel_dir(filename.name.as_ref()),
                 format!("{}.msgpack", filename.stem()),
             );
@@ -453,7 +657,7 @@ impl RegistryClient {
                     .in_scope(|| Metadata23::parse_metadata(bytes.as_ref()))
                     .map_err(|err| {
                         Error::from(ErrorKind::MetadataParseError(
-                            filename,
+                            filename.clone(),
                             url.to_string(),
                             Box::new(err),
                         ))
@@ -464,17 +668,28 @@ impl RegistryClient {
                 .get(url.clone())
                 .build()
                 .map_err(ErrorKind::from)?;
-            Ok(self
+            match self
                 .cached_client()
                 .get_serde(req, &cache_entry, cache_control, response_callback)
-                .await?)
-        } else {
-            // If we lack PEP 658 support, try using HTTP range requests to read only the
-            // `.dist-info/METADATA` file from the zip, and if that also fails, download the whole wheel
-            // into the cache and read from there
-            self.wheel_metadata_no_pep658(&filename, url, WheelCache::Index(index))
                 .await
+            {
+                Ok(metadata) => return Ok(metadata),
+                Err(err) => {
+                    // The metadata file is an optimization; if it can't be fetched, fall back to
+                    // reading the metadata from the wheel itself.
+                    warn!(
+                        "Failed to fetch PEP 658 metadata for {filename}; reading the wheel instead: {}",
+                        Error::from(err)
+                    );
+                }
+            }
         }
+
+        // If we lack PEP 658 support, try using HTTP range requests to read only the
+        // `.dist-info/METADATA` file from the zip, and if that also fails, download the whole wheel
+        // into the cache and read from there
+        self.wheel_metadata_no_pep658(&filename, url, WheelCache::Index(index))
+            .await
     }
 
     /// Get the wheel metadata if it isn't available in an index through PEP 658
@@ -498,71 +713,75 @@ impl RegistryClient {
             Connectivity::Offline => CacheControl::AllowStale,
         };
 
-        let req = self
-            .uncached_client()
-            .head(url.clone())
-            .header(
-                "accept-encoding",
-                http::HeaderValue::from_static("identity"),
-            )
-            .build()
-            .map_err(ErrorKind::from)?;
-
-        // Copy authorization headers from the HEAD request to subsequent requests
-        let mut headers = HeaderMap::default();
-        if let Some(authorization) = req.headers().get("authorization") {
-            headers.append("authorization", authorization.clone());
-        }
-
-        // This response callback is special, we actually make a number of subsequent requests to
-        // fetch the file from the remote zip.
-        let read_metadata_range_request = |response: Response| {
-            async {
-                let mut reader = AsyncHttpRangeReader::from_head_response(
-                    self.uncached_client().client(),
-                    response,
-                    headers,
+        // Presigned object store URLs are only valid for a single method, so a `HEAD` request
+        // can't be followed by range requests; stream the file instead.
+        if !is_object_store_url(url) {
+            let req = self
+                .uncached_client()
+                .head(url.clone())
+                .header(
+                    "accept-encoding",
+                    http::HeaderValue::from_static("identity"),
                 )
-                .await
-                .map_err(ErrorKind::AsyncHttpRangeReader)?;
-                trace!("Getting metadata for {filename} by range request");
-                let text = wheel_metadata_from_remote_zip(filename, &mut reader).await?;
-                let metadata = Metadata23::parse_metadata(text.as_bytes()).map_err(|err| {
-                    Error::from(ErrorKind::MetadataParseError(
-                        filename.clone(),
-                        url.to_string(),
-                        Box::new(err),
-                    ))
-                })?;
-                Ok::<Metadata23, CachedClientError<Error>>(metadata)
+                .build()
+                .map_err(ErrorKind::from)?;
+
+            // Copy authorization headers from the HEAD request to subsequent requests
+            let mut headers = HeaderMap::default();
+            if let Some(authorization) = req.headers().get("authorization") {
+                headers.append("authorization", authorization.clone());
             }
-            .boxed()
-            .instrument(info_span!("read_metadata_range_request", wheel = %filename))
-        };
 
-        let result = self
-            .cached_client()
-            .get_serde(
-                req,
-                &cache_entry,
-                cache_control,
-                read_metadata_range_request,
-            )
-            .await
-            .map_err(crate::Error::from);
+            // This response callback is special, we actually make a number of subsequent requests to
+            // fetch the file from the remote zip.
+            let read_metadata_range_request = |response: Response| {
+                async {
+                    let mut reader = AsyncHttpRangeReader::from_head_response(
+                        self.uncached_client().client(),
+                        response,
+                        headers,
+                    )
+                    .await
+                    .map_err(ErrorKind::AsyncHttpRangeReader)?;
+                    trace!("Getting metadata for {filename} by range request");
+                    let text = wheel_metadata_from_remote_zip(filename, &mut reader).await?;
+                    let metadata = Metadata23::parse_metadata(text.as_bytes()).map_err(|err| {
+                        Error::from(ErrorKind::MetadataParseError(
+                            filename.clone(),
+                            url.to_string(),
+                            Box::new(err),
+                        ))
+                    })?;
+                    Ok::<Metadata23, CachedClientError<Error>>(metadata)
+                }
+                .boxed()
+                .instrument(info_span!("read_metadata_range_request", wheel = %filename))
+            };
 
-        match result {
-            Ok(metadata) => return Ok(metadata),
-            Err(err) => {
-                if err.is_http_range_requests_unsupported() {
-                    // The range request version failed. Fall back to streaming the file to search
-                    // for the METADATA file.
-                    warn!("Range requests not supported for {filename}; streaming wheel");
-                } else {
-                    return Err(err);
+            let result = self
+                .cached_client()
+                .get_serde(
+                    req,
+                    &cache_entry,
+                    cache_control,
+                    read_metadata_range_request,
+                )
+                .await
+                .map_err(crate::Error::from);
+
+            match result {
+                Ok(metadata) => return Ok(metadata),
+                Err(err) => {
+                    if err.is_http_range_requests_unsupported() {
+                        // The range request version failed. Fall back to streaming the file to search
+                        // for the METADATA file.
+                        warn!("Range requests not supported for {filename}; streaming wheel");
+                    } else {
+                        return Err(err);
+                    }
                 }
-            }
-        };
+            };
+        }
 
         // Create a request to stream the file.
         let req = self
@@ -581,9 +800,9 @@ impl RegistryClient {
         // Stream the file, searching for the METADATA.
         let read_metadata_stream = |response: Response| {
             async {
-                let reader = response
-                    .bytes_stream()
-                    .map_err(|err| self.handle_response_errors(err))
+                let reader = self
+                    .uncached_client()
+                    .bytes_stream(response)
                     .into_async_read();
 
                 read_metadata_async_stream(filename, url.to_string(), reader).await
@@ -596,20 +815,12 @@ impl RegistryClient {
             .await
             .map_err(crate::Error::from)
     }
+}
 
-    /// Handle a specific `reqwest` error, and convert it to [`io::Error`].
-    fn handle_response_errors(&self, err: reqwest::Error) -> std::io::Error {
-        if err.is_timeout() {
-            std::io::Error::new(
-                std::io::ErrorKind::TimedOut,
-                format!(
-                    "Failed to download distribution due to network timeout. Try increasing UV_HTTP_TIMEOUT (current value: {}s).",  self.timeout()
-                ),
-            )
-        } else {
-            std::io::Error::new(std::io::ErrorKind::Other, err)
-        }
-    }
+/// Write an (empty) negative cache entry, creating its bucket directory if necessary.
+async fn write_missing_entry(missing_entry: &CacheEntry) -> std::io::Result<()> {
+    fs_err::tokio::create_dir_all(missing_entry.dir()).await?;
+    uv_fs::write_atomic(missing_entry.path(), b"").await
 }
 
 /// Read a wheel's `METADATA` file from a zip file.
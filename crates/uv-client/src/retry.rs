@@ -0,0 +1,294 @@
+use std::time::{Duration, SystemTime};
+
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use reqwest::header::RETRY_AFTER;
+use reqwest::{Request, Response};
+use reqwest_middleware::{Middleware, Next};
+use reqwest_retry::{default_on_request_failure, Retryable};
+use task_local_extensions::Extensions;
+use tracing::debug;
+use url::Url;
+
+/// The policy for retrying requests that fail with a transient error.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// The maximum number of times to retry a request.
+    pub retries: u32,
+    /// The delay before the first retry, which doubles with each subsequent retry.
+    pub backoff_base: Duration,
+    /// The maximum delay between retries, prior to jitter. Delays requested by the server via
+    /// `Retry-After` are capped at the same value.
+    pub backoff_max: Duration,
+    /// The maximum fraction by which to randomly shorten each delay (e.g., `0.5` for up to 50%), to
+    /// avoid retrying in lockstep with other clients.
+    pub jitter: f64,
+    /// The HTTP status codes to treat as transient. Connection errors and timeouts are always
+    /// treated as transient.
+    pub status_codes: Vec<u16>,
+    /// Additional HTTP status codes to treat as transient for requests to specific indexes.
+    pub index_status_codes: Vec<IndexRetryStatus>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            retries: 3,
+            backoff_base: Duration::from_secs(1),
+            backoff_max: Duration::from_secs(30),
+            jitter: 0.0,
+            // Request Timeout, Too Many Requests, and server errors that typically indicate an
+            // overloaded or restarting upstream.
+            status_codes: vec![408, 429, 500, 502, 503, 504],
+            index_status_codes: Vec::new(),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Returns `true` if a response to the given URL with the given status code should be
+    /// retried.
+    fn is_transient_status(&self, url: &Url, status: u16) -> bool {
+        self.status_codes.contains(&status)
+            || self
+                .index_status_codes
+                .iter()
+                .any(|index| index.matches(url) && index.status_codes.contains(&status))
+    }
+
+    /// Return the delay before the given retry (zero-indexed), as determined by the backoff
+    /// settings alone.
+    fn backoff(&self, retry: u32) -> Duration {
+        let delay = self
+            .backoff_base
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.backoff_max);
+        if self.jitter > 0.0 {
+            let jitter = rand::thread_rng().gen_range(0.0..=self.jitter.min(1.0));
+            delay.mul_f64(1.0 - jitter)
+        } else {
+            delay
+        }
+    }
+
+    /// Return the delay before the given retry (zero-indexed) of a request that failed with a
+    /// transient status, honoring any delay requested by the server up to
+    /// [`RetryPolicy::backoff_max`].
+    fn delay(&self, retry: u32, retry_after: Option<Duration>) -> Duration {
+        let backoff = self.backoff(retry);
+        retry_after.map_or(backoff, |retry_after| {
+            retry_after.min(self.backoff_max).max(backoff)
+        })
+    }
+}
+
+/// HTTP status codes to treat as transient for requests to a given index, in addition to those
+/// in [`RetryPolicy::status_codes`].
+///
+/// Some indexes respond with otherwise-permanent status codes during routine maintenance (e.g.,
+/// a `403 Forbidden` from Artifactory during garbage collection), which should only be retried
+/// for those indexes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexRetryStatus {
+    /// The URL of the index. The status codes apply to every request sent to the same host as
+    /// the index, such that files hosted alongside the index are covered too.
+    pub index: Url,
+    /// The HTTP status codes to treat as transient.
+    pub status_codes: Vec<u16>,
+}
+
+impl IndexRetryStatus {
+    /// Returns `true` if the given URL is hosted alongside the index.
+    fn matches(&self, url: &Url) -> bool {
+        url.origin() == self.index.origin()
+    }
+}
+
+/// A middleware that retries requests according to a [`RetryPolicy`], honoring any `Retry-After`
+/// header sent by the server.
+pub(crate) struct RetryMiddleware {
+    policy: RetryPolicy,
+}
+
+impl RetryMiddleware {
+    pub(crate) fn new(policy: RetryPolicy) -> Self {
+        Self { policy }
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for RetryMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        let mut retry = 0;
+        loop {
+            // Requests with streaming bodies can't be cloned, and so can't be retried.
+            let Some(attempt) = req.try_clone() else {
+                return next.run(req, extensions).await;
+            };
+            let result = next.clone().run(attempt, extensions).await;
+            if retry >= self.policy.retries {
+                return result;
+            }
+
+            let delay = match &result {
+                Ok(response)
+                    if self
+                        .policy
+                        .is_transient_status(req.url(), response.status().as_u16()) =>
+                {
+                    self.policy.delay(retry, retry_after(response))
+                }
+                Err(err)
+                    if matches!(default_on_request_failure(err), Some(Retryable::Transient)) =>
+                {
+                    self.policy.backoff(retry)
+                }
+                _ => return result,
+            };
+
+            retry += 1;
+            debug!(
+                "Transient failure for {} (retry {retry} of {}); retrying in {:.1}s",
+                req.url(),
+                self.policy.retries,
+                delay.as_secs_f32()
+            );
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+/// Parse the `Retry-After` header of a response, which may be given either as a number of
+/// seconds or as an HTTP date.
+fn retry_after(response: &Response) -> Option<Duration> {
+    let value = response.headers().get(RETRY_AFTER)?.to_str().ok()?;
+    parse_retry_after(value, SystemTime::now())
+}
+
+fn parse_retry_after(value: &str, now: SystemTime) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    let now = DateTime::<Utc>::from(now);
+    // A date in the past means the request may be retried immediately.
+    Some(
+        date.signed_duration_since(now)
+            .to_std()
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use url::Url;
+
+    use super::{parse_retry_after, IndexRetryStatus, RetryPolicy};
+
+    #[test]
+    fn retry_after_seconds() {
+        assert_eq!(
+            parse_retry_after("120", SystemTime::UNIX_EPOCH),
+            Some(Duration::from_secs(120))
+        );
+    }
+
+    #[test]
+    fn retry_after_date() {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_445_412_480);
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:30 GMT", now),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:27:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+    }
+
+    #[test]
+    fn retry_after_invalid() {
+        assert_eq!(parse_retry_after("soon", SystemTime::UNIX_EPOCH), None);
+    }
+
+    #[test]
+    fn backoff() {
+        let policy = RetryPolicy {
+            backoff_base: Duration::from_millis(500),
+            backoff_max: Duration::from_secs(3),
+            ..RetryPolicy::default()
+        };
+        let delays = (0..4)
+            .map(|retry| policy.backoff(retry))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            delays,
+            vec![
+                Duration::from_millis(500),
+                Duration::from_secs(1),
+                Duration::from_secs(2),
+                Duration::from_secs(3),
+            ]
+        );
+    }
+
+    #[test]
+    fn delay_retry_after() {
+        let policy = RetryPolicy {
+            backoff_base: Duration::from_secs(1),
+            backoff_max: Duration::from_secs(30),
+            ..RetryPolicy::default()
+        };
+        // The server's delay is honored, if longer than the backoff...
+        assert_eq!(
+            policy.delay(0, Some(Duration::from_secs(10))),
+            Duration::from_secs(10)
+        );
+        // ...but never shortens the backoff...
+        assert_eq!(
+            policy.delay(2, Some(Duration::ZERO)),
+            Duration::from_secs(4)
+        );
+        // ...and is capped at the maximum backoff.
+        assert_eq!(
+            policy.delay(0, Some(Duration::from_secs(3600))),
+            Duration::from_secs(30)
+        );
+        assert_eq!(policy.delay(1, None), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn index_status_codes() {
+        let policy = RetryPolicy {
+            index_status_codes: vec![IndexRetryStatus {
+                index: Url::parse("https://example.jfrog.io/artifactory/api/pypi/pypi/simple")
+                    .unwrap(),
+                status_codes: vec![403],
+            }],
+            ..RetryPolicy::default()
+        };
+
+        // The additional status codes apply to the index, and to files hosted alongside it.
+        let url =
+            Url::parse("https://example.jfrog.io/artifactory/api/pypi/pypi/simple/flask/").unwrap();
+        assert!(policy.is_transient_status(&url, 403));
+        let url =
+            Url::parse("https://example.jfrog.io/artifactory/api/pypi/pypi/packages/flask.whl")
+                .unwrap();
+        assert!(policy.is_transient_status(&url, 403));
+        assert!(policy.is_transient_status(&url, 503));
+
+        // But not to other hosts.
+        let url = Url::parse("https://pypi.org/simple/flask/").unwrap();
+        assert!(!policy.is_transient_status(&url, 403));
+        assert!(policy.is_transient_status(&url, 503));
+    }
+}
@@ -22,20 +22,20 @@ pub(crate) enum Roots {
 /// Initialize a TLS configuration for the client.
 ///
 /// This is equivalent to the TLS initialization `reqwest` when `rustls-tls` is enabled,
-/// with two notable changes:
+/// with three notable changes:
 ///
 /// 1. It enables _either_ the `webpki-roots` or the `native-certs` feature, but not both.
-/// 2. It assumes the following builder settings (which match the defaults):
+/// 2. It only advertises HTTP/2 via ALPN if `http2` is set, as HTTP/2 is opt-in.
+/// 3. It assumes the following builder settings (which match the defaults):
 ///    - `root_certs: vec![]`
 ///    - `min_tls_version: None`
 ///    - `max_tls_version: None`
 ///    - `identity: None`
 ///    - `certs_verification: false`
 ///    - `tls_sni: true`
-///    - `http_version_pref: HttpVersionPref::All`
 ///
 /// See: <https://github.com/seanmonstar/reqwest/blob/e3192638518d577759dd89da489175b8f992b12f/src/async_impl/client.rs#L498>
-pub(crate) fn load(roots: Roots) -> Result<ClientConfig, TlsError> {
+pub(crate) fn load(roots: Roots, http2: bool) -> Result<ClientConfig, TlsError> {
     // Set root certificates.
     let mut root_cert_store = rustls::RootCertStore::empty();
 
@@ -96,7 +96,11 @@ pub(crate) fn load(roots: Roots) -> Result<ClientConfig, TlsError> {
     tls.enable_sni = true;
 
     // ALPN protocol
-    tls.alpn_protocols = vec!["h2".into(), "http/1.1".into()];
+    tls.alpn_protocols = if http2 {
+        vec!["h2".into(), "http/1.1".into()]
+    } else {
+        vec!["http/1.1".into()]
+    };
 
     Ok(tls)
 }
@@ -0,0 +1,55 @@
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use futures::future;
+use hyper::server::conn::Http;
+use hyper::service::service_fn;
+use hyper::{Body, Request, Response};
+use tokio::net::TcpListener;
+use url::Url;
+
+use uv_cache::Cache;
+use uv_client::RegistryClientBuilder;
+
+#[tokio::test]
+async fn explicit_proxy() -> Result<()> {
+    // Start a server that acts as a plain HTTP proxy, recording the URIs it's asked to fetch.
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    let uris = Arc::new(Mutex::new(Vec::new()));
+
+    let recorded = uris.clone();
+    tokio::spawn(async move {
+        loop {
+            let (socket, _) = listener.accept().await.unwrap();
+            let recorded = recorded.clone();
+            let svc = service_fn(move |req: Request<Body>| {
+                recorded.lock().unwrap().push(req.uri().to_string());
+                future::ok::<_, hyper::Error>(Response::new(Body::from("ok")))
+            });
+            Http::new()
+                .http1_keep_alive(false)
+                .serve_connection(socket, svc)
+                .await
+                .expect("Server Started");
+        }
+    });
+
+    // The target host doesn't resolve, so the request can only succeed via the proxy.
+    let client = RegistryClientBuilder::new(Cache::temp()?)
+        .proxy(Some(Url::parse(&format!("http://{addr}"))?))
+        .build();
+    let res = client
+        .uncached_client()
+        .get("http://pypi.invalid/simple/")
+        .send()
+        .await?;
+
+    assert!(res.status().is_success());
+    assert_eq!(
+        *uris.lock().unwrap(),
+        vec!["http://pypi.invalid/simple/".to_string()]
+    );
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use anyhow::Result;
+use futures::future;
+use hyper::server::conn::Http;
+use hyper::service::service_fn;
+use hyper::{Body, Request, Response};
+use tokio::net::TcpListener;
+
+use uv_cache::Cache;
+use uv_client::{CacheDisposition, HttpTrace, RegistryClientBuilder};
+
+#[tokio::test]
+async fn trace_requests() -> Result<()> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    tokio::spawn(async move {
+        loop {
+            let (socket, _) = listener.accept().await.unwrap();
+            let svc = service_fn(move |_req: Request<Body>| {
+                future::ok::<_, hyper::Error>(Response::new(Body::from("ok")))
+            });
+            Http::new()
+                .http1_keep_alive(false)
+                .serve_connection(socket, svc)
+                .await
+                .expect("Server Started");
+        }
+    });
+
+    // Tracing must be enabled before the client is built.
+    HttpTrace::enable();
+    let client = RegistryClientBuilder::new(Cache::temp()?).build();
+    client
+        .uncached_client()
+        .get(format!("http://user:secret@{addr}/simple/"))
+        .send()
+        .await?;
+
+    let entries = HttpTrace::entries();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].method, "GET");
+    assert_eq!(entries[0].url, format!("http://{addr}/simple/"));
+    assert_eq!(entries[0].status, Some(200));
+    assert_eq!(entries[0].cache, CacheDisposition::Uncached);
+    assert!(entries[0]
+        .request_headers
+        .iter()
+        .all(|(_, value)| !value.contains("dXNlcjpzZWNyZXQ=")));
+
+    // The trace is written as JSON Lines, unless a `.har` path is requested.
+    let dir = tempfile::tempdir()?;
+    let jsonl = dir.path().join("trace.jsonl");
+    HttpTrace::write(&jsonl)?;
+    let line: serde_json::Value = serde_json::from_str(fs_err::read_to_string(&jsonl)?.trim())?;
+    assert_eq!(line["status"], 200);
+    assert_eq!(line["cache"], "uncached");
+
+    let har = dir.path().join("trace.har");
+    HttpTrace::write(&har)?;
+    let har: serde_json::Value = serde_json::from_str(&fs_err::read_to_string(&har)?)?;
+    assert_eq!(har["log"]["version"], "1.2");
+    assert_eq!(har["log"]["entries"][0]["response"]["status"], 200);
+
+    Ok(())
+}
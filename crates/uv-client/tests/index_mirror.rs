@@ -0,0 +1,76 @@
+use std::str::FromStr;
+
+use anyhow::Result;
+use futures::future;
+use hyper::server::conn::Http;
+use hyper::service::service_fn;
+use hyper::{Body, Request, Response, StatusCode};
+use tokio::net::TcpListener;
+use url::Url;
+
+use distribution_types::{IndexLocations, IndexUrl};
+use uv_cache::Cache;
+use uv_client::RegistryClientBuilder;
+
+/// Start a server that responds to every request with the given status code and the request path.
+async fn server(status: StatusCode) -> Result<String> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+
+    tokio::spawn(async move {
+        loop {
+            let (socket, _) = listener.accept().await.unwrap();
+            let svc = service_fn(move |req: Request<Body>| {
+                let response = Response::builder()
+                    .status(status)
+                    .body(Body::from(req.uri().path().to_string()))
+                    .unwrap();
+                future::ok::<_, hyper::Error>(response)
+            });
+            Http::new()
+                .http1_keep_alive(false)
+                .serve_connection(socket, svc)
+                .await
+                .expect("Server Started");
+        }
+    });
+
+    Ok(format!("http://{addr}"))
+}
+
+#[tokio::test]
+async fn index_mirror_fallback() -> Result<()> {
+    let index = server(StatusCode::SERVICE_UNAVAILABLE).await?;
+    let mirror = server(StatusCode::OK).await?;
+
+    let index_locations = IndexLocations::new(
+        Some(IndexUrl::from_str(&format!("{index}/simple"))?),
+        Vec::new(),
+        Vec::new(),
+        false,
+    )
+    .with_index_mirrors(vec![Url::parse(&format!("{mirror}/pypi/simple/"))?]);
+    let client = RegistryClientBuilder::new(Cache::temp()?)
+        .index_urls(index_locations.index_urls())
+        .retries(0)
+        .build();
+
+    // Requests for the index are served by the mirror.
+    let res = client
+        .uncached_client()
+        .get(format!("{index}/simple/flask/"))
+        .send()
+        .await?;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.text().await?, "/pypi/simple/flask/");
+
+    // Requests outside the index are left untouched.
+    let res = client
+        .uncached_client()
+        .get(format!("{index}/packages/flask.whl"))
+        .send()
+        .await?;
+    assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use anyhow::Result;
+use futures::future;
+use hyper::server::conn::Http;
+use hyper::service::service_fn;
+use hyper::{Body, Request, Response, StatusCode};
+use tokio::net::TcpListener;
+
+use distribution_types::{IndexLocations, IndexUrl};
+use uv_cache::Cache;
+use uv_client::{ErrorKind, RegistryClientBuilder};
+use uv_normalize::PackageName;
+
+/// Start a Simple API server that responds to every request with `404 Not Found`, counting the
+/// requests it receives.
+async fn server(requests: Arc<AtomicUsize>) -> Result<String> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+
+    tokio::spawn(async move {
+        loop {
+            let (socket, _) = listener.accept().await.unwrap();
+            let requests = requests.clone();
+            let svc = service_fn(move |_req: Request<Body>| {
+                requests.fetch_add(1, Ordering::SeqCst);
+                future::ok::<_, hyper::Error>(
+                    Response::builder()
+                        .status(StatusCode::NOT_FOUND)
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+            });
+            Http::new()
+                .http1_keep_alive(false)
+                .serve_connection(socket, svc)
+                .await
+                .expect("Server Started");
+        }
+    });
+
+    Ok(format!("http://{addr}"))
+}
+
+/// An unknown package should be reported as not found on a fresh cache, in which the simple API
+/// bucket doesn't exist yet, and should be recorded such that the index isn't queried again.
+#[tokio::test]
+async fn missing_package_fresh_cache() -> Result<()> {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let index = server(requests.clone()).await?;
+    let index_locations = IndexLocations::new(
+        Some(IndexUrl::from_str(&format!("{index}/simple"))?),
+        Vec::new(),
+        Vec::new(),
+        false,
+    );
+    let package = PackageName::from_str("not-a-real-package")?;
+    let cache = Cache::temp()?;
+
+    let client = RegistryClientBuilder::new(cache)
+        .index_urls(index_locations.index_urls())
+        .retries(0)
+        .build();
+
+    let err = client.simple(&package).await.unwrap_err();
+    assert!(
+        matches!(err.kind(), ErrorKind::PackageNotFound(name) if name == "not-a-real-package"),
+        "{err}"
+    );
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+    // The negative cache entry is respected on subsequent requests.
+    let err = client.simple(&package).await.unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::PackageNotFound(_)), "{err}");
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+    Ok(())
+}
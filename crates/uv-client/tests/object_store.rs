@@ -0,0 +1,89 @@
+use std::env;
+
+use anyhow::Result;
+use futures::future;
+use hyper::server::conn::Http;
+use hyper::service::service_fn;
+use hyper::{Body, Request, Response};
+use tokio::net::TcpListener;
+
+use uv_cache::Cache;
+use uv_client::RegistryClientBuilder;
+
+/// Start a server that mimics an S3 bucket, responding to `ListObjectsV2` requests with a single
+/// object and nested prefix, and to every other request with the request path and query.
+async fn s3_server() -> Result<String> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+
+    tokio::spawn(async move {
+        loop {
+            let (socket, _) = listener.accept().await.unwrap();
+            let svc = service_fn(move |req: Request<Body>| {
+                let query = req.uri().query().unwrap_or_default().to_string();
+                let body = if query.contains("list-type=2") {
+                    "<ListBucketResult>\
+                    <Contents><Key>wheels/tqdm-1.0.0-py3-none-any.whl</Key></Contents>\
+                    <CommonPrefixes><Prefix>wheels/nightly/</Prefix></CommonPrefixes>\
+                    </ListBucketResult>"
+                        .to_string()
+                } else {
+                    format!("{}?{query}", req.uri().path())
+                };
+                future::ok::<_, hyper::Error>(Response::new(Body::from(body)))
+            });
+            Http::new()
+                .http1_keep_alive(false)
+                .serve_connection(socket, svc)
+                .await
+                .expect("Server Started");
+        }
+    });
+
+    Ok(format!("http://{addr}"))
+}
+
+#[tokio::test]
+async fn s3_bucket() -> Result<()> {
+    let endpoint = s3_server().await?;
+    env::set_var("AWS_ENDPOINT_URL_S3", &endpoint);
+    env::set_var("AWS_ACCESS_KEY_ID", "AKIDEXAMPLE");
+    env::set_var("AWS_SECRET_ACCESS_KEY", "secret");
+    env::remove_var("AWS_SESSION_TOKEN");
+
+    let client = RegistryClientBuilder::new(Cache::temp()?)
+        .retries(0)
+        .build();
+
+    // Prefixes are listed as a page of links.
+    let res = client
+        .uncached_client()
+        .get("s3://bucket/wheels/")
+        .send()
+        .await?;
+    assert!(res.status().is_success());
+    assert_eq!(
+        res.headers()["content-type"].to_str()?,
+        "text/html",
+        "listings are served as HTML"
+    );
+    let html = res.text().await?;
+    assert!(html.contains(r#"<a href="s3://bucket/wheels/nightly/">nightly</a>"#));
+    assert!(html.contains(
+        r#"<a href="s3://bucket/wheels/tqdm-1.0.0-py3-none-any.whl">tqdm-1.0.0-py3-none-any.whl</a>"#
+    ));
+
+    // Objects are fetched with a presigned request, against the configured endpoint.
+    let res = client
+        .uncached_client()
+        .get("s3://bucket/wheels/tqdm-1.0.0-py3-none-any.whl")
+        .send()
+        .await?;
+    assert!(res.status().is_success());
+    let body = res.text().await?;
+    assert!(body.starts_with("/bucket/wheels/tqdm-1.0.0-py3-none-any.whl?"));
+    assert!(body.contains("X-Amz-Credential=AKIDEXAMPLE%2F"));
+    assert!(body.contains("X-Amz-Signature="));
+
+    Ok(())
+}
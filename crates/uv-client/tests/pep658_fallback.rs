@@ -0,0 +1,124 @@
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use anyhow::Result;
+use futures::future;
+use hyper::server::conn::Http;
+use hyper::service::service_fn;
+use hyper::{Body, Request, Response, StatusCode};
+use tokio::net::TcpListener;
+
+use distribution_filename::{SourceDistFilename, WheelFilename};
+use distribution_types::{
+    BuiltDist, File, FileLocation, IndexUrl, RegistryBuiltDist, RegistrySourceDist,
+};
+use pep508_rs::VerbatimUrl;
+use pypi_types::{DistInfoMetadata, Hashes};
+use uv_cache::Cache;
+use uv_client::RegistryClientBuilder;
+use uv_normalize::PackageName;
+
+/// Start a server that fails every request for a `.metadata` file with `500 Internal Server
+/// Error`, counting them, and otherwise serves the given wheel (without support for range
+/// requests).
+async fn server(wheel: Vec<u8>, metadata_requests: Arc<AtomicUsize>) -> Result<String> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    let wheel = Arc::new(wheel);
+
+    tokio::spawn(async move {
+        loop {
+            let (socket, _) = listener.accept().await.unwrap();
+            let wheel = wheel.clone();
+            let metadata_requests = metadata_requests.clone();
+            let svc = service_fn(move |req: Request<Body>| {
+                let response = if req.uri().path().ends_with(".metadata") {
+                    metadata_requests.fetch_add(1, Ordering::SeqCst);
+                    Response::builder()
+                        .status(StatusCode::INTERNAL_SERVER_ERROR)
+                        .body(Body::empty())
+                } else {
+                    Response::builder()
+                        .header("content-length", wheel.len())
+                        .body(Body::from(wheel.as_ref().clone()))
+                };
+                future::ok::<_, hyper::Error>(response.unwrap())
+            });
+            tokio::spawn(
+                Http::new()
+                    .http1_keep_alive(false)
+                    .serve_connection(socket, svc),
+            );
+        }
+    });
+
+    Ok(format!("http://{addr}"))
+}
+
+/// A file advertising PEP 658 metadata, served from the given URL.
+fn file(filename: &str, url: String) -> Box<File> {
+    Box::new(File {
+        dist_info_metadata: Some(DistInfoMetadata::Bool(true)),
+        filename: filename.to_string(),
+        hashes: Hashes::default(),
+        requires_python: None,
+        size: None,
+        upload_time_utc_ms: None,
+        url: FileLocation::AbsoluteUrl(url),
+        yanked: None,
+    })
+}
+
+/// If the PEP 658 metadata for a wheel can't be fetched, the metadata is read from the wheel
+/// itself.
+#[tokio::test]
+async fn wheel_metadata_fallback() -> Result<()> {
+    let wheel = fs_err::read(
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../scripts/links/validation-1.0.0-py3-none-any.whl"),
+    )?;
+    let metadata_requests = Arc::new(AtomicUsize::new(0));
+    let server = server(wheel, metadata_requests.clone()).await?;
+
+    let filename = "validation-1.0.0-py3-none-any.whl";
+    let dist = BuiltDist::Registry(RegistryBuiltDist {
+        filename: WheelFilename::from_str(filename)?,
+        file: file(filename, format!("{server}/{filename}")),
+        index: IndexUrl::Url(VerbatimUrl::from_str(&format!("{server}/simple"))?),
+    });
+
+    let client = RegistryClientBuilder::new(Cache::temp()?)
+        .retries(0)
+        .build();
+    let metadata = client.wheel_metadata(&dist).await?;
+    assert_eq!(metadata.name.as_ref(), "validation");
+    assert_eq!(metadata.version.to_string(), "1.0.0");
+    assert_eq!(metadata_requests.load(Ordering::SeqCst), 1);
+
+    Ok(())
+}
+
+/// If the PEP 658 metadata for a source distribution can't be fetched, the source distribution
+/// is built instead.
+#[tokio::test]
+async fn sdist_metadata_fallback() -> Result<()> {
+    let metadata_requests = Arc::new(AtomicUsize::new(0));
+    let server = server(Vec::new(), metadata_requests.clone()).await?;
+
+    let filename = "validation-1.0.0.tar.gz";
+    let sdist = RegistrySourceDist {
+        filename: SourceDistFilename::parse(filename, &PackageName::from_str("validation")?)?,
+        file: file(filename, format!("{server}/{filename}")),
+        index: IndexUrl::Url(VerbatimUrl::from_str(&format!("{server}/simple"))?),
+    };
+
+    let client = RegistryClientBuilder::new(Cache::temp()?)
+        .retries(0)
+        .build();
+    assert!(client.sdist_metadata(&sdist).await?.is_none());
+    assert_eq!(metadata_requests.load(Ordering::SeqCst), 1);
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use std::io;
+use std::time::Duration;
+
+use anyhow::Result;
+use futures::{future, StreamExt};
+use hyper::server::conn::Http;
+use hyper::service::service_fn;
+use hyper::{Body, Request, Response};
+use tokio::net::TcpListener;
+
+use uv_client::{BaseClientBuilder, ConnectionOptions};
+
+/// Start a server that responds to every request with the first chunk of its body, and then
+/// stalls indefinitely.
+async fn server() -> Result<String> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+
+    tokio::spawn(async move {
+        loop {
+            let (socket, _) = listener.accept().await.unwrap();
+            tokio::spawn(async move {
+                let svc = service_fn(|_req: Request<Body>| async {
+                    let (mut sender, body) = Body::channel();
+                    tokio::spawn(async move {
+                        sender.send_data("0123456789".into()).await.unwrap();
+                        // Hold the connection open without sending the rest of the body.
+                        future::pending::<()>().await;
+                        drop(sender);
+                    });
+                    Ok::<_, hyper::Error>(Response::new(body))
+                });
+                let _ = Http::new().serve_connection(socket, svc).await;
+            });
+        }
+    });
+
+    Ok(format!("http://{addr}/file"))
+}
+
+/// A response body that stalls fails with the read timeout, rather than waiting for the overall
+/// request timeout.
+#[tokio::test]
+async fn stalled_response() -> Result<()> {
+    let url = server().await?;
+    let client = BaseClientBuilder::new()
+        .connection_options(ConnectionOptions {
+            read_timeout: Some(Duration::from_secs(1)),
+            ..ConnectionOptions::default()
+        })
+        .retries(0)
+        .build();
+
+    let response = client.get(&url).send().await?;
+    assert_eq!(response.version(), reqwest::Version::HTTP_11);
+
+    let mut stream = client.bytes_stream(response);
+    assert_eq!(stream.next().await.unwrap()?.as_ref(), b"0123456789");
+    let err = stream.next().await.unwrap().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+    assert!(stream.next().await.is_none());
+
+    Ok(())
+}
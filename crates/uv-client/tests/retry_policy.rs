@@ -0,0 +1,109 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use futures::future;
+use hyper::header::RETRY_AFTER;
+use hyper::server::conn::Http;
+use hyper::service::service_fn;
+use hyper::{Body, Request, Response, StatusCode};
+use tokio::net::TcpListener;
+use url::Url;
+
+use uv_cache::Cache;
+use uv_client::{IndexRetryStatus, RegistryClientBuilder, RetryPolicy};
+
+/// Start a server that fails the first request with the given status code, and succeeds
+/// thereafter. Returns the server address and the number of requests received.
+async fn flaky_server(status: StatusCode) -> Result<(String, Arc<AtomicUsize>)> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    let requests = Arc::new(AtomicUsize::new(0));
+
+    let counter = requests.clone();
+    tokio::spawn(async move {
+        loop {
+            let (socket, _) = listener.accept().await.unwrap();
+            let counter = counter.clone();
+            let svc = service_fn(move |_req: Request<Body>| {
+                let response = if counter.fetch_add(1, Ordering::SeqCst) == 0 {
+                    Response::builder()
+                        .status(status)
+                        .header(RETRY_AFTER, "0")
+                        .body(Body::empty())
+                        .unwrap()
+                } else {
+                    Response::new(Body::from("ok"))
+                };
+                future::ok::<_, hyper::Error>(response)
+            });
+            Http::new()
+                .http1_keep_alive(false)
+                .serve_connection(socket, svc)
+                .await
+                .expect("Server Started");
+        }
+    });
+
+    Ok((format!("http://{addr}"), requests))
+}
+
+#[tokio::test]
+async fn retry_too_many_requests() -> Result<()> {
+    let (url, requests) = flaky_server(StatusCode::TOO_MANY_REQUESTS).await?;
+
+    let client = RegistryClientBuilder::new(Cache::temp()?)
+        .retry_policy(RetryPolicy {
+            backoff_base: Duration::ZERO,
+            ..RetryPolicy::default()
+        })
+        .build();
+    let res = client.uncached_client().get(url).send().await?;
+
+    assert!(res.status().is_success());
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn no_retry_unlisted_status() -> Result<()> {
+    let (url, requests) = flaky_server(StatusCode::INTERNAL_SERVER_ERROR).await?;
+
+    let client = RegistryClientBuilder::new(Cache::temp()?)
+        .retry_policy(RetryPolicy {
+            backoff_base: Duration::ZERO,
+            status_codes: vec![429],
+            ..RetryPolicy::default()
+        })
+        .build();
+    let res = client.uncached_client().get(url).send().await?;
+
+    assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn retry_index_status() -> Result<()> {
+    let (url, requests) = flaky_server(StatusCode::FORBIDDEN).await?;
+
+    let client = RegistryClientBuilder::new(Cache::temp()?)
+        .retry_policy(RetryPolicy {
+            backoff_base: Duration::ZERO,
+            index_status_codes: vec![IndexRetryStatus {
+                index: Url::parse(&format!("{url}/simple"))?,
+                status_codes: vec![403],
+            }],
+            ..RetryPolicy::default()
+        })
+        .build();
+    let res = client.uncached_client().get(url).send().await?;
+
+    assert!(res.status().is_success());
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+
+    Ok(())
+}
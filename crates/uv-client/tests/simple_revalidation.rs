@@ -0,0 +1,103 @@
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::Result;
+use futures::future;
+use hyper::header::{CACHE_CONTROL, CONTENT_TYPE, ETAG, IF_NONE_MATCH};
+use hyper::server::conn::Http;
+use hyper::service::service_fn;
+use hyper::{Body, Request, Response, StatusCode};
+use tokio::net::TcpListener;
+
+use distribution_types::{IndexLocations, IndexUrl};
+use uv_cache::{Cache, Refresh, Timestamp};
+use uv_client::RegistryClientBuilder;
+use uv_normalize::PackageName;
+
+const ETAG_VALUE: &str = "\"flask-v1\"";
+
+/// Start a Simple API server that tags each page with an `ETag`, responds to matching
+/// `If-None-Match` requests with `304 Not Modified`, and records the `If-None-Match` header (if
+/// any) of each request.
+async fn server(requests: Arc<Mutex<Vec<Option<String>>>>) -> Result<String> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+
+    tokio::spawn(async move {
+        loop {
+            let (socket, _) = listener.accept().await.unwrap();
+            let requests = requests.clone();
+            let svc = service_fn(move |req: Request<Body>| {
+                let if_none_match = req
+                    .headers()
+                    .get(IF_NONE_MATCH)
+                    .and_then(|value| value.to_str().ok())
+                    .map(ToString::to_string);
+                let not_modified = if_none_match.as_deref() == Some(ETAG_VALUE);
+                requests.lock().unwrap().push(if_none_match);
+
+                let response = Response::builder()
+                    .header(ETAG, ETAG_VALUE)
+                    .header(CACHE_CONTROL, "max-age=600");
+                let response = if not_modified {
+                    response
+                        .status(StatusCode::NOT_MODIFIED)
+                        .body(Body::empty())
+                } else {
+                    response.header(CONTENT_TYPE, "text/html").body(Body::from(
+                        r#"<a href="/files/flask-3.0.0.tar.gz">flask-3.0.0.tar.gz</a>"#,
+                    ))
+                };
+                future::ok::<_, hyper::Error>(response.unwrap())
+            });
+            Http::new()
+                .http1_keep_alive(false)
+                .serve_connection(socket, svc)
+                .await
+                .expect("Server Started");
+        }
+    });
+
+    Ok(format!("http://{addr}"))
+}
+
+#[tokio::test]
+async fn simple_revalidation() -> Result<()> {
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let index = server(requests.clone()).await?;
+    let index_locations = IndexLocations::new(
+        Some(IndexUrl::from_str(&format!("{index}/simple"))?),
+        Vec::new(),
+        Vec::new(),
+        false,
+    );
+    let package = PackageName::from_str("flask")?;
+    let cache = Cache::temp()?;
+
+    // The first request populates the cache.
+    let client = RegistryClientBuilder::new(cache.clone())
+        .index_urls(index_locations.index_urls())
+        .retries(0)
+        .build();
+    assert_eq!(client.simple(&package).await?.len(), 1);
+
+    // While the page is fresh, the cache is used without a request.
+    assert_eq!(client.simple(&package).await?.len(), 1);
+    assert_eq!(*requests.lock().unwrap(), vec![None]);
+
+    // Refreshing the cache revalidates the page with a conditional request, and reuses the
+    // cached page when the server responds with `304 Not Modified`.
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    let client = RegistryClientBuilder::new(cache.with_refresh(Refresh::All(Timestamp::now())))
+        .index_urls(index_locations.index_urls())
+        .retries(0)
+        .build();
+    assert_eq!(client.simple(&package).await?.len(), 1);
+    assert_eq!(
+        *requests.lock().unwrap(),
+        vec![None, Some(ETAG_VALUE.to_string())]
+    );
+
+    Ok(())
+}
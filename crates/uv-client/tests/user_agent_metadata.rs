@@ -0,0 +1,47 @@
+use std::env;
+
+use anyhow::Result;
+use pep508_rs::{MarkerEnvironment, StringVersion};
+use uv_client::BaseClientBuilder;
+use uv_version::version;
+
+#[test]
+fn user_agent_metadata_opt_out() -> Result<()> {
+    let markers = MarkerEnvironment {
+        implementation_name: "cpython".to_string(),
+        implementation_version: StringVersion {
+            string: "3.12.2".to_string(),
+            version: "3.12.2".parse()?,
+        },
+        os_name: "posix".to_string(),
+        platform_machine: "x86_64".to_string(),
+        platform_python_implementation: "CPython".to_string(),
+        platform_release: "6.5.0-1016-azure".to_string(),
+        platform_system: "Linux".to_string(),
+        platform_version: "#16~22.04.1-Ubuntu SMP Fri Feb 16 15:42:02 UTC 2024".to_string(),
+        python_full_version: StringVersion {
+            string: "3.12.2".to_string(),
+            version: "3.12.2".parse()?,
+        },
+        python_version: StringVersion {
+            string: "3.12".to_string(),
+            version: "3.12".parse()?,
+        },
+        sys_platform: "linux".to_string(),
+    };
+    let builder = BaseClientBuilder::new().markers(&markers);
+
+    // By default, the installer metadata follows the version.
+    env::remove_var("UV_NO_USER_AGENT_METADATA");
+    let user_agent = builder.user_agent();
+    let (prefix, metadata) = user_agent.split_once(' ').unwrap();
+    assert_eq!(prefix, format!("uv/{}", version()));
+    assert!(metadata.starts_with(r#"{"installer":{"name":"uv","#));
+    assert!(metadata.contains(r#""python":"3.12.2""#));
+
+    // But it can be omitted.
+    env::set_var("UV_NO_USER_AGENT_METADATA", "1");
+    assert_eq!(builder.user_agent(), format!("uv/{}", version()));
+
+    Ok(())
+}
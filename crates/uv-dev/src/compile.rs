@@ -28,6 +28,8 @@ pub(crate) async fn compile(args: CompileArgs) -> anyhow::Result<()> {
         &fs_err::canonicalize(args.root)?,
         &interpreter,
         cache.root(),
+        // Leave the RECORD of any existing distributions untouched.
+        &[],
     )
     .await?;
     info!("Compiled {files} files");
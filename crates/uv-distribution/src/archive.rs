@@ -0,0 +1,12 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// A downloaded wheel that was unzipped into the cache, as persisted in the HTTP cache.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct Archive {
+    /// The path to the unzipped wheel in the cache.
+    pub(crate) path: PathBuf,
+    /// The SHA-256 digest of the downloaded wheel.
+    pub(crate) sha256: String,
+}
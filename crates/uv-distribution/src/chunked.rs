@@ -0,0 +1,103 @@
+use std::env;
+use std::ops::RangeInclusive;
+
+use uv_warnings::warn_user_once;
+
+/// Settings for downloading very large wheels as concurrent range requests, to saturate links
+/// that a single connection can't.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ChunkedDownloads {
+    /// The size (in bytes) above which wheels are downloaded in chunks.
+    pub(crate) threshold: u64,
+    /// The number of chunks to fetch concurrently. A value of `1` disables chunked downloads.
+    pub(crate) concurrency: usize,
+}
+
+impl Default for ChunkedDownloads {
+    fn default() -> Self {
+        Self {
+            threshold: 256 * 1024 * 1024,
+            concurrency: 8,
+        }
+    }
+}
+
+impl ChunkedDownloads {
+    /// Read the chunked download settings from the environment, via
+    /// `UV_CHUNKED_DOWNLOAD_THRESHOLD` and `UV_CHUNKED_DOWNLOAD_CONCURRENCY`.
+    pub(crate) fn from_env() -> Self {
+        let default = Self::default();
+        let threshold = env::var("UV_CHUNKED_DOWNLOAD_THRESHOLD")
+            .ok()
+            .and_then(|value| {
+                value.parse::<u64>().map_or_else(
+                    |_| {
+                        warn_user_once!("Ignoring invalid value from environment for UV_CHUNKED_DOWNLOAD_THRESHOLD. Expected integer number of bytes, got \"{value}\".");
+                        None
+                    },
+                    Some,
+                )
+            })
+            .unwrap_or(default.threshold);
+        let concurrency = env::var("UV_CHUNKED_DOWNLOAD_CONCURRENCY")
+            .ok()
+            .and_then(|value| match value.parse::<usize>() {
+                Ok(concurrency) if concurrency > 0 => Some(concurrency),
+                _ => {
+                    warn_user_once!("Ignoring invalid value from environment for UV_CHUNKED_DOWNLOAD_CONCURRENCY. Expected positive integer, got \"{value}\".");
+                    None
+                }
+            })
+            .unwrap_or(default.concurrency);
+        Self {
+            threshold,
+            concurrency,
+        }
+    }
+
+    /// Returns `true` if a file of the given size should be downloaded in chunks.
+    pub(crate) fn enabled(&self, size: u64) -> bool {
+        self.concurrency > 1 && size >= self.threshold
+    }
+
+    /// Split a file of the given size into (inclusive) byte ranges, one per concurrent request.
+    pub(crate) fn ranges(&self, size: u64) -> Vec<RangeInclusive<u64>> {
+        let chunk = size.div_ceil(self.concurrency as u64).max(1);
+        (0..size)
+            .step_by(usize::try_from(chunk).unwrap_or(usize::MAX))
+            .map(|start| RangeInclusive::new(start, (start + chunk).min(size) - 1))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::chunked::ChunkedDownloads;
+
+    #[test]
+    fn ranges() {
+        let chunked = ChunkedDownloads {
+            threshold: 0,
+            concurrency: 3,
+        };
+        assert_eq!(chunked.ranges(10), vec![0..=3, 4..=7, 8..=9]);
+        assert_eq!(chunked.ranges(2), vec![0..=0, 1..=1]);
+        assert_eq!(chunked.ranges(0), vec![]);
+    }
+
+    #[test]
+    fn enabled() {
+        let chunked = ChunkedDownloads {
+            threshold: 100,
+            concurrency: 4,
+        };
+        assert!(chunked.enabled(100));
+        assert!(!chunked.enabled(99));
+
+        let chunked = ChunkedDownloads {
+            threshold: 100,
+            concurrency: 1,
+        };
+        assert!(!chunked.enabled(1000));
+    }
+}
@@ -1,12 +1,15 @@
 use std::io;
+use std::ops::RangeInclusive;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
-use futures::{FutureExt, TryStreamExt};
+use futures::{FutureExt, StreamExt, TryStreamExt};
+use reqwest::header::{HeaderValue, ACCEPT_RANGES, CONTENT_RANGE, ETAG, IF_RANGE, RANGE};
+use reqwest::StatusCode;
 use tempfile::TempDir;
-use tokio::io::AsyncSeekExt;
+use tokio::io::{AsyncSeekExt, AsyncWriteExt};
 use tokio_util::compat::FuturesAsyncReadCompatExt;
-use tracing::{info_span, instrument, warn, Instrument};
+use tracing::{debug, info_span, instrument, warn, Instrument};
 use url::Url;
 
 use distribution_filename::WheelFilename;
@@ -14,15 +17,24 @@ use distribution_types::{
     BuildableSource, BuiltDist, Dist, FileLocation, IndexLocations, LocalEditable, Name, SourceDist,
 };
 use platform_tags::Tags;
-use pypi_types::Metadata23;
+use pypi_types::{Hashes, Metadata23};
 use uv_cache::{ArchiveTimestamp, CacheBucket, CacheEntry, CachedByTimestamp, WheelCache};
 use uv_client::{CacheControl, CachedClientError, Connectivity, RegistryClient};
 use uv_fs::write_atomic;
-use uv_types::{BuildContext, NoBinary, NoBuild};
+use uv_types::{BuildContext, NoBinary, NoBuild, RequiredHashes};
 
+use crate::archive::Archive;
+use crate::chunked::ChunkedDownloads;
+use crate::hash::{self, DownloadHasher, HashReader};
 use crate::locks::Locks;
+use crate::partial::PartialDownload;
+use crate::resumable::download_resumable;
 use crate::{Error, LocalWheel, Reporter, SourceDistributionBuilder};
 
+/// The size above which wheels are downloaded to disk prior to unzipping, rather than streamed,
+/// such that an interrupted download can be resumed rather than restarted.
+const RESUMABLE_DOWNLOAD_THRESHOLD: u64 = 64 * 1024 * 1024;
+
 /// A cached high-level interface to convert distributions (a requirement resolved to a location)
 /// to a wheel or wheel metadata.
 ///
@@ -40,6 +52,8 @@ pub struct DistributionDatabase<'a, Context: BuildContext + Send + Sync> {
     build_context: &'a Context,
     builder: SourceDistributionBuilder<'a, Context>,
     locks: Arc<Locks>,
+    chunked: ChunkedDownloads,
+    hashes: RequiredHashes,
 }
 
 impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context> {
@@ -49,6 +63,19 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
             build_context,
             builder: SourceDistributionBuilder::new(client, build_context),
             locks: Arc::new(Locks::default()),
+            chunked: ChunkedDownloads::from_env(),
+            hashes: RequiredHashes::default(),
+        }
+    }
+
+    /// Set the hashes that every fetched distribution is required to match (as in
+    /// `--require-hashes`).
+    #[must_use]
+    pub fn with_required_hashes(self, hashes: RequiredHashes) -> Self {
+        Self {
+            builder: self.builder.with_required_hashes(hashes.clone()),
+            hashes,
+            ..self
         }
     }
 
@@ -62,26 +89,15 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
         }
     }
 
-    /// Handle a specific `reqwest` error, and convert it to [`io::Error`].
-    fn handle_response_errors(&self, err: reqwest::Error) -> io::Error {
-        if err.is_timeout() {
-            io::Error::new(
-                io::ErrorKind::TimedOut,
-                format!(
-                    "Failed to download distribution due to network timeout. Try increasing UV_HTTP_TIMEOUT (current value: {}s).",  self.client.timeout()
-                ),
-            )
-        } else {
-            io::Error::new(io::ErrorKind::Other, err)
-        }
-    }
-
     /// Either fetch the wheel or fetch and build the source distribution
     ///
     /// If `no_remote_wheel` is set, the wheel will be built from a source distribution
     /// even if compatible pre-built wheels are available.
     #[instrument(skip_all, fields(%dist))]
     pub async fn get_or_build_wheel(&self, dist: &Dist, tags: &Tags) -> Result<LocalWheel, Error> {
+        if !self.hashes.is_empty() {
+            self.check_required_hashes(dist).await?;
+        }
         match dist {
             Dist::Built(built) => self.get_wheel(built).await,
             Dist::Source(source) => self.build_wheel(source, tags).await,
@@ -99,32 +115,44 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
         match dist {
             Dist::Built(built) => self.get_wheel_metadata(built).await,
             Dist::Source(source) => {
+                // If the index serves the metadata for the source distribution directly (PEP 658),
+                // we can avoid downloading and building it.
+                if let SourceDist::Registry(sdist) = source {
+                    if let Some(metadata) = self.client.sdist_metadata(sdist).await? {
+                        return Ok(metadata);
+                    }
+                }
                 self.build_wheel_metadata(&BuildableSource::Dist(source))
                     .await
             }
         }
     }
 
-    /// Build a directory into an editable wheel.
+    /// Build a directory into an editable wheel, or fetch it from the cache if possible.
     pub async fn build_wheel_editable(
         &self,
         editable: &LocalEditable,
-        editable_wheel_dir: &Path,
+        tags: &Tags,
     ) -> Result<(LocalWheel, Metadata23), Error> {
         // Build the wheel.
-        let (dist, disk_filename, filename, metadata) = self
-            .builder
-            .build_editable(editable, editable_wheel_dir)
-            .await?;
-
-        // Unzip.
-        let path = editable_wheel_dir.join(&disk_filename);
-        let target = editable_wheel_dir.join(cache_key::digest(&editable.path));
-        let archive = self.unzip_wheel(&path, &target).await?;
+        let (dist, built_wheel, metadata) =
+            self.builder.build_editable(editable, tags).boxed().await?;
+
+        // If the wheel was unzipped previously, respect it. Editables are cached under a unique
+        // build ID, so unzipped directories are never stale.
+        let archive = match built_wheel.target.canonicalize() {
+            Ok(archive) => archive,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                self.unzip_wheel(&built_wheel.path, &built_wheel.target)
+                    .await?
+            }
+            Err(err) => return Err(Error::CacheRead(err)),
+        };
         let wheel = LocalWheel {
             dist,
-            filename,
+            filename: built_wheel.filename,
             archive,
+            sha256: None,
         };
 
         Ok((wheel, metadata))
@@ -162,6 +190,14 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
                     }
                 };
 
+                // If the index doesn't provide a SHA-256 hash, verify the wheel against the
+                // required hashes, if any, as it's downloaded.
+                let required = if wheel.file.hashes.sha256.is_some() {
+                    &[]
+                } else {
+                    self.hashes.get(wheel.name()).unwrap_or_default()
+                };
+
                 // Create a cache entry for the wheel.
                 let wheel_entry = self.build_context.cache().entry(
                     CacheBucket::Wheels,
@@ -169,15 +205,45 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
                     wheel.filename.stem(),
                 );
 
+                // Download large wheels to disk, such that interrupted downloads can be resumed.
+                if wheel.file.size.is_some_and(|size| {
+                    size >= RESUMABLE_DOWNLOAD_THRESHOLD || self.chunked.enabled(size)
+                }) {
+                    let archive = self
+                        .download_wheel(
+                            url,
+                            &wheel.filename,
+                            &wheel.file.hashes,
+                            required,
+                            &wheel_entry,
+                            dist,
+                        )
+                        .await?;
+                    return Ok(LocalWheel {
+                        dist: Dist::Built(dist.clone()),
+                        archive: archive.path,
+                        filename: wheel.filename.clone(),
+                        sha256: Some(archive.sha256),
+                    });
+                }
+
                 // Download and unzip.
                 match self
-                    .stream_wheel(url.clone(), &wheel.filename, &wheel_entry, dist)
+                    .stream_wheel(
+                        url.clone(),
+                        &wheel.filename,
+                        &wheel.file.hashes,
+                        required,
+                        &wheel_entry,
+                        dist,
+                    )
                     .await
                 {
                     Ok(archive) => Ok(LocalWheel {
                         dist: Dist::Built(dist.clone()),
-                        archive,
+                        archive: archive.path,
                         filename: wheel.filename.clone(),
+                        sha256: Some(archive.sha256),
                     }),
                     Err(Error::Extract(err)) if err.is_http_streaming_unsupported() => {
                         warn!(
@@ -187,12 +253,20 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
                         // If the request failed because streaming is unsupported, download the
                         // wheel directly.
                         let archive = self
-                            .download_wheel(url, &wheel.filename, &wheel_entry, dist)
+                            .download_wheel(
+                                url,
+                                &wheel.filename,
+                                &wheel.file.hashes,
+                                required,
+                                &wheel_entry,
+                                dist,
+                            )
                             .await?;
                         Ok(LocalWheel {
                             dist: Dist::Built(dist.clone()),
-                            archive,
+                            archive: archive.path,
                             filename: wheel.filename.clone(),
+                            sha256: Some(archive.sha256),
                         })
                     }
                     Err(err) => Err(err),
@@ -200,6 +274,10 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
             }
 
             BuiltDist::DirectUrl(wheel) => {
+                // Direct URLs don't provide any hashes, so verify the wheel against the required
+                // hashes, if any, as it's downloaded.
+                let required = self.hashes.get(wheel.name()).unwrap_or_default();
+
                 // Create a cache entry for the wheel.
                 let wheel_entry = self.build_context.cache().entry(
                     CacheBucket::Wheels,
@@ -209,13 +287,21 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
 
                 // Download and unzip.
                 match self
-                    .stream_wheel(wheel.url.raw().clone(), &wheel.filename, &wheel_entry, dist)
+                    .stream_wheel(
+                        wheel.url.raw().clone(),
+                        &wheel.filename,
+                        &Hashes::default(),
+                        required,
+                        &wheel_entry,
+                        dist,
+                    )
                     .await
                 {
                     Ok(archive) => Ok(LocalWheel {
                         dist: Dist::Built(dist.clone()),
-                        archive,
+                        archive: archive.path,
                         filename: wheel.filename.clone(),
+                        sha256: Some(archive.sha256),
                     }),
                     Err(Error::Client(err)) if err.is_http_streaming_unsupported() => {
                         warn!(
@@ -228,14 +314,17 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
                             .download_wheel(
                                 wheel.url.raw().clone(),
                                 &wheel.filename,
+                                &Hashes::default(),
+                                required,
                                 &wheel_entry,
                                 dist,
                             )
                             .await?;
                         Ok(LocalWheel {
                             dist: Dist::Built(dist.clone()),
-                            archive,
+                            archive: archive.path,
                             filename: wheel.filename.clone(),
+                            sha256: Some(archive.sha256),
                         })
                     }
                     Err(err) => Err(err),
@@ -274,6 +363,7 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
                     dist: Dist::Source(dist.clone()),
                     archive,
                     filename: built_wheel.filename,
+                    sha256: None,
                 });
             }
             Err(err) if err.kind() == io::ErrorKind::NotFound => {}
@@ -287,6 +377,7 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
                 .unzip_wheel(&built_wheel.path, &built_wheel.target)
                 .await?,
             filename: built_wheel.filename,
+            sha256: None,
         })
     }
 
@@ -335,42 +426,122 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
         Ok(metadata)
     }
 
+    /// Verify that a distribution matches the hashes required for its package, prior to fetching
+    /// it.
+    ///
+    /// Registry distributions published with a SHA-256 hash must match one of the required
+    /// hashes, as their downloads are verified against the index; local archives are hashed
+    /// directly; and registry distributions without a hash, along with archives at direct URLs,
+    /// are verified as they're downloaded. Other distributions (like Git repositories and source
+    /// trees) can't be hashed at all.
+    async fn check_required_hashes(&self, dist: &Dist) -> Result<(), Error> {
+        let Some(required) = self.hashes.get(dist.name()) else {
+            return Err(Error::MissingRequiredHash(dist.name().clone()));
+        };
+
+        let file = match dist {
+            Dist::Built(BuiltDist::Registry(wheel)) => Some(&wheel.file),
+            Dist::Source(SourceDist::Registry(sdist)) => Some(&sdist.file),
+            _ => None,
+        };
+        if let Some(file) = file {
+            let url = match &file.url {
+                FileLocation::RelativeUrl(base, url) => {
+                    pypi_types::base_url_join_relative(base, url)?
+                }
+                FileLocation::AbsoluteUrl(url) => {
+                    Url::parse(url).map_err(|err| Error::Url(url.clone(), err))?
+                }
+                FileLocation::Path(path) => {
+                    let url = Url::from_file_path(path).expect("path is absolute");
+                    return verify_file(&url, path, required).await;
+                }
+            };
+            return match file.hashes.sha256.as_deref() {
+                Some(sha256) => hash::verify_required(&url, required, sha256),
+                None => Ok(()),
+            };
+        }
+
+        match dist {
+            Dist::Built(BuiltDist::DirectUrl(_)) | Dist::Source(SourceDist::DirectUrl(_)) => Ok(()),
+            Dist::Built(BuiltDist::Path(wheel)) => {
+                verify_file(wheel.url.raw(), &wheel.path, required).await
+            }
+            Dist::Source(SourceDist::Path(sdist)) if sdist.path.is_file() => {
+                verify_file(sdist.url.raw(), &sdist.path, required).await
+            }
+            _ => Err(Error::UnhashableDist(dist.to_string())),
+        }
+    }
+
     /// Stream a wheel from a URL, unzipping it into the cache as it's downloaded.
+    ///
+    /// The wheel is verified against the given hashes as it's streamed, and is only persisted to
+    /// the cache if it matches. If any `required` hashes are provided, the wheel must match one of
+    /// them, and is always downloaded, rather than read from the HTTP cache.
     async fn stream_wheel(
         &self,
         url: Url,
         filename: &WheelFilename,
+        hashes: &Hashes,
+        required: &[String],
         wheel_entry: &CacheEntry,
         dist: &BuiltDist,
-    ) -> Result<PathBuf, Error> {
+    ) -> Result<Archive, Error> {
         // Create an entry for the HTTP cache.
         let http_entry = wheel_entry.with_file(format!("{}.http", filename.stem()));
 
         let download = |response: reqwest::Response| {
             async {
-                let reader = response
-                    .bytes_stream()
-                    .map_err(|err| self.handle_response_errors(err))
+                let reader = self
+                    .client
+                    .uncached_client()
+                    .bytes_stream(response)
                     .into_async_read();
+                let mut reader = HashReader::new(reader);
 
                 // Download and unzip the wheel to a temporary directory.
                 let temp_dir = tempfile::tempdir_in(self.build_context.cache().root())
                     .map_err(Error::CacheWrite)?;
-                uv_extract::stream::unzip(reader.compat(), temp_dir.path()).await?;
+                uv_extract::stream::unzip((&mut reader).compat(), temp_dir.path()).await?;
+
+                // Verify the wheel before persisting it to the cache.
+                let digest = reader.finish().await.map_err(Error::CacheWrite)?;
+                hash::verify(&url, hashes, &digest)?;
+                if !required.is_empty() {
+                    hash::verify_required(&url, required, &digest)?;
+                }
 
                 // Persist the temporary directory to the directory store.
-                let archive = self
+                let path = self
                     .build_context
                     .cache()
                     .persist(temp_dir.into_path(), wheel_entry.path())
                     .await
                     .map_err(Error::CacheRead)?;
-                Ok(archive)
+                Ok(Archive {
+                    path,
+                    sha256: digest,
+                })
             }
             .instrument(info_span!("wheel", wheel = %dist))
         };
 
         let req = self.request(url.clone())?;
+
+        // Cached wheels may not have been verified against the required hashes, so bypass the
+        // HTTP cache.
+        if !required.is_empty() {
+            let response = self
+                .client
+                .uncached_client()
+                .execute(req)
+                .await?
+                .error_for_status()?;
+            return download(response).await;
+        }
+
         let cache_control = match self.client.connectivity() {
             Connectivity::Online => CacheControl::from(
                 self.build_context
@@ -394,53 +565,100 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
     }
 
     /// Download a wheel from a URL, then unzip it into the cache.
+    ///
+    /// The wheel is verified against the given hashes as it's downloaded, and is only unzipped
+    /// into the cache if it matches. If any `required` hashes are provided, the wheel must match
+    /// one of them, and is always downloaded, rather than read from the HTTP cache.
     async fn download_wheel(
         &self,
         url: Url,
         filename: &WheelFilename,
+        hashes: &Hashes,
+        required: &[String],
         wheel_entry: &CacheEntry,
         dist: &BuiltDist,
-    ) -> Result<PathBuf, Error> {
+    ) -> Result<Archive, Error> {
         // Create an entry for the HTTP cache.
         let http_entry = wheel_entry.with_file(format!("{}.http", filename.stem()));
 
         let download = |response: reqwest::Response| {
             async {
-                let reader = response
-                    .bytes_stream()
-                    .map_err(|err| self.handle_response_errors(err))
-                    .into_async_read();
-
-                // Download the wheel to a temporary file.
-                let temp_file = tempfile::tempfile_in(self.build_context.cache().root())
-                    .map_err(Error::CacheWrite)?;
-                let mut writer = tokio::io::BufWriter::new(tokio::fs::File::from_std(temp_file));
-                tokio::io::copy(&mut reader.compat(), &mut writer)
-                    .await
+                // Download the wheel to a partial file, resuming any interrupted download.
+                let partial = PartialDownload::acquire(self.build_context.cache(), &url)
                     .map_err(Error::CacheWrite)?;
+                let mut hasher = DownloadHasher::new();
+                let mut file = self
+                    .download_partial(&url, &partial, response, &mut hasher)
+                    .await?;
+
+                // Verify the wheel before unzipping it into the cache. If the digest couldn't be
+                // computed during the download (e.g., for chunked downloads), hash the file.
+                let digest = match hasher.finish() {
+                    Some(digest) => digest,
+                    None => hash::sha256_file(&mut file)
+                        .await
+                        .map_err(Error::CacheRead)?,
+                };
+                let verified = hash::verify(&url, hashes, &digest).and_then(|()| {
+                    if required.is_empty() {
+                        Ok(())
+                    } else {
+                        hash::verify_required(&url, required, &digest)
+                    }
+                });
+                if let Err(err) = verified {
+                    // Discard the download, such that it isn't resumed on the next attempt.
+                    drop(file);
+                    if let Err(err) = partial.remove().await {
+                        warn!("Failed to remove partial download of {url}: {err}");
+                    }
+                    return Err(err);
+                }
 
                 // Unzip the wheel to a temporary directory.
                 let temp_dir = tempfile::tempdir_in(self.build_context.cache().root())
                     .map_err(Error::CacheWrite)?;
-                let mut file = writer.into_inner();
                 file.seek(io::SeekFrom::Start(0))
                     .await
                     .map_err(Error::CacheWrite)?;
-                uv_extract::seek::unzip(file, temp_dir.path()).await?;
+                let unzipped = uv_extract::seek::unzip(file, temp_dir.path()).await;
+
+                // Discard the download whether or not it could be unzipped, such that a corrupt
+                // file isn't resumed on the next attempt.
+                if let Err(err) = partial.remove().await {
+                    warn!("Failed to remove partial download of {url}: {err}");
+                }
+                unzipped?;
 
                 // Persist the temporary directory to the directory store.
-                let archive = self
+                let path = self
                     .build_context
                     .cache()
                     .persist(temp_dir.into_path(), wheel_entry.path())
                     .await
                     .map_err(Error::CacheRead)?;
-                Ok(archive)
+                Ok(Archive {
+                    path,
+                    sha256: digest,
+                })
             }
             .instrument(info_span!("wheel", wheel = %dist))
         };
 
         let req = self.request(url.clone())?;
+
+        // Cached downloads may not have been verified against the required hashes, so bypass the
+        // HTTP cache.
+        if !required.is_empty() {
+            let response = self
+                .client
+                .uncached_client()
+                .execute(req)
+                .await?
+                .error_for_status()?;
+            return download(response).await;
+        }
+
         let cache_control = match self.client.connectivity() {
             Connectivity::Online => CacheControl::from(
                 self.build_context
@@ -464,6 +682,121 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
         Ok(archive)
     }
 
+    /// Download the file behind a response to the given [`PartialDownload`], either in concurrent
+    /// chunks (for very large files) or sequentially.
+    async fn download_partial(
+        &self,
+        url: &Url,
+        partial: &PartialDownload,
+        response: reqwest::Response,
+        hasher: &mut DownloadHasher,
+    ) -> Result<tokio::fs::File, Error> {
+        let Some((size, etag)) = response
+            .content_length()
+            .filter(|size| self.chunked.enabled(*size))
+            .zip(range_etag(&response))
+        else {
+            return download_resumable(self.client, url, partial, response, hasher).await;
+        };
+
+        // Discard the response, and request the file in chunks instead.
+        drop(response);
+        debug!(
+            "Downloading {url} ({size} bytes) in {} concurrent chunks",
+            self.chunked.concurrency
+        );
+        // Chunks are written out of order, so can't be hashed as they're downloaded.
+        hasher.discard();
+        match self.download_chunked(url, partial, size, &etag).await {
+            Ok(file) => Ok(file),
+            Err(err) => {
+                warn!("Chunked download of {url} failed; downloading sequentially ({err})");
+                let response = self
+                    .client
+                    .uncached_client()
+                    .execute(self.request(url.clone())?)
+                    .await?
+                    .error_for_status()?;
+                download_resumable(self.client, url, partial, response, hasher).await
+            }
+        }
+    }
+
+    /// Download a file of the given size to the given [`PartialDownload`] as concurrent range
+    /// requests, each of which must match the given `ETag`.
+    async fn download_chunked(
+        &self,
+        url: &Url,
+        partial: &PartialDownload,
+        size: u64,
+        etag: &str,
+    ) -> Result<tokio::fs::File, Error> {
+        // Pre-allocate the file, such that each chunk can be written at its offset.
+        let file = partial.open(0, None).await.map_err(Error::CacheWrite)?;
+        file.set_len(size).await.map_err(Error::CacheWrite)?;
+
+        futures::stream::iter(self.chunked.ranges(size))
+            .map(|range| self.download_chunk(url, partial, range, etag))
+            .buffer_unordered(self.chunked.concurrency)
+            .try_collect::<Vec<()>>()
+            .await?;
+
+        Ok(file)
+    }
+
+    /// Download a single (inclusive) byte range of a file to the given [`PartialDownload`].
+    async fn download_chunk(
+        &self,
+        url: &Url,
+        partial: &PartialDownload,
+        range: RangeInclusive<u64>,
+        etag: &str,
+    ) -> Result<(), Error> {
+        let (start, end) = (*range.start(), *range.end());
+
+        let mut req = self.request(url.clone())?;
+        req.headers_mut().insert(
+            RANGE,
+            HeaderValue::from_str(&format!("bytes={start}-{end}")).expect("valid header value"),
+        );
+        if let Ok(etag) = HeaderValue::from_str(etag) {
+            req.headers_mut().insert(IF_RANGE, etag);
+        }
+        let response = self
+            .client
+            .uncached_client()
+            .execute(req)
+            .await?
+            .error_for_status()?;
+
+        // If the file changed, the server returns it in its entirety.
+        let expected = format!("bytes {start}-{end}/");
+        if response.status() != StatusCode::PARTIAL_CONTENT
+            || !response
+                .headers()
+                .get(CONTENT_RANGE)
+                .and_then(|value| value.to_str().ok())
+                .is_some_and(|value| value.starts_with(&expected))
+        {
+            return Err(Error::UnexpectedRange(url.clone()));
+        }
+
+        let mut file = partial.open_at(start).await.map_err(Error::CacheWrite)?;
+        let mut stream = self.client.uncached_client().bytes_stream(response);
+        let mut written = 0;
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(Error::CacheWrite)?;
+            file.write_all(&chunk).await.map_err(Error::CacheWrite)?;
+            written += chunk.len() as u64;
+        }
+        file.flush().await.map_err(Error::CacheWrite)?;
+
+        if written != end - start + 1 {
+            return Err(Error::UnexpectedRange(url.clone()));
+        }
+        Ok(())
+    }
+
     /// Load a wheel from a local path.
     async fn load_wheel(
         &self,
@@ -485,6 +818,7 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
                 dist: Dist::Built(dist.clone()),
                 archive,
                 filename: filename.clone(),
+                sha256: None,
             })
         } else {
             // Otherwise, unzip the wheel.
@@ -495,6 +829,7 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
                 dist: Dist::Built(dist.clone()),
                 archive,
                 filename: filename.clone(),
+                sha256: None,
             })
         }
     }
@@ -526,17 +861,7 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
 
     /// Returns a GET [`reqwest::Request`] for the given URL.
     fn request(&self, url: Url) -> Result<reqwest::Request, reqwest::Error> {
-        self.client
-            .uncached_client()
-            .get(url)
-            .header(
-                // `reqwest` defaults to accepting compressed responses.
-                // Specify identity encoding to get consistent .whl downloading
-                // behavior from servers. ref: https://github.com/pypa/pip/pull/1688
-                "accept-encoding",
-                reqwest::header::HeaderValue::from_static("identity"),
-            )
-            .build()
+        download_request(self.client, url)
     }
 
     /// Return the [`IndexLocations`] used by this resolver.
@@ -545,6 +870,40 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
     }
 }
 
+/// Returns a GET [`reqwest::Request`] for the given URL.
+pub(crate) fn download_request(
+    client: &RegistryClient,
+    url: Url,
+) -> Result<reqwest::Request, reqwest::Error> {
+    client
+        .uncached_client()
+        .get(url)
+        .header(
+            // `reqwest` defaults to accepting compressed responses.
+            // Specify identity encoding to get consistent .whl downloading
+            // behavior from servers. ref: https://github.com/pypa/pip/pull/1688
+            "accept-encoding",
+            reqwest::header::HeaderValue::from_static("identity"),
+        )
+        .build()
+}
+
+/// Return the `ETag` of a response, if the server supports range requests for the file.
+///
+/// A weak `ETag` doesn't guarantee byte-for-byte equality, so can't be used to combine ranges.
+pub(crate) fn range_etag(response: &reqwest::Response) -> Option<String> {
+    let accepts_ranges = response
+        .headers()
+        .get(ACCEPT_RANGES)
+        .is_some_and(|value| value.as_bytes() == b"bytes");
+    response
+        .headers()
+        .get(ETAG)
+        .and_then(|value| value.to_str().ok())
+        .filter(|etag| accepts_ranges && !etag.starts_with("W/"))
+        .map(ToString::to_string)
+}
+
 /// Write a timestamped archive path to the cache.
 async fn write_timestamped_archive(
     cache_entry: &CacheEntry,
@@ -567,7 +926,7 @@ fn read_timestamped_archive(
     cache_entry: &CacheEntry,
     modified: ArchiveTimestamp,
 ) -> Result<Option<PathBuf>, Error> {
-    match fs_err::read(cache_entry.path()) {
+    match fs_err::read(cache_entry.read_path()) {
         Ok(cached) => {
             let cached = rmp_serde::from_slice::<CachedByTimestamp<PathBuf>>(&cached)?;
             if cached.timestamp == modified.timestamp() {
@@ -579,3 +938,18 @@ fn read_timestamped_archive(
     }
     Ok(None)
 }
+
+/// Verify that a local archive matches one of the required hashes.
+async fn verify_file(url: &Url, path: &Path, required: &[String]) -> Result<(), Error> {
+    let mut file = match tokio::fs::File::open(path).await {
+        Ok(file) => file,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            return Err(Error::NotFound(path.to_path_buf()));
+        }
+        Err(err) => return Err(Error::CacheRead(err)),
+    };
+    let digest = hash::sha256_file(&mut file)
+        .await
+        .map_err(Error::CacheRead)?;
+    hash::verify_required(url, required, &digest)
+}
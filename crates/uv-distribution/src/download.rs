@@ -3,6 +3,7 @@ use std::path::{Path, PathBuf};
 use distribution_filename::WheelFilename;
 use distribution_types::{CachedDist, Dist};
 use pypi_types::Metadata23;
+use uv_cache::ArchiveFormat;
 
 use crate::Error;
 
@@ -16,6 +17,9 @@ pub struct LocalWheel {
     /// The canonicalized path in the cache directory to which the wheel was downloaded.
     /// Typically, a directory within the archive bucket.
     pub(crate) archive: PathBuf,
+    /// The SHA-256 digest of the downloaded wheel, if it was downloaded (rather than built or read
+    /// from a local file).
+    pub(crate) sha256: Option<String>,
 }
 
 impl LocalWheel {
@@ -34,16 +38,27 @@ impl LocalWheel {
         &self.filename
     }
 
+    /// Return the SHA-256 digest of the downloaded wheel, if it was downloaded.
+    pub fn sha256(&self) -> Option<&str> {
+        self.sha256.as_deref()
+    }
+
     /// Read the [`Metadata23`] from a wheel.
     pub fn metadata(&self) -> Result<Metadata23, Error> {
-        read_flat_wheel_metadata(&self.filename, &self.archive)
+        if ArchiveFormat::from_path(&self.archive).is_compressed() {
+            let temp_dir = tempfile::tempdir().map_err(Error::CacheRead)?;
+            uv_cache::decompress(&self.archive, temp_dir.path()).map_err(Error::CacheRead)?;
+            read_flat_wheel_metadata(&self.filename, temp_dir.path())
+        } else {
+            read_flat_wheel_metadata(&self.filename, &self.archive)
+        }
     }
 }
 
 /// Convert a [`LocalWheel`] into a [`CachedDist`].
 impl From<LocalWheel> for CachedDist {
     fn from(wheel: LocalWheel) -> CachedDist {
-        CachedDist::from_remote(wheel.dist, wheel.filename, wheel.archive)
+        CachedDist::from_remote(wheel.dist, wheel.filename, wheel.archive, wheel.sha256)
     }
 }
 
@@ -1,5 +1,6 @@
 use std::path::PathBuf;
 use tokio::task::JoinError;
+use url::Url;
 use zip::result::ZipError;
 
 use distribution_filename::WheelFilenameError;
@@ -69,6 +70,20 @@ pub enum Error {
     DynamicPyprojectToml(#[source] pypi_types::MetadataError),
     #[error("Unsupported scheme in URL: {0}")]
     UnsupportedScheme(String),
+    #[error("Received an unexpected response to a range request for: {0}")]
+    UnexpectedRange(Url),
+    #[error("Hash mismatch for {url}: expected sha256 `{expected}`, but the downloaded file has sha256 `{actual}`")]
+    HashMismatch {
+        url: Box<Url>,
+        expected: String,
+        actual: String,
+    },
+    #[error("Hash mismatch for {url}: expected one of the sha256 hashes provided via `--hash`, but the file has sha256 `{actual}`")]
+    RequiredHashMismatch { url: Url, actual: String },
+    #[error("In `--require-hashes` mode, all requirements must have a hash, but none were provided for: `{0}`")]
+    MissingRequiredHash(PackageName),
+    #[error("In `--require-hashes` mode, hashes can't be verified for: {0}")]
+    UnhashableDist(String),
 
     /// A generic request middleware error happened while making a request.
     /// Refer to the error message for more details.
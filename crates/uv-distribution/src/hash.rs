@@ -0,0 +1,221 @@
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::io::AsyncReadExt;
+use sha2::{Digest, Sha256};
+use tokio::io::{AsyncReadExt as _, AsyncSeekExt};
+use url::Url;
+
+use pypi_types::Hashes;
+
+use crate::Error;
+
+/// A reader that computes the SHA-256 digest of the bytes read through it, such that a download
+/// can be verified as it's streamed to disk, rather than in a separate pass.
+pub(crate) struct HashReader<R> {
+    reader: R,
+    hasher: Sha256,
+}
+
+impl<R: futures::AsyncRead + Unpin> HashReader<R> {
+    pub(crate) fn new(reader: R) -> Self {
+        Self {
+            reader,
+            hasher: Sha256::new(),
+        }
+    }
+
+    /// Read any remaining bytes (e.g., the central directory of a streamed zip archive), and
+    /// return the hex-encoded digest of the entire stream.
+    pub(crate) async fn finish(mut self) -> io::Result<String> {
+        let mut buffer = vec![0; 64 * 1024];
+        loop {
+            let n = self.reader.read(&mut buffer).await?;
+            if n == 0 {
+                break;
+            }
+            self.hasher.update(&buffer[..n]);
+        }
+        Ok(format!("{:x}", self.hasher.finalize()))
+    }
+}
+
+impl<R: futures::AsyncRead + Unpin> futures::AsyncRead for HashReader<R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = &mut *self;
+        let poll = Pin::new(&mut this.reader).poll_read(cx, buf);
+        if let Poll::Ready(Ok(n)) = &poll {
+            this.hasher.update(&buf[..*n]);
+        }
+        poll
+    }
+}
+
+/// The SHA-256 digest of a file as it's downloaded, which is discarded if the download resumes
+/// from bytes that weren't observed (e.g., bytes written by a previous invocation).
+pub(crate) struct DownloadHasher {
+    hasher: Option<Sha256>,
+    len: u64,
+}
+
+impl DownloadHasher {
+    pub(crate) fn new() -> Self {
+        Self {
+            hasher: Some(Sha256::new()),
+            len: 0,
+        }
+    }
+
+    /// Hash the next chunk of the file.
+    pub(crate) fn update(&mut self, chunk: &[u8]) {
+        if let Some(hasher) = self.hasher.as_mut() {
+            hasher.update(chunk);
+            self.len += chunk.len() as u64;
+        }
+    }
+
+    /// Continue the download from the given offset, restarting the digest if the download is
+    /// restarted, and discarding it if the download skips over unobserved bytes.
+    pub(crate) fn seek(&mut self, offset: u64) {
+        if offset == 0 {
+            *self = Self::new();
+        } else if offset != self.len {
+            self.discard();
+        }
+    }
+
+    /// Discard the digest (e.g., if the file was written out of order).
+    pub(crate) fn discard(&mut self) {
+        self.hasher = None;
+    }
+
+    /// Return the hex-encoded digest, if every byte of the file was observed in order.
+    pub(crate) fn finish(self) -> Option<String> {
+        self.hasher.map(|hasher| format!("{:x}", hasher.finalize()))
+    }
+}
+
+/// Compute the hex-encoded SHA-256 digest of a file, from the start.
+pub(crate) async fn sha256_file(file: &mut tokio::fs::File) -> io::Result<String> {
+    file.seek(io::SeekFrom::Start(0)).await?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0; 64 * 1024];
+    loop {
+        let n = file.read(&mut buffer).await?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buffer[..n]);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Verify that the SHA-256 digest of a file downloaded from the given URL matches the hash
+/// provided by the index, if any.
+pub(crate) fn verify(url: &Url, hashes: &Hashes, actual: &str) -> Result<(), Error> {
+    let Some(expected) = hashes.sha256.as_deref() else {
+        return Ok(());
+    };
+    if expected.eq_ignore_ascii_case(actual) {
+        Ok(())
+    } else {
+        Err(Error::HashMismatch {
+            url: Box::new(url.clone()),
+            expected: expected.to_string(),
+            actual: actual.to_string(),
+        })
+    }
+}
+
+/// Verify that the SHA-256 digest of a file matches one of the hashes required for its package
+/// (e.g., as provided via `--hash` in a `requirements.txt`).
+pub(crate) fn verify_required(url: &Url, required: &[String], actual: &str) -> Result<(), Error> {
+    if required
+        .iter()
+        .any(|expected| expected.eq_ignore_ascii_case(actual))
+    {
+        Ok(())
+    } else {
+        Err(Error::RequiredHashMismatch {
+            url: url.clone(),
+            actual: actual.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::io::AsyncReadExt;
+    use url::Url;
+
+    use pypi_types::Hashes;
+
+    use super::{verify, verify_required, DownloadHasher, HashReader};
+
+    const HELLO_SHA256: &str = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+
+    #[tokio::test]
+    async fn hash_reader() {
+        let mut reader = HashReader::new(&b"hello"[..]);
+
+        // The digest covers both the bytes read by the caller, and any that remain.
+        let mut buffer = [0; 2];
+        reader.read_exact(&mut buffer).await.unwrap();
+        assert_eq!(reader.finish().await.unwrap(), HELLO_SHA256);
+    }
+
+    #[test]
+    fn download_hasher() {
+        let mut hasher = DownloadHasher::new();
+        hasher.update(b"he");
+        hasher.seek(2);
+        hasher.update(b"llo");
+        assert_eq!(hasher.finish().as_deref(), Some(HELLO_SHA256));
+
+        // Resuming past the observed bytes discards the digest.
+        let mut hasher = DownloadHasher::new();
+        hasher.update(b"he");
+        hasher.seek(4);
+        hasher.update(b"o");
+        assert_eq!(hasher.finish(), None);
+
+        // But restarting the download restarts the digest.
+        hasher = DownloadHasher::new();
+        hasher.discard();
+        hasher.seek(0);
+        hasher.update(b"hello");
+        assert_eq!(hasher.finish().as_deref(), Some(HELLO_SHA256));
+    }
+
+    #[test]
+    fn verify_sha256() {
+        let url = Url::parse("https://files.example.com/hello-1.0.0.tar.gz").unwrap();
+        let hashes = Hashes {
+            md5: None,
+            sha256: Some(HELLO_SHA256.into()),
+            sha384: None,
+            sha512: None,
+        };
+        assert!(verify(&url, &hashes, HELLO_SHA256).is_ok());
+        assert!(verify(&url, &hashes, &"0".repeat(64)).is_err());
+
+        // Files without a SHA-256 hash can't be verified.
+        assert!(verify(&url, &Hashes::default(), &"0".repeat(64)).is_ok());
+    }
+
+    #[test]
+    fn verify_required_sha256() {
+        let url = Url::parse("https://files.example.com/hello-1.0.0.tar.gz").unwrap();
+        let required = ["0".repeat(64), HELLO_SHA256.to_ascii_uppercase()];
+        assert!(verify_required(&url, &required, HELLO_SHA256).is_ok());
+        assert!(verify_required(&url, &required[..1], HELLO_SHA256).is_err());
+
+        // Unlike index hashes, an empty set of required hashes matches nothing.
+        assert!(verify_required(&url, &[], HELLO_SHA256).is_err());
+    }
+}
@@ -1,7 +1,6 @@
 use distribution_types::{git_reference, DirectUrlSourceDist, GitSourceDist, PathSourceDist};
 use platform_tags::Tags;
-use uv_cache::{ArchiveTimestamp, Cache, CacheBucket, CacheShard, WheelCache};
-use uv_fs::symlinks;
+use uv_cache::{ArchiveTimestamp, Cache, CacheBucket, CacheMetrics, CacheShard, WheelCache};
 
 use crate::index::cached_wheel::CachedWheel;
 use crate::source::{read_http_revision, read_timestamped_revision, REVISION};
@@ -30,6 +29,7 @@ impl BuiltWheelIndex {
         // enforce freshness on the entries.
         let revision_entry = cache_shard.entry(REVISION);
         let Some(revision) = read_http_revision(&revision_entry)? else {
+            CacheMetrics::record_miss();
             return Ok(None);
         };
 
@@ -58,6 +58,7 @@ impl BuiltWheelIndex {
         // enforce freshness on the entries.
         let revision_entry = cache_shard.entry(REVISION);
         let Some(revision) = read_timestamped_revision(&revision_entry, modified)? else {
+            CacheMetrics::record_miss();
             return Ok(None);
         };
 
@@ -67,6 +68,7 @@ impl BuiltWheelIndex {
     /// Return the most compatible [`CachedWheel`] for a given source distribution at a git URL.
     pub fn git(source_dist: &GitSourceDist, cache: &Cache, tags: &Tags) -> Option<CachedWheel> {
         let Ok(Some(git_sha)) = git_reference(&source_dist.url) else {
+            CacheMetrics::record_miss();
             return None;
         };
 
@@ -94,36 +96,40 @@ impl BuiltWheelIndex {
     /// ```
     ///
     /// The `shard` should be `built-wheels-v0/pypi/django-allauth-0.51.0.tar.gz`.
+    ///
+    /// If the cache is layered over a fallback cache, wheels from both are considered, with ties
+    /// resolved in favor of the primary cache.
     fn find(shard: &CacheShard, tags: &Tags) -> Option<CachedWheel> {
         let mut candidate: Option<CachedWheel> = None;
 
-        // Unzipped wheels are stored as symlinks into the archive directory.
-        for subdir in symlinks(shard) {
-            match CachedWheel::from_path(&subdir) {
-                None => {}
-                Some(dist_info) => {
-                    // Pick the wheel with the highest priority
-                    let compatibility = dist_info.filename.compatibility(tags);
-
-                    // Only consider wheels that are compatible with our tags.
-                    if !compatibility.is_compatible() {
-                        continue;
-                    }
-
-                    if let Some(existing) = candidate.as_ref() {
-                        // Override if the wheel is newer, or "more" compatible.
-                        if dist_info.filename.version > existing.filename.version
-                            || compatibility > existing.filename.compatibility(tags)
-                        {
-                            candidate = Some(dist_info);
-                        }
-                    } else {
-                        candidate = Some(dist_info);
-                    }
+        for dist_info in shard.read_paths().flat_map(CachedWheel::read_dir) {
+            // Pick the wheel with the highest priority
+            let compatibility = dist_info.filename.compatibility(tags);
+
+            // Only consider wheels that are compatible with our tags.
+            if !compatibility.is_compatible() {
+                continue;
+            }
+
+            if let Some(existing) = candidate.as_ref() {
+                // Override if the wheel is newer, or "more" compatible.
+                if dist_info.filename.version > existing.filename.version
+                    || compatibility > existing.filename.compatibility(tags)
+                {
+                    candidate = Some(dist_info);
                 }
+            } else {
+                candidate = Some(dist_info);
             }
         }
 
+        // Record whether a compatible built wheel was found in the cache.
+        if candidate.is_some() {
+            CacheMetrics::record_hit(0);
+        } else {
+            CacheMetrics::record_miss();
+        }
+
         candidate
     }
 }
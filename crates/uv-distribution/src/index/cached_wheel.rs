@@ -1,5 +1,7 @@
 use std::path::Path;
 
+use tracing::warn;
+
 use distribution_filename::WheelFilename;
 use distribution_types::{CachedDirectUrlDist, CachedRegistryDist};
 use pep508_rs::VerbatimUrl;
@@ -23,11 +25,45 @@ impl CachedWheel {
         Some(Self { filename, entry })
     }
 
+    /// Iterate over the unzipped wheels in a cache shard.
+    ///
+    /// Wheels are typically stored as symlinks into the archive bucket, but are copied (or, if
+    /// compressed, hard-linked) into place where symlinks aren't available. Any metadata stored
+    /// alongside the wheels (e.g., `{stem}.http`) is skipped: as no wheel tag contains a `.`, it's
+    /// distinguished by its extension.
+    pub fn read_dir(path: &Path) -> impl Iterator<Item = Self> {
+        path.read_dir()
+            .ok()
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| match entry {
+                Ok(entry) => Some(entry),
+                Err(err) => {
+                    warn!("Failed to read entry: {}", err);
+                    None
+                }
+            })
+            .filter(|entry| {
+                entry.file_type().is_ok_and(|file_type| {
+                    file_type.is_symlink()
+                        || file_type.is_dir()
+                        || (file_type.is_file()
+                            && entry
+                                .file_name()
+                                .to_str()
+                                .and_then(|name| name.rsplit('-').next())
+                                .is_some_and(|tag| !tag.contains('.')))
+                })
+            })
+            .filter_map(|entry| Self::from_path(&entry.path()))
+    }
+
     /// Convert a [`CachedWheel`] into a [`CachedRegistryDist`].
     pub fn into_registry_dist(self) -> CachedRegistryDist {
         CachedRegistryDist {
             filename: self.filename,
             path: self.entry.into_path_buf(),
+            sha256: None,
         }
     }
 
@@ -38,6 +74,7 @@ impl CachedWheel {
             url,
             path: self.entry.into_path_buf(),
             editable: false,
+            sha256: None,
         }
     }
 }
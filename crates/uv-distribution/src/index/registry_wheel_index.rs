@@ -1,5 +1,5 @@
 use std::collections::hash_map::Entry;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::Path;
 
 use rustc_hash::FxHashMap;
@@ -8,8 +8,8 @@ use distribution_types::{CachedRegistryDist, FlatIndexLocation, IndexLocations,
 use pep440_rs::Version;
 use pep508_rs::VerbatimUrl;
 use platform_tags::Tags;
-use uv_cache::{Cache, CacheBucket, WheelCache};
-use uv_fs::{directories, symlinks};
+use uv_cache::{Cache, CacheBucket, CacheMetrics, WheelCache};
+use uv_fs::directories;
 use uv_normalize::PackageName;
 
 use crate::index::cached_wheel::CachedWheel;
@@ -100,7 +100,10 @@ impl<'a> RegistryWheelIndex<'a> {
                 WheelCache::Index(index_url).wheel_dir(package.to_string()),
             );
 
-            Self::add_directory(&wheel_dir, tags, &mut versions);
+            // If the cache is layered over a fallback cache, the primary cache takes precedence.
+            for wheel_dir in wheel_dir.read_paths() {
+                Self::add_directory(wheel_dir, tags, &mut versions);
+            }
 
             // Index all the built wheels, created by downloading and building source distributions
             // from the registry.
@@ -110,46 +113,98 @@ impl<'a> RegistryWheelIndex<'a> {
             );
 
             // For registry wheels, the cache structure is: `<index>/<package-name>/<version>/`.
-            for shard in directories(&cache_shard) {
+            let shards = cache_shard
+                .read_paths()
+                .flat_map(directories)
+                .filter_map(|shard| shard.file_name().map(ToOwned::to_owned))
+                .collect::<BTreeSet<_>>();
+            for shard in shards {
                 // Read the existing metadata from the cache, if it exists.
                 let cache_shard = cache_shard.shard(shard);
                 let revision_entry = cache_shard.entry(REVISION);
                 if let Ok(Some(revision)) = read_http_revision(&revision_entry) {
-                    Self::add_directory(cache_shard.join(revision.id()), tags, &mut versions);
+                    for revision_dir in cache_shard.shard(revision.id()).read_paths() {
+                        Self::add_directory(revision_dir, tags, &mut versions);
+                    }
                 };
             }
         }
 
+        // Record whether any wheels for the package were found in the cache.
+        if versions.is_empty() {
+            CacheMetrics::record_miss();
+        } else {
+            CacheMetrics::record_hit(0);
+        }
+
         versions
     }
 
     /// Add the wheels in a given directory to the index.
     ///
-    /// Each subdirectory in the given path is expected to be that of an unzipped wheel.
+    /// Each entry in the given path is expected to be that of an unzipped wheel.
     fn add_directory(
         path: impl AsRef<Path>,
         tags: &Tags,
         versions: &mut BTreeMap<Version, CachedRegistryDist>,
     ) {
-        // Unzipped wheels are stored as symlinks into the archive directory.
-        for wheel_dir in symlinks(path.as_ref()) {
-            match CachedWheel::from_path(&wheel_dir) {
-                None => {}
-                Some(dist_info) => {
-                    let dist_info = dist_info.into_registry_dist();
-
-                    // Pick the wheel with the highest priority
-                    let compatibility = dist_info.filename.compatibility(tags);
-                    if let Some(existing) = versions.get_mut(&dist_info.filename.version) {
-                        // Override if we have better compatibility
-                        if compatibility > existing.filename.compatibility(tags) {
-                            *existing = dist_info;
-                        }
-                    } else if compatibility.is_compatible() {
-                        versions.insert(dist_info.filename.version.clone(), dist_info);
-                    }
+        for dist_info in CachedWheel::read_dir(path.as_ref()) {
+            let dist_info = dist_info.into_registry_dist();
+
+            // Pick the wheel with the highest priority
+            let compatibility = dist_info.filename.compatibility(tags);
+            if let Some(existing) = versions.get_mut(&dist_info.filename.version) {
+                // Override if we have better compatibility
+                if compatibility > existing.filename.compatibility(tags) {
+                    *existing = dist_info;
                 }
+            } else if compatibility.is_compatible() {
+                versions.insert(dist_info.filename.version.clone(), dist_info);
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use distribution_types::IndexLocations;
+    use platform_tags::Tags;
+    use uv_cache::{Cache, CacheBucket, WheelCache};
+    use uv_normalize::PackageName;
+
+    use super::RegistryWheelIndex;
+
+    /// Wheels that were copied or hard-linked into place, rather than symlinked, are indexed.
+    #[test]
+    fn index_without_symlinks() -> std::io::Result<()> {
+        let cache = Cache::temp()?;
+        let tags = Tags::new(vec![(
+            "py3".to_string(),
+            "none".to_string(),
+            "any".to_string(),
+        )]);
+        let index_locations = IndexLocations::default();
+        let name = PackageName::from_str("iniconfig").unwrap();
+        let shard = cache.shard(
+            CacheBucket::Wheels,
+            WheelCache::Index(index_locations.indexes().next().unwrap()).wheel_dir(name.as_ref()),
+        );
+
+        // An uncompressed wheel, copied into place.
+        fs_err::create_dir_all(shard.join("iniconfig-1.1.1-py3-none-any"))?;
+        // A compressed wheel, hard-linked into place, alongside its metadata.
+        fs_err::write(shard.join("iniconfig-2.0.0-py3-none-any"), b"")?;
+        fs_err::write(shard.join("iniconfig-2.0.0-py3-none-any.http"), b"")?;
+
+        let mut index = RegistryWheelIndex::new(&cache, &tags, &index_locations);
+        let versions = index
+            .get(&name)
+            .map(|(version, _)| version.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(versions, ["2.0.0", "1.1.1"]);
+
+        Ok(())
+    }
+}
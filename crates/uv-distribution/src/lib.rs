@@ -6,11 +6,16 @@ pub use index::{BuiltWheelIndex, RegistryWheelIndex};
 pub use reporter::Reporter;
 pub use source::SourceDistributionBuilder;
 
+mod archive;
+mod chunked;
 mod distribution_database;
 mod download;
 mod error;
 mod git;
+mod hash;
 mod index;
 mod locks;
+mod partial;
 mod reporter;
+mod resumable;
 mod source;
@@ -0,0 +1,148 @@
+use std::io;
+use std::path::PathBuf;
+
+use tokio::io::AsyncSeekExt;
+use url::Url;
+
+use cache_key::CanonicalUrl;
+use uv_cache::{Cache, CacheBucket};
+use uv_fs::LockedFile;
+
+/// A wheel download in progress, persisted to the `.partial` area of the wheel cache such that it
+/// can be resumed (via a `Range` request) if it's interrupted.
+///
+/// Alongside the partial file, we store the `ETag` of the response from which it was downloaded,
+/// to ensure that resumed bytes are appended to the same file.
+pub(crate) struct PartialDownload {
+    /// The path to the partially downloaded file.
+    path: PathBuf,
+    /// The path to the `ETag` of the response from which the partial file was downloaded.
+    etag: PathBuf,
+    /// A lock to guard against concurrent downloads of the same URL by other uv processes.
+    _lock: LockedFile,
+}
+
+impl PartialDownload {
+    /// Acquire the partial download for the given URL.
+    pub(crate) fn acquire(cache: &Cache, url: &Url) -> Result<Self, io::Error> {
+        let root = cache.bucket(CacheBucket::Wheels).join(".partial");
+        fs_err::create_dir_all(&root)?;
+
+        let key = cache_key::digest(&CanonicalUrl::new(url));
+        let lock = LockedFile::acquire(root.join(format!("{key}.lock")), url)?;
+        Ok(Self {
+            path: root.join(format!("{key}.whl")),
+            etag: root.join(format!("{key}.etag")),
+            _lock: lock,
+        })
+    }
+
+    /// Return the number of bytes previously downloaded from a response with the given `ETag`.
+    pub(crate) async fn resumable_len(&self, etag: &str) -> u64 {
+        match tokio::fs::read_to_string(&self.etag).await {
+            Ok(stored) if stored == etag => tokio::fs::metadata(&self.path)
+                .await
+                .map(|metadata| metadata.len())
+                .unwrap_or(0),
+            _ => 0,
+        }
+    }
+
+    /// Open the partial file for writing at the given offset, discarding any bytes beyond it.
+    ///
+    /// When starting from scratch, the `ETag` of the response (if any) is recorded, such that a
+    /// later attempt can resume the download.
+    pub(crate) async fn open(
+        &self,
+        offset: u64,
+        etag: Option<&str>,
+    ) -> Result<tokio::fs::File, io::Error> {
+        if offset == 0 {
+            match etag {
+                Some(etag) => tokio::fs::write(&self.etag, etag).await?,
+                None => match tokio::fs::remove_file(&self.etag).await {
+                    Ok(()) => {}
+                    Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                    Err(err) => return Err(err),
+                },
+            }
+        }
+
+        let mut file = tokio::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&self.path)
+            .await?;
+        file.set_len(offset).await?;
+        file.seek(io::SeekFrom::Start(offset)).await?;
+        Ok(file)
+    }
+
+    /// Open an additional handle to the partial file for writing at the given offset, without
+    /// discarding any bytes (e.g., to write one chunk of a concurrent download).
+    pub(crate) async fn open_at(&self, offset: u64) -> Result<tokio::fs::File, io::Error> {
+        let mut file = tokio::fs::OpenOptions::new()
+            .write(true)
+            .open(&self.path)
+            .await?;
+        file.seek(io::SeekFrom::Start(offset)).await?;
+        Ok(file)
+    }
+
+    /// Remove the partial file, once the download has been consumed.
+    pub(crate) async fn remove(self) -> Result<(), io::Error> {
+        tokio::fs::remove_file(&self.path).await?;
+        match tokio::fs::remove_file(&self.etag).await {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use tokio::io::AsyncWriteExt;
+    use url::Url;
+
+    use uv_cache::Cache;
+
+    use crate::partial::PartialDownload;
+
+    #[tokio::test]
+    async fn resume() -> Result<()> {
+        let cache = Cache::temp()?;
+        let url =
+            Url::parse("https://files.pythonhosted.org/packages/torch-2.2.0-py3-none-any.whl")?;
+        let partial = PartialDownload::acquire(&cache, &url)?;
+
+        // Nothing has been downloaded yet.
+        assert_eq!(partial.resumable_len("\"abc\"").await, 0);
+
+        let mut file = partial.open(0, Some("\"abc\"")).await?;
+        file.write_all(b"hello world").await?;
+        file.flush().await?;
+        drop(file);
+
+        // The download can only be resumed from a response with the same `ETag`.
+        assert_eq!(partial.resumable_len("\"abc\"").await, 11);
+        assert_eq!(partial.resumable_len("\"def\"").await, 0);
+
+        // Resuming discards any bytes beyond the offset.
+        let mut file = partial.open(5, Some("\"abc\"")).await?;
+        file.write_all(b"!").await?;
+        file.flush().await?;
+        drop(file);
+        assert_eq!(partial.resumable_len("\"abc\"").await, 6);
+
+        // Restarting without an `ETag` prevents any later resumption.
+        drop(partial.open(0, None).await?);
+        assert_eq!(partial.resumable_len("\"abc\"").await, 0);
+
+        partial.remove().await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,346 @@
+use futures::StreamExt;
+use reqwest::header::{HeaderValue, CONTENT_RANGE, IF_RANGE, RANGE};
+use reqwest::StatusCode;
+use tokio::io::AsyncWriteExt;
+use tracing::{debug, warn};
+use url::Url;
+
+use uv_client::RegistryClient;
+
+use crate::distribution_database::{download_request, range_etag};
+use crate::hash::DownloadHasher;
+use crate::partial::PartialDownload;
+use crate::Error;
+
+/// The maximum number of times to resume an interrupted download.
+const MAX_DOWNLOAD_RESUMES: usize = 3;
+
+/// Write the body of a response to the given [`PartialDownload`].
+///
+/// If the server supports range requests and identifies the file with a strong `ETag`, an
+/// interrupted download is resumed from the last received byte (including one interrupted
+/// during a previous invocation), rather than restarted.
+///
+/// The bytes written are passed to the given [`DownloadHasher`].
+pub(crate) async fn download_resumable(
+    client: &RegistryClient,
+    url: &Url,
+    partial: &PartialDownload,
+    mut response: reqwest::Response,
+    hasher: &mut DownloadHasher,
+) -> Result<tokio::fs::File, Error> {
+    let etag = range_etag(&response);
+
+    let mut offset = 0;
+    if let Some(etag) = etag.as_deref() {
+        let len = partial.resumable_len(etag).await;
+        if len > 0 {
+            debug!("Resuming download of {url} from byte {len}");
+            (response, offset) = request_range(client, url, len, etag).await?;
+        }
+    }
+    hasher.seek(offset);
+
+    let mut resumes = 0;
+    loop {
+        let mut file = partial
+            .open(offset, etag.as_deref())
+            .await
+            .map_err(Error::CacheWrite)?;
+
+        let mut stream = client.uncached_client().bytes_stream(response);
+        let mut interrupted = None;
+        while let Some(chunk) = stream.next().await {
+            match chunk {
+                Ok(chunk) => {
+                    file.write_all(&chunk).await.map_err(Error::CacheWrite)?;
+                    hasher.update(&chunk);
+                }
+                Err(err) => {
+                    interrupted = Some(err);
+                    break;
+                }
+            }
+        }
+        file.flush().await.map_err(Error::CacheWrite)?;
+
+        let Some(err) = interrupted else {
+            return Ok(file);
+        };
+        let Some(etag) = etag.as_deref().filter(|_| resumes < MAX_DOWNLOAD_RESUMES) else {
+            return Err(Error::CacheWrite(err));
+        };
+
+        resumes += 1;
+        let len = file.metadata().await.map_err(Error::CacheWrite)?.len();
+        warn!("Download of {url} was interrupted after {len} bytes; resuming ({err})");
+        (response, offset) = request_range(client, url, len, etag).await?;
+        hasher.seek(offset);
+    }
+}
+
+/// Request the bytes of a file starting at the given offset, provided that the file still
+/// matches the given `ETag`.
+///
+/// Returns the response, along with the offset at which its body starts (i.e., zero, if the
+/// server returned the entire file, or if the range couldn't be satisfied and the download was
+/// restarted).
+async fn request_range(
+    client: &RegistryClient,
+    url: &Url,
+    offset: u64,
+    etag: &str,
+) -> Result<(reqwest::Response, u64), Error> {
+    let mut req = download_request(client, url.clone())?;
+    req.headers_mut().insert(
+        RANGE,
+        HeaderValue::from_str(&format!("bytes={offset}-")).expect("valid header value"),
+    );
+    if let Ok(etag) = HeaderValue::from_str(etag) {
+        req.headers_mut().insert(IF_RANGE, etag);
+    }
+    let response = client.uncached_client().execute(req).await?;
+
+    // If the range can't be satisfied (e.g., the partial file already contains the entire file,
+    // but was never consumed), start over.
+    if response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+        debug!("Range of {url} starting at byte {offset} is not satisfiable; restarting download");
+        return Ok((request_full(client, url).await?, 0));
+    }
+    let response = response.error_for_status()?;
+
+    let expected = format!("bytes {offset}-");
+    if response.status() == StatusCode::PARTIAL_CONTENT
+        && response
+            .headers()
+            .get(CONTENT_RANGE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.starts_with(&expected))
+    {
+        return Ok((response, offset));
+    }
+
+    // If the file changed, the server returns it in its entirety, and we start over.
+    if response.status() == StatusCode::PARTIAL_CONTENT {
+        debug!("Received an unexpected range for {url}; restarting download");
+        return Ok((request_full(client, url).await?, 0));
+    }
+    debug!("Server returned the entire file at {url} for a range request; restarting download");
+    Ok((response, 0))
+}
+
+/// Request the entire file at the given URL.
+async fn request_full(client: &RegistryClient, url: &Url) -> Result<reqwest::Response, Error> {
+    Ok(client
+        .uncached_client()
+        .execute(download_request(client, url.clone())?)
+        .await?
+        .error_for_status()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+
+    use anyhow::Result;
+    use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+    use url::Url;
+
+    use uv_cache::Cache;
+    use uv_client::{RegistryClient, RegistryClientBuilder};
+
+    use crate::distribution_database::download_request;
+    use crate::hash::DownloadHasher;
+    use crate::partial::PartialDownload;
+    use crate::resumable::download_resumable;
+
+    const BODY: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+    const ETAG: &str = "\"v1\"";
+
+    /// How the server responds to a request, given the start of the requested range (if any).
+    type Handler = dyn Fn(Option<usize>) -> Vec<u8> + Send + Sync;
+
+    /// The start of the requested range (if any) of each request received by the server.
+    type Requests = Arc<Mutex<Vec<Option<usize>>>>;
+
+    /// Start an HTTP server that responds to each request via the given handler, recording the
+    /// start of the requested range (if any) of each request.
+    fn server(handler: Box<Handler>) -> Result<(Url, Requests)> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let url = Url::parse(&format!(
+            "http://{}/foo-1.0.0-py3-none-any.whl",
+            listener.local_addr()?
+        ))?;
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        std::thread::spawn({
+            let requests = requests.clone();
+            move || {
+                for stream in listener.incoming() {
+                    let mut stream = stream.unwrap();
+                    let mut range = None;
+                    for line in BufReader::new(&stream).lines() {
+                        let line = line.unwrap();
+                        if line.is_empty() {
+                            break;
+                        }
+                        if let Some(value) = line.to_ascii_lowercase().strip_prefix("range: bytes=")
+                        {
+                            range = value.trim_end_matches('-').parse().ok();
+                        }
+                    }
+                    requests.lock().unwrap().push(range);
+                    stream.write_all(&handler(range)).unwrap();
+                }
+            }
+        });
+
+        Ok((url, requests))
+    }
+
+    /// Return a response with the given status line, containing the given slice of [`BODY`], and
+    /// declaring the given (possibly larger) content length.
+    fn response(status: &str, body: &[u8], len: usize, range: Option<usize>) -> Vec<u8> {
+        let content_range = range
+            .map(|start| {
+                format!(
+                    "Content-Range: bytes {start}-{}/{}\r\n",
+                    BODY.len() - 1,
+                    BODY.len()
+                )
+            })
+            .unwrap_or_default();
+        let mut response = format!(
+            "HTTP/1.1 {status}\r\nAccept-Ranges: bytes\r\nETag: {ETAG}\r\n\
+            Content-Length: {len}\r\n{content_range}Connection: close\r\n\r\n"
+        )
+        .into_bytes();
+        response.extend_from_slice(body);
+        response
+    }
+
+    async fn get(client: &RegistryClient, url: &Url) -> Result<reqwest::Response> {
+        Ok(client
+            .uncached_client()
+            .execute(download_request(client, url.clone())?)
+            .await?
+            .error_for_status()?)
+    }
+
+    async fn contents(mut file: tokio::fs::File) -> Result<Vec<u8>> {
+        let mut contents = Vec::new();
+        file.seek(std::io::SeekFrom::Start(0)).await?;
+        file.read_to_end(&mut contents).await?;
+        Ok(contents)
+    }
+
+    /// An interrupted download is resumed from the last received byte.
+    #[tokio::test]
+    async fn resume_interrupted() -> Result<()> {
+        let (url, requests) = server(Box::new(|range: Option<usize>| match range {
+            // Declare the full length, but close the connection after the first ten bytes.
+            None => response("200 OK", &BODY[..10], BODY.len(), None),
+            Some(start) => response(
+                "206 Partial Content",
+                &BODY[start..],
+                BODY.len() - start,
+                Some(start),
+            ),
+        }))?;
+        let cache = Cache::temp()?;
+        let client = RegistryClientBuilder::new(cache.clone()).retries(0).build();
+        let partial = PartialDownload::acquire(&cache, &url)?;
+
+        let response = get(&client, &url).await?;
+        let file = download_resumable(
+            &client,
+            &url,
+            &partial,
+            response,
+            &mut DownloadHasher::new(),
+        )
+        .await?;
+        assert_eq!(contents(file).await?, BODY);
+        assert_eq!(*requests.lock().unwrap(), vec![None, Some(10)]);
+
+        Ok(())
+    }
+
+    /// A partial file that already contains the entire file (e.g., as it failed to unzip) is
+    /// downloaded again from scratch when the server can't satisfy the range.
+    #[tokio::test]
+    async fn restart_unsatisfiable() -> Result<()> {
+        let (url, requests) = server(Box::new(|range: Option<usize>| match range {
+            Some(start) if start >= BODY.len() => {
+                response("416 Range Not Satisfiable", b"", 0, None)
+            }
+            Some(start) => response(
+                "206 Partial Content",
+                &BODY[start..],
+                BODY.len() - start,
+                Some(start),
+            ),
+            None => response("200 OK", BODY, BODY.len(), None),
+        }))?;
+        let cache = Cache::temp()?;
+        let client = RegistryClientBuilder::new(cache.clone()).retries(0).build();
+        let partial = PartialDownload::acquire(&cache, &url)?;
+
+        // Leave a complete download behind.
+        let mut file = partial.open(0, Some(ETAG)).await?;
+        file.write_all(BODY).await?;
+        file.flush().await?;
+        drop(file);
+
+        let response = get(&client, &url).await?;
+        let file = download_resumable(
+            &client,
+            &url,
+            &partial,
+            response,
+            &mut DownloadHasher::new(),
+        )
+        .await?;
+        assert_eq!(contents(file).await?, BODY);
+        assert_eq!(
+            *requests.lock().unwrap(),
+            vec![None, Some(BODY.len()), None]
+        );
+
+        Ok(())
+    }
+
+    /// If the server ignores the range request, the download restarts from the entire file it
+    /// returned.
+    #[tokio::test]
+    async fn restart_ignored_range() -> Result<()> {
+        let (url, requests) = server(Box::new(|_: Option<usize>| {
+            response("200 OK", BODY, BODY.len(), None)
+        }))?;
+        let cache = Cache::temp()?;
+        let client = RegistryClientBuilder::new(cache.clone()).retries(0).build();
+        let partial = PartialDownload::acquire(&cache, &url)?;
+
+        // Leave an incomplete download behind.
+        let mut file = partial.open(0, Some(ETAG)).await?;
+        file.write_all(&BODY[..10]).await?;
+        file.flush().await?;
+        drop(file);
+
+        let response = get(&client, &url).await?;
+        let file = download_resumable(
+            &client,
+            &url,
+            &partial,
+            response,
+            &mut DownloadHasher::new(),
+        )
+        .await?;
+        assert_eq!(contents(file).await?, BODY);
+        assert_eq!(*requests.lock().unwrap(), vec![None, Some(10)]);
+
+        Ok(())
+    }
+}
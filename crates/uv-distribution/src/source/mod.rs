@@ -17,7 +17,7 @@ use zip::ZipArchive;
 
 use distribution_filename::WheelFilename;
 use distribution_types::{
-    BuildableSource, DirectArchiveUrl, Dist, FileLocation, GitSourceUrl, LocalEditable,
+    BuildableSource, DirectArchiveUrl, Dist, FileLocation, GitSourceUrl, LocalEditable, Name,
     PathSourceDist, PathSourceUrl, RemoteSource, SourceDist, SourceUrl,
 };
 use install_wheel_rs::metadata::read_archive_metadata;
@@ -31,10 +31,11 @@ use uv_client::{
     CacheControl, CachedClientError, Connectivity, DataWithCachePolicy, RegistryClient,
 };
 use uv_fs::write_atomic;
-use uv_types::{BuildContext, BuildKind, NoBuild, SourceBuildTrait};
+use uv_types::{BuildContext, BuildKind, NoBuild, RequiredHashes, SourceBuildTrait};
 
 use crate::error::Error;
 use crate::git::{fetch_git_archive, resolve_precise};
+use crate::hash::{self, HashReader};
 use crate::source::built_wheel_metadata::BuiltWheelMetadata;
 use crate::source::revision::Revision;
 use crate::Reporter;
@@ -47,6 +48,7 @@ pub struct SourceDistributionBuilder<'a, T: BuildContext> {
     client: &'a RegistryClient,
     build_context: &'a T,
     reporter: Option<Arc<dyn Reporter>>,
+    hashes: RequiredHashes,
 }
 
 /// The name of the file that contains the revision ID, encoded via `MsgPack`.
@@ -64,9 +66,17 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
             client,
             build_context,
             reporter: None,
+            hashes: RequiredHashes::default(),
         }
     }
 
+    /// Set the hashes that downloaded source distributions are required to match (as in
+    /// `--require-hashes`).
+    #[must_use]
+    pub fn with_required_hashes(self, hashes: RequiredHashes) -> Self {
+        Self { hashes, ..self }
+    }
+
     /// Set the [`Reporter`] to use for this source distribution fetcher.
     #[must_use]
     pub fn with_reporter(self, reporter: Arc<dyn Reporter>) -> Self {
@@ -127,7 +137,9 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
             }
             BuildableSource::Dist(SourceDist::DirectUrl(dist)) => {
                 let filename = dist.filename().expect("Distribution must have a filename");
-                let DirectArchiveUrl { url, subdirectory } = DirectArchiveUrl::from(dist.url.raw());
+                let DirectArchiveUrl {
+                    url, subdirectory, ..
+                } = DirectArchiveUrl::from(dist.url.raw());
 
                 // For direct URLs, cache directly under the hash of the URL itself.
                 let cache_shard = self
@@ -164,7 +176,9 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
                     .url
                     .filename()
                     .expect("Distribution must have a filename");
-                let DirectArchiveUrl { url, subdirectory } = DirectArchiveUrl::from(resource.url);
+                let DirectArchiveUrl {
+                    url, subdirectory, ..
+                } = DirectArchiveUrl::from(resource.url);
 
                 // For direct URLs, cache directly under the hash of the URL itself.
                 let cache_shard = self
@@ -249,7 +263,9 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
             }
             BuildableSource::Dist(SourceDist::DirectUrl(dist)) => {
                 let filename = dist.filename().expect("Distribution must have a filename");
-                let DirectArchiveUrl { url, subdirectory } = DirectArchiveUrl::from(dist.url.raw());
+                let DirectArchiveUrl {
+                    url, subdirectory, ..
+                } = DirectArchiveUrl::from(dist.url.raw());
 
                 // For direct URLs, cache directly under the hash of the URL itself.
                 let cache_shard = self
@@ -285,7 +301,9 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
                     .url
                     .filename()
                     .expect("Distribution must have a filename");
-                let DirectArchiveUrl { url, subdirectory } = DirectArchiveUrl::from(resource.url);
+                let DirectArchiveUrl {
+                    url, subdirectory, ..
+                } = DirectArchiveUrl::from(resource.url);
 
                 // For direct URLs, cache directly under the hash of the URL itself.
                 let cache_shard = self
@@ -341,6 +359,10 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
             Connectivity::Offline => CacheControl::AllowStale,
         };
 
+        // Source distributions without a hash from the index must match one of the required
+        // hashes, if any, as they're downloaded.
+        let required = self.required_hashes(source);
+
         let download = |response| {
             async {
                 // At this point, we're seeing a new or updated source distribution. Initialize a
@@ -350,7 +372,7 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
                 // Download the source distribution.
                 debug!("Downloading source distribution: {source}");
                 let source_dist_entry = cache_shard.shard(revision.id()).entry(filename);
-                self.persist_url(response, source, filename, &source_dist_entry)
+                self.persist_url(response, source, filename, required, &source_dist_entry)
                     .await?;
 
                 Ok(revision)
@@ -359,15 +381,26 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
             .instrument(info_span!("download", source_dist = %source))
         };
         let req = self.request(url.clone())?;
-        let revision = self
-            .client
-            .cached_client()
-            .get_serde(req, &cache_entry, cache_control, download)
-            .await
-            .map_err(|err| match err {
-                CachedClientError::Callback(err) => err,
-                CachedClientError::Client(err) => Error::Client(err),
-            })?;
+        let revision = if required.is_empty() {
+            self.client
+                .cached_client()
+                .get_serde(req, &cache_entry, cache_control, download)
+                .await
+                .map_err(|err| match err {
+                    CachedClientError::Callback(err) => err,
+                    CachedClientError::Client(err) => Error::Client(err),
+                })?
+        } else {
+            // Cached revisions may not have been verified against the required hashes, so
+            // download into a new revision, bypassing the HTTP cache.
+            let response = self
+                .client
+                .uncached_client()
+                .execute(req)
+                .await?
+                .error_for_status()?;
+            download(response).await?
+        };
 
         // From here on, scope all operations to the current build. Within the revision shard,
         // there's no need to check for freshness, since entries have to be fresher than the
@@ -443,7 +476,7 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
                 // Download the source distribution.
                 debug!("Downloading source distribution: {source}");
                 let source_dist_entry = cache_shard.shard(revision.id()).entry(filename);
-                self.persist_url(response, source, filename, &source_dist_entry)
+                self.persist_url(response, source, filename, &[], &source_dist_entry)
                     .await?;
 
                 Ok(revision)
@@ -635,7 +668,7 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
             .build_context
             .cache()
             .freshness(&metadata_entry, source.name())
-            .is_ok_and(Freshness::is_fresh)
+            .is_ok_and(Freshness::is_fresh_or_revalidate)
         {
             if let Some(metadata) = read_cached_metadata(&metadata_entry).await? {
                 debug!("Using cached metadata for: {source}");
@@ -787,7 +820,7 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
             .build_context
             .cache()
             .freshness(&metadata_entry, source.name())
-            .is_ok_and(Freshness::is_fresh)
+            .is_ok_and(Freshness::is_fresh_or_revalidate)
         {
             if let Some(metadata) = read_cached_metadata(&metadata_entry).await? {
                 debug!("Using cached metadata for: {source}");
@@ -839,11 +872,16 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
     }
 
     /// Download and unzip a source distribution into the cache from an HTTP response.
+    ///
+    /// Source distributions from a registry are verified against the hashes provided by the index
+    /// as they're downloaded, and are only persisted to the cache if they match. If any `required`
+    /// hashes are provided, the source distribution must match one of them.
     async fn persist_url<'data>(
         &self,
         response: Response,
         source: &BuildableSource<'_>,
         filename: &str,
+        required: &[String],
         cache_entry: &'data CacheEntry,
     ) -> Result<&'data Path, Error> {
         let cache_path = cache_entry.path();
@@ -857,11 +895,26 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
         let temp_dir =
             tempfile::tempdir_in(self.build_context.cache().bucket(CacheBucket::BuiltWheels))
                 .map_err(Error::CacheWrite)?;
-        let reader = response
-            .bytes_stream()
-            .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))
+        let url = response.url().clone();
+        let reader = self
+            .client
+            .uncached_client()
+            .bytes_stream(response)
             .into_async_read();
-        uv_extract::stream::archive(reader.compat(), filename, temp_dir.path()).await?;
+        let mut reader = HashReader::new(reader);
+        uv_extract::stream::archive((&mut reader).compat(), filename, temp_dir.path()).await?;
+
+        // Verify the source distribution before persisting it to the cache.
+        if let BuildableSource::Dist(SourceDist::Registry(dist)) = source {
+            let digest = reader.finish().await.map_err(Error::CacheWrite)?;
+            hash::verify(&url, &dist.file.hashes, &digest)?;
+            if !required.is_empty() {
+                hash::verify_required(&url, required, &digest)?;
+            }
+        } else if !required.is_empty() {
+            let digest = reader.finish().await.map_err(Error::CacheWrite)?;
+            hash::verify_required(&url, required, &digest)?;
+        }
         drop(span);
 
         // Extract the top-level directory.
@@ -882,6 +935,25 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
         Ok(cache_path)
     }
 
+    /// Return the hashes that a downloaded source distribution must match, if any.
+    ///
+    /// Registry source distributions with a SHA-256 hash are verified against the index, and
+    /// against the required hashes prior to download.
+    fn required_hashes(&self, source: &BuildableSource<'_>) -> &[String] {
+        if self.hashes.is_empty() {
+            return &[];
+        }
+        match source {
+            BuildableSource::Dist(SourceDist::Registry(dist))
+                if dist.file.hashes.sha256.is_some() =>
+            {
+                &[]
+            }
+            BuildableSource::Dist(dist) => self.hashes.get(dist.name()).unwrap_or_default(),
+            BuildableSource::Url(_) => &[],
+        }
+    }
+
     /// Build a source distribution, storing the built wheel in the cache.
     ///
     /// Returns the un-normalized disk filename, the parsed, normalized filename and the metadata
@@ -1042,20 +1114,66 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
         Ok(Some(metadata))
     }
 
-    /// Build a single directory into an editable wheel
+    /// Build a single directory into an editable wheel, or fetch it from the cache if the
+    /// directory is unchanged since the last build.
+    ///
+    /// Like local source trees, editable builds are cached per-revision, keyed by the
+    /// [`ArchiveTimestamp`] of every file in the project.
     pub async fn build_editable(
         &self,
         editable: &LocalEditable,
-        editable_wheel_dir: &Path,
-    ) -> Result<(Dist, String, WheelFilename, Metadata23), Error> {
-        debug!("Building (editable) {editable}");
-
+        tags: &Tags,
+    ) -> Result<(Dist, BuiltWheelMetadata, Metadata23), Error> {
         // Verify that the editable exists.
         if !editable.path.exists() {
             return Err(Error::NotFound(editable.path.clone()));
         }
 
+        let cache_shard = self.build_context.cache().shard(
+            CacheBucket::BuiltWheels,
+            WheelCache::Editable(editable.url()).root(),
+        );
+
+        // Determine the last-modified time of the project. Any file in the project may be an
+        // input to the (dynamic) metadata, so consider the entire tree.
+        let Some(modified) =
+            ArchiveTimestamp::from_source_tree(&editable.path).map_err(Error::CacheRead)?
+        else {
+            return Err(Error::DirWithoutEntrypoint);
+        };
+
+        // Read the existing revision from the cache, creating a new revision if the project has
+        // changed.
+        let revision_entry = cache_shard.entry(REVISION);
+        let revision_freshness = self
+            .build_context
+            .cache()
+            .freshness(&revision_entry, None)
+            .map_err(Error::CacheRead)?;
+        let revision =
+            refresh_timestamped_revision(&revision_entry, revision_freshness, modified).await?;
+        let cache_shard = cache_shard.shard(revision.id());
+
+        // If the cache contains a compatible wheel and its metadata, return it.
+        if let Some(built_wheel) = BuiltWheelMetadata::find_in_cache(tags, &cache_shard) {
+            if let Some(metadata) = read_cached_metadata(&cache_shard.entry(METADATA)).await? {
+                debug!("Using cached editable: {editable}");
+                let dist = Dist::Source(SourceDist::Path(PathSourceDist {
+                    name: built_wheel.filename.name.clone(),
+                    url: editable.url().clone(),
+                    path: editable.path.clone(),
+                    editable: true,
+                }));
+                return Ok((dist, built_wheel, metadata));
+            }
+        }
+
+        debug!("Building (editable) {editable}");
+
         // Build the wheel.
+        fs::create_dir_all(&cache_shard)
+            .await
+            .map_err(Error::CacheWrite)?;
         let disk_filename = self
             .build_context
             .setup_build(
@@ -1067,7 +1185,7 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
             )
             .await
             .map_err(|err| Error::BuildEditable(editable.to_string(), err))?
-            .wheel(editable_wheel_dir)
+            .wheel(&cache_shard)
             .await
             .map_err(|err| Error::BuildEditable(editable.to_string(), err))?;
         let filename = WheelFilename::from_str(&disk_filename)?;
@@ -1078,10 +1196,26 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
             path: editable.path.clone(),
             editable: true,
         }));
-        let metadata = read_wheel_metadata(&filename, editable_wheel_dir.join(&disk_filename))?;
+        let metadata = read_wheel_metadata(&filename, cache_shard.join(&disk_filename))?;
+
+        // Store the metadata.
+        write_atomic(
+            cache_shard.entry(METADATA).path(),
+            rmp_serde::to_vec(&metadata)?,
+        )
+        .await
+        .map_err(Error::CacheWrite)?;
 
         debug!("Finished building (editable): {dist}");
-        Ok((dist, disk_filename, filename, metadata))
+        Ok((
+            dist,
+            BuiltWheelMetadata {
+                path: cache_shard.join(&disk_filename),
+                target: cache_shard.join(filename.stem()),
+                filename,
+            },
+            metadata,
+        ))
     }
 
     /// Returns a GET [`reqwest::Request`] for the given URL.
@@ -1102,7 +1236,7 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
 
 /// Read an existing HTTP-cached [`Revision`], if it exists.
 pub(crate) fn read_http_revision(cache_entry: &CacheEntry) -> Result<Option<Revision>, Error> {
-    match fs_err::File::open(cache_entry.path()) {
+    match fs_err::File::open(cache_entry.read_path()) {
         Ok(file) => {
             let data = DataWithCachePolicy::from_reader(file)?.data;
             Ok(Some(rmp_serde::from_slice::<Revision>(&data)?))
@@ -1120,7 +1254,7 @@ pub(crate) fn read_timestamped_revision(
     modified: ArchiveTimestamp,
 ) -> Result<Option<Revision>, Error> {
     // If the cache entry is up-to-date, return it.
-    match fs_err::read(cache_entry.path()) {
+    match fs_err::read(cache_entry.read_path()) {
         Ok(cached) => {
             let cached = rmp_serde::from_slice::<CachedByTimestamp<Revision>>(&cached)?;
             if cached.timestamp == modified.timestamp() {
@@ -1217,7 +1351,7 @@ async fn refresh_timestamped_revision(
     modified: ArchiveTimestamp,
 ) -> Result<Revision, Error> {
     // If we know the exact modification time, we don't need to force a revalidate.
-    if matches!(modified, ArchiveTimestamp::Exact(_)) || freshness.is_fresh() {
+    if matches!(modified, ArchiveTimestamp::Exact(_)) || freshness.is_fresh_or_revalidate() {
         if let Some(revision) = read_timestamped_revision(cache_entry, modified)? {
             return Ok(revision);
         }
@@ -1242,7 +1376,7 @@ async fn refresh_timestamped_revision(
 
 /// Read an existing cached [`Metadata23`], if it exists.
 async fn read_cached_metadata(cache_entry: &CacheEntry) -> Result<Option<Metadata23>, Error> {
-    match fs::read(&cache_entry.path()).await {
+    match fs::read(cache_entry.read_path()).await {
         Ok(cached) => Ok(Some(rmp_serde::from_slice::<Metadata23>(&cached)?)),
         Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
         Err(err) => Err(Error::CacheRead(err)),
@@ -2,6 +2,7 @@ use std::io;
 use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
+use std::str::FromStr;
 use std::time::Duration;
 
 use async_channel::{Receiver, SendError};
@@ -14,6 +15,7 @@ use tracing::{debug, instrument};
 use walkdir::WalkDir;
 
 use uv_fs::Simplified;
+use uv_normalize::PackageName;
 use uv_warnings::warn_user;
 
 const COMPILEALL_SCRIPT: &str = include_str!("pip_compileall.py");
@@ -48,6 +50,8 @@ pub enum CompileError {
     },
     #[error("Bytecode timed out ({}s)", _0.as_secs_f32())]
     Timeout(Duration),
+    #[error("Failed to record compiled bytecode in: {}", _0.user_display())]
+    Record(PathBuf, #[source] install_wheel_rs::Error),
 }
 
 /// Bytecode compile all file in `dir` using a pool of work-stealing Python interpreters running a
@@ -56,15 +60,17 @@ pub enum CompileError {
 /// All compilation errors are muted (like pip). There is a 60s timeout for each file to handle
 /// a broken `python`.
 ///
-/// We only compile all files, but we don't update the RECORD, relying on PEP 491:
-/// > Uninstallers should be smart enough to remove .pyc even if it is not mentioned in RECORD.
-///
-/// We've confirmed that both `uv` and `pip` (as of 24.0.0) remove the `__pycache__` directory.
-#[instrument(skip(python_executable))]
+/// Once compiled, the `.pyc` files are added to the RECORD of each of the `installed`
+/// distributions in `dir` (like pip), such that they're tracked alongside the other installed
+/// files. The RECORDs of any other distributions are left untouched, as they weren't installed
+/// by this run. (Regardless, both `uv` and `pip` (as of 24.0.0) remove the `__pycache__`
+/// directory on uninstall, per PEP 491.)
+#[instrument(skip(python_executable, installed))]
 pub async fn compile_tree(
     dir: &Path,
     python_executable: &Path,
     cache: &Path,
+    installed: &[PackageName],
 ) -> Result<usize, CompileError> {
     debug_assert!(
         dir.is_absolute(),
@@ -139,9 +145,51 @@ pub async fn compile_tree(
         return Err(CompileError::WorkerDisappeared(send_error));
     }
 
+    // Add the compiled files to the RECORD of each installed distribution.
+    if !installed.is_empty() {
+        let site_packages = dir.to_path_buf();
+        let installed = installed.to_vec();
+        tokio::task::spawn_blocking(move || record_bytecode(&site_packages, &installed)).await??;
+    }
+
     Ok(source_files)
 }
 
+/// Add the compiled bytecode to the RECORD of each of the `installed` distributions in
+/// `site_packages`.
+fn record_bytecode(site_packages: &Path, installed: &[PackageName]) -> Result<(), CompileError> {
+    let read_error = |err: io::Error| {
+        CompileError::Record(
+            site_packages.to_path_buf(),
+            install_wheel_rs::Error::Io(err),
+        )
+    };
+    for entry in fs_err::read_dir(site_packages).map_err(read_error)? {
+        let path = entry.map_err(read_error)?.path();
+        if !path.extension().is_some_and(|ext| ext == "dist-info") {
+            continue;
+        }
+        // Ex) `django-5.0a1.dist-info`
+        let name = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.split_once('-'))
+            .and_then(|(name, _)| PackageName::from_str(name).ok());
+        if !name.is_some_and(|name| installed.contains(&name)) {
+            continue;
+        }
+        if path.join("RECORD").is_file() {
+            let count = install_wheel_rs::record_bytecode(site_packages, &path)
+                .map_err(|err| CompileError::Record(path.clone(), err))?;
+            debug!(
+                "Recorded {count} bytecode files in: {}",
+                path.user_display()
+            );
+        }
+    }
+    Ok(())
+}
+
 async fn worker(
     dir: PathBuf,
     interpreter: PathBuf,
@@ -1,5 +1,4 @@
 use std::cmp::Reverse;
-use std::path::Path;
 use std::sync::Arc;
 
 use futures::{FutureExt, Stream, StreamExt, TryFutureExt, TryStreamExt};
@@ -14,7 +13,7 @@ use platform_tags::Tags;
 use uv_cache::Cache;
 use uv_client::RegistryClient;
 use uv_distribution::DistributionDatabase;
-use uv_types::{BuildContext, InFlight};
+use uv_types::{BuildContext, Concurrency, InFlight, RequiredHashes};
 
 use crate::editable::BuiltEditable;
 
@@ -41,6 +40,7 @@ pub struct Downloader<'a, Context: BuildContext + Send + Sync> {
     cache: &'a Cache,
     database: DistributionDatabase<'a, Context>,
     reporter: Option<Arc<dyn Reporter>>,
+    concurrency: usize,
 }
 
 impl<'a, Context: BuildContext + Send + Sync> Downloader<'a, Context> {
@@ -55,6 +55,7 @@ impl<'a, Context: BuildContext + Send + Sync> Downloader<'a, Context> {
             cache,
             database: DistributionDatabase::new(client, build_context),
             reporter: None,
+            concurrency: Concurrency::DEFAULT_DOWNLOADS,
         }
     }
 
@@ -67,6 +68,25 @@ impl<'a, Context: BuildContext + Send + Sync> Downloader<'a, Context> {
             cache: self.cache,
             database: self.database.with_reporter(Facade::from(reporter.clone())),
             reporter: Some(reporter.clone()),
+            concurrency: self.concurrency,
+        }
+    }
+
+    /// Set the maximum number of distributions to fetch (and build) at once.
+    #[must_use]
+    pub fn with_concurrency(self, concurrency: usize) -> Self {
+        Self {
+            concurrency: concurrency.max(1),
+            ..self
+        }
+    }
+
+    /// Set the hashes that every downloaded distribution is required to match.
+    #[must_use]
+    pub fn with_required_hashes(self, hashes: RequiredHashes) -> Self {
+        Self {
+            database: self.database.with_required_hashes(hashes),
+            ..self
         }
     }
 
@@ -84,9 +104,9 @@ impl<'a, Context: BuildContext + Send + Sync> Downloader<'a, Context> {
                 }
                 Ok::<CachedDist, Error>(wheel)
             })
-            // TODO(charlie): The number of concurrent fetches, such that we limit the number of
-            // concurrent builds to the number of cores, while allowing more concurrent downloads.
-            .buffer_unordered(50)
+            // Unzipping happens on the shared `rayon` pool, so is bounded separately from the
+            // number of concurrent fetches.
+            .buffer_unordered(self.concurrency)
     }
 
     /// Download, build, and unzip a set of downloaded wheels.
@@ -117,7 +137,6 @@ impl<'a, Context: BuildContext + Send + Sync> Downloader<'a, Context> {
     pub async fn build_editables(
         &self,
         editables: LocalEditables,
-        editable_wheel_dir: &Path,
     ) -> Result<Vec<BuiltEditable>, Error> {
         // Build editables in parallel
         let mut results = Vec::with_capacity(editables.len());
@@ -129,7 +148,7 @@ impl<'a, Context: BuildContext + Send + Sync> Downloader<'a, Context> {
                     .map(|reporter| reporter.on_editable_build_start(&editable));
                 let (local_wheel, metadata) = self
                     .database
-                    .build_wheel_editable(&editable, editable_wheel_dir)
+                    .build_wheel_editable(&editable, self.tags)
                     .await
                     .map_err(Error::Editable)?;
                 let cached_dist = CachedDist::from(local_wheel);
@@ -140,7 +159,7 @@ impl<'a, Context: BuildContext + Send + Sync> Downloader<'a, Context> {
                 }
                 Ok::<_, Error>((editable, cached_dist, metadata))
             })
-            .buffer_unordered(50);
+            .buffer_unordered(self.concurrency);
 
         while let Some((editable, wheel, metadata)) = fetches.next().await.transpose()? {
             if let Some(reporter) = self.reporter.as_ref() {
@@ -0,0 +1,94 @@
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use thiserror::Error;
+use tracing::debug;
+
+#[derive(Debug, Error)]
+pub enum HookError {
+    #[error("Failed to run post-install hook: `{0}`")]
+    Spawn(String, #[source] io::Error),
+    #[error("Failed to pass the report to the post-install hook: `{0}`")]
+    Stdin(String, #[source] io::Error),
+    #[error("Post-install hook failed with {status}: `{command}`")]
+    Status {
+        command: String,
+        status: std::process::ExitStatus,
+    },
+}
+
+/// A hook that runs after packages are successfully installed into, or uninstalled from, an
+/// environment (e.g., to record a software bill of materials, or to notify another system).
+pub trait PostInstallHook {
+    /// Run the hook, given the JSON report of the changes made to the environment at `root` (as
+    /// written by `--report`).
+    fn run(&self, root: &Path, report: &str) -> Result<(), HookError>;
+}
+
+/// A [`PostInstallHook`] that runs a shell command, passing the JSON report on `stdin`.
+///
+/// The command is run via `sh -c` on Unix and `cmd /C` on Windows, with the `UV_HOOK_ENVIRONMENT`
+/// environment variable set to the root of the modified environment.
+#[derive(Debug, Clone)]
+pub struct CommandHook {
+    command: String,
+}
+
+impl CommandHook {
+    /// Create a hook that runs the given shell command.
+    pub fn new(command: impl Into<String>) -> Self {
+        Self {
+            command: command.into(),
+        }
+    }
+
+    /// Build the [`Command`] used to run the hook.
+    fn shell(&self) -> Command {
+        let mut command = if cfg!(windows) {
+            let mut command = Command::new("cmd");
+            command.arg("/C");
+            command
+        } else {
+            let mut command = Command::new("sh");
+            command.arg("-c");
+            command
+        };
+        command.arg(&self.command);
+        command
+    }
+}
+
+impl PostInstallHook for CommandHook {
+    fn run(&self, root: &Path, report: &str) -> Result<(), HookError> {
+        debug!("Running post-install hook: `{}`", self.command);
+        let mut child = self
+            .shell()
+            .env("UV_HOOK_ENVIRONMENT", root)
+            .stdin(Stdio::piped())
+            .spawn()
+            .map_err(|err| HookError::Spawn(self.command.clone(), err))?;
+
+        // Write the report, then close `stdin` such that the hook sees EOF. A hook that exits
+        // without reading its input is not an error.
+        if let Some(mut stdin) = child.stdin.take() {
+            match stdin.write_all(report.as_bytes()) {
+                Ok(()) => {}
+                Err(err) if err.kind() == io::ErrorKind::BrokenPipe => {}
+                Err(err) => return Err(HookError::Stdin(self.command.clone(), err)),
+            }
+        }
+
+        let status = child
+            .wait()
+            .map_err(|err| HookError::Spawn(self.command.clone(), err))?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(HookError::Status {
+                command: self.command.clone(),
+                status,
+            })
+        }
+    }
+}
@@ -3,11 +3,13 @@ use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use tracing::instrument;
 
 use distribution_types::CachedDist;
+use uv_cache::ArchiveFormat;
 use uv_interpreter::PythonEnvironment;
 
 pub struct Installer<'a> {
     venv: &'a PythonEnvironment,
     link_mode: install_wheel_rs::linker::LinkMode,
+    shebang: install_wheel_rs::ShebangMode,
     reporter: Option<Box<dyn Reporter>>,
     installer_name: Option<String>,
 }
@@ -18,6 +20,7 @@ impl<'a> Installer<'a> {
         Self {
             venv,
             link_mode: install_wheel_rs::linker::LinkMode::default(),
+            shebang: install_wheel_rs::ShebangMode::default(),
             reporter: None,
             installer_name: Some("uv".to_string()),
         }
@@ -29,6 +32,12 @@ impl<'a> Installer<'a> {
         Self { link_mode, ..self }
     }
 
+    /// Set the [`ShebangMode`][`install_wheel_rs::ShebangMode`] to use for installed scripts.
+    #[must_use]
+    pub fn with_shebang(self, shebang: install_wheel_rs::ShebangMode) -> Self {
+        Self { shebang, ..self }
+    }
+
     /// Set the [`Reporter`] to use for this installer.
     #[must_use]
     pub fn with_reporter(self, reporter: impl Reporter + 'static) -> Self {
@@ -53,9 +62,23 @@ impl<'a> Installer<'a> {
         let layout = self.venv.interpreter().layout();
         tokio::task::block_in_place(|| {
             wheels.par_iter().try_for_each(|wheel| {
+                // If the wheel is stored compressed in the cache, decompress it alongside the
+                // environment, such that it can still be linked (rather than copied) into place.
+                let unpacked = if ArchiveFormat::from_path(wheel.path()).is_compressed() {
+                    let temp_dir = tempfile::tempdir_in(self.venv.root())?;
+                    uv_cache::decompress(wheel.path(), temp_dir.path()).with_context(|| {
+                        format!("Failed to decompress cached wheel: {}", wheel.filename())
+                    })?;
+                    Some(temp_dir)
+                } else {
+                    None
+                };
+
                 install_wheel_rs::linker::install_wheel(
                     &layout,
-                    wheel.path(),
+                    unpacked
+                        .as_ref()
+                        .map_or_else(|| wheel.path(), tempfile::TempDir::path),
                     wheel.filename(),
                     wheel
                         .direct_url()?
@@ -65,6 +88,7 @@ impl<'a> Installer<'a> {
                         .as_ref(),
                     self.installer_name.as_deref(),
                     self.link_mode,
+                    self.shebang,
                 )
                 .with_context(|| format!("Failed to install: {} ({wheel})", wheel.filename()))?;
 
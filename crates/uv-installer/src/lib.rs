@@ -1,6 +1,7 @@
 pub use compile::{compile_tree, CompileError};
 pub use downloader::{Downloader, Reporter as DownloadReporter};
 pub use editable::{is_dynamic, BuiltEditable, ResolvedEditable};
+pub use hook::{CommandHook, HookError, PostInstallHook};
 pub use installer::{Installer, Reporter as InstallReporter};
 pub use plan::{Plan, Planner};
 pub use site_packages::{Diagnostic, SitePackages};
@@ -9,6 +10,7 @@ pub use uninstall::{uninstall, UninstallError};
 mod compile;
 mod downloader;
 mod editable;
+mod hook;
 mod installer;
 mod plan;
 mod site_packages;
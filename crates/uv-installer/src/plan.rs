@@ -12,10 +12,11 @@ use distribution_types::{
 };
 use pep508_rs::{Requirement, VersionOrUrl};
 use platform_tags::Tags;
-use uv_cache::{ArchiveTarget, ArchiveTimestamp, Cache, CacheBucket, WheelCache};
+use uv_cache::{ArchiveTarget, ArchiveTimestamp, Cache, CacheBucket, CacheMetrics, WheelCache};
 use uv_distribution::{BuiltWheelIndex, RegistryWheelIndex};
 use uv_fs::Simplified;
 use uv_interpreter::PythonEnvironment;
+use uv_normalize::PackageName;
 use uv_types::{NoBinary, Reinstall};
 
 use crate::{ResolvedEditable, SitePackages};
@@ -25,6 +26,8 @@ use crate::{ResolvedEditable, SitePackages};
 pub struct Planner<'a> {
     requirements: &'a [Requirement],
     editable_requirements: &'a [ResolvedEditable],
+    hash_checking: bool,
+    excluded: &'a [PackageName],
 }
 
 impl<'a> Planner<'a> {
@@ -34,6 +37,8 @@ impl<'a> Planner<'a> {
         Self {
             requirements,
             editable_requirements: &[],
+            hash_checking: false,
+            excluded: &[],
         }
     }
 
@@ -46,6 +51,23 @@ impl<'a> Planner<'a> {
         }
     }
 
+    /// Enable hash-checking mode (as in `--require-hashes`), in which distributions are never
+    /// linked from the cache, such that they're verified as they're fetched.
+    #[must_use]
+    pub fn with_hash_checking(self, hash_checking: bool) -> Self {
+        Self {
+            hash_checking,
+            ..self
+        }
+    }
+
+    /// Exclude the given packages from the [`Plan`], such that they're neither installed nor
+    /// removed.
+    #[must_use]
+    pub fn with_excluded(self, excluded: &'a [PackageName]) -> Self {
+        Self { excluded, ..self }
+    }
+
     /// Partition a set of requirements into those that should be linked from the cache, those that
     /// need to be downloaded, and those that should be removed.
     ///
@@ -133,6 +155,12 @@ impl<'a> Planner<'a> {
                 continue;
             }
 
+            // Filter out excluded requirements.
+            if self.excluded.contains(&requirement.name) {
+                debug!("Skipping excluded requirement: {requirement}");
+                continue;
+            }
+
             // If we see the same requirement twice, then we have a conflict.
             let specifier = Specifier::NonEditable(requirement.version_or_url.as_ref());
             match seen.entry(requirement.name.clone()) {
@@ -171,7 +199,23 @@ impl<'a> Planner<'a> {
             } else {
                 let installed_dists = site_packages.remove_packages(&requirement.name);
                 match installed_dists.as_slice() {
-                    [] => {}
+                    [] => {
+                        // If the environment has access to the system site packages, a matching
+                        // distribution there satisfies the requirement. Otherwise, install into
+                        // the environment, shadowing (but never modifying) the system package.
+                        if let Some(distribution) = site_packages
+                            .get_system_packages(&requirement.name)
+                            .into_iter()
+                            .find(|distribution| {
+                                installed_satisfies_requirement(distribution, requirement)
+                                    .unwrap_or(false)
+                            })
+                        {
+                            debug!("Requirement already installed in system site packages: {distribution}");
+                            installed.push(distribution.clone());
+                            continue;
+                        }
+                    }
                     [distribution] => {
                         if installed_satisfies_requirement(distribution, requirement)? {
                             debug!("Requirement already installed: {distribution}");
@@ -193,6 +237,12 @@ impl<'a> Planner<'a> {
                 continue;
             }
 
+            if self.hash_checking {
+                debug!("Must verify hashes for requirement: {requirement}");
+                remote.push(requirement.clone());
+                continue;
+            }
+
             // Identify any cached distributions that satisfy the requirement.
             match requirement.version_or_url.as_ref() {
                 None => {
@@ -222,6 +272,12 @@ impl<'a> Planner<'a> {
                     }
                 }
                 Some(VersionOrUrl::Url(url)) => {
+                    if cache.must_revalidate_url(url.raw()) {
+                        debug!("Must revalidate requirement: {requirement}");
+                        remote.push(requirement.clone());
+                        continue;
+                    }
+
                     match Dist::from_url(requirement.name.clone(), url.clone())? {
                         Dist::Built(BuiltDist::Registry(_)) => {
                             // Nothing to do.
@@ -262,11 +318,13 @@ impl<'a> Planner<'a> {
                                     );
 
                                     debug!("URL wheel requirement already cached: {cached_dist}");
+                                    CacheMetrics::record_hit(0);
                                     cached.push(CachedDist::Url(cached_dist));
                                     continue;
                                 }
                                 Err(err) if err.kind() == io::ErrorKind::NotFound => {
                                     // The cache entry doesn't exist, so it's not fresh.
+                                    CacheMetrics::record_miss();
                                 }
                                 Err(err) => return Err(err.into()),
                             }
@@ -310,12 +368,15 @@ impl<'a> Planner<'a> {
                                         debug!(
                                             "URL wheel requirement already cached: {cached_dist}"
                                         );
+                                        CacheMetrics::record_hit(0);
                                         cached.push(CachedDist::Url(cached_dist));
                                         continue;
                                     }
+                                    CacheMetrics::record_miss();
                                 }
                                 Err(err) if err.kind() == io::ErrorKind::NotFound => {
                                     // The cache entry doesn't exist, so it's not fresh.
+                                    CacheMetrics::record_miss();
                                 }
                                 Err(err) => return Err(err.into()),
                             }
@@ -363,6 +424,10 @@ impl<'a> Planner<'a> {
             // If uv created the virtual environment, then remove all packages, regardless of
             // whether they're considered "seed" packages.
             let seed_packages = !venv.cfg().is_ok_and(|cfg| cfg.is_uv());
+
+            // In a Conda environment, leave any packages managed by Conda itself in place, since
+            // Conda tracks them separately (in `conda-meta`).
+            let conda_env = venv.interpreter().is_conda_env();
             for dist_info in site_packages {
                 if seed_packages
                     && matches!(
@@ -374,6 +439,22 @@ impl<'a> Planner<'a> {
                     continue;
                 }
 
+                if self.excluded.contains(dist_info.name()) {
+                    debug!("Preserving excluded package: {dist_info}");
+                    continue;
+                }
+
+                if conda_env
+                    && dist_info
+                        .installer()
+                        .ok()
+                        .flatten()
+                        .is_some_and(|installer| installer.trim() == "conda")
+                {
+                    debug!("Preserving Conda-managed package: {dist_info}");
+                    continue;
+                }
+
                 debug!("Unnecessary package: {dist_info}");
                 extraneous.push(dist_info);
             }
@@ -1,10 +1,11 @@
 use std::iter::Flatten;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::{collections::BTreeSet, hash::BuildHasherDefault};
 
 use anyhow::{Context, Result};
 use fs_err as fs;
 use rustc_hash::{FxHashMap, FxHashSet};
+use tracing::warn;
 use url::Url;
 
 use distribution_types::{InstalledDist, InstalledMetadata, InstalledVersion, Name};
@@ -34,6 +35,10 @@ pub struct SitePackages<'a> {
     by_name: FxHashMap<PackageName, Vec<usize>>,
     /// The installed editable distributions, keyed by URL.
     by_url: FxHashMap<Url, Vec<usize>>,
+    /// The distributions installed in the system site packages, keyed by name, for virtual
+    /// environments created with `--system-site-packages`. These are never modified, but may
+    /// satisfy requirements that aren't installed in the environment itself.
+    system: FxHashMap<PackageName, Vec<InstalledDist>>,
 }
 
 impl<'a> SitePackages<'a> {
@@ -43,6 +48,17 @@ impl<'a> SitePackages<'a> {
         let mut by_name = FxHashMap::default();
         let mut by_url = FxHashMap::default();
 
+        // Index the system site packages, if they're visible to the environment.
+        let mut system: FxHashMap<PackageName, Vec<InstalledDist>> = FxHashMap::default();
+        for site_packages in venv.system_site_packages() {
+            for dist_info in read_installed(site_packages)? {
+                system
+                    .entry(dist_info.name().clone())
+                    .or_default()
+                    .push(dist_info);
+            }
+        }
+
         for site_packages in venv.site_packages() {
             // Read the site-packages directory.
             let site_packages = match fs::read_dir(site_packages) {
@@ -65,6 +81,7 @@ impl<'a> SitePackages<'a> {
                         distributions,
                         by_name,
                         by_url,
+                        system,
                     });
                 }
                 Err(err) => return Err(err).context("Failed to read site-packages directory"),
@@ -104,6 +121,7 @@ impl<'a> SitePackages<'a> {
             distributions,
             by_name,
             by_url,
+            system,
         })
     }
 
@@ -153,6 +171,15 @@ impl<'a> SitePackages<'a> {
             .collect()
     }
 
+    /// Returns the distributions with the given name in the system site packages, if the
+    /// environment has access to them.
+    pub fn get_system_packages(&self, name: &PackageName) -> Vec<&InstalledDist> {
+        self.system
+            .get(name)
+            .map(|dists| dists.iter().collect())
+            .unwrap_or_default()
+    }
+
     /// Returns the distributions installed from the given URL, if any.
     pub fn get_urls(&self, url: &Url) -> Vec<&InstalledDist> {
         let Some(indexes) = self.by_url.get(url) else {
@@ -364,7 +391,14 @@ impl<'a> SitePackages<'a> {
         while let Some(requirement) = stack.pop() {
             let installed = match &requirement {
                 RequirementsTxtRequirement::Pep508(requirement) => {
-                    self.get_packages(&requirement.name)
+                    // Fall back to the system site packages, which are shadowed by the
+                    // environment's own packages.
+                    let installed = self.get_packages(&requirement.name);
+                    if installed.is_empty() {
+                        self.get_system_packages(&requirement.name)
+                    } else {
+                        installed
+                    }
                 }
                 RequirementsTxtRequirement::Unnamed(requirement) => {
                     self.get_urls(requirement.url.raw())
@@ -483,6 +517,38 @@ impl<'a> SitePackages<'a> {
     }
 }
 
+/// Read the distributions installed in the given `site-packages` directory, in sorted order.
+///
+/// Used for the system `site-packages`, which uv never modifies: any distributions with malformed
+/// metadata are skipped, rather than preventing installation into the environment.
+fn read_installed(site_packages: &Path) -> Result<Vec<InstalledDist>> {
+    let entries = match fs::read_dir(site_packages) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err).context("Failed to read site-packages directory"),
+    };
+    let directories = entries
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<BTreeSet<_>, std::io::Error>>()?;
+    let mut dists = Vec::new();
+    for path in directories {
+        if !path.is_dir() {
+            continue;
+        }
+        match InstalledDist::try_from_path(&path) {
+            Ok(Some(dist_info)) => dists.push(dist_info),
+            Ok(None) => {}
+            Err(err) => {
+                warn!(
+                    "Skipping malformed distribution in system site packages ({}): {err}",
+                    path.display()
+                );
+            }
+        }
+    }
+    Ok(dists)
+}
+
 impl IntoIterator for SitePackages<'_> {
     type Item = InstalledDist;
     type IntoIter = Flatten<std::vec::IntoIter<Option<InstalledDist>>>;
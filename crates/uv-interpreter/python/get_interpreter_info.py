@@ -9,6 +9,7 @@ import sys
 import json
 import os
 import platform
+import site
 import struct
 import sysconfig
 
@@ -99,6 +100,11 @@ def get_major_minor_version() -> str:
     return "{}.{}".format(*sys.version_info)
 
 
+def is_conda_environment() -> bool:
+    """Checks if the interpreter belongs to a Conda environment, as marked by `conda-meta`."""
+    return os.path.isdir(os.path.join(sys.prefix, "conda-meta"))
+
+
 def get_virtualenv():
     """Return the expected Scheme for virtualenvs created by this interpreter.
 
@@ -305,7 +311,21 @@ def get_scheme():
             return "posix_prefix"
 
         scheme_name = _infer_prefix()
-        paths = sysconfig.get_paths(scheme=scheme_name)
+        if is_conda_environment():
+            # Conda environments are relocated after they're built, so the prefixes recorded in
+            # `sysconfig` may not match the environment's actual location. Expand the scheme
+            # relative to the running interpreter's prefixes instead.
+            paths = sysconfig.get_paths(
+                scheme=scheme_name,
+                vars={
+                    "base": sys.prefix,
+                    "platbase": sys.exec_prefix,
+                    "installed_base": sys.base_prefix,
+                    "installed_platbase": sys.base_exec_prefix,
+                },
+            )
+        else:
+            paths = sysconfig.get_paths(scheme=scheme_name)
 
         # Logic here is very arbitrary, we're doing it for compatibility, don't ask.
         # 1. Pip historically uses a special header path in virtual environments.
@@ -397,7 +417,10 @@ def get_scheme():
     # But Python distributors can override this decision by setting:
     #     sysconfig._PIP_USE_SYSCONFIG = True / False
     # Rationale in https://github.com/pypa/pip/issues/10647
-    use_sysconfig = bool(
+    #
+    # Conda environments always use `sysconfig`, since the `distutils` configuration of a
+    # relocated environment may refer to the location at which it was built.
+    use_sysconfig = is_conda_environment() or bool(
         getattr(sysconfig, "_PIP_USE_SYSCONFIG", sys.version_info >= (3, 10))
     )
 
@@ -508,6 +531,34 @@ def get_operating_system_and_architecture():
     return {"os": operating_system, "arch": architecture}
 
 
+def get_base_site_packages():
+    """Return the `site-packages` directories of the base installation.
+
+    These are the directories exposed to a virtual environment created with
+    `--system-site-packages`. Unlike `sysconfig`, `site` respects distribution patches (e.g.,
+    Debian's `dist-packages`).
+    """
+    # Legacy versions of `virtualenv` replace `site.py`, omitting `getsitepackages`.
+    if not hasattr(site, "getsitepackages"):
+        return []
+    prefixes = [sys.base_prefix]
+    if sys.base_exec_prefix != sys.base_prefix:
+        prefixes.append(sys.base_exec_prefix)
+    return [path for path in site.getsitepackages(prefixes) if os.path.isdir(path)]
+
+
+def get_platform_triplet():
+    """Return the platform triplet (e.g., `x86_64-linux-gnu`), if any.
+
+    The triplet is used to name the platform-specific include and library directories on
+    multiarch systems (e.g., Debian), and isn't defined on Windows or macOS.
+    """
+    triplet = sysconfig.get_config_var("MULTIARCH")
+    if triplet:
+        return triplet
+    return getattr(sys.implementation, "_multiarch", None)
+
+
 def main() -> None:
     markers = {
         "implementation_name": implementation_name,
@@ -531,6 +582,11 @@ def main() -> None:
         "base_executable": getattr(sys, "_base_executable", None),
         "sys_executable": sys.executable,
         "stdlib": sysconfig.get_path("stdlib"),
+        "base_site_packages": get_base_site_packages(),
+        "sysconfig_paths": sysconfig.get_paths(),
+        "ext_suffix": sysconfig.get_config_var("EXT_SUFFIX"),
+        "soabi": sysconfig.get_config_var("SOABI"),
+        "platform_triplet": get_platform_triplet(),
         "scheme": get_scheme(),
         "virtualenv": get_virtualenv(),
         "platform": get_operating_system_and_architecture(),
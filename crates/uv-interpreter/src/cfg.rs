@@ -10,6 +10,10 @@ pub struct PyVenvConfiguration {
     pub(crate) virtualenv: bool,
     /// The version of the `uv` package used to create the virtual environment, if any.
     pub(crate) uv: bool,
+    /// Whether the virtual environment has access to the system site packages.
+    pub(crate) include_system_site_packages: bool,
+    /// The Python version the virtual environment was created with, if recorded.
+    pub(crate) version_info: Option<String>,
 }
 
 impl PyVenvConfiguration {
@@ -17,13 +21,15 @@ impl PyVenvConfiguration {
     pub fn parse(cfg: impl AsRef<Path>) -> Result<Self, Error> {
         let mut virtualenv = false;
         let mut uv = false;
+        let mut include_system_site_packages = false;
+        let mut version_info = None;
 
         // Per https://snarky.ca/how-virtual-environments-work/, the `pyvenv.cfg` file is not a
         // valid INI file, and is instead expected to be parsed by partitioning each line on the
         // first equals sign.
         let content = fs::read_to_string(&cfg)?;
         for line in content.lines() {
-            let Some((key, _value)) = line.split_once('=') else {
+            let Some((key, value)) = line.split_once('=') else {
                 continue;
             };
             match key.trim() {
@@ -33,11 +39,24 @@ impl PyVenvConfiguration {
                 "uv" => {
                     uv = true;
                 }
+                "include-system-site-packages" => {
+                    include_system_site_packages = value.trim().eq_ignore_ascii_case("true");
+                }
+                // `virtualenv` and `uv` write `version_info`; the standard library's `venv`
+                // writes `version`.
+                "version_info" | "version" => {
+                    version_info = Some(value.trim().to_string());
+                }
                 _ => {}
             }
         }
 
-        Ok(Self { virtualenv, uv })
+        Ok(Self {
+            virtualenv,
+            uv,
+            include_system_site_packages,
+            version_info,
+        })
     }
 
     /// Returns true if the virtual environment was created with the `virtualenv` package.
@@ -49,6 +68,17 @@ impl PyVenvConfiguration {
     pub fn is_uv(&self) -> bool {
         self.uv
     }
+
+    /// Returns true if the virtual environment has access to the system site packages.
+    pub fn include_system_site_packages(&self) -> bool {
+        self.include_system_site_packages
+    }
+
+    /// Returns the Python version the virtual environment was created with (e.g., `3.12.1`), if
+    /// recorded.
+    pub fn version_info(&self) -> Option<&str> {
+        self.version_info.as_deref()
+    }
 }
 
 #[derive(Debug, Error)]
@@ -56,3 +86,65 @@ pub enum Error {
     #[error(transparent)]
     Io(#[from] std::io::Error),
 }
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::PyVenvConfiguration;
+
+    #[test]
+    fn parse_system_site_packages() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cfg = temp_dir.path().join("pyvenv.cfg");
+
+        fs_err::write(
+            &cfg,
+            indoc! {"
+                home = /usr/bin
+                implementation = CPython
+                version_info = 3.12.1
+                include-system-site-packages = true
+                uv = 0.1.31
+            "},
+        )
+        .unwrap();
+        let parsed = PyVenvConfiguration::parse(&cfg).unwrap();
+        assert!(parsed.include_system_site_packages());
+        assert!(parsed.is_uv());
+        assert!(!parsed.is_virtualenv());
+        assert_eq!(parsed.version_info(), Some("3.12.1"));
+
+        fs_err::write(
+            &cfg,
+            indoc! {"
+                home = /usr/bin
+                include-system-site-packages = false
+                virtualenv = 20.25.0
+            "},
+        )
+        .unwrap();
+        let parsed = PyVenvConfiguration::parse(&cfg).unwrap();
+        assert!(!parsed.include_system_site_packages());
+        assert!(parsed.is_virtualenv());
+        assert_eq!(parsed.version_info(), None);
+    }
+
+    #[test]
+    fn parse_stdlib_venv_version() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cfg = temp_dir.path().join("pyvenv.cfg");
+
+        fs_err::write(
+            &cfg,
+            indoc! {"
+                home = /usr/bin
+                include-system-site-packages = false
+                version = 3.11.8
+            "},
+        )
+        .unwrap();
+        let parsed = PyVenvConfiguration::parse(&cfg).unwrap();
+        assert_eq!(parsed.version_info(), Some("3.11.8"));
+    }
+}
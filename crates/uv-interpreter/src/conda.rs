@@ -0,0 +1,256 @@
+//! Discovery of Conda environments, such that `--python` can name an environment (e.g., `-p data`
+//! for `~/miniconda3/envs/data`).
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde::Deserialize;
+use tracing::debug;
+
+/// The names of the directories in which Conda distributions are typically installed, relative to
+/// the user's home directory (or `/opt` on Unix).
+const CONDA_ROOT_NAMES: &[&str] = &[
+    "miniconda3",
+    "anaconda3",
+    "miniforge3",
+    "mambaforge",
+    "miniconda",
+    "anaconda",
+];
+
+/// Returns `true` if the given directory is a Conda environment, as marked by the `conda-meta`
+/// directory.
+pub(crate) fn is_conda_env(path: &Path) -> bool {
+    path.join("conda-meta").is_dir()
+}
+
+/// Find the Conda environment with the given name.
+///
+/// Conda environments are discovered (in order) from:
+/// - The `CONDA_ENVS_PATH` and `CONDA_ENVS_DIRS` environment variables, and `~/.conda/envs`.
+/// - The `envs` directory of the installation that owns the active environment (via
+///   `CONDA_PREFIX`) or provides `conda` (via `CONDA_EXE`).
+/// - The `envs` directories of the standard installation locations (e.g., `~/miniconda3`).
+/// - `conda env list`, if no environment was found otherwise and `conda` is active (via
+///   `CONDA_EXE`). Spawning `conda` is slow, so it's never located via `PATH`.
+///
+/// The name `base` refers to the root environment of a Conda installation.
+pub(crate) fn find_conda_env(name: &str) -> Option<PathBuf> {
+    let found = find_conda_env_in(name, &conda_env_dirs(), &conda_roots())
+        .or_else(|| find_conda_env_listed(name, &conda_env_list()?));
+    if let Some(found) = found.as_ref() {
+        debug!("Found Conda environment `{name}` at: {}", found.display());
+    }
+    found
+}
+
+/// Find the Conda environment with the given name in the given `envs` directories, or as the
+/// `base` environment of one of the given installation roots.
+fn find_conda_env_in(name: &str, env_dirs: &[PathBuf], roots: &[PathBuf]) -> Option<PathBuf> {
+    if name == "base" {
+        return roots.iter().find(|root| is_conda_env(root)).cloned();
+    }
+    env_dirs
+        .iter()
+        .cloned()
+        .chain(roots.iter().map(|root| root.join("envs")))
+        .map(|dir| dir.join(name))
+        .find(|env| is_conda_env(env))
+}
+
+/// Find the Conda environment with the given name among the environments reported by
+/// `conda env list`, the first of which is the `base` environment.
+fn find_conda_env_listed(name: &str, envs: &[PathBuf]) -> Option<PathBuf> {
+    if name == "base" {
+        return envs.first().cloned();
+    }
+    envs.iter()
+        .find(|env| env.file_name().is_some_and(|file_name| file_name == name))
+        .cloned()
+}
+
+/// Return every Conda environment that can be discovered, including the `base` environment of
+/// each installation.
+pub(crate) fn find_conda_envs() -> Vec<PathBuf> {
+    let mut envs: Vec<PathBuf> = Vec::new();
+    let candidates = conda_roots()
+        .into_iter()
+        .flat_map(|root| {
+            let mut envs = vec![root.clone()];
+            envs.extend(read_envs_dir(&root.join("envs")));
+            envs
+        })
+        .chain(
+            conda_env_dirs()
+                .into_iter()
+                .flat_map(|dir| read_envs_dir(&dir)),
+        )
+        .chain(conda_env_list().unwrap_or_default());
+    for env in candidates {
+        if is_conda_env(&env) && !envs.contains(&env) {
+            envs.push(env);
+        }
+    }
+    envs
+}
+
+/// List the entries of an `envs` directory, in sorted order.
+fn read_envs_dir(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs_err::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut envs = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .collect::<Vec<_>>();
+    envs.sort();
+    envs
+}
+
+/// The `envs` directories configured via the environment (`CONDA_ENVS_PATH` and
+/// `CONDA_ENVS_DIRS`), along with the per-user `~/.conda/envs`.
+fn conda_env_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    for var in ["CONDA_ENVS_PATH", "CONDA_ENVS_DIRS"] {
+        if let Some(value) = env::var_os(var).filter(|value| !value.is_empty()) {
+            dirs.extend(env::split_paths(&value));
+        }
+    }
+    if let Some(home) = home::home_dir() {
+        dirs.push(home.join(".conda").join("envs"));
+    }
+    dirs
+}
+
+/// The roots of the Conda installations that can be discovered, i.e., the installation that owns
+/// the active environment (via `CONDA_PREFIX`) and those in the standard locations.
+fn conda_roots() -> Vec<PathBuf> {
+    let mut roots = Vec::new();
+
+    // The active environment is either the `base` environment, or an environment within the
+    // `envs` directory of the installation root.
+    if let Some(prefix) = env::var_os("CONDA_PREFIX").filter(|value| !value.is_empty()) {
+        let prefix = PathBuf::from(prefix);
+        match prefix.parent() {
+            Some(parent) if parent.file_name().is_some_and(|name| name == "envs") => {
+                if let Some(root) = parent.parent() {
+                    roots.push(root.to_path_buf());
+                }
+            }
+            _ => roots.push(prefix),
+        }
+    }
+
+    // The installation that provides `conda` itself (e.g., `~/miniconda3/bin/conda`).
+    if let Some(exe) = env::var_os("CONDA_EXE").filter(|value| !value.is_empty()) {
+        if let Some(root) = Path::new(&exe).parent().and_then(Path::parent) {
+            roots.push(root.to_path_buf());
+        }
+    }
+
+    if let Some(home) = home::home_dir() {
+        roots.extend(CONDA_ROOT_NAMES.iter().map(|name| home.join(name)));
+    }
+    if cfg!(unix) {
+        roots.push(PathBuf::from("/opt/conda"));
+        roots.extend(
+            CONDA_ROOT_NAMES
+                .iter()
+                .map(|name| Path::new("/opt").join(name)),
+        );
+    }
+
+    roots.dedup();
+    roots
+}
+
+/// The output of `conda env list --json`.
+#[derive(Debug, Deserialize)]
+struct CondaEnvList {
+    envs: Vec<PathBuf>,
+}
+
+/// Query `conda env list` for the known environments, if `conda` is active (via `CONDA_EXE`).
+fn conda_env_list() -> Option<Vec<PathBuf>> {
+    let conda = env::var_os("CONDA_EXE")
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)?;
+    let output = match Command::new(&conda)
+        .args(["env", "list", "--json"])
+        .output()
+    {
+        Ok(output) if output.status.success() => output,
+        Ok(output) => {
+            debug!(
+                "`{} env list` failed with {}",
+                conda.display(),
+                output.status
+            );
+            return None;
+        }
+        Err(err) => {
+            debug!("Failed to run `{} env list`: {err}", conda.display());
+            return None;
+        }
+    };
+    match serde_json::from_slice::<CondaEnvList>(&output.stdout) {
+        Ok(list) => Some(list.envs),
+        Err(err) => {
+            debug!("Failed to parse `{} env list`: {err}", conda.display());
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use fs_err as fs;
+
+    use super::{find_conda_env_in, find_conda_env_listed};
+
+    #[test]
+    fn find_named_env() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path().join("miniconda3");
+        let user_envs = temp_dir.path().join(".conda").join("envs");
+        fs::create_dir_all(root.join("conda-meta")).unwrap();
+        fs::create_dir_all(root.join("envs").join("data").join("conda-meta")).unwrap();
+        fs::create_dir_all(user_envs.join("ml").join("conda-meta")).unwrap();
+        // Not a Conda environment.
+        fs::create_dir_all(root.join("envs").join("broken")).unwrap();
+
+        let roots = [temp_dir.path().join("anaconda3"), root.clone()];
+        let env_dirs = [user_envs.clone()];
+        assert_eq!(
+            find_conda_env_in("data", &env_dirs, &roots),
+            Some(root.join("envs").join("data"))
+        );
+        assert_eq!(
+            find_conda_env_in("ml", &env_dirs, &roots),
+            Some(user_envs.join("ml"))
+        );
+        assert_eq!(find_conda_env_in("base", &env_dirs, &roots), Some(root));
+        assert_eq!(find_conda_env_in("broken", &env_dirs, &roots), None);
+        assert_eq!(find_conda_env_in("missing", &env_dirs, &roots), None);
+    }
+
+    #[test]
+    fn find_listed_env() {
+        let envs = [
+            PathBuf::from("/opt/conda"),
+            PathBuf::from("/opt/conda/envs/data"),
+            PathBuf::from("/home/ferris/envs/ml"),
+        ];
+        assert_eq!(
+            find_conda_env_listed("base", &envs),
+            Some(PathBuf::from("/opt/conda"))
+        );
+        assert_eq!(
+            find_conda_env_listed("ml", &envs),
+            Some(PathBuf::from("/home/ferris/envs/ml"))
+        );
+        assert_eq!(find_conda_env_listed("missing", &envs), None);
+    }
+}
@@ -1,15 +1,20 @@
 use std::borrow::Cow;
+use std::collections::HashSet;
 use std::env;
 use std::ffi::{OsStr, OsString};
 use std::path::PathBuf;
 
+use regex::Regex;
 use tracing::{debug, instrument};
 
 use uv_cache::Cache;
 use uv_fs::normalize_path;
 
+use crate::conda::{find_conda_env, find_conda_envs};
 use crate::interpreter::InterpreterInfoError;
-use crate::python_environment::{detect_python_executable, detect_virtual_env};
+use crate::python_environment::{detect_environment, detect_python_executable};
+use crate::python_sources::{python_sources, PythonSource};
+use crate::python_version_file::find_python_version_file;
 use crate::{Error, Interpreter, PythonVersion};
 
 /// Find a Python of a specific version, a binary with a name or a path to a binary.
@@ -18,7 +23,9 @@ use crate::{Error, Interpreter, PythonVersion};
 /// * `-p 3.10` searches for an installed Python 3.10 (`py --list-paths` on Windows, `python3.10` on
 ///   Linux/Mac). Specifying a patch version is not supported.
 /// * `-p python3.10` or `-p python.exe` looks for a binary in `PATH`.
+/// * `-p data` uses the Conda environment named `data`, if no such binary is in `PATH`.
 /// * `-p /home/ferris/.local/bin/python3.10` uses this exact Python.
+/// * `-p /home/ferris/miniconda3/envs/data` uses the Python of this environment.
 ///
 /// When the user passes a patch version (e.g. 3.12.1), we currently search for a matching minor
 /// version (e.g. `python3.12` on unix) and error when the version mismatches, as a binary with the
@@ -45,22 +52,41 @@ pub fn find_requested_python(request: &str, cache: &Cache) -> Result<Option<Inte
         find_python(selector, cache)
     } else if !request.contains(std::path::MAIN_SEPARATOR) {
         // `-p python3.10`; Generally not used on windows because all Python are `python.exe`.
-        let Some(executable) = find_executable(request)? else {
+        if python_sources().contains(PythonSource::Path) {
+            if let Some(executable) = find_executable(request)? {
+                return Interpreter::query(executable, cache).map(Some);
+            }
+        }
+
+        // `-p data`: the name of a Conda environment (e.g., `~/miniconda3/envs/data`).
+        if !python_sources().contains(PythonSource::Conda) {
+            return Ok(None);
+        }
+        let Some(env) = find_conda_env(request) else {
             return Ok(None);
         };
-        Interpreter::query(executable, cache).map(Some)
+        Interpreter::query(detect_python_executable(env), cache).map(Some)
     } else {
         // `-p /home/ferris/.local/bin/python3.10`
         let executable = normalize_path(request);
 
+        // `-p /home/ferris/miniconda3/envs/data`: the root of an environment (e.g., a virtual
+        // environment or a Conda environment), rather than the interpreter itself.
+        let executable = if executable.is_dir() {
+            detect_python_executable(&executable)
+        } else {
+            executable
+        };
+
         Interpreter::query(executable, cache).map(Some)
     }
 }
 
 /// Pick a sensible default for the Python a user wants when they didn't specify a version.
 ///
-/// We prefer the test overwrite `UV_TEST_PYTHON_PATH` if it is set, otherwise `python3`/`python` or
-/// `python.exe` respectively.
+/// If a `.python-version` file is found in the current directory or any parent directory, we use
+/// the Python it requests. Otherwise, we prefer the test overwrite `UV_TEST_PYTHON_PATH` if it is
+/// set, otherwise `python3`/`python` or `python.exe` respectively.
 #[instrument(skip_all)]
 pub fn find_default_python(cache: &Cache) -> Result<Interpreter, Error> {
     debug!("Starting interpreter discovery for default Python");
@@ -74,10 +100,60 @@ pub fn find_default_python(cache: &Cache) -> Result<Interpreter, Error> {
 }
 
 /// Same as [`find_default_python`] but returns `None` if no python is found instead of returning an `Err`.
+///
+/// A `.python-version` file that requests an unavailable Python is an error, rather than falling
+/// back to the default.
 pub(crate) fn try_find_default_python(cache: &Cache) -> Result<Option<Interpreter>, Error> {
+    if let Some((path, request)) = find_python_version_file(&env::current_dir()?)? {
+        debug!(
+            "Using Python `{request}` as requested by: {}",
+            path.display()
+        );
+        return match find_pinned_python(&request, cache)? {
+            Some(interpreter) => Ok(Some(interpreter)),
+            None => Err(Error::PinnedPythonNotFound(request, path)),
+        };
+    }
     find_python(PythonVersionSelector::Default, cache)
 }
 
+/// Find the Python requested by a `.python-version` file.
+///
+/// In addition to the formats supported by `--python`, the request may name a `pyenv` version
+/// (e.g., `pypy3.10-7.3.15`), as installed under `$PYENV_ROOT/versions`.
+fn find_pinned_python(request: &str, cache: &Cache) -> Result<Option<Interpreter>, Error> {
+    if python_sources().contains(PythonSource::Pyenv) {
+        if let Some(pyenv_root) = pyenv_root() {
+            let version = pyenv_root.join("versions").join(request);
+            if version.is_dir() {
+                return Interpreter::query(detect_python_executable(version), cache).map(Some);
+            }
+        }
+    }
+    find_requested_python(request, cache)
+}
+
+/// The root of the `pyenv` installation, as set by `PYENV_ROOT` or defaulting to `~/.pyenv`.
+fn pyenv_root() -> Option<PathBuf> {
+    env::var_os("PYENV_ROOT")
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| home::home_dir().map(|home| home.join(".pyenv")))
+}
+
+/// The installations managed by `pyenv`, in sorted order.
+fn pyenv_versions() -> Vec<PathBuf> {
+    let Some(Ok(entries)) = pyenv_root().map(|root| fs_err::read_dir(root.join("versions"))) else {
+        return Vec::new();
+    };
+    let mut versions = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .collect::<Vec<_>>();
+    versions.sort();
+    versions
+}
+
 /// Find a Python version matching `selector`.
 ///
 /// It searches for an existing installation in the following order:
@@ -89,6 +165,10 @@ pub(crate) fn try_find_default_python(cache: &Cache) -> Result<Option<Interprete
 ///   * Default: `python3`, `python`
 ///   * (windows): For each of the above, test for the existence of `python.bat` shim (pyenv-windows) last.
 /// * (windows): Discover installations using `py --list-paths` (PEP514). Continue if `py` is not installed.
+/// * Search the installations managed by `pyenv`, unless `UV_TEST_PYTHON_PATH` is set.
+///
+/// The sources are consulted in the order configured by [`crate::set_python_sources`], and
+/// excluded sources are skipped.
 ///
 /// (Windows): Filter out the Windows store shim (Enabled in Settings/Apps/Advanced app settings/App execution aliases).
 fn find_python(
@@ -99,17 +179,41 @@ fn find_python(
     let UV_TEST_PYTHON_PATH = env::var_os("UV_TEST_PYTHON_PATH");
 
     let use_override = UV_TEST_PYTHON_PATH.is_some();
-    let possible_names = selector.possible_names();
 
     #[allow(non_snake_case)]
     let PATH = UV_TEST_PYTHON_PATH
         .or(env::var_os("PATH"))
         .unwrap_or_default();
 
+    for source in python_sources().iter() {
+        let interpreter = match source {
+            PythonSource::Path => find_python_in_path(selector, &PATH, cache)?,
+            PythonSource::PyLauncher if cfg!(windows) && !use_override => {
+                find_python_in_py_launcher(selector, cache)?
+            }
+            PythonSource::Pyenv if !use_override => find_python_in_pyenv(selector, cache)?,
+            _ => None,
+        };
+        if interpreter.is_some() {
+            return Ok(interpreter);
+        }
+    }
+
+    Ok(None)
+}
+
+/// Find a Python version matching `selector` in the given `PATH`.
+fn find_python_in_path(
+    selector: PythonVersionSelector,
+    path: &OsStr,
+    cache: &Cache,
+) -> Result<Option<Interpreter>, Error> {
+    let possible_names = selector.possible_names();
+
     // We use `which` here instead of joining the paths ourselves because `which` checks for us if the python
     // binary is executable and exists. It also has some extra logic that handles inconsistent casing on Windows
     // and expands `~`.
-    for path in env::split_paths(&PATH) {
+    for path in env::split_paths(path) {
         for name in possible_names.iter().flatten() {
             if let Ok(paths) = which::which_in_global(&**name, Some(&path)) {
                 for path in paths {
@@ -171,23 +275,51 @@ fn find_python(
         }
     }
 
-    if cfg!(windows) && !use_override {
-        // Use `py` to find the python installation on the system.
-        match windows::py_list_paths() {
-            Ok(paths) => {
-                for entry in paths {
-                    let installation = PythonInstallation::PyListPath(entry);
-                    if let Some(interpreter) = installation.select(selector, cache)? {
-                        return Ok(Some(interpreter));
-                    }
+    Ok(None)
+}
+
+/// Find a Python version matching `selector` using `py --list-paths`.
+fn find_python_in_py_launcher(
+    selector: PythonVersionSelector,
+    cache: &Cache,
+) -> Result<Option<Interpreter>, Error> {
+    match windows::py_list_paths() {
+        Ok(paths) => {
+            for entry in paths {
+                let installation = PythonInstallation::PyListPath(entry);
+                if let Some(interpreter) = installation.select(selector, cache)? {
+                    return Ok(Some(interpreter));
                 }
             }
-            Err(Error::PyList(error)) => {
-                if error.kind() == std::io::ErrorKind::NotFound {
-                    debug!("`py` is not installed");
-                }
+        }
+        Err(Error::PyList(error)) => {
+            if error.kind() == std::io::ErrorKind::NotFound {
+                debug!("`py` is not installed");
             }
-            Err(error) => return Err(error),
+        }
+        Err(error) => return Err(error),
+    }
+
+    Ok(None)
+}
+
+/// Find a Python version matching `selector` among the installations managed by `pyenv`.
+fn find_python_in_pyenv(
+    selector: PythonVersionSelector,
+    cache: &Cache,
+) -> Result<Option<Interpreter>, Error> {
+    for version in pyenv_versions() {
+        let interpreter = match Interpreter::query(detect_python_executable(&version), cache) {
+            Ok(interpreter) => interpreter,
+            Err(err) => {
+                debug!("Skipping `pyenv` version at {}: {err}", version.display());
+                continue;
+            }
+        };
+        if let Some(interpreter) =
+            PythonInstallation::Interpreter(interpreter).select(selector, cache)?
+        {
+            return Ok(Some(interpreter));
         }
     }
 
@@ -394,6 +526,114 @@ impl PythonVersionSelector {
     }
 }
 
+/// Find every Python interpreter that can be discovered, along with the location from which it
+/// was discovered.
+///
+/// Interpreters are discovered from `PATH` (or `UV_TEST_PYTHON_PATH`, if set), the `py` launcher
+/// (on Windows), `pyenv`, and Conda environments, in the order configured by
+/// [`crate::set_python_sources`]. Executables that resolve to the same
+/// file (e.g., `python3` and `python3.12`) are reported once, and executables that can't be
+/// queried (e.g., Python 2 installations) are skipped.
+#[instrument(skip_all)]
+pub fn find_all_pythons(cache: &Cache) -> Result<Vec<(PythonSource, Interpreter)>, Error> {
+    #[allow(non_snake_case)]
+    let UV_TEST_PYTHON_PATH = env::var_os("UV_TEST_PYTHON_PATH");
+
+    let use_override = UV_TEST_PYTHON_PATH.is_some();
+
+    #[allow(non_snake_case)]
+    let PATH = UV_TEST_PYTHON_PATH
+        .or(env::var_os("PATH"))
+        .unwrap_or_default();
+
+    let mut candidates = Vec::new();
+    for source in python_sources().iter() {
+        match source {
+            // Any `python`, `python3`, or `python3.12` executable in `PATH`.
+            PythonSource::Path => {
+                let pattern = Regex::new(r"^python(\d+(\.\d+)?)?(\.exe)?$").unwrap();
+                for dir in env::split_paths(&PATH) {
+                    let Ok(entries) = fs_err::read_dir(&dir) else {
+                        continue;
+                    };
+                    let mut executables = entries
+                        .filter_map(Result::ok)
+                        .map(|entry| entry.path())
+                        .filter(|path| {
+                            path.file_name()
+                                .and_then(OsStr::to_str)
+                                .is_some_and(|name| pattern.is_match(name))
+                                && path.is_file()
+                        })
+                        .collect::<Vec<_>>();
+                    executables.sort();
+                    for executable in executables {
+                        #[cfg(windows)]
+                        if windows::is_windows_store_shim(&executable) {
+                            continue;
+                        }
+                        candidates.push((PythonSource::Path, executable));
+                    }
+                }
+            }
+            // Installations registered with the `py` launcher.
+            PythonSource::PyLauncher if cfg!(windows) && !use_override => {
+                match windows::py_list_paths() {
+                    Ok(paths) => candidates.extend(
+                        paths
+                            .into_iter()
+                            .map(|entry| (PythonSource::PyLauncher, entry.executable_path)),
+                    ),
+                    Err(Error::PyList(error)) => {
+                        if error.kind() == std::io::ErrorKind::NotFound {
+                            debug!("`py` is not installed");
+                        }
+                    }
+                    Err(error) => return Err(error),
+                }
+            }
+            // Installations managed by `pyenv`.
+            PythonSource::Pyenv if !use_override => {
+                candidates.extend(
+                    pyenv_versions()
+                        .into_iter()
+                        .map(|version| (PythonSource::Pyenv, detect_python_executable(version))),
+                );
+            }
+            // Conda environments.
+            PythonSource::Conda if !use_override => {
+                candidates.extend(
+                    find_conda_envs()
+                        .into_iter()
+                        .map(|env| (PythonSource::Conda, detect_python_executable(env))),
+                );
+            }
+            _ => {}
+        }
+    }
+
+    let mut seen = HashSet::new();
+    let mut interpreters = Vec::new();
+    for (source, executable) in candidates {
+        let Ok(canonical) = fs_err::canonicalize(&executable) else {
+            continue;
+        };
+        if !seen.insert(canonical) {
+            continue;
+        }
+        match Interpreter::query(&executable, cache) {
+            Ok(interpreter) => interpreters.push((source, interpreter)),
+            Err(err) => {
+                debug!(
+                    "Skipping Python interpreter at {}: {err}",
+                    executable.display()
+                );
+            }
+        }
+    }
+    Ok(interpreters)
+}
+
 /// Find a matching Python or any fallback Python.
 ///
 /// If no Python version is provided, we will use the first available interpreter.
@@ -451,6 +691,9 @@ pub fn find_best_python(
 /// - If a python version is given: Search `PATH` and `py --list-paths`, see `find_python`
 /// - `python3` (unix) or `python.exe` (windows)
 ///
+/// The environments and installations are consulted in the order configured by
+/// [`crate::set_python_sources`].
+///
 /// If `UV_TEST_PYTHON_PATH` is set, we will not check for Python versions in the
 /// global PATH, instead we will search using the provided path. Virtual environments
 /// will still be respected.
@@ -471,30 +714,38 @@ fn find_version(
         }
     };
 
-    // Check if the venv Python matches.
-    if let Some(venv) = detect_virtual_env()? {
-        let executable = detect_python_executable(venv);
-        let interpreter = Interpreter::query(executable, cache)?;
-
-        if version_matches(&interpreter) {
-            return Ok(Some(interpreter));
+    // Check the environments and the base installations in order of priority. The base
+    // installations are searched together, since `find_python` respects their relative order.
+    let mut searched_installations = false;
+    for source in python_sources().iter() {
+        if source.is_environment() {
+            if let Some(venv) = detect_environment(source)? {
+                let executable = detect_python_executable(venv);
+                let interpreter = Interpreter::query(executable, cache)?;
+
+                if version_matches(&interpreter) {
+                    return Ok(Some(interpreter));
+                }
+            }
+        } else if !searched_installations {
+            searched_installations = true;
+
+            // Look for the requested version with by search for `python{major}.{minor}` in `PATH` on
+            // Unix and `py --list-paths` on Windows.
+            let interpreter = if let Some(python_version) = python_version {
+                find_requested_python(&python_version.string, cache)?
+            } else {
+                try_find_default_python(cache)?
+            };
+
+            if let Some(interpreter) = interpreter {
+                debug_assert!(version_matches(&interpreter));
+                return Ok(Some(interpreter));
+            }
         }
-    };
-
-    // Look for the requested version with by search for `python{major}.{minor}` in `PATH` on
-    // Unix and `py --list-paths` on Windows.
-    let interpreter = if let Some(python_version) = python_version {
-        find_requested_python(&python_version.string, cache)?
-    } else {
-        try_find_default_python(cache)?
-    };
-
-    if let Some(interpreter) = interpreter {
-        debug_assert!(version_matches(&interpreter));
-        Ok(Some(interpreter))
-    } else {
-        Ok(None)
     }
+
+    Ok(None)
 }
 
 mod windows {
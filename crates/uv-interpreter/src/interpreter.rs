@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
@@ -17,6 +18,7 @@ use pypi_types::Scheme;
 use uv_cache::{Cache, CacheBucket, CachedByTimestamp, Freshness, Timestamp};
 use uv_fs::{write_atomic_sync, PythonExt, Simplified};
 
+use crate::target::PythonTarget;
 use crate::Error;
 use crate::Virtualenv;
 
@@ -33,6 +35,11 @@ pub struct Interpreter {
     base_executable: Option<PathBuf>,
     sys_executable: PathBuf,
     stdlib: PathBuf,
+    base_site_packages: Vec<PathBuf>,
+    sysconfig_paths: BTreeMap<String, PathBuf>,
+    ext_suffix: Option<String>,
+    soabi: Option<String>,
+    platform_triplet: Option<String>,
     tags: OnceCell<Tags>,
 }
 
@@ -58,6 +65,11 @@ impl Interpreter {
             base_executable: info.base_executable,
             sys_executable: info.sys_executable,
             stdlib: info.stdlib,
+            base_site_packages: info.base_site_packages,
+            sysconfig_paths: info.sysconfig_paths,
+            ext_suffix: info.ext_suffix,
+            soabi: info.soabi,
+            platform_triplet: info.platform_triplet,
             tags: OnceCell::new(),
         })
     }
@@ -87,6 +99,11 @@ impl Interpreter {
             base_executable: None,
             sys_executable: PathBuf::from("/dev/null"),
             stdlib: PathBuf::from("/dev/null"),
+            base_site_packages: Vec::new(),
+            sysconfig_paths: BTreeMap::new(),
+            ext_suffix: None,
+            soabi: None,
+            platform_triplet: None,
             tags: OnceCell::new(),
         }
     }
@@ -133,6 +150,15 @@ impl Interpreter {
         self.prefix != self.base_prefix
     }
 
+    /// Returns `true` if the environment is a Conda environment, as marked by the `conda-meta`
+    /// directory in which Conda tracks the packages it manages.
+    ///
+    /// Unlike virtual environments, Conda environments contain a full Python installation (i.e.,
+    /// `sys.prefix` and `sys.base_prefix` are the same).
+    pub fn is_conda_env(&self) -> bool {
+        crate::conda::is_conda_env(&self.prefix)
+    }
+
     /// Returns `Some` if the environment is externally managed, optionally including an error
     /// message from the `EXTERNALLY-MANAGED` file.
     ///
@@ -259,6 +285,12 @@ impl Interpreter {
         &self.stdlib
     }
 
+    /// Return the `site-packages` directories of the base installation (i.e., those that are
+    /// visible to a virtual environment created with `--system-site-packages`).
+    pub fn base_site_packages(&self) -> &[PathBuf] {
+        &self.base_site_packages
+    }
+
     /// Return the `purelib` path for this Python interpreter, as returned by `sysconfig.get_paths()`.
     pub fn purelib(&self) -> &Path {
         &self.scheme.purelib
@@ -284,11 +316,90 @@ impl Interpreter {
         &self.scheme.include
     }
 
+    /// Return every installation path for this Python interpreter, keyed by name (e.g., `stdlib`,
+    /// `platinclude`), as returned by `sysconfig.get_paths()`.
+    pub fn sysconfig_paths(&self) -> &BTreeMap<String, PathBuf> {
+        &self.sysconfig_paths
+    }
+
+    /// Return the suffix for extension modules (e.g., `.cpython-312-x86_64-linux-gnu.so`), as
+    /// returned by `sysconfig.get_config_var("EXT_SUFFIX")`.
+    pub fn ext_suffix(&self) -> Option<&str> {
+        self.ext_suffix.as_deref()
+    }
+
+    /// Return the ABI of this Python interpreter (e.g., `cpython-312-x86_64-linux-gnu`), as
+    /// returned by `sysconfig.get_config_var("SOABI")`.
+    pub fn soabi(&self) -> Option<&str> {
+        self.soabi.as_deref()
+    }
+
+    /// Return the platform triplet (e.g., `x86_64-linux-gnu`), on platforms that define one.
+    pub fn platform_triplet(&self) -> Option<&str> {
+        self.platform_triplet.as_deref()
+    }
+
+    /// Return a complete description of the environment targeted by this Python interpreter,
+    /// including its markers and compatible wheel tags.
+    pub fn target(&self) -> Result<PythonTarget, TagsError> {
+        Ok(PythonTarget {
+            markers: self.markers().clone(),
+            platform: Some(self.platform().clone()),
+            tags: self
+                .tags()?
+                .to_vec()
+                .into_iter()
+                .map(|(python_tag, abi_tag, platform_tag)| {
+                    format!("{python_tag}-{abi_tag}-{platform_tag}")
+                })
+                .collect(),
+            sysconfig_paths: self.sysconfig_paths.clone(),
+            ext_suffix: self.ext_suffix.clone(),
+            soabi: self.soabi.clone(),
+            platform_triplet: self.platform_triplet.clone(),
+        })
+    }
+
     /// Return the [`Scheme`] for a virtual environment created by this [`Interpreter`].
     pub fn virtualenv(&self) -> &Scheme {
         &self.virtualenv
     }
 
+    /// Return a copy of this [`Interpreter`] that installs packages under the given prefix (as
+    /// with `pip install --prefix`), rather than into its own `sys.prefix`.
+    ///
+    /// The installation paths are those of the interpreter's `sysconfig` scheme (e.g.,
+    /// `lib/python3.12/site-packages` and `bin` on Unix), relative to the prefix.
+    #[must_use]
+    pub fn with_prefix(self, prefix: &Path) -> Self {
+        let scheme = Scheme {
+            purelib: prefix.join(&self.virtualenv.purelib),
+            platlib: prefix.join(&self.virtualenv.platlib),
+            scripts: prefix.join(&self.virtualenv.scripts),
+            data: prefix.join(&self.virtualenv.data),
+            include: prefix.join(&self.virtualenv.include),
+        };
+        Self { scheme, ..self }
+    }
+
+    /// Return a copy of this [`Interpreter`] that installs packages directly into the given target
+    /// directory (as with `pip install --target`), rather than into its own `sys.prefix`.
+    ///
+    /// Unlike [`Interpreter::with_prefix`], packages are installed at the root of the directory,
+    /// independent of the interpreter's `sysconfig` scheme, with scripts in `bin` and headers in
+    /// `include`.
+    #[must_use]
+    pub fn with_target(self, target: &Path) -> Self {
+        let scheme = Scheme {
+            purelib: target.to_path_buf(),
+            platlib: target.to_path_buf(),
+            scripts: target.join("bin"),
+            data: target.to_path_buf(),
+            include: target.join("include"),
+        };
+        Self { scheme, ..self }
+    }
+
     /// Return the [`Layout`] environment used to install wheels into this interpreter.
     pub fn layout(&self) -> Layout {
         Layout {
@@ -361,6 +472,11 @@ struct InterpreterInfo {
     base_executable: Option<PathBuf>,
     sys_executable: PathBuf,
     stdlib: PathBuf,
+    base_site_packages: Vec<PathBuf>,
+    sysconfig_paths: BTreeMap<String, PathBuf>,
+    ext_suffix: Option<String>,
+    soabi: Option<String>,
+    platform_triplet: Option<String>,
 }
 
 impl InterpreterInfo {
@@ -474,9 +590,9 @@ impl InterpreterInfo {
         // Read from the cache.
         if cache
             .freshness(&cache_entry, None)
-            .is_ok_and(Freshness::is_fresh)
+            .is_ok_and(Freshness::is_fresh_or_revalidate)
         {
-            if let Ok(data) = fs::read(cache_entry.path()) {
+            if let Ok(data) = fs::read(cache_entry.read_path()) {
                 match rmp_serde::from_slice::<CachedByTimestamp<Self>>(&data) {
                     Ok(cached) => {
                         if cached.timestamp == modified {
@@ -542,7 +658,42 @@ mod tests {
     use pep440_rs::Version;
     use uv_cache::Cache;
 
-    use crate::Interpreter;
+    use crate::{Interpreter, PythonTarget};
+
+    /// Query a real interpreter, to ensure that the probe captures a complete description of its
+    /// target environment.
+    #[test]
+    fn test_query_target() {
+        let Ok(python) = which::which("python3") else {
+            return;
+        };
+        let cache = Cache::temp().unwrap();
+        let interpreter = Interpreter::query(python, &cache).unwrap();
+
+        assert_eq!(
+            interpreter.sysconfig_paths().get("stdlib"),
+            Some(&interpreter.stdlib().to_path_buf())
+        );
+        assert!(interpreter.sysconfig_paths().contains_key("purelib"));
+        let ext_suffix = interpreter
+            .ext_suffix()
+            .expect("`EXT_SUFFIX` to be defined");
+        assert!(ext_suffix.starts_with('.'));
+        if interpreter.implementation_name() == "cpython" {
+            let soabi = interpreter.soabi().expect("CPython to define `SOABI`");
+            assert!(ext_suffix.contains(soabi));
+        }
+
+        // The description round-trips, and stands in for the interpreter's tags.
+        let target = interpreter.target().unwrap();
+        let json = serde_json::to_string(&target).unwrap();
+        let target: PythonTarget = serde_json::from_str(&json).unwrap();
+        assert_eq!(target, interpreter.target().unwrap());
+        assert_eq!(
+            target.tags().unwrap().to_string(),
+            interpreter.tags().unwrap().to_string()
+        );
+    }
 
     #[test]
     fn test_cache_invalidation() {
@@ -577,6 +728,16 @@ mod tests {
                 "prefix": "/home/ferris/projects/uv/.venv",
                 "sys_executable": "/home/ferris/projects/uv/.venv/bin/python",
                 "stdlib": "/home/ferris/.pyenv/versions/3.12.0/lib/python3.12",
+                "base_site_packages": [
+                    "/home/ferris/.pyenv/versions/3.12.0/lib/python3.12/site-packages"
+                ],
+                "sysconfig_paths": {
+                    "stdlib": "/home/ferris/.pyenv/versions/3.12.0/lib/python3.12",
+                    "platinclude": "/home/ferris/.pyenv/versions/3.12.0/include/python3.12"
+                },
+                "ext_suffix": ".cpython-312-x86_64-linux-gnu.so",
+                "soabi": "cpython-312-x86_64-linux-gnu",
+                "platform_triplet": "x86_64-linux-gnu",
                 "scheme": {
                     "data": "/home/ferris/.pyenv/versions/3.12.0",
                     "include": "/home/ferris/.pyenv/versions/3.12.0/include",
@@ -15,18 +15,26 @@ use std::process::ExitStatus;
 use thiserror::Error;
 
 pub use crate::cfg::PyVenvConfiguration;
-pub use crate::find_python::{find_best_python, find_default_python, find_requested_python};
+pub use crate::find_python::{
+    find_all_pythons, find_best_python, find_default_python, find_requested_python,
+};
 pub use crate::interpreter::Interpreter;
 use crate::interpreter::InterpreterInfoError;
 pub use crate::python_environment::PythonEnvironment;
+pub use crate::python_sources::{set_python_sources, PythonSource, PythonSources};
 pub use crate::python_version::PythonVersion;
+pub use crate::target::{PythonTarget, TargetError};
 pub use crate::virtualenv::Virtualenv;
 
 mod cfg;
+mod conda;
 mod find_python;
 mod interpreter;
 mod python_environment;
+mod python_sources;
 mod python_version;
+mod python_version_file;
+mod target;
 mod virtualenv;
 
 #[derive(Debug, Error)]
@@ -39,6 +47,8 @@ pub enum Error {
     VenvNotFound,
     #[error("Failed to locate Python interpreter at `{0}`")]
     RequestedPythonNotFound(String),
+    #[error("No Python {0} found, as requested by `{1}`. Is Python {0} installed?")]
+    PinnedPythonNotFound(String, PathBuf),
     #[error(transparent)]
     Io(#[from] io::Error),
     #[error("Failed to query Python interpreter at `{interpreter}`")]
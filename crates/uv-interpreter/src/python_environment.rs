@@ -7,6 +7,7 @@ use uv_cache::Cache;
 use uv_fs::{LockedFile, Simplified};
 
 use crate::cfg::PyVenvConfiguration;
+use crate::python_sources::{python_sources, PythonSource};
 use crate::{find_default_python, find_requested_python, Error, Interpreter};
 
 /// A Python environment, consisting of a Python [`Interpreter`] and its associated paths.
@@ -18,6 +19,9 @@ pub struct PythonEnvironment {
 
 impl PythonEnvironment {
     /// Create a [`PythonEnvironment`] for an existing virtual environment.
+    ///
+    /// The activated environment and any `.venv` are consulted in the order configured by
+    /// [`crate::set_python_sources`], as in [`crate::find_best_python`].
     pub fn from_virtualenv(cache: &Cache) -> Result<Self, Error> {
         let Some(venv) = detect_virtual_env()? else {
             return Err(Error::VenvNotFound);
@@ -67,6 +71,25 @@ impl PythonEnvironment {
         }
     }
 
+    /// Install packages under the given prefix (as with `pip install --prefix`), using the paths
+    /// of the interpreter's `sysconfig` scheme relative to the prefix.
+    #[must_use]
+    pub fn with_prefix(self, prefix: PathBuf) -> Self {
+        Self {
+            interpreter: self.interpreter.with_prefix(&prefix),
+            root: prefix,
+        }
+    }
+
+    /// Install packages directly into the given target directory (as with `pip install --target`).
+    #[must_use]
+    pub fn with_target(self, target: PathBuf) -> Self {
+        Self {
+            interpreter: self.interpreter.with_target(&target),
+            root: target,
+        }
+    }
+
     /// Returns the location of the Python interpreter.
     pub fn root(&self) -> &Path {
         &self.root
@@ -102,6 +125,25 @@ impl PythonEnvironment {
         )
     }
 
+    /// Returns an iterator over the system `site-packages` directories that are visible to the
+    /// environment, i.e., if it's a virtual environment created with `--system-site-packages`.
+    pub fn system_site_packages(&self) -> impl Iterator<Item = &Path> {
+        let include = self.interpreter.is_virtualenv()
+            && self
+                .cfg()
+                .is_ok_and(|cfg| cfg.include_system_site_packages());
+        self.interpreter
+            .base_site_packages()
+            .iter()
+            .filter(move |_| include)
+            .map(PathBuf::as_path)
+            .filter(|path| {
+                !self
+                    .site_packages()
+                    .any(|site_packages| site_packages == *path)
+            })
+    }
+
     /// Returns the path to the `bin` directory inside a virtual environment.
     pub fn scripts(&self) -> &Path {
         self.interpreter.scripts()
@@ -122,24 +164,48 @@ impl PythonEnvironment {
     }
 }
 
-/// Locate the current virtual environment.
+/// Locate the current virtual environment, consulting the activated environment and any `.venv`
+/// in the order configured by [`crate::set_python_sources`].
 pub(crate) fn detect_virtual_env() -> Result<Option<PathBuf>, Error> {
+    for source in python_sources().iter() {
+        if let Some(venv) = detect_environment(source)? {
+            return Ok(Some(venv));
+        }
+    }
+    Ok(None)
+}
+
+/// Locate the environment provided by the given source, if it's an environment source (i.e.,
+/// [`PythonSource::VirtualEnv`] or [`PythonSource::DotVenv`]).
+pub(crate) fn detect_environment(source: PythonSource) -> Result<Option<PathBuf>, Error> {
+    match source {
+        PythonSource::VirtualEnv => Ok(detect_active_env()),
+        PythonSource::DotVenv => detect_dot_venv(),
+        _ => Ok(None),
+    }
+}
+
+/// Locate the activated environment, via `VIRTUAL_ENV` or `CONDA_PREFIX`.
+fn detect_active_env() -> Option<PathBuf> {
     if let Some(dir) = env::var_os("VIRTUAL_ENV").filter(|value| !value.is_empty()) {
         info!(
             "Found a virtualenv through VIRTUAL_ENV at: {}",
             Path::new(&dir).display()
         );
-        return Ok(Some(PathBuf::from(dir)));
+        return Some(PathBuf::from(dir));
     }
     if let Some(dir) = env::var_os("CONDA_PREFIX").filter(|value| !value.is_empty()) {
         info!(
-            "Found a virtualenv through CONDA_PREFIX at: {}",
+            "Found a Conda environment through CONDA_PREFIX at: {}",
             Path::new(&dir).display()
         );
-        return Ok(Some(PathBuf::from(dir)));
+        return Some(PathBuf::from(dir));
     }
+    None
+}
 
-    // Search for a `.venv` directory in the current or any parent directory.
+/// Locate a `.venv` directory in the current or any parent directory.
+fn detect_dot_venv() -> Result<Option<PathBuf>, Error> {
     let current_dir = env::current_dir().expect("Failed to detect current directory");
     for dir in current_dir.ancestors() {
         let dot_venv = dir.join(".venv");
@@ -0,0 +1,194 @@
+//! The sources from which Python interpreters are discovered, and the order in which they're
+//! consulted.
+use std::fmt;
+use std::str::FromStr;
+
+use once_cell::sync::OnceCell;
+
+/// The location from which a Python interpreter was discovered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PythonSource {
+    /// An activated environment, via `VIRTUAL_ENV` or `CONDA_PREFIX`.
+    VirtualEnv,
+    /// A `.venv` in the current directory, or in the nearest parent directory.
+    DotVenv,
+    /// An executable in `PATH` (e.g., `python3.12`).
+    Path,
+    /// An installation reported by the `py` launcher (`py --list-paths`).
+    PyLauncher,
+    /// An installation managed by `pyenv` (e.g., `~/.pyenv/versions/3.12.1`).
+    Pyenv,
+    /// A Conda environment (e.g., `~/miniconda3/envs/data`).
+    Conda,
+}
+
+impl PythonSource {
+    /// Returns `true` if the source is an environment, rather than a base installation.
+    pub fn is_environment(self) -> bool {
+        matches!(self, Self::VirtualEnv | Self::DotVenv)
+    }
+}
+
+impl FromStr for PythonSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "virtual-env" => Ok(Self::VirtualEnv),
+            "dot-venv" => Ok(Self::DotVenv),
+            "path" => Ok(Self::Path),
+            "py-launcher" => Ok(Self::PyLauncher),
+            "pyenv" => Ok(Self::Pyenv),
+            "conda" => Ok(Self::Conda),
+            _ => Err(format!(
+                "Unknown Python source `{s}` (expected one of: `virtual-env`, `dot-venv`, `path`, `py-launcher`, `pyenv`, or `conda`)"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for PythonSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::VirtualEnv => f.write_str("virtual-env"),
+            Self::DotVenv => f.write_str("dot-venv"),
+            Self::Path => f.write_str("path"),
+            Self::PyLauncher => f.write_str("py-launcher"),
+            Self::Pyenv => f.write_str("pyenv"),
+            Self::Conda => f.write_str("conda"),
+        }
+    }
+}
+
+/// The sources from which Python interpreters may be discovered, in order of priority.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PythonSources(Vec<PythonSource>);
+
+impl Default for PythonSources {
+    fn default() -> Self {
+        Self(vec![
+            PythonSource::VirtualEnv,
+            PythonSource::DotVenv,
+            PythonSource::Path,
+            PythonSource::PyLauncher,
+            PythonSource::Pyenv,
+            PythonSource::Conda,
+        ])
+    }
+}
+
+impl PythonSources {
+    /// Prioritize the given sources, in order.
+    ///
+    /// If `only` is set, discovery is restricted to the given sources. Otherwise, any environment
+    /// sources (e.g., an activated virtual environment) that aren't listed retain their default
+    /// priority ahead of the given sources, and any remaining sources are consulted afterwards, in
+    /// their default order.
+    pub fn new(preferred: &[PythonSource], only: bool) -> Self {
+        let mut sources = Vec::new();
+        if !only {
+            for source in Self::default().0 {
+                if source.is_environment() && !preferred.contains(&source) {
+                    sources.push(source);
+                }
+            }
+        }
+        for source in preferred {
+            if !sources.contains(source) {
+                sources.push(*source);
+            }
+        }
+        if !only {
+            for source in Self::default().0 {
+                if !sources.contains(&source) {
+                    sources.push(source);
+                }
+            }
+        }
+        Self(sources)
+    }
+
+    /// Returns `true` if discovery may consult the given source.
+    pub fn contains(&self, source: PythonSource) -> bool {
+        self.0.contains(&source)
+    }
+
+    /// Iterate over the sources, in order of priority.
+    pub fn iter(&self) -> impl Iterator<Item = PythonSource> + '_ {
+        self.0.iter().copied()
+    }
+}
+
+static PYTHON_SOURCES: OnceCell<PythonSources> = OnceCell::new();
+
+/// Configure the sources used for Python discovery for the lifetime of the process.
+///
+/// Returns the given sources back if discovery has already been configured.
+pub fn set_python_sources(sources: PythonSources) -> Result<(), PythonSources> {
+    PYTHON_SOURCES.set(sources)
+}
+
+/// The sources used for Python discovery, as configured by [`set_python_sources`].
+pub(crate) fn python_sources() -> &'static PythonSources {
+    PYTHON_SOURCES.get_or_init(PythonSources::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PythonSource, PythonSources};
+
+    #[test]
+    fn prioritize() {
+        // Environments retain their priority unless listed.
+        let sources = PythonSources::new(&[PythonSource::Pyenv, PythonSource::Path], false);
+        assert_eq!(
+            sources.iter().collect::<Vec<_>>(),
+            [
+                PythonSource::VirtualEnv,
+                PythonSource::DotVenv,
+                PythonSource::Pyenv,
+                PythonSource::Path,
+                PythonSource::PyLauncher,
+                PythonSource::Conda,
+            ]
+        );
+
+        let sources = PythonSources::new(&[PythonSource::Path, PythonSource::VirtualEnv], false);
+        assert_eq!(
+            sources.iter().collect::<Vec<_>>(),
+            [
+                PythonSource::DotVenv,
+                PythonSource::Path,
+                PythonSource::VirtualEnv,
+                PythonSource::PyLauncher,
+                PythonSource::Pyenv,
+                PythonSource::Conda,
+            ]
+        );
+    }
+
+    #[test]
+    fn restrict() {
+        let sources = PythonSources::new(
+            &[
+                PythonSource::Path,
+                PythonSource::VirtualEnv,
+                PythonSource::Path,
+            ],
+            true,
+        );
+        assert_eq!(
+            sources.iter().collect::<Vec<_>>(),
+            [PythonSource::Path, PythonSource::VirtualEnv]
+        );
+        assert!(!sources.contains(PythonSource::Pyenv));
+    }
+
+    #[test]
+    fn round_trip() {
+        for source in PythonSources::default().iter() {
+            assert_eq!(source.to_string().parse::<PythonSource>(), Ok(source));
+        }
+        assert!("toolchain".parse::<PythonSource>().is_err());
+    }
+}
@@ -0,0 +1,91 @@
+//! Discovery of `.python-version` files, which pin the Python version for a project (as in
+//! `pyenv`).
+use std::io;
+use std::path::{Path, PathBuf};
+
+use tracing::debug;
+
+/// The name of the file that pins the Python version for a directory and its descendants.
+pub(crate) const PYTHON_VERSION_FILENAME: &str = ".python-version";
+
+/// Find the nearest `.python-version` file in the given directory or any of its ancestors,
+/// returning its path along with the requested Python version.
+///
+/// The request is the first line of the file that is neither blank nor a comment. Files without a
+/// request are skipped, as are requests for the `system` Python, which `pyenv` uses to disable
+/// pinning.
+pub(crate) fn find_python_version_file(dir: &Path) -> Result<Option<(PathBuf, String)>, io::Error> {
+    for ancestor in dir.ancestors() {
+        let path = ancestor.join(PYTHON_VERSION_FILENAME);
+        let contents = match fs_err::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+            Err(err) => return Err(err),
+        };
+        let Some(request) = parse_python_version_file(&contents) else {
+            debug!("Ignoring empty Python version file: {}", path.display());
+            continue;
+        };
+        if request == "system" {
+            debug!("Ignoring `system` Python version file: {}", path.display());
+            return Ok(None);
+        }
+        return Ok(Some((path, request.to_string())));
+    }
+    Ok(None)
+}
+
+/// Return the first request in a `.python-version` file.
+///
+/// `pyenv` allows multiple versions to be listed (one per line), in order of preference; only the
+/// first is respected.
+fn parse_python_version_file(contents: &str) -> Option<&str> {
+    contents
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('#'))
+}
+
+#[cfg(test)]
+mod tests {
+    use fs_err as fs;
+
+    use super::{find_python_version_file, parse_python_version_file, PYTHON_VERSION_FILENAME};
+
+    #[test]
+    fn parse() {
+        assert_eq!(parse_python_version_file("3.12\n"), Some("3.12"));
+        assert_eq!(
+            parse_python_version_file("# Pinned.\n\n  3.11.4  \n3.10\n"),
+            Some("3.11.4")
+        );
+        assert_eq!(parse_python_version_file("\n# Nothing.\n"), None);
+    }
+
+    #[test]
+    fn find_in_ancestor() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let project = temp_dir.path().join("project");
+        let nested = project.join("src").join("package");
+        fs::create_dir_all(&nested).unwrap();
+        assert_eq!(find_python_version_file(&nested).unwrap(), None);
+
+        fs::write(project.join(PYTHON_VERSION_FILENAME), "3.11\n").unwrap();
+        assert_eq!(
+            find_python_version_file(&nested).unwrap(),
+            Some((project.join(PYTHON_VERSION_FILENAME), "3.11".to_string()))
+        );
+
+        // The nearest file takes precedence, but empty files are skipped.
+        fs::write(nested.join(PYTHON_VERSION_FILENAME), "pypy3.10\n").unwrap();
+        fs::write(project.join("src").join(PYTHON_VERSION_FILENAME), "\n").unwrap();
+        assert_eq!(
+            find_python_version_file(&nested).unwrap(),
+            Some((nested.join(PYTHON_VERSION_FILENAME), "pypy3.10".to_string()))
+        );
+
+        // `system` disables pinning.
+        fs::write(nested.join(PYTHON_VERSION_FILENAME), "system\n").unwrap();
+        assert_eq!(find_python_version_file(&nested).unwrap(), None);
+    }
+}
@@ -0,0 +1,186 @@
+//! A complete description of the environment targeted by a Python interpreter, as needed to
+//! resolve and install packages for it without access to the interpreter itself.
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use pep508_rs::MarkerEnvironment;
+use platform_tags::{Platform, Tags};
+
+/// The environment targeted by a Python interpreter: its markers, compatible wheel tags, and
+/// (optionally) the installation details required to build for it.
+///
+/// The description can be captured from an interpreter via [`crate::Interpreter::target`] and
+/// serialized, such that it can stand in for the interpreter elsewhere (e.g., on a build host for
+/// another platform).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PythonTarget {
+    /// The PEP 508 marker environment.
+    pub markers: MarkerEnvironment,
+    /// The operating system and architecture, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub platform: Option<Platform>,
+    /// The compatible wheel tags (e.g., `cp312-cp312-manylinux_2_17_x86_64`), from high to low
+    /// priority.
+    pub tags: Vec<String>,
+    /// The installation paths, as returned by `sysconfig.get_paths()`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub sysconfig_paths: BTreeMap<String, PathBuf>,
+    /// The suffix for extension modules (e.g., `.cpython-312-x86_64-linux-gnu.so`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ext_suffix: Option<String>,
+    /// The ABI of the interpreter (e.g., `cpython-312-x86_64-linux-gnu`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub soabi: Option<String>,
+    /// The platform triplet (e.g., `x86_64-linux-gnu`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub platform_triplet: Option<String>,
+}
+
+impl PythonTarget {
+    /// Read a [`PythonTarget`] from a JSON file or, if the file has a `.toml` extension, a TOML
+    /// file.
+    pub fn from_path(path: &Path) -> Result<Self, TargetError> {
+        let contents = fs_err::read_to_string(path)?;
+        if path
+            .extension()
+            .is_some_and(|extension| extension == "toml")
+        {
+            toml::from_str(&contents)
+                .map_err(|err| TargetError::Toml(path.to_path_buf(), Box::new(err)))
+        } else {
+            serde_json::from_str(&contents)
+                .map_err(|err| TargetError::Json(path.to_path_buf(), err))
+        }
+    }
+
+    /// Return the compatible [`Tags`], prioritized in the order in which they're listed.
+    pub fn tags(&self) -> Result<Tags, TargetError> {
+        let tags = self
+            .tags
+            .iter()
+            .map(|tag| {
+                let mut parts = tag.splitn(3, '-');
+                match (parts.next(), parts.next(), parts.next()) {
+                    (Some(python_tag), Some(abi_tag), Some(platform_tag))
+                        if !python_tag.is_empty()
+                            && !abi_tag.is_empty()
+                            && !platform_tag.is_empty() =>
+                    {
+                        Ok((
+                            python_tag.to_string(),
+                            abi_tag.to_string(),
+                            platform_tag.to_string(),
+                        ))
+                    }
+                    _ => Err(TargetError::InvalidTag(tag.clone())),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Tags::new(tags))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TargetError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("Failed to parse target environment: `{0}`")]
+    Json(PathBuf, #[source] serde_json::Error),
+    #[error("Failed to parse target environment: `{0}`")]
+    Toml(PathBuf, #[source] Box<toml::de::Error>),
+    #[error("Invalid wheel tag `{0}` (expected `<python tag>-<abi tag>-<platform tag>`, e.g., `cp312-cp312-manylinux_2_17_x86_64`)")]
+    InvalidTag(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PythonTarget;
+
+    const TARGET: &str = r#"{
+        "markers": {
+            "implementation_name": "cpython",
+            "implementation_version": "3.12.1",
+            "os_name": "posix",
+            "platform_machine": "aarch64",
+            "platform_python_implementation": "CPython",
+            "platform_release": "",
+            "platform_system": "Linux",
+            "platform_version": "",
+            "python_full_version": "3.12.1",
+            "python_version": "3.12",
+            "sys_platform": "linux"
+        },
+        "tags": [
+            "cp312-cp312-manylinux_2_17_aarch64",
+            "cp312-abi3-manylinux_2_17_aarch64",
+            "py3-none-any"
+        ]
+    }"#;
+
+    #[test]
+    fn tags() {
+        let target: PythonTarget = serde_json::from_str(TARGET).unwrap();
+        let tags = target.tags().unwrap();
+        let tag = |s: &str| vec![s.to_string()];
+        assert!(tags.is_compatible(&tag("cp312"), &tag("cp312"), &tag("manylinux_2_17_aarch64")));
+        assert!(tags.is_compatible(&tag("py3"), &tag("none"), &tag("any")));
+        assert!(!tags.is_compatible(&tag("cp312"), &tag("cp312"), &tag("manylinux_2_17_x86_64")));
+        // Tags are prioritized in the order in which they're listed.
+        assert!(
+            tags.compatibility(&tag("cp312"), &tag("cp312"), &tag("manylinux_2_17_aarch64"))
+                > tags.compatibility(&tag("py3"), &tag("none"), &tag("any"))
+        );
+    }
+
+    #[test]
+    fn invalid_tag() {
+        let mut target: PythonTarget = serde_json::from_str(TARGET).unwrap();
+        target.tags.push("cp312-manylinux_2_17_aarch64".to_string());
+        assert_eq!(
+            target.tags().unwrap_err().to_string(),
+            "Invalid wheel tag `cp312-manylinux_2_17_aarch64` (expected `<python tag>-<abi tag>-<platform tag>`, e.g., `cp312-cp312-manylinux_2_17_x86_64`)"
+        );
+    }
+
+    #[test]
+    fn from_toml() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("target.toml");
+        fs_err::write(
+            &path,
+            indoc::indoc! {r#"
+                tags = ["cp312-cp312-manylinux_2_17_aarch64", "py3-none-any"]
+
+                [markers]
+                implementation_name = "cpython"
+                implementation_version = "3.12.1"
+                os_name = "posix"
+                platform_machine = "aarch64"
+                platform_python_implementation = "CPython"
+                platform_release = ""
+                platform_system = "Linux"
+                platform_version = ""
+                python_full_version = "3.12.1"
+                python_version = "3.12"
+                sys_platform = "linux"
+            "#},
+        )
+        .unwrap();
+        let target = PythonTarget::from_path(&path).unwrap();
+        assert_eq!(target.markers.platform_machine, "aarch64");
+        assert_eq!(target.tags.len(), 2);
+    }
+
+    #[test]
+    fn round_trip() {
+        let target: PythonTarget = serde_json::from_str(TARGET).unwrap();
+        let serialized = serde_json::to_string(&target).unwrap();
+        assert_eq!(
+            serde_json::from_str::<PythonTarget>(&serialized).unwrap(),
+            target
+        );
+    }
+}
@@ -1,12 +1,13 @@
 use std::path::PathBuf;
 
 use anyhow::{Context, Result};
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
 use tracing::{instrument, Level};
+use url::Url;
 
 use cache_key::CanonicalUrl;
 use distribution_types::{FlatIndexLocation, IndexUrl};
-use pep508_rs::{Requirement, RequirementsTxtRequirement};
+use pep508_rs::{Requirement, RequirementsTxtRequirement, VersionOrUrl};
 use requirements_txt::{EditableRequirement, FindLink, RequirementsTxt};
 use uv_client::BaseClientBuilder;
 use uv_fs::Simplified;
@@ -22,8 +23,13 @@ pub struct RequirementsSpecification {
     pub project: Option<PackageName>,
     /// The requirements for the project.
     pub requirements: Vec<RequirementsTxtRequirement>,
+    /// The hashes provided for each requirement or constraint via `--hash`, if any.
+    pub hashes: RequirementHashes,
     /// The constraints for the project.
     pub constraints: Vec<Requirement>,
+    /// The location (e.g., `constraints.txt:3`) at which each requirement or constraint was
+    /// declared, if read from a requirements file.
+    pub sources: FxHashMap<Requirement, String>,
     /// The overrides for the project.
     pub overrides: Vec<Requirement>,
     /// Package to install as editable installs
@@ -46,6 +52,81 @@ pub struct RequirementsSpecification {
     pub no_build: NoBuild,
 }
 
+/// The hashes provided via `--hash` for each requirement, keyed by package name or, for unnamed
+/// requirements (like `./foo-0.1.0.whl`), by URL.
+#[derive(Debug, Default, Clone)]
+pub struct RequirementHashes {
+    names: FxHashMap<PackageName, Vec<String>>,
+    urls: FxHashMap<Url, Vec<String>>,
+}
+
+impl RequirementHashes {
+    /// Add the hashes for a requirement, merging them with any hashes already provided for it.
+    pub fn insert(&mut self, requirement: &RequirementsTxtRequirement, hashes: &[String]) {
+        if hashes.is_empty() {
+            return;
+        }
+        let existing = match requirement {
+            RequirementsTxtRequirement::Pep508(requirement) => {
+                self.names.entry(requirement.name.clone()).or_default()
+            }
+            RequirementsTxtRequirement::Unnamed(requirement) => {
+                self.urls.entry(requirement.url.to_url()).or_default()
+            }
+        };
+        extend_unique(existing, hashes.iter().cloned());
+    }
+
+    /// Add the hashes for a constraint, merging them with any hashes already provided for it.
+    pub fn insert_constraint(&mut self, constraint: &Requirement, hashes: &[String]) {
+        if hashes.is_empty() {
+            return;
+        }
+        extend_unique(
+            self.names.entry(constraint.name.clone()).or_default(),
+            hashes.iter().cloned(),
+        );
+    }
+
+    /// Merge the hashes from another set of requirements.
+    pub fn merge(&mut self, other: Self) {
+        for (name, hashes) in other.names {
+            extend_unique(self.names.entry(name).or_default(), hashes);
+        }
+        for (url, hashes) in other.urls {
+            extend_unique(self.urls.entry(url).or_default(), hashes);
+        }
+    }
+
+    /// Returns `true` if no hashes were provided.
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty() && self.urls.is_empty()
+    }
+
+    /// Returns the hashes provided for a (named) requirement, matching unnamed requirements by
+    /// their URL.
+    pub fn get(&self, requirement: &Requirement) -> &[String] {
+        if let Some(hashes) = self.names.get(&requirement.name) {
+            return hashes;
+        }
+        if let Some(VersionOrUrl::Url(url)) = requirement.version_or_url.as_ref() {
+            if let Some(hashes) = self.urls.get(url.raw()) {
+                return hashes;
+            }
+        }
+        &[]
+    }
+}
+
+/// Extend a list of hashes, skipping any that are already present.
+fn extend_unique(existing: &mut Vec<String>, hashes: impl IntoIterator<Item = String>) {
+    for hash in hashes {
+        if !existing.contains(&hash) {
+            existing.push(hash);
+        }
+    }
+}
+
 impl RequirementsSpecification {
     /// Read the requirements and constraints from a source.
     #[instrument(skip_all, level = Level::DEBUG, fields(source = % source))]
@@ -61,7 +142,9 @@ impl RequirementsSpecification {
                 Self {
                     project: None,
                     requirements: vec![requirement],
+                    hashes: RequirementHashes::default(),
                     constraints: vec![],
+                    sources: FxHashMap::default(),
                     overrides: vec![],
                     editables: vec![],
                     source_trees: vec![],
@@ -80,7 +163,9 @@ impl RequirementsSpecification {
                 Self {
                     project: None,
                     requirements: vec![],
+                    hashes: RequirementHashes::default(),
                     constraints: vec![],
+                    sources: FxHashMap::default(),
                     overrides: vec![],
                     editables: vec![requirement],
                     source_trees: vec![],
@@ -96,6 +181,30 @@ impl RequirementsSpecification {
             RequirementsSource::RequirementsTxt(path) => {
                 let requirements_txt =
                     RequirementsTxt::parse(path, std::env::current_dir()?, client_builder).await?;
+                let mut hashes = RequirementHashes::default();
+                for entry in &requirements_txt.requirements {
+                    hashes.insert(&entry.requirement, &entry.hashes);
+                }
+                for entry in &requirements_txt.constraints {
+                    hashes.insert_constraint(&entry.requirement, &entry.hashes);
+                }
+                let mut sources = FxHashMap::default();
+                for entry in &requirements_txt.requirements {
+                    if let RequirementsTxtRequirement::Pep508(requirement) = &entry.requirement {
+                        sources.insert(requirement.clone(), format!("{source}:{}", entry.line));
+                    }
+                }
+                let constraints = requirements_txt
+                    .constraints
+                    .into_iter()
+                    .map(|entry| {
+                        sources.insert(
+                            entry.requirement.clone(),
+                            format!("{}:{}", entry.source, entry.line),
+                        );
+                        entry.requirement
+                    })
+                    .collect();
                 Self {
                     project: None,
                     requirements: requirements_txt
@@ -103,7 +212,9 @@ impl RequirementsSpecification {
                         .into_iter()
                         .map(|entry| entry.requirement)
                         .collect(),
-                    constraints: requirements_txt.constraints,
+                    hashes,
+                    constraints,
+                    sources,
                     overrides: vec![],
                     editables: requirements_txt.editables,
                     source_trees: vec![],
@@ -153,7 +264,9 @@ impl RequirementsSpecification {
                             .into_iter()
                             .map(RequirementsTxtRequirement::Pep508)
                             .collect(),
+                        hashes: RequirementHashes::default(),
                         constraints: vec![],
+                        sources: FxHashMap::default(),
                         overrides: vec![],
                         editables: vec![],
                         source_trees: vec![],
@@ -176,7 +289,9 @@ impl RequirementsSpecification {
                     Self {
                         project: None,
                         requirements: vec![],
+                        hashes: RequirementHashes::default(),
                         constraints: vec![],
+                        sources: FxHashMap::default(),
                         overrides: vec![],
                         editables: vec![],
                         source_trees: vec![source_tree.to_path_buf()],
@@ -201,7 +316,9 @@ impl RequirementsSpecification {
                 Self {
                     project: None,
                     requirements: vec![],
+                    hashes: RequirementHashes::default(),
                     constraints: vec![],
+                    sources: FxHashMap::default(),
                     overrides: vec![],
                     editables: vec![],
                     source_trees: vec![source_tree.to_path_buf()],
@@ -233,7 +350,9 @@ impl RequirementsSpecification {
         for source in requirements {
             let source = Self::from_source(source, extras, client_builder).await?;
             spec.requirements.extend(source.requirements);
+            spec.hashes.merge(source.hashes);
             spec.constraints.extend(source.constraints);
+            spec.sources.extend(source.sources);
             spec.overrides.extend(source.overrides);
             spec.extras.extend(source.extras);
             spec.editables.extend(source.editables);
@@ -276,7 +395,9 @@ impl RequirementsSpecification {
                     }
                 }
             }
+            spec.hashes.merge(source.hashes);
             spec.constraints.extend(source.constraints);
+            spec.sources.extend(source.sources);
             spec.constraints.extend(source.overrides);
 
             if let Some(index_url) = source.index_url {
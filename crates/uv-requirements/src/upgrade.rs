@@ -4,7 +4,7 @@ use anyhow::Result;
 
 use requirements_txt::RequirementsTxt;
 use uv_client::{BaseClientBuilder, Connectivity};
-use uv_resolver::{Preference, PreferenceError};
+use uv_resolver::{Lock, Preference, PreferenceError};
 use uv_types::Upgrade;
 
 /// Load the preferred requirements from an existing lockfile, applying the upgrade strategy.
@@ -20,19 +20,25 @@ pub async fn read_lockfile(
         return Ok(Vec::new());
     };
 
-    // Parse the requirements from the lockfile.
-    let requirements_txt = RequirementsTxt::parse(
-        output_file,
-        std::env::current_dir()?,
-        &BaseClientBuilder::new().connectivity(Connectivity::Offline),
-    )
-    .await?;
-    let preferences = requirements_txt
-        .requirements
-        .into_iter()
-        .filter(|entry| !entry.editable)
-        .map(Preference::from_entry)
-        .collect::<Result<Vec<_>, PreferenceError>>()?;
+    let preferences = if Lock::is_lockfile(output_file) {
+        // Parse the pinned versions from the `uv.lock`.
+        let contents = fs_err::tokio::read_to_string(output_file).await?;
+        Lock::from_toml(&contents)?.preferences()?
+    } else {
+        // Parse the requirements from the lockfile.
+        let requirements_txt = RequirementsTxt::parse(
+            output_file,
+            std::env::current_dir()?,
+            &BaseClientBuilder::new().connectivity(Connectivity::Offline),
+        )
+        .await?;
+        requirements_txt
+            .requirements
+            .into_iter()
+            .filter(|entry| !entry.editable)
+            .map(Preference::from_entry)
+            .collect::<Result<Vec<_>, PreferenceError>>()?
+    };
 
     // Apply the upgrade strategy to the requirements.
     Ok(match upgrade {
@@ -43,7 +49,7 @@ pub async fn read_lockfile(
         // Ignore pinned versions for the specified packages.
         Upgrade::Packages(packages) => preferences
             .into_iter()
-            .filter(|preference| !packages.contains(preference.name()))
+            .filter(|preference| !packages.contains_key(preference.name()))
             .collect(),
     })
 }
@@ -0,0 +1,41 @@
+use distribution_types::CompatibleDist;
+use pep440_rs::Version;
+use uv_normalize::PackageName;
+
+/// A policy that is consulted before the resolver commits to a candidate, allowing library users
+/// to veto or re-rank specific package versions or distributions (e.g., to forbid source
+/// distributions for a given package, to enforce a minimum version across an organization, or to
+/// prefer an organization's vetted releases).
+///
+/// A rejected candidate is treated like any other unusable version: the resolver backtracks and
+/// tries the next candidate, and the rejection reason is included in the error message if the
+/// resolution ultimately fails.
+pub trait CandidatePolicy: Send + Sync {
+    /// Decide whether the given distribution may be selected for the given package version.
+    fn evaluate(
+        &self,
+        name: &PackageName,
+        version: &Version,
+        dist: &CompatibleDist,
+    ) -> CandidateDecision;
+
+    /// Return the versions of the given package to try first, in order of preference.
+    ///
+    /// Preferred versions that are compatible with the current requirements take precedence over
+    /// the resolver's usual ordering (including any preferences from an existing lockfile or
+    /// installed packages); if none of them are usable, the resolver falls back to that ordering.
+    /// Preferred versions are still subject to [`CandidatePolicy::evaluate`].
+    fn preferred_versions(&self, _name: &PackageName) -> Vec<Version> {
+        Vec::new()
+    }
+}
+
+/// The outcome of evaluating a [`CandidatePolicy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CandidateDecision {
+    /// The candidate may be selected.
+    Allow,
+    /// The candidate must not be selected. The reason is rendered as "... is unusable because
+    /// {reason}".
+    Reject(String),
+}
@@ -1,3 +1,4 @@
+use itertools::Itertools;
 use pubgrub::range::Range;
 
 use distribution_types::{CompatibleDist, IncompatibleDist, IncompatibleSource};
@@ -6,8 +7,9 @@ use pep440_rs::Version;
 use pep508_rs::MarkerEnvironment;
 use tracing::debug;
 use uv_normalize::PackageName;
-use uv_types::InstalledPackagesProvider;
+use uv_types::{IndexStrategy, InstalledPackagesProvider};
 
+use crate::local_version::LocalVersionPreference;
 use crate::preferences::Preferences;
 use crate::prerelease_mode::PreReleaseStrategy;
 use crate::resolution_mode::ResolutionStrategy;
@@ -18,6 +20,8 @@ use crate::{Exclusions, Manifest, Options};
 pub(crate) struct CandidateSelector {
     resolution_strategy: ResolutionStrategy,
     prerelease_strategy: PreReleaseStrategy,
+    index_strategy: IndexStrategy,
+    local_version_preference: Option<LocalVersionPreference>,
 }
 
 impl CandidateSelector {
@@ -38,6 +42,21 @@ impl CandidateSelector {
                 manifest,
                 markers,
             ),
+            index_strategy: options.index_strategy,
+            local_version_preference: None,
+        }
+    }
+
+    /// Prefer candidates with the given local version label (e.g., `cu118`), falling back to
+    /// other candidates if none are available.
+    #[must_use]
+    pub(crate) fn with_local_version_preference(
+        self,
+        local_version_preference: LocalVersionPreference,
+    ) -> Self {
+        Self {
+            local_version_preference: Some(local_version_preference),
+            ..self
         }
     }
 
@@ -90,6 +109,29 @@ impl CandidateSelector {
         self.select_no_preference(package_name, range, version_maps)
     }
 
+    /// Select the first of the given versions, in order, that satisfies the range and has a
+    /// compatible distribution.
+    ///
+    /// This is used to apply the ranking of a [`CandidatePolicy`](crate::CandidatePolicy) ahead of
+    /// the selector's own ordering.
+    pub(crate) fn select_ranked<'a>(
+        package_name: &'a PackageName,
+        range: &Range<Version>,
+        version_maps: &'a [VersionMap],
+        ranked: &[Version],
+    ) -> Option<Candidate<'a>> {
+        ranked
+            .iter()
+            .filter(|version| range.contains(version))
+            .find_map(|version| {
+                let (version, dist) = version_maps
+                    .iter()
+                    .find_map(|version_map| version_map.get_with_version(version))?;
+                let candidate = Candidate::new(package_name, version, dist);
+                candidate.compatible().is_some().then_some(candidate)
+            })
+    }
+
     /// Get a preferred version if one exists. This is the preference from a lockfile or a locally
     /// installed version.
     fn get_preferred<'a, InstalledPackages: InstalledPackagesProvider>(
@@ -208,6 +250,62 @@ impl CandidateSelector {
         let highest = self.use_highest_version(package_name);
         let allow_prerelease = self.allow_prereleases(package_name);
 
+        // If the best version across all indexes should win, consider the versions from every
+        // index at once, rather than exhausting each index in turn.
+        if self.index_strategy == IndexStrategy::UnsafeBestMatch {
+            if let Some(local) = &self.local_version_preference {
+                let candidate = Self::select_candidate(
+                    merged_versions(version_maps, highest)
+                        .filter(|(version, _)| local.matches(version)),
+                    package_name,
+                    range,
+                    allow_prerelease,
+                );
+                if candidate.is_some() {
+                    return candidate;
+                }
+            }
+            return Self::select_candidate(
+                merged_versions(version_maps, highest),
+                package_name,
+                range,
+                allow_prerelease,
+            );
+        }
+
+        // If a local version label is preferred, first consider only the versions with that label.
+        // If the selected version turns out to be unusable, it'll be excluded from the range, and
+        // we'll fall back to the next matching version, or to the unlabeled versions.
+        if let Some(local) = &self.local_version_preference {
+            let candidate = if highest {
+                version_maps.iter().find_map(|version_map| {
+                    Self::select_candidate(
+                        version_map
+                            .iter()
+                            .rev()
+                            .filter(|(version, _)| local.matches(version)),
+                        package_name,
+                        range,
+                        allow_prerelease,
+                    )
+                })
+            } else {
+                version_maps.iter().find_map(|version_map| {
+                    Self::select_candidate(
+                        version_map
+                            .iter()
+                            .filter(|(version, _)| local.matches(version)),
+                        package_name,
+                        range,
+                        allow_prerelease,
+                    )
+                })
+            };
+            if candidate.is_some() {
+                return candidate;
+            }
+        }
+
         if highest {
             version_maps.iter().find_map(|version_map| {
                 Self::select_candidate(
@@ -239,7 +337,7 @@ impl CandidateSelector {
     /// Select the first-matching [`Candidate`] from a set of candidate versions and files,
     /// preferring wheels over source distributions.
     fn select_candidate<'a>(
-        versions: impl Iterator<Item = (&'a Version, VersionMapDistHandle<'a>)> + ExactSizeIterator,
+        versions: impl Iterator<Item = (&'a Version, VersionMapDistHandle<'a>)>,
         package_name: &'a PackageName,
         range: &Range<Version>,
         allow_prerelease: AllowPreRelease,
@@ -250,9 +348,21 @@ impl CandidateSelector {
             IfNecessary(&'a Version, &'a PrioritizedDist),
         }
 
+        /// Log any versions that were skipped due to `--exclude-newer`.
+        fn log_excluded_newer(package_name: &PackageName, excluded_newer: &[&Version]) {
+            if !excluded_newer.is_empty() {
+                debug!(
+                    "Excluded versions of {package_name} published after the `--exclude-newer` cutoff: {}",
+                    excluded_newer.iter().join(", ")
+                );
+            }
+        }
+
         let mut prerelease = None;
-        let versions_len = versions.len();
+        let mut excluded_newer = Vec::new();
+        let mut steps = 0;
         for (step, (version, maybe_dist)) in versions.enumerate() {
+            steps = step + 1;
             let candidate = if version.any_prerelease() {
                 if range.contains(version) {
                     match allow_prerelease {
@@ -317,28 +427,23 @@ impl CandidateSelector {
 
             // If candidate is not compatible due to exclude newer, continue searching.
             // This is a special case — we pretend versions with exclude newer incompatibilities
-            // do not exist so that they are not present in error messages in our test suite.
-            // TODO(zanieb): Now that `--exclude-newer` is user facing we may want to consider
-            // flagging this behavior such that we _will_ report filtered distributions due to
-            // exclude-newer in our error messages.
-            if matches!(
-                candidate.dist(),
-                CandidateDist::Incompatible(
-                    IncompatibleDist::Source(IncompatibleSource::ExcludeNewer(_))
-                        | IncompatibleDist::Wheel(IncompatibleWheel::ExcludeNewer(_))
-                )
-            ) {
+            // do not exist, such that they don't clutter the derivation tree in error messages.
+            // Instead, they're logged here, and reported via a hint on resolution failure.
+            if candidate.dist().is_excluded_newer() {
+                excluded_newer.push(version);
                 continue;
             }
 
+            log_excluded_newer(package_name, &excluded_newer);
             return Some(candidate);
         }
+        log_excluded_newer(package_name, &excluded_newer);
         tracing::trace!(
             "exhausted all candidates for package {:?} with range {:?} \
              after {} steps",
             package_name,
             range,
-            versions_len,
+            steps,
         );
         match prerelease {
             None => None,
@@ -350,12 +455,55 @@ impl CandidateSelector {
     }
 }
 
+/// Iterate over the versions in all of the given version maps at once, in order of preference
+/// (i.e., highest first, if `highest` is set), breaking ties in favor of the earlier index.
+fn merged_versions(
+    version_maps: &[VersionMap],
+    highest: bool,
+) -> impl Iterator<Item = (&Version, VersionMapDistHandle<'_>)> {
+    version_maps
+        .iter()
+        .enumerate()
+        .map(move |(index, version_map)| {
+            let versions = if highest {
+                either::Either::Left(version_map.iter().rev())
+            } else {
+                either::Either::Right(version_map.iter())
+            };
+            versions.map(move |(version, dist)| (index, version, dist))
+        })
+        .kmerge_by(move |(a_index, a, _), (b_index, b, _)| {
+            if a == b {
+                a_index < b_index
+            } else if highest {
+                a > b
+            } else {
+                a < b
+            }
+        })
+        .map(|(_, version, dist)| (version, dist))
+}
+
 #[derive(Debug, Clone)]
 pub(crate) enum CandidateDist<'a> {
     Compatible(CompatibleDist<'a>),
     Incompatible(IncompatibleDist),
 }
 
+impl CandidateDist<'_> {
+    /// Returns `true` if the distribution is incompatible because it was published after the
+    /// `--exclude-newer` cutoff.
+    pub(crate) fn is_excluded_newer(&self) -> bool {
+        matches!(
+            self,
+            CandidateDist::Incompatible(
+                IncompatibleDist::Source(IncompatibleSource::ExcludeNewer(_))
+                    | IncompatibleDist::Wheel(IncompatibleWheel::ExcludeNewer(_))
+            )
+        )
+    }
+}
+
 impl<'a> From<&'a PrioritizedDist> for CandidateDist<'a> {
     fn from(value: &'a PrioritizedDist) -> Self {
         if let Some(dist) = value.get() {
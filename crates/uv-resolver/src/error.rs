@@ -1,9 +1,11 @@
 use std::collections::BTreeSet;
 use std::fmt::Formatter;
 use std::ops::Deref;
+use std::time::Duration;
 
 use dashmap::{DashMap, DashSet};
 use indexmap::IndexMap;
+use itertools::Itertools;
 use pubgrub::range::Range;
 use pubgrub::report::{DefaultStringReporter, DerivationTree, Reporter};
 use rustc_hash::FxHashMap;
@@ -13,12 +15,15 @@ use distribution_types::{
 };
 use once_map::OnceMap;
 use pep440_rs::Version;
-use pep508_rs::Requirement;
+use pep508_rs::{Requirement, VersionOrUrl};
 use uv_normalize::PackageName;
+use uv_types::Constraints;
 
-use crate::candidate_selector::CandidateSelector;
+use crate::candidate_selector::{CandidateDist, CandidateSelector};
 use crate::dependency_provider::UvDependencyProvider;
-use crate::pubgrub::{PubGrubPackage, PubGrubPython, PubGrubReportFormatter};
+use crate::pubgrub::{
+    PubGrubPackage, PubGrubPython, PubGrubReportFormatter, PubGrubSpecifier, SourcedConstraint,
+};
 use crate::python_requirement::PythonRequirement;
 use crate::resolver::{UnavailablePackage, VersionsResponse};
 
@@ -93,11 +98,47 @@ pub enum ResolveError {
     #[error("Attempted to construct an invalid version specifier")]
     InvalidVersion(#[from] pep440_rs::VersionSpecifierBuildError),
 
+    #[error("Resolution exceeded the limit of {limit} tried {}; the most backtracked packages were:\n{tried}", if *limit == 1 { "version" } else { "versions" })]
+    BudgetExceeded { limit: usize, tried: TriedVersions },
+
+    #[error("Tried {limit} versions of {package} without finding a compatible one; the conflict is likely elsewhere in the dependency graph (increase `--max-candidates` to keep searching)")]
+    CandidateLimitExceeded {
+        package: Box<PubGrubPackage>,
+        limit: usize,
+    },
+
+    #[error("Resolution timed out after {}s; the most backtracked packages were:\n{tried}", timeout.as_secs_f32())]
+    Timeout {
+        timeout: Duration,
+        tried: TriedVersions,
+    },
+
     /// Something unexpected happened.
     #[error("{0}")]
     Failure(String),
 }
 
+/// The packages that consumed the most of the resolver's budget, along with the number of versions
+/// tried for each.
+#[derive(Debug)]
+pub struct TriedVersions(pub(crate) Vec<(PubGrubPackage, usize)>);
+
+impl std::fmt::Display for TriedVersions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (index, (package, count)) in self.0.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            if *count == 1 {
+                write!(f, "- {package} (1 version)")?;
+            } else {
+                write!(f, "- {package} ({count} versions)")?;
+            }
+        }
+        Ok(())
+    }
+}
+
 impl<T> From<tokio::sync::mpsc::error::SendError<T>> for ResolveError {
     /// Drop the value we want to send to not leak the private type we're sending.
     /// The tokio error only says "channel closed", so we don't lose information.
@@ -121,10 +162,12 @@ impl From<pubgrub::error::PubGrubError<UvDependencyProvider>> for ResolveError {
                     derivation_tree,
                     // The following should be populated before display for the best error messages
                     available_versions: IndexMap::default(),
+                    excluded_newer_versions: IndexMap::default(),
                     selector: None,
                     python_requirement: None,
                     index_locations: None,
                     unavailable_packages: FxHashMap::default(),
+                    constraint_sources: FxHashMap::default(),
                 })
             }
             pubgrub::error::PubGrubError::SelfDependency { package, version } => {
@@ -142,10 +185,12 @@ impl From<pubgrub::error::PubGrubError<UvDependencyProvider>> for ResolveError {
 pub struct NoSolutionError {
     derivation_tree: DerivationTree<PubGrubPackage, Range<Version>>,
     available_versions: IndexMap<PubGrubPackage, BTreeSet<Version>>,
+    excluded_newer_versions: IndexMap<PubGrubPackage, BTreeSet<Version>>,
     selector: Option<CandidateSelector>,
     python_requirement: Option<PythonRequirement>,
     index_locations: Option<IndexLocations>,
     unavailable_packages: FxHashMap<PackageName, UnavailablePackage>,
+    constraint_sources: FxHashMap<PackageName, Vec<SourcedConstraint>>,
 }
 
 impl std::error::Error for NoSolutionError {}
@@ -155,6 +200,7 @@ impl std::fmt::Display for NoSolutionError {
         // Write the derivation report.
         let formatter = PubGrubReportFormatter {
             available_versions: &self.available_versions,
+            excluded_newer_versions: &self.excluded_newer_versions,
             python_requirement: self.python_requirement.as_ref(),
         };
         let report =
@@ -167,6 +213,7 @@ impl std::fmt::Display for NoSolutionError {
             &self.selector,
             &self.index_locations,
             &self.unavailable_packages,
+            &self.constraint_sources,
         ) {
             write!(f, "\n\n{hint}")?;
         }
@@ -176,6 +223,17 @@ impl std::fmt::Display for NoSolutionError {
 }
 
 impl NoSolutionError {
+    /// Render the derivation tree for the resolution failure as a nested explanation, with each
+    /// conclusion followed by the facts it was derived from.
+    pub fn explain(&self) -> String {
+        let formatter = PubGrubReportFormatter {
+            available_versions: &self.available_versions,
+            excluded_newer_versions: &self.excluded_newer_versions,
+            python_requirement: self.python_requirement.as_ref(),
+        };
+        formatter.explain(&self.derivation_tree)
+    }
+
     /// Update the available versions attached to the error using the given package version index.
     ///
     /// Only packages used in the error's derivation tree will be retrieved.
@@ -187,6 +245,7 @@ impl NoSolutionError {
         package_versions: &OnceMap<PackageName, VersionsResponse>,
     ) -> Self {
         let mut available_versions = IndexMap::default();
+        let mut excluded_newer_versions = IndexMap::default();
         for package in self.derivation_tree.packages() {
             match package {
                 PubGrubPackage::Root(_) => {}
@@ -217,6 +276,18 @@ impl NoSolutionError {
                                         .extend(
                                             version_map.iter().map(|(version, _)| version.clone()),
                                         );
+
+                                    // Track any versions that were excluded by `--exclude-newer`.
+                                    for (version, dist) in version_map.iter() {
+                                        if dist.prioritized_dist().is_some_and(|dist| {
+                                            CandidateDist::from(dist).is_excluded_newer()
+                                        }) {
+                                            excluded_newer_versions
+                                                .entry(package.clone())
+                                                .or_insert_with(BTreeSet::new)
+                                                .insert(version.clone());
+                                        }
+                                    }
                                 }
                             }
                         }
@@ -225,6 +296,7 @@ impl NoSolutionError {
             }
         }
         self.available_versions = available_versions;
+        self.excluded_newer_versions = excluded_newer_versions;
         self
     }
 
@@ -261,6 +333,50 @@ impl NoSolutionError {
         self
     }
 
+    /// Update the constraints attached to the error, retaining those that were declared in a
+    /// constraints file.
+    ///
+    /// Only constraints on packages used in the error's derivation tree will be retained.
+    #[must_use]
+    pub(crate) fn with_constraint_sources(mut self, constraints: &Constraints) -> Self {
+        let mut new: FxHashMap<PackageName, Vec<SourcedConstraint>> = FxHashMap::default();
+        for package in self.derivation_tree.packages() {
+            let PubGrubPackage::Package(name, ..) = package else {
+                continue;
+            };
+            if new.contains_key(name) {
+                continue;
+            }
+            for constraint in constraints.get(name).into_iter().flatten() {
+                let Some(source) = constraints.source(constraint) else {
+                    continue;
+                };
+                let Some(VersionOrUrl::VersionSpecifier(specifiers)) = &constraint.version_or_url
+                else {
+                    continue;
+                };
+                let Ok(range) = specifiers
+                    .iter()
+                    .map(PubGrubSpecifier::try_from)
+                    .fold_ok(Range::full(), |range, specifier| {
+                        range.intersection(&specifier.into())
+                    })
+                else {
+                    continue;
+                };
+                new.entry(name.clone())
+                    .or_default()
+                    .push(SourcedConstraint {
+                        range,
+                        constraint: constraint.to_string(),
+                        source: source.to_string(),
+                    });
+            }
+        }
+        self.constraint_sources = new;
+        self
+    }
+
     /// Update the Python requirements attached to the error.
     #[must_use]
     pub(crate) fn with_python_requirement(
@@ -1,8 +1,11 @@
+pub use candidate_policy::{CandidateDecision, CandidatePolicy};
 pub use dependency_mode::DependencyMode;
-pub use error::ResolveError;
+pub use error::{ResolveError, TriedVersions};
 pub use exclusions::Exclusions;
+pub use local_version::LocalVersionPreference;
+pub use lock::{Lock, LockError};
 pub use manifest::Manifest;
-pub use options::{Options, OptionsBuilder};
+pub use options::{Options, OptionsBuilder, PrefetchOptions};
 pub use preferences::{Preference, PreferenceError};
 pub use prerelease_mode::PreReleaseMode;
 pub use python_requirement::PythonRequirement;
@@ -10,13 +13,19 @@ pub use resolution::{AnnotationStyle, Diagnostic, DisplayResolutionGraph, Resolu
 pub use resolution_mode::ResolutionMode;
 pub use resolver::{
     BuildId, DefaultResolverProvider, InMemoryIndex, MetadataResponse, PackageVersionsResult,
-    Reporter as ResolverReporter, Resolver, ResolverProvider, VersionsResponse,
+    Reporter as ResolverReporter, Resolver, ResolverEvent, ResolverProvider, VersionsResponse,
     WheelMetadataResult,
 };
+pub use universal::{
+    DisplayUniversalResolution, ForkEnvironment, TargetArch, TargetOs, UniversalResolution,
+    LATEST_PYTHON_MINOR,
+};
 pub use version_map::VersionMap;
+pub use yanked_mode::YankedMode;
 pub use yanks::AllowedYanks;
 
 mod bare;
+mod candidate_policy;
 mod candidate_selector;
 
 mod dependency_mode;
@@ -24,6 +33,8 @@ mod dependency_provider;
 mod editables;
 mod error;
 mod exclusions;
+mod local_version;
+mod lock;
 mod manifest;
 mod options;
 mod pins;
@@ -35,5 +46,7 @@ mod redirect;
 mod resolution;
 mod resolution_mode;
 mod resolver;
+mod universal;
 mod version_map;
+mod yanked_mode;
 mod yanks;
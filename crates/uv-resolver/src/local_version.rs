@@ -0,0 +1,60 @@
+use std::str::FromStr;
+
+use itertools::Itertools;
+use pep440_rs::{LocalSegment, Version, VersionParseError};
+
+/// A preference for candidates with a given local version label (e.g., `cu118` in `2.1.0+cu118`),
+/// as used by some indexes to publish variants of the same release, such as CUDA or CPU-only
+/// builds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalVersionPreference(Vec<LocalSegment>);
+
+impl LocalVersionPreference {
+    /// Returns `true` if the given version has the preferred local version label.
+    pub(crate) fn matches(&self, version: &Version) -> bool {
+        version.local() == self.0.as_slice()
+    }
+}
+
+impl FromStr for LocalVersionPreference {
+    type Err = VersionParseError;
+
+    /// Parse a local version label (e.g., `cu118` or `+cu118`), applying the same normalization as
+    /// the local segment of a PEP 440 version.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let label = s.strip_prefix('+').unwrap_or(s);
+        let version = Version::from_str(&format!("0+{label}"))?;
+        Ok(Self(version.local().to_vec()))
+    }
+}
+
+impl std::fmt::Display for LocalVersionPreference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.iter().join("."))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn local_version_preference() -> Result<(), VersionParseError> {
+        let preference = LocalVersionPreference::from_str("cu118")?;
+        assert!(preference.matches(&Version::from_str("2.1.0+cu118")?));
+        assert!(!preference.matches(&Version::from_str("2.1.0+cu121")?));
+        assert!(!preference.matches(&Version::from_str("2.1.0")?));
+
+        // The label is normalized, like the local segment of a version.
+        let preference = LocalVersionPreference::from_str("+CU118")?;
+        assert_eq!(preference.to_string(), "cu118");
+        assert!(preference.matches(&Version::from_str("2.1.0+cu118")?));
+
+        // Multiple segments must all match.
+        let preference = LocalVersionPreference::from_str("cu118-1")?;
+        assert!(preference.matches(&Version::from_str("2.1.0+cu118.1")?));
+        assert!(!preference.matches(&Version::from_str("2.1.0+cu118")?));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,458 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::str::FromStr;
+
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use distribution_filename::DistFilename;
+use distribution_types::{
+    BuiltDist, Dist, File, FileLocation, IndexUrl, LocalEditable, Name, ResolvedDist, SourceDist,
+};
+use pep440_rs::{Operator, Version, VersionSpecifier};
+use pep508_rs::{MarkerEnvironment, MarkerTree, Requirement, VerbatimUrl, VersionOrUrl};
+use platform_tags::Tags;
+use pypi_types::{HashError, Hashes};
+use uv_normalize::PackageName;
+
+use crate::{Preference, ResolutionGraph, UniversalResolution};
+
+#[derive(Debug, thiserror::Error)]
+pub enum LockError {
+    #[error("Failed to parse lockfile")]
+    Deserialize(#[from] toml::de::Error),
+
+    #[error("Failed to serialize lockfile")]
+    Serialize(#[from] toml::ser::Error),
+
+    #[error("Unsupported lockfile version: {0} (expected {expected})", expected = Lock::VERSION)]
+    UnsupportedVersion(u32),
+
+    #[error("Invalid URL in lockfile: `{0}`")]
+    Url(String, #[source] url::ParseError),
+
+    #[error("Invalid path in lockfile: `{0}`")]
+    Path(String),
+
+    #[error(transparent)]
+    JoinRelativeUrl(#[from] pypi_types::JoinRelativeError),
+
+    #[error("Invalid marker expression for `{0}` in lockfile")]
+    Marker(PackageName, #[source] pep508_rs::Pep508Error),
+
+    #[error("Invalid hash for `{0}` in lockfile")]
+    Hash(String, #[source] HashError),
+
+    #[error("Invalid filename for `{0}` in lockfile: `{1}`")]
+    Filename(PackageName, String),
+
+    #[error(transparent)]
+    Dist(#[from] distribution_types::Error),
+
+    #[error("The lockfile doesn't contain a distribution of `{0}` that's compatible with the current platform")]
+    Incompatible(PackageName),
+}
+
+/// A lockfile (`uv.lock`), capturing the output of a resolution in a structured format.
+///
+/// Unlike a `requirements.txt` file, the lockfile records the source of every distribution
+/// (e.g., the index from which it was resolved, or the precise Git commit), along with the file
+/// (and hash) that was pinned for each target environment, such that it can be installed directly
+/// without re-resolving.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lock {
+    /// The version of the lockfile format.
+    version: u32,
+    /// The locked distributions, sorted by name.
+    #[serde(
+        rename = "distribution",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    distributions: Vec<LockedDistribution>,
+}
+
+/// A single distribution in a [`Lock`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct LockedDistribution {
+    /// The name of the package.
+    name: PackageName,
+    /// The pinned version of the package, if known. Versions aren't recorded for distributions
+    /// that were resolved from a URL, which is precise on its own.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    version: Option<Version>,
+    /// The source of the distribution.
+    source: Source,
+    /// The environment markers under which the distribution applies, if it doesn't apply
+    /// universally.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    marker: Option<String>,
+    /// The files that were pinned for the distribution, for registry sources.
+    #[serde(rename = "file", default, skip_serializing_if = "Vec::is_empty")]
+    files: Vec<LockedFile>,
+}
+
+/// The source of a [`LockedDistribution`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+struct Source {
+    /// The kind of source.
+    kind: SourceKind,
+    /// The URL of the index, for registry sources; otherwise, the (precise) URL of the
+    /// distribution.
+    url: String,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum SourceKind {
+    /// A distribution from a registry, like PyPI.
+    Registry,
+    /// A distribution at an arbitrary URL.
+    Direct,
+    /// A source distribution in a Git repository, pinned to a specific commit.
+    Git,
+    /// A distribution on the local filesystem.
+    Path,
+    /// An editable installation of a local source tree.
+    Editable,
+}
+
+/// A file (wheel or source distribution) pinned for a registry-based [`LockedDistribution`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+struct LockedFile {
+    /// The name of the file.
+    filename: String,
+    /// The absolute URL of the file.
+    url: String,
+    /// The hash of the file, as `<algorithm>:<hash>`, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    hash: Option<String>,
+}
+
+/// The name, version, and source identifying a [`LockedDistribution`].
+type DistributionKey = (PackageName, Option<Version>, Source);
+
+impl Lock {
+    /// The name of the lockfile.
+    pub const FILENAME: &'static str = "uv.lock";
+
+    /// The current version of the lockfile format.
+    pub const VERSION: u32 = 1;
+
+    /// Returns `true` if the given path refers to a lockfile.
+    pub fn is_lockfile(path: &Path) -> bool {
+        path.ends_with(Self::FILENAME)
+    }
+
+    /// Create a [`Lock`] from a resolution for the current environment.
+    pub fn from_resolution(resolution: &ResolutionGraph) -> Result<Self, LockError> {
+        Self::from_forks(&[resolution], |_| None)
+    }
+
+    /// Create a [`Lock`] from a universal resolution, annotating each distribution with the
+    /// environment markers under which it applies.
+    pub fn from_universal(resolution: &UniversalResolution) -> Result<Self, LockError> {
+        let graphs = resolution.graphs().collect::<Vec<_>>();
+        Self::from_forks(&graphs, |forks| resolution.marker(forks))
+    }
+
+    /// Merge the resolutions for one or more forks into a [`Lock`].
+    fn from_forks(
+        graphs: &[&ResolutionGraph],
+        marker: impl Fn(&BTreeSet<usize>) -> Option<MarkerTree>,
+    ) -> Result<Self, LockError> {
+        let mut merged: BTreeMap<DistributionKey, (BTreeSet<usize>, BTreeSet<LockedFile>)> =
+            BTreeMap::new();
+        for (fork, graph) in graphs.iter().enumerate() {
+            for dist in graph.petgraph().node_weights() {
+                let ResolvedDist::Installable(dist) = dist else {
+                    continue;
+                };
+                let (version, source, file) = locked(dist)?;
+                let (forks, files) = merged
+                    .entry((dist.name().clone(), version, source))
+                    .or_default();
+                forks.insert(fork);
+                files.extend(file);
+            }
+        }
+
+        let distributions = merged
+            .into_iter()
+            .map(
+                |((name, version, source), (forks, files))| LockedDistribution {
+                    name,
+                    version,
+                    source,
+                    marker: marker(&forks).map(|marker| marker.to_string()),
+                    files: files.into_iter().collect(),
+                },
+            )
+            .collect();
+
+        Ok(Self {
+            version: Self::VERSION,
+            distributions,
+        })
+    }
+
+    /// Parse a [`Lock`] from its TOML representation.
+    pub fn from_toml(contents: &str) -> Result<Self, LockError> {
+        let lock: Self = toml::from_str(contents)?;
+        if lock.version != Self::VERSION {
+            return Err(LockError::UnsupportedVersion(lock.version));
+        }
+        Ok(lock)
+    }
+
+    /// Serialize the [`Lock`] to TOML.
+    pub fn to_toml(&self) -> Result<String, LockError> {
+        Ok(toml::to_string(self)?)
+    }
+
+    /// Return the number of distributions in the lockfile, across all environments.
+    pub fn len(&self) -> usize {
+        self.distributions.len()
+    }
+
+    /// Return `true` if the lockfile contains no distributions.
+    pub fn is_empty(&self) -> bool {
+        self.distributions.is_empty()
+    }
+
+    /// Return the pinned versions in the lockfile as [`Preference`]s, for use in a subsequent
+    /// resolution.
+    pub fn preferences(&self) -> Result<Vec<Preference>, LockError> {
+        let mut preferences = Vec::new();
+        for distribution in &self.distributions {
+            let Some(version) = distribution.version.as_ref() else {
+                continue;
+            };
+            if distribution.source.kind != SourceKind::Registry {
+                continue;
+            }
+            let hashes = distribution
+                .files
+                .iter()
+                .filter_map(|file| file.hash.as_deref())
+                .map(|hash| {
+                    Hashes::from_str(hash).map_err(|err| LockError::Hash(hash.to_string(), err))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let requirement = Requirement {
+                name: distribution.name.clone(),
+                extras: vec![],
+                version_or_url: Some(VersionOrUrl::VersionSpecifier(
+                    [
+                        VersionSpecifier::from_version(Operator::Equal, version.clone())
+                            .expect("Equal operator to be valid for any version"),
+                    ]
+                    .into_iter()
+                    .collect(),
+                )),
+                marker: distribution.marker(&distribution.name)?,
+            };
+            preferences.push(Preference::from_requirement(requirement).with_hashes(hashes));
+        }
+        Ok(preferences)
+    }
+
+    /// Return the distributions to install for the given environment, without re-resolving.
+    ///
+    /// Distributions whose markers don't apply to the environment are omitted. For registry
+    /// sources, the most compatible locked wheel is selected, falling back to the source
+    /// distribution.
+    pub fn to_resolution(
+        &self,
+        markers: &MarkerEnvironment,
+        tags: &Tags,
+    ) -> Result<distribution_types::Resolution, LockError> {
+        let mut packages = FxHashMap::default();
+        for distribution in &self.distributions {
+            if let Some(marker) = distribution.marker(&distribution.name)? {
+                if !marker.evaluate(markers, &[]) {
+                    continue;
+                }
+            }
+            let dist = distribution.to_dist(tags)?;
+            packages.insert(distribution.name.clone(), ResolvedDist::Installable(dist));
+        }
+        Ok(distribution_types::Resolution::new(packages))
+    }
+}
+
+impl LockedDistribution {
+    /// Parse the marker expression for the distribution, if any.
+    fn marker(&self, name: &PackageName) -> Result<Option<MarkerTree>, LockError> {
+        self.marker
+            .as_deref()
+            .map(MarkerTree::from_str)
+            .transpose()
+            .map_err(|err| LockError::Marker(name.clone(), err))
+    }
+
+    /// Convert the locked distribution into a [`Dist`] that can be installed in an environment
+    /// with the given tags.
+    fn to_dist(&self, tags: &Tags) -> Result<Dist, LockError> {
+        let url = || {
+            VerbatimUrl::parse_url(&self.source.url)
+                .map_err(|err| LockError::Url(self.source.url.clone(), err))
+        };
+        match self.source.kind {
+            SourceKind::Registry => {
+                let index = IndexUrl::from_str(&self.source.url)
+                    .map_err(|err| LockError::Url(self.source.url.clone(), err))?;
+
+                // Parse the filenames of the locked files.
+                let mut candidates = Vec::with_capacity(self.files.len());
+                for file in &self.files {
+                    let filename = DistFilename::try_from_filename(&file.filename, &self.name)
+                        .ok_or_else(|| {
+                            LockError::Filename(self.name.clone(), file.filename.clone())
+                        })?;
+                    candidates.push((filename, file));
+                }
+
+                // Prefer the most compatible wheel, then any source distribution.
+                let wheel = candidates
+                    .iter()
+                    .filter_map(|(filename, file)| match filename {
+                        DistFilename::WheelFilename(wheel) => {
+                            wheel.is_compatible(tags).then_some((wheel, *file))
+                        }
+                        DistFilename::SourceDistFilename(_) => None,
+                    })
+                    .max_by_key(|(wheel, _)| wheel.compatibility(tags))
+                    .map(|(wheel, file)| (DistFilename::WheelFilename(wheel.clone()), file));
+                let sdist = candidates
+                    .iter()
+                    .find_map(|(filename, file)| match filename {
+                        DistFilename::SourceDistFilename(_) => Some((filename.clone(), *file)),
+                        DistFilename::WheelFilename(_) => None,
+                    });
+                let Some((filename, file)) = wheel.or(sdist) else {
+                    return Err(LockError::Incompatible(self.name.clone()));
+                };
+
+                Ok(Dist::from_registry(filename, file.to_file()?, index))
+            }
+            SourceKind::Direct | SourceKind::Git | SourceKind::Path => {
+                Ok(Dist::from_url(self.name.clone(), url()?)?)
+            }
+            SourceKind::Editable => {
+                let url = url()?;
+                let path = url
+                    .to_file_path()
+                    .map_err(|()| LockError::Path(self.source.url.clone()))?;
+                Ok(Dist::from_editable(
+                    self.name.clone(),
+                    LocalEditable {
+                        url,
+                        path,
+                        extras: vec![],
+                    },
+                )?)
+            }
+        }
+    }
+}
+
+impl LockedFile {
+    /// Convert the locked file into a [`File`].
+    fn to_file(&self) -> Result<File, LockError> {
+        let url = Url::parse(&self.url).map_err(|err| LockError::Url(self.url.clone(), err))?;
+        let location = if url.scheme() == "file" {
+            FileLocation::Path(
+                url.to_file_path()
+                    .map_err(|()| LockError::Path(self.url.clone()))?,
+            )
+        } else {
+            FileLocation::AbsoluteUrl(self.url.clone())
+        };
+        let hashes = self
+            .hash
+            .as_deref()
+            .map(Hashes::from_str)
+            .transpose()
+            .map_err(|err| LockError::Hash(self.hash.clone().unwrap_or_default(), err))?
+            .unwrap_or_default();
+        Ok(File {
+            dist_info_metadata: None,
+            filename: self.filename.clone(),
+            hashes,
+            requires_python: None,
+            size: None,
+            upload_time_utc_ms: None,
+            url: location,
+            yanked: None,
+        })
+    }
+
+    /// Create a [`LockedFile`] from a registry [`File`].
+    fn from_file(file: &File) -> Result<Self, LockError> {
+        let url = match &file.url {
+            FileLocation::RelativeUrl(base, url) => {
+                pypi_types::base_url_join_relative(base, url)?.to_string()
+            }
+            FileLocation::AbsoluteUrl(url) => url.clone(),
+            FileLocation::Path(path) => Url::from_file_path(path).map_or_else(
+                |()| path.to_string_lossy().to_string(),
+                |url| url.to_string(),
+            ),
+        };
+        Ok(Self {
+            filename: file.filename.clone(),
+            url,
+            hash: file.hashes.to_string(),
+        })
+    }
+}
+
+/// Return the version, source, and file (for registry distributions) to lock for a [`Dist`].
+fn locked(dist: &Dist) -> Result<(Option<Version>, Source, Option<LockedFile>), LockError> {
+    let source = |kind, url: &VerbatimUrl| Source {
+        kind,
+        url: url.raw().to_string(),
+    };
+    Ok(match dist {
+        Dist::Built(BuiltDist::Registry(wheel)) => (
+            Some(wheel.filename.version.clone()),
+            Source {
+                kind: SourceKind::Registry,
+                url: wheel.index.url().to_string(),
+            },
+            Some(LockedFile::from_file(&wheel.file)?),
+        ),
+        Dist::Source(SourceDist::Registry(sdist)) => (
+            Some(sdist.filename.version.clone()),
+            Source {
+                kind: SourceKind::Registry,
+                url: sdist.index.url().to_string(),
+            },
+            Some(LockedFile::from_file(&sdist.file)?),
+        ),
+        Dist::Built(BuiltDist::DirectUrl(wheel)) => (
+            Some(wheel.filename.version.clone()),
+            source(SourceKind::Direct, &wheel.url),
+            None,
+        ),
+        Dist::Built(BuiltDist::Path(wheel)) => (
+            Some(wheel.filename.version.clone()),
+            source(SourceKind::Path, &wheel.url),
+            None,
+        ),
+        Dist::Source(SourceDist::DirectUrl(sdist)) => {
+            (None, source(SourceKind::Direct, &sdist.url), None)
+        }
+        Dist::Source(SourceDist::Git(sdist)) => (None, source(SourceKind::Git, &sdist.url), None),
+        Dist::Source(SourceDist::Path(sdist)) => {
+            let kind = if sdist.editable {
+                SourceKind::Editable
+            } else {
+                SourceKind::Path
+            };
+            (None, source(kind, &sdist.url), None)
+        }
+    })
+}
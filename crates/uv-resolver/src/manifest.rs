@@ -2,7 +2,7 @@ use distribution_types::LocalEditable;
 use pep508_rs::{MarkerEnvironment, Requirement};
 use pypi_types::Metadata23;
 use uv_normalize::PackageName;
-use uv_types::{Constraints, Overrides, RequestedRequirements};
+use uv_types::{Constraints, Overrides, RequestedRequirements, RequiredHashes};
 
 use crate::{preferences::Preference, Exclusions};
 
@@ -46,6 +46,12 @@ pub struct Manifest {
     /// determinations around "allowed" versions (for example, "allowed" URLs or "allowed"
     /// pre-release versions).
     pub(crate) lookaheads: Vec<RequestedRequirements>,
+
+    /// The hashes that the distributions of each package must match, if any.
+    ///
+    /// These represent the hashes provided alongside pinned requirements or constraints (e.g.,
+    /// via `--hash`), such that only the identified distributions are selected.
+    pub(crate) required_hashes: RequiredHashes,
 }
 
 impl Manifest {
@@ -69,6 +75,7 @@ impl Manifest {
             editables,
             exclusions,
             lookaheads,
+            required_hashes: RequiredHashes::default(),
         }
     }
 
@@ -82,6 +89,43 @@ impl Manifest {
             editables: Vec::new(),
             exclusions: Exclusions::default(),
             lookaheads: Vec::new(),
+            required_hashes: RequiredHashes::default(),
+        }
+    }
+
+    /// Set the hashes that the distributions of each package must match.
+    #[must_use]
+    pub fn with_required_hashes(self, required_hashes: RequiredHashes) -> Self {
+        Self {
+            required_hashes,
+            ..self
+        }
+    }
+
+    /// Return the direct requirements of the manifest.
+    pub fn direct_requirements(&self) -> &[Requirement] {
+        &self.requirements
+    }
+
+    /// Return the constraints of the manifest.
+    pub fn constraints(&self) -> &Constraints {
+        &self.constraints
+    }
+
+    /// Return a copy of the manifest that retains only the given direct requirements and the
+    /// constraints that satisfy the given predicate.
+    ///
+    /// Used to narrow down the inputs that participate in a conflict.
+    #[must_use]
+    pub fn with_inputs(
+        &self,
+        requirements: Vec<Requirement>,
+        constraints: impl Fn(&Requirement) -> bool,
+    ) -> Self {
+        Self {
+            requirements,
+            constraints: self.constraints.filter(constraints),
+            ..self.clone()
         }
     }
 
@@ -1,6 +1,9 @@
+use std::time::Duration;
+
 use chrono::{DateTime, Utc};
+use uv_types::IndexStrategy;
 
-use crate::{DependencyMode, PreReleaseMode, ResolutionMode};
+use crate::{DependencyMode, PreReleaseMode, ResolutionMode, YankedMode};
 
 /// Options for resolving a manifest.
 #[derive(Debug, Default, Copy, Clone)]
@@ -8,7 +11,68 @@ pub struct Options {
     pub resolution_mode: ResolutionMode,
     pub prerelease_mode: PreReleaseMode,
     pub dependency_mode: DependencyMode,
+    pub yanked_mode: YankedMode,
     pub exclude_newer: Option<DateTime<Utc>>,
+    pub index_strategy: IndexStrategy,
+    pub require_hashes: bool,
+    pub allow_url_override: bool,
+    pub max_tried_versions: Option<usize>,
+    pub max_candidates: Option<usize>,
+    pub timeout: Option<Duration>,
+    pub prefetch: PrefetchOptions,
+}
+
+/// Options for speculatively prefetching metadata for packages that require many versions to be
+/// tried during resolution.
+///
+/// Once `start` versions of a package have been tried, prefetching is triggered each time the
+/// number of tried versions doubles, until it reaches `interval`, after which prefetching is
+/// triggered every `interval` tried versions.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PrefetchOptions {
+    /// Whether to prefetch at all.
+    pub enabled: bool,
+    /// The number of tried versions after which to start prefetching.
+    pub start: usize,
+    /// The number of tried versions between prefetches, once prefetching is underway.
+    pub interval: usize,
+    /// The maximum number of versions to prefetch in a single batch.
+    pub max_batch: usize,
+    /// The maximum number of prefetch requests to have in flight at once.
+    pub max_concurrent: usize,
+}
+
+impl Default for PrefetchOptions {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            start: 5,
+            interval: 20,
+            max_batch: 50,
+            max_concurrent: 50,
+        }
+    }
+}
+
+impl PrefetchOptions {
+    /// Override the default batch size and concurrency limit, if provided. A limit of `0` disables
+    /// prefetching entirely.
+    pub fn from_args(max_batch: Option<usize>, max_concurrent: Option<usize>) -> Self {
+        let default = Self::default();
+        Self {
+            max_batch: max_batch.unwrap_or(default.max_batch),
+            max_concurrent: max_concurrent.unwrap_or(default.max_concurrent),
+            ..default
+        }
+    }
+
+    /// Disable prefetching entirely.
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            ..Self::default()
+        }
+    }
 }
 
 /// Builder for [`Options`].
@@ -17,7 +81,15 @@ pub struct OptionsBuilder {
     resolution_mode: ResolutionMode,
     prerelease_mode: PreReleaseMode,
     dependency_mode: DependencyMode,
+    yanked_mode: YankedMode,
     exclude_newer: Option<DateTime<Utc>>,
+    index_strategy: IndexStrategy,
+    require_hashes: bool,
+    allow_url_override: bool,
+    max_tried_versions: Option<usize>,
+    max_candidates: Option<usize>,
+    timeout: Option<Duration>,
+    prefetch: PrefetchOptions,
 }
 
 impl OptionsBuilder {
@@ -47,6 +119,13 @@ impl OptionsBuilder {
         self
     }
 
+    /// Sets the [`YankedMode`].
+    #[must_use]
+    pub fn yanked_mode(mut self, yanked_mode: YankedMode) -> Self {
+        self.yanked_mode = yanked_mode;
+        self
+    }
+
     /// Sets the exclusion date.
     #[must_use]
     pub fn exclude_newer(mut self, exclude_newer: Option<DateTime<Utc>>) -> Self {
@@ -54,13 +133,74 @@ impl OptionsBuilder {
         self
     }
 
+    /// Sets the [`IndexStrategy`], which determines how versions found across multiple indexes
+    /// are considered.
+    #[must_use]
+    pub fn index_strategy(mut self, index_strategy: IndexStrategy) -> Self {
+        self.index_strategy = index_strategy;
+        self
+    }
+
+    /// Sets whether to reject any distribution that lacks a hash on the index.
+    #[must_use]
+    pub fn require_hashes(mut self, require_hashes: bool) -> Self {
+        self.require_hashes = require_hashes;
+        self
+    }
+
+    /// Sets whether a URL requirement takes precedence over any version specifiers for the same
+    /// package.
+    #[must_use]
+    pub fn allow_url_override(mut self, allow_url_override: bool) -> Self {
+        self.allow_url_override = allow_url_override;
+        self
+    }
+
+    /// Sets the maximum number of versions the resolver may try, across all packages, before
+    /// giving up.
+    #[must_use]
+    pub fn max_tried_versions(mut self, max_tried_versions: Option<usize>) -> Self {
+        self.max_tried_versions = max_tried_versions;
+        self
+    }
+
+    /// Sets the maximum number of versions the resolver may try for any single package before
+    /// giving up.
+    #[must_use]
+    pub fn max_candidates(mut self, max_candidates: Option<usize>) -> Self {
+        self.max_candidates = max_candidates;
+        self
+    }
+
+    /// Sets the maximum wall-clock time the resolver may spend solving before giving up.
+    #[must_use]
+    pub fn timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the [`PrefetchOptions`].
+    #[must_use]
+    pub fn prefetch(mut self, prefetch: PrefetchOptions) -> Self {
+        self.prefetch = prefetch;
+        self
+    }
+
     /// Builds the options.
     pub fn build(self) -> Options {
         Options {
             resolution_mode: self.resolution_mode,
             prerelease_mode: self.prerelease_mode,
             dependency_mode: self.dependency_mode,
+            yanked_mode: self.yanked_mode,
             exclude_newer: self.exclude_newer,
+            index_strategy: self.index_strategy,
+            require_hashes: self.require_hashes,
+            allow_url_override: self.allow_url_override,
+            max_tried_versions: self.max_tried_versions,
+            max_candidates: self.max_candidates,
+            timeout: self.timeout,
+            prefetch: self.prefetch,
         }
     }
 }
@@ -53,6 +53,13 @@ impl Preference {
         }
     }
 
+    /// Set the hashes for this preference.
+    #[must_use]
+    pub fn with_hashes(mut self, hashes: Vec<Hashes>) -> Self {
+        self.hashes = hashes;
+        self
+    }
+
     /// Return the name of the package for this preference.
     pub fn name(&self) -> &PackageName {
         &self.requirement.name
@@ -1,6 +1,6 @@
 use itertools::Itertools;
 use pubgrub::range::Range;
-use tracing::warn;
+use tracing::{debug, warn};
 
 use distribution_types::Verbatim;
 use pep440_rs::Version;
@@ -140,6 +140,19 @@ fn to_pubgrub(
 
         // The requirement has a specifier (e.g., `flask>=1.0`).
         Some(VersionOrUrl::VersionSpecifier(specifiers)) => {
+            // If the package is pinned to a URL, and the URL is allowed to take precedence, ignore
+            // the specifiers.
+            if urls.overrides_specifiers(&requirement.name) {
+                debug!(
+                    "Ignoring `{requirement}` in favor of the URL for `{}`",
+                    requirement.name
+                );
+                return Ok((
+                    PubGrubPackage::from_package(requirement.name.clone(), extra, urls),
+                    Range::full(),
+                ));
+            }
+
             // If the specifier is an exact version, and the user requested a local version that's
             // more precise than the specifier, use the local version instead.
             let version = if let Some(expected) = locals.get(&requirement.name) {
@@ -4,7 +4,7 @@ use std::collections::BTreeSet;
 use std::ops::Bound;
 
 use derivative::Derivative;
-use distribution_types::IndexLocations;
+use distribution_types::{IndexLocations, Verbatim};
 use indexmap::{IndexMap, IndexSet};
 use owo_colors::OwoColorize;
 use pep440_rs::Version;
@@ -12,7 +12,7 @@ use pubgrub::range::Range;
 use pubgrub::report::{DerivationTree, Derived, External, ReportFormatter};
 use pubgrub::term::Term;
 use pubgrub::type_aliases::Map;
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 use uv_normalize::PackageName;
 
 use crate::candidate_selector::CandidateSelector;
@@ -26,6 +26,10 @@ pub(crate) struct PubGrubReportFormatter<'a> {
     /// The versions that were available for each package
     pub(crate) available_versions: &'a IndexMap<PubGrubPackage, BTreeSet<Version>>,
 
+    /// The versions that were excluded for each package, as they were published after the
+    /// `--exclude-newer` cutoff
+    pub(crate) excluded_newer_versions: &'a IndexMap<PubGrubPackage, BTreeSet<Version>>,
+
     /// The versions that were available for each package
     pub(crate) python_requirement: Option<&'a PythonRequirement>,
 }
@@ -332,6 +336,70 @@ impl PubGrubReportFormatter<'_> {
         }
     }
 
+    /// Render a derivation tree as a nested explanation, in which each derived incompatibility is
+    /// followed by the incompatibilities it was derived from.
+    ///
+    /// Unlike the default report, which linearizes the tree into a chain of "Because ..."
+    /// sentences, this preserves the structure of the tree, which is easier to follow for deep
+    /// conflicts.
+    pub(crate) fn explain(
+        &self,
+        derivation_tree: &DerivationTree<PubGrubPackage, Range<Version>>,
+    ) -> String {
+        let mut lines = Vec::new();
+        self.explain_node(derivation_tree, 0, &mut FxHashSet::default(), &mut lines);
+        lines.join("\n")
+    }
+
+    fn explain_node(
+        &self,
+        derivation_tree: &DerivationTree<PubGrubPackage, Range<Version>>,
+        depth: usize,
+        explained: &mut FxHashSet<usize>,
+        lines: &mut Vec<String>,
+    ) {
+        let (prefix, continuation) = if depth == 0 {
+            (String::new(), String::new())
+        } else {
+            let indent = "  ".repeat(depth - 1);
+            (format!("{indent}- "), format!("{indent}  "))
+        };
+        let indent = |line: String| line.replace('\n', &format!("\n{continuation}"));
+
+        match derivation_tree {
+            DerivationTree::External(external) => {
+                lines.push(format!(
+                    "{prefix}{}",
+                    indent(self.format_external(external))
+                ));
+            }
+            DerivationTree::Derived(derived) => {
+                let terms = indent(self.format_terms(&derived.terms));
+
+                // Avoid repeating the explanation for incompatibilities that are shared across
+                // multiple branches of the tree.
+                if let Some(id) = derived.shared_id {
+                    if !explained.insert(id) {
+                        lines.push(format!("{prefix}{terms} (as explained above)"));
+                        return;
+                    }
+                }
+
+                lines.push(format!("{prefix}{terms}, because:"));
+
+                // Explain derived causes first, mirroring the default report.
+                let (first, second) = match (&*derived.cause1, &*derived.cause2) {
+                    (DerivationTree::External(_), DerivationTree::Derived(_)) => {
+                        (&derived.cause2, &derived.cause1)
+                    }
+                    _ => (&derived.cause1, &derived.cause2),
+                };
+                self.explain_node(first, depth + 1, explained, lines);
+                self.explain_node(second, depth + 1, explained, lines);
+            }
+        }
+    }
+
     /// Generate the [`PubGrubHints`] for a derivation tree.
     ///
     /// The [`PubGrubHints`] help users resolve errors by providing additional context or modifying
@@ -342,6 +410,7 @@ impl PubGrubReportFormatter<'_> {
         selector: &Option<CandidateSelector>,
         index_locations: &Option<IndexLocations>,
         unavailable_packages: &FxHashMap<PackageName, UnavailablePackage>,
+        constraint_sources: &FxHashMap<PackageName, Vec<SourcedConstraint>>,
     ) -> IndexSet<PubGrubHint> {
         /// Returns `true` if pre-releases were allowed for a package.
         fn allowed_prerelease(package: &PubGrubPackage, selector: &CandidateSelector) -> bool {
@@ -417,10 +486,47 @@ impl PubGrubReportFormatter<'_> {
                             }
                         }
                     }
+
+                    // Check for versions in the requested range that were excluded by
+                    // `--exclude-newer`
+                    if let Some(version) = self
+                        .excluded_newer_versions
+                        .get(package)
+                        .and_then(|versions| versions.iter().find(|version| set.contains(version)))
+                    {
+                        hints.insert(PubGrubHint::ExcludeNewer {
+                            package: package.clone(),
+                            version: version.clone(),
+                        });
+                    }
                 }
                 External::NotRoot(..) => {}
                 External::Unavailable(..) => {}
-                External::FromDependencyOf(..) => {}
+                External::FromDependencyOf(_, _, dependency, dependency_set) => {
+                    // Check for version specifiers on a package that's pinned to a URL
+                    if let PubGrubPackage::Package(name, _, Some(url)) = dependency {
+                        if dependency_set != &Range::full() {
+                            hints.insert(PubGrubHint::UrlConflict {
+                                name: name.clone(),
+                                url: url.verbatim().to_string(),
+                                requirement: self.format_external(external),
+                            });
+                        }
+                    }
+
+                    // Check for a range that was narrowed by a constraints file
+                    if let PubGrubPackage::Package(name, ..) = dependency {
+                        for constraint in constraint_sources.get(name).into_iter().flatten() {
+                            if &constraint.range == dependency_set {
+                                hints.insert(PubGrubHint::Constrained {
+                                    name: name.clone(),
+                                    constraint: constraint.constraint.clone(),
+                                    source: constraint.source.clone(),
+                                });
+                            }
+                        }
+                    }
+                }
             },
             DerivationTree::Derived(derived) => {
                 hints.extend(self.hints(
@@ -428,12 +534,14 @@ impl PubGrubReportFormatter<'_> {
                     selector,
                     index_locations,
                     unavailable_packages,
+                    constraint_sources,
                 ));
                 hints.extend(self.hints(
                     &derived.cause2,
                     selector,
                     index_locations,
                     unavailable_packages,
+                    constraint_sources,
                 ));
             }
         }
@@ -441,6 +549,17 @@ impl PubGrubReportFormatter<'_> {
     }
 }
 
+/// A constraint declared in a constraints file, retained to explain resolution failures.
+#[derive(Debug, Clone)]
+pub(crate) struct SourcedConstraint {
+    /// The range of versions allowed by the constraint.
+    pub(crate) range: Range<Version>,
+    /// The constraint, as declared (e.g., `idna<3.4`).
+    pub(crate) constraint: String,
+    /// The location at which the constraint was declared (e.g., `constraints.txt:3`).
+    pub(crate) source: String,
+}
+
 #[derive(Derivative, Debug, Clone)]
 #[derivative(Hash, PartialEq, Eq)]
 pub(crate) enum PubGrubHint {
@@ -464,6 +583,28 @@ pub(crate) enum PubGrubHint {
     NoIndex,
     /// A package was not found in the registry, but
     Offline,
+    /// A package was pinned to a URL, but another requirement included version specifiers for the
+    /// same package that the URL doesn't satisfy.
+    UrlConflict {
+        name: PackageName,
+        #[derivative(PartialEq = "ignore", Hash = "ignore")]
+        url: String,
+        #[derivative(PartialEq = "ignore", Hash = "ignore")]
+        requirement: String,
+    },
+    /// Versions of a package in the requested range exist, but were published after the
+    /// `--exclude-newer` cutoff.
+    ExcludeNewer {
+        package: PubGrubPackage,
+        #[derivative(PartialEq = "ignore", Hash = "ignore")]
+        version: Version,
+    },
+    /// A package's versions were narrowed by a constraint declared in a constraints file.
+    Constrained {
+        name: PackageName,
+        constraint: String,
+        source: String,
+    },
 }
 
 impl std::fmt::Display for PubGrubHint {
@@ -505,6 +646,46 @@ impl std::fmt::Display for PubGrubHint {
                     ":".bold(),
                 )
             }
+            Self::UrlConflict {
+                name,
+                url,
+                requirement,
+            } => {
+                write!(
+                    f,
+                    "{}{} {} was requested from a URL ({}), but {}, which the URL doesn't satisfy (try: `--allow-url-override` to ignore version specifiers for packages requested from a URL)",
+                    "hint".bold().cyan(),
+                    ":".bold(),
+                    name.bold(),
+                    url.cyan(),
+                    requirement,
+                )
+            }
+            Self::ExcludeNewer { package, version } => {
+                write!(
+                    f,
+                    "{}{} {} has versions in the requested range (e.g., {}), but they were excluded because they were published after the `--exclude-newer` cutoff",
+                    "hint".bold().cyan(),
+                    ":".bold(),
+                    package.bold(),
+                    version.bold()
+                )
+            }
+            Self::Constrained {
+                name,
+                constraint,
+                source,
+            } => {
+                write!(
+                    f,
+                    "{}{} {} was constrained by `{}`, declared in `{}`",
+                    "hint".bold().cyan(),
+                    ":".bold(),
+                    name.bold(),
+                    constraint.bold(),
+                    source.cyan(),
+                )
+            }
         }
     }
 }
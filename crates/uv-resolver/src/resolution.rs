@@ -16,16 +16,16 @@ use distribution_types::{
     VersionOrUrl,
 };
 use once_map::OnceMap;
-use pep440_rs::Version;
-use pep508_rs::MarkerEnvironment;
-use pypi_types::Hashes;
+use pep440_rs::{Operator, Version, VersionSpecifier};
+use pep508_rs::{MarkerEnvironment, MarkerTree, Requirement};
+use pypi_types::{Hashes, Yanked};
 use uv_distribution::to_precise;
 use uv_normalize::{ExtraName, PackageName};
 
 use crate::dependency_provider::UvDependencyProvider;
 use crate::editables::Editables;
 use crate::pins::FilePins;
-use crate::preferences::Preferences;
+use crate::preferences::{Preference, Preferences};
 use crate::pubgrub::{PubGrubDistribution, PubGrubPackage};
 use crate::redirect::apply_redirect;
 use crate::resolver::{InMemoryIndex, MetadataResponse, VersionsResponse};
@@ -110,6 +110,27 @@ impl ResolutionGraph {
                         }
                     }
 
+                    // Warn if the distribution was yanked (e.g., if it was pinned explicitly).
+                    if let ResolvedDist::Installable(dist) = &pinned_package {
+                        if let Some(yanked) = dist.file().and_then(|file| file.yanked.as_ref()) {
+                            match yanked {
+                                Yanked::Bool(false) => {}
+                                Yanked::Bool(true) => {
+                                    diagnostics.push(Diagnostic::YankedVersion {
+                                        dist: pinned_package.clone(),
+                                        reason: None,
+                                    });
+                                }
+                                Yanked::Reason(reason) => {
+                                    diagnostics.push(Diagnostic::YankedVersion {
+                                        dist: pinned_package.clone(),
+                                        reason: Some(reason.clone()),
+                                    });
+                                }
+                            }
+                        }
+                    }
+
                     // Add the distribution to the graph.
                     let index = petgraph.add_node(pinned_package);
                     inverse.insert(package_name, index);
@@ -325,6 +346,41 @@ impl ResolutionGraph {
             .any(|index| self.petgraph[index].name() == name)
     }
 
+    /// Return the pinned versions of the registry-based packages in this resolution, such that
+    /// they can be preferred by a subsequent resolution.
+    pub fn preferences(&self) -> Vec<Preference> {
+        self.preferences_for(None)
+    }
+
+    /// Return the pinned versions of the registry-based packages in this resolution, restricted
+    /// to the environments matching the given marker (e.g., that of a universal resolution's
+    /// fork).
+    pub(crate) fn preferences_for(&self, marker: Option<&MarkerTree>) -> Vec<Preference> {
+        self.petgraph
+            .node_indices()
+            .filter_map(|index| {
+                let dist = &self.petgraph[index];
+                let VersionOrUrl::Version(version) = dist.version_or_url() else {
+                    return None;
+                };
+                let requirement = Requirement {
+                    name: dist.name().clone(),
+                    extras: vec![],
+                    version_or_url: Some(pep508_rs::VersionOrUrl::VersionSpecifier(
+                        [
+                            VersionSpecifier::from_version(Operator::Equal, version.clone())
+                                .expect("Equal operator to be valid for any version"),
+                        ]
+                        .into_iter()
+                        .collect(),
+                    )),
+                    marker: marker.cloned(),
+                };
+                Some(Preference::from_requirement(requirement))
+            })
+            .collect()
+    }
+
     /// Iterate over the [`ResolvedDist`] entities in this resolution.
     pub fn into_distributions(self) -> impl Iterator<Item = ResolvedDist> {
         self.petgraph
@@ -339,6 +395,24 @@ impl ResolutionGraph {
         &self.diagnostics
     }
 
+    /// Return the hashes for the given package, if any.
+    pub(crate) fn hashes(&self, name: &PackageName) -> &[Hashes] {
+        self.hashes.get(name).map_or(&[], Vec::as_slice)
+    }
+
+    /// Return the display [`Node`] for the distribution at the given index.
+    pub(crate) fn node(&self, index: petgraph::graph::NodeIndex, include_extras: bool) -> Node<'_> {
+        let dist = &self.petgraph[index];
+        let name = dist.name();
+        if let Some((editable, _)) = self.editables.get(name) {
+            Node::Editable(name, editable)
+        } else if include_extras {
+            Node::Distribution(name, dist, self.extras.get(name).map_or(&[], Vec::as_slice))
+        } else {
+            Node::Distribution(name, dist, &[])
+        }
+    }
+
     /// Return the underlying graph.
     pub fn petgraph(
         &self,
@@ -545,7 +619,7 @@ impl<'a> DisplayResolutionGraph<'a> {
 }
 
 #[derive(Debug)]
-enum Node<'a> {
+pub(crate) enum Node<'a> {
     /// A node linked to an editable distribution.
     Editable(&'a PackageName, &'a LocalEditable),
     /// A node linked to a non-editable distribution.
@@ -553,7 +627,7 @@ enum Node<'a> {
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
-enum NodeKey<'a> {
+pub(crate) enum NodeKey<'a> {
     /// A node linked to an editable distribution, sorted by verbatim representation.
     Editable(Cow<'a, str>),
     /// A node linked to a non-editable distribution, sorted by package name.
@@ -562,7 +636,7 @@ enum NodeKey<'a> {
 
 impl<'a> Node<'a> {
     /// Return the name of the package.
-    fn name(&self) -> &'a PackageName {
+    pub(crate) fn name(&self) -> &'a PackageName {
         match self {
             Node::Editable(name, _) => name,
             Node::Distribution(name, _, _) => name,
@@ -570,7 +644,7 @@ impl<'a> Node<'a> {
     }
 
     /// Return a comparable key for the node.
-    fn key(&self) -> NodeKey<'a> {
+    pub(crate) fn key(&self) -> NodeKey<'a> {
         match self {
             Node::Editable(_, editable) => NodeKey::Editable(editable.verbatim()),
             Node::Distribution(name, _, _) => NodeKey::Distribution(name),
@@ -607,26 +681,10 @@ impl std::fmt::Display for DisplayResolutionGraph<'_> {
             .petgraph
             .node_indices()
             .filter_map(|index| {
-                let dist = &self.resolution.petgraph[index];
-                let name = dist.name();
-                if self.no_emit_packages.contains(name) {
+                let node = self.resolution.node(index, self.include_extras);
+                if self.no_emit_packages.contains(node.name()) {
                     return None;
                 }
-
-                let node = if let Some((editable, _)) = self.resolution.editables.get(name) {
-                    Node::Editable(name, editable)
-                } else if self.include_extras {
-                    Node::Distribution(
-                        name,
-                        dist,
-                        self.resolution
-                            .extras
-                            .get(name)
-                            .map_or(&[], |extras| extras.as_slice()),
-                    )
-                } else {
-                    Node::Distribution(name, dist, &[])
-                };
                 Some((index, node))
             })
             .collect::<Vec<_>>();
@@ -642,84 +700,99 @@ impl std::fmt::Display for DisplayResolutionGraph<'_> {
             // Display the distribution hashes, if any.
             let mut has_hashes = false;
             if self.show_hashes {
-                if let Some(hashes) = self
-                    .resolution
-                    .hashes
-                    .get(node.name())
-                    .filter(|hashes| !hashes.is_empty())
-                {
-                    for hash in hashes {
-                        if let Some(hash) = hash.to_string() {
-                            has_hashes = true;
-                            line.push_str(" \\\n");
-                            line.push_str("    --hash=");
-                            line.push_str(&hash);
-                        }
+                for hash in self.resolution.hashes(node.name()) {
+                    if let Some(hash) = hash.to_string() {
+                        has_hashes = true;
+                        line.push_str(" \\\n");
+                        line.push_str("    --hash=");
+                        line.push_str(&hash);
                     }
                 }
             }
 
-            // Determine the annotation comment and separator (between comment and requirement).
-            let mut annotation = None;
-
-            if self.include_annotations {
-                // Display all dependencies.
-                let mut edges = self
-                    .resolution
-                    .petgraph
-                    .edges_directed(index, Direction::Incoming)
-                    .map(|edge| &self.resolution.petgraph[edge.source()])
-                    .collect::<Vec<_>>();
-                edges.sort_unstable_by_key(|package| package.name());
-
-                match self.annotation_style {
-                    AnnotationStyle::Line => {
-                        if !edges.is_empty() {
-                            let separator = if has_hashes { "\n    " } else { "  " };
-                            let deps = edges
-                                .into_iter()
-                                .map(|dependency| dependency.name().to_string())
-                                .collect::<Vec<_>>()
-                                .join(", ");
-                            let comment = format!("# via {deps}").green().to_string();
-                            annotation = Some((separator, comment));
-                        }
-                    }
-                    AnnotationStyle::Split => match edges.as_slice() {
-                        [] => {}
-                        [edge] => {
-                            let separator = "\n";
-                            let comment = format!("    # via {}", edge.name()).green().to_string();
-                            annotation = Some((separator, comment));
-                        }
-                        edges => {
-                            let separator = "\n";
-                            let deps = edges
-                                .iter()
-                                .map(|dependency| format!("    #   {}", dependency.name()))
-                                .collect::<Vec<_>>()
-                                .join("\n");
-                            let comment = format!("    # via\n{deps}").green().to_string();
-                            annotation = Some((separator, comment));
-                        }
-                    },
-                }
-            }
+            // Display all dependencies.
+            let mut edges = self
+                .resolution
+                .petgraph
+                .edges_directed(index, Direction::Incoming)
+                .map(|edge| self.resolution.petgraph[edge.source()].name())
+                .collect::<Vec<_>>();
+            edges.sort_unstable();
+
+            write_requirement(
+                f,
+                &line,
+                has_hashes,
+                &edges,
+                self.include_annotations,
+                self.annotation_style,
+            )?;
+        }
 
-            if let Some((separator, comment)) = annotation {
-                // Assemble the line with the annotations and remove trailing whitespaces.
-                for line in format!("{line:24}{separator}{comment}").lines() {
-                    let line = line.trim_end();
-                    writeln!(f, "{line}")?;
+        Ok(())
+    }
+}
+
+/// Write a single requirement to the output, along with an annotation comment indicating the
+/// dependencies that requested it (if enabled).
+pub(crate) fn write_requirement(
+    f: &mut std::fmt::Formatter<'_>,
+    line: &str,
+    has_hashes: bool,
+    edges: &[&PackageName],
+    include_annotations: bool,
+    annotation_style: AnnotationStyle,
+) -> std::fmt::Result {
+    // Determine the annotation comment and separator (between comment and requirement).
+    let mut annotation = None;
+
+    if include_annotations {
+        match annotation_style {
+            AnnotationStyle::Line => {
+                if !edges.is_empty() {
+                    let separator = if has_hashes { "\n    " } else { "  " };
+                    let deps = edges
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    let comment = format!("# via {deps}").green().to_string();
+                    annotation = Some((separator, comment));
                 }
-            } else {
-                // Write the line as is.
-                writeln!(f, "{line}")?;
             }
+            AnnotationStyle::Split => match edges {
+                [] => {}
+                [edge] => {
+                    let separator = "\n";
+                    let comment = format!("    # via {edge}").green().to_string();
+                    annotation = Some((separator, comment));
+                }
+                edges => {
+                    let separator = "\n";
+                    let deps = edges
+                        .iter()
+                        .map(|dependency| format!("    #   {dependency}"))
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    let comment = format!("    # via\n{deps}").green().to_string();
+                    annotation = Some((separator, comment));
+                }
+            },
         }
+    }
 
-        Ok(())
+    if let Some((separator, comment)) = annotation {
+        // Assemble the line with the annotations and remove trailing whitespaces.
+        for line in format!("{line:24}{separator}{comment}").lines() {
+            let line = line.trim_end();
+            writeln!(f, "{line}")?;
+        }
+    } else {
+        // Write the line as is.
+        writeln!(f, "{line}")?;
     }
+
+    Ok(())
 }
 
 impl From<ResolutionGraph> for distribution_types::Resolution {
@@ -748,6 +821,13 @@ pub enum Diagnostic {
         /// The extra that was requested. For example, `colorama` in `black[colorama]`.
         extra: ExtraName,
     },
+    YankedVersion {
+        /// The distribution that was selected, despite being yanked. For example,
+        /// `colorama==0.4.2`.
+        dist: ResolvedDist,
+        /// The reason the distribution was yanked, if any.
+        reason: Option<String>,
+    },
 }
 
 impl Diagnostic {
@@ -757,13 +837,19 @@ impl Diagnostic {
             Self::MissingExtra { dist, extra } => {
                 format!("The package `{dist}` does not have an extra named `{extra}`.")
             }
+            Self::YankedVersion { dist, reason } => match reason {
+                Some(reason) => format!("{dist} is yanked (reason: \"{reason}\")."),
+                None => format!("{dist} is yanked."),
+            },
         }
     }
 
     /// Returns `true` if the [`PackageName`] is involved in this diagnostic.
     pub fn includes(&self, name: &PackageName) -> bool {
         match self {
-            Self::MissingExtra { dist, .. } => name == dist.name(),
+            Self::MissingExtra { dist, .. } | Self::YankedVersion { dist, .. } => {
+                name == dist.name()
+            }
         }
     }
 }
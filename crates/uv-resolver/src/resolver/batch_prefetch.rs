@@ -11,7 +11,7 @@ use pep440_rs::Version;
 use crate::candidate_selector::{CandidateDist, CandidateSelector};
 use crate::pubgrub::PubGrubPackage;
 use crate::resolver::Request;
-use crate::{InMemoryIndex, ResolveError, VersionsResponse};
+use crate::{InMemoryIndex, PrefetchOptions, ResolveError, VersionsResponse};
 
 enum BatchPrefetchStrategy {
     /// Go through the next versions assuming the existing selection and its constraints
@@ -37,12 +37,23 @@ enum BatchPrefetchStrategy {
 /// Note that these all heuristics that could totally prefetch lots of irrelevant versions.
 #[derive(Default)]
 pub(crate) struct BatchPrefetcher {
+    options: PrefetchOptions,
     tried_versions: FxHashMap<PubGrubPackage, usize>,
     last_prefetch: FxHashMap<PubGrubPackage, usize>,
 }
 
 impl BatchPrefetcher {
+    /// Create a new [`BatchPrefetcher`] with the given [`PrefetchOptions`].
+    pub(crate) fn new(options: PrefetchOptions) -> Self {
+        Self {
+            options,
+            ..Self::default()
+        }
+    }
+
     /// Prefetch a large number of versions if we already unsuccessfully tried many versions.
+    ///
+    /// Returns the number of versions for which a prefetch was issued.
     pub(crate) async fn prefetch_batches(
         &mut self,
         next: &PubGrubPackage,
@@ -51,16 +62,16 @@ impl BatchPrefetcher {
         request_sink: &Sender<Request>,
         index: &InMemoryIndex,
         selector: &CandidateSelector,
-    ) -> anyhow::Result<(), ResolveError> {
+    ) -> anyhow::Result<usize, ResolveError> {
         let PubGrubPackage::Package(package_name, _, _) = &next else {
-            return Ok(());
+            return Ok(0);
         };
 
         let (num_tried, do_prefetch) = self.should_prefetch(next);
         if !do_prefetch {
-            return Ok(());
+            return Ok(0);
         }
-        let total_prefetch = min(num_tried, 50);
+        let total_prefetch = min(num_tried, self.options.max_batch);
 
         // This is immediate, we already fetched the version map.
         let versions_response = index
@@ -70,7 +81,7 @@ impl BatchPrefetcher {
             .ok_or(ResolveError::Unregistered)?;
 
         let VersionsResponse::Found(ref version_map) = *versions_response else {
-            return Ok(());
+            return Ok(0);
         };
 
         let mut phase = BatchPrefetchStrategy::Compatible {
@@ -125,7 +136,8 @@ impl BatchPrefetcher {
             let CandidateDist::Compatible(dist) = candidate.dist() else {
                 continue;
             };
-            // Avoid building a lot of source distributions.
+            // Avoid building a lot of source distributions, unless their metadata is served by the
+            // index directly.
             if !dist.prefetchable() {
                 continue;
             }
@@ -143,7 +155,7 @@ impl BatchPrefetcher {
             prefetch_count += 1;
             if index.distributions.register(candidate.package_id()) {
                 let request = match dist {
-                    ResolvedDistRef::Installable(dist) => Request::Dist(dist.clone()),
+                    ResolvedDistRef::Installable(dist) => Request::PrefetchDist(dist.clone()),
                     ResolvedDistRef::Installed(dist) => Request::Installed(dist.clone()),
                 };
                 request_sink.send(request).await?;
@@ -153,7 +165,7 @@ impl BatchPrefetcher {
         debug!("Prefetching {prefetch_count} {package_name} versions");
 
         self.last_prefetch.insert(next.clone(), num_tried);
-        Ok(())
+        Ok(prefetch_count)
     }
 
     /// Each time we tried a version for a package, we register that here.
@@ -161,16 +173,122 @@ impl BatchPrefetcher {
         *self.tried_versions.entry(package).or_default() += 1;
     }
 
-    /// After 5, 10, 20, 40 tried versions, prefetch that many versions to start early but not
-    /// too aggressive. Later we schedule the prefetch of 50 versions every 20 versions, this gives
-    /// us a good buffer until we see prefetch again and is high enough to saturate the task pool.
+    /// Return the number of versions tried for the given package.
+    pub(crate) fn tried(&self, package: &PubGrubPackage) -> usize {
+        self.tried_versions
+            .get(package)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Return the total number of versions tried, across all packages.
+    pub(crate) fn total_tried(&self) -> usize {
+        self.tried_versions
+            .iter()
+            .filter(|(package, _)| matches!(package, PubGrubPackage::Package(..)))
+            .map(|(_, count)| count)
+            .sum()
+    }
+
+    /// Return the packages for which the most versions were tried, in descending order.
+    pub(crate) fn most_tried(&self, limit: usize) -> Vec<(PubGrubPackage, usize)> {
+        let mut tried = self
+            .tried_versions
+            .iter()
+            .filter(|(package, _)| matches!(package, PubGrubPackage::Package(..)))
+            .map(|(package, count)| (package.clone(), *count))
+            .collect::<Vec<_>>();
+        tried.sort_by(|(a, a_count), (b, b_count)| {
+            b_count
+                .cmp(a_count)
+                .then_with(|| a.to_string().cmp(&b.to_string()))
+        });
+        tried.truncate(limit);
+        tried
+    }
+
+    /// By default, after 5, 10, 20, 40 tried versions, prefetch that many versions to start early
+    /// but not too aggressive. Later we schedule the prefetch of 50 versions every 20 versions, this
+    /// gives us a good buffer until we see prefetch again and is high enough to saturate the task
+    /// pool.
     fn should_prefetch(&self, next: &PubGrubPackage) -> (usize, bool) {
         let num_tried = self.tried_versions.get(next).copied().unwrap_or_default();
+        if !self.options.enabled || self.options.max_batch == 0 || self.options.max_concurrent == 0
+        {
+            return (num_tried, false);
+        }
+
         let previous_prefetch = self.last_prefetch.get(next).copied().unwrap_or_default();
-        let do_prefetch = (num_tried >= 5 && previous_prefetch < 5)
-            || (num_tried >= 10 && previous_prefetch < 10)
-            || (num_tried >= 20 && previous_prefetch < 20)
-            || (num_tried >= 20 && num_tried - previous_prefetch >= 20);
+        let interval = self.options.interval.max(1);
+
+        // Double the threshold from the start until we reach the interval.
+        let mut threshold = self.options.start.max(1);
+        while threshold < interval {
+            if num_tried >= threshold && previous_prefetch < threshold {
+                return (num_tried, true);
+            }
+            threshold *= 2;
+        }
+
+        let do_prefetch = num_tried >= interval
+            && (previous_prefetch < interval || num_tried - previous_prefetch >= interval);
         (num_tried, do_prefetch)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use anyhow::Result;
+
+    use uv_normalize::PackageName;
+
+    use crate::pubgrub::PubGrubPackage;
+    use crate::resolver::batch_prefetch::BatchPrefetcher;
+    use crate::PrefetchOptions;
+
+    /// Return the number of tried versions at which a prefetch is triggered, up to `limit`.
+    fn schedule(options: PrefetchOptions, limit: usize) -> Result<Vec<usize>> {
+        let package = PubGrubPackage::Package(PackageName::from_str("foo")?, None, None);
+        let mut prefetcher = BatchPrefetcher::new(options);
+        let mut triggered = Vec::new();
+        for _ in 0..limit {
+            prefetcher.version_tried(package.clone());
+            let (num_tried, do_prefetch) = prefetcher.should_prefetch(&package);
+            if do_prefetch {
+                prefetcher.last_prefetch.insert(package.clone(), num_tried);
+                triggered.push(num_tried);
+            }
+        }
+        Ok(triggered)
+    }
+
+    #[test]
+    fn default_schedule() -> Result<()> {
+        assert_eq!(
+            schedule(PrefetchOptions::default(), 100)?,
+            vec![5, 10, 20, 40, 60, 80, 100]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn custom_schedule() -> Result<()> {
+        let options = PrefetchOptions {
+            start: 2,
+            interval: 50,
+            ..PrefetchOptions::default()
+        };
+        assert_eq!(schedule(options, 120)?, vec![2, 4, 8, 16, 32, 50, 100]);
+        Ok(())
+    }
+
+    #[test]
+    fn disabled_schedule() -> Result<()> {
+        assert!(schedule(PrefetchOptions::disabled(), 100)?.is_empty());
+        assert!(schedule(PrefetchOptions::from_args(Some(0), None), 100)?.is_empty());
+        assert!(schedule(PrefetchOptions::from_args(None, Some(0)), 100)?.is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+use distribution_types::{DistributionMetadata, VersionOrUrl};
+use pep440_rs::Version;
+use uv_normalize::PackageName;
+
+/// An event emitted by the resolver as it makes progress.
+///
+/// Consumers embedding the resolver (e.g., IDEs or TUIs) can subscribe to these events via
+/// [`crate::Resolver::with_events`] to render live progress. Events are emitted on a best-effort
+/// basis: if the receiver is dropped, the resolver continues without emitting further events.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolverEvent {
+    /// The available versions of a package were fetched from the index.
+    VersionsFetched { package: PackageName },
+    /// The metadata for a distribution was fetched (or built).
+    ///
+    /// The version is `None` for distributions requested by URL.
+    MetadataFetched {
+        package: PackageName,
+        version: Option<Version>,
+    },
+    /// The resolver speculatively requested metadata for additional versions of a package,
+    /// after backtracking through many of its versions.
+    PrefetchIssued { package: PackageName, count: usize },
+    /// The resolver rejected a version of a package, and will backtrack to another version.
+    VersionRejected {
+        package: PackageName,
+        version: Version,
+        reason: String,
+    },
+    /// The resolver tentatively chose a version of a package.
+    PackageChosen {
+        package: PackageName,
+        version: Version,
+    },
+}
+
+impl ResolverEvent {
+    /// Create a [`ResolverEvent::MetadataFetched`] event for the given distribution.
+    pub(crate) fn metadata_fetched(dist: &impl DistributionMetadata) -> Self {
+        Self::MetadataFetched {
+            package: dist.name().clone(),
+            version: match dist.version_or_url() {
+                VersionOrUrl::Version(version) => Some(version.clone()),
+                VersionOrUrl::Url(_) => None,
+            },
+        }
+    }
+}
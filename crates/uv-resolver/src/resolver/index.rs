@@ -15,9 +15,28 @@ pub struct InMemoryIndex {
 
     /// A map from package ID to metadata for that distribution.
     pub(crate) distributions: OnceMap<PackageId, MetadataResponse>,
+
+    /// A map from package name to the metadata for that package as fetched, shared between all
+    /// forks of the index. `None` if the fetch was abandoned or failed.
+    pub(crate) fetched: Arc<OnceMap<PackageName, Option<VersionsResponse>>>,
 }
 
 impl InMemoryIndex {
+    /// Create an index for a different target environment, e.g., another fork of a universal
+    /// resolution.
+    ///
+    /// The available versions of a package depend on the target tags and Python version, so the
+    /// fork tracks them separately, but re-targets the metadata fetched by any other fork rather
+    /// than fetching it again.
+    #[must_use]
+    pub fn fork(&self) -> Self {
+        Self {
+            packages: OnceMap::default(),
+            distributions: OnceMap::default(),
+            fetched: self.fetched.clone(),
+        }
+    }
+
     /// Insert a [`VersionsResponse`] into the index.
     pub fn insert_package(&self, package_name: PackageName, response: VersionsResponse) {
         self.packages.done(package_name, response);
@@ -38,3 +57,78 @@ impl InMemoryIndex {
         self.distributions.get(package_id)
     }
 }
+
+/// A fetch registered in [`InMemoryIndex::fetched`].
+///
+/// If dropped before completion (e.g., because the fetch failed, or because the resolution
+/// finished with the fetch still in flight), the fetch is marked as abandoned, such that other
+/// forks fetch the metadata themselves rather than waiting forever.
+pub(crate) struct PendingFetch<'a> {
+    fetched: &'a OnceMap<PackageName, Option<VersionsResponse>>,
+    package_name: Option<PackageName>,
+}
+
+impl<'a> PendingFetch<'a> {
+    pub(crate) fn new(
+        fetched: &'a OnceMap<PackageName, Option<VersionsResponse>>,
+        package_name: PackageName,
+    ) -> Self {
+        Self {
+            fetched,
+            package_name: Some(package_name),
+        }
+    }
+
+    /// Submit the fetched metadata.
+    pub(crate) fn done(mut self, response: VersionsResponse) {
+        if let Some(package_name) = self.package_name.take() {
+            self.fetched.done(package_name, Some(response));
+        }
+    }
+}
+
+impl Drop for PendingFetch<'_> {
+    fn drop(&mut self) {
+        if let Some(package_name) = self.package_name.take() {
+            self.fetched.done(package_name, None);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    /// Forks waiting on a fetch that's dropped before completion are released, rather than
+    /// waiting forever.
+    #[tokio::test]
+    async fn abandoned_fetch() {
+        let index = InMemoryIndex::default();
+        let fork = index.fork();
+        let package_name = PackageName::from_str("anyio").unwrap();
+
+        assert!(index.fetched.register(package_name.clone()));
+        let pending = PendingFetch::new(&index.fetched, package_name.clone());
+        assert!(!fork.fetched.register(package_name.clone()));
+
+        drop(pending);
+        let response = fork.fetched.wait(&package_name).await.unwrap();
+        assert!(response.is_none());
+    }
+
+    /// Completed fetches are shared with all forks of the index.
+    #[tokio::test]
+    async fn completed_fetch() {
+        let index = InMemoryIndex::default();
+        let fork = index.fork();
+        let package_name = PackageName::from_str("anyio").unwrap();
+
+        assert!(fork.fetched.register(package_name.clone()));
+        PendingFetch::new(&fork.fetched, package_name.clone()).done(VersionsResponse::NotFound);
+
+        let response = index.fetched.wait(&package_name).await.unwrap();
+        assert!(matches!(*response, Some(VersionsResponse::NotFound)));
+    }
+}
@@ -4,6 +4,7 @@ use std::borrow::Cow;
 use std::fmt::{Display, Formatter};
 use std::ops::Deref;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use anyhow::Result;
 use dashmap::{DashMap, DashSet};
@@ -13,6 +14,8 @@ use pubgrub::error::PubGrubError;
 use pubgrub::range::Range;
 use pubgrub::solver::{Incompatibility, State};
 use rustc_hash::{FxHashMap, FxHashSet};
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::sync::Semaphore;
 use tokio_stream::wrappers::ReceiverStream;
 use tracing::{debug, info_span, instrument, trace, warn, Instrument};
 
@@ -32,9 +35,10 @@ use uv_interpreter::Interpreter;
 use uv_normalize::PackageName;
 use uv_types::{BuildContext, Constraints, InstalledPackagesProvider, Overrides};
 
+use crate::candidate_policy::{CandidateDecision, CandidatePolicy};
 use crate::candidate_selector::{CandidateDist, CandidateSelector};
 use crate::editables::Editables;
-use crate::error::ResolveError;
+use crate::error::{ResolveError, TriedVersions};
 use crate::manifest::Manifest;
 use crate::pins::FilePins;
 use crate::preferences::Preferences;
@@ -45,7 +49,9 @@ use crate::pubgrub::{
 use crate::python_requirement::PythonRequirement;
 use crate::resolution::ResolutionGraph;
 use crate::resolver::batch_prefetch::BatchPrefetcher;
+pub use crate::resolver::events::ResolverEvent;
 pub use crate::resolver::index::InMemoryIndex;
+use crate::resolver::index::PendingFetch;
 pub use crate::resolver::provider::{
     DefaultResolverProvider, MetadataResponse, PackageVersionsResult, ResolverProvider,
     VersionsResponse, WheelMetadataResult,
@@ -53,9 +59,10 @@ pub use crate::resolver::provider::{
 use crate::resolver::reporter::Facade;
 pub use crate::resolver::reporter::{BuildId, Reporter};
 use crate::yanks::AllowedYanks;
-use crate::{DependencyMode, Exclusions, Options};
+use crate::{DependencyMode, Exclusions, LocalVersionPreference, Options, PrefetchOptions};
 
 mod batch_prefetch;
+mod events;
 mod index;
 mod locals;
 mod provider;
@@ -68,6 +75,8 @@ mod urls;
 pub(crate) enum UnavailableVersion {
     /// Version is incompatible because it has no usable distributions
     IncompatibleDist(IncompatibleDist),
+    /// Version was rejected by the [`CandidatePolicy`]
+    Rejected(String),
 }
 
 /// The package is unavailable and cannot be used
@@ -107,6 +116,12 @@ pub struct Resolver<
     urls: Urls,
     locals: Locals,
     dependency_mode: DependencyMode,
+    max_tried_versions: Option<usize>,
+    max_candidates: Option<usize>,
+    timeout: Option<Duration>,
+    prefetch: PrefetchOptions,
+    /// The permits for in-flight prefetch requests.
+    prefetch_permits: Semaphore,
     markers: &'a MarkerEnvironment,
     python_requirement: PythonRequirement,
     selector: CandidateSelector,
@@ -117,6 +132,8 @@ pub struct Resolver<
     /// The set of all registry-based packages visited during resolution.
     visited: DashSet<PackageName>,
     reporter: Option<Arc<dyn Reporter>>,
+    events: Option<UnboundedSender<ResolverEvent>>,
+    candidate_policy: Option<Arc<dyn CandidatePolicy>>,
     provider: Provider,
 }
 
@@ -148,8 +165,10 @@ impl<
             flat_index,
             tags,
             PythonRequirement::new(interpreter, markers),
-            AllowedYanks::from_manifest(&manifest, markers),
+            AllowedYanks::from_manifest(&manifest, markers, options.yanked_mode),
             options.exclude_newer,
+            options.require_hashes,
+            manifest.required_hashes.clone(),
             build_context.no_binary(),
             build_context.no_build(),
         );
@@ -187,7 +206,12 @@ impl<
             visited: DashSet::default(),
             selector: CandidateSelector::for_resolution(options, &manifest, markers),
             dependency_mode: options.dependency_mode,
-            urls: Urls::from_manifest(&manifest, markers)?,
+            max_tried_versions: options.max_tried_versions,
+            max_candidates: options.max_candidates,
+            timeout: options.timeout,
+            prefetch: options.prefetch,
+            prefetch_permits: Semaphore::new(options.prefetch.max_concurrent),
+            urls: Urls::from_manifest(&manifest, markers, options.allow_url_override)?,
             locals: Locals::from_manifest(&manifest, markers),
             project: manifest.project,
             requirements: manifest.requirements,
@@ -199,6 +223,8 @@ impl<
             markers,
             python_requirement,
             reporter: None,
+            events: None,
+            candidate_policy: None,
             provider,
             installed_packages,
         })
@@ -215,6 +241,36 @@ impl<
         }
     }
 
+    /// Emit [`ResolverEvent`]s over the given channel as the resolution progresses.
+    #[must_use]
+    pub fn with_events(self, events: UnboundedSender<ResolverEvent>) -> Self {
+        Self {
+            events: Some(events),
+            ..self
+        }
+    }
+
+    /// Set the [`CandidatePolicy`] to consult before selecting a candidate.
+    ///
+    /// The policy is shared, such that the same policy can be applied across multiple resolutions.
+    #[must_use]
+    pub fn with_candidate_policy(self, policy: Arc<dyn CandidatePolicy>) -> Self {
+        Self {
+            candidate_policy: Some(policy),
+            ..self
+        }
+    }
+
+    /// Prefer candidates with the given local version label (e.g., `cu118` in `2.1.0+cu118`),
+    /// falling back to other candidates if none are available.
+    #[must_use]
+    pub fn with_local_version_preference(self, preference: LocalVersionPreference) -> Self {
+        Self {
+            selector: self.selector.with_local_version_preference(preference),
+            ..self
+        }
+    }
+
     /// Resolve a set of requirements into a set of pinned versions.
     pub async fn resolve(self) -> Result<ResolutionGraph, ResolveError> {
         // A channel to fetch package metadata (e.g., given `flask`, fetch all versions) and version
@@ -246,7 +302,8 @@ impl<
                         .with_selector(self.selector.clone())
                         .with_python_requirement(&self.python_requirement)
                         .with_index_locations(self.provider.index_locations())
-                        .with_unavailable_packages(&self.unavailable_packages),
+                        .with_unavailable_packages(&self.unavailable_packages)
+                        .with_constraint_sources(&self.constraints),
                     )
                 } else {
                     err
@@ -255,14 +312,36 @@ impl<
         }
     }
 
-    /// Run the `PubGrub` solver.
+    /// Run the `PubGrub` solver, enforcing the resolution timeout (if any).
+    ///
+    /// The timeout is enforced at every await point (e.g., while waiting on a slow fetch or
+    /// source distribution build), in addition to between decisions.
     #[instrument(skip_all)]
     async fn solve(
         &self,
         request_sink: tokio::sync::mpsc::Sender<Request>,
+    ) -> Result<ResolutionGraph, ResolveError> {
+        let mut prefetcher = BatchPrefetcher::new(self.prefetch);
+        let Some(timeout) = self.timeout else {
+            return self.solve_with(request_sink, &mut prefetcher).await;
+        };
+        match tokio::time::timeout(timeout, self.solve_with(request_sink, &mut prefetcher)).await {
+            Ok(result) => result,
+            Err(_) => Err(ResolveError::Timeout {
+                timeout,
+                tried: TriedVersions(prefetcher.most_tried(5)),
+            }),
+        }
+    }
+
+    /// Run the `PubGrub` solver, tracking tried versions in the given [`BatchPrefetcher`].
+    async fn solve_with(
+        &self,
+        request_sink: tokio::sync::mpsc::Sender<Request>,
+        prefetcher: &mut BatchPrefetcher,
     ) -> Result<ResolutionGraph, ResolveError> {
         let root = PubGrubPackage::Root(self.project.clone());
-        let mut prefetcher = BatchPrefetcher::default();
+        let start = Instant::now();
 
         // Keep track of the packages for which we've requested metadata.
         let mut pins = FilePins::default();
@@ -313,6 +392,32 @@ impl<
 
             prefetcher.version_tried(next.clone());
 
+            // Bail if we've exhausted the resolution budget, rather than backtracking forever.
+            if let Some(limit) = self.max_tried_versions {
+                if prefetcher.total_tried() > limit {
+                    return Err(ResolveError::BudgetExceeded {
+                        limit,
+                        tried: TriedVersions(prefetcher.most_tried(5)),
+                    });
+                }
+            }
+            if let Some(limit) = self.max_candidates {
+                if matches!(next, PubGrubPackage::Package(..)) && prefetcher.tried(&next) > limit {
+                    return Err(ResolveError::CandidateLimitExceeded {
+                        package: Box::new(next),
+                        limit,
+                    });
+                }
+            }
+            if let Some(timeout) = self.timeout {
+                if start.elapsed() > timeout {
+                    return Err(ResolveError::Timeout {
+                        timeout,
+                        tried: TriedVersions(prefetcher.most_tried(5)),
+                    });
+                }
+            }
+
             let term_intersection = state
                 .partial_solution
                 .term_intersection_for_package(&next)
@@ -410,7 +515,13 @@ impl<
                         UnavailableVersion::IncompatibleDist(incompatibility) => {
                             incompatibility.to_string()
                         }
+                        UnavailableVersion::Rejected(reason) => reason,
                     };
+                    self.emit_for(&next, |package| ResolverEvent::VersionRejected {
+                        package: package.clone(),
+                        version: version.clone(),
+                        reason: reason.clone(),
+                    });
                     state.add_incompatibility(Incompatibility::unavailable(
                         next.clone(),
                         version.clone(),
@@ -420,7 +531,7 @@ impl<
                 }
             };
 
-            prefetcher
+            let prefetched = prefetcher
                 .prefetch_batches(
                     &next,
                     &version,
@@ -430,8 +541,18 @@ impl<
                     &self.selector,
                 )
                 .await?;
+            if prefetched > 0 {
+                self.emit_for(&next, |package| ResolverEvent::PrefetchIssued {
+                    package: package.clone(),
+                    count: prefetched,
+                });
+            }
 
             self.on_progress(&next, &version);
+            self.emit_for(&next, |package| ResolverEvent::PackageChosen {
+                package: package.clone(),
+                version: version.clone(),
+            });
 
             if added_dependencies
                 .entry(next.clone())
@@ -445,6 +566,11 @@ impl<
                     .await?
                 {
                     Dependencies::Unavailable(reason) => {
+                        self.emit_for(package, |package| ResolverEvent::VersionRejected {
+                            package: package.clone(),
+                            version: version.clone(),
+                            reason: reason.clone(),
+                        });
                         state.add_incompatibility(Incompatibility::unavailable(
                             package.clone(),
                             version.clone(),
@@ -696,15 +822,25 @@ impl<
                     debug!("Searching for a compatible version of {package_name} ({range})");
                 }
 
-                // Find a version.
-                let Some(candidate) = self.selector.select(
-                    package_name,
-                    range,
-                    version_maps,
-                    &self.preferences,
-                    self.installed_packages,
-                    &self.exclusions,
-                ) else {
+                // Find a version, trying any versions preferred by the policy first.
+                let preferred = self
+                    .candidate_policy
+                    .as_ref()
+                    .map(|policy| policy.preferred_versions(package_name))
+                    .unwrap_or_default();
+                let Some(candidate) =
+                    CandidateSelector::select_ranked(package_name, range, version_maps, &preferred)
+                        .or_else(|| {
+                            self.selector.select(
+                                package_name,
+                                range,
+                                version_maps,
+                                &self.preferences,
+                                self.installed_packages,
+                                &self.exclusions,
+                            )
+                        })
+                else {
                     // Short circuit: we couldn't find _any_ versions for a package.
                     return Ok(None);
                 };
@@ -720,6 +856,23 @@ impl<
                     }
                 };
 
+                // If the candidate is rejected by the policy, mark the version as unavailable.
+                if let Some(policy) = &self.candidate_policy {
+                    if let CandidateDecision::Reject(reason) =
+                        policy.evaluate(candidate.name(), candidate.version(), dist)
+                    {
+                        debug!(
+                            "Rejecting: {}=={} ({reason})",
+                            candidate.name(),
+                            candidate.version()
+                        );
+                        return Ok(Some(ResolverVersion::Unavailable(
+                            candidate.version().clone(),
+                            UnavailableVersion::Rejected(reason),
+                        )));
+                    }
+                }
+
                 let filename = match dist.for_installation() {
                     ResolvedDistRef::Installable(dist) => {
                         dist.filename().unwrap_or(Cow::Borrowed("unknown filename"))
@@ -977,10 +1130,14 @@ impl<
             match response? {
                 Some(Response::Package(package_name, version_map)) => {
                     trace!("Received package metadata for: {package_name}");
+                    self.emit(|| ResolverEvent::VersionsFetched {
+                        package: package_name.clone(),
+                    });
                     self.index.packages.done(package_name, version_map);
                 }
                 Some(Response::Installed { dist, metadata }) => {
                     trace!("Received installed distribution metadata for: {dist}");
+                    self.emit(|| ResolverEvent::metadata_fetched(&dist));
                     self.index
                         .distributions
                         .done(dist.package_id(), MetadataResponse::Found(metadata));
@@ -990,6 +1147,7 @@ impl<
                     metadata,
                 }) => {
                     trace!("Received built distribution metadata for: {dist}");
+                    self.emit(|| ResolverEvent::metadata_fetched(&dist));
                     match &metadata {
                         MetadataResponse::InvalidMetadata(err) => {
                             warn!("Unable to extract metadata for {dist}: {err}");
@@ -1006,6 +1164,7 @@ impl<
                     metadata,
                 }) => {
                     trace!("Received source distribution metadata for: {dist}");
+                    self.emit(|| ResolverEvent::metadata_fetched(&dist));
                     match &metadata {
                         MetadataResponse::InvalidMetadata(err) => {
                             warn!("Unable to extract metadata for {dist}: {err}");
@@ -1029,6 +1188,22 @@ impl<
         match request {
             // Fetch package metadata from the registry.
             Request::Package(package_name) => {
+                // If another fork of the index fetched the package, re-target its versions to
+                // this resolver's environment rather than fetching them again.
+                let pending = if self.index.fetched.register(package_name.clone()) {
+                    Some(PendingFetch::new(&self.index.fetched, package_name.clone()))
+                } else {
+                    if let Some(response) = self.index.fetched.wait(&package_name).await {
+                        if let Some(ref versions) = *response {
+                            let package_versions = self
+                                .provider
+                                .retarget_package_versions(&package_name, versions);
+                            return Ok(Some(Response::Package(package_name, package_versions)));
+                        }
+                    }
+                    None
+                };
+
                 let package_versions = self
                     .provider
                     .get_package_versions(&package_name)
@@ -1036,29 +1211,25 @@ impl<
                     .await
                     .map_err(ResolveError::Client)?;
 
+                if let Some(pending) = pending {
+                    pending.done(package_versions.clone());
+                }
+
                 Ok(Some(Response::Package(package_name, package_versions)))
             }
 
             // Fetch distribution metadata from the distribution database.
-            Request::Dist(dist) => {
-                let metadata = self
-                    .provider
-                    .get_or_build_wheel_metadata(&dist)
-                    .boxed()
+            Request::Dist(dist) => self.fetch_dist(dist).await,
+
+            // Speculatively fetch distribution metadata, limiting the number of such requests in
+            // flight.
+            Request::PrefetchDist(dist) => {
+                let _permit = self
+                    .prefetch_permits
+                    .acquire()
                     .await
-                    .map_err(|err| match dist.clone() {
-                        Dist::Built(BuiltDist::Path(built_dist)) => {
-                            ResolveError::Read(Box::new(built_dist), err)
-                        }
-                        Dist::Source(SourceDist::Path(source_dist)) => {
-                            ResolveError::Build(Box::new(source_dist), err)
-                        }
-                        Dist::Built(built_dist) => ResolveError::Fetch(Box::new(built_dist), err),
-                        Dist::Source(source_dist) => {
-                            ResolveError::FetchAndBuild(Box::new(source_dist), err)
-                        }
-                    })?;
-                Ok(Some(Response::Dist { dist, metadata }))
+                    .map_err(|err| ResolveError::Failure(err.to_string()))?;
+                self.fetch_dist(dist).await
             }
 
             Request::Installed(dist) => {
@@ -1162,6 +1333,28 @@ impl<
         }
     }
 
+    /// Fetch the metadata for a distribution from the distribution database.
+    async fn fetch_dist(&self, dist: Dist) -> Result<Option<Response>, ResolveError> {
+        let metadata = self
+            .provider
+            .get_or_build_wheel_metadata(&dist)
+            .boxed()
+            .await
+            .map_err(|err| match dist.clone() {
+                Dist::Built(BuiltDist::Path(built_dist)) => {
+                    ResolveError::Read(Box::new(built_dist), err)
+                }
+                Dist::Source(SourceDist::Path(source_dist)) => {
+                    ResolveError::Build(Box::new(source_dist), err)
+                }
+                Dist::Built(built_dist) => ResolveError::Fetch(Box::new(built_dist), err),
+                Dist::Source(source_dist) => {
+                    ResolveError::FetchAndBuild(Box::new(source_dist), err)
+                }
+            })?;
+        Ok(Some(Response::Dist { dist, metadata }))
+    }
+
     fn on_progress(&self, package: &PubGrubPackage, version: &Version) {
         if let Some(reporter) = self.reporter.as_ref() {
             match package {
@@ -1182,6 +1375,28 @@ impl<
             reporter.on_complete();
         }
     }
+
+    /// Emit a [`ResolverEvent`], if a subscriber is registered.
+    fn emit(&self, event: impl FnOnce() -> ResolverEvent) {
+        if let Some(events) = self.events.as_ref() {
+            // If the receiver was dropped, the event is discarded.
+            let _ = events.send(event());
+        }
+    }
+
+    /// Emit a [`ResolverEvent`] for the given package, if a subscriber is registered.
+    ///
+    /// Events are only emitted for the base package, and not for its extras, which are resolved
+    /// to the same version.
+    fn emit_for(
+        &self,
+        package: &PubGrubPackage,
+        event: impl FnOnce(&PackageName) -> ResolverEvent,
+    ) {
+        if let PubGrubPackage::Package(package_name, None, _) = package {
+            self.emit(|| event(package_name));
+        }
+    }
 }
 
 /// Fetch the metadata for an item
@@ -1192,6 +1407,9 @@ pub(crate) enum Request {
     Package(PackageName),
     /// A request to fetch the metadata for a built or source distribution.
     Dist(Dist),
+    /// A request to speculatively fetch the metadata for a built or source distribution, issued by
+    /// the [`BatchPrefetcher`]. These requests are subject to [`PrefetchOptions::max_concurrent`].
+    PrefetchDist(Dist),
     /// A request to fetch the metadata from an already-installed distribution.
     Installed(InstalledDist),
     /// A request to pre-fetch the metadata for a package and the best-guess distribution.
@@ -1207,6 +1425,9 @@ impl Display for Request {
             Self::Dist(dist) => {
                 write!(f, "Metadata {dist}")
             }
+            Self::PrefetchDist(dist) => {
+                write!(f, "Prefetch metadata {dist}")
+            }
             Self::Installed(dist) => {
                 write!(f, "Installed metadata {dist}")
             }
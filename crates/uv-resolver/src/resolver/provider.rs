@@ -6,10 +6,10 @@ use chrono::{DateTime, Utc};
 use distribution_types::{Dist, IndexLocations};
 use platform_tags::Tags;
 use pypi_types::Metadata23;
-use uv_client::{FlatIndex, RegistryClient};
+use uv_client::{FlatDistributions, FlatIndex, RegistryClient};
 use uv_distribution::DistributionDatabase;
 use uv_normalize::PackageName;
-use uv_types::{BuildContext, NoBinary, NoBuild};
+use uv_types::{BuildContext, NoBinary, NoBuild, RequiredHashes};
 
 use crate::python_requirement::PythonRequirement;
 use crate::version_map::VersionMap;
@@ -19,7 +19,7 @@ pub type PackageVersionsResult = Result<VersionsResponse, uv_client::Error>;
 pub type WheelMetadataResult = Result<MetadataResponse, uv_distribution::Error>;
 
 /// The response when requesting versions for a package
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum VersionsResponse {
     /// The package was found in the registry with the included versions
     Found(Vec<VersionMap>),
@@ -50,6 +50,14 @@ pub trait ResolverProvider: Send + Sync {
         package_name: &'io PackageName,
     ) -> impl Future<Output = PackageVersionsResult> + Send + 'io;
 
+    /// Re-target the version map for a package, as fetched by a provider for a different
+    /// environment (e.g., another fork of a universal resolution), to this provider's environment.
+    fn retarget_package_versions(
+        &self,
+        package_name: &PackageName,
+        response: &VersionsResponse,
+    ) -> VersionsResponse;
+
     /// Get the metadata for a distribution.
     ///
     /// For a wheel, this is done by querying it's (remote) metadata, for a source dist we
@@ -80,6 +88,8 @@ pub struct DefaultResolverProvider<'a, Context: BuildContext + Send + Sync> {
     python_requirement: PythonRequirement,
     allowed_yanks: AllowedYanks,
     exclude_newer: Option<DateTime<Utc>>,
+    require_hashes: bool,
+    required_hashes: RequiredHashes,
     no_binary: NoBinary,
     no_build: NoBuild,
 }
@@ -95,6 +105,8 @@ impl<'a, Context: BuildContext + Send + Sync> DefaultResolverProvider<'a, Contex
         python_requirement: PythonRequirement,
         allowed_yanks: AllowedYanks,
         exclude_newer: Option<DateTime<Utc>>,
+        require_hashes: bool,
+        required_hashes: RequiredHashes,
         no_binary: &'a NoBinary,
         no_build: &'a NoBuild,
     ) -> Self {
@@ -106,10 +118,23 @@ impl<'a, Context: BuildContext + Send + Sync> DefaultResolverProvider<'a, Contex
             python_requirement,
             allowed_yanks,
             exclude_newer,
+            require_hashes,
+            required_hashes,
             no_binary: no_binary.clone(),
             no_build: no_build.clone(),
         }
     }
+
+    /// Return the `--find-links` distributions for the given package, if any.
+    ///
+    /// Flat indexes don't record hashes, so their distributions are ignored when hashes are
+    /// required.
+    fn flat_distributions(&self, package_name: &PackageName) -> Option<FlatDistributions> {
+        if self.require_hashes {
+            return None;
+        }
+        self.flat_index.get(package_name).cloned()
+    }
 }
 
 impl<'a, Context: BuildContext + Send + Sync> ResolverProvider
@@ -133,7 +158,9 @@ impl<'a, Context: BuildContext + Send + Sync> ResolverProvider
                             &self.python_requirement,
                             &self.allowed_yanks,
                             self.exclude_newer.as_ref(),
-                            self.flat_index.get(package_name).cloned(),
+                            self.require_hashes,
+                            self.required_hashes.get(package_name).unwrap_or_default(),
+                            self.flat_distributions(package_name),
                             &self.no_binary,
                             &self.no_build,
                         )
@@ -142,14 +169,14 @@ impl<'a, Context: BuildContext + Send + Sync> ResolverProvider
             )),
             Err(err) => match err.into_kind() {
                 uv_client::ErrorKind::PackageNotFound(_) => {
-                    if let Some(flat_index) = self.flat_index.get(package_name).cloned() {
+                    if let Some(flat_index) = self.flat_distributions(package_name) {
                         Ok(VersionsResponse::Found(vec![VersionMap::from(flat_index)]))
                     } else {
                         Ok(VersionsResponse::NotFound)
                     }
                 }
                 uv_client::ErrorKind::NoIndex(_) => {
-                    if let Some(flat_index) = self.flat_index.get(package_name).cloned() {
+                    if let Some(flat_index) = self.flat_distributions(package_name) {
                         Ok(VersionsResponse::Found(vec![VersionMap::from(flat_index)]))
                     } else if self.flat_index.offline() {
                         Ok(VersionsResponse::Offline)
@@ -158,7 +185,7 @@ impl<'a, Context: BuildContext + Send + Sync> ResolverProvider
                     }
                 }
                 uv_client::ErrorKind::Offline(_) => {
-                    if let Some(flat_index) = self.flat_index.get(package_name).cloned() {
+                    if let Some(flat_index) = self.flat_distributions(package_name) {
                         Ok(VersionsResponse::Found(vec![VersionMap::from(flat_index)]))
                     } else {
                         Ok(VersionsResponse::Offline)
@@ -169,6 +196,30 @@ impl<'a, Context: BuildContext + Send + Sync> ResolverProvider
         }
     }
 
+    /// Re-prioritize the distributions for the package against this provider's tags and Python
+    /// requirement, and substitute this provider's `--find-links` distributions.
+    fn retarget_package_versions(
+        &self,
+        package_name: &PackageName,
+        response: &VersionsResponse,
+    ) -> VersionsResponse {
+        match response {
+            VersionsResponse::Found(version_maps) => VersionsResponse::Found(
+                version_maps
+                    .iter()
+                    .map(|version_map| {
+                        version_map.retarget(
+                            &self.tags,
+                            &self.python_requirement,
+                            self.flat_distributions(package_name),
+                        )
+                    })
+                    .collect(),
+            ),
+            response => response.clone(),
+        }
+    }
+
     /// Fetch the metadata for a distribution, building it if necessary.
     async fn get_or_build_wheel_metadata<'io>(&'io self, dist: &'io Dist) -> WheelMetadataResult {
         match self.fetcher.get_or_build_wheel_metadata(dist).await {
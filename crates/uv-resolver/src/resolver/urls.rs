@@ -10,12 +10,17 @@ use crate::{Manifest, ResolveError};
 
 /// A map of package names to their associated, required URLs.
 #[derive(Debug, Default)]
-pub(crate) struct Urls(FxHashMap<PackageName, VerbatimUrl>);
+pub(crate) struct Urls {
+    required: FxHashMap<PackageName, VerbatimUrl>,
+    /// Whether a required URL takes precedence over any version specifiers for the same package.
+    allow_override: bool,
+}
 
 impl Urls {
     pub(crate) fn from_manifest(
         manifest: &Manifest,
         markers: &MarkerEnvironment,
+        allow_override: bool,
     ) -> Result<Self, ResolveError> {
         let mut urls: FxHashMap<PackageName, VerbatimUrl> = FxHashMap::default();
 
@@ -55,12 +60,21 @@ impl Urls {
             }
         }
 
-        Ok(Self(urls))
+        Ok(Self {
+            required: urls,
+            allow_override,
+        })
     }
 
     /// Return the [`VerbatimUrl`] associated with the given package name, if any.
     pub(crate) fn get(&self, package: &PackageName) -> Option<&VerbatimUrl> {
-        self.0.get(package)
+        self.required.get(package)
+    }
+
+    /// Returns `true` if version specifiers for the given package should be ignored, since the
+    /// package is pinned to a URL and URLs were allowed to override them.
+    pub(crate) fn overrides_specifiers(&self, package: &PackageName) -> bool {
+        self.allow_override && self.required.contains_key(package)
     }
 
     /// Returns `true` if the provided URL is compatible with the given "allowed" URL.
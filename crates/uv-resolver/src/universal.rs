@@ -0,0 +1,593 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::str::FromStr;
+
+use petgraph::visit::EdgeRef;
+use petgraph::Direction;
+
+use distribution_types::{Name, Verbatim};
+use pep508_rs::{
+    MarkerEnvironment, MarkerExpression, MarkerOperator, MarkerTree, MarkerValue,
+    MarkerValueString, MarkerValueVersion,
+};
+use platform_tags::{Arch, Os, Platform, Tags, TagsError};
+use pypi_types::Hashes;
+use uv_interpreter::PythonVersion;
+use uv_normalize::PackageName;
+
+use crate::resolution::{write_requirement, NodeKey};
+use crate::{AnnotationStyle, Diagnostic, Preference, ResolutionGraph};
+
+/// The latest Python minor version known to uv, targeted by a universal resolution unless a later
+/// version is requested.
+pub const LATEST_PYTHON_MINOR: u8 = 12;
+
+/// An operating system targeted by a universal resolution.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TargetOs {
+    Linux,
+    Macos,
+    Windows,
+}
+
+impl TargetOs {
+    /// All operating systems targeted by a universal resolution.
+    const ALL: [Self; 3] = [Self::Linux, Self::Macos, Self::Windows];
+
+    /// Return the value of `sys_platform` on this operating system.
+    fn sys_platform(self) -> &'static str {
+        match self {
+            Self::Linux => "linux",
+            Self::Macos => "darwin",
+            Self::Windows => "win32",
+        }
+    }
+
+    /// Return the value of `platform_system` on this operating system.
+    fn platform_system(self) -> &'static str {
+        match self {
+            Self::Linux => "Linux",
+            Self::Macos => "Darwin",
+            Self::Windows => "Windows",
+        }
+    }
+
+    /// Return the value of `os_name` on this operating system.
+    fn os_name(self) -> &'static str {
+        match self {
+            Self::Linux | Self::Macos => "posix",
+            Self::Windows => "nt",
+        }
+    }
+
+    /// Return the value of `platform_machine` for the given architecture on this operating system.
+    fn platform_machine(self, arch: TargetArch) -> &'static str {
+        match (self, arch) {
+            (Self::Linux | Self::Macos, TargetArch::X86_64) => "x86_64",
+            (Self::Linux, TargetArch::Aarch64) => "aarch64",
+            (Self::Macos, TargetArch::Aarch64) => "arm64",
+            (Self::Windows, TargetArch::X86_64) => "AMD64",
+            (Self::Windows, TargetArch::Aarch64) => "ARM64",
+        }
+    }
+
+    /// Return the [`Os`] to use when computing compatible tags for this operating system.
+    ///
+    /// We target broadly-supported releases, such that the resolution is valid on all but the
+    /// oldest systems.
+    fn os(self) -> Os {
+        match self {
+            Self::Linux => Os::Manylinux {
+                major: 2,
+                minor: 28,
+            },
+            Self::Macos => Os::Macos {
+                major: 12,
+                minor: 0,
+            },
+            Self::Windows => Os::Windows,
+        }
+    }
+}
+
+impl std::fmt::Display for TargetOs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Linux => write!(f, "Linux"),
+            Self::Macos => write!(f, "macOS"),
+            Self::Windows => write!(f, "Windows"),
+        }
+    }
+}
+
+/// A CPU architecture targeted by a universal resolution.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TargetArch {
+    X86_64,
+    Aarch64,
+}
+
+impl TargetArch {
+    /// All architectures targeted by a universal resolution.
+    const ALL: [Self; 2] = [Self::X86_64, Self::Aarch64];
+
+    /// Return the [`Arch`] to use when computing compatible tags for this architecture.
+    fn arch(self) -> Arch {
+        match self {
+            Self::X86_64 => Arch::X86_64,
+            Self::Aarch64 => Arch::Aarch64,
+        }
+    }
+}
+
+/// A target environment for a universal resolution: an operating system, architecture, and Python
+/// version.
+///
+/// A universal resolution forks the resolver once per target environment, evaluating markers
+/// against each in turn, and merges the results into a single, marker-annotated output.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ForkEnvironment {
+    os: TargetOs,
+    arch: TargetArch,
+    python_minor: u8,
+}
+
+impl ForkEnvironment {
+    /// Return the target environments for a universal resolution that supports the Python 3 minor
+    /// versions from `python_minor` through `max_python_minor`, inclusive.
+    pub fn all(python_minor: u8, max_python_minor: u8) -> Vec<Self> {
+        let python_minors = python_minor..=max_python_minor.max(python_minor);
+        TargetOs::ALL
+            .into_iter()
+            .flat_map(|os| TargetArch::ALL.into_iter().map(move |arch| (os, arch)))
+            .flat_map(|(os, arch)| {
+                python_minors.clone().map(move |python_minor| Self {
+                    os,
+                    arch,
+                    python_minor,
+                })
+            })
+            .collect()
+    }
+
+    /// Return the [`PythonVersion`] targeted by this environment.
+    fn python_version(self) -> PythonVersion {
+        PythonVersion::from_str(&format!("3.{}", self.python_minor))
+            .expect("Python minor version to be valid")
+    }
+
+    /// Return the [`Platform`] targeted by this environment.
+    pub fn platform(self) -> Platform {
+        Platform::new(self.os.os(), self.arch.arch())
+    }
+
+    /// Return the [`MarkerEnvironment`] for this environment, derived from the given base markers.
+    pub fn markers(self, base: &MarkerEnvironment) -> MarkerEnvironment {
+        let mut markers = self.python_version().markers(base);
+        markers.sys_platform = self.os.sys_platform().to_string();
+        markers.platform_system = self.os.platform_system().to_string();
+        markers.os_name = self.os.os_name().to_string();
+        markers.platform_machine = self.os.platform_machine(self.arch).to_string();
+        markers.platform_release = String::new();
+        markers.platform_version = String::new();
+        markers
+    }
+
+    /// Return a marker matching this environment (e.g., `sys_platform == 'linux' and
+    /// platform_machine == 'x86_64' and python_version == '3.12'`).
+    pub fn marker(self) -> MarkerTree {
+        MarkerTree::And(vec![
+            sys_platform_expression(MarkerOperator::Equal, self.os),
+            platform_machine_expression(self.os, self.arch),
+            MarkerTree::Expression(MarkerExpression {
+                l_value: MarkerValue::MarkerEnvVersion(MarkerValueVersion::PythonVersion),
+                operator: MarkerOperator::Equal,
+                r_value: MarkerValue::QuotedString(format!("3.{}", self.python_minor)),
+            }),
+        ])
+    }
+
+    /// Return the compatible [`Tags`] for this environment, for the given implementation.
+    pub fn tags(
+        self,
+        implementation_name: &str,
+        implementation_version: (u8, u8),
+    ) -> Result<Tags, TagsError> {
+        // For CPython, the implementation version matches the Python version.
+        let implementation_version = if implementation_name == "cpython" {
+            (3, self.python_minor)
+        } else {
+            implementation_version
+        };
+        Tags::from_env(
+            &self.platform(),
+            (3, self.python_minor),
+            implementation_name,
+            implementation_version,
+        )
+    }
+}
+
+impl std::fmt::Display for ForkEnvironment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Python 3.{} on {} ({})",
+            self.python_minor,
+            self.os,
+            self.os.platform_machine(self.arch)
+        )
+    }
+}
+
+/// A resolution that's valid across multiple target environments, composed of a
+/// [`ResolutionGraph`] for each [`ForkEnvironment`].
+#[derive(Debug)]
+pub struct UniversalResolution {
+    forks: Vec<(ForkEnvironment, ResolutionGraph)>,
+}
+
+impl UniversalResolution {
+    /// Create a universal resolution from the resolutions for each target environment.
+    pub fn new(forks: Vec<(ForkEnvironment, ResolutionGraph)>) -> Self {
+        Self { forks }
+    }
+
+    /// Return the number of distinct packages (by name and version) in the resolution.
+    pub fn len(&self) -> usize {
+        self.forks
+            .iter()
+            .flat_map(|(_, graph)| graph.petgraph().node_weights())
+            .map(|dist| dist.verbatim())
+            .collect::<BTreeSet<_>>()
+            .len()
+    }
+
+    /// Return `true` if there are no packages in the resolution.
+    pub fn is_empty(&self) -> bool {
+        self.forks.iter().all(|(_, graph)| graph.is_empty())
+    }
+
+    /// Return `true` if the resolution contains the given package, in any environment.
+    pub fn contains(&self, name: &PackageName) -> bool {
+        self.forks.iter().any(|(_, graph)| graph.contains(name))
+    }
+
+    /// Return the pinned versions of the registry-based packages across all environments, each
+    /// restricted to the environment in which it was selected.
+    pub fn preferences(&self) -> Vec<Preference> {
+        self.forks
+            .iter()
+            .flat_map(|(fork, graph)| graph.preferences_for(Some(&fork.marker())))
+            .collect()
+    }
+
+    /// Iterate over the resolutions for each target environment.
+    pub(crate) fn graphs(&self) -> impl Iterator<Item = &ResolutionGraph> {
+        self.forks.iter().map(|(_, graph)| graph)
+    }
+
+    /// Return the diagnostics encountered across all environments, without duplicates.
+    pub fn diagnostics(&self) -> Vec<&Diagnostic> {
+        let mut seen = BTreeSet::new();
+        self.forks
+            .iter()
+            .flat_map(|(_, graph)| graph.diagnostics())
+            .filter(|diagnostic| seen.insert(diagnostic.message()))
+            .collect()
+    }
+
+    /// Return the marker expression under which a requirement applies, given the indices of the
+    /// forks in which it was resolved, or `None` if it applies in every environment.
+    ///
+    /// Environments are grouped by operating system, such that the marker is expressed in terms
+    /// of `sys_platform` and `python_version`. Where the requirement differs across architectures
+    /// on a given operating system, `platform_machine` is used to distinguish them.
+    pub(crate) fn marker(&self, present: &BTreeSet<usize>) -> Option<MarkerTree> {
+        let oses = self
+            .forks
+            .iter()
+            .map(|(fork, _)| fork.os)
+            .collect::<BTreeSet<_>>();
+        let arches = self
+            .forks
+            .iter()
+            .map(|(fork, _)| fork.arch)
+            .collect::<BTreeSet<_>>();
+        let python_minors = self
+            .forks
+            .iter()
+            .map(|(fork, _)| fork.python_minor)
+            .collect::<BTreeSet<_>>();
+
+        // Group the operating systems (or, where the requirement differs by architecture, the
+        // operating system and architecture pairs) by the Python versions for which the
+        // requirement applies.
+        let mut groups: BTreeMap<BTreeSet<u8>, Vec<(TargetOs, Option<TargetArch>)>> =
+            BTreeMap::new();
+        for os in &oses {
+            let by_arch = arches
+                .iter()
+                .map(|arch| {
+                    let minors = self
+                        .forks
+                        .iter()
+                        .enumerate()
+                        .filter(|(index, (fork, _))| {
+                            fork.os == *os && fork.arch == *arch && present.contains(index)
+                        })
+                        .map(|(_, (fork, _))| fork.python_minor)
+                        .collect::<BTreeSet<_>>();
+                    (*arch, minors)
+                })
+                .collect::<Vec<_>>();
+            if by_arch.windows(2).all(|pair| pair[0].1 == pair[1].1) {
+                if let Some((_, minors)) = by_arch.into_iter().next() {
+                    if !minors.is_empty() {
+                        groups.entry(minors).or_default().push((*os, None));
+                    }
+                }
+            } else {
+                for (arch, minors) in by_arch {
+                    if !minors.is_empty() {
+                        groups.entry(minors).or_default().push((*os, Some(arch)));
+                    }
+                }
+            }
+        }
+
+        let mut disjuncts = Vec::new();
+        for (minors, group) in groups {
+            let python_version =
+                (minors != python_minors).then(|| python_version_marker(&minors, &python_minors));
+
+            // Operating systems on which the requirement applies regardless of architecture.
+            let whole = group
+                .iter()
+                .filter(|(_, arch)| arch.is_none())
+                .map(|(os, _)| *os)
+                .collect::<Vec<_>>();
+            if !whole.is_empty() {
+                let mut conjuncts = Vec::new();
+                if whole.len() != oses.len() {
+                    conjuncts.push(sys_platform_marker(&whole, &oses));
+                }
+                conjuncts.extend(python_version.clone());
+                match conjuncts.len() {
+                    0 => return None,
+                    1 => disjuncts.extend(conjuncts),
+                    _ => disjuncts.push(MarkerTree::And(conjuncts)),
+                }
+            }
+
+            // Operating systems on which the requirement only applies to some architectures.
+            for (os, arch) in group {
+                let Some(arch) = arch else {
+                    continue;
+                };
+                let mut conjuncts = vec![
+                    sys_platform_expression(MarkerOperator::Equal, os),
+                    platform_machine_expression(os, arch),
+                ];
+                conjuncts.extend(python_version.clone());
+                disjuncts.push(MarkerTree::And(conjuncts));
+            }
+        }
+
+        if disjuncts.len() == 1 {
+            disjuncts.pop()
+        } else {
+            Some(MarkerTree::Or(disjuncts))
+        }
+    }
+}
+
+/// Return a `sys_platform` expression comparing against the given operating system.
+fn sys_platform_expression(operator: MarkerOperator, os: TargetOs) -> MarkerTree {
+    MarkerTree::Expression(MarkerExpression {
+        l_value: MarkerValue::MarkerEnvString(MarkerValueString::SysPlatform),
+        operator,
+        r_value: MarkerValue::QuotedString(os.sys_platform().to_string()),
+    })
+}
+
+/// Return a `platform_machine` expression matching the given architecture on the given operating
+/// system.
+fn platform_machine_expression(os: TargetOs, arch: TargetArch) -> MarkerTree {
+    MarkerTree::Expression(MarkerExpression {
+        l_value: MarkerValue::MarkerEnvString(MarkerValueString::PlatformMachine),
+        operator: MarkerOperator::Equal,
+        r_value: MarkerValue::QuotedString(os.platform_machine(arch).to_string()),
+    })
+}
+
+/// Return a marker matching the given operating systems, out of all targeted operating systems.
+fn sys_platform_marker(group: &[TargetOs], oses: &BTreeSet<TargetOs>) -> MarkerTree {
+    // If all but one operating system is included, exclude it instead (e.g.,
+    // `sys_platform != 'win32'`).
+    if group.len() > 1 && group.len() + 1 == oses.len() {
+        if let Some(excluded) = oses.iter().find(|os| !group.contains(os)) {
+            return sys_platform_expression(MarkerOperator::NotEqual, *excluded);
+        }
+    }
+
+    match group {
+        [os] => sys_platform_expression(MarkerOperator::Equal, *os),
+        group => MarkerTree::Or(
+            group
+                .iter()
+                .map(|os| sys_platform_expression(MarkerOperator::Equal, *os))
+                .collect(),
+        ),
+    }
+}
+
+/// Return a marker matching the given Python minor versions, out of all targeted versions.
+///
+/// Consecutive versions are collapsed into ranges. Ranges that extend to the oldest (or latest)
+/// targeted version are left unbounded below (or above).
+fn python_version_marker(minors: &BTreeSet<u8>, python_minors: &BTreeSet<u8>) -> MarkerTree {
+    let expression = |operator, minor: u8| {
+        MarkerTree::Expression(MarkerExpression {
+            l_value: MarkerValue::MarkerEnvVersion(MarkerValueVersion::PythonVersion),
+            operator,
+            r_value: MarkerValue::QuotedString(format!("3.{minor}")),
+        })
+    };
+
+    // Partition the targeted versions into runs of consecutive included versions.
+    let python_minors = python_minors.iter().copied().collect::<Vec<_>>();
+    let mut runs: Vec<(usize, usize)> = Vec::new();
+    for (index, minor) in python_minors.iter().enumerate() {
+        if !minors.contains(minor) {
+            continue;
+        }
+        match runs.last_mut() {
+            Some((_, end)) if *end + 1 == index => *end = index,
+            _ => runs.push((index, index)),
+        }
+    }
+
+    let mut disjuncts = runs
+        .into_iter()
+        .map(|(start, end)| {
+            let lower = (start > 0).then(|| python_minors[start]);
+            let upper = python_minors.get(end + 1).copied();
+            match (lower, upper) {
+                (Some(lower), Some(_)) if start == end => expression(MarkerOperator::Equal, lower),
+                (Some(lower), Some(upper)) => MarkerTree::And(vec![
+                    expression(MarkerOperator::GreaterEqual, lower),
+                    expression(MarkerOperator::LessThan, upper),
+                ]),
+                (Some(lower), None) => expression(MarkerOperator::GreaterEqual, lower),
+                (None, Some(upper)) => expression(MarkerOperator::LessThan, upper),
+                (None, None) => unreachable!("a partial set of versions must be bounded"),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    match disjuncts.pop() {
+        Some(disjunct) if disjuncts.is_empty() => disjunct,
+        Some(disjunct) => {
+            disjuncts.push(disjunct);
+            MarkerTree::Or(disjuncts)
+        }
+        None => unreachable!("a partial set of versions must be non-empty"),
+    }
+}
+
+/// A [`std::fmt::Display`] implementation for a universal resolution.
+///
+/// Each requirement is annotated with the environment markers under which it applies.
+#[derive(Debug)]
+pub struct DisplayUniversalResolution<'a> {
+    /// The underlying resolution.
+    resolution: &'a UniversalResolution,
+    /// The packages to exclude from the output.
+    no_emit_packages: &'a [PackageName],
+    /// Whether to include hashes in the output.
+    show_hashes: bool,
+    /// Whether to include extras in the output (e.g., `black[colorama]`).
+    include_extras: bool,
+    /// Whether to include annotations in the output, to indicate which dependency or dependencies
+    /// requested each package.
+    include_annotations: bool,
+    /// The style of annotation comments, used to indicate the dependencies that requested each
+    /// package.
+    annotation_style: AnnotationStyle,
+}
+
+impl<'a> DisplayUniversalResolution<'a> {
+    /// Create a new [`DisplayUniversalResolution`] for the given resolution.
+    pub fn new(
+        resolution: &'a UniversalResolution,
+        no_emit_packages: &'a [PackageName],
+        show_hashes: bool,
+        include_extras: bool,
+        include_annotations: bool,
+        annotation_style: AnnotationStyle,
+    ) -> Self {
+        Self {
+            resolution,
+            no_emit_packages,
+            show_hashes,
+            include_extras,
+            include_annotations,
+            annotation_style,
+        }
+    }
+}
+
+/// A requirement in a universal resolution, merged across the environments in which it appears.
+#[derive(Debug, Default)]
+struct MergedNode<'a> {
+    /// The indices of the forks in which the requirement was resolved.
+    forks: BTreeSet<usize>,
+    /// The hashes for the requirement.
+    hashes: BTreeSet<String>,
+    /// The packages that requested the requirement, in any environment.
+    edges: BTreeSet<&'a PackageName>,
+}
+
+/// Write the resolution in the `{name}=={version} ; {marker}` format of requirements.txt.
+impl std::fmt::Display for DisplayUniversalResolution<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Merge identical requirements across all forks, sorted by name, but with editable
+        // packages first.
+        let mut nodes: BTreeMap<(NodeKey, String), MergedNode> = BTreeMap::new();
+        for (fork, (_, graph)) in self.resolution.forks.iter().enumerate() {
+            for index in graph.petgraph().node_indices() {
+                let node = graph.node(index, self.include_extras);
+                if self.no_emit_packages.contains(node.name()) {
+                    continue;
+                }
+
+                let merged = nodes
+                    .entry((node.key(), node.verbatim().to_string()))
+                    .or_default();
+                merged.forks.insert(fork);
+                if self.show_hashes {
+                    merged.hashes.extend(
+                        graph
+                            .hashes(node.name())
+                            .iter()
+                            .filter_map(Hashes::to_string),
+                    );
+                }
+                merged.edges.extend(
+                    graph
+                        .petgraph()
+                        .edges_directed(index, Direction::Incoming)
+                        .map(|edge| graph.petgraph()[edge.source()].name()),
+                );
+            }
+        }
+
+        for ((_, verbatim), merged) in nodes {
+            // Display the node itself, along with the environments in which it applies.
+            let mut line = verbatim;
+            if let Some(marker) = self.resolution.marker(&merged.forks) {
+                line.push_str(&format!(" ; {marker}"));
+            }
+
+            // Display the distribution hashes, if any.
+            let has_hashes = !merged.hashes.is_empty();
+            for hash in merged.hashes {
+                line.push_str(" \\\n");
+                line.push_str("    --hash=");
+                line.push_str(&hash);
+            }
+
+            let edges = merged.edges.into_iter().collect::<Vec<_>>();
+            write_requirement(
+                f,
+                &line,
+                has_hashes,
+                &edges,
+                self.include_annotations,
+                self.annotation_style,
+            )?;
+        }
+
+        Ok(())
+    }
+}
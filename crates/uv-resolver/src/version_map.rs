@@ -1,5 +1,5 @@
 use std::collections::btree_map::{BTreeMap, Entry};
-use std::sync::OnceLock;
+use std::sync::{Arc, OnceLock};
 
 use chrono::{DateTime, Utc};
 use rustc_hash::FxHashSet;
@@ -22,9 +22,9 @@ use uv_warnings::warn_user_once;
 use crate::{python_requirement::PythonRequirement, yanks::AllowedYanks};
 
 /// A map from versions to distributions.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct VersionMap {
-    inner: VersionMapInner,
+    inner: Arc<VersionMapInner>,
 }
 
 impl VersionMap {
@@ -47,6 +47,8 @@ impl VersionMap {
         python_requirement: &PythonRequirement,
         allowed_yanks: &AllowedYanks,
         exclude_newer: Option<&DateTime<Utc>>,
+        require_hashes: bool,
+        required_hashes: &[String],
         flat_index: Option<FlatDistributions>,
         no_binary: &NoBinary,
         no_build: &NoBuild,
@@ -68,31 +70,7 @@ impl VersionMap {
                 }),
             );
         }
-        // If a set of flat distributions have been given, we need to add those
-        // to our map of entries as well.
-        for (version, prioritized_dist) in flat_index.into_iter().flatten() {
-            match map.entry(version) {
-                Entry::Vacant(e) => {
-                    e.insert(LazyPrioritizedDist::OnlyFlat(prioritized_dist));
-                }
-                // When there is both a `VersionFiles` (from the "simple"
-                // metadata) and a flat distribution for the same version of
-                // a package, we store both and "merge" them into a single
-                // `PrioritizedDist` upon access later.
-                Entry::Occupied(e) => match e.remove_entry() {
-                    (version, LazyPrioritizedDist::OnlySimple(simple_dist)) => {
-                        map.insert(
-                            version,
-                            LazyPrioritizedDist::Both {
-                                flat: prioritized_dist,
-                                simple: simple_dist,
-                            },
-                        );
-                    }
-                    _ => unreachable!(),
-                },
-            }
-        }
+        merge_flat_distributions(&mut map, flat_index);
         // Check if binaries are allowed for this package.
         let no_binary = match no_binary {
             NoBinary::None => false,
@@ -105,22 +83,76 @@ impl VersionMap {
             NoBuild::All => true,
             NoBuild::Packages(packages) => packages.contains(package_name),
         };
+        let allow_all_yanks = allowed_yanks.allows_all();
         let allowed_yanks = allowed_yanks
             .allowed_versions(package_name)
             .cloned()
             .unwrap_or_default();
         Self {
-            inner: VersionMapInner::Lazy(VersionMapLazy {
+            inner: Arc::new(VersionMapInner::Lazy(VersionMapLazy {
                 map,
-                simple_metadata,
+                simple_metadata: Arc::new(simple_metadata),
                 no_binary,
                 no_build,
                 index: index.clone(),
                 tags: tags.clone(),
                 python_requirement: python_requirement.clone(),
                 exclude_newer: exclude_newer.copied(),
+                require_hashes,
+                required_hashes: required_hashes.to_vec(),
+                allow_all_yanks,
                 allowed_yanks,
-            }),
+            })),
+        }
+    }
+
+    /// Re-target the [`VersionMap`] to a different environment.
+    ///
+    /// The simple metadata is reused as-is, but distributions are prioritized against the given
+    /// tags and Python requirement (upon access), and the `--find-links` distributions are
+    /// replaced with the given ones, which are specific to the environment.
+    pub(crate) fn retarget(
+        &self,
+        tags: &Tags,
+        python_requirement: &PythonRequirement,
+        flat_index: Option<FlatDistributions>,
+    ) -> Self {
+        let lazy = match *self.inner {
+            VersionMapInner::Eager(_) => return flat_index.map(Self::from).unwrap_or_default(),
+            VersionMapInner::Lazy(ref lazy) => lazy,
+        };
+        let mut map = BTreeMap::new();
+        for (version, dist) in &lazy.map {
+            match dist {
+                LazyPrioritizedDist::OnlyFlat(_) => {}
+                LazyPrioritizedDist::OnlySimple(simple)
+                | LazyPrioritizedDist::Both { simple, .. } => {
+                    map.insert(
+                        version.clone(),
+                        LazyPrioritizedDist::OnlySimple(SimplePrioritizedDist {
+                            datum_index: simple.datum_index,
+                            dist: OnceLock::new(),
+                        }),
+                    );
+                }
+            }
+        }
+        merge_flat_distributions(&mut map, flat_index);
+        Self {
+            inner: Arc::new(VersionMapInner::Lazy(VersionMapLazy {
+                map,
+                simple_metadata: lazy.simple_metadata.clone(),
+                no_binary: lazy.no_binary,
+                no_build: lazy.no_build,
+                index: lazy.index.clone(),
+                tags: tags.clone(),
+                python_requirement: python_requirement.clone(),
+                exclude_newer: lazy.exclude_newer,
+                require_hashes: lazy.require_hashes,
+                required_hashes: lazy.required_hashes.clone(),
+                allow_all_yanks: lazy.allow_all_yanks,
+                allowed_yanks: lazy.allowed_yanks.clone(),
+            })),
         }
     }
 
@@ -140,7 +172,7 @@ impl VersionMap {
         &self,
         version: &Version,
     ) -> Option<(&Version, &PrioritizedDist)> {
-        match self.inner {
+        match *self.inner {
             VersionMapInner::Eager(ref map) => map.get_key_value(version),
             VersionMapInner::Lazy(ref lazy) => lazy.get_with_version(version),
         }
@@ -155,7 +187,7 @@ impl VersionMap {
     pub(crate) fn iter(
         &self,
     ) -> impl DoubleEndedIterator<Item = (&Version, VersionMapDistHandle)> + ExactSizeIterator {
-        match self.inner {
+        match *self.inner {
             VersionMapInner::Eager(ref map) => {
                 either::Either::Left(map.iter().map(|(version, dist)| {
                     let version_map_dist = VersionMapDistHandle {
@@ -177,7 +209,7 @@ impl VersionMap {
 
     /// Return the [`Hashes`] for the given version, if any.
     pub(crate) fn hashes(&self, version: &Version) -> Option<Vec<Hashes>> {
-        match self.inner {
+        match *self.inner {
             VersionMapInner::Eager(ref map) => map.get(version).map(|file| file.hashes().to_vec()),
             VersionMapInner::Lazy(ref lazy) => lazy.get(version).map(|file| file.hashes().to_vec()),
         }
@@ -188,7 +220,7 @@ impl VersionMap {
     /// Note that this may include versions of distributions that are not
     /// usable in the current environment.
     pub(crate) fn len(&self) -> usize {
-        match self.inner {
+        match *self.inner {
             VersionMapInner::Eager(ref map) => map.len(),
             VersionMapInner::Lazy(VersionMapLazy { ref map, .. }) => map.len(),
         }
@@ -199,7 +231,7 @@ impl Default for VersionMap {
     /// Create an empty version map.
     fn default() -> Self {
         Self {
-            inner: VersionMapInner::Eager(BTreeMap::default()),
+            inner: Arc::new(VersionMapInner::Eager(BTreeMap::default())),
         }
     }
 }
@@ -207,7 +239,7 @@ impl Default for VersionMap {
 impl From<FlatDistributions> for VersionMap {
     fn from(flat_index: FlatDistributions) -> Self {
         Self {
-            inner: VersionMapInner::Eager(flat_index.into()),
+            inner: Arc::new(VersionMapInner::Eager(flat_index.into())),
         }
     }
 }
@@ -215,7 +247,39 @@ impl From<FlatDistributions> for VersionMap {
 impl From<BTreeMap<Version, PrioritizedDist>> for VersionMap {
     fn from(value: BTreeMap<Version, PrioritizedDist>) -> Self {
         Self {
-            inner: VersionMapInner::Eager(value),
+            inner: Arc::new(VersionMapInner::Eager(value)),
+        }
+    }
+}
+
+/// Add the given `--find-links` distributions to a map of lazily initialized distributions.
+fn merge_flat_distributions(
+    map: &mut BTreeMap<Version, LazyPrioritizedDist>,
+    flat_index: Option<FlatDistributions>,
+) {
+    // If a set of flat distributions have been given, we need to add those
+    // to our map of entries as well.
+    for (version, prioritized_dist) in flat_index.into_iter().flatten() {
+        match map.entry(version) {
+            Entry::Vacant(e) => {
+                e.insert(LazyPrioritizedDist::OnlyFlat(prioritized_dist));
+            }
+            // When there is both a `VersionFiles` (from the "simple"
+            // metadata) and a flat distribution for the same version of
+            // a package, we store both and "merge" them into a single
+            // `PrioritizedDist` upon access later.
+            Entry::Occupied(e) => match e.remove_entry() {
+                (version, LazyPrioritizedDist::OnlySimple(simple_dist)) => {
+                    map.insert(
+                        version,
+                        LazyPrioritizedDist::Both {
+                            flat: prioritized_dist,
+                            simple: simple_dist,
+                        },
+                    );
+                }
+                _ => unreachable!(),
+            },
         }
     }
 }
@@ -279,12 +343,13 @@ enum VersionMapInner {
 /// avoiding another conversion step into a fully filled out `VersionMap` can
 /// provide substantial savings in some cases.
 #[derive(Debug)]
+#[allow(clippy::struct_excessive_bools)]
 struct VersionMapLazy {
     /// A map from version to possibly-initialized distribution.
     map: BTreeMap<Version, LazyPrioritizedDist>,
     /// The raw simple metadata from which `PrioritizedDist`s should
     /// be constructed.
-    simple_metadata: OwnedArchive<SimpleMetadata>,
+    simple_metadata: Arc<OwnedArchive<SimpleMetadata>>,
     /// When true, wheels aren't allowed.
     no_binary: bool,
     /// When true, source dists aren't allowed.
@@ -300,6 +365,13 @@ struct VersionMapLazy {
     python_requirement: PythonRequirement,
     /// Whether files newer than this timestamp should be excluded or not.
     exclude_newer: Option<DateTime<Utc>>,
+    /// Whether files without a hash should be excluded or not.
+    require_hashes: bool,
+    /// The SHA-256 digests provided for the package (e.g., via `--hash` in a constraints file), if
+    /// any. Files matching none of them are excluded.
+    required_hashes: Vec<String>,
+    /// Whether all yanked versions are allowed
+    allow_all_yanks: bool,
     /// Which yanked versions are allowed
     allowed_yanks: FxHashSet<Version>,
 }
@@ -388,6 +460,7 @@ impl VersionMapLazy {
                             yanked,
                             excluded,
                             upload_time,
+                            &hash,
                         );
                         let dist = Dist::from_registry(
                             DistFilename::WheelFilename(filename),
@@ -403,6 +476,7 @@ impl VersionMapLazy {
                             yanked,
                             excluded,
                             upload_time,
+                            &hash,
                         );
                         let dist = Dist::from_registry(
                             DistFilename::SourceDistFilename(filename),
@@ -429,6 +503,7 @@ impl VersionMapLazy {
         yanked: Option<Yanked>,
         excluded: bool,
         upload_time: Option<i64>,
+        hash: &Hashes,
     ) -> SourceDistCompatibility {
         // Check if builds are disabled
         if self.no_build {
@@ -444,11 +519,22 @@ impl VersionMapLazy {
 
         // Check if yanked
         if let Some(yanked) = yanked {
-            if yanked.is_yanked() && !self.allowed_yanks.contains(version) {
+            if yanked.is_yanked() && !self.allow_all_yanks && !self.allowed_yanks.contains(version)
+            {
                 return SourceDistCompatibility::Incompatible(IncompatibleSource::Yanked(yanked));
             }
         }
 
+        // Check if the index is missing a hash, when hashes are required
+        if self.require_hashes && hash.as_str().is_none() {
+            return SourceDistCompatibility::Incompatible(IncompatibleSource::MissingHash);
+        }
+
+        // Check if the hash doesn't match any of those provided for the package
+        if self.is_mismatched_hash(hash) {
+            return SourceDistCompatibility::Incompatible(IncompatibleSource::MismatchedHash);
+        }
+
         // Check if Python version is supported
         // Source distributions must meet both the _target_ Python version and the
         // _installed_ Python version (to build successfully)
@@ -465,6 +551,7 @@ impl VersionMapLazy {
         SourceDistCompatibility::Compatible
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn wheel_compatibility(
         &self,
         filename: &WheelFilename,
@@ -473,6 +560,7 @@ impl VersionMapLazy {
         yanked: Option<Yanked>,
         excluded: bool,
         upload_time: Option<i64>,
+        hash: &Hashes,
     ) -> WheelCompatibility {
         // Check if binaries are disabled
         if self.no_binary {
@@ -486,11 +574,22 @@ impl VersionMapLazy {
 
         // Check if yanked
         if let Some(yanked) = yanked {
-            if yanked.is_yanked() && !self.allowed_yanks.contains(version) {
+            if yanked.is_yanked() && !self.allow_all_yanks && !self.allowed_yanks.contains(version)
+            {
                 return WheelCompatibility::Incompatible(IncompatibleWheel::Yanked(yanked));
             }
         }
 
+        // Check if the index is missing a hash, when hashes are required
+        if self.require_hashes && hash.as_str().is_none() {
+            return WheelCompatibility::Incompatible(IncompatibleWheel::MissingHash);
+        }
+
+        // Check if the hash doesn't match any of those provided for the package
+        if self.is_mismatched_hash(hash) {
+            return WheelCompatibility::Incompatible(IncompatibleWheel::MismatchedHash);
+        }
+
         // Check for a Python version incompatibility`
         if let Some(requires_python) = requires_python {
             if !requires_python.contains(self.python_requirement.target()) {
@@ -503,6 +602,17 @@ impl VersionMapLazy {
         // Determine a compatibility for the wheel based on tags
         WheelCompatibility::from(filename.compatibility(&self.tags))
     }
+
+    /// Returns `true` if hashes were provided for the package, but the given hash matches none of
+    /// them.
+    fn is_mismatched_hash(&self, hash: &Hashes) -> bool {
+        !self.required_hashes.is_empty()
+            && !hash.sha256.as_deref().is_some_and(|sha256| {
+                self.required_hashes
+                    .iter()
+                    .any(|required| required.eq_ignore_ascii_case(sha256))
+            })
+    }
 }
 
 /// Represents a possibly initialized [`PrioritizedDist`] for
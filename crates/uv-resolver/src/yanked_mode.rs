@@ -0,0 +1,13 @@
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum YankedMode {
+    /// Disallow all yanked versions, even if pinned.
+    Disallow,
+
+    /// Allow all yanked versions.
+    Allow,
+
+    /// Allow yanked versions only when a requirement pins the exact version (e.g., `foo==1.0.0`).
+    #[default]
+    AllowPinned,
+}
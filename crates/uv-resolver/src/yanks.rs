@@ -4,15 +4,35 @@ use pep440_rs::Version;
 use pep508_rs::{MarkerEnvironment, VersionOrUrl};
 use uv_normalize::PackageName;
 
-use crate::{Manifest, Preference};
+use crate::{Manifest, Preference, YankedMode};
 
 /// A set of package versions that are permitted, even if they're marked as yanked by the
 /// relevant index.
 #[derive(Debug, Default, Clone)]
-pub struct AllowedYanks(FxHashMap<PackageName, FxHashSet<Version>>);
+pub struct AllowedYanks {
+    /// Whether all yanked versions are permitted.
+    all: bool,
+    /// The pinned versions that are permitted, by package.
+    pinned: FxHashMap<PackageName, FxHashSet<Version>>,
+}
 
 impl AllowedYanks {
-    pub fn from_manifest(manifest: &Manifest, markers: &MarkerEnvironment) -> Self {
+    pub fn from_manifest(
+        manifest: &Manifest,
+        markers: &MarkerEnvironment,
+        yanked_mode: YankedMode,
+    ) -> Self {
+        match yanked_mode {
+            YankedMode::Disallow => return Self::default(),
+            YankedMode::Allow => {
+                return Self {
+                    all: true,
+                    pinned: FxHashMap::default(),
+                }
+            }
+            YankedMode::AllowPinned => {}
+        }
+
         let mut allowed_yanks = FxHashMap::<PackageName, FxHashSet<Version>>::default();
 
         for requirement in manifest
@@ -36,12 +56,20 @@ impl AllowedYanks {
                     .insert(specifier.version().clone());
             }
         }
-        Self(allowed_yanks)
+        Self {
+            all: false,
+            pinned: allowed_yanks,
+        }
+    }
+
+    /// Returns `true` if all yanked versions are permitted.
+    pub fn allows_all(&self) -> bool {
+        self.all
     }
 
     /// Returns versions for the given package which are allowed even if marked as yanked by the
     /// relevant index.
     pub fn allowed_versions(&self, package_name: &PackageName) -> Option<&FxHashSet<Version>> {
-        self.0.get(package_name)
+        self.pinned.get(package_name)
     }
 }
@@ -5,20 +5,27 @@
 
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Result;
 use chrono::{DateTime, Utc};
+use futures::future;
 use once_cell::sync::Lazy;
 
-use distribution_types::{IndexLocations, Resolution, SourceDist};
+use distribution_types::{CompatibleDist, Dist, IndexLocations, Resolution, SourceDist};
+use pep440_rs::Version;
 use pep508_rs::{MarkerEnvironment, Requirement, StringVersion};
 use platform_tags::{Arch, Os, Platform, Tags};
 use uv_cache::Cache;
 use uv_client::{FlatIndex, RegistryClientBuilder};
 use uv_interpreter::{find_default_python, Interpreter, PythonEnvironment};
+use uv_normalize::PackageName;
 use uv_resolver::{
-    DisplayResolutionGraph, Exclusions, InMemoryIndex, Manifest, Options, OptionsBuilder,
-    PreReleaseMode, Preference, ResolutionGraph, ResolutionMode, Resolver,
+    CandidateDecision, CandidatePolicy, DisplayResolutionGraph, Exclusions, InMemoryIndex,
+    Manifest, Options, OptionsBuilder, PackageVersionsResult, PreReleaseMode, Preference,
+    PythonRequirement, ResolutionGraph, ResolutionMode, Resolver, ResolverEvent, ResolverProvider,
+    VersionsResponse, WheelMetadataResult,
 };
 use uv_types::{
     BuildContext, BuildIsolation, BuildKind, Constraints, EmptyInstalledPackages, NoBinary,
@@ -116,6 +123,16 @@ async fn resolve(
     options: Options,
     markers: &'static MarkerEnvironment,
     tags: &Tags,
+) -> Result<ResolutionGraph> {
+    resolve_with_policy(manifest, options, markers, tags, None).await
+}
+
+async fn resolve_with_policy(
+    manifest: Manifest,
+    options: Options,
+    markers: &'static MarkerEnvironment,
+    tags: &Tags,
+    policy: Option<Arc<dyn CandidatePolicy>>,
 ) -> Result<ResolutionGraph> {
     let client = RegistryClientBuilder::new(Cache::temp()?).build();
     let flat_index = FlatIndex::default();
@@ -138,6 +155,11 @@ async fn resolve(
         &build_context,
         &installed_packages,
     )?;
+    let resolver = if let Some(policy) = policy {
+        resolver.with_candidate_policy(policy)
+    } else {
+        resolver
+    };
     Ok(resolver.resolve().await?)
 }
 
@@ -378,6 +400,171 @@ async fn black_flake8() -> Result<()> {
     Ok(())
 }
 
+/// Reject black 23.9.x via a [`CandidatePolicy`], falling back to the prior release.
+#[tokio::test]
+async fn black_candidate_policy() -> Result<()> {
+    struct VersionCeiling;
+
+    impl CandidatePolicy for VersionCeiling {
+        fn evaluate(
+            &self,
+            name: &PackageName,
+            version: &Version,
+            _dist: &CompatibleDist,
+        ) -> CandidateDecision {
+            if name.as_ref() == "black" && *version >= Version::from_str("23.9.0").unwrap() {
+                CandidateDecision::Reject("it is newer than the allowed ceiling".to_string())
+            } else {
+                CandidateDecision::Allow
+            }
+        }
+    }
+
+    let manifest = Manifest::simple(vec![Requirement::from_str("black<=23.9.1").unwrap()]);
+    let options = OptionsBuilder::new()
+        .exclude_newer(Some(*EXCLUDE_NEWER))
+        .build();
+
+    let resolution = resolve_with_policy(
+        manifest,
+        options,
+        &MARKERS_311,
+        &TAGS_311,
+        Some(Arc::new(VersionCeiling)),
+    )
+    .await?;
+
+    assert_snapshot!(DisplayResolutionGraph::from(&resolution), @r###"
+    black==23.7.0
+    click==8.1.7
+        # via black
+    mypy-extensions==1.0.0
+        # via black
+    packaging==23.2
+        # via black
+    pathspec==0.11.2
+        # via black
+    platformdirs==4.0.0
+        # via black
+    "###);
+
+    Ok(())
+}
+
+/// Prefer an older release of black via a [`CandidatePolicy`], ahead of the latest compatible
+/// version.
+#[tokio::test]
+async fn black_candidate_policy_preferred() -> Result<()> {
+    struct VettedReleases;
+
+    impl CandidatePolicy for VettedReleases {
+        fn evaluate(
+            &self,
+            _name: &PackageName,
+            _version: &Version,
+            _dist: &CompatibleDist,
+        ) -> CandidateDecision {
+            CandidateDecision::Allow
+        }
+
+        fn preferred_versions(&self, name: &PackageName) -> Vec<Version> {
+            if name.as_ref() == "black" {
+                // `24.1.0` is excluded by the requirement, so `23.3.0` is selected.
+                vec![
+                    Version::from_str("24.1.0").unwrap(),
+                    Version::from_str("23.3.0").unwrap(),
+                ]
+            } else {
+                Vec::new()
+            }
+        }
+    }
+
+    let manifest = Manifest::simple(vec![Requirement::from_str("black<=23.9.1").unwrap()]);
+    let options = OptionsBuilder::new()
+        .exclude_newer(Some(*EXCLUDE_NEWER))
+        .build();
+
+    let resolution = resolve_with_policy(
+        manifest,
+        options,
+        &MARKERS_311,
+        &TAGS_311,
+        Some(Arc::new(VettedReleases)),
+    )
+    .await?;
+
+    assert_snapshot!(DisplayResolutionGraph::from(&resolution), @r###"
+    black==23.3.0
+    click==8.1.7
+        # via black
+    mypy-extensions==1.0.0
+        # via black
+    packaging==23.2
+        # via black
+    pathspec==0.11.2
+        # via black
+    platformdirs==4.0.0
+        # via black
+    "###);
+
+    Ok(())
+}
+
+/// Subscribe to the resolver's progress events.
+#[tokio::test]
+async fn black_events() -> Result<()> {
+    let manifest = Manifest::simple(vec![Requirement::from_str("black<=23.9.1").unwrap()]);
+    let options = OptionsBuilder::new()
+        .exclude_newer(Some(*EXCLUDE_NEWER))
+        .build();
+
+    let client = RegistryClientBuilder::new(Cache::temp()?).build();
+    let flat_index = FlatIndex::default();
+    let index = InMemoryIndex::default();
+    let real_interpreter =
+        find_default_python(&Cache::temp().unwrap()).expect("Expected a python to be installed");
+    let interpreter =
+        Interpreter::artificial(real_interpreter.platform().clone(), MARKERS_311.clone());
+    let build_context = DummyContext::new(Cache::temp()?, interpreter.clone());
+    let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+    let resolver = Resolver::new(
+        manifest,
+        options,
+        &MARKERS_311,
+        &interpreter,
+        &TAGS_311,
+        &client,
+        &flat_index,
+        &index,
+        &build_context,
+        &EmptyInstalledPackages,
+    )?
+    .with_events(sender);
+    resolver.resolve().await?;
+
+    let mut events = Vec::new();
+    while let Some(event) = receiver.recv().await {
+        events.push(event);
+    }
+
+    let black = PackageName::from_str("black")?;
+    let version = Version::from_str("23.9.1")?;
+    assert!(events.contains(&ResolverEvent::VersionsFetched {
+        package: black.clone()
+    }));
+    assert!(events.contains(&ResolverEvent::MetadataFetched {
+        package: black.clone(),
+        version: Some(version.clone())
+    }));
+    assert!(events.contains(&ResolverEvent::PackageChosen {
+        package: black,
+        version
+    }));
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn black_lowest() -> Result<()> {
     let manifest = Manifest::simple(vec![Requirement::from_str("black>21").unwrap()]);
@@ -670,6 +857,80 @@ async fn msgraph_sdk() -> Result<()> {
     Ok(())
 }
 
+/// A [`ResolverProvider`] whose requests never complete, as with a stalled index or a source
+/// distribution build that hangs.
+struct StalledProvider {
+    index_locations: IndexLocations,
+}
+
+impl ResolverProvider for StalledProvider {
+    async fn get_package_versions<'io>(&'io self, _: &'io PackageName) -> PackageVersionsResult {
+        future::pending().await
+    }
+
+    fn retarget_package_versions(
+        &self,
+        _: &PackageName,
+        response: &VersionsResponse,
+    ) -> VersionsResponse {
+        response.clone()
+    }
+
+    async fn get_or_build_wheel_metadata<'io>(&'io self, _: &'io Dist) -> WheelMetadataResult {
+        future::pending().await
+    }
+
+    fn index_locations(&self) -> &IndexLocations {
+        &self.index_locations
+    }
+
+    fn with_reporter(self, _reporter: impl uv_distribution::Reporter + 'static) -> Self {
+        self
+    }
+}
+
+/// The resolution timeout applies while the resolver is waiting on metadata, and not only between
+/// decisions.
+#[tokio::test]
+async fn timeout_stalled_fetch() -> Result<()> {
+    let manifest = Manifest::simple(vec![Requirement::from_str("anyio").unwrap()]);
+    let options = OptionsBuilder::new()
+        .timeout(Some(Duration::from_millis(100)))
+        .build();
+    let interpreter = Interpreter::artificial(
+        Platform::new(
+            Os::Macos {
+                major: 21,
+                minor: 6,
+            },
+            Arch::Aarch64,
+        ),
+        MARKERS_311.clone(),
+    );
+    let index = InMemoryIndex::default();
+    let provider = StalledProvider {
+        index_locations: IndexLocations::default(),
+    };
+    let resolver = Resolver::new_custom_io(
+        manifest,
+        options,
+        &MARKERS_311,
+        PythonRequirement::new(&interpreter, &MARKERS_311),
+        &index,
+        provider,
+        &EmptyInstalledPackages,
+    )?;
+
+    let err = resolver.resolve().await.unwrap_err();
+
+    assert_snapshot!(err, @r###"
+    Resolution timed out after 0.1s; the most backtracked packages were:
+    - anyio (1 version)
+    "###);
+
+    Ok(())
+}
+
 static MARKERS_311: Lazy<MarkerEnvironment> = Lazy::new(|| {
     MarkerEnvironment {
         implementation_name: "cpython".to_string(),
@@ -211,7 +211,7 @@ impl NoBuild {
     }
 }
 
-#[derive(Debug, Default, Clone, Hash, Eq, PartialEq)]
+#[derive(Debug, Default, Copy, Clone, Hash, Eq, PartialEq)]
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
 pub enum IndexStrategy {
     /// Only use results from the first index that returns a match for a given package name.
@@ -232,6 +232,15 @@ pub enum IndexStrategy {
     ///
     /// See: https://peps.python.org/pep-0708/
     UnsafeAnyMatch,
+    /// Search for every package name across all indexes, preferring the "best" version found.
+    ///
+    /// In this strategy, we look for every package across all indexes, and consider the union of
+    /// the versions found, such that the best version wins regardless of the index on which it
+    /// was found (matching pip's behavior). If the same version is available on multiple indexes,
+    /// the distributions from the earlier index are preferred.
+    ///
+    /// See: https://peps.python.org/pep-0708/
+    UnsafeBestMatch,
 }
 
 #[cfg(test)]
@@ -0,0 +1,41 @@
+use std::num::NonZeroUsize;
+
+/// The degree of parallelism used when downloading and installing distributions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Concurrency {
+    /// The maximum number of distributions to download (and, if necessary, build) at once.
+    pub downloads: usize,
+    /// The maximum number of threads used to unzip and link distributions at once.
+    pub installs: usize,
+}
+
+impl Concurrency {
+    /// The default number of concurrent downloads.
+    pub const DEFAULT_DOWNLOADS: usize = 50;
+
+    /// Create a [`Concurrency`], falling back to the defaults for any unset values.
+    pub fn new(downloads: Option<usize>, installs: Option<usize>) -> Self {
+        let default = Self::default();
+        Self {
+            downloads: downloads.unwrap_or(default.downloads).max(1),
+            installs: installs.unwrap_or(default.installs).max(1),
+        }
+    }
+
+    /// The number of threads available to the process, used as the default number of concurrent
+    /// installs.
+    pub fn threads() -> usize {
+        std::thread::available_parallelism()
+            .map(NonZeroUsize::get)
+            .unwrap_or(1)
+    }
+}
+
+impl Default for Concurrency {
+    fn default() -> Self {
+        Self {
+            downloads: Self::DEFAULT_DOWNLOADS,
+            installs: Self::threads(),
+        }
+    }
+}
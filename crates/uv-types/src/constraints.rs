@@ -7,7 +7,11 @@ use uv_normalize::PackageName;
 
 /// A set of constraints for a set of requirements.
 #[derive(Debug, Default, Clone)]
-pub struct Constraints(FxHashMap<PackageName, Vec<Requirement>>);
+pub struct Constraints {
+    constraints: FxHashMap<PackageName, Vec<Requirement>>,
+    /// The location (e.g., `constraints.txt:3`) at which each constraint was declared, if known.
+    sources: FxHashMap<Requirement, String>,
+}
 
 impl Constraints {
     /// Create a new set of constraints from a set of requirements.
@@ -20,17 +24,46 @@ impl Constraints {
                 .or_default()
                 .push(requirement);
         }
-        Self(constraints)
+        Self {
+            constraints,
+            sources: FxHashMap::default(),
+        }
+    }
+
+    /// Attach the location at which each constraint was declared, for use in error messages.
+    #[must_use]
+    pub fn with_sources(mut self, sources: FxHashMap<Requirement, String>) -> Self {
+        self.sources = sources;
+        self
+    }
+
+    /// Return the location (e.g., `constraints.txt:3`) at which the given constraint was declared,
+    /// if known.
+    pub fn source(&self, constraint: &Requirement) -> Option<&str> {
+        self.sources.get(constraint).map(String::as_str)
+    }
+
+    /// Return the subset of constraints that satisfy the given predicate, retaining their sources.
+    #[must_use]
+    pub fn filter(&self, predicate: impl Fn(&Requirement) -> bool) -> Self {
+        let constraints = self
+            .requirements()
+            .filter(|requirement| predicate(requirement))
+            .cloned()
+            .collect();
+        Self::from_requirements(constraints).with_sources(self.sources.clone())
     }
 
     /// Return an iterator over all [`Requirement`]s in the constraint set.
     pub fn requirements(&self) -> impl Iterator<Item = &Requirement> {
-        self.0.values().flat_map(|requirements| requirements.iter())
+        self.constraints
+            .values()
+            .flat_map(|requirements| requirements.iter())
     }
 
     /// Get the constraints for a package.
     pub fn get(&self, name: &PackageName) -> Option<&Vec<Requirement>> {
-        self.0.get(name)
+        self.constraints.get(name)
     }
 
     /// Apply the constraints to a set of requirements.
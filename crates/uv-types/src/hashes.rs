@@ -0,0 +1,141 @@
+use rustc_hash::FxHashMap;
+
+use pep440_rs::Operator;
+use pep508_rs::{Requirement, VersionOrUrl};
+use uv_normalize::PackageName;
+
+/// The hashes that the distributions of each package are required to match, as provided via
+/// `--hash` in a `requirements.txt` file.
+///
+/// Each hash is stored as a lowercase, hex-encoded SHA-256 digest.
+#[derive(Debug, Default, Clone)]
+pub struct RequiredHashes(FxHashMap<PackageName, Vec<String>>);
+
+impl RequiredHashes {
+    /// Generate the [`RequiredHashes`] from a set of requirements and their `--hash` values (e.g.,
+    /// `sha256:2cf24d...`).
+    ///
+    /// Every requirement must be pinned to an exact version (or a URL), and must include at least
+    /// one SHA-256 hash.
+    pub fn from_requirements<'a>(
+        requirements: impl IntoIterator<Item = (&'a Requirement, &'a [String])>,
+    ) -> Result<Self, RequiredHashesError> {
+        let mut hashes: FxHashMap<PackageName, Vec<String>> = FxHashMap::default();
+        for (requirement, digests) in requirements {
+            // Every requirement must be pinned, such that its hashes identify the distributions
+            // of a single version.
+            let pinned = match requirement.version_or_url.as_ref() {
+                Some(VersionOrUrl::Url(_)) => true,
+                Some(VersionOrUrl::VersionSpecifier(specifiers)) => {
+                    specifiers.len() == 1
+                        && matches!(
+                            specifiers[0].operator(),
+                            Operator::Equal | Operator::ExactEqual
+                        )
+                }
+                None => false,
+            };
+            if !pinned {
+                return Err(RequiredHashesError::Unpinned(requirement.clone()));
+            }
+
+            let mut sha256 = Vec::with_capacity(digests.len());
+            for digest in digests {
+                let Some((algorithm, value)) = digest.split_once(':') else {
+                    return Err(RequiredHashesError::InvalidHash(digest.clone()));
+                };
+                // Hashes computed with other algorithms are accepted, but can't be verified.
+                if algorithm != "sha256" {
+                    continue;
+                }
+                if value.len() != 64 || !value.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return Err(RequiredHashesError::InvalidHash(digest.clone()));
+                }
+                sha256.push(value.to_ascii_lowercase());
+            }
+            if sha256.is_empty() {
+                return Err(RequiredHashesError::MissingHash(requirement.name.clone()));
+            }
+
+            hashes
+                .entry(requirement.name.clone())
+                .or_default()
+                .extend(sha256);
+        }
+        Ok(Self(hashes))
+    }
+
+    /// Returns `true` if no hashes are required.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the hex-encoded SHA-256 digests allowed for the given package, if any.
+    pub fn get(&self, name: &PackageName) -> Option<&[String]> {
+        self.0.get(name).map(Vec::as_slice)
+    }
+
+    /// Returns `true` if the given hex-encoded SHA-256 digest is allowed for the given package.
+    pub fn contains(&self, name: &PackageName, digest: &str) -> bool {
+        self.get(name).is_some_and(|digests| {
+            digests
+                .iter()
+                .any(|expected| expected.eq_ignore_ascii_case(digest))
+        })
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RequiredHashesError {
+    #[error("In `--require-hashes` mode, all requirements must be pinned upfront with `==`, but found: `{0}`")]
+    Unpinned(Requirement),
+    #[error("In `--require-hashes` mode, all requirements must have a SHA-256 hash, but none were provided for: `{0}`")]
+    MissingHash(PackageName),
+    #[error("Unsupported hash format (expected `sha256:<hex digest>`): `{0}`")]
+    InvalidHash(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use pep508_rs::Requirement;
+    use uv_normalize::PackageName;
+
+    use super::{RequiredHashes, RequiredHashesError};
+
+    const SHA256: &str = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+
+    #[test]
+    fn from_requirements() {
+        let requirement = Requirement::from_str("anyio==4.0.0").unwrap();
+        let digests = vec![
+            format!("sha256:{}", SHA256.to_ascii_uppercase()),
+            "md5:5d41402abc4b2a76b9719d911017c592".to_string(),
+        ];
+        let hashes =
+            RequiredHashes::from_requirements([(&requirement, digests.as_slice())]).unwrap();
+        let name = PackageName::from_str("anyio").unwrap();
+        assert_eq!(hashes.get(&name), Some([SHA256.to_string()].as_slice()));
+        assert!(hashes.contains(&name, SHA256));
+        assert!(!hashes.contains(&name, &"0".repeat(64)));
+    }
+
+    #[test]
+    fn unpinned() {
+        let requirement = Requirement::from_str("anyio>=4.0.0").unwrap();
+        let digests = vec![format!("sha256:{SHA256}")];
+        let err =
+            RequiredHashes::from_requirements([(&requirement, digests.as_slice())]).unwrap_err();
+        assert!(matches!(err, RequiredHashesError::Unpinned(_)));
+    }
+
+    #[test]
+    fn missing_hash() {
+        let requirement = Requirement::from_str("anyio==4.0.0").unwrap();
+        let digests = vec!["md5:5d41402abc4b2a76b9719d911017c592".to_string()];
+        let err =
+            RequiredHashes::from_requirements([(&requirement, digests.as_slice())]).unwrap_err();
+        assert!(matches!(err, RequiredHashesError::MissingHash(_)));
+    }
+}
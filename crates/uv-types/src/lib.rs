@@ -1,8 +1,10 @@
 //! Fundamental types shared across `uv` crates.
 pub use build_options::*;
+pub use concurrency::*;
 pub use config_settings::*;
 pub use constraints::*;
 pub use downloads::*;
+pub use hashes::*;
 pub use name_specifiers::*;
 pub use overrides::*;
 pub use package_options::*;
@@ -10,9 +12,11 @@ pub use requirements::*;
 pub use traits::*;
 
 mod build_options;
+mod concurrency;
 mod config_settings;
 mod constraints;
 mod downloads;
+mod hashes;
 mod name_specifiers;
 mod overrides;
 mod package_options;
@@ -24,6 +24,17 @@ impl Overrides {
         Self(overrides)
     }
 
+    /// Exclude the given packages, such that any requirement on them is dropped.
+    ///
+    /// Excluded packages are never resolved, and so their own dependencies are never considered.
+    #[must_use]
+    pub fn with_excluded(mut self, packages: impl IntoIterator<Item = PackageName>) -> Self {
+        for package in packages {
+            self.0.insert(package, Vec::new());
+        }
+        self
+    }
+
     /// Return an iterator over all [`Requirement`]s in the override set.
     pub fn requirements(&self) -> impl Iterator<Item = &Requirement> {
         self.0.values().flat_map(|requirements| requirements.iter())
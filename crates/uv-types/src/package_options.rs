@@ -1,6 +1,7 @@
-use pep508_rs::PackageName;
+use either::Either;
+use pep508_rs::{PackageName, Requirement};
 
-use rustc_hash::FxHashSet;
+use rustc_hash::FxHashMap;
 
 /// Whether to reinstall packages.
 #[derive(Debug, Clone)]
@@ -47,22 +48,40 @@ pub enum Upgrade {
     /// Allow package upgrades for all packages, ignoring the existing lockfile.
     All,
 
-    /// Allow package upgrades, but only for the specified packages.
-    Packages(FxHashSet<PackageName>),
+    /// Allow package upgrades, but only for the specified packages, bounded by any version
+    /// specifiers provided alongside them (e.g., `--upgrade-package "django<5"`).
+    Packages(FxHashMap<PackageName, Vec<Requirement>>),
 }
 
 impl Upgrade {
     /// Determine the upgrade strategy from the command-line arguments.
-    pub fn from_args(upgrade: bool, upgrade_package: Vec<PackageName>) -> Self {
+    pub fn from_args(upgrade: bool, upgrade_package: Vec<Requirement>) -> Self {
         if upgrade {
             Self::All
         } else if !upgrade_package.is_empty() {
-            Self::Packages(upgrade_package.into_iter().collect())
+            let mut packages: FxHashMap<PackageName, Vec<Requirement>> = FxHashMap::default();
+            for requirement in upgrade_package {
+                let entry = packages.entry(requirement.name.clone()).or_default();
+                if requirement.version_or_url.is_some() {
+                    entry.push(requirement);
+                }
+            }
+            Self::Packages(packages)
         } else {
             Self::None
         }
     }
 
+    /// Returns the constraints that bound the upgraded packages, as provided via
+    /// `--upgrade-package`.
+    pub fn constraints(&self) -> impl Iterator<Item = &Requirement> {
+        if let Self::Packages(packages) = self {
+            Either::Left(packages.values().flatten())
+        } else {
+            Either::Right(std::iter::empty())
+        }
+    }
+
     /// Returns `true` if no packages should be upgraded.
     pub fn is_none(&self) -> bool {
         matches!(self, Self::None)
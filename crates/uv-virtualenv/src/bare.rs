@@ -4,7 +4,7 @@ use std::env;
 use std::env::consts::EXE_SUFFIX;
 use std::io;
 use std::io::{BufWriter, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use fs_err as fs;
 use fs_err::File;
@@ -42,43 +42,18 @@ fn write_cfg(f: &mut impl Write, data: &[(String, String)]) -> io::Result<()> {
 }
 
 /// Write all the files that belong to a venv without any packages installed.
+///
+/// If `copies` is set, the Python executables are copied from the base interpreter, rather than
+/// symlinked to it (on Windows, the executables are always copied).
 pub fn create_bare_venv(
     location: &Path,
     interpreter: &Interpreter,
     prompt: Prompt,
     system_site_packages: bool,
+    copies: bool,
     extra_cfg: Vec<(String, String)>,
 ) -> Result<Virtualenv, Error> {
-    // Determine the base Python executable; that is, the Python executable that should be
-    // considered the "base" for the virtual environment. This is typically the Python executable
-    // from the [`Interpreter`]; however, if the interpreter is a virtual environment itself, then
-    // the base Python executable is the Python executable of the interpreter's base interpreter.
-    let base_python = if cfg!(unix) {
-        // On Unix, follow symlinks to resolve the base interpreter, since the Python executable in
-        // a virtual environment is a symlink to the base interpreter.
-        uv_fs::canonicalize_executable(interpreter.sys_executable())?
-    } else if cfg!(windows) {
-        // On Windows, follow `virtualenv`. If we're in a virtual environment, use
-        // `sys._base_executable` if it exists; if not, use `sys.base_prefix`. For example, with
-        // Python installed from the Windows Store, `sys.base_prefix` is slightly "incorrect".
-        //
-        // If we're _not_ in a virtual environment, use the interpreter's executable, since it's
-        // already a "system Python". We canonicalize the path to ensure that it's real and
-        // consistent, though we don't expect any symlinks on Windows.
-        if interpreter.is_virtualenv() {
-            if let Some(base_executable) = interpreter.base_executable() {
-                base_executable.to_path_buf()
-            } else {
-                // Assume `python.exe`, though the exact executable name is never used (below) on
-                // Windows, only its parent directory.
-                interpreter.base_prefix().join("python.exe")
-            }
-        } else {
-            uv_fs::canonicalize_executable(interpreter.sys_executable())?
-        }
-    } else {
-        unimplemented!("Only Windows and Unix are supported")
-    };
+    let base_python = base_python(interpreter)?;
 
     // Validate the existing location.
     match location.metadata() {
@@ -143,79 +118,7 @@ pub fn create_bare_venv(
     fs::create_dir(&scripts)?;
     let executable = scripts.join(format!("python{EXE_SUFFIX}"));
 
-    #[cfg(unix)]
-    {
-        use fs_err::os::unix::fs::symlink;
-
-        symlink(&base_python, &executable)?;
-        symlink(
-            "python",
-            scripts.join(format!("python{}", interpreter.python_major())),
-        )?;
-        symlink(
-            "python",
-            scripts.join(format!(
-                "python{}.{}",
-                interpreter.python_major(),
-                interpreter.python_minor(),
-            )),
-        )?;
-    }
-
-    // No symlinking on Windows, at least not on a regular non-dev non-admin Windows install.
-    #[cfg(windows)]
-    {
-        // https://github.com/python/cpython/blob/d457345bbc6414db0443819290b04a9a4333313d/Lib/venv/__init__.py#L261-L267
-        // https://github.com/pypa/virtualenv/blob/d9fdf48d69f0d0ca56140cf0381edbb5d6fe09f5/src/virtualenv/create/via_global_ref/builtin/cpython/cpython3.py#L78-L83
-        // There's two kinds of applications on windows: Those that allocate a console (python.exe) and those that
-        // don't because they use window(s) (pythonw.exe).
-        for python_exe in ["python.exe", "pythonw.exe"] {
-            let shim = interpreter
-                .stdlib()
-                .join("venv")
-                .join("scripts")
-                .join("nt")
-                .join(python_exe);
-            match fs_err::copy(shim, scripts.join(python_exe)) {
-                Ok(_) => {}
-                Err(err) if err.kind() == io::ErrorKind::NotFound => {
-                    let launcher = match python_exe {
-                        "python.exe" => "venvwlauncher.exe",
-                        "pythonw.exe" => "venvwlauncher.exe",
-                        _ => unreachable!(),
-                    };
-
-                    // If `python.exe` doesn't exist, try the `venvlaucher.exe` shim.
-                    let shim = interpreter
-                        .stdlib()
-                        .join("venv")
-                        .join("scripts")
-                        .join("nt")
-                        .join(launcher);
-
-                    // If the `venvwlauncher.exe` shim doesn't exist, then on Conda at least, we
-                    // can look for it next to the Python executable itself.
-                    match fs_err::copy(shim, scripts.join(python_exe)) {
-                        Ok(_) => {}
-                        Err(err) if err.kind() == io::ErrorKind::NotFound => {
-                            let shim = base_python.with_file_name(launcher);
-                            fs_err::copy(shim, scripts.join(python_exe))?;
-                        }
-                        Err(err) => {
-                            return Err(err.into());
-                        }
-                    }
-                }
-                Err(err) => {
-                    return Err(err.into());
-                }
-            }
-        }
-    }
-    #[cfg(not(any(unix, windows)))]
-    {
-        compile_error!("Only Windows and Unix are supported")
-    }
+    write_executables(&scripts, interpreter, &base_python, copies)?;
 
     // Add all the activate scripts for different shells
     for (name, template) in ACTIVATE_TEMPLATES {
@@ -242,17 +145,7 @@ pub fn create_bare_venv(
         fs::write(scripts.join(name), activator)?;
     }
 
-    // Per PEP 405, the Python `home` is the parent directory of the interpreter.
-    let python_home = base_python
-        .parent()
-        .ok_or_else(|| {
-            io::Error::new(
-                io::ErrorKind::NotFound,
-                "The Python interpreter needs to have a parent directory",
-            )
-        })?
-        .simplified_display()
-        .to_string();
+    let python_home = python_home(&base_python)?;
 
     // Validate extra_cfg
     let reserved_keys = [
@@ -322,3 +215,246 @@ pub fn create_bare_venv(
         executable,
     })
 }
+
+/// Re-point an existing venv at the given interpreter, e.g., after the base interpreter was
+/// upgraded to a new patch release and the old one removed.
+///
+/// Rewrites `home`, `version_info`, and (if present) `executable` and `command` in `pyvenv.cfg`,
+/// and replaces the Python executables, keeping the installed packages. Executables that were
+/// copies are copied again; symlinks are relinked.
+pub fn upgrade_bare_venv(location: &Path, interpreter: &Interpreter) -> Result<Virtualenv, Error> {
+    let pyvenv_cfg = location.join("pyvenv.cfg");
+    if !pyvenv_cfg.is_file() {
+        return Err(Error::MissingVirtualenv(location.to_path_buf()));
+    }
+    let location = location.canonicalize()?;
+    let pyvenv_cfg = location.join("pyvenv.cfg");
+    let content = fs::read_to_string(&pyvenv_cfg)?;
+
+    // The `site-packages` directory is versioned by `major.minor`, so only patch upgrades can be
+    // applied in-place.
+    let version_info = interpreter.markers().python_full_version.string.clone();
+    if let Some(existing) = content.lines().find_map(|line| {
+        let (key, value) = line.split_once('=')?;
+        matches!(key.trim(), "version_info" | "version").then(|| value.trim().to_string())
+    }) {
+        let minor = |version: &str| version.split('.').take(2).collect::<Vec<_>>().join(".");
+        if minor(&existing) != minor(&version_info) {
+            return Err(Error::IncompatibleUpgrade {
+                existing,
+                requested: version_info,
+            });
+        }
+    }
+
+    let base_python = base_python(interpreter)?;
+    let python_home = python_home(&base_python)?;
+
+    // Replace the executables, preserving whether they were copied or symlinked.
+    let scripts = location.join(&interpreter.virtualenv().scripts);
+    let executable = scripts.join(format!("python{EXE_SUFFIX}"));
+    let copies = fs::symlink_metadata(&executable).is_ok_and(|metadata| metadata.is_file());
+    #[cfg(unix)]
+    for name in [
+        "python".to_string(),
+        format!("python{}", interpreter.python_major()),
+        format!(
+            "python{}.{}",
+            interpreter.python_major(),
+            interpreter.python_minor()
+        ),
+    ] {
+        match fs::remove_file(scripts.join(name)) {
+            Ok(()) => {}
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err.into()),
+        }
+    }
+    write_executables(&scripts, interpreter, &base_python, copies)?;
+
+    // Rewrite `pyvenv.cfg`, retaining any keys we don't manage. The `executable` and `command`
+    // keys (as written by the standard library's `venv`) refer to the old base interpreter.
+    let base_executable = base_python.simplified_display().to_string();
+    let stale_executable = content.lines().find_map(|line| {
+        let (key, value) = line.split_once('=')?;
+        (key.trim() == "executable").then(|| value.trim().to_string())
+    });
+    let pyvenv_cfg_data = content
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| {
+            let key = key.trim().to_string();
+            let value = match key.as_str() {
+                "home" => python_home.clone(),
+                "version_info" | "version" => version_info.clone(),
+                "executable" => base_executable.clone(),
+                "command" => match stale_executable.as_deref() {
+                    Some(stale) if !stale.is_empty() => {
+                        value.trim().replacen(stale, &base_executable, 1)
+                    }
+                    _ => value.trim().to_string(),
+                },
+                _ => value.trim().to_string(),
+            };
+            (key, value)
+        })
+        .collect::<Vec<_>>();
+    let mut file = BufWriter::new(File::create(&pyvenv_cfg)?);
+    write_cfg(&mut file, &pyvenv_cfg_data)?;
+    drop(file);
+
+    Ok(Virtualenv {
+        scheme: Scheme {
+            purelib: location.join(&interpreter.virtualenv().purelib),
+            platlib: location.join(&interpreter.virtualenv().platlib),
+            scripts: location.join(&interpreter.virtualenv().scripts),
+            data: location.join(&interpreter.virtualenv().data),
+            include: location.join(&interpreter.virtualenv().include),
+        },
+        root: location,
+        executable,
+    })
+}
+
+/// Determine the base Python executable; that is, the Python executable that should be
+/// considered the "base" for the virtual environment. This is typically the Python executable
+/// from the [`Interpreter`]; however, if the interpreter is a virtual environment itself, then
+/// the base Python executable is the Python executable of the interpreter's base interpreter.
+fn base_python(interpreter: &Interpreter) -> io::Result<PathBuf> {
+    if cfg!(unix) {
+        // On Unix, follow symlinks to resolve the base interpreter, since the Python executable in
+        // a virtual environment is a symlink to the base interpreter.
+        uv_fs::canonicalize_executable(interpreter.sys_executable())
+    } else if cfg!(windows) {
+        // On Windows, follow `virtualenv`. If we're in a virtual environment, use
+        // `sys._base_executable` if it exists; if not, use `sys.base_prefix`. For example, with
+        // Python installed from the Windows Store, `sys.base_prefix` is slightly "incorrect".
+        //
+        // If we're _not_ in a virtual environment, use the interpreter's executable, since it's
+        // already a "system Python". We canonicalize the path to ensure that it's real and
+        // consistent, though we don't expect any symlinks on Windows.
+        if interpreter.is_virtualenv() {
+            if let Some(base_executable) = interpreter.base_executable() {
+                Ok(base_executable.to_path_buf())
+            } else {
+                // Assume `python.exe`, though the exact executable name is never used (below) on
+                // Windows, only its parent directory.
+                Ok(interpreter.base_prefix().join("python.exe"))
+            }
+        } else {
+            uv_fs::canonicalize_executable(interpreter.sys_executable())
+        }
+    } else {
+        unimplemented!("Only Windows and Unix are supported")
+    }
+}
+
+/// Write the Python executables into the `bin` (or `Scripts`) directory, as symlinks to (or
+/// copies of) the base interpreter on Unix, and as launcher shims on Windows.
+#[cfg_attr(windows, allow(unused_variables))]
+fn write_executables(
+    scripts: &Path,
+    interpreter: &Interpreter,
+    base_python: &Path,
+    copies: bool,
+) -> Result<(), Error> {
+    #[cfg(unix)]
+    {
+        use fs_err::os::unix::fs::symlink;
+
+        let executable = scripts.join(format!("python{EXE_SUFFIX}"));
+
+        let aliases = [
+            scripts.join(format!("python{}", interpreter.python_major())),
+            scripts.join(format!(
+                "python{}.{}",
+                interpreter.python_major(),
+                interpreter.python_minor(),
+            )),
+        ];
+        if copies {
+            // Copy the executable itself, such that the environment survives being moved to a
+            // filesystem (or container image) on which the symlinks would dangle.
+            fs::copy(base_python, &executable)?;
+            for alias in aliases {
+                fs::copy(base_python, alias)?;
+            }
+        } else {
+            symlink(base_python, &executable)?;
+            for alias in aliases {
+                symlink("python", alias)?;
+            }
+        }
+    }
+
+    // No symlinking on Windows, at least not on a regular non-dev non-admin Windows install.
+    #[cfg(windows)]
+    {
+        // https://github.com/python/cpython/blob/d457345bbc6414db0443819290b04a9a4333313d/Lib/venv/__init__.py#L261-L267
+        // https://github.com/pypa/virtualenv/blob/d9fdf48d69f0d0ca56140cf0381edbb5d6fe09f5/src/virtualenv/create/via_global_ref/builtin/cpython/cpython3.py#L78-L83
+        // There's two kinds of applications on windows: Those that allocate a console (python.exe) and those that
+        // don't because they use window(s) (pythonw.exe).
+        for python_exe in ["python.exe", "pythonw.exe"] {
+            let shim = interpreter
+                .stdlib()
+                .join("venv")
+                .join("scripts")
+                .join("nt")
+                .join(python_exe);
+            match fs_err::copy(shim, scripts.join(python_exe)) {
+                Ok(_) => {}
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                    let launcher = match python_exe {
+                        "python.exe" => "venvwlauncher.exe",
+                        "pythonw.exe" => "venvwlauncher.exe",
+                        _ => unreachable!(),
+                    };
+
+                    // If `python.exe` doesn't exist, try the `venvlaucher.exe` shim.
+                    let shim = interpreter
+                        .stdlib()
+                        .join("venv")
+                        .join("scripts")
+                        .join("nt")
+                        .join(launcher);
+
+                    // If the `venvwlauncher.exe` shim doesn't exist, then on Conda at least, we
+                    // can look for it next to the Python executable itself.
+                    match fs_err::copy(shim, scripts.join(python_exe)) {
+                        Ok(_) => {}
+                        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                            let shim = base_python.with_file_name(launcher);
+                            fs_err::copy(shim, scripts.join(python_exe))?;
+                        }
+                        Err(err) => {
+                            return Err(err.into());
+                        }
+                    }
+                }
+                Err(err) => {
+                    return Err(err.into());
+                }
+            }
+        }
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        compile_error!("Only Windows and Unix are supported")
+    }
+
+    Ok(())
+}
+
+/// Per PEP 405, the Python `home` is the parent directory of the interpreter.
+fn python_home(base_python: &Path) -> io::Result<String> {
+    Ok(base_python
+        .parent()
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                "The Python interpreter needs to have a parent directory",
+            )
+        })?
+        .simplified_display()
+        .to_string())
+}
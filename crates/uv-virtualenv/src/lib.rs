@@ -1,12 +1,13 @@
 use std::io;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use platform_tags::PlatformError;
 use thiserror::Error;
 
+use uv_fs::Simplified;
 use uv_interpreter::{Interpreter, PythonEnvironment};
 
-pub use crate::bare::create_bare_venv;
+pub use crate::bare::{create_bare_venv, upgrade_bare_venv};
 
 mod bare;
 
@@ -20,6 +21,10 @@ pub enum Error {
     Platform(#[from] PlatformError),
     #[error("Reserved key used for pyvenv.cfg: {0}")]
     ReservedConfigKey(String),
+    #[error("No virtualenv found at `{}`", _0.user_display())]
+    MissingVirtualenv(PathBuf),
+    #[error("Can't upgrade a Python {existing} virtualenv to Python {requested} in-place; only patch upgrades are supported")]
+    IncompatibleUpgrade { existing: String, requested: String },
 }
 
 /// The value to use for the shell prompt when inside a virtual environment.
@@ -51,6 +56,7 @@ pub fn create_venv(
     interpreter: Interpreter,
     prompt: Prompt,
     system_site_packages: bool,
+    copies: bool,
     extra_cfg: Vec<(String, String)>,
 ) -> Result<PythonEnvironment, Error> {
     // Create the virtualenv at the given location.
@@ -59,6 +65,7 @@ pub fn create_venv(
         &interpreter,
         prompt,
         system_site_packages,
+        copies,
         extra_cfg,
     )?;
 
@@ -66,3 +73,12 @@ pub fn create_venv(
     let interpreter = interpreter.with_virtualenv(virtualenv);
     Ok(PythonEnvironment::from_interpreter(interpreter))
 }
+
+/// Upgrade an existing virtualenv in-place to use the given interpreter.
+pub fn upgrade_venv(location: &Path, interpreter: Interpreter) -> Result<PythonEnvironment, Error> {
+    let virtualenv = upgrade_bare_venv(location, &interpreter)?;
+
+    // Create the corresponding `PythonEnvironment`.
+    let interpreter = interpreter.with_virtualenv(virtualenv);
+    Ok(PythonEnvironment::from_interpreter(interpreter))
+}
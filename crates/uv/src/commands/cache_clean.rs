@@ -3,7 +3,7 @@ use std::fmt::Write;
 use anyhow::{Context, Result};
 use owo_colors::OwoColorize;
 
-use uv_cache::Cache;
+use uv_cache::{Cache, RemovalMode};
 use uv_fs::Simplified;
 use uv_normalize::PackageName;
 
@@ -11,8 +11,12 @@ use crate::commands::{human_readable_bytes, ExitStatus};
 use crate::printer::Printer;
 
 /// Clear the cache, removing all entries or those linked to specific packages.
+///
+/// Unless `force` is set, the entries for pinned packages are preserved.
 pub(crate) fn cache_clean(
     packages: &[PackageName],
+    dry_run: bool,
+    force: bool,
     cache: &Cache,
     printer: Printer,
 ) -> Result<ExitStatus> {
@@ -25,16 +29,26 @@ pub(crate) fn cache_clean(
         return Ok(ExitStatus::Success);
     }
 
+    let (mode, removed) = if dry_run {
+        (RemovalMode::Preview, "Would remove")
+    } else {
+        (RemovalMode::Remove, "Removed")
+    };
+
     if packages.is_empty() {
         writeln!(
             printer.stderr(),
-            "Clearing cache at: {}",
+            "{} cache at: {}",
+            if dry_run { "Would clear" } else { "Clearing" },
             cache.root().user_display().cyan()
         )?;
 
-        let summary = cache.clear().with_context(|| {
-            format!("Failed to clear cache at: {}", cache.root().user_display())
-        })?;
+        let summary = if force {
+            cache.clear_all(mode)
+        } else {
+            cache.clear(mode)
+        }
+        .with_context(|| format!("Failed to clear cache at: {}", cache.root().user_display()))?;
 
         // Write a summary of the number of files and directories removed.
         match (summary.num_files, summary.num_dirs) {
@@ -42,16 +56,16 @@ pub(crate) fn cache_clean(
                 write!(printer.stderr(), "No cache entries found")?;
             }
             (0, 1) => {
-                write!(printer.stderr(), "Removed 1 directory")?;
+                write!(printer.stderr(), "{removed} 1 directory")?;
             }
             (0, num_dirs_removed) => {
-                write!(printer.stderr(), "Removed {num_dirs_removed} directories")?;
+                write!(printer.stderr(), "{removed} {num_dirs_removed} directories")?;
             }
             (1, _) => {
-                write!(printer.stderr(), "Removed 1 file")?;
+                write!(printer.stderr(), "{removed} 1 file")?;
             }
             (num_files_removed, _) => {
-                write!(printer.stderr(), "Removed {num_files_removed} files")?;
+                write!(printer.stderr(), "{removed} {num_files_removed} files")?;
             }
         }
 
@@ -68,8 +82,18 @@ pub(crate) fn cache_clean(
 
         writeln!(printer.stderr())?;
     } else {
+        let pins = cache.pins()?;
         for package in packages {
-            let summary = cache.remove(package)?;
+            if !force && pins.contains(package) {
+                writeln!(
+                    printer.stderr(),
+                    "Skipping pinned package {} (use `--force` to remove)",
+                    package.cyan()
+                )?;
+                continue;
+            }
+
+            let summary = cache.remove(package, mode)?;
 
             // Write a summary of the number of files and directories removed.
             match (summary.num_files, summary.num_dirs) {
@@ -83,24 +107,24 @@ pub(crate) fn cache_clean(
                 (0, 1) => {
                     write!(
                         printer.stderr(),
-                        "Removed 1 directory for {}",
+                        "{removed} 1 directory for {}",
                         package.cyan()
                     )?;
                 }
                 (0, num_dirs_removed) => {
                     write!(
                         printer.stderr(),
-                        "Removed {num_dirs_removed} directories for {}",
+                        "{removed} {num_dirs_removed} directories for {}",
                         package.cyan()
                     )?;
                 }
                 (1, _) => {
-                    write!(printer.stderr(), "Removed 1 file for {}", package.cyan())?;
+                    write!(printer.stderr(), "{removed} 1 file for {}", package.cyan())?;
                 }
                 (num_files_removed, _) => {
                     write!(
                         printer.stderr(),
-                        "Removed {num_files_removed} files for {}",
+                        "{removed} {num_files_removed} files for {}",
                         package.cyan()
                     )?;
                 }
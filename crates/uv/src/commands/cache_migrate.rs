@@ -0,0 +1,66 @@
+use std::fmt::Write;
+
+use anyhow::{Context, Result};
+use owo_colors::OwoColorize;
+
+use uv_cache::Cache;
+use uv_fs::Simplified;
+
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+/// Migrate entries from outdated cache buckets into their current versions.
+pub(crate) fn cache_migrate(cache: &Cache, printer: Printer) -> Result<ExitStatus> {
+    if !cache.root().exists() {
+        writeln!(
+            printer.stderr(),
+            "No cache found at: {}",
+            cache.root().user_display().cyan()
+        )?;
+        return Ok(ExitStatus::Success);
+    }
+
+    writeln!(
+        printer.stderr(),
+        "Migrating cache at: {}",
+        cache.root().user_display().cyan()
+    )?;
+
+    let summary = cache.migrate().with_context(|| {
+        format!(
+            "Failed to migrate cache at: {}",
+            cache.root().user_display()
+        )
+    })?;
+
+    // Write a summary of the number of entries migrated.
+    match (summary.num_buckets, summary.num_entries) {
+        (0, _) => {
+            write!(printer.stderr(), "No outdated cache buckets found")?;
+        }
+        (_, 1) => {
+            write!(printer.stderr(), "Migrated 1 entry")?;
+        }
+        (_, num_entries) => {
+            write!(printer.stderr(), "Migrated {num_entries} entries")?;
+        }
+    }
+
+    // If any, write a summary of the entries that couldn't be migrated.
+    if summary.num_discarded > 0 {
+        let s = if summary.num_discarded == 1 {
+            "y"
+        } else {
+            "ies"
+        };
+        write!(
+            printer.stderr(),
+            " ({} discarded)",
+            format!("{} entr{s}", summary.num_discarded).yellow()
+        )?;
+    }
+
+    writeln!(printer.stderr())?;
+
+    Ok(ExitStatus::Success)
+}
@@ -0,0 +1,57 @@
+use std::fmt::Write;
+
+use anyhow::{Context, Result};
+use owo_colors::OwoColorize;
+
+use uv_cache::Cache;
+use uv_fs::Simplified;
+use uv_normalize::PackageName;
+
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+/// Pin packages in the cache, protecting their entries against eviction.
+pub(crate) fn cache_pin(
+    packages: Vec<PackageName>,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    for package in packages {
+        let pinned = cache.pin(package.clone()).with_context(|| {
+            format!(
+                "Failed to pin package in cache at: {}",
+                cache.root().user_display()
+            )
+        })?;
+        if pinned {
+            writeln!(printer.stderr(), "Pinned {}", package.cyan())?;
+        } else {
+            writeln!(printer.stderr(), "{} is already pinned", package.cyan())?;
+        }
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// Unpin packages in the cache.
+pub(crate) fn cache_unpin(
+    packages: &[PackageName],
+    cache: &Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    for package in packages {
+        let unpinned = cache.unpin(package).with_context(|| {
+            format!(
+                "Failed to unpin package in cache at: {}",
+                cache.root().user_display()
+            )
+        })?;
+        if unpinned {
+            writeln!(printer.stderr(), "Unpinned {}", package.cyan())?;
+        } else {
+            writeln!(printer.stderr(), "{} is not pinned", package.cyan())?;
+        }
+    }
+
+    Ok(ExitStatus::Success)
+}
@@ -1,16 +1,24 @@
 use std::fmt::Write;
+use std::time::Duration;
 
 use anyhow::{Context, Result};
 use owo_colors::OwoColorize;
+use tracing::debug;
 
-use uv_cache::Cache;
+use uv_cache::{Cache, RemovalMode};
 use uv_fs::Simplified;
 
 use crate::commands::{human_readable_bytes, ExitStatus};
 use crate::printer::Printer;
 
-/// Prune all unreachable objects from the cache.
-pub(crate) fn cache_prune(cache: &Cache, printer: Printer) -> Result<ExitStatus> {
+/// Prune all unreachable objects from the cache, along with any packages that haven't been used
+/// within the `unused_for` duration.
+pub(crate) fn cache_prune(
+    dry_run: bool,
+    unused_for: Option<Duration>,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
     if !cache.root().exists() {
         writeln!(
             printer.stderr(),
@@ -20,15 +28,31 @@ pub(crate) fn cache_prune(cache: &Cache, printer: Printer) -> Result<ExitStatus>
         return Ok(ExitStatus::Success);
     }
 
+    let (mode, removed) = if dry_run {
+        (RemovalMode::Preview, "Would remove")
+    } else {
+        (RemovalMode::Remove, "Removed")
+    };
+
     writeln!(
         printer.stderr(),
-        "Pruning cache at: {}",
+        "{} cache at: {}",
+        if dry_run { "Would prune" } else { "Pruning" },
         cache.root().user_display().cyan()
     )?;
 
-    let summary = cache
-        .prune()
+    let _lock = cache
+        .lock()
+        .with_context(|| format!("Failed to lock cache at: {}", cache.root().user_display()))?;
+
+    let mut summary = cache
+        .prune(mode)
         .with_context(|| format!("Failed to prune cache at: {}", cache.root().user_display()))?;
+    if let Some(unused_for) = unused_for {
+        summary += cache.prune_unused(unused_for, mode).with_context(|| {
+            format!("Failed to prune cache at: {}", cache.root().user_display())
+        })?;
+    }
 
     // Write a summary of the number of files and directories removed.
     match (summary.num_files, summary.num_dirs) {
@@ -36,16 +60,16 @@ pub(crate) fn cache_prune(cache: &Cache, printer: Printer) -> Result<ExitStatus>
             write!(printer.stderr(), "No unused entries found")?;
         }
         (0, 1) => {
-            write!(printer.stderr(), "Removed 1 directory")?;
+            write!(printer.stderr(), "{removed} 1 directory")?;
         }
         (0, num_dirs_removed) => {
-            write!(printer.stderr(), "Removed {num_dirs_removed} directories")?;
+            write!(printer.stderr(), "{removed} {num_dirs_removed} directories")?;
         }
         (1, _) => {
-            write!(printer.stderr(), "Removed 1 file")?;
+            write!(printer.stderr(), "{removed} 1 file")?;
         }
         (num_files_removed, _) => {
-            write!(printer.stderr(), "Removed {num_files_removed} files")?;
+            write!(printer.stderr(), "{removed} {num_files_removed} files")?;
         }
     }
 
@@ -64,3 +88,19 @@ pub(crate) fn cache_prune(cache: &Cache, printer: Printer) -> Result<ExitStatus>
 
     Ok(ExitStatus::Success)
 }
+
+/// Run a bounded, incremental collection on the cache under the configured automatic pruning
+/// policy.
+pub(crate) fn cache_auto_prune(cache: &Cache) -> Result<ExitStatus> {
+    let summary = cache.auto_prune().with_context(|| {
+        format!(
+            "Failed to automatically prune cache at: {}",
+            cache.root().user_display()
+        )
+    })?;
+    debug!(
+        "Automatically pruned {} files and {} directories from the cache",
+        summary.num_files, summary.num_dirs
+    );
+    Ok(ExitStatus::Success)
+}
@@ -0,0 +1,167 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use distribution_types::{
+    BuiltDist, CachedDist, Dist, FileLocation, InstalledDist, Name, SourceDist,
+};
+use install_wheel_rs::linker::LinkMode;
+use uv_fs::Simplified;
+use uv_installer::PostInstallHook;
+use uv_warnings::warn_user;
+
+/// A machine-readable report of the changes made to an environment (as in `--report`).
+#[derive(Debug, Serialize)]
+pub(crate) struct InstallReport {
+    /// The version of the report format.
+    version: &'static str,
+    /// The link mode used to install packages from the cache, accounting for any fallback (e.g.,
+    /// from `clone` to `copy`), if any were installed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    link_mode: Option<String>,
+    /// The distributions that were installed.
+    installed: Vec<InstalledEntry>,
+    /// The distributions that were removed.
+    removed: Vec<RemovedEntry>,
+}
+
+/// A distribution that was installed into the environment.
+#[derive(Debug, Serialize)]
+struct InstalledEntry {
+    name: String,
+    version: String,
+    /// The URL from which the distribution was fetched, if known.
+    url: Option<String>,
+    /// The SHA-256 hash of the distribution, as computed when it was downloaded or as reported by
+    /// the index, if known.
+    sha256: Option<String>,
+    /// Whether the distribution was linked from the cache, without being fetched.
+    cached: bool,
+}
+
+/// A distribution that was removed from the environment.
+#[derive(Debug, Serialize)]
+struct RemovedEntry {
+    name: String,
+    version: String,
+}
+
+impl InstallReport {
+    /// Create an empty report for an installation with the given link mode.
+    pub(crate) fn new(link_mode: LinkMode) -> Self {
+        Self {
+            version: "1",
+            link_mode: Some(link_mode.to_string()),
+            installed: Vec::new(),
+            removed: Vec::new(),
+        }
+    }
+
+    /// Create an empty report, for an operation that installs nothing (e.g., an uninstallation,
+    /// or an installation in which all requirements are already satisfied).
+    pub(crate) fn empty() -> Self {
+        Self {
+            version: "1",
+            link_mode: None,
+            installed: Vec::new(),
+            removed: Vec::new(),
+        }
+    }
+
+    /// Record the link mode that was used to install packages, after any fallback.
+    pub(crate) fn set_link_mode(&mut self, link_mode: LinkMode) {
+        self.link_mode = Some(link_mode.to_string());
+    }
+
+    /// Record an installed distribution, along with the distribution from which it was fetched,
+    /// if known.
+    pub(crate) fn add_installed(&mut self, dist: &CachedDist, source: Option<&Dist>, cached: bool) {
+        let url = match dist {
+            CachedDist::Registry(_) => source.and_then(source_url),
+            CachedDist::Url(dist) => Some(dist.url.to_string()),
+        };
+        // Prefer the digest computed when the distribution was downloaded, since direct URLs
+        // don't provide any hashes.
+        let sha256 = dist
+            .sha256()
+            .or_else(|| {
+                source
+                    .and_then(Dist::file)
+                    .and_then(|file| file.hashes.sha256.as_deref())
+            })
+            .map(ToString::to_string);
+        self.installed.push(InstalledEntry {
+            name: dist.name().to_string(),
+            version: dist.filename().version.to_string(),
+            url,
+            sha256,
+            cached,
+        });
+    }
+
+    /// Record a removed distribution.
+    pub(crate) fn add_removed(&mut self, dist: &InstalledDist) {
+        self.removed.push(RemovedEntry {
+            name: dist.name().to_string(),
+            version: dist.version().to_string(),
+        });
+    }
+
+    /// Write the report to the given path (as in `--report`), if any, and pass it to the
+    /// post-install hook, if any, provided that the environment at `root` was modified.
+    ///
+    /// Should be called once the changes have been reported to the user.
+    pub(crate) fn emit(
+        mut self,
+        path: Option<&Path>,
+        hook: Option<&dyn PostInstallHook>,
+        root: &Path,
+    ) -> Result<()> {
+        self.installed
+            .sort_unstable_by(|a, b| a.name.cmp(&b.name).then_with(|| a.version.cmp(&b.version)));
+        self.removed
+            .sort_unstable_by(|a, b| a.name.cmp(&b.name).then_with(|| a.version.cmp(&b.version)));
+        let contents = serde_json::to_string_pretty(&self)? + "\n";
+        if let Some(path) = path {
+            fs_err::write(path, &contents)
+                .with_context(|| format!("Failed to write report to: {}", path.user_display()))?;
+        }
+        // The environment has already been modified, so a failing hook is reported as a warning,
+        // rather than an error.
+        if let Some(hook) = hook {
+            if !self.installed.is_empty() || !self.removed.is_empty() {
+                if let Err(err) = hook.run(root, &contents) {
+                    warn_user!("{:#}", anyhow::Error::from(err));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Return the URL of the file from which a distribution is fetched.
+fn source_url(dist: &Dist) -> Option<String> {
+    match dist {
+        Dist::Built(BuiltDist::Registry(wheel)) => file_url(&wheel.file.url),
+        Dist::Source(SourceDist::Registry(sdist)) => file_url(&sdist.file.url),
+        Dist::Built(BuiltDist::DirectUrl(wheel)) => Some(wheel.url.to_string()),
+        Dist::Built(BuiltDist::Path(wheel)) => Some(wheel.url.to_string()),
+        Dist::Source(SourceDist::DirectUrl(sdist)) => Some(sdist.url.to_string()),
+        Dist::Source(SourceDist::Git(sdist)) => Some(sdist.url.to_string()),
+        Dist::Source(SourceDist::Path(sdist)) => Some(sdist.url.to_string()),
+    }
+}
+
+/// Return the absolute URL of a file in a registry.
+fn file_url(location: &FileLocation) -> Option<String> {
+    match location {
+        FileLocation::RelativeUrl(base, url) => pypi_types::base_url_join_relative(base, url)
+            .ok()
+            .map(|url| url.to_string()),
+        FileLocation::AbsoluteUrl(url) => Some(url.clone()),
+        FileLocation::Path(path) => url::Url::from_file_path(path)
+            .ok()
+            .map(|url| url.to_string()),
+    }
+}
@@ -2,12 +2,18 @@ use std::time::Duration;
 use std::{fmt::Display, fmt::Write, process::ExitCode};
 
 use anyhow::Context;
+use itertools::Itertools;
 use owo_colors::OwoColorize;
 
 pub(crate) use cache_clean::cache_clean;
+pub(crate) use cache_dedup::cache_dedup;
 pub(crate) use cache_dir::cache_dir;
-pub(crate) use cache_prune::cache_prune;
-use distribution_types::InstalledMetadata;
+pub(crate) use cache_migrate::cache_migrate;
+pub(crate) use cache_pin::{cache_pin, cache_unpin};
+pub(crate) use cache_prune::{cache_auto_prune, cache_prune};
+use distribution_types::{
+    CachedDist, Dist, DistributionMetadata, InstalledDist, InstalledMetadata, Name,
+};
 pub(crate) use pip_check::pip_check;
 pub(crate) use pip_compile::{extra_name_with_clap_error, pip_compile};
 pub(crate) use pip_freeze::pip_freeze;
@@ -16,6 +22,11 @@ pub(crate) use pip_list::pip_list;
 pub(crate) use pip_show::pip_show;
 pub(crate) use pip_sync::pip_sync;
 pub(crate) use pip_uninstall::pip_uninstall;
+pub(crate) use pip_verify::pip_verify;
+pub(crate) use pip_why::pip_why;
+pub(crate) use python_find::python_find;
+pub(crate) use python_list::python_list;
+use requirements_txt::EditableRequirement;
 #[cfg(feature = "self-update")]
 pub(crate) use self_update::self_update;
 use uv_cache::Cache;
@@ -23,14 +34,19 @@ use uv_fs::Simplified;
 use uv_installer::compile_tree;
 use uv_interpreter::PythonEnvironment;
 use uv_normalize::PackageName;
+use uv_warnings::warn_user;
 pub(crate) use venv::venv;
 pub(crate) use version::version;
 
 use crate::printer::Printer;
 
 mod cache_clean;
+mod cache_dedup;
 mod cache_dir;
+mod cache_migrate;
+mod cache_pin;
 mod cache_prune;
+mod install_report;
 mod pip_check;
 mod pip_compile;
 mod pip_freeze;
@@ -39,6 +55,10 @@ mod pip_list;
 mod pip_show;
 mod pip_sync;
 mod pip_uninstall;
+mod pip_verify;
+mod pip_why;
+mod python_find;
+mod python_list;
 mod reporters;
 #[cfg(feature = "self-update")]
 mod self_update;
@@ -70,6 +90,26 @@ impl From<ExitStatus> for ExitCode {
     }
 }
 
+/// Warn if a distribution that's about to be removed was installed by another tool (e.g., `conda`
+/// or a system package manager), as indicated by its `INSTALLER` file, since that tool may still
+/// consider it installed.
+pub(super) fn warn_foreign_installer(dist: &InstalledDist) {
+    let Ok(Some(installer)) = dist.installer() else {
+        return;
+    };
+    let installer = installer.trim();
+    if installer.is_empty()
+        || installer.eq_ignore_ascii_case("uv")
+        || installer.eq_ignore_ascii_case("pip")
+    {
+        return;
+    }
+    warn_user!(
+        "Uninstalling {}, which was installed by `{installer}`. This may leave `{installer}` in an inconsistent state.",
+        format!("{}=={}", dist.name(), dist.version()).bold(),
+    );
+}
+
 /// Format a duration as a human-readable string, Cargo-style.
 pub(super) fn elapsed(duration: Duration) -> String {
     let secs = duration.as_secs();
@@ -107,6 +147,121 @@ pub(super) struct DryRunEvent<T: Display> {
     kind: ChangeEventKind,
 }
 
+/// Report the changes that an installation would make to the environment (as in `--dry-run`),
+/// given the distributions that would be downloaded, linked from the cache, built from editables
+/// (which are identified by their URL, since their metadata is only known once built), and
+/// removed.
+pub(super) fn report_dry_run_changes(
+    remote: &[Dist],
+    cached: &[CachedDist],
+    editables: &[EditableRequirement],
+    removed: &[&InstalledDist],
+    printer: Printer,
+) -> std::fmt::Result {
+    if !remote.is_empty() {
+        let s = if remote.len() == 1 { "" } else { "s" };
+        writeln!(
+            printer.stderr(),
+            "{}",
+            format!(
+                "Would download {}",
+                format!("{} package{}", remote.len(), s).bold(),
+            )
+            .dimmed()
+        )?;
+    }
+
+    // Source distributions and editables would additionally need to be built.
+    let builds = remote
+        .iter()
+        .filter(|dist| matches!(dist, Dist::Source(_)))
+        .count()
+        + editables.len();
+    if builds > 0 {
+        let s = if builds == 1 { "" } else { "s" };
+        writeln!(
+            printer.stderr(),
+            "{}",
+            format!("Would build {}", format!("{builds} package{s}").bold()).dimmed()
+        )?;
+    }
+
+    if !removed.is_empty() {
+        let s = if removed.len() == 1 { "" } else { "s" };
+        writeln!(
+            printer.stderr(),
+            "{}",
+            format!(
+                "Would uninstall {}",
+                format!("{} package{}", removed.len(), s).bold(),
+            )
+            .dimmed()
+        )?;
+    }
+
+    let installs = remote.len() + cached.len() + editables.len();
+    if installs > 0 {
+        let s = if installs == 1 { "" } else { "s" };
+        writeln!(
+            printer.stderr(),
+            "{}",
+            format!("Would install {}", format!("{installs} package{s}").bold()).dimmed()
+        )?;
+    }
+
+    for event in removed
+        .iter()
+        .map(|distribution| DryRunEvent {
+            name: distribution.name().clone(),
+            version: distribution.installed_version().to_string(),
+            kind: ChangeEventKind::Removed,
+        })
+        .chain(remote.iter().map(|distribution| DryRunEvent {
+            name: distribution.name().clone(),
+            version: distribution.version_or_url().to_string(),
+            kind: ChangeEventKind::Added,
+        }))
+        .chain(cached.iter().map(|distribution| DryRunEvent {
+            name: distribution.name().clone(),
+            version: distribution.installed_version().to_string(),
+            kind: ChangeEventKind::Added,
+        }))
+        .sorted_unstable_by(|a, b| a.name.cmp(&b.name).then_with(|| a.kind.cmp(&b.kind)))
+    {
+        match event.kind {
+            ChangeEventKind::Added => {
+                writeln!(
+                    printer.stderr(),
+                    " {} {}{}",
+                    "+".green(),
+                    event.name.as_ref().bold(),
+                    event.version.dimmed()
+                )?;
+            }
+            ChangeEventKind::Removed => {
+                writeln!(
+                    printer.stderr(),
+                    " {} {}{}",
+                    "-".red(),
+                    event.name.as_ref().bold(),
+                    event.version.dimmed()
+                )?;
+            }
+        }
+    }
+
+    for editable in editables {
+        writeln!(
+            printer.stderr(),
+            " {} {}",
+            "+".green(),
+            format!("-e {editable}").bold()
+        )?;
+    }
+
+    Ok(())
+}
+
 #[derive(Debug, Clone, Copy, clap::ValueEnum)]
 pub(crate) enum VersionFormat {
     Text,
@@ -128,23 +283,31 @@ pub(crate) enum ListFormat {
 /// Compile all Python source files in site-packages to bytecode, to speed up the
 /// initial run of any subsequent executions.
 ///
+/// Bytecode is recorded in the RECORD of the `installed` distributions only.
+///
 /// See the `--compile` option on `pip sync` and `pip install`.
 pub(super) async fn compile_bytecode(
     venv: &PythonEnvironment,
     cache: &Cache,
+    installed: &[PackageName],
     printer: Printer,
 ) -> anyhow::Result<()> {
     let start = std::time::Instant::now();
     let mut files = 0;
     for site_packages in venv.site_packages() {
-        files += compile_tree(site_packages, venv.python_executable(), cache.root())
-            .await
-            .with_context(|| {
-                format!(
-                    "Failed to bytecode-compile Python file in: {}",
-                    site_packages.user_display()
-                )
-            })?;
+        files += compile_tree(
+            site_packages,
+            venv.python_executable(),
+            cache.root(),
+            installed,
+        )
+        .await
+        .with_context(|| {
+            format!(
+                "Failed to bytecode-compile Python file in: {}",
+                site_packages.user_display()
+            )
+        })?;
     }
     let s = if files == 1 { "" } else { "s" };
     writeln!(
@@ -1,43 +1,56 @@
 use std::borrow::Cow;
 use std::env;
 use std::fmt::Write;
+use std::future::Future;
 use std::io::stdout;
 use std::ops::Deref;
 use std::path::Path;
 use std::str::FromStr;
+use std::time::Duration;
 
 use anstream::{eprint, AutoStream, StripStream};
 use anyhow::{anyhow, Context, Result};
 use chrono::{DateTime, Utc};
 use itertools::Itertools;
 use owo_colors::OwoColorize;
-use tempfile::tempdir_in;
+use serde::{Deserialize, Serialize};
 use tracing::debug;
+use url::Url;
 
-use distribution_types::{IndexLocations, LocalEditable, LocalEditables, Verbatim};
+use distribution_types::{
+    FlatIndexLocation, IndexLocations, LocalEditable, LocalEditables, Verbatim,
+};
+use pep508_rs::{Requirement, RequirementsTxtRequirement, VersionOrUrl};
 use platform_tags::Tags;
+use pypi_types::Hashes;
 use requirements_txt::EditableRequirement;
 use uv_auth::{KeyringProvider, GLOBAL_AUTH_STORE};
-use uv_cache::Cache;
+use uv_cache::{Cache, CacheBucket, CacheEntry, Timestamp};
 use uv_client::{
-    BaseClientBuilder, Connectivity, FlatIndex, FlatIndexClient, RegistryClientBuilder,
+    BaseClientBuilder, ConnectionOptions, Connectivity, FlatIndex, FlatIndexClient,
+    RegistryClientBuilder, RetryPolicy,
 };
 use uv_dispatch::BuildDispatch;
 use uv_fs::Simplified;
 use uv_installer::Downloader;
-use uv_interpreter::{find_best_python, PythonEnvironment, PythonVersion};
+use uv_interpreter::{
+    find_best_python, Interpreter, PythonEnvironment, PythonTarget, PythonVersion,
+};
 use uv_normalize::{ExtraName, PackageName};
 use uv_requirements::{
     upgrade::read_lockfile, ExtrasSpecification, LookaheadResolver, NamedRequirementsResolver,
     RequirementsSource, RequirementsSpecification, SourceTreeResolver,
 };
 use uv_resolver::{
-    AnnotationStyle, DependencyMode, DisplayResolutionGraph, Exclusions, InMemoryIndex, Manifest,
-    OptionsBuilder, PreReleaseMode, PythonRequirement, ResolutionMode, Resolver,
+    AnnotationStyle, DependencyMode, Diagnostic, DisplayResolutionGraph,
+    DisplayUniversalResolution, Exclusions, ForkEnvironment, InMemoryIndex, LocalVersionPreference,
+    Lock, Manifest, OptionsBuilder, PreReleaseMode, Preference, PrefetchOptions, PythonRequirement,
+    ResolutionGraph, ResolutionMode, ResolveError, Resolver, UniversalResolution, YankedMode,
+    LATEST_PYTHON_MINOR,
 };
 use uv_types::{
-    BuildIsolation, ConfigSettings, Constraints, EmptyInstalledPackages, InFlight, IndexStrategy,
-    NoBinary, NoBuild, Overrides, SetupPyStrategy, Upgrade,
+    BuildIsolation, Concurrency, ConfigSettings, Constraints, EmptyInstalledPackages, InFlight,
+    IndexStrategy, NoBinary, NoBuild, Overrides, RequiredHashes, SetupPyStrategy, Upgrade,
 };
 use uv_warnings::warn_user;
 
@@ -56,8 +69,10 @@ pub(crate) async fn pip_compile(
     resolution_mode: ResolutionMode,
     prerelease_mode: PreReleaseMode,
     dependency_mode: DependencyMode,
+    yanked_mode: YankedMode,
     upgrade: Upgrade,
     generate_hashes: bool,
+    require_hashes: bool,
     no_emit_packages: Vec<PackageName>,
     include_extras: bool,
     include_annotations: bool,
@@ -74,11 +89,28 @@ pub(crate) async fn pip_compile(
     connectivity: Connectivity,
     no_build_isolation: bool,
     no_build: NoBuild,
+    no_binary: NoBinary,
     python_version: Option<PythonVersion>,
+    python_env: Option<&Path>,
+    universal: bool,
+    max_python_version: Option<PythonVersion>,
     exclude_newer: Option<DateTime<Utc>>,
+    allow_url_override: bool,
+    local_version_preference: Option<LocalVersionPreference>,
+    exclude: Vec<PackageName>,
+    incremental: bool,
+    max_tried_versions: Option<usize>,
+    max_candidates: Option<usize>,
+    resolution_timeout: Option<Duration>,
+    prefetch: PrefetchOptions,
+    explain: bool,
     annotation_style: AnnotationStyle,
     native_tls: bool,
+    retry_policy: RetryPolicy,
+    proxy: Option<Url>,
+    connection_options: ConnectionOptions,
     quiet: bool,
+    concurrency: Concurrency,
     cache: Cache,
     printer: Printer,
 ) -> Result<ExitStatus> {
@@ -92,9 +124,27 @@ pub(crate) async fn pip_compile(
         ));
     }
 
+    // Identify the requirement files, such that `--incremental` can reuse the decisions from a
+    // previous resolution of the same files, regardless of the working directory.
+    let source_paths = requirements
+        .iter()
+        .chain(constraints)
+        .chain(overrides)
+        .filter_map(|source| match source {
+            RequirementsSource::RequirementsTxt(path)
+            | RequirementsSource::PyprojectToml(path)
+            | RequirementsSource::SetupPy(path)
+            | RequirementsSource::SetupCfg(path) => fs_err::canonicalize(path).ok(),
+            RequirementsSource::Package(_) | RequirementsSource::Editable(_) => None,
+        })
+        .collect::<Vec<_>>();
+
     let client_builder = BaseClientBuilder::new()
         .connectivity(connectivity)
         .native_tls(native_tls)
+        .retry_policy(retry_policy.clone())
+        .proxy(proxy.clone())
+        .connection_options(connection_options.clone())
         .keyring_provider(keyring_provider);
 
     // Read all requirements from the provided sources.
@@ -102,15 +152,17 @@ pub(crate) async fn pip_compile(
         project,
         requirements,
         constraints,
+        sources: constraint_sources,
         overrides,
         editables,
         source_trees,
+        hashes,
         extras: used_extras,
         index_url,
         extra_index_urls,
         no_index,
         find_links,
-        no_binary: _,
+        no_binary: specified_no_binary,
         no_build: specified_no_build,
     } = RequirementsSpecification::from_sources(
         requirements,
@@ -121,6 +173,10 @@ pub(crate) async fn pip_compile(
     )
     .await?;
 
+    // Combine the `--no-binary` and `--no-build` flags.
+    let no_binary = no_binary.combine(specified_no_binary);
+    let no_build = no_build.combine(specified_no_build);
+
     // If all the metadata could be statically resolved, validate that every extra was used. If we
     // need to resolve metadata via PEP 517, we don't know which extras are used until much later.
     if source_trees.is_empty() {
@@ -141,13 +197,91 @@ pub(crate) async fn pip_compile(
         }
     }
 
-    // Find an interpreter to use for building distributions
-    let interpreter = find_best_python(python_version.as_ref(), &cache)?;
-    debug!(
-        "Using Python {} interpreter at {} for builds",
-        interpreter.python_version(),
-        interpreter.sys_executable().user_display().cyan()
-    );
+    // In `--require-hashes` mode, every distribution must be selected from an index that records
+    // its hash. Editables and direct URL requirements can't be hashed, so they're rejected upfront.
+    // Any hashes provided alongside pinned requirements or constraints restrict the selection to
+    // the identified distributions, and are reproduced in the output.
+    let (required_hashes, hashed_preferences) = if require_hashes {
+        if let Some(editable) = editables.first() {
+            return Err(anyhow!(
+                "In `--require-hashes` mode, editable requirements can't be hashed, but found: `{editable}`"
+            ));
+        }
+        let named = requirements
+            .iter()
+            .map(|requirement| match requirement {
+                RequirementsTxtRequirement::Pep508(requirement) => Ok(requirement),
+                RequirementsTxtRequirement::Unnamed(requirement) => Err(anyhow!(
+                    "In `--require-hashes` mode, URL requirements can't be hashed, but found: `{requirement}`"
+                )),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if let Some(requirement) = named
+            .iter()
+            .copied()
+            .chain(&constraints)
+            .chain(&overrides)
+            .find(|requirement| matches!(requirement.version_or_url, Some(VersionOrUrl::Url(_))))
+        {
+            return Err(anyhow!(
+                "In `--require-hashes` mode, URL requirements can't be hashed, but found: `{requirement}`"
+            ));
+        }
+
+        // Requirements without a version specifier (e.g., `foo`, alongside a `foo==1.0.0`
+        // constraint) share the hashes of the pinned entries for the same package.
+        let hashed = named
+            .into_iter()
+            .chain(&constraints)
+            .filter(|requirement| {
+                requirement.version_or_url.is_some() && !hashes.get(requirement).is_empty()
+            })
+            .collect::<Vec<_>>();
+        let required_hashes = RequiredHashes::from_requirements(
+            hashed
+                .iter()
+                .map(|requirement| (*requirement, hashes.get(requirement))),
+        )?;
+        let hashed_preferences = hashed
+            .into_iter()
+            .map(|requirement| {
+                let digests = hashes
+                    .get(requirement)
+                    .iter()
+                    .map(|digest| Hashes::from_str(digest))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Preference::from_requirement(requirement.clone()).with_hashes(digests))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        (required_hashes, hashed_preferences)
+    } else {
+        (RequiredHashes::default(), Vec::new())
+    };
+
+    // Read the target environment, if provided, to stand in for the interpreter during resolution.
+    let python_env = python_env.map(PythonTarget::from_path).transpose()?;
+
+    // Find an interpreter to use for building distributions. If builds are disabled, and the
+    // target environment specifies its platform, it can stand in for the interpreter entirely.
+    let interpreter = match python_env.as_ref() {
+        Some(PythonTarget {
+            markers,
+            platform: Some(platform),
+            ..
+        }) if matches!(no_build, NoBuild::All) => {
+            debug!("Resolving for the target environment without a local interpreter");
+            Interpreter::artificial(platform.clone(), markers.clone())
+        }
+        _ => {
+            let interpreter = find_best_python(python_version.as_ref(), &cache)?;
+            debug!(
+                "Using Python {} interpreter at {} for builds",
+                interpreter.python_version(),
+                interpreter.sys_executable().user_display().cyan()
+            );
+            interpreter
+        }
+    };
 
     if let Some(python_version) = python_version.as_ref() {
         // If the requested version does not match the version we're using warn the user
@@ -169,20 +303,46 @@ pub(crate) async fn pip_compile(
         }
     }
 
+    if let Some(python_env) = python_env.as_ref() {
+        // Source distributions can only be built with a local interpreter, which may differ from
+        // the target environment.
+        if no_build.is_none()
+            && python_env.markers.python_version.version
+                != interpreter.markers().python_version.version
+        {
+            warn_user!(
+                "The target environment requires Python {}; {} will be used to build dependencies instead.",
+                python_env.markers.python_version,
+                interpreter.python_version(),
+            );
+        }
+    }
+
     // Create a shared in-memory index.
     let source_index = InMemoryIndex::default();
 
     // If we're resolving against a different Python version, use a separate index. Source
     // distributions will be built against the installed version, and so the index may contain
     // different package priorities than in the top-level resolution.
-    let top_level_index = if python_version.is_some() {
+    let top_level_index = if python_version.is_some() || python_env.is_some() {
         InMemoryIndexRef::Owned(InMemoryIndex::default())
     } else {
         InMemoryIndexRef::Borrowed(&source_index)
     };
 
+    // Determine the range of Python versions to target in a universal resolution.
+    let python_minor = python_version
+        .as_ref()
+        .map_or(interpreter.python_minor(), PythonVersion::minor);
+    let max_python_minor = max_python_version.as_ref().map_or(
+        LATEST_PYTHON_MINOR.max(interpreter.python_minor()),
+        PythonVersion::minor,
+    );
+
     // Determine the tags, markers, and interpreter to use for resolution.
-    let tags = if let Some(python_version) = python_version.as_ref() {
+    let tags = if let Some(python_env) = python_env.as_ref() {
+        Cow::Owned(python_env.tags()?)
+    } else if let Some(python_version) = python_version.as_ref() {
         Cow::Owned(Tags::from_env(
             interpreter.platform(),
             (python_version.major(), python_version.minor()),
@@ -192,10 +352,18 @@ pub(crate) async fn pip_compile(
     } else {
         Cow::Borrowed(interpreter.tags()?)
     };
-    let markers = python_version.map_or_else(
-        || Cow::Borrowed(interpreter.markers()),
-        |python_version| Cow::Owned(python_version.markers(interpreter.markers())),
-    );
+    let markers = if let Some(python_env) = python_env.as_ref() {
+        Cow::Owned(python_env.markers.clone())
+    } else {
+        python_version.map_or_else(
+            || Cow::Borrowed(interpreter.markers()),
+            |python_version| Cow::Owned(python_version.markers(interpreter.markers())),
+        )
+    };
+    let platform = python_env
+        .as_ref()
+        .and_then(|python_env| python_env.platform.as_ref())
+        .unwrap_or(interpreter.platform());
 
     // Incorporate any index locations from the provided sources.
     let index_locations =
@@ -209,27 +377,176 @@ pub(crate) async fn pip_compile(
     // Initialize the registry client.
     let client = RegistryClientBuilder::new(cache.clone())
         .native_tls(native_tls)
+        .retry_policy(retry_policy.clone())
+        .proxy(proxy.clone())
+        .connection_options(connection_options.clone())
         .connectivity(connectivity)
         .index_urls(index_locations.index_urls())
         .index_strategy(index_strategy)
         .keyring_provider(keyring_provider)
         .markers(&markers)
-        .platform(interpreter.platform())
+        .platform(platform)
         .build();
 
-    // Read the lockfile, if present.
-    let preferences = read_lockfile(output_file, upgrade).await?;
+    // If the output file is a `uv.lock`, emit a lockfile rather than a `requirements.txt`.
+    let lockfile = output_file.is_some_and(Lock::is_lockfile);
+
+    // Resolutions are only reusable if the inputs fully determine the output, which requires
+    // that the available versions are bounded by `--exclude-newer`.
+    let cacheable = exclude_newer.is_some()
+        && matches!(upgrade, Upgrade::None)
+        && editables.is_empty()
+        && source_trees.is_empty()
+        && requirements.iter().all(|requirement| {
+            matches!(
+                requirement,
+                RequirementsTxtRequirement::Pep508(requirement)
+                    if !matches!(requirement.version_or_url, Some(VersionOrUrl::Url(_)))
+            )
+        });
+
+    // Bound any targeted upgrades by the version specifiers provided alongside them.
+    let constraints = constraints
+        .into_iter()
+        .chain(upgrade.constraints().cloned())
+        .collect::<Vec<_>>();
+
+    // If requested, locate the decisions from the previous resolution of the same requirement
+    // files. Requirements provided on the command line aren't tied to any file, so there are no
+    // decisions to reuse.
+    if incremental && source_paths.is_empty() {
+        debug!("Ignoring `--incremental`, as no requirement files were provided");
+    }
+    let decisions_entry = (incremental && !source_paths.is_empty()).then(|| {
+        cache.entry(
+            CacheBucket::Resolutions,
+            "decisions",
+            format!("{}.msgpack", cache_key::digest(&source_paths)),
+        )
+    });
+    let decisions = match decisions_entry.as_ref() {
+        Some(entry) => read_decisions(entry, &upgrade)?,
+        None => Vec::new(),
+    };
+
+    // Read the lockfile, if present, and seed the resolution with the previous decisions as
+    // preferences, such that the resolver tries the previously selected versions first. The
+    // resolution itself is still performed in full. Later preferences take precedence, so any pins
+    // in the lockfile override the previous decisions, and any hashed pins override the lockfile.
+    let preferences = decisions
+        .into_iter()
+        .chain(read_lockfile(output_file, upgrade).await?)
+        .chain(hashed_preferences)
+        .collect::<Vec<_>>();
+
+    // If an identical resolution was previously performed, reuse its output.
+    let resolution_key = if cacheable {
+        ResolutionKey::new(
+            vec![
+                format!("requirements: {requirements:?}"),
+                format!("constraints: {constraints:?}"),
+                format!("overrides: {overrides:?}"),
+                format!("preferences: {preferences:?}"),
+                format!("extras: {extras:?}"),
+                format!("markers: {:?}", &*markers),
+                format!("platform: {platform:?}"),
+                format!(
+                    "tags: {:?}",
+                    python_env.as_ref().map(|python_env| &python_env.tags)
+                ),
+                format!("index-locations: {index_locations:?}"),
+                format!("index-strategy: {index_strategy:?}"),
+                format!("no-build: {no_build:?}"),
+                format!("no-binary: {no_binary:?}"),
+                format!("exclude-newer: {exclude_newer:?}"),
+                format!("resolution: {resolution_mode:?}"),
+                format!("prerelease: {prerelease_mode:?}"),
+                format!("dependency-mode: {dependency_mode:?}"),
+                format!("generate-hashes: {generate_hashes:?}"),
+                format!("no-emit-packages: {no_emit_packages:?}"),
+                format!("include-extras: {include_extras:?}"),
+                format!("include-annotations: {include_annotations:?}"),
+                format!("include-index-url: {include_index_url:?}"),
+                format!("include-find-links: {include_find_links:?}"),
+                format!("include-marker-expression: {include_marker_expression:?}"),
+                format!("annotation-style: {annotation_style:?}"),
+                format!("universal: {universal:?}"),
+                format!("max-python-version: {max_python_minor:?}"),
+                format!("lockfile: {lockfile:?}"),
+                format!("yanked: {yanked_mode:?}"),
+                format!("require-hashes: {require_hashes:?}"),
+                format!("allow-url-override: {allow_url_override:?}"),
+                format!("local-version-preference: {local_version_preference:?}"),
+                format!("exclude: {exclude:?}"),
+                format!("max-tried-versions: {max_tried_versions:?}"),
+                format!("max-candidates: {max_candidates:?}"),
+                format!("resolution-timeout: {resolution_timeout:?}"),
+                format!("prefetch: {prefetch:?}"),
+                format!("explain: {explain:?}"),
+            ],
+            &index_locations,
+        )?
+    } else {
+        None
+    };
+    let resolution_entry = if let Some(resolution_key) = resolution_key {
+        let entry = cache.entry(
+            CacheBucket::Resolutions,
+            "",
+            format!("{}.msgpack", resolution_key.digest()),
+        );
+        if let Some(cached) = read_cached_resolution(&cache, &entry)? {
+            let s = if cached.packages == 1 { "" } else { "s" };
+            writeln!(
+                printer.stderr(),
+                "{}",
+                format!(
+                    "Resolved {} in {}",
+                    format!("{} package{}", cached.packages, s).bold(),
+                    elapsed(start.elapsed())
+                )
+                .dimmed()
+            )?;
+
+            // Replay any diagnostics from the original resolution.
+            for diagnostic in &cached.diagnostics {
+                writeln!(
+                    printer.stderr(),
+                    "{}{} {}",
+                    "warning".yellow().bold(),
+                    ":".bold(),
+                    diagnostic.bold()
+                )?;
+            }
+
+            let mut writer = OutputWriter::new(!quiet || output_file.is_none(), output_file)?;
+            if include_header {
+                write_header(
+                    &mut writer,
+                    include_index_url,
+                    include_find_links,
+                    custom_compile_command,
+                )?;
+            }
+            write!(writer, "{}", cached.output)?;
+
+            return Ok(ExitStatus::Success);
+        }
+        Some(entry)
+    } else {
+        None
+    };
 
     // Collect constraints and overrides.
-    let constraints = Constraints::from_requirements(constraints);
-    let overrides = Overrides::from_requirements(overrides);
+    let constraints = Constraints::from_requirements(constraints).with_sources(constraint_sources);
+    let overrides = Overrides::from_requirements(overrides).with_excluded(exclude);
 
     // Resolve the flat indexes from `--find-links`.
-    let flat_index = {
-        let client = FlatIndexClient::new(&client, &cache);
-        let entries = client.fetch(index_locations.flat_index()).await?;
-        FlatIndex::from_entries(entries, &tags, &no_build, &NoBinary::None)
-    };
+    let flat_index_entries = FlatIndexClient::new(&client, &cache)
+        .fetch(index_locations.flat_index())
+        .await?;
+    let flat_index =
+        FlatIndex::from_entries(flat_index_entries.clone(), &tags, &no_build, &no_binary);
 
     // Track in-flight downloads, builds, etc., across resolutions.
     let in_flight = InFlight::default();
@@ -243,9 +560,6 @@ pub(crate) async fn pip_compile(
         BuildIsolation::Isolated
     };
 
-    // Combine the `--no-build` flags.
-    let no_build = no_build.combine(specified_no_build);
-
     let build_dispatch = BuildDispatch::new(
         &client,
         &cache,
@@ -258,9 +572,15 @@ pub(crate) async fn pip_compile(
         &config_settings,
         build_isolation,
         &no_build,
-        &NoBinary::None,
+        &no_binary,
     )
-    .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build());
+    .with_options(
+        OptionsBuilder::new()
+            .exclude_newer(exclude_newer)
+            .index_strategy(index_strategy)
+            .build(),
+    )
+    .with_concurrency(concurrency);
 
     // Resolve the requirements from the provided sources.
     let requirements = {
@@ -309,9 +629,8 @@ pub(crate) async fn pip_compile(
             .with_reporter(DownloadReporter::from(printer).with_length(editables.len() as u64));
 
         // Build all editables.
-        let editable_wheel_dir = tempdir_in(cache.root())?;
         let editables: Vec<_> = downloader
-            .build_editables(editables, editable_wheel_dir.path())
+            .build_editables(editables)
             .await
             .context("Failed to build editables")?
             .into_iter()
@@ -372,39 +691,203 @@ pub(crate) async fn pip_compile(
         // Do not consider any installed packages during compilation
         Exclusions::All,
         lookaheads,
-    );
+    )
+    .with_required_hashes(required_hashes);
 
     let options = OptionsBuilder::new()
         .resolution_mode(resolution_mode)
         .prerelease_mode(prerelease_mode)
         .dependency_mode(dependency_mode)
+        .yanked_mode(yanked_mode)
         .exclude_newer(exclude_newer)
+        .index_strategy(index_strategy)
+        .require_hashes(require_hashes)
+        .allow_url_override(allow_url_override)
+        .max_tried_versions(max_tried_versions)
+        .max_candidates(max_candidates)
+        .timeout(resolution_timeout)
+        .prefetch(prefetch)
         .build();
 
     // Resolve the dependencies.
-    let resolver = Resolver::new(
-        manifest.clone(),
-        options,
-        &markers,
-        &interpreter,
-        &tags,
-        &client,
-        &flat_index,
-        &top_level_index,
-        &build_dispatch,
-        &EmptyInstalledPackages,
-    )?
-    .with_reporter(ResolverReporter::from(printer));
+    let resolution = if universal {
+        // Fork the resolution for each target environment, evaluating markers against each in
+        // turn, rather than against the current interpreter. The forks are independent, so solve
+        // them concurrently, sharing the client and build context (and thus the HTTP cache and
+        // any built source distributions) along with the fetched package versions.
+        let (
+            markers,
+            interpreter,
+            client,
+            build_dispatch,
+            top_level_index,
+            flat_index_entries,
+            no_build,
+            no_binary,
+        ) = (
+            &*markers,
+            &interpreter,
+            &client,
+            &build_dispatch,
+            &*top_level_index,
+            &flat_index_entries,
+            &no_build,
+            &no_binary,
+        );
+        let forks = ForkEnvironment::all(python_minor, max_python_minor);
+        let reporter = ResolverReporter::from(printer).with_resolutions(forks.len());
+        let results = futures::future::join_all(forks.into_iter().map(|fork| {
+            let manifest = manifest.clone();
+            let local_version_preference = local_version_preference.clone();
+            let reporter = reporter.clone();
+            async move {
+                debug!("Resolving dependencies for {fork}");
+                let markers = fork.markers(markers);
+                let tags = fork.tags(
+                    interpreter.implementation_name(),
+                    interpreter.implementation_tuple(),
+                )?;
+                let flat_index =
+                    FlatIndex::from_entries(flat_index_entries.clone(), &tags, no_build, no_binary);
+
+                // Available versions depend on the target tags, so each fork tracks them
+                // in its own fork of the index, re-targeting the versions fetched by any
+                // other fork.
+                let index = top_level_index.fork();
+                let resolver = Resolver::new(
+                    manifest,
+                    options,
+                    &markers,
+                    interpreter,
+                    &tags,
+                    client,
+                    &flat_index,
+                    &index,
+                    build_dispatch,
+                    &EmptyInstalledPackages,
+                )?
+                .with_reporter(reporter);
+                let resolver = match local_version_preference {
+                    Some(preference) => resolver.with_local_version_preference(preference),
+                    None => resolver,
+                };
+
+                Ok::<_, anyhow::Error>((fork, resolver.resolve().await))
+            }
+        }))
+        .await;
+
+        // Report failures in fork order, regardless of which fork finished first.
+        let mut forks = Vec::new();
+        for result in results {
+            let (fork, result) = result?;
+            match result {
+                Err(uv_resolver::ResolveError::NoSolution(err)) => {
+                    let report = miette::Report::msg(format!("{err}")).context(format!(
+                        "No solution found when resolving dependencies for {fork}:"
+                    ));
+                    eprint!("{report:?}");
+                    if explain {
+                        eprint!("\n{}\n", err.explain());
+                    }
+                    return Ok(ExitStatus::Failure);
+                }
+                result => forks.push((fork, result?)),
+            }
+        }
+        Resolution::Universal(UniversalResolution::new(forks))
+    } else {
+        let resolver = Resolver::new(
+            manifest.clone(),
+            options,
+            &markers,
+            &interpreter,
+            &tags,
+            &client,
+            &flat_index,
+            &top_level_index,
+            &build_dispatch,
+            &EmptyInstalledPackages,
+        )?
+        .with_reporter(ResolverReporter::from(printer));
+        let resolver = match local_version_preference.clone() {
+            Some(preference) => resolver.with_local_version_preference(preference),
+            None => resolver,
+        };
 
-    let resolution = match resolver.resolve().await {
-        Err(uv_resolver::ResolveError::NoSolution(err)) => {
-            let report = miette::Report::msg(format!("{err}"))
-                .context("No solution found when resolving dependencies:");
-            eprint!("{report:?}");
-            return Ok(ExitStatus::Failure);
+        match resolver.resolve().await {
+            Err(uv_resolver::ResolveError::NoSolution(err)) => {
+                let report = miette::Report::msg(format!("{err}"))
+                    .context("No solution found when resolving dependencies:");
+                eprint!("{report:?}");
+                if explain {
+                    eprint!("\n{}\n", err.explain());
+
+                    // Narrow the inputs down to those that participate in the conflict, re-using
+                    // the metadata fetched for the failed resolution.
+                    let resolve = |manifest: Manifest| {
+                        let resolver = Resolver::new(
+                            manifest,
+                            options,
+                            &markers,
+                            &interpreter,
+                            &tags,
+                            &client,
+                            &flat_index,
+                            &top_level_index,
+                            &build_dispatch,
+                            &EmptyInstalledPackages,
+                        );
+                        let local_version_preference = local_version_preference.clone();
+                        async move {
+                            let resolver = match local_version_preference {
+                                Some(preference) => {
+                                    resolver?.with_local_version_preference(preference)
+                                }
+                                None => resolver?,
+                            };
+                            resolver.resolve().await
+                        }
+                    };
+                    if let Some((requirements, constraints)) =
+                        minimal_conflict(&manifest, resolve).await
+                    {
+                        let mut message = String::from(
+                            "\nthe following inputs are unsatisfiable on their own:\n",
+                        );
+                        for requirement in &requirements {
+                            writeln!(message, "- {requirement}")?;
+                        }
+                        for constraint in &constraints {
+                            match manifest.constraints().source(constraint) {
+                                Some(source) => writeln!(
+                                    message,
+                                    "- {constraint} (constraint, declared in `{source}`)"
+                                )?,
+                                None => writeln!(message, "- {constraint} (constraint)")?,
+                            }
+                        }
+                        eprint!("{message}");
+                    }
+                }
+                return Ok(ExitStatus::Failure);
+            }
+            result => Resolution::Single(result?),
         }
-        result => result,
-    }?;
+    };
+
+    // Persist the decisions, for reuse by subsequent incremental resolutions of the same files.
+    if let Some(entry) = decisions_entry {
+        let decisions = CachedDecisions {
+            requirements: resolution
+                .preferences()
+                .iter()
+                .map(|preference| preference.requirement().to_string())
+                .collect(),
+        };
+        fs_err::tokio::create_dir_all(entry.dir()).await?;
+        uv_fs::write_atomic(entry.path(), rmp_serde::to_vec(&decisions)?).await?;
+    }
 
     let s = if resolution.len() == 1 { "" } else { "s" };
     writeln!(
@@ -433,97 +916,358 @@ pub(crate) async fn pip_compile(
     let mut writer = OutputWriter::new(!quiet || output_file.is_none(), output_file)?;
 
     if include_header {
-        writeln!(
-            writer,
-            "{}",
-            "# This file was autogenerated by uv via the following command:".green()
-        )?;
-        writeln!(
-            writer,
-            "{}",
-            format!(
-                "#    {}",
-                cmd(
-                    include_index_url,
-                    include_find_links,
-                    custom_compile_command
-                )
-            )
-            .green()
+        write_header(
+            &mut writer,
+            include_index_url,
+            include_find_links,
+            custom_compile_command,
         )?;
     }
 
+    // Render the remainder of the output separately from the header, such that it can be reused
+    // by subsequent resolutions with identical inputs.
+    let mut output = String::new();
+
     if include_marker_expression {
-        let relevant_markers = resolution.marker_tree(&manifest, &top_level_index, &markers);
-        writeln!(
-            writer,
-            "{}",
-            "# Pinned dependencies known to be valid for:".green()
-        )?;
-        writeln!(writer, "{}", format!("#    {relevant_markers}").green())?;
+        if let Resolution::Single(resolution) = &resolution {
+            let relevant_markers = resolution.marker_tree(&manifest, &top_level_index, &markers);
+            writeln!(
+                output,
+                "{}",
+                "# Pinned dependencies known to be valid for:".green()
+            )?;
+            writeln!(output, "{}", format!("#    {relevant_markers}").green())?;
+        }
     }
 
     // Write the index locations to the output channel.
     let mut wrote_index = false;
 
-    // If necessary, include the `--index-url` and `--extra-index-url` locations.
-    if include_index_url {
+    // If necessary, include the `--index-url` and `--extra-index-url` locations. (Lockfiles record
+    // the index for each distribution instead.)
+    if include_index_url && !lockfile {
         if let Some(index) = index_locations.index() {
-            writeln!(writer, "--index-url {}", index.verbatim())?;
+            writeln!(output, "--index-url {}", index.verbatim())?;
             wrote_index = true;
         }
         for extra_index in index_locations.extra_index() {
-            writeln!(writer, "--extra-index-url {}", extra_index.verbatim())?;
+            writeln!(output, "--extra-index-url {}", extra_index.verbatim())?;
             wrote_index = true;
         }
     }
 
     // If necessary, include the `--find-links` locations.
-    if include_find_links {
+    if include_find_links && !lockfile {
         for flat_index in index_locations.flat_index() {
-            writeln!(writer, "--find-links {flat_index}")?;
+            writeln!(output, "--find-links {flat_index}")?;
             wrote_index = true;
         }
     }
 
     // If we wrote an index, add a newline to separate it from the requirements
     if wrote_index {
-        writeln!(writer)?;
+        writeln!(output)?;
     }
 
-    write!(
-        writer,
-        "{}",
-        DisplayResolutionGraph::new(
-            &resolution,
-            &no_emit_packages,
-            generate_hashes,
-            include_extras,
-            include_annotations,
-            annotation_style,
-        )
-    )?;
+    match &resolution {
+        Resolution::Single(resolution) if lockfile => {
+            write!(output, "{}", Lock::from_resolution(resolution)?.to_toml()?)?;
+        }
+        Resolution::Universal(resolution) if lockfile => {
+            write!(output, "{}", Lock::from_universal(resolution)?.to_toml()?)?;
+        }
+        Resolution::Single(resolution) => write!(
+            output,
+            "{}",
+            DisplayResolutionGraph::new(
+                resolution,
+                &no_emit_packages,
+                generate_hashes,
+                include_extras,
+                include_annotations,
+                annotation_style,
+            )
+        )?,
+        Resolution::Universal(resolution) => write!(
+            output,
+            "{}",
+            DisplayUniversalResolution::new(
+                resolution,
+                &no_emit_packages,
+                generate_hashes,
+                include_extras,
+                include_annotations,
+                annotation_style,
+            )
+        )?,
+    }
 
     // If any "unsafe" packages were excluded, notify the user.
     let excluded = no_emit_packages
         .into_iter()
         .filter(|name| resolution.contains(name))
         .collect::<Vec<_>>();
-    if !excluded.is_empty() {
-        writeln!(writer)?;
+    if !excluded.is_empty() && !lockfile {
+        writeln!(output)?;
         writeln!(
-            writer,
+            output,
             "{}",
             "# The following packages were excluded from the output:".green()
         )?;
         for package in excluded {
-            writeln!(writer, "# {package}")?;
+            writeln!(output, "# {package}")?;
         }
     }
 
+    write!(writer, "{output}")?;
+
+    // Cache the output for subsequent resolutions with identical inputs.
+    if let Some(entry) = resolution_entry {
+        let cached = CachedResolution {
+            packages: resolution.len(),
+            diagnostics: resolution
+                .diagnostics()
+                .into_iter()
+                .map(Diagnostic::message)
+                .collect(),
+            output,
+        };
+        fs_err::tokio::create_dir_all(entry.dir()).await?;
+        uv_fs::write_atomic(entry.path(), rmp_serde::to_vec(&cached)?).await?;
+    }
+
     Ok(ExitStatus::Success)
 }
 
+/// Narrow the direct requirements and constraints of a failed resolution down to a minimal subset
+/// that remains unsatisfiable, by dropping each input in turn and re-resolving without it.
+///
+/// Returns `None` if every input participates in the conflict.
+async fn minimal_conflict<Fut>(
+    manifest: &Manifest,
+    resolve: impl Fn(Manifest) -> Fut,
+) -> Option<(Vec<Requirement>, Vec<Requirement>)>
+where
+    Fut: Future<Output = Result<ResolutionGraph, ResolveError>>,
+{
+    let mut requirements = manifest.direct_requirements().to_vec();
+    let mut constraints = manifest
+        .constraints()
+        .requirements()
+        .cloned()
+        .collect::<Vec<_>>();
+    constraints.sort_by_key(ToString::to_string);
+    let total = requirements.len() + constraints.len();
+    if total < 2 {
+        return None;
+    }
+
+    let mut index = 0;
+    while index < requirements.len() {
+        let mut candidate = requirements.clone();
+        candidate.remove(index);
+        let trial = manifest.with_inputs(candidate.clone(), |constraint| {
+            constraints.contains(constraint)
+        });
+        if matches!(resolve(trial).await, Err(ResolveError::NoSolution(_))) {
+            requirements = candidate;
+        } else {
+            index += 1;
+        }
+    }
+
+    let mut index = 0;
+    while index < constraints.len() {
+        let mut candidate = constraints.clone();
+        candidate.remove(index);
+        let trial = manifest.with_inputs(requirements.clone(), |constraint| {
+            candidate.contains(constraint)
+        });
+        if matches!(resolve(trial).await, Err(ResolveError::NoSolution(_))) {
+            constraints = candidate;
+        } else {
+            index += 1;
+        }
+    }
+
+    if requirements.len() + constraints.len() == total {
+        None
+    } else {
+        Some((requirements, constraints))
+    }
+}
+
+/// The output of a previous resolution, as stored in [`CacheBucket::Resolutions`].
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedResolution {
+    /// The number of packages in the resolution.
+    packages: usize,
+    /// The diagnostics emitted during the resolution, to be replayed on reuse.
+    diagnostics: Vec<String>,
+    /// The rendered output, exclusive of the header.
+    output: String,
+}
+
+/// The inputs that determine the output of a `pip compile` invocation, used to key cached
+/// resolutions in [`CacheBucket::Resolutions`].
+#[derive(Debug)]
+struct ResolutionKey {
+    /// The resolver inputs and options, each rendered alongside its name.
+    inputs: Vec<String>,
+    /// The files in each local `--find-links` directory, alongside their sizes and modification
+    /// times.
+    find_links: Vec<String>,
+}
+
+impl ResolutionKey {
+    /// Create a [`ResolutionKey`] from the given inputs and the contents of any `--find-links`
+    /// locations.
+    ///
+    /// Returns `None` if any `--find-links` location is remote, since its contents can change
+    /// without any change to the inputs, and aren't bounded by `--exclude-newer`.
+    fn new(inputs: Vec<String>, index_locations: &IndexLocations) -> Result<Option<Self>> {
+        let mut find_links = Vec::new();
+        for location in index_locations.flat_index() {
+            let path = match location {
+                FlatIndexLocation::Path(path) => path.clone(),
+                FlatIndexLocation::Url(url) if url.scheme() == "file" => {
+                    let Ok(path) = url.to_file_path() else {
+                        return Ok(None);
+                    };
+                    path
+                }
+                FlatIndexLocation::Url(_) => return Ok(None),
+            };
+            if !path.exists() {
+                find_links.push(format!("{}: missing", path.display()));
+                continue;
+            }
+            for entry in walkdir::WalkDir::new(&path).sort_by_file_name() {
+                let entry = entry?;
+                if !entry.file_type().is_file() {
+                    continue;
+                }
+                let metadata = entry.metadata()?;
+                find_links.push(format!(
+                    "{}: {} bytes, {:?}",
+                    entry.path().display(),
+                    metadata.len(),
+                    Timestamp::from_metadata(&metadata)
+                ));
+            }
+        }
+        Ok(Some(Self { inputs, find_links }))
+    }
+
+    /// Return the digest of the key, as used for the cache entry.
+    fn digest(&self) -> String {
+        cache_key::digest(&(&self.inputs, &self.find_links))
+    }
+}
+
+/// Read a [`CachedResolution`] from the cache, if it exists.
+///
+/// Any refresh (e.g., `--refresh` or `--refresh-package`) bypasses the cached resolutions
+/// entirely, since the refreshed metadata may change the output.
+fn read_cached_resolution(cache: &Cache, entry: &CacheEntry) -> Result<Option<CachedResolution>> {
+    if !cache.refresh().is_none() {
+        return Ok(None);
+    }
+    let contents = match fs_err::read(entry.read_path()) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+    match rmp_serde::from_slice(&contents) {
+        Ok(cached) => {
+            debug!("Reusing cached resolution: {}", entry.path().user_display());
+            Ok(Some(cached))
+        }
+        Err(err) => {
+            debug!(
+                "Ignoring invalid cached resolution at {}: {err}",
+                entry.path().user_display()
+            );
+            Ok(None)
+        }
+    }
+}
+
+/// The decisions from a previous resolution, as stored in [`CacheBucket::Resolutions`].
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedDecisions {
+    /// The pinned version of each registry-based package (e.g., `flask==3.0.0`).
+    requirements: Vec<String>,
+}
+
+/// Read the decisions from a previous resolution as a set of preferences, omitting any packages
+/// that are marked for upgrade.
+///
+/// Unlike a cached resolution, the decisions needn't be fresh, nor match the current markers, as
+/// they only seed the resolver's preferences: any that no longer satisfy the requirements are
+/// ignored.
+fn read_decisions(entry: &CacheEntry, upgrade: &Upgrade) -> Result<Vec<Preference>> {
+    let contents = match fs_err::read(entry.read_path()) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+    let decisions: CachedDecisions = match rmp_serde::from_slice(&contents) {
+        Ok(decisions) => decisions,
+        Err(err) => {
+            debug!(
+                "Ignoring invalid decisions at {}: {err}",
+                entry.path().user_display()
+            );
+            return Ok(Vec::new());
+        }
+    };
+    debug!(
+        "Reusing {} decision(s) from a previous resolution: {}",
+        decisions.requirements.len(),
+        entry.path().user_display()
+    );
+    Ok(decisions
+        .requirements
+        .iter()
+        .filter_map(|requirement| Requirement::from_str(requirement).ok())
+        .filter(|requirement| match upgrade {
+            Upgrade::None => true,
+            Upgrade::All => false,
+            Upgrade::Packages(packages) => !packages.contains_key(&requirement.name),
+        })
+        .map(Preference::from_requirement)
+        .collect())
+}
+
+/// Write the autogenerated header to the output channel.
+fn write_header(
+    writer: &mut OutputWriter,
+    include_index_url: bool,
+    include_find_links: bool,
+    custom_compile_command: Option<String>,
+) -> Result<()> {
+    writeln!(
+        writer,
+        "{}",
+        "# This file was autogenerated by uv via the following command:".green()
+    )?;
+    writeln!(
+        writer,
+        "{}",
+        format!(
+            "#    {}",
+            cmd(
+                include_index_url,
+                include_find_links,
+                custom_compile_command
+            )
+        )
+        .green()
+    )?;
+    Ok(())
+}
+
 /// Format the `uv` command used to generate the output file.
 #[allow(clippy::fn_params_excessive_bools)]
 fn cmd(
@@ -649,6 +1393,46 @@ pub(crate) fn extra_name_with_clap_error(arg: &str) -> Result<ExtraName> {
     })
 }
 
+/// The result of a resolution: either for the current environment, or universal.
+enum Resolution {
+    Single(ResolutionGraph),
+    Universal(UniversalResolution),
+}
+
+impl Resolution {
+    /// Return the number of packages in the resolution.
+    fn len(&self) -> usize {
+        match self {
+            Self::Single(resolution) => resolution.len(),
+            Self::Universal(resolution) => resolution.len(),
+        }
+    }
+
+    /// Return `true` if the resolution contains the given package.
+    fn contains(&self, name: &PackageName) -> bool {
+        match self {
+            Self::Single(resolution) => resolution.contains(name),
+            Self::Universal(resolution) => resolution.contains(name),
+        }
+    }
+
+    /// Return the pinned versions of the registry-based packages in the resolution.
+    fn preferences(&self) -> Vec<Preference> {
+        match self {
+            Self::Single(resolution) => resolution.preferences(),
+            Self::Universal(resolution) => resolution.preferences(),
+        }
+    }
+
+    /// Return the diagnostics encountered during resolution.
+    fn diagnostics(&self) -> Vec<&Diagnostic> {
+        match self {
+            Self::Single(resolution) => resolution.diagnostics().iter().collect(),
+            Self::Universal(resolution) => resolution.diagnostics(),
+        }
+    }
+}
+
 /// An owned or unowned [`InMemoryIndex`].
 enum InMemoryIndexRef<'a> {
     Owned(InMemoryIndex),
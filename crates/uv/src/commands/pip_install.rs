@@ -1,54 +1,62 @@
+use std::borrow::Cow;
 use std::fmt::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use anstream::eprint;
 use anyhow::{anyhow, Context, Result};
 use chrono::{DateTime, Utc};
 use itertools::Itertools;
 use owo_colors::OwoColorize;
-use tempfile::tempdir_in;
+use rustc_hash::FxHashMap;
 use tracing::debug;
+use url::Url;
 
 use distribution_types::{
-    DistributionMetadata, IndexLocations, InstalledMetadata, LocalDist, LocalEditable,
-    LocalEditables, Name, Resolution,
+    IndexLocations, InstalledMetadata, LocalDist, LocalEditable, LocalEditables, Name, Resolution,
 };
 use install_wheel_rs::linker::LinkMode;
+use install_wheel_rs::ShebangMode;
 use pep508_rs::{MarkerEnvironment, Requirement};
-use platform_tags::Tags;
-use pypi_types::{Metadata23, Yanked};
+use platform_tags::{Arch, Os, Platform, Tags};
+use pypi_types::Metadata23;
 use requirements_txt::EditableRequirement;
 use uv_auth::{KeyringProvider, GLOBAL_AUTH_STORE};
 use uv_cache::Cache;
 use uv_client::{
-    BaseClientBuilder, Connectivity, FlatIndex, FlatIndexClient, RegistryClient,
-    RegistryClientBuilder,
+    BaseClientBuilder, ConnectionOptions, Connectivity, FlatIndex, FlatIndexClient, RegistryClient,
+    RegistryClientBuilder, RetryPolicy,
 };
 use uv_dispatch::BuildDispatch;
 use uv_fs::Simplified;
-use uv_installer::{BuiltEditable, Downloader, Plan, Planner, ResolvedEditable, SitePackages};
-use uv_interpreter::{Interpreter, PythonEnvironment};
+use uv_installer::{
+    BuiltEditable, Downloader, Plan, Planner, PostInstallHook, ResolvedEditable, SitePackages,
+};
+use uv_interpreter::{Interpreter, PythonEnvironment, PythonVersion};
 use uv_normalize::PackageName;
 use uv_requirements::{
     ExtrasSpecification, LookaheadResolver, NamedRequirementsResolver, RequirementsSource,
     RequirementsSpecification, SourceTreeResolver,
 };
 use uv_resolver::{
-    DependencyMode, Exclusions, InMemoryIndex, Manifest, Options, OptionsBuilder, PreReleaseMode,
-    Preference, ResolutionGraph, ResolutionMode, Resolver,
+    DependencyMode, Exclusions, InMemoryIndex, LocalVersionPreference, Manifest, Options,
+    OptionsBuilder, PreReleaseMode, Preference, PrefetchOptions, ResolutionGraph, ResolutionMode,
+    Resolver, YankedMode,
 };
 use uv_types::{
-    BuildIsolation, ConfigSettings, Constraints, InFlight, IndexStrategy, NoBinary, NoBuild,
-    Overrides, Reinstall, SetupPyStrategy, Upgrade,
+    BuildIsolation, Concurrency, ConfigSettings, Constraints, InFlight, IndexStrategy, NoBinary,
+    NoBuild, Overrides, Reinstall, RequiredHashes, SetupPyStrategy, Upgrade,
 };
 use uv_warnings::warn_user;
 
+use crate::commands::install_report::InstallReport;
 use crate::commands::reporters::{DownloadReporter, InstallReporter, ResolverReporter};
-use crate::commands::{compile_bytecode, elapsed, ChangeEvent, ChangeEventKind, ExitStatus};
+use crate::commands::{
+    compile_bytecode, elapsed, report_dry_run_changes, warn_foreign_installer, ChangeEvent,
+    ChangeEventKind, ExitStatus,
+};
 use crate::printer::Printer;
 
-use super::DryRunEvent;
-
 /// Install packages into the current environment.
 #[allow(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
 pub(crate) async fn pip_install(
@@ -59,13 +67,18 @@ pub(crate) async fn pip_install(
     resolution_mode: ResolutionMode,
     prerelease_mode: PreReleaseMode,
     dependency_mode: DependencyMode,
+    yanked_mode: YankedMode,
     upgrade: Upgrade,
     index_locations: IndexLocations,
     index_strategy: IndexStrategy,
     keyring_provider: KeyringProvider,
     reinstall: Reinstall,
     link_mode: LinkMode,
+    shebang: ShebangMode,
     compile: bool,
+    require_hashes: bool,
+    report: Option<&Path>,
+    post_install_hook: Option<&dyn PostInstallHook>,
     setup_py: SetupPyStrategy,
     connectivity: Connectivity,
     config_settings: &ConfigSettings,
@@ -74,10 +87,28 @@ pub(crate) async fn pip_install(
     no_binary: NoBinary,
     strict: bool,
     exclude_newer: Option<DateTime<Utc>>,
+    allow_url_override: bool,
+    local_version_preference: Option<LocalVersionPreference>,
+    exclude: Vec<PackageName>,
+    max_tried_versions: Option<usize>,
+    max_candidates: Option<usize>,
+    resolution_timeout: Option<Duration>,
+    prefetch: PrefetchOptions,
+    explain: bool,
     python: Option<String>,
     system: bool,
     break_system_packages: bool,
+    prefix: Option<PathBuf>,
+    target: Option<PathBuf>,
+    python_version: Option<PythonVersion>,
+    platform: Vec<String>,
+    implementation: Option<String>,
+    abi: Vec<String>,
     native_tls: bool,
+    retry_policy: RetryPolicy,
+    proxy: Option<Url>,
+    connection_options: ConnectionOptions,
+    concurrency: Concurrency,
     cache: Cache,
     dry_run: bool,
     printer: Printer,
@@ -86,13 +117,18 @@ pub(crate) async fn pip_install(
     let client_builder = BaseClientBuilder::new()
         .connectivity(connectivity)
         .native_tls(native_tls)
+        .retry_policy(retry_policy.clone())
+        .proxy(proxy.clone())
+        .connection_options(connection_options.clone())
         .keyring_provider(keyring_provider);
 
     // Read all requirements from the provided sources.
     let RequirementsSpecification {
         project,
         requirements,
+        hashes,
         constraints,
+        sources: constraint_sources,
         overrides,
         editables,
         source_trees,
@@ -117,6 +153,14 @@ pub(crate) async fn pip_install(
         PythonEnvironment::from_requested_python(python, &cache)?
     } else if system {
         PythonEnvironment::from_default_python(&cache)?
+    } else if prefix.is_some() || target.is_some() {
+        match PythonEnvironment::from_virtualenv(&cache) {
+            Ok(venv) => venv,
+            Err(uv_interpreter::Error::VenvNotFound) => {
+                PythonEnvironment::from_default_python(&cache)?
+            }
+            Err(err) => return Err(err.into()),
+        }
     } else {
         PythonEnvironment::from_virtualenv(&cache)?
     };
@@ -126,8 +170,26 @@ pub(crate) async fn pip_install(
         venv.python_executable().user_display().cyan()
     );
 
-    // If the environment is externally managed, abort.
-    if let Some(externally_managed) = venv.interpreter().is_externally_managed() {
+    // Install under the prefix, if requested, in lieu of the environment itself.
+    let venv = if let Some(prefix) = prefix.as_ref() {
+        let prefix = std::env::current_dir()?.join(prefix);
+        debug!("Installing under prefix: {}", prefix.user_display());
+        venv.with_prefix(prefix)
+    } else if let Some(target) = target.as_ref() {
+        let target = std::env::current_dir()?.join(target);
+        debug!("Installing into target: {}", target.user_display());
+        venv.with_target(target)
+    } else {
+        venv
+    };
+
+    // If the environment is externally managed, abort. Installs under a prefix or into a target
+    // leave the environment untouched, so are exempt.
+    if let Some(externally_managed) = venv
+        .interpreter()
+        .is_externally_managed()
+        .filter(|_| prefix.is_none() && target.is_none())
+    {
         if break_system_packages {
             debug!("Ignoring externally managed environment due to `--break-system-packages`");
         } else {
@@ -174,13 +236,90 @@ pub(crate) async fn pip_install(
         if dry_run {
             writeln!(printer.stderr(), "Would make no changes")?;
         }
+        if report.is_some() {
+            InstallReport::empty().emit(report, post_install_hook, venv.root())?;
+        }
         return Ok(ExitStatus::Success);
     }
 
-    // Determine the tags, markers, and interpreter to use for resolution.
+    // Determine the tags, markers, and interpreter to use for resolution. When targeting another
+    // environment, wheels are selected for its tags rather than those of the current interpreter.
     let interpreter = venv.interpreter().clone();
-    let tags = venv.interpreter().tags()?;
-    let markers = venv.interpreter().markers();
+    let overrides_tags = python_version.is_some()
+        || !platform.is_empty()
+        || implementation.is_some()
+        || !abi.is_empty();
+    let implementation_name = match implementation.as_deref() {
+        None => interpreter.implementation_name().to_string(),
+        Some("cp") => "cpython".to_string(),
+        Some("pp") => "pypy".to_string(),
+        Some("pt") => "pyston".to_string(),
+        Some(implementation) => implementation.to_lowercase(),
+    };
+    let target_platform = platform.iter().find_map(|tag| Platform::from_tag(tag));
+    if !platform.is_empty() && target_platform.is_none() {
+        warn_user!(
+            "None of the requested platforms ({}) could be mapped to an operating system and architecture; markers will be evaluated against the current platform",
+            platform.join(", ")
+        );
+    }
+    let tags = if overrides_tags {
+        // Building source distributions would produce wheels for the current interpreter, so only
+        // pre-built wheels are supported.
+        if !matches!(no_build, NoBuild::All) && !matches!(specified_no_build, NoBuild::All) {
+            return Err(anyhow::anyhow!(
+                "Overriding the target Python version, platform, implementation, or ABI requires `--only-binary :all:`"
+            ));
+        }
+        if compile {
+            return Err(anyhow::anyhow!(
+                "Bytecode compilation (`--compile`) is not supported when overriding the target Python version, platform, implementation, or ABI"
+            ));
+        }
+
+        let python_tuple = python_version.as_ref().map_or_else(
+            || interpreter.python_tuple(),
+            |python_version| (python_version.major(), python_version.minor()),
+        );
+        let implementation_tuple = if implementation.is_some() || python_version.is_some() {
+            python_tuple
+        } else {
+            interpreter.implementation_tuple()
+        };
+        let platform_tags = if platform.is_empty() {
+            platform_tags::compatible_tags(interpreter.platform())?
+        } else {
+            // Include the older tags that each requested platform is compatible with (e.g.,
+            // `manylinux_2_17_aarch64` for `manylinux_2_28_aarch64`).
+            platform_tags::expand_platform_tags(&platform)?
+        };
+        Cow::Owned(Tags::from_platform_tags(
+            &platform_tags,
+            python_tuple,
+            &implementation_name,
+            implementation_tuple,
+            (!abi.is_empty()).then_some(abi.as_slice()),
+        )?)
+    } else {
+        Cow::Borrowed(interpreter.tags()?)
+    };
+    let markers = if overrides_tags {
+        // Evaluate markers against the requested platform and implementation, rather than those of
+        // the current interpreter.
+        let mut markers = interpreter.markers().clone();
+        if implementation.is_some() {
+            implementation_markers(&mut markers, &implementation_name);
+        }
+        if let Some(target_platform) = target_platform.as_ref() {
+            platform_markers(&mut markers, target_platform);
+        }
+        if let Some(python_version) = python_version {
+            markers = python_version.markers(&markers);
+        }
+        Cow::Owned(markers)
+    } else {
+        Cow::Borrowed(interpreter.markers())
+    };
 
     // Incorporate any index locations from the provided sources.
     let index_locations =
@@ -194,19 +333,26 @@ pub(crate) async fn pip_install(
     // Initialize the registry client.
     let client = RegistryClientBuilder::new(cache.clone())
         .native_tls(native_tls)
+        .retry_policy(retry_policy.clone())
+        .proxy(proxy.clone())
+        .connection_options(connection_options.clone())
         .connectivity(connectivity)
         .index_urls(index_locations.index_urls())
         .index_strategy(index_strategy)
         .keyring_provider(keyring_provider)
-        .markers(markers)
+        .markers(&markers)
         .platform(interpreter.platform())
         .build();
 
+    // Combine the `--no-binary` and `--no-build` flags, such that the flat index respects them.
+    let no_binary = no_binary.combine(specified_no_binary);
+    let no_build = no_build.combine(specified_no_build);
+
     // Resolve the flat indexes from `--find-links`.
     let flat_index = {
         let client = FlatIndexClient::new(&client, &cache);
         let entries = client.fetch(index_locations.flat_index()).await?;
-        FlatIndex::from_entries(entries, tags, &no_build, &no_binary)
+        FlatIndex::from_entries(entries, &tags, &no_build, &no_binary)
     };
 
     // Determine whether to enable build isolation.
@@ -216,10 +362,6 @@ pub(crate) async fn pip_install(
         BuildIsolation::Isolated
     };
 
-    // Combine the `--no-binary` and `--no-build` flags.
-    let no_binary = no_binary.combine(specified_no_binary);
-    let no_build = no_build.combine(specified_no_build);
-
     // Create a shared in-memory index.
     let index = InMemoryIndex::default();
 
@@ -241,7 +383,13 @@ pub(crate) async fn pip_install(
         &no_build,
         &no_binary,
     )
-    .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build());
+    .with_options(
+        OptionsBuilder::new()
+            .exclude_newer(exclude_newer)
+            .index_strategy(index_strategy)
+            .build(),
+    )
+    .with_concurrency(concurrency);
 
     // Resolve the requirements from the provided sources.
     let requirements = {
@@ -265,20 +413,33 @@ pub(crate) async fn pip_install(
         requirements
     };
 
+    // In hash-checking mode, every requirement must be pinned, with at least one hash.
+    let required_hashes = if require_hashes || !hashes.is_empty() {
+        if let Some(editable) = editables.first() {
+            return Err(anyhow!(
+                "In `--require-hashes` mode, editable requirements can't be hashed, but found: `{editable}`"
+            ));
+        }
+        RequiredHashes::from_requirements(
+            requirements
+                .iter()
+                .map(|requirement| (requirement, hashes.get(requirement))),
+        )?
+    } else {
+        RequiredHashes::default()
+    };
+
     // Build all editable distributions. The editables are shared between resolution and
-    // installation, and should live for the duration of the command. If an editable is already
-    // installed in the environment, we'll still re-build it here.
-    let editable_wheel_dir;
+    // installation. If an editable is already installed in the environment, we'll still "build"
+    // it here, though unchanged editables are read from the cache.
     let editables = if editables.is_empty() {
         vec![]
     } else {
-        editable_wheel_dir = tempdir_in(cache.root())?;
         build_editables(
             &editables,
-            editable_wheel_dir.path(),
             &cache,
             &interpreter,
-            tags,
+            &tags,
             &client,
             &resolve_dispatch,
             printer,
@@ -290,13 +451,21 @@ pub(crate) async fn pip_install(
         .resolution_mode(resolution_mode)
         .prerelease_mode(prerelease_mode)
         .dependency_mode(dependency_mode)
+        .yanked_mode(yanked_mode)
         .exclude_newer(exclude_newer)
+        .index_strategy(index_strategy)
+        .allow_url_override(allow_url_override)
+        .max_tried_versions(max_tried_versions)
+        .max_candidates(max_candidates)
+        .timeout(resolution_timeout)
+        .prefetch(prefetch)
         .build();
 
     // Resolve the requirements.
     let resolution = match resolve(
         requirements,
         constraints,
+        constraint_sources,
         overrides,
         project,
         &editables,
@@ -304,13 +473,15 @@ pub(crate) async fn pip_install(
         &reinstall,
         &upgrade,
         &interpreter,
-        tags,
-        markers,
+        &tags,
+        &markers,
         &client,
         &flat_index,
         &index,
         &resolve_dispatch,
         options,
+        local_version_preference,
+        exclude,
         printer,
     )
     .await
@@ -320,6 +491,9 @@ pub(crate) async fn pip_install(
             let report = miette::Report::msg(format!("{err}"))
                 .context("No solution found when resolving dependencies:");
             eprint!("{report:?}");
+            if explain {
+                eprint!("\n{}\n", err.explain());
+            }
             return Ok(ExitStatus::Failure);
         }
         Err(err) => return Err(err.into()),
@@ -347,7 +521,13 @@ pub(crate) async fn pip_install(
             &no_build,
             &no_binary,
         )
-        .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build())
+        .with_options(
+            OptionsBuilder::new()
+                .exclude_newer(exclude_newer)
+                .index_strategy(index_strategy)
+                .build(),
+        )
+        .with_concurrency(concurrency)
     };
 
     // Sync the environment.
@@ -357,13 +537,18 @@ pub(crate) async fn pip_install(
         site_packages,
         &reinstall,
         &no_binary,
+        &required_hashes,
         link_mode,
+        shebang,
         compile,
+        report,
+        post_install_hook,
         &index_locations,
-        tags,
+        &tags,
         &client,
         &in_flight,
         &install_dispatch,
+        concurrency,
         &cache,
         &venv,
         dry_run,
@@ -434,7 +619,6 @@ async fn read_requirements(
 #[allow(clippy::too_many_arguments)]
 async fn build_editables(
     editables: &[EditableRequirement],
-    editable_wheel_dir: &Path,
     cache: &Cache,
     interpreter: &Interpreter,
     tags: &Tags,
@@ -457,7 +641,7 @@ async fn build_editables(
     }));
 
     let editables: Vec<_> = downloader
-        .build_editables(editables, editable_wheel_dir)
+        .build_editables(editables)
         .await
         .context("Failed to build editables")?
         .into_iter()
@@ -498,6 +682,7 @@ async fn build_editables(
 async fn resolve(
     requirements: Vec<Requirement>,
     constraints: Vec<Requirement>,
+    constraint_sources: FxHashMap<Requirement, String>,
     overrides: Vec<Requirement>,
     project: Option<PackageName>,
     editables: &[BuiltEditable],
@@ -512,6 +697,8 @@ async fn resolve(
     index: &InMemoryIndex,
     build_dispatch: &BuildDispatch<'_>,
     options: Options,
+    local_version_preference: Option<LocalVersionPreference>,
+    exclude: Vec<PackageName>,
     printer: Printer,
 ) -> Result<ResolutionGraph, Error> {
     let start = std::time::Instant::now();
@@ -526,9 +713,16 @@ async fn resolve(
         .map(Preference::from_requirement)
         .collect();
 
-    // Collect constraints and overrides.
-    let constraints = Constraints::from_requirements(constraints);
-    let overrides = Overrides::from_requirements(overrides);
+    // Collect constraints and overrides, bounding any targeted upgrades by the version
+    // specifiers provided alongside them.
+    let constraints = Constraints::from_requirements(
+        constraints
+            .into_iter()
+            .chain(upgrade.constraints().cloned())
+            .collect(),
+    )
+    .with_sources(constraint_sources);
+    let overrides = Overrides::from_requirements(overrides).with_excluded(exclude);
 
     // Map the editables to their metadata.
     let editables: Vec<(LocalEditable, Metadata23)> = editables
@@ -581,6 +775,10 @@ async fn resolve(
         site_packages,
     )?
     .with_reporter(ResolverReporter::from(printer));
+    let resolver = match local_version_preference {
+        Some(preference) => resolver.with_local_version_preference(preference),
+        None => resolver,
+    };
     let resolution = resolver.resolve().await?;
 
     let s = if resolution.len() == 1 { "" } else { "s" };
@@ -617,13 +815,18 @@ async fn install(
     site_packages: SitePackages<'_>,
     reinstall: &Reinstall,
     no_binary: &NoBinary,
+    required_hashes: &RequiredHashes,
     link_mode: LinkMode,
+    shebang: ShebangMode,
     compile: bool,
+    report: Option<&Path>,
+    post_install_hook: Option<&dyn PostInstallHook>,
     index_urls: &IndexLocations,
     tags: &Tags,
     client: &RegistryClient,
     in_flight: &InFlight,
     build_dispatch: &BuildDispatch<'_>,
+    concurrency: Concurrency,
     cache: &Cache,
     venv: &PythonEnvironment,
     dry_run: bool,
@@ -643,6 +846,7 @@ async fn install(
     // downloaded (`remote`), and those that should be removed (`extraneous`).
     let plan = Planner::with_requirements(&requirements)
         .with_editable_requirements(&editables)
+        .with_hash_checking(!required_hashes.is_empty())
         .build(
             site_packages,
             reinstall,
@@ -679,6 +883,9 @@ async fn install(
             )
             .dimmed()
         )?;
+        if report.is_some() {
+            InstallReport::empty().emit(report, post_install_hook, venv.root())?;
+        }
         return Ok(());
     }
 
@@ -700,10 +907,12 @@ async fn install(
         let start = std::time::Instant::now();
 
         let downloader = Downloader::new(cache, tags, client, build_dispatch)
+            .with_concurrency(concurrency.downloads)
+            .with_required_hashes(required_hashes.clone())
             .with_reporter(DownloadReporter::from(printer).with_length(remote.len() as u64));
 
         let wheels = downloader
-            .download(remote.clone(), in_flight)
+            .download(remote, in_flight)
             .await
             .context("Failed to download distributions")?;
 
@@ -725,6 +934,7 @@ async fn install(
     // Remove any existing installations.
     if !reinstalls.is_empty() {
         for dist_info in &reinstalls {
+            warn_foreign_installer(dist_info);
             match uv_installer::uninstall(dist_info).await {
                 Ok(summary) => {
                     debug!(
@@ -749,15 +959,38 @@ async fn install(
         }
     }
 
+    // Record the changes to the environment, if a report or post-install hook was requested.
+    let mut install_report = (report.is_some() || post_install_hook.is_some()).then(|| {
+        let mut report = InstallReport::new(link_mode);
+        for wheel in &wheels {
+            report.add_installed(wheel, resolution.get_remote(wheel.name()), false);
+        }
+        for wheel in &cached {
+            report.add_installed(wheel, resolution.get_remote(wheel.name()), true);
+        }
+        for dist_info in &reinstalls {
+            report.add_removed(dist_info);
+        }
+        report
+    });
+
     // Install the resolved distributions.
     let wheels = wheels.into_iter().chain(cached).collect::<Vec<_>>();
     if !wheels.is_empty() {
         let start = std::time::Instant::now();
         uv_installer::Installer::new(venv)
             .with_link_mode(link_mode)
+            .with_shebang(shebang)
             .with_reporter(InstallReporter::from(printer).with_length(wheels.len() as u64))
             .install(&wheels)?;
 
+        // Record the link mode that was used, after any fallback.
+        if let (Some(install_report), Some(site_packages)) =
+            (install_report.as_mut(), venv.site_packages().next())
+        {
+            install_report.set_link_mode(link_mode.effective(site_packages));
+        }
+
         let s = if wheels.len() == 1 { "" } else { "s" };
         writeln!(
             printer.stderr(),
@@ -772,7 +1005,11 @@ async fn install(
     }
 
     if compile {
-        compile_bytecode(venv, cache, printer).await?;
+        let installed = wheels
+            .iter()
+            .map(|wheel| wheel.name().clone())
+            .collect::<Vec<_>>();
+        compile_bytecode(venv, cache, &installed, printer).await?;
     }
 
     for event in reinstalls
@@ -815,6 +1052,10 @@ async fn install(
         }
     }
 
+    if let Some(install_report) = install_report {
+        install_report.emit(report, post_install_hook, venv.root())?;
+    }
+
     #[allow(clippy::items_after_statements)]
     fn report_dry_run(
         resolution: &Resolution,
@@ -858,120 +1099,12 @@ async fn install(
             })
             .collect::<Vec<_>>();
 
-        // Download, build, and unzip any missing distributions.
-        let wheels = if remote.is_empty() {
-            vec![]
-        } else {
-            let s = if remote.len() == 1 { "" } else { "s" };
-            writeln!(
-                printer.stderr(),
-                "{}",
-                format!(
-                    "Would download {}",
-                    format!("{} package{}", remote.len(), s).bold(),
-                )
-                .dimmed()
-            )?;
-            remote
-        };
-
-        // Remove any existing installations.
-        if !reinstalls.is_empty() {
-            let s = if reinstalls.len() == 1 { "" } else { "s" };
-            writeln!(
-                printer.stderr(),
-                "{}",
-                format!(
-                    "Would uninstall {}",
-                    format!("{} package{}", reinstalls.len(), s).bold(),
-                )
-                .dimmed()
-            )?;
-        }
-
-        // Install the resolved distributions.
-        let installs = wheels.len() + cached.len();
-
-        if installs > 0 {
-            let s = if installs == 1 { "" } else { "s" };
-            writeln!(
-                printer.stderr(),
-                "{}",
-                format!("Would install {}", format!("{installs} package{s}").bold()).dimmed()
-            )?;
-        }
-
-        for event in reinstalls
-            .into_iter()
-            .map(|distribution| DryRunEvent {
-                name: distribution.name().clone(),
-                version: distribution.installed_version().to_string(),
-                kind: ChangeEventKind::Removed,
-            })
-            .chain(wheels.into_iter().map(|distribution| DryRunEvent {
-                name: distribution.name().clone(),
-                version: distribution.version_or_url().to_string(),
-                kind: ChangeEventKind::Added,
-            }))
-            .chain(cached.into_iter().map(|distribution| DryRunEvent {
-                name: distribution.name().clone(),
-                version: distribution.installed_version().to_string(),
-                kind: ChangeEventKind::Added,
-            }))
-            .sorted_unstable_by(|a, b| a.name.cmp(&b.name).then_with(|| a.kind.cmp(&b.kind)))
-        {
-            match event.kind {
-                ChangeEventKind::Added => {
-                    writeln!(
-                        printer.stderr(),
-                        " {} {}{}",
-                        "+".green(),
-                        event.name.as_ref().bold(),
-                        event.version.dimmed()
-                    )?;
-                }
-                ChangeEventKind::Removed => {
-                    writeln!(
-                        printer.stderr(),
-                        " {} {}{}",
-                        "-".red(),
-                        event.name.as_ref().bold(),
-                        event.version.dimmed()
-                    )?;
-                }
-            }
-        }
+        let reinstalls = reinstalls.iter().collect::<Vec<_>>();
+        report_dry_run_changes(&remote, &cached, &[], &reinstalls, printer)?;
 
         Ok(())
     }
 
-    // TODO(konstin): Also check the cache whether any cached or installed dist is already known to
-    // have been yanked, we currently don't show this message on the second run anymore
-    for dist in &remote {
-        let Some(file) = dist.file() else {
-            continue;
-        };
-        match &file.yanked {
-            None | Some(Yanked::Bool(false)) => {}
-            Some(Yanked::Bool(true)) => {
-                writeln!(
-                    printer.stderr(),
-                    "{}{} {dist} is yanked.",
-                    "warning".yellow().bold(),
-                    ":".bold(),
-                )?;
-            }
-            Some(Yanked::Reason(reason)) => {
-                writeln!(
-                    printer.stderr(),
-                    "{}{} {dist} is yanked (reason: \"{reason}\").",
-                    "warning".yellow().bold(),
-                    ":".bold(),
-                )?;
-            }
-        }
-    }
-
     Ok(())
 }
 
@@ -1001,6 +1134,39 @@ fn validate(
     Ok(())
 }
 
+/// Update the implementation markers (e.g., `implementation_name`) to match the requested
+/// implementation (e.g., `cpython`).
+fn implementation_markers(markers: &mut MarkerEnvironment, implementation_name: &str) {
+    markers.implementation_name = implementation_name.to_string();
+    markers.platform_python_implementation = match implementation_name {
+        "cpython" => "CPython".to_string(),
+        "pypy" => "PyPy".to_string(),
+        "pyston" => "Pyston".to_string(),
+        _ => return,
+    };
+}
+
+/// Update the platform markers (e.g., `sys_platform`) to match the requested [`Platform`].
+fn platform_markers(markers: &mut MarkerEnvironment, platform: &Platform) {
+    let (sys_platform, platform_system, os_name) = match platform.os() {
+        Os::Manylinux { .. } | Os::Musllinux { .. } => ("linux", "Linux", "posix"),
+        Os::Macos { .. } => ("darwin", "Darwin", "posix"),
+        Os::Windows => ("win32", "Windows", "nt"),
+        _ => return,
+    };
+    let platform_machine = match (platform.os(), platform.arch()) {
+        (Os::Macos { .. }, Arch::Aarch64) => "arm64".to_string(),
+        (Os::Windows, Arch::X86_64) => "AMD64".to_string(),
+        (Os::Windows, Arch::X86) => "x86".to_string(),
+        (Os::Windows, Arch::Aarch64) => "ARM64".to_string(),
+        (_, arch) => arch.to_string(),
+    };
+    markers.sys_platform = sys_platform.to_string();
+    markers.platform_system = platform_system.to_string();
+    markers.os_name = os_name.to_string();
+    markers.platform_machine = platform_machine;
+}
+
 #[derive(thiserror::Error, Debug)]
 enum Error {
     #[error(transparent)]
@@ -7,6 +7,7 @@ use rustc_hash::FxHashMap;
 use tracing::debug;
 
 use distribution_types::Name;
+use install_wheel_rs::read_installed_files;
 use uv_cache::Cache;
 use uv_fs::Simplified;
 use uv_installer::SitePackages;
@@ -20,6 +21,7 @@ use crate::printer::Printer;
 pub(crate) fn pip_show(
     mut packages: Vec<PackageName>,
     strict: bool,
+    files: bool,
     python: Option<&str>,
     system: bool,
     cache: &Cache,
@@ -196,6 +198,21 @@ pub(crate) fn pip_show(
                 )?;
             }
         }
+
+        // If requested, print the installed files, as listed in the `RECORD`.
+        if files {
+            writeln!(printer.stdout(), "Files:")?;
+            match read_installed_files(distribution.path())? {
+                Some(files) => {
+                    for file in files {
+                        writeln!(printer.stdout(), "  {file}")?;
+                    }
+                }
+                None => {
+                    writeln!(printer.stdout(), "  Cannot locate RECORD")?;
+                }
+            }
+        }
     }
 
     // Validate that the environment is consistent.
@@ -1,41 +1,52 @@
 use std::fmt::Write;
+use std::path::Path;
 
 use anstream::eprint;
 use anyhow::{anyhow, Context, Result};
 use itertools::Itertools;
 use owo_colors::OwoColorize;
 use tracing::debug;
+use url::Url;
 
 use distribution_types::{
-    IndexLocations, InstalledMetadata, LocalDist, LocalEditable, LocalEditables, Name, ResolvedDist,
+    Dist, IndexLocations, InstalledMetadata, LocalDist, LocalEditable, LocalEditables, Name,
+    ResolvedDist, SourceDist,
 };
 use install_wheel_rs::linker::LinkMode;
+use install_wheel_rs::ShebangMode;
 use platform_tags::Tags;
 use pypi_types::Yanked;
 use requirements_txt::EditableRequirement;
 use uv_auth::{KeyringProvider, GLOBAL_AUTH_STORE};
 use uv_cache::{ArchiveTarget, ArchiveTimestamp, Cache};
 use uv_client::{
-    BaseClientBuilder, Connectivity, FlatIndex, FlatIndexClient, RegistryClient,
-    RegistryClientBuilder,
+    BaseClientBuilder, ConnectionOptions, Connectivity, FlatIndex, FlatIndexClient, RegistryClient,
+    RegistryClientBuilder, RetryPolicy,
 };
 use uv_dispatch::BuildDispatch;
 use uv_fs::Simplified;
-use uv_installer::{is_dynamic, Downloader, Plan, Planner, ResolvedEditable, SitePackages};
+use uv_installer::{
+    is_dynamic, Downloader, Plan, Planner, PostInstallHook, ResolvedEditable, SitePackages,
+};
 use uv_interpreter::{Interpreter, PythonEnvironment};
+use uv_normalize::PackageName;
 use uv_requirements::{
     ExtrasSpecification, NamedRequirementsResolver, RequirementsSource, RequirementsSpecification,
     SourceTreeResolver,
 };
-use uv_resolver::{DependencyMode, InMemoryIndex, Manifest, OptionsBuilder, Resolver};
+use uv_resolver::{DependencyMode, InMemoryIndex, Lock, Manifest, OptionsBuilder, Resolver};
 use uv_types::{
-    BuildIsolation, ConfigSettings, EmptyInstalledPackages, InFlight, IndexStrategy, NoBinary,
-    NoBuild, Reinstall, SetupPyStrategy,
+    BuildIsolation, Concurrency, ConfigSettings, EmptyInstalledPackages, InFlight, IndexStrategy,
+    NoBinary, NoBuild, Reinstall, RequiredHashes, SetupPyStrategy,
 };
 use uv_warnings::warn_user;
 
+use crate::commands::install_report::InstallReport;
 use crate::commands::reporters::{DownloadReporter, InstallReporter, ResolverReporter};
-use crate::commands::{compile_bytecode, elapsed, ChangeEvent, ChangeEventKind, ExitStatus};
+use crate::commands::{
+    compile_bytecode, elapsed, report_dry_run_changes, warn_foreign_installer, ChangeEvent,
+    ChangeEventKind, ExitStatus,
+};
 use crate::printer::Printer;
 
 /// Install a set of locked requirements into the current Python environment.
@@ -44,7 +55,12 @@ pub(crate) async fn pip_sync(
     sources: &[RequirementsSource],
     reinstall: &Reinstall,
     link_mode: LinkMode,
+    shebang: ShebangMode,
     compile: bool,
+    require_hashes: bool,
+    report: Option<&Path>,
+    post_install_hook: Option<&dyn PostInstallHook>,
+    dry_run: bool,
     index_locations: IndexLocations,
     index_strategy: IndexStrategy,
     keyring_provider: KeyringProvider,
@@ -54,11 +70,16 @@ pub(crate) async fn pip_sync(
     no_build_isolation: bool,
     no_build: NoBuild,
     no_binary: NoBinary,
+    exclude: Vec<PackageName>,
     strict: bool,
     python: Option<String>,
     system: bool,
     break_system_packages: bool,
     native_tls: bool,
+    retry_policy: RetryPolicy,
+    proxy: Option<Url>,
+    connection_options: ConnectionOptions,
+    concurrency: Concurrency,
     cache: Cache,
     printer: Printer,
 ) -> Result<ExitStatus> {
@@ -67,13 +88,42 @@ pub(crate) async fn pip_sync(
     let client_builder = BaseClientBuilder::new()
         .connectivity(connectivity)
         .native_tls(native_tls)
+        .retry_policy(retry_policy.clone())
+        .proxy(proxy.clone())
+        .connection_options(connection_options.clone())
         .keyring_provider(keyring_provider);
 
+    // If a `uv.lock` was provided, install the locked distributions directly, without
+    // re-resolving.
+    let lockfile = match sources {
+        [RequirementsSource::RequirementsTxt(path)] if Lock::is_lockfile(path) => {
+            let contents = fs_err::read_to_string(path)?;
+            Some(
+                Lock::from_toml(&contents)
+                    .with_context(|| format!("Failed to read `{}`", path.user_display()))?,
+            )
+        }
+        sources => {
+            if let Some(path) = sources.iter().find_map(|source| match source {
+                RequirementsSource::RequirementsTxt(path) if Lock::is_lockfile(path) => Some(path),
+                _ => None,
+            }) {
+                return Err(anyhow!(
+                    "`{}` can't be combined with other requirements sources",
+                    path.user_display()
+                ));
+            }
+            None
+        }
+    };
+
     // Read all requirements from the provided sources.
     let RequirementsSpecification {
         project: _,
         requirements,
+        hashes,
         constraints: _,
+        sources: _,
         overrides: _,
         editables,
         source_trees,
@@ -84,10 +134,17 @@ pub(crate) async fn pip_sync(
         find_links,
         no_binary: specified_no_binary,
         no_build: specified_no_build,
-    } = RequirementsSpecification::from_simple_sources(sources, &client_builder).await?;
+    } = if lockfile.is_some() {
+        RequirementsSpecification::default()
+    } else {
+        RequirementsSpecification::from_simple_sources(sources, &client_builder).await?
+    };
 
     // Validate that the requirements are non-empty.
-    let num_requirements = requirements.len() + source_trees.len() + editables.len();
+    let num_requirements = requirements.len()
+        + source_trees.len()
+        + editables.len()
+        + lockfile.as_ref().map_or(0, Lock::len);
     if num_requirements == 0 {
         writeln!(printer.stderr(), "No requirements found")?;
         return Ok(ExitStatus::Success);
@@ -132,6 +189,32 @@ pub(crate) async fn pip_sync(
     // Determine the current environment markers.
     let tags = venv.interpreter().tags()?;
 
+    // Select the locked distributions that apply to the current environment.
+    let locked = lockfile
+        .map(|lockfile| lockfile.to_resolution(venv.interpreter().markers(), tags))
+        .transpose()?;
+
+    // Install any locked editables alongside those provided directly.
+    let editables = if let Some(locked) = locked.as_ref() {
+        locked
+            .distributions()
+            .filter_map(|dist| match dist {
+                ResolvedDist::Installable(Dist::Source(SourceDist::Path(sdist)))
+                    if sdist.editable =>
+                {
+                    Some(EditableRequirement {
+                        url: sdist.url.clone(),
+                        extras: vec![],
+                        path: sdist.path.clone(),
+                    })
+                }
+                _ => None,
+            })
+            .collect()
+    } else {
+        editables
+    };
+
     // Incorporate any index locations from the provided sources.
     let index_locations =
         index_locations.combine(index_url, extra_index_urls, find_links, no_index);
@@ -144,6 +227,9 @@ pub(crate) async fn pip_sync(
     // Initialize the registry client.
     let client = RegistryClientBuilder::new(cache.clone())
         .native_tls(native_tls)
+        .retry_policy(retry_policy.clone())
+        .proxy(proxy.clone())
+        .connection_options(connection_options.clone())
         .connectivity(connectivity)
         .index_urls(index_locations.index_urls())
         .index_strategy(index_strategy)
@@ -193,10 +279,13 @@ pub(crate) async fn pip_sync(
         build_isolation,
         &no_build,
         &no_binary,
-    );
+    )
+    .with_concurrency(concurrency);
 
     // Convert from unnamed to named requirements.
-    let requirements = {
+    let requirements = if let Some(locked) = locked.as_ref() {
+        locked.requirements()
+    } else {
         // Convert from unnamed to named requirements.
         let mut requirements =
             NamedRequirementsResolver::new(requirements, &build_dispatch, &client, &index)
@@ -223,11 +312,29 @@ pub(crate) async fn pip_sync(
         requirements
     };
 
-    // Resolve any editables.
+    // In hash-checking mode, every requirement must be pinned, with at least one hash.
+    let required_hashes = if require_hashes || !hashes.is_empty() {
+        if let Some(editable) = editables.first() {
+            return Err(anyhow!(
+                "In `--require-hashes` mode, editable requirements can't be hashed, but found: `{editable}`"
+            ));
+        }
+        RequiredHashes::from_requirements(
+            requirements
+                .iter()
+                .map(|requirement| (requirement, hashes.get(requirement))),
+        )?
+    } else {
+        RequiredHashes::default()
+    };
+
+    // Resolve any editables. In a dry run, editables that would need to be built are reported
+    // rather than built.
     let resolved_editables = resolve_editables(
         editables,
         &site_packages,
         reinstall,
+        dry_run,
         venv.interpreter(),
         tags,
         &cache,
@@ -247,6 +354,8 @@ pub(crate) async fn pip_sync(
         extraneous,
     } = Planner::with_requirements(&requirements)
         .with_editable_requirements(&resolved_editables.editables)
+        .with_hash_checking(!required_hashes.is_empty())
+        .with_excluded(&exclude)
         .build(
             site_packages,
             reinstall,
@@ -259,7 +368,12 @@ pub(crate) async fn pip_sync(
         .context("Failed to determine installation plan")?;
 
     // Nothing to do.
-    if remote.is_empty() && cached.is_empty() && reinstalls.is_empty() && extraneous.is_empty() {
+    if remote.is_empty()
+        && cached.is_empty()
+        && reinstalls.is_empty()
+        && extraneous.is_empty()
+        && resolved_editables.unbuilt.is_empty()
+    {
         let s = if num_requirements == 1 { "" } else { "s" };
         writeln!(
             printer.stderr(),
@@ -271,6 +385,12 @@ pub(crate) async fn pip_sync(
             )
             .dimmed()
         )?;
+        if dry_run {
+            writeln!(printer.stderr(), "Would make no changes")?;
+        }
+        if report.is_some() {
+            InstallReport::empty().emit(report, post_install_hook, venv.root())?;
+        }
 
         return Ok(ExitStatus::Success);
     }
@@ -278,6 +398,12 @@ pub(crate) async fn pip_sync(
     // Resolve any registry-based requirements.
     let remote = if remote.is_empty() {
         Vec::new()
+    } else if let Some(locked) = locked.as_ref() {
+        // Use the locked distributions as-is.
+        remote
+            .iter()
+            .filter_map(|requirement| locked.get_remote(&requirement.name).cloned())
+            .collect::<Vec<_>>()
     } else {
         let start = std::time::Instant::now();
 
@@ -289,6 +415,7 @@ pub(crate) async fn pip_sync(
         // Resolve with `--no-deps`.
         let options = OptionsBuilder::new()
             .dependency_mode(DependencyMode::Direct)
+            .index_strategy(index_strategy)
             .build();
 
         // Create a bound on the progress bar, since we know the number of packages upfront.
@@ -341,6 +468,19 @@ pub(crate) async fn pip_sync(
             .collect::<Vec<_>>()
     };
 
+    // If this is a dry run, report the planned changes without modifying the environment.
+    if dry_run {
+        let removed = extraneous.iter().chain(&reinstalls).collect::<Vec<_>>();
+        report_dry_run_changes(
+            &remote,
+            &cached,
+            &resolved_editables.unbuilt,
+            &removed,
+            printer,
+        )?;
+        return Ok(ExitStatus::Success);
+    }
+
     // Download, build, and unzip any missing distributions.
     let wheels = if remote.is_empty() {
         Vec::new()
@@ -348,6 +488,8 @@ pub(crate) async fn pip_sync(
         let start = std::time::Instant::now();
 
         let downloader = Downloader::new(&cache, tags, &client, &build_dispatch)
+            .with_concurrency(concurrency.downloads)
+            .with_required_hashes(required_hashes)
             .with_reporter(DownloadReporter::from(printer).with_length(remote.len() as u64));
 
         let wheels = downloader
@@ -375,6 +517,7 @@ pub(crate) async fn pip_sync(
         let start = std::time::Instant::now();
 
         for dist_info in extraneous.iter().chain(reinstalls.iter()) {
+            warn_foreign_installer(dist_info);
             match uv_installer::uninstall(dist_info).await {
                 Ok(summary) => {
                     debug!(
@@ -415,15 +558,43 @@ pub(crate) async fn pip_sync(
         )?;
     }
 
+    // Record the changes to the environment, if a report or post-install hook was requested.
+    let mut install_report = (report.is_some() || post_install_hook.is_some()).then(|| {
+        let mut report = InstallReport::new(link_mode);
+        for wheel in &wheels {
+            let source = remote.iter().find(|dist| dist.name() == wheel.name());
+            report.add_installed(wheel, source, false);
+        }
+        for wheel in &cached {
+            // Distributions linked from the cache are only resolved when reading from a lockfile.
+            let source = locked
+                .as_ref()
+                .and_then(|locked| locked.get_remote(wheel.name()));
+            report.add_installed(wheel, source, true);
+        }
+        for dist_info in extraneous.iter().chain(reinstalls.iter()) {
+            report.add_removed(dist_info);
+        }
+        report
+    });
+
     // Install the resolved distributions.
     let wheels = wheels.into_iter().chain(cached).collect::<Vec<_>>();
     if !wheels.is_empty() {
         let start = std::time::Instant::now();
         uv_installer::Installer::new(&venv)
             .with_link_mode(link_mode)
+            .with_shebang(shebang)
             .with_reporter(InstallReporter::from(printer).with_length(wheels.len() as u64))
             .install(&wheels)?;
 
+        // Record the link mode that was used, after any fallback.
+        if let (Some(install_report), Some(site_packages)) =
+            (install_report.as_mut(), venv.site_packages().next())
+        {
+            install_report.set_link_mode(link_mode.effective(site_packages));
+        }
+
         let s = if wheels.len() == 1 { "" } else { "s" };
         writeln!(
             printer.stderr(),
@@ -438,7 +609,11 @@ pub(crate) async fn pip_sync(
     }
 
     if compile {
-        compile_bytecode(&venv, &cache, printer).await?;
+        let installed = wheels
+            .iter()
+            .map(|wheel| wheel.name().clone())
+            .collect::<Vec<_>>();
+        compile_bytecode(&venv, &cache, &installed, printer).await?;
     }
 
     // Report on any changes in the environment.
@@ -483,6 +658,10 @@ pub(crate) async fn pip_sync(
         }
     }
 
+    if let Some(install_report) = install_report {
+        install_report.emit(report, post_install_hook, venv.root())?;
+    }
+
     // Validate that the environment is consistent.
     if strict {
         let site_packages = SitePackages::from_executable(&venv)?;
@@ -532,9 +711,8 @@ struct ResolvedEditables {
     /// The set of resolved editables, including both those that were already installed and those
     /// that were built.
     editables: Vec<ResolvedEditable>,
-    /// The temporary directory in which the built editables were stored.
-    #[allow(dead_code)]
-    temp_dir: Option<tempfile::TempDir>,
+    /// The editables that must be built, but weren't, since this is a dry run.
+    unbuilt: Vec<EditableRequirement>,
 }
 
 /// Resolve the set of editables that need to be installed.
@@ -543,6 +721,7 @@ async fn resolve_editables(
     editables: Vec<EditableRequirement>,
     site_packages: &SitePackages<'_>,
     reinstall: &Reinstall,
+    dry_run: bool,
     interpreter: &Interpreter,
     tags: &Tags,
     cache: &Cache,
@@ -603,9 +782,20 @@ async fn resolve_editables(
         }
     }
 
+    // In a dry run, skip building any editables.
+    if dry_run {
+        return Ok(ResolvedEditables {
+            editables: installed
+                .into_iter()
+                .map(ResolvedEditable::Installed)
+                .collect::<Vec<_>>(),
+            unbuilt: uninstalled,
+        });
+    }
+
     // Build any editable installs.
-    let (built_editables, temp_dir) = if uninstalled.is_empty() {
-        (Vec::new(), None)
+    let built_editables = if uninstalled.is_empty() {
+        Vec::new()
     } else {
         let start = std::time::Instant::now();
 
@@ -621,9 +811,8 @@ async fn resolve_editables(
             }
         }));
 
-        let editable_wheel_dir = tempfile::tempdir_in(cache.root())?;
         let editables: Vec<_> = downloader
-            .build_editables(editables, editable_wheel_dir.path())
+            .build_editables(editables)
             .await
             .context("Failed to build editables")?
             .into_iter()
@@ -655,7 +844,7 @@ async fn resolve_editables(
             .dimmed()
         )?;
 
-        (editables, Some(editable_wheel_dir))
+        editables
     };
 
     Ok(ResolvedEditables {
@@ -664,6 +853,6 @@ async fn resolve_editables(
             .map(ResolvedEditable::Installed)
             .chain(built_editables.into_iter().map(ResolvedEditable::Built))
             .collect::<Vec<_>>(),
-        temp_dir,
+        unbuilt: Vec::new(),
     })
 }
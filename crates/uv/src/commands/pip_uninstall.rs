@@ -4,16 +4,19 @@ use anyhow::Result;
 use itertools::{Either, Itertools};
 use owo_colors::OwoColorize;
 use tracing::debug;
+use url::Url;
 
 use distribution_types::{InstalledMetadata, Name};
 use pep508_rs::{Requirement, RequirementsTxtRequirement, UnnamedRequirement};
 use uv_auth::KeyringProvider;
 use uv_cache::Cache;
-use uv_client::{BaseClientBuilder, Connectivity};
+use uv_client::{BaseClientBuilder, ConnectionOptions, Connectivity, RetryPolicy};
 use uv_fs::Simplified;
+use uv_installer::PostInstallHook;
 use uv_interpreter::PythonEnvironment;
 
-use crate::commands::{elapsed, ExitStatus};
+use crate::commands::install_report::InstallReport;
+use crate::commands::{elapsed, warn_foreign_installer, ExitStatus};
 use crate::printer::Printer;
 use uv_requirements::{RequirementsSource, RequirementsSpecification};
 
@@ -24,9 +27,13 @@ pub(crate) async fn pip_uninstall(
     python: Option<String>,
     system: bool,
     break_system_packages: bool,
+    post_install_hook: Option<&dyn PostInstallHook>,
     cache: Cache,
     connectivity: Connectivity,
     native_tls: bool,
+    retry_policy: RetryPolicy,
+    proxy: Option<Url>,
+    connection_options: ConnectionOptions,
     keyring_provider: KeyringProvider,
     printer: Printer,
 ) -> Result<ExitStatus> {
@@ -34,6 +41,9 @@ pub(crate) async fn pip_uninstall(
     let client_builder = BaseClientBuilder::new()
         .connectivity(connectivity)
         .native_tls(native_tls)
+        .retry_policy(retry_policy)
+        .proxy(proxy)
+        .connection_options(connection_options)
         .keyring_provider(keyring_provider);
 
     // Read all requirements from the provided sources.
@@ -164,6 +174,7 @@ pub(crate) async fn pip_uninstall(
 
     // Uninstall each package.
     for distribution in &distributions {
+        warn_foreign_installer(distribution);
         let summary = uv_installer::uninstall(distribution).await?;
         debug!(
             "Uninstalled {} ({} file{}, {} director{})",
@@ -191,7 +202,7 @@ pub(crate) async fn pip_uninstall(
         .dimmed()
     )?;
 
-    for distribution in distributions {
+    for distribution in &distributions {
         writeln!(
             printer.stderr(),
             " {} {}{}",
@@ -201,5 +212,13 @@ pub(crate) async fn pip_uninstall(
         )?;
     }
 
+    if post_install_hook.is_some() {
+        let mut report = InstallReport::empty();
+        for distribution in &distributions {
+            report.add_removed(distribution);
+        }
+        report.emit(None, post_install_hook, venv.root())?;
+    }
+
     Ok(ExitStatus::Success)
 }
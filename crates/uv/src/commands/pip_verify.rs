@@ -0,0 +1,196 @@
+use std::fmt::Write;
+use std::path::{Component, Path, PathBuf};
+use std::time::Instant;
+
+use anyhow::Result;
+use owo_colors::OwoColorize;
+use rustc_hash::FxHashSet;
+use tracing::debug;
+
+use distribution_types::{InstalledDist, Name};
+use install_wheel_rs::{verify_installed_files, InstalledFile, InstalledFileStatus};
+use uv_cache::Cache;
+use uv_fs::{normalize_path, Simplified};
+use uv_installer::SitePackages;
+use uv_interpreter::PythonEnvironment;
+
+use crate::commands::{elapsed, ExitStatus};
+use crate::printer::Printer;
+
+/// Verify the integrity of the files installed for each package, as listed in their `RECORD`.
+pub(crate) fn pip_verify(
+    python: Option<&str>,
+    system: bool,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let start = Instant::now();
+
+    // Detect the current Python interpreter.
+    let venv = if let Some(python) = python {
+        PythonEnvironment::from_requested_python(python, cache)?
+    } else if system {
+        PythonEnvironment::from_default_python(cache)?
+    } else {
+        match PythonEnvironment::from_virtualenv(cache) {
+            Ok(venv) => venv,
+            Err(uv_interpreter::Error::VenvNotFound) => {
+                PythonEnvironment::from_default_python(cache)?
+            }
+            Err(err) => return Err(err.into()),
+        }
+    };
+
+    debug!(
+        "Using Python {} environment at {}",
+        venv.interpreter().python_version(),
+        venv.python_executable().user_display().cyan()
+    );
+
+    // Build the installed index.
+    let site_packages = SitePackages::from_executable(&venv)?;
+    let mut packages: Vec<&InstalledDist> = site_packages.iter().collect();
+    packages.sort_unstable_by(|a, b| a.name().cmp(b.name()).then(a.version().cmp(b.version())));
+
+    // Re-hash the files recorded for each package.
+    let mut records = Vec::with_capacity(packages.len());
+    for dist in packages {
+        let files = verify_installed_files(dist.path())?;
+        records.push((dist, files));
+    }
+
+    // Collect every recorded path across all packages, such that files shared between packages
+    // (e.g., in namespace packages) aren't reported as extra.
+    let recorded: FxHashSet<PathBuf> = records
+        .iter()
+        .filter_map(|(dist, files)| Some((root(dist)?, files.as_ref()?)))
+        .flat_map(|(root, files)| {
+            files
+                .iter()
+                .map(move |file| normalize_path(root.join(&file.path)))
+        })
+        .collect();
+
+    let mut issues = 0usize;
+    for (dist, files) in &records {
+        let Some(files) = files else {
+            writeln!(
+                printer.stdout(),
+                "{} {}",
+                format!("{}=={}", dist.name(), dist.version()).bold(),
+                "(cannot locate RECORD)".dimmed()
+            )?;
+            issues += 1;
+            continue;
+        };
+
+        let mut problems: Vec<String> = files
+            .iter()
+            .filter_map(|file| match file.status {
+                InstalledFileStatus::Unchanged => None,
+                InstalledFileStatus::Modified => {
+                    Some(format!("{}: {}", "modified".yellow(), file.path))
+                }
+                InstalledFileStatus::Missing => Some(format!("{}: {}", "missing".red(), file.path)),
+            })
+            .collect();
+        if let Some(root) = root(dist) {
+            for path in extra_files(root, files, &recorded) {
+                problems.push(format!("{}: {path}", "extra".cyan()));
+            }
+        }
+        if problems.is_empty() {
+            continue;
+        }
+
+        writeln!(
+            printer.stdout(),
+            "{}",
+            format!("{}=={}", dist.name(), dist.version()).bold()
+        )?;
+        for problem in &problems {
+            writeln!(printer.stdout(), "  {problem}")?;
+        }
+        issues += problems.len();
+    }
+
+    let s = if records.len() == 1 { "" } else { "s" };
+    writeln!(
+        printer.stderr(),
+        "{}",
+        format!(
+            "Verified {} in {}",
+            format!("{} package{}", records.len(), s).bold(),
+            elapsed(start.elapsed())
+        )
+        .dimmed()
+    )?;
+
+    if issues == 0 {
+        writeln!(
+            printer.stderr(),
+            "{}",
+            "All installed files match their RECORD"
+                .to_string()
+                .dimmed()
+        )?;
+        Ok(ExitStatus::Success)
+    } else {
+        let s = if issues == 1 { "" } else { "s" };
+        writeln!(
+            printer.stderr(),
+            "{}",
+            format!("Found {}", format!("{issues} issue{s}").bold()).dimmed()
+        )?;
+        Ok(ExitStatus::Failure)
+    }
+}
+
+/// Return the directory against which the paths in a distribution's `RECORD` are resolved (e.g.,
+/// `site-packages`).
+fn root(dist: &InstalledDist) -> Option<&Path> {
+    dist.path().parent()
+}
+
+/// Find the files within a distribution's top-level directories (e.g., `site-packages/tqdm`) that
+/// aren't listed in the `RECORD` of any installed package.
+///
+/// Bytecode in `__pycache__` directories is ignored, since it's regenerated by the interpreter.
+fn extra_files(root: &Path, files: &[InstalledFile], recorded: &FxHashSet<PathBuf>) -> Vec<String> {
+    // Determine the top-level directories owned by the distribution, ignoring any paths outside
+    // of the root (e.g., scripts in `../../../bin`).
+    let mut directories: Vec<&str> = files
+        .iter()
+        .filter_map(|file| {
+            let mut components = Path::new(&file.path).components();
+            let Some(Component::Normal(first)) = components.next() else {
+                return None;
+            };
+            components.next()?;
+            first.to_str()
+        })
+        .collect();
+    directories.sort_unstable();
+    directories.dedup();
+
+    let mut extra = Vec::new();
+    for directory in directories {
+        for entry in walkdir::WalkDir::new(root.join(directory))
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| entry.file_name() != "__pycache__")
+            .filter_map(Result::ok)
+        {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            if recorded.contains(&normalize_path(entry.path())) {
+                continue;
+            }
+            if let Ok(relative) = entry.path().strip_prefix(root) {
+                extra.push(relative.to_string_lossy().replace('\\', "/"));
+            }
+        }
+    }
+    extra
+}
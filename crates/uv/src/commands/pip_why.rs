@@ -0,0 +1,222 @@
+use std::fmt::Write;
+
+use anyhow::Result;
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+use rustc_hash::{FxHashMap, FxHashSet};
+use tracing::debug;
+
+use distribution_types::{InstalledDist, Name};
+use pep508_rs::Requirement;
+use uv_cache::Cache;
+use uv_fs::Simplified;
+use uv_installer::SitePackages;
+use uv_interpreter::PythonEnvironment;
+use uv_normalize::{ExtraName, PackageName};
+
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+/// Show the chain of requirements that caused one or more packages to be installed.
+pub(crate) fn pip_why(
+    mut packages: Vec<PackageName>,
+    python: Option<&str>,
+    system: bool,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    if packages.is_empty() {
+        writeln!(
+            printer.stderr(),
+            "{}{} Please provide a package name or names.",
+            "warning".yellow().bold(),
+            ":".bold(),
+        )?;
+        return Ok(ExitStatus::Failure);
+    }
+
+    // Detect the current Python interpreter.
+    let venv = if let Some(python) = python {
+        PythonEnvironment::from_requested_python(python, cache)?
+    } else if system {
+        PythonEnvironment::from_default_python(cache)?
+    } else {
+        match PythonEnvironment::from_virtualenv(cache) {
+            Ok(venv) => venv,
+            Err(uv_interpreter::Error::VenvNotFound) => {
+                PythonEnvironment::from_default_python(cache)?
+            }
+            Err(err) => return Err(err.into()),
+        }
+    };
+
+    debug!(
+        "Using Python {} environment at {}",
+        venv.interpreter().python_version(),
+        venv.python_executable().user_display().cyan()
+    );
+
+    // Build the installed index.
+    let site_packages = SitePackages::from_executable(&venv)?;
+
+    // Determine the markers to use for evaluating requirements.
+    let markers = venv.interpreter().markers();
+
+    // Sort and deduplicate the packages, which are keyed by name.
+    packages.sort_unstable();
+    packages.dedup();
+
+    // Read the requirements of every installed package.
+    let mut installed = FxHashMap::default();
+    for dist in site_packages.iter() {
+        let requires_dist = match dist.metadata() {
+            Ok(metadata) => metadata.requires_dist,
+            Err(err) => {
+                debug!("Failed to read metadata for {dist}: {err}");
+                Vec::new()
+            }
+        };
+        installed.insert(dist.name().clone(), (dist, requires_dist));
+    }
+
+    // Determine the extras that are active for each package, i.e., those that were requested by
+    // another installed package. Activating an extra can activate further extras, so iterate until
+    // we reach a fixed point.
+    let mut extras: FxHashMap<PackageName, FxHashSet<ExtraName>> = FxHashMap::default();
+    loop {
+        let mut changed = false;
+        for (name, (_, requires_dist)) in &installed {
+            let active = active_extras(&extras, name);
+            for requirement in requires_dist {
+                if !installed.contains_key(&requirement.name)
+                    || !requirement.evaluate_markers(markers, &active)
+                {
+                    continue;
+                }
+                for extra in &requirement.extras {
+                    changed |= extras
+                        .entry(requirement.name.clone())
+                        .or_default()
+                        .insert(extra.clone());
+                }
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    // Invert the dependency graph, mapping each package to the requirements that pulled it in.
+    let mut dependents: FxHashMap<&PackageName, Vec<(&InstalledDist, &Requirement)>> =
+        FxHashMap::default();
+    for (name, (dist, requires_dist)) in &installed {
+        let active = active_extras(&extras, name);
+        for requirement in requires_dist {
+            if &requirement.name == name
+                || !installed.contains_key(&requirement.name)
+                || !requirement.evaluate_markers(markers, &active)
+            {
+                continue;
+            }
+            dependents
+                .entry(&requirement.name)
+                .or_default()
+                .push((*dist, requirement));
+        }
+    }
+    for parents in dependents.values_mut() {
+        parents.sort_by(|(a, _), (b, _)| a.name().cmp(b.name()));
+        parents.dedup_by(|(a, _), (b, _)| a.name() == b.name());
+    }
+
+    // Map to the local distributions and collect missing packages.
+    let missing = packages
+        .iter()
+        .filter(|name| !installed.contains_key(*name))
+        .collect_vec();
+    if !missing.is_empty() {
+        writeln!(
+            printer.stderr(),
+            "{}{} Package(s) not found for: {}",
+            "warning".yellow().bold(),
+            ":".bold(),
+            missing.iter().join(", ").bold()
+        )?;
+    }
+
+    // Like `pip show`, if no packages were found, return a failure.
+    let found = packages
+        .iter()
+        .filter_map(|name| installed.get(name))
+        .collect_vec();
+    if found.is_empty() {
+        return Ok(ExitStatus::Failure);
+    }
+
+    // Print the inverted dependency tree for each package.
+    for (dist, _) in found {
+        writeln!(printer.stdout(), "{}", dist.bold())?;
+        let mut path = vec![dist.name()];
+        write_dependents(dist.name(), &dependents, &mut path, "", printer)?;
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// Return the active extras for the given package.
+fn active_extras(
+    extras: &FxHashMap<PackageName, FxHashSet<ExtraName>>,
+    name: &PackageName,
+) -> Vec<ExtraName> {
+    extras
+        .get(name)
+        .map(|extras| extras.iter().cloned().sorted_unstable().collect())
+        .unwrap_or_default()
+}
+
+/// Write the packages that depend on the given package, recursively, as a tree.
+fn write_dependents<'a>(
+    name: &PackageName,
+    dependents: &FxHashMap<&PackageName, Vec<(&'a InstalledDist, &Requirement)>>,
+    path: &mut Vec<&'a PackageName>,
+    prefix: &str,
+    printer: Printer,
+) -> Result<()> {
+    let Some(parents) = dependents.get(name) else {
+        return Ok(());
+    };
+    for (index, &(dist, requirement)) in parents.iter().enumerate() {
+        let last = index == parents.len() - 1;
+        let (branch, indent) = if last {
+            ("└── ", "    ")
+        } else {
+            ("├── ", "│   ")
+        };
+
+        // Avoid infinite recursion on dependency cycles.
+        if path.contains(&dist.name()) {
+            writeln!(
+                printer.stdout(),
+                "{prefix}{branch}{dist} {} (*)",
+                format!("(requires {requirement})").dimmed()
+            )?;
+            continue;
+        }
+
+        writeln!(
+            printer.stdout(),
+            "{prefix}{branch}{dist} {}",
+            format!("(requires {requirement})").dimmed()
+        )?;
+        path.push(dist.name());
+        write_dependents(
+            dist.name(),
+            dependents,
+            path,
+            &format!("{prefix}{indent}"),
+            printer,
+        )?;
+        path.pop();
+    }
+    Ok(())
+}
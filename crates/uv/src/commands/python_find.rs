@@ -0,0 +1,50 @@
+use std::fmt::Write;
+
+use anyhow::Result;
+
+use uv_cache::Cache;
+use uv_fs::Simplified;
+use uv_interpreter::{find_best_python, find_default_python, find_requested_python};
+
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+/// Show the Python interpreter that would be selected for the given request, as in `--python`.
+///
+/// If `json` is set, the interpreter's [`uv_interpreter::PythonTarget`] is printed instead of its
+/// path.
+pub(crate) fn python_find(
+    python: Option<&str>,
+    system: bool,
+    json: bool,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let interpreter = if let Some(python) = python {
+        find_requested_python(python, cache)?
+            .ok_or_else(|| uv_interpreter::Error::RequestedPythonNotFound(python.to_string()))?
+    } else if system {
+        find_default_python(cache)?
+    } else {
+        // Prefer any virtual environment, falling back to the default Python, in the order
+        // configured by `--python-sources`.
+        find_best_python(None, cache)?
+    };
+
+    if json {
+        let target = interpreter.target()?;
+        writeln!(
+            printer.stdout(),
+            "{}",
+            serde_json::to_string_pretty(&target)?
+        )?;
+    } else {
+        writeln!(
+            printer.stdout(),
+            "{}",
+            interpreter.sys_executable().simplified_display()
+        )?;
+    }
+
+    Ok(ExitStatus::Success)
+}
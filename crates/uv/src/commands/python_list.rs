@@ -0,0 +1,48 @@
+use std::fmt::Write;
+
+use anyhow::Result;
+use owo_colors::OwoColorize;
+
+use uv_cache::Cache;
+use uv_fs::Simplified;
+use uv_interpreter::find_all_pythons;
+
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+/// List the Python interpreters that can be discovered.
+pub(crate) fn python_list(cache: &Cache, printer: Printer) -> Result<ExitStatus> {
+    let interpreters = find_all_pythons(cache)?;
+
+    // Identify each interpreter by its implementation, version, and platform (e.g.,
+    // `cpython-3.12.1-linux-x86_64`).
+    let rows = interpreters
+        .iter()
+        .map(|(source, interpreter)| {
+            let key = format!(
+                "{}-{}-{}-{}",
+                interpreter.implementation_name(),
+                interpreter.python_version(),
+                interpreter.markers().sys_platform,
+                interpreter.platform().arch(),
+            );
+            (key, interpreter.sys_executable(), source)
+        })
+        .collect::<Vec<_>>();
+
+    let width = rows.iter().map(|(key, ..)| key.len()).max().unwrap_or(0);
+    for (key, executable, source) in rows {
+        writeln!(
+            printer.stdout(),
+            "{key:width$}  {} {}",
+            executable.user_display().cyan(),
+            format!("({source})").dimmed()
+        )?;
+    }
+
+    if interpreters.is_empty() {
+        writeln!(printer.stderr(), "No Python interpreters found")?;
+    }
+
+    Ok(ExitStatus::Success)
+}
@@ -1,3 +1,4 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
@@ -163,12 +164,14 @@ impl uv_installer::InstallReporter for InstallReporter {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) struct ResolverReporter {
     printer: Printer,
     multi_progress: MultiProgress,
     progress: ProgressBar,
     bars: Arc<Mutex<Vec<ProgressBar>>>,
+    /// The number of resolutions sharing the reporter that have yet to complete.
+    pending: Arc<AtomicUsize>,
 }
 
 impl From<Printer> for ResolverReporter {
@@ -189,6 +192,7 @@ impl From<Printer> for ResolverReporter {
             multi_progress,
             progress,
             bars: Arc::new(Mutex::new(Vec::new())),
+            pending: Arc::new(AtomicUsize::new(1)),
         }
     }
 }
@@ -200,6 +204,14 @@ impl ResolverReporter {
         self
     }
 
+    /// Share the reporter between the given number of concurrent resolutions (e.g., the forks of
+    /// a universal resolution), which are reported as complete once all of them complete.
+    #[must_use]
+    pub(crate) fn with_resolutions(self, resolutions: usize) -> Self {
+        self.pending.store(resolutions, Ordering::Release);
+        self
+    }
+
     fn on_progress(&self, name: &PackageName, version_or_url: &VersionOrUrl) {
         match version_or_url {
             VersionOrUrl::Version(version) => {
@@ -212,7 +224,9 @@ impl ResolverReporter {
     }
 
     fn on_complete(&self) {
-        self.progress.finish_and_clear();
+        if self.pending.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.progress.finish_and_clear();
+        }
     }
 
     fn on_build_start(&self, source: &BuildableSource) -> usize {
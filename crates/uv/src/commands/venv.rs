@@ -10,27 +10,37 @@ use itertools::Itertools;
 use miette::{Diagnostic, IntoDiagnostic};
 use owo_colors::OwoColorize;
 use thiserror::Error;
+use url::Url;
 
 use distribution_types::{DistributionMetadata, IndexLocations, Name, ResolvedDist};
 use pep508_rs::Requirement;
 use uv_auth::{KeyringProvider, GLOBAL_AUTH_STORE};
 use uv_cache::Cache;
-use uv_client::{Connectivity, FlatIndex, FlatIndexClient, RegistryClientBuilder};
+use uv_client::{
+    ConnectionOptions, Connectivity, FlatIndex, FlatIndexClient, RegistryClientBuilder, RetryPolicy,
+};
 use uv_dispatch::BuildDispatch;
 use uv_fs::Simplified;
-use uv_interpreter::{find_default_python, find_requested_python, Error};
+use uv_installer::SitePackages;
+use uv_interpreter::{find_default_python, find_requested_python, Error, PyVenvConfiguration};
+use uv_normalize::PackageName;
 use uv_resolver::{InMemoryIndex, OptionsBuilder};
 use uv_types::{
     BuildContext, BuildIsolation, ConfigSettings, InFlight, IndexStrategy, NoBinary, NoBuild,
     SetupPyStrategy,
 };
+use uv_warnings::warn_user;
 
 use crate::commands::ExitStatus;
 use crate::printer::Printer;
 use crate::shell::Shell;
 
 /// Create a virtual environment.
-#[allow(clippy::unnecessary_wraps, clippy::too_many_arguments)]
+#[allow(
+    clippy::unnecessary_wraps,
+    clippy::too_many_arguments,
+    clippy::fn_params_excessive_bools
+)]
 pub(crate) async fn venv(
     path: &Path,
     python_request: Option<&str>,
@@ -39,10 +49,15 @@ pub(crate) async fn venv(
     keyring_provider: KeyringProvider,
     prompt: uv_virtualenv::Prompt,
     system_site_packages: bool,
+    copies: bool,
+    upgrade: bool,
     connectivity: Connectivity,
     seed: bool,
     exclude_newer: Option<DateTime<Utc>>,
     native_tls: bool,
+    retry_policy: RetryPolicy,
+    proxy: Option<Url>,
+    connection_options: ConnectionOptions,
     cache: &Cache,
     printer: Printer,
 ) -> Result<ExitStatus> {
@@ -54,10 +69,15 @@ pub(crate) async fn venv(
         keyring_provider,
         prompt,
         system_site_packages,
+        copies,
+        upgrade,
         connectivity,
         seed,
         exclude_newer,
         native_tls,
+        retry_policy,
+        proxy,
+        connection_options,
         cache,
         printer,
     )
@@ -77,6 +97,10 @@ enum VenvError {
     #[diagnostic(code(uv::venv::creation))]
     Creation(#[source] uv_virtualenv::Error),
 
+    #[error("Failed to upgrade virtualenv")]
+    #[diagnostic(code(uv::venv::upgrade))]
+    Upgrade(#[source] uv_virtualenv::Error),
+
     #[error("Failed to install seed packages")]
     #[diagnostic(code(uv::venv::seed))]
     Seed(#[source] anyhow::Error),
@@ -91,7 +115,7 @@ enum VenvError {
 }
 
 /// Create a virtual environment.
-#[allow(clippy::too_many_arguments)]
+#[allow(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
 async fn venv_impl(
     path: &Path,
     python_request: Option<&str>,
@@ -100,13 +124,32 @@ async fn venv_impl(
     keyring_provider: KeyringProvider,
     prompt: uv_virtualenv::Prompt,
     system_site_packages: bool,
+    copies: bool,
+    upgrade: bool,
     connectivity: Connectivity,
     seed: bool,
     exclude_newer: Option<DateTime<Utc>>,
     native_tls: bool,
+    retry_policy: RetryPolicy,
+    proxy: Option<Url>,
+    connection_options: ConnectionOptions,
     cache: &Cache,
     printer: Printer,
 ) -> miette::Result<ExitStatus> {
+    // When upgrading, default to an interpreter matching the environment's `major.minor` version,
+    // since the environment's own interpreter may no longer exist.
+    let upgrade_request = if upgrade && python_request.is_none() {
+        PyVenvConfiguration::parse(path.join("pyvenv.cfg"))
+            .ok()
+            .and_then(|cfg| {
+                cfg.version_info()
+                    .map(|version| version.split('.').take(2).join("."))
+            })
+    } else {
+        None
+    };
+    let python_request = python_request.or(upgrade_request.as_deref());
+
     // Locate the Python interpreter.
     let interpreter = if let Some(python_request) = python_request {
         find_requested_python(python_request, cache)
@@ -127,18 +170,54 @@ async fn venv_impl(
 
     writeln!(
         printer.stderr(),
-        "Creating virtualenv at: {}",
+        "{} virtualenv at: {}",
+        if upgrade { "Upgrading" } else { "Creating" },
         path.user_display().cyan()
     )
     .into_diagnostic()?;
 
-    // Extra cfg for pyvenv.cfg to specify uv version
-    let extra_cfg = vec![("uv".to_string(), env!("CARGO_PKG_VERSION").to_string())];
+    let venv = if upgrade {
+        // Upgrade the virtual environment in-place.
+        uv_virtualenv::upgrade_venv(path, interpreter).map_err(VenvError::Upgrade)?
+    } else {
+        // Extra cfg for pyvenv.cfg to specify uv version
+        let extra_cfg = vec![("uv".to_string(), env!("CARGO_PKG_VERSION").to_string())];
 
-    // Create the virtual environment.
-    let venv =
-        uv_virtualenv::create_venv(path, interpreter, prompt, system_site_packages, extra_cfg)
-            .map_err(VenvError::Creation)?;
+        // Create the virtual environment.
+        uv_virtualenv::create_venv(
+            path,
+            interpreter,
+            prompt,
+            system_site_packages,
+            copies,
+            extra_cfg,
+        )
+        .map_err(VenvError::Creation)?
+    };
+
+    // When upgrading, check that any existing seed packages still work with the new interpreter,
+    // rather than reinstalling them.
+    if upgrade && !seed {
+        let site_packages = SitePackages::from_executable(&venv).map_err(VenvError::Seed)?;
+        for name in ["pip", "setuptools", "wheel"] {
+            if site_packages
+                .get_packages(&PackageName::from_str(name).unwrap())
+                .is_empty()
+            {
+                continue;
+            }
+            let output = std::process::Command::new(venv.python_executable())
+                .arg("-c")
+                .arg(format!("import {name}"))
+                .output()
+                .into_diagnostic()?;
+            if !output.status.success() {
+                warn_user!(
+                    "Seed package `{name}` can't be imported by the upgraded interpreter; re-run with `--seed` to reinstall it"
+                );
+            }
+        }
+    }
 
     // Install seed packages.
     if seed {
@@ -153,6 +232,9 @@ async fn venv_impl(
         // Instantiate a client.
         let client = RegistryClientBuilder::new(cache.clone())
             .native_tls(native_tls)
+            .retry_policy(retry_policy)
+            .proxy(proxy)
+            .connection_options(connection_options)
             .index_urls(index_locations.index_urls())
             .index_strategy(index_strategy)
             .keyring_provider(keyring_provider)
@@ -196,7 +278,12 @@ async fn venv_impl(
             &NoBuild::All,
             &NoBinary::None,
         )
-        .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build());
+        .with_options(
+            OptionsBuilder::new()
+                .exclude_newer(exclude_newer)
+                .index_strategy(index_strategy)
+                .build(),
+        );
 
         // Resolve the seed packages.
         let mut requirements = vec![Requirement::from_str("pip").unwrap()];
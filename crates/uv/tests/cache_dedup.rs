@@ -0,0 +1,107 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+
+use common::uv_snapshot;
+
+use crate::common::{get_bin, TestContext};
+
+mod common;
+
+/// Create a `cache dedup` command with options shared across scenarios.
+fn dedup_command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("cache")
+        .arg("dedup")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir);
+
+    if cfg!(all(windows, debug_assertions)) {
+        // TODO(konstin): Reduce stack usage in debug mode enough that the tests pass with the
+        // default windows stack of 1MB
+        command.env("UV_STACK_SIZE", (8 * 1024 * 1024).to_string());
+    }
+
+    command
+}
+
+/// `cache dedup` should be a no-op if there are no duplicate files in the cache.
+#[test]
+fn dedup_no_op() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let archive = context.cache_dir.child("archive-v0");
+    archive
+        .child("a")
+        .child("foo.py")
+        .write_str("print('foo')")?;
+    archive
+        .child("b")
+        .child("bar.py")
+        .write_str("print('bar')")?;
+
+    uv_snapshot!(context.filters(), dedup_command(&context), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Deduplicating cache at: [CACHE_DIR]/
+    No duplicate files found
+    "###);
+
+    Ok(())
+}
+
+/// `cache dedup` should link identical files across archive entries.
+#[test]
+fn dedup_identical_files() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let archive = context.cache_dir.child("archive-v0");
+    archive
+        .child("a")
+        .child("foo.py")
+        .write_str("print('foo')")?;
+    archive
+        .child("b")
+        .child("foo.py")
+        .write_str("print('foo')")?;
+    archive
+        .child("c")
+        .child("bar.py")
+        .write_str("print('bar')")?;
+
+    uv_snapshot!(context.filters(), dedup_command(&context), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Deduplicating cache at: [CACHE_DIR]/
+    Linked 1 file ([SIZE] saved)
+    "###);
+
+    // Running again should find nothing left to deduplicate.
+    uv_snapshot!(context.filters(), dedup_command(&context), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Deduplicating cache at: [CACHE_DIR]/
+    No duplicate files found
+    "###);
+
+    archive.child("a").child("foo.py").assert("print('foo')");
+    archive.child("b").child("foo.py").assert("print('foo')");
+
+    Ok(())
+}
@@ -0,0 +1,134 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+
+use common::uv_snapshot;
+
+use crate::common::{get_bin, TestContext};
+
+mod common;
+
+/// `cache dir` should prefer a project-local `.uv-cache` directory in an ancestor of the current
+/// working directory.
+#[test]
+fn cache_dir_project_local() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let project = context.temp_dir.child("project");
+    project.child(".uv-cache").create_dir_all()?;
+    let subdirectory = project.child("subdirectory");
+    subdirectory.create_dir_all()?;
+
+    uv_snapshot!(context.filters(), Command::new(get_bin())
+        .arg("cache")
+        .arg("dir")
+        .env_remove("UV_CACHE_DIR")
+        .current_dir(&subdirectory), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    [TEMP_DIR]/project/.uv-cache
+
+    ----- stderr -----
+    "###);
+
+    // An explicit `--cache-dir` takes precedence.
+    uv_snapshot!(context.filters(), Command::new(get_bin())
+        .arg("cache")
+        .arg("dir")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .current_dir(&subdirectory), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    [CACHE_DIR]/
+
+    ----- stderr -----
+    "###);
+
+    Ok(())
+}
+
+/// Wheels in the global cache should be reused when a project-local `.uv-cache` is layered over
+/// it, including wheels from a `--find-links` index.
+#[test]
+#[cfg(target_os = "linux")]
+fn cache_dir_project_local_fallback() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let project = context.temp_dir.child("project");
+    project.create_dir_all()?;
+    let xdg_cache_home = context.temp_dir.child("xdg");
+
+    // Populate the global cache.
+    uv_snapshot!(context.filters(), Command::new(get_bin())
+        .arg("pip")
+        .arg("install")
+        .arg("tqdm")
+        .arg("--find-links")
+        .arg(context.workspace_root.join("scripts/links/"))
+        .arg("--cache-dir")
+        .arg(xdg_cache_home.child("uv").path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&project), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + tqdm==1000.0.0
+    "###
+    );
+
+    uv_snapshot!(context.filters(), Command::new(get_bin())
+        .arg("pip")
+        .arg("uninstall")
+        .arg("tqdm")
+        .arg("--cache-dir")
+        .arg(xdg_cache_home.child("uv").path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&project), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Uninstalled 1 package in [TIME]
+     - tqdm==1000.0.0
+    "###
+    );
+
+    // With a project-local cache, the wheel should be read from the global cache, rather than
+    // unzipped again.
+    project.child(".uv-cache").create_dir_all()?;
+    uv_snapshot!(context.filters(), Command::new(get_bin())
+        .arg("pip")
+        .arg("install")
+        .arg("tqdm")
+        .arg("--find-links")
+        .arg(context.workspace_root.join("scripts/links/"))
+        .arg("--offline")
+        .env_remove("UV_CACHE_DIR")
+        .env("XDG_CACHE_HOME", xdg_cache_home.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&project), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + tqdm==1000.0.0
+    "###
+    );
+
+    Ok(())
+}
@@ -0,0 +1,82 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use itertools::Itertools;
+
+use uv_cache::{Cache, CacheBucket};
+
+use crate::common::{get_bin, TestContext};
+
+mod common;
+
+/// Create a `pip sync` command with options shared across scenarios.
+fn sync_command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("pip")
+        .arg("sync")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir);
+
+    if cfg!(all(windows, debug_assertions)) {
+        // TODO(konstin): Reduce stack usage in debug mode enough that the tests pass with the
+        // default windows stack of 1MB
+        command.env("UV_STACK_SIZE", (8 * 1024 * 1024).to_string());
+    }
+
+    command
+}
+
+/// `Cache::entries` should describe the wheels written to the cache by an install.
+#[test]
+fn entries_wheels() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("tqdm==1000.0.0")?;
+
+    sync_command(&context)
+        .arg("requirements.txt")
+        .arg("--no-index")
+        .arg("--find-links")
+        .arg(context.workspace_root.join("scripts/links/"))
+        .assert()
+        .success();
+
+    let cache = Cache::from_path(context.cache_dir.path())?;
+    let entries = cache
+        .entries(CacheBucket::Wheels)
+        .map(|entry| {
+            let entry = entry?;
+            assert!(entry.size > 0);
+            assert!(entry.modified.is_some());
+            Ok(format!(
+                "{}: {} {} ({:?})",
+                entry.path.file_name().unwrap().to_string_lossy(),
+                entry.name.map(|name| name.to_string()).unwrap_or_default(),
+                entry
+                    .version
+                    .map(|version| version.to_string())
+                    .unwrap_or_default(),
+                entry.kind.unwrap(),
+            ))
+        })
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .sorted()
+        .join("\n");
+
+    insta::assert_snapshot!(entries, @r###"
+    tqdm-1000.0.0-py3-none-any: tqdm 1000.0.0 (Index)
+    tqdm-1000.0.0-py3-none-any.rev: tqdm 1000.0.0 (Index)
+    "###);
+
+    Ok(())
+}
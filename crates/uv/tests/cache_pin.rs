@@ -0,0 +1,101 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+
+use common::uv_snapshot;
+
+use crate::common::{get_bin, TestContext};
+
+mod common;
+
+/// Create a `cache` command with options shared across scenarios.
+fn cache_command(context: &TestContext, subcommand: &str) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("cache")
+        .arg(subcommand)
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir);
+
+    if cfg!(all(windows, debug_assertions)) {
+        // TODO(konstin): Reduce stack usage in debug mode enough that the tests pass with the
+        // default windows stack of 1MB
+        command.env("UV_STACK_SIZE", (8 * 1024 * 1024).to_string());
+    }
+
+    command
+}
+
+/// `cache clean` should preserve the entries for pinned packages, unless `--force` is provided.
+#[test]
+fn clean_pinned() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("anyio")?;
+
+    // Install a requirement, to populate the cache.
+    Command::new(get_bin())
+        .arg("pip")
+        .arg("sync")
+        .arg("requirements.txt")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir)
+        .assert()
+        .success();
+
+    uv_snapshot!(context.filters(), cache_command(&context, "pin").arg("anyio"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Pinned anyio
+    "###);
+
+    // Removing a pinned package should be skipped.
+    uv_snapshot!(context.filters(), cache_command(&context, "clean").arg("anyio"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Skipping pinned package anyio (use `--force` to remove)
+    "###);
+
+    // Clearing the cache should preserve the pinned package, and its archive.
+    cache_command(&context, "clean").assert().success();
+
+    let wheels = context.cache_dir.child("wheels-v0").child("pypi");
+    wheels.child("anyio").assert(predicates::path::is_dir());
+    wheels.child("sniffio").assert(predicates::path::missing());
+
+    uv_snapshot!(context.filters(), cache_command(&context, "prune"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Pruning cache at: [CACHE_DIR]/
+    No unused entries found
+    "###);
+
+    // With `--force`, the pinned package should be removed too.
+    cache_command(&context, "clean")
+        .arg("--force")
+        .assert()
+        .success();
+
+    context.cache_dir.assert(predicates::path::missing());
+
+    Ok(())
+}
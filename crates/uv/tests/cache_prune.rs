@@ -1,6 +1,7 @@
 #![cfg(all(feature = "python", feature = "pypi"))]
 
 use std::process::Command;
+use std::time::{Duration, Instant};
 
 use anyhow::Result;
 use assert_cmd::prelude::*;
@@ -113,6 +114,39 @@ fn prune_stale_directory() -> Result<()> {
     Ok(())
 }
 
+/// `cache prune --dry-run` should report stale top-level directories without removing them.
+#[test]
+fn prune_stale_directory_dry_run() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("anyio")?;
+
+    // Install a requirement, to populate the cache.
+    sync_command(&context)
+        .arg("requirements.txt")
+        .assert()
+        .success();
+
+    // Add a stale directory to the cache.
+    let simple = context.cache_dir.child("simple-v4");
+    simple.create_dir_all()?;
+
+    uv_snapshot!(context.filters(), prune_command(&context).arg("--dry-run"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Would prune cache at: [CACHE_DIR]/
+    Would remove 1 directory
+    "###);
+
+    simple.assert(predicates::path::is_dir());
+
+    Ok(())
+}
+
 /// `cache prune` should remove any stale symlink from the cache.
 #[test]
 fn prune_stale_symlink() -> Result<()> {
@@ -156,3 +190,79 @@ fn prune_stale_symlink() -> Result<()> {
 
     Ok(())
 }
+
+/// With `--cache-auto-prune-interval`, dangling archives should be removed at the end of a command.
+#[test]
+fn auto_prune_stale_symlink() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("anyio")?;
+
+    // Install a requirement, to populate the cache.
+    sync_command(&context)
+        .arg("requirements.txt")
+        .assert()
+        .success();
+
+    // Remove the wheels directory, causing the symlink to become stale.
+    let wheels = context.cache_dir.child("wheels-v0");
+    fs_err::remove_dir_all(wheels)?;
+
+    // Re-run the sync with automatic pruning enabled on every command.
+    sync_command(&context)
+        .arg("requirements.txt")
+        .arg("--cache-auto-prune-interval")
+        .arg("1")
+        .assert()
+        .success();
+
+    // The dangling archive should be removed by a background process, shortly after the command
+    // exits.
+    let archive = context.cache_dir.child("archive-v0");
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while fs_err::read_dir(archive.path())?.count() > 0 {
+        assert!(
+            Instant::now() < deadline,
+            "Dangling archive was not removed"
+        );
+        std::thread::sleep(Duration::from_millis(50));
+    }
+
+    Ok(())
+}
+
+/// `cache prune --unused-for` should retain packages that were used recently, as recorded in the
+/// access log.
+#[test]
+fn prune_unused_for() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("anyio")?;
+
+    // Install a requirement, to populate the cache.
+    sync_command(&context)
+        .arg("requirements.txt")
+        .assert()
+        .success();
+
+    // The wheels used by the install should be recorded in the access log.
+    context
+        .cache_dir
+        .child(".access")
+        .child("wheels-v0.msgpack")
+        .assert(predicates::path::is_file());
+
+    uv_snapshot!(context.filters(), prune_command(&context).arg("--unused-for").arg("1w"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Pruning cache at: [CACHE_DIR]/
+    No unused entries found
+    "###);
+
+    Ok(())
+}
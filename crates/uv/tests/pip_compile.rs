@@ -7,12 +7,13 @@ use std::path::PathBuf;
 use std::process::Command;
 
 use anyhow::{bail, Context, Result};
+use assert_cmd::assert::OutputAssertExt;
 use assert_fs::prelude::*;
 use assert_fs::TempDir;
 use indoc::indoc;
 use url::Url;
 
-use common::{uv_snapshot, TestContext};
+use common::{uv_snapshot, TestContext, EXCLUDE_NEWER};
 use uv_fs::Simplified;
 
 use crate::common::get_bin;
@@ -795,6 +796,156 @@ fn compile_python_312() -> Result<()> {
     Ok(())
 }
 
+/// Mirrors given on the command line apply to the default index, not to an index set by `-i` in a
+/// requirements file; otherwise, requests for a private index could be sent to a public mirror.
+#[test]
+fn compile_index_mirror_requirements_file_index() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str(indoc! {"
+        -i http://127.0.0.1:1/simple
+        anyio==3.7.0
+    "})?;
+
+    // The index in the file is unreachable; if requests failed over to the mirror, the
+    // resolution would succeed.
+    context
+        .compile()
+        .arg("requirements.in")
+        .arg("--index-mirror")
+        .arg("https://pypi.org/simple")
+        .assert()
+        .failure();
+
+    Ok(())
+}
+
+/// Resolve a specific version of Black for a Windows environment described by `--python-env`,
+/// which should pull in `colorama` (via `click`) regardless of the current platform.
+#[test]
+fn compile_python_env() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("black==23.10.1")?;
+    let target = context.temp_dir.child("target.json");
+    target.write_str(indoc! {r#"
+        {
+            "markers": {
+                "implementation_name": "cpython",
+                "implementation_version": "3.12.1",
+                "os_name": "nt",
+                "platform_machine": "AMD64",
+                "platform_python_implementation": "CPython",
+                "platform_release": "10",
+                "platform_system": "Windows",
+                "platform_version": "10.0.22631",
+                "python_full_version": "3.12.1",
+                "python_version": "3.12",
+                "sys_platform": "win32"
+            },
+            "tags": [
+                "cp312-cp312-win_amd64",
+                "cp312-abi3-win_amd64",
+                "cp312-none-win_amd64",
+                "py3-none-win_amd64",
+                "cp312-none-any",
+                "py3-none-any"
+            ]
+        }
+    "#})?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--python-env")
+            .arg("target.json"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --python-env target.json
+    black==23.10.1
+    click==8.1.7
+        # via black
+    colorama==0.4.6
+        # via click
+    mypy-extensions==1.0.0
+        # via black
+    packaging==24.0
+        # via black
+    pathspec==0.12.1
+        # via black
+    platformdirs==4.2.0
+        # via black
+
+    ----- stderr -----
+    Resolved 7 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Resolve for an environment described by `--python-env` with `--only-binary :all:`, which
+/// shouldn't require a local interpreter.
+#[test]
+fn compile_python_env_without_interpreter() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("tqdm")?;
+    let target = context.temp_dir.child("target.json");
+    target.write_str(indoc! {r#"
+        {
+            "markers": {
+                "implementation_name": "cpython",
+                "implementation_version": "3.12.1",
+                "os_name": "posix",
+                "platform_machine": "aarch64",
+                "platform_python_implementation": "CPython",
+                "platform_release": "",
+                "platform_system": "Linux",
+                "platform_version": "",
+                "python_full_version": "3.12.1",
+                "python_version": "3.12",
+                "sys_platform": "linux"
+            },
+            "platform": {
+                "os": { "name": "manylinux", "major": 2, "minor": 17 },
+                "arch": "aarch64"
+            },
+            "tags": [
+                "cp312-cp312-manylinux_2_17_aarch64",
+                "cp312-abi3-manylinux_2_17_aarch64",
+                "py3-none-any"
+            ]
+        }
+    "#})?;
+
+    // Point `VIRTUAL_ENV` at a missing environment, such that interpreter discovery would fail.
+    uv_snapshot!(context.filters(), context.compile()
+            .arg("requirements.in")
+            .arg("--python-env")
+            .arg("target.json")
+            .arg("--only-binary")
+            .arg(":all:")
+            .arg("--no-index")
+            .arg("--find-links")
+            .arg(context.workspace_root.join("scripts").join("links"))
+            .env("VIRTUAL_ENV", context.temp_dir.child("missing").path()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --python-env target.json --only-binary :all: --no-index
+    tqdm==1000.0.0
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
 /// Resolve a specific version of Black at Python 3.12 with `--annotation-style=line`.
 #[test]
 fn compile_python_312_annotation_line() -> Result<()> {
@@ -1581,6 +1732,80 @@ fn conflicting_transitive_url_dependency() -> Result<()> {
       × No solution found when resolving dependencies:
       ╰─▶ Because only werkzeug<3.0.0 is available and flask==3.0.0 depends on werkzeug>=3.0.0, we can conclude that flask==3.0.0 cannot be used.
           And because you require flask==3.0.0, we can conclude that the requirements are unsatisfiable.
+
+          hint: werkzeug was requested from a URL (https://files.pythonhosted.org/packages/ff/1d/960bb4017c68674a1cb099534840f18d3def3ce44aed12b5ed8b78e0153e/Werkzeug-2.0.0-py3-none-any.whl), but flask==3.0.0 depends on werkzeug>=3.0.0, which the URL doesn't satisfy (try: `--allow-url-override` to ignore version specifiers for packages requested from a URL)
+    "###
+    );
+
+    Ok(())
+}
+
+/// Request Flask, but include a URL dependency for a conflicting version of Werkzeug, and explain
+/// the conflict with `--explain`.
+#[test]
+fn conflicting_transitive_url_dependency_explain() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("flask==3.0.0\nwerkzeug @ https://files.pythonhosted.org/packages/ff/1d/960bb4017c68674a1cb099534840f18d3def3ce44aed12b5ed8b78e0153e/Werkzeug-2.0.0-py3-none-any.whl")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--explain"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+      × No solution found when resolving dependencies:
+      ╰─▶ Because only werkzeug<3.0.0 is available and flask==3.0.0 depends on werkzeug>=3.0.0, we can conclude that flask==3.0.0 cannot be used.
+          And because you require flask==3.0.0, we can conclude that the requirements are unsatisfiable.
+
+          hint: werkzeug was requested from a URL (https://files.pythonhosted.org/packages/ff/1d/960bb4017c68674a1cb099534840f18d3def3ce44aed12b5ed8b78e0153e/Werkzeug-2.0.0-py3-none-any.whl), but flask==3.0.0 depends on werkzeug>=3.0.0, which the URL doesn't satisfy (try: `--allow-url-override` to ignore version specifiers for packages requested from a URL)
+
+    the requirements are unsatisfiable, because:
+    - flask==3.0.0 cannot be used, because:
+      - only werkzeug<3.0.0 is available
+      - flask==3.0.0 depends on werkzeug>=3.0.0
+    - you require flask==3.0.0
+    "###
+    );
+
+    Ok(())
+}
+
+/// Request Flask, but include a URL dependency for a conflicting version of Werkzeug, allowing the
+/// URL to take precedence over Flask's version specifiers with `--allow-url-override`.
+#[test]
+#[cfg(not(windows))]
+fn conflicting_transitive_url_dependency_allow_url_override() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("flask==3.0.0\nwerkzeug @ https://files.pythonhosted.org/packages/ff/1d/960bb4017c68674a1cb099534840f18d3def3ce44aed12b5ed8b78e0153e/Werkzeug-2.0.0-py3-none-any.whl")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--allow-url-override"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --allow-url-override
+    blinker==1.7.0
+        # via flask
+    click==8.1.7
+        # via flask
+    flask==3.0.0
+    itsdangerous==2.1.2
+        # via flask
+    jinja2==3.1.3
+        # via flask
+    markupsafe==2.1.5
+        # via jinja2
+    werkzeug @ https://files.pythonhosted.org/packages/ff/1d/960bb4017c68674a1cb099534840f18d3def3ce44aed12b5ed8b78e0153e/Werkzeug-2.0.0-py3-none-any.whl
+        # via flask
+
+    ----- stderr -----
+    Resolved 7 packages in [TIME]
     "###
     );
 
@@ -2596,6 +2821,59 @@ fn compile_yanked_version_direct() -> Result<()> {
 
     ----- stderr -----
     Resolved 1 package in [TIME]
+    warning: attrs==21.1.0 is yanked (reason: "Installable but not importable on Python 3.4").
+    "###
+    );
+
+    Ok(())
+}
+
+/// Fail to resolve a yanked version of `attrs`, even when specifying the version directly, with
+/// `--yanked disallow`.
+#[test]
+fn compile_yanked_version_direct_disallow() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("attrs==21.1.0")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--yanked")
+            .arg("disallow"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+      × No solution found when resolving dependencies:
+      ╰─▶ Because attrs==21.1.0 is unusable because it was yanked (reason: Installable but not importable on Python 3.4) and you require attrs==21.1.0, we can conclude that the requirements are unsatisfiable.
+    "###
+    );
+
+    Ok(())
+}
+
+/// Resolve a yanked version of `attrs` through an indirect range with `--yanked allow`.
+#[test]
+fn compile_yanked_version_indirect_allow() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("attrs>20.3.0,<21.2.0")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--yanked")
+            .arg("allow"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --yanked allow
+    attrs==21.1.0
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    warning: attrs==21.1.0 is yanked (reason: "Installable but not importable on Python 3.4").
     "###
     );
 
@@ -3233,7 +3511,7 @@ fn cache_errors_are_non_fatal() -> Result<()> {
     let interpreter_cache = context
         .cache_dir
         .path()
-        .join("interpreter-v0")
+        .join("interpreter-v3")
         .read_dir()?
         .next()
         .context("Expected a python interpreter cache file")??
@@ -3610,117 +3888,350 @@ fn find_links_directory() -> Result<()> {
     Ok(())
 }
 
-/// Compile using `--find-links` with a URL by resolving `tqdm` from the `PyTorch` wheels index.
+/// Compile using `--find-links` with a local directory, in which the distributions are nested
+/// within subdirectories.
 #[test]
-fn find_links_url() -> Result<()> {
+fn find_links_directory_nested() -> Result<()> {
     let context = TestContext::new("3.12");
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("tqdm")?;
 
-    uv_snapshot!(context.compile()
+    let links = context.temp_dir.child("links");
+    links.child("nested").child("deeper").create_dir_all()?;
+    fs_err::copy(
+        context
+            .workspace_root
+            .join("scripts")
+            .join("links")
+            .join("tqdm-1000.0.0-py3-none-any.whl"),
+        links
+            .child("nested")
+            .child("deeper")
+            .child("tqdm-1000.0.0-py3-none-any.whl"),
+    )?;
+
+    uv_snapshot!(context.filters(), context.compile()
             .arg("requirements.in")
             .arg("--no-index")
             .arg("--find-links")
-            .arg("https://download.pytorch.org/whl/torch_stable.html"), @r###"
+            .arg(links.path()), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
     # This file was autogenerated by uv via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --no-index
-    tqdm==4.64.1
+    tqdm==1000.0.0
 
     ----- stderr -----
     Resolved 1 package in [TIME]
-    "###
-    );
+    "###);
 
     Ok(())
 }
 
-/// Compile using `--find-links` with a URL passed via an environment variable.
+/// Compile using `--find-links` with wheels disabled for a package. The resolver should backtrack
+/// past the wheel-only `tqdm==1000.0.0` to the source distribution.
 #[test]
-fn find_links_env_var() -> Result<()> {
+fn compile_find_links_no_binary() -> Result<()> {
     let context = TestContext::new("3.12");
     let requirements_in = context.temp_dir.child("requirements.in");
-    requirements_in.write_str("tqdm\n--find-links ${URL}")?;
+    requirements_in.write_str("tqdm")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(context.filters(), context.compile()
             .arg("requirements.in")
             .arg("--no-index")
-            .env("URL", "https://download.pytorch.org/whl/torch_stable.html"), @r###"
+            .arg("--no-binary")
+            .arg("tqdm")
+            .arg("--find-links")
+            .arg(context.workspace_root.join("scripts").join("links")), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --no-index --no-binary tqdm
+    tqdm==999.0.0
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###);
+
+    // The same policy can be declared in the requirements file.
+    requirements_in.write_str(indoc! {r"
+        --no-binary tqdm
+        tqdm
+    "})?;
+
+    uv_snapshot!(context.filters(), context.compile()
+            .arg("requirements.in")
+            .arg("--no-index")
+            .arg("--find-links")
+            .arg(context.workspace_root.join("scripts").join("links")), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
     # This file was autogenerated by uv via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --no-index
-    tqdm==4.64.1
+    tqdm==999.0.0
 
     ----- stderr -----
     Resolved 1 package in [TIME]
-    "###
-    );
+    "###);
 
     Ok(())
 }
 
-/// Compile using `--find-links` with a URL by resolving `tqdm` from the `PyTorch` wheels index,
-/// with the URL itself provided in a `requirements.txt` file.
+/// Compile using `--find-links` with wheels required for a package. The resolver should backtrack
+/// past the source-only `tqdm==999.0.0`.
 #[test]
-fn find_links_requirements_txt() -> Result<()> {
+fn compile_find_links_only_binary() -> Result<()> {
     let context = TestContext::new("3.12");
     let requirements_in = context.temp_dir.child("requirements.in");
-    requirements_in.write_str("-f https://download.pytorch.org/whl/torch_stable.html\ntqdm")?;
+    requirements_in.write_str("tqdm<1000")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(context.filters(), context.compile()
             .arg("requirements.in")
             .arg("--no-index")
-            .arg("--emit-find-links"), @r###"
+            .arg("--only-binary")
+            .arg("tqdm")
+            .arg("--find-links")
+            .arg(context.workspace_root.join("scripts").join("links")), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
     # This file was autogenerated by uv via the following command:
-    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --no-index --emit-find-links
-    --find-links https://download.pytorch.org/whl/torch_stable.html
-
-    tqdm==4.64.1
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --no-index --only-binary tqdm
+    tqdm==4.66.1
 
     ----- stderr -----
     Resolved 1 package in [TIME]
-    "###
-    );
+    "###);
 
     Ok(())
 }
 
-/// Use an existing resolution for `black==23.10.1`, with stale versions of `click` and `pathspec`.
-/// Nothing should change.
+/// Compile using `--find-links` with `--require-hashes`. The flat index doesn't record hashes, so
+/// `tqdm` should be resolved from the registry instead, with its hashes included in the output.
 #[test]
-fn upgrade_none() -> Result<()> {
+fn find_links_require_hashes() -> Result<()> {
     let context = TestContext::new("3.12");
     let requirements_in = context.temp_dir.child("requirements.in");
-    requirements_in.write_str("black==23.10.1")?;
+    requirements_in.write_str("tqdm")?;
 
-    let requirements_txt = context.temp_dir.child("requirements.txt");
-    requirements_txt.write_str(indoc! {r"
-        black==23.10.1
-        click==8.1.2
-            # via black
-        mypy-extensions==1.0.0
-            # via black
-        packaging==23.2
-            # via black
-        pathspec==0.11.0
-            # via black
-        platformdirs==4.0.0
-            # via black
-    "})?;
+    let filters: Vec<_> = [(r"--hash=sha256:[0-9a-f]{64}", "--hash=sha256:[HASH]")]
+        .into_iter()
+        .chain(context.filters())
+        .collect();
 
-    uv_snapshot!(context.compile()
-        .arg("requirements.in")
-        .arg("--output-file")
-        .arg("requirements.txt"), @r###"
-    success: true
-    exit_code: 0
+    uv_snapshot!(filters, context.compile()
+            .arg("requirements.in")
+            .arg("--require-hashes")
+            .arg("--find-links")
+            .arg(context.workspace_root.join("scripts").join("links")), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --require-hashes
+    tqdm==4.66.2 \
+        --hash=sha256:[HASH] \
+        --hash=sha256:[HASH]
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###);
+
+    Ok(())
+}
+
+/// Compile with `--require-hashes`, using the hash of the `colorama` wheel from a constraints
+/// file. Only the wheel should be selected, and only its hash should be included in the output.
+#[test]
+fn require_hashes_constraint() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("colorama")?;
+    let constraints_txt = context.temp_dir.child("constraints.txt");
+    constraints_txt.write_str("colorama==0.4.6 --hash=sha256:4f1d9991f5acc0ca119f9d443620b77f9d6b33703e51011c16baf57afb285fc6")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--require-hashes")
+            .arg("--constraint")
+            .arg("constraints.txt"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --require-hashes --constraint constraints.txt
+    colorama==0.4.6 \
+        --hash=sha256:4f1d9991f5acc0ca119f9d443620b77f9d6b33703e51011c16baf57afb285fc6
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###);
+
+    Ok(())
+}
+
+/// Compile with `--require-hashes`, using a hash from a constraints file that matches none of the
+/// distributions on the index.
+#[test]
+fn require_hashes_constraint_mismatch() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("colorama==0.4.6")?;
+    let constraints_txt = context.temp_dir.child("constraints.txt");
+    constraints_txt.write_str("colorama==0.4.6 --hash=sha256:0000000000000000000000000000000000000000000000000000000000000000")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--require-hashes")
+            .arg("--constraint")
+            .arg("constraints.txt"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+      × No solution found when resolving dependencies:
+      ╰─▶ Because colorama==0.4.6 is unusable because no source distribution is available with a matching hash and you require colorama==0.4.6, we can conclude that the requirements are unsatisfiable.
+    "###);
+
+    Ok(())
+}
+
+/// Compile with `--require-hashes` and a direct URL requirement, which can't be hashed.
+#[test]
+fn require_hashes_url() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio @ https://files.pythonhosted.org/packages/14/fd/2f20c40b45e4fb4324834aea24bd4afdf1143390242c0b33774da0e2e34f/anyio-4.3.0-py3-none-any.whl")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--require-hashes"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: In `--require-hashes` mode, URL requirements can't be hashed, but found: `anyio @ https://files.pythonhosted.org/packages/14/fd/2f20c40b45e4fb4324834aea24bd4afdf1143390242c0b33774da0e2e34f/anyio-4.3.0-py3-none-any.whl`
+    "###);
+
+    Ok(())
+}
+
+/// Compile using `--find-links` with a URL by resolving `tqdm` from the `PyTorch` wheels index.
+#[test]
+fn find_links_url() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("tqdm")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--no-index")
+            .arg("--find-links")
+            .arg("https://download.pytorch.org/whl/torch_stable.html"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --no-index
+    tqdm==4.64.1
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Compile using `--find-links` with a URL passed via an environment variable.
+#[test]
+fn find_links_env_var() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("tqdm\n--find-links ${URL}")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--no-index")
+            .env("URL", "https://download.pytorch.org/whl/torch_stable.html"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --no-index
+    tqdm==4.64.1
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Compile using `--find-links` with a URL by resolving `tqdm` from the `PyTorch` wheels index,
+/// with the URL itself provided in a `requirements.txt` file.
+#[test]
+fn find_links_requirements_txt() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("-f https://download.pytorch.org/whl/torch_stable.html\ntqdm")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--no-index")
+            .arg("--emit-find-links"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --no-index --emit-find-links
+    --find-links https://download.pytorch.org/whl/torch_stable.html
+
+    tqdm==4.64.1
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Use an existing resolution for `black==23.10.1`, with stale versions of `click` and `pathspec`.
+/// Nothing should change.
+#[test]
+fn upgrade_none() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("black==23.10.1")?;
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(indoc! {r"
+        black==23.10.1
+        click==8.1.2
+            # via black
+        mypy-extensions==1.0.0
+            # via black
+        packaging==23.2
+            # via black
+        pathspec==0.11.0
+            # via black
+        platformdirs==4.0.0
+            # via black
+    "})?;
+
+    uv_snapshot!(context.compile()
+        .arg("requirements.in")
+        .arg("--output-file")
+        .arg("requirements.txt"), @r###"
+    success: true
+    exit_code: 0
     ----- stdout -----
     # This file was autogenerated by uv via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --output-file requirements.txt
@@ -3855,6 +4366,62 @@ fn upgrade_package() -> Result<()> {
     Ok(())
 }
 
+/// Use an existing resolution for `black==23.10.1`, with stale versions of `click` and `pathspec`.
+/// Only `click` should be upgraded, and only up to the bound provided via `--upgrade-package`.
+#[test]
+fn upgrade_package_with_specifier() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("black==23.10.1")?;
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(indoc! {r"
+        # This file was autogenerated by uv via the following command:
+        #    uv pip compile requirements.in --python-version 3.12 --cache-dir [CACHE_DIR]
+        black==23.10.1
+        click==8.1.2
+            # via black
+        mypy-extensions==1.0.0
+            # via black
+        packaging==23.2
+            # via black
+        pathspec==0.11.0
+            # via black
+        platformdirs==4.0.0
+            # via black
+    "})?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--output-file")
+            .arg("requirements.txt")
+            .arg("--upgrade-package")
+            .arg("click<8.1.7"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --output-file requirements.txt --upgrade-package click<8.1.7
+    black==23.10.1
+    click==8.1.6
+        # via black
+    mypy-extensions==1.0.0
+        # via black
+    packaging==23.2
+        # via black
+    pathspec==0.11.0
+        # via black
+    platformdirs==4.0.0
+        # via black
+
+    ----- stderr -----
+    Resolved 6 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
 /// Attempt to resolve a requirement at a path that doesn't exist.
 #[test]
 fn missing_path_requirement() -> Result<()> {
@@ -5153,19 +5720,21 @@ fn compile_constraints_incompatible_version() -> Result<()> {
     ----- stderr -----
       × No solution found when resolving dependencies:
       ╰─▶ Because you require filelock==1.0.0 and you require filelock==3.8.0, we can conclude that the requirements are unsatisfiable.
+
+          hint: filelock was constrained by `filelock==3.8.0`, declared in `constraints.txt:1`
     "###
     );
 
     Ok(())
 }
 
-/// Resolve a package from a `requirements.in` file, with a `constraints.txt` file pinning one of
-/// its direct dependencies to an incompatible version.
+/// Resolve a set of requirements in which only some participate in a conflict, and narrow them down
+/// to the minimal unsatisfiable subset with `--explain`.
 #[test]
-fn conflicting_url_markers() -> Result<()> {
+fn compile_constraints_incompatible_version_explain() -> Result<()> {
     let context = TestContext::new("3.12");
     let requirements_in = context.temp_dir.child("requirements.in");
-    requirements_in.write_str("filelock==1.0.0")?;
+    requirements_in.write_str("filelock==1.0.0\niniconfig")?;
 
     let constraints_txt = context.temp_dir.child("constraints.txt");
     constraints_txt.write_str("filelock==3.8.0")?;
@@ -5173,7 +5742,8 @@ fn conflicting_url_markers() -> Result<()> {
     uv_snapshot!(context.compile()
             .arg("requirements.in")
             .arg("--constraint")
-            .arg("constraints.txt"), @r###"
+            .arg("constraints.txt")
+            .arg("--explain"), @r###"
     success: false
     exit_code: 1
     ----- stdout -----
@@ -5181,26 +5751,98 @@ fn conflicting_url_markers() -> Result<()> {
     ----- stderr -----
       × No solution found when resolving dependencies:
       ╰─▶ Because you require filelock==1.0.0 and you require filelock==3.8.0, we can conclude that the requirements are unsatisfiable.
+
+          hint: filelock was constrained by `filelock==3.8.0`, declared in `constraints.txt:1`
+
+    the requirements are unsatisfiable, because:
+    - you require filelock==1.0.0
+    - you require filelock==3.8.0
+
+    the following inputs are unsatisfiable on their own:
+    - filelock==1.0.0
+    - filelock==3.8.0 (constraint, declared in `constraints.txt:1`)
     "###
     );
 
     Ok(())
 }
 
-/// Override a regular package with an editable.
-///
-/// At present, this incorrectly resolves to the regular package.
+/// Resolve a package from a `requirements.in` file, with a `constraints.txt` file that includes
+/// another constraints file pinning one of its direct dependencies to an incompatible version. The
+/// error should cite the file and line that declared the constraint.
 #[test]
-fn editable_override() -> Result<()> {
+fn compile_constraints_incompatible_version_nested() -> Result<()> {
     let context = TestContext::new("3.12");
-
-    // Add a non-editable requirement.
     let requirements_in = context.temp_dir.child("requirements.in");
-    requirements_in.write_str("black")?;
+    requirements_in.write_str("-c constraints.txt\nfilelock==1.0.0")?;
 
-    // Add an editable override.
-    let overrides_txt = context.temp_dir.child("overrides.txt");
-    overrides_txt.write_str("-e file://../../scripts/packages/black_editable")?;
+    let constraints_txt = context.temp_dir.child("constraints.txt");
+    constraints_txt.write_str("-c pins.txt")?;
+
+    let pins_txt = context.temp_dir.child("pins.txt");
+    pins_txt.write_str("# Pinned for compatibility.\nfilelock==3.8.0")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+      × No solution found when resolving dependencies:
+      ╰─▶ Because you require filelock==1.0.0 and you require filelock==3.8.0, we can conclude that the requirements are unsatisfiable.
+
+          hint: filelock was constrained by `filelock==3.8.0`, declared in `pins.txt:2`
+    "###
+    );
+
+    Ok(())
+}
+
+/// Resolve a package from a `requirements.in` file, with a `constraints.txt` file pinning one of
+/// its direct dependencies to an incompatible version.
+#[test]
+fn conflicting_url_markers() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("filelock==1.0.0")?;
+
+    let constraints_txt = context.temp_dir.child("constraints.txt");
+    constraints_txt.write_str("filelock==3.8.0")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--constraint")
+            .arg("constraints.txt"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+      × No solution found when resolving dependencies:
+      ╰─▶ Because you require filelock==1.0.0 and you require filelock==3.8.0, we can conclude that the requirements are unsatisfiable.
+
+          hint: filelock was constrained by `filelock==3.8.0`, declared in `constraints.txt:1`
+    "###
+    );
+
+    Ok(())
+}
+
+/// Override a regular package with an editable.
+///
+/// At present, this incorrectly resolves to the regular package.
+#[test]
+fn editable_override() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Add a non-editable requirement.
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("black")?;
+
+    // Add an editable override.
+    let overrides_txt = context.temp_dir.child("overrides.txt");
+    overrides_txt.write_str("-e file://../../scripts/packages/black_editable")?;
 
     uv_snapshot!(context.compile()
             .arg("requirements.in")
@@ -5329,6 +5971,8 @@ fn override_with_incompatible_constraint() -> Result<()> {
     ----- stderr -----
       × No solution found when resolving dependencies:
       ╰─▶ Because you require anyio>=3.0.0 and you require anyio<3.0.0, we can conclude that the requirements are unsatisfiable.
+
+          hint: anyio was constrained by `anyio<3.0.0`, declared in `constraints.txt:1`
     "###
     );
 
@@ -7334,3 +7978,730 @@ fn compile_index_url_fallback_prefer_primary() -> Result<()> {
 
     Ok(())
 }
+
+/// Install a package via `--extra-index-url`.
+///
+/// With `--index-strategy unsafe-best-match`, the resolver should select the best version across
+/// all indexes, even if an older compatible version exists on the "extra" index.
+///
+/// In this case, Jinja 3.1.2 is hosted on the "extra" index, but Jinja 3.1.3 is available on the
+/// "primary" index.
+#[test]
+fn compile_index_url_best_match() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("jinja2<3.1.4")?;
+
+    uv_snapshot!(context.compile_without_exclude_newer()
+        .arg("--index-strategy")
+        .arg("unsafe-best-match")
+        .arg("--index-url")
+        .arg("https://pypi.org/simple")
+        .arg("--extra-index-url")
+        .arg("https://download.pytorch.org/whl/cpu")
+        .arg("requirements.in")
+        .arg("--no-deps"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --index-strategy unsafe-best-match requirements.in --no-deps
+    jinja2==3.1.3
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Reuse the output of a previous resolution with identical inputs, without querying the index.
+#[test]
+fn compile_cached_resolution() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==3.7.0")?;
+
+    uv_snapshot!(context
+        .compile()
+        .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in
+    anyio==3.7.0
+    idna==3.6
+        # via anyio
+    sniffio==1.3.1
+        # via anyio
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###);
+
+    // Remove the index responses from the cache; the resolution should be reused regardless.
+    fs_err::remove_dir_all(context.cache_dir.child("simple-v7"))?;
+
+    uv_snapshot!(context
+        .compile()
+        .arg("requirements.in")
+        .arg("--offline"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --offline
+    anyio==3.7.0
+    idna==3.6
+        # via anyio
+    sniffio==1.3.1
+        # via anyio
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###);
+
+    // Any refresh bypasses the cached resolution, so the (missing) index responses are required.
+    uv_snapshot!(context
+        .compile()
+        .arg("requirements.in")
+        .arg("--offline")
+        .arg("--refresh"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+      × No solution found when resolving dependencies:
+      ╰─▶ Because anyio==3.7.0 was not found in the cache and you require anyio==3.7.0, we can conclude that the requirements are unsatisfiable.
+
+          hint: Packages were unavailable because the network was disabled
+    "###);
+
+    Ok(())
+}
+
+/// Invalidate a cached resolution when the contents of a local `--find-links` directory change,
+/// and never cache resolutions that aren't bounded by `--exclude-newer`.
+#[test]
+fn compile_cached_resolution_find_links() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("validation")?;
+
+    let links = context.temp_dir.child("links");
+    links.create_dir_all()?;
+    fs_err::copy(
+        context
+            .workspace_root
+            .join("scripts")
+            .join("links")
+            .join("validation-1.0.0-py3-none-any.whl"),
+        links.child("validation-1.0.0-py3-none-any.whl"),
+    )?;
+
+    uv_snapshot!(context.filters(), context.compile()
+            .arg("requirements.in")
+            .arg("--no-index")
+            .arg("--find-links")
+            .arg(links.path()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --no-index
+    validation==1.0.0
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###);
+
+    // Add a newer version to the directory; the cached resolution should be ignored.
+    fs_err::copy(
+        context
+            .workspace_root
+            .join("scripts")
+            .join("links")
+            .join("validation-2.0.0-py3-none-any.whl"),
+        links.child("validation-2.0.0-py3-none-any.whl"),
+    )?;
+
+    uv_snapshot!(context.filters(), context.compile()
+            .arg("requirements.in")
+            .arg("--no-index")
+            .arg("--find-links")
+            .arg(links.path()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --no-index
+    validation==2.0.0
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###);
+
+    // Without `--exclude-newer`, the resolution isn't cached.
+    fs_err::remove_dir_all(context.cache_dir.child("resolutions-v1"))?;
+    uv_snapshot!(context.filters(), context.compile_without_exclude_newer()
+            .arg("requirements.in")
+            .arg("--no-index")
+            .arg("--find-links")
+            .arg(links.path()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] requirements.in --no-index
+    validation==2.0.0
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###);
+    assert!(!context.cache_dir.child("resolutions-v1").exists());
+
+    Ok(())
+}
+
+/// With `--cache-read-only`, resolve from the existing cache without writing to it.
+#[test]
+fn compile_cache_read_only() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==3.7.0")?;
+
+    // Populate the cache.
+    let output = context.compile().arg("requirements.in").output()?;
+    assert!(output.status.success());
+
+    // Remove the cached resolution, to force a resolution from the cached index responses.
+    fs_err::remove_dir_all(context.cache_dir.child("resolutions-v1"))?;
+
+    uv_snapshot!(context
+        .compile()
+        .arg("requirements.in")
+        .arg("--cache-read-only")
+        .arg("--offline"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --cache-read-only --offline
+    anyio==3.7.0
+    idna==3.6
+        # via anyio
+    sniffio==1.3.1
+        # via anyio
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###);
+
+    // The resolution should have been written to the temporary cache, not the cache directory.
+    context
+        .cache_dir
+        .child("resolutions-v1")
+        .assert(predicates::path::missing());
+
+    Ok(())
+}
+
+/// With `--cache-read-only`, a cache directory that doesn't exist should not be created.
+#[test]
+fn compile_cache_read_only_missing() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==3.7.0")?;
+
+    let cache_dir = context.temp_dir.child("missing-cache");
+    let output = Command::new(get_bin())
+        .arg("pip")
+        .arg("compile")
+        .arg("requirements.in")
+        .arg("--cache-read-only")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(context.temp_dir.path())
+        .output()?;
+    assert!(output.status.success());
+
+    cache_dir.assert(predicates::path::missing());
+
+    Ok(())
+}
+
+/// Perform a universal resolution, annotating requirements that only apply to some platforms or
+/// Python versions with the relevant environment markers.
+#[test]
+fn compile_universal() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==4.3.0\nclick==8.1.7")?;
+
+    let filters: Vec<_> = [
+        // 3.10 may not be installed
+        (
+            "warning: The requested Python version 3.10 is not available; .* will be used to build dependencies instead.\n",
+            "",
+        ),
+    ]
+        .into_iter()
+        .chain(context.filters())
+        .collect();
+
+    uv_snapshot!(filters, context.compile()
+            .arg("requirements.in")
+            .arg("--universal")
+            .arg("--python-version")
+            .arg("3.10"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --universal --python-version 3.10
+    anyio==4.3.0
+    click==8.1.7
+    colorama==0.4.6 ; sys_platform == 'win32'
+        # via click
+    exceptiongroup==1.2.0 ; python_version < '3.11'
+        # via anyio
+    idna==3.6
+        # via anyio
+    sniffio==1.3.1
+        # via anyio
+    typing-extensions==4.10.0 ; python_version < '3.11'
+        # via anyio
+
+    ----- stderr -----
+    Resolved 7 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Perform a universal resolution with requirements that conflict across disjoint markers. Each
+/// target environment is resolved separately, so the requirements shouldn't conflict, and the
+/// output should include both versions, annotated with the relevant markers.
+#[test]
+fn compile_universal_disjoint_markers() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str(indoc! {r"
+        numpy>=1.26 ; python_version >= '3.12'
+        numpy<1.26 ; python_version < '3.12'
+    "})?;
+
+    let filters: Vec<_> = [
+        // 3.10 may not be installed
+        (
+            "warning: The requested Python version 3.10 is not available; .* will be used to build dependencies instead.\n",
+            "",
+        ),
+    ]
+        .into_iter()
+        .chain(context.filters())
+        .collect();
+
+    uv_snapshot!(filters, context.compile()
+            .arg("requirements.in")
+            .arg("--universal")
+            .arg("--python-version")
+            .arg("3.10"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --universal --python-version 3.10
+    numpy==1.25.2 ; python_version < '3.12'
+    numpy==1.26.4 ; python_version >= '3.12'
+
+    ----- stderr -----
+    Resolved 2 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Perform a universal resolution with a requirement that only applies to some architectures,
+/// which differ in their `platform_machine` values across operating systems.
+#[test]
+fn compile_universal_platform_machine() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("iniconfig==2.0.0 ; platform_machine == 'x86_64'")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--universal")
+            .arg("--python-version")
+            .arg("3.12")
+            .arg("--max-python-version")
+            .arg("3.12"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --universal --python-version 3.12 --max-python-version 3.12
+    iniconfig==2.0.0 ; (sys_platform == 'linux' and platform_machine == 'x86_64') or (sys_platform == 'darwin' and platform_machine == 'x86_64')
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Fail resolution once the resolver has tried more than `--max-tried-versions` versions.
+#[test]
+fn compile_max_tried_versions() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==4.3.0")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--max-tried-versions")
+            .arg("1"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Resolution exceeded the limit of 1 tried version; the most backtracked packages were:
+    - anyio (1 version)
+    - idna (1 version)
+    "###
+    );
+
+    Ok(())
+}
+
+/// Give up on a package after trying `--max-candidates` versions of it.
+#[test]
+fn compile_max_candidates() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("validation")?;
+
+    // `2.0.0` and `3.0.0` have invalid metadata, so resolving `1.0.0` requires trying three
+    // versions.
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--no-index")
+            .arg("--find-links")
+            .arg(context.workspace_root.join("scripts").join("links"))
+            .arg("--max-candidates")
+            .arg("2"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Tried 2 versions of validation without finding a compatible one; the conflict is likely elsewhere in the dependency graph (increase `--max-candidates` to keep searching)
+    "###
+    );
+
+    Ok(())
+}
+
+/// Exclude a transitive dependency from the resolution with `--exclude`.
+#[test]
+fn compile_exclude() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==3.7.0")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--exclude")
+            .arg("idna"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --exclude idna
+    anyio==3.7.0
+    sniffio==1.3.1
+        # via anyio
+
+    ----- stderr -----
+    Resolved 2 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Request a version of a package that was published after the `--exclude-newer` cutoff. The error
+/// should indicate that `--exclude-newer` excluded the matching versions.
+#[test]
+fn compile_exclude_newer_hint() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("flask>3.0.2")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+      × No solution found when resolving dependencies:
+      ╰─▶ Because only flask<=3.0.2 is available and you require flask>3.0.2, we can conclude that the requirements are unsatisfiable.
+
+          hint: flask has versions in the requested range (e.g., 3.0.3), but they were excluded because they were published after the `--exclude-newer` cutoff
+    "###
+    );
+
+    Ok(())
+}
+
+/// Reuse the decisions from a previous resolution of the same requirements file with
+/// `--incremental`, such that relaxing a requirement retains the existing pin.
+#[test]
+fn compile_incremental() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==3.7.0\niniconfig<2")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--incremental"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --incremental
+    anyio==3.7.0
+    idna==3.6
+        # via anyio
+    iniconfig==1.1.1
+    sniffio==1.3.1
+        # via anyio
+
+    ----- stderr -----
+    Resolved 4 packages in [TIME]
+    "###
+    );
+
+    // Relax the requirement on `iniconfig`. The previous decision should be retained.
+    requirements_in.write_str("anyio==3.7.0\niniconfig")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--incremental"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --incremental
+    anyio==3.7.0
+    idna==3.6
+        # via anyio
+    iniconfig==1.1.1
+    sniffio==1.3.1
+        # via anyio
+
+    ----- stderr -----
+    Resolved 4 packages in [TIME]
+    "###
+    );
+
+    // Without `--incremental`, the latest version is selected.
+    uv_snapshot!(context.compile()
+            .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in
+    anyio==3.7.0
+    idna==3.6
+        # via anyio
+    iniconfig==2.0.0
+    sniffio==1.3.1
+        # via anyio
+
+    ----- stderr -----
+    Resolved 4 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// `--incremental` should combine the previous decisions with any pins in the output file, with
+/// the output file taking precedence.
+#[test]
+fn compile_incremental_output_file() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio<4\niniconfig<2")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--incremental"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --incremental
+    anyio==3.7.1
+    idna==3.6
+        # via anyio
+    iniconfig==1.1.1
+    sniffio==1.3.1
+        # via anyio
+
+    ----- stderr -----
+    Resolved 4 packages in [TIME]
+    "###
+    );
+
+    // The output file pins `anyio`, but not `iniconfig`.
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("anyio==3.7.0\nidna==3.6\nsniffio==1.3.1\n")?;
+
+    // Relax both requirements. The pin from the output file should be retained for `anyio`, and
+    // the previous decision for `iniconfig`.
+    requirements_in.write_str("anyio\niniconfig")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--output-file")
+            .arg("requirements.txt")
+            .arg("--incremental"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --output-file requirements.txt --incremental
+    anyio==3.7.0
+    idna==3.6
+        # via anyio
+    iniconfig==1.1.1
+    sniffio==1.3.1
+        # via anyio
+
+    ----- stderr -----
+    Resolved 4 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// `--incremental` should reuse the decisions for the same requirements file, regardless of the
+/// working directory from which it's referenced.
+#[test]
+fn compile_incremental_current_dir() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("iniconfig<2")?;
+    let subdir = context.temp_dir.child("subdir");
+    subdir.create_dir_all()?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--incremental"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --incremental
+    iniconfig==1.1.1
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    // Relax the requirement, and reference the file from a different directory. The previous
+    // decision should be retained.
+    requirements_in.write_str("iniconfig")?;
+
+    uv_snapshot!(context.compile()
+            .arg("../requirements.in")
+            .arg("--incremental")
+            .current_dir(&subdir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z ../requirements.in --incremental
+    iniconfig==1.1.1
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Write a summary of cache activity with `--cache-metrics`, including when the command fails.
+#[test]
+fn cache_metrics() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("iniconfig==2.0.0")?;
+    let metrics_json = context.temp_dir.child("metrics.json");
+
+    let read_metrics = || -> Result<serde_json::Value> {
+        Ok(serde_json::from_str(&fs_err::read_to_string(
+            metrics_json.path(),
+        )?)?)
+    };
+
+    // With an empty cache, every lookup is a miss.
+    context
+        .compile()
+        .arg("requirements.in")
+        .arg("--cache-metrics")
+        .arg(metrics_json.path())
+        .assert()
+        .success();
+    let metrics = read_metrics()?;
+    assert_eq!(metrics["hits"], 0);
+    assert!(metrics["misses"].as_u64().unwrap() > 0);
+
+    // With a warm cache, lookups are served from the cache. Omit `--exclude-newer`, such that the
+    // resolution itself isn't reused from the cache.
+    context
+        .compile_without_exclude_newer()
+        .arg("requirements.in")
+        .arg("--cache-metrics")
+        .arg(metrics_json.path())
+        .assert()
+        .success();
+    let metrics = read_metrics()?;
+    assert!(metrics["hits"].as_u64().unwrap() > 0);
+    assert!(metrics["bytes_served"].as_u64().unwrap() > 0);
+
+    // The metrics are written even if the command fails.
+    fs_err::remove_file(metrics_json.path())?;
+    context
+        .compile()
+        .arg("requirements.in")
+        .arg("--constraint")
+        .arg("missing.txt")
+        .arg("--cache-metrics")
+        .arg(metrics_json.path())
+        .assert()
+        .failure();
+    let metrics = read_metrics()?;
+    assert_eq!(metrics["revalidations"], 0);
+
+    Ok(())
+}
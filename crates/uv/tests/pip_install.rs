@@ -269,6 +269,8 @@ fn no_solution() {
       × No solution found when resolving dependencies:
       ╰─▶ Because only flask<=3.0.2 is available and flask==3.0.2 depends on werkzeug>=3.0.0, we can conclude that flask>=3.0.2 depends on werkzeug>=3.0.0.
           And because you require flask>=3.0.2 and you require werkzeug<1.0.0, we can conclude that the requirements are unsatisfiable.
+
+          hint: flask has versions in the requested range (e.g., 3.0.3), but they were excluded because they were published after the `--exclude-newer` cutoff
     "###);
 }
 
@@ -888,6 +890,39 @@ fn install_editable_no_binary() {
     );
 }
 
+/// Install a legacy `setup.py` project as editable, without the PEP 660 hooks.
+#[test]
+fn install_editable_legacy_setup_py() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(context.filters(), context.install()
+        .arg("-e")
+        .arg(context.workspace_root.join("scripts/packages/setup_py_editable"))
+        .arg("--legacy-setup-py"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Built 1 editable in [TIME]
+    Resolved 8 packages in [TIME]
+    Downloaded 7 packages in [TIME]
+    Installed 8 packages in [TIME]
+     + anyio==4.3.0
+     + certifi==2024.2.2
+     + h11==0.14.0
+     + httpcore==1.0.4
+     + httpx==0.27.0
+     + idna==3.6
+     + setup-py-editable==0.0.1 (from file://[WORKSPACE]/scripts/packages/setup_py_editable)
+     + sniffio==1.3.1
+    "###
+    );
+
+    // The project is importable from its source tree.
+    context.assert_command("import setup_py_editable").success();
+}
+
 /// Install a source distribution that uses the `flit` build system, along with `flit`
 /// at the top-level, along with `--reinstall` to force a re-download after resolution, to ensure
 /// that the `flit` install and the source distribution build don't conflict.
@@ -929,6 +964,250 @@ fn reinstall_build_system() -> Result<()> {
     Ok(())
 }
 
+/// Install a package under a prefix, using the interpreter's installation scheme.
+#[test]
+#[cfg(unix)]
+fn install_prefix() {
+    let context = TestContext::new("3.12");
+    let prefix = context.temp_dir.child("prefix");
+
+    uv_snapshot!(context.install()
+        .arg("tqdm==4.66.1")
+        .arg("--prefix")
+        .arg("prefix")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + tqdm==4.66.1
+    "###
+    );
+
+    // The package and its scripts are installed under the prefix, rather than into the
+    // environment.
+    prefix
+        .child("lib")
+        .child("python3.12")
+        .child("site-packages")
+        .child("tqdm")
+        .child("__init__.py")
+        .assert(predicates::path::is_file());
+    prefix
+        .child("bin")
+        .child("tqdm")
+        .assert(predicates::path::is_file());
+    context.assert_command("import tqdm").failure();
+
+    // Installing again is a no-op, as the prefix already satisfies the requirements.
+    uv_snapshot!(context.install()
+        .arg("tqdm==4.66.1")
+        .arg("--prefix")
+        .arg("prefix")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Audited 1 package in [TIME]
+    "###
+    );
+}
+
+/// Install a package into a target directory, selecting wheels for another platform.
+#[test]
+#[cfg(unix)]
+fn install_target_platform() {
+    let context = TestContext::new("3.12");
+    let target = context.temp_dir.child("target");
+
+    // Overriding the platform requires that source distributions are not built.
+    uv_snapshot!(context.install()
+        .arg("markupsafe==2.1.5")
+        .arg("--target")
+        .arg("target")
+        .arg("--platform")
+        .arg("manylinux2014_aarch64"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Overriding the target Python version, platform, implementation, or ABI requires `--only-binary :all:`
+    "###
+    );
+
+    uv_snapshot!(context.install()
+        .arg("markupsafe==2.1.5")
+        .arg("--target")
+        .arg("target")
+        .arg("--platform")
+        .arg("manylinux2014_aarch64")
+        .arg("--python-version")
+        .arg("3.12")
+        .arg("--only-binary")
+        .arg(":all:")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + markupsafe==2.1.5
+    "###
+    );
+
+    // The package is installed at the root of the target, rather than into the environment.
+    target
+        .child("markupsafe")
+        .child("__init__.py")
+        .assert(predicates::path::is_file());
+    target
+        .child("markupsafe")
+        .child("_speedups.cpython-312-aarch64-linux-gnu.so")
+        .assert(predicates::path::is_file());
+    context.assert_command("import markupsafe").failure();
+}
+
+/// Install into a target directory for another platform, evaluating markers against that platform
+/// and accepting wheels built for older compatible releases.
+#[test]
+#[cfg(unix)]
+fn install_target_platform_markers() {
+    let context = TestContext::new("3.12");
+
+    // `click` depends on `colorama` on Windows only.
+    uv_snapshot!(context.install()
+        .arg("click==8.1.7")
+        .arg("--target")
+        .arg("windows")
+        .arg("--platform")
+        .arg("win_amd64")
+        .arg("--python-version")
+        .arg("3.12")
+        .arg("--only-binary")
+        .arg(":all:")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 2 packages in [TIME]
+    Downloaded 2 packages in [TIME]
+    Installed 2 packages in [TIME]
+     + click==8.1.7
+     + colorama==0.4.6
+    "###
+    );
+
+    // `markupsafe` only publishes `manylinux_2_17` wheels, which are compatible with
+    // `manylinux_2_28`.
+    uv_snapshot!(context.install()
+        .arg("markupsafe==2.1.5")
+        .arg("--target")
+        .arg("linux")
+        .arg("--platform")
+        .arg("manylinux_2_28_aarch64")
+        .arg("--python-version")
+        .arg("3.12")
+        .arg("--only-binary")
+        .arg(":all:")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + markupsafe==2.1.5
+    "###
+    );
+
+    context
+        .temp_dir
+        .child("linux")
+        .child("markupsafe")
+        .child("_speedups.cpython-312-aarch64-linux-gnu.so")
+        .assert(predicates::path::is_file());
+}
+
+/// Install with bytecode compilation, recording the compiled files in the RECORD of the newly
+/// installed distributions only.
+#[test]
+fn install_compile_record() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(context.install()
+        .arg("MarkupSafe==2.1.3")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + markupsafe==2.1.3
+    "###
+    );
+
+    uv_snapshot!(context.install()
+        .arg("tomli==2.0.1")
+        .arg("--compile")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+    Bytecode compiled 7 files in [TIME]
+     + tomli==2.0.1
+    "###
+    );
+
+    // The RECORD of the newly installed distribution includes its compiled files.
+    let record = fs_err::read_to_string(
+        context
+            .site_packages()
+            .join("tomli-2.0.1.dist-info")
+            .join("RECORD"),
+    )?;
+    assert!(record.contains("tomli/__pycache__/__init__.cpython-312.pyc,sha256="));
+
+    // The RECORD of the existing distribution is left untouched, even though its files were
+    // compiled.
+    assert!(context
+        .site_packages()
+        .join("markupsafe")
+        .join("__pycache__")
+        .join("__init__.cpython-312.pyc")
+        .exists());
+    let record = fs_err::read_to_string(
+        context
+            .site_packages()
+            .join("MarkupSafe-2.1.3.dist-info")
+            .join("RECORD"),
+    )?;
+    assert!(!record.contains("__pycache__"));
+
+    Ok(())
+}
+
 /// Install a package without using the remote index
 #[test]
 fn install_no_index() {
@@ -2182,6 +2461,90 @@ requires-python = ">=3.8"
     Ok(())
 }
 
+/// Re-building an unchanged editable should reuse the cached wheel, without setting up a build
+/// environment.
+#[test]
+fn reinstall_editable_cached() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Create an editable package with no dependencies.
+    let editable_dir = context.temp_dir.child("editable");
+    editable_dir.create_dir_all()?;
+    let pyproject_toml = editable_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"[project]
+name = "example"
+version = "0.0.0"
+requires-python = ">=3.8"
+"#,
+    )?;
+
+    uv_snapshot!(context.filters(), context.install()
+        .arg("--editable")
+        .arg(editable_dir.path()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Built 1 editable in [TIME]
+    Resolved 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + example==0.0.0 (from file://[TEMP_DIR]/editable)
+    "###
+    );
+
+    // Re-installing without access to an index should succeed, since the build backend isn't
+    // needed to produce the (cached) editable wheel.
+    uv_snapshot!(context.filters(), context.install()
+        .arg("--editable")
+        .arg(editable_dir.path())
+        .arg("--reinstall")
+        .arg("--no-index"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Built 1 editable in [TIME]
+    Resolved 1 package in [TIME]
+    Installed 1 package in [TIME]
+     - example==0.0.0 (from file://[TEMP_DIR]/editable)
+     + example==0.0.0 (from file://[TEMP_DIR]/editable)
+    "###
+    );
+
+    // Modifying the package invalidates the cached wheel, which requires the build backend.
+    pyproject_toml.write_str(
+        r#"[project]
+name = "example"
+version = "0.0.1"
+requires-python = ">=3.8"
+"#,
+    )?;
+
+    uv_snapshot!(context.filters(), context.install()
+        .arg("--editable")
+        .arg(editable_dir.path())
+        .arg("--no-index"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to build editables
+      Caused by: Failed to build editable: file://[TEMP_DIR]/editable
+      Caused by: Failed to install requirements from build-system.requires (resolve)
+      Caused by: No solution found when resolving: setuptools>=40.8.0
+      Caused by: Because setuptools was not found in the provided package locations and you require setuptools>=40.8.0, we can conclude that your requirements are unsatisfiable.
+
+    hint: Packages were unavailable because index lookups were disabled and no additional package locations were provided (try: `--find-links <uri>`)
+    "###
+    );
+
+    Ok(())
+}
+
 #[test]
 fn invalidate_editable_dynamic() -> Result<()> {
     let context = TestContext::new("3.12");
@@ -2627,6 +2990,7 @@ fn dry_run_install_url_dependency() -> std::result::Result<(), Box<dyn std::erro
     ----- stderr -----
     Resolved 3 packages in [TIME]
     Would download 3 packages
+    Would build 1 package
     Would install 3 packages
      + anyio @ https://files.pythonhosted.org/packages/2d/b8/7333d87d5f03247215d86a86362fd3e324111788c6cdd8d2e6196a6ba833/anyio-4.2.0.tar.gz
      + idna==3.6
@@ -3726,6 +4090,32 @@ fn find_links() -> Result<()> {
     Ok(())
 }
 
+/// Install with `--exclude`, which should skip the excluded package.
+#[test]
+fn exclude() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(context.filters(), context.install()
+        .arg("tqdm")
+        .arg("validation")
+        .arg("--exclude")
+        .arg("validation")
+        .arg("--no-index")
+        .arg("--find-links")
+        .arg(context.workspace_root.join("scripts/links/")), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + tqdm==1000.0.0
+    "###
+    );
+}
+
 /// Sync using `--find-links` with a local directory, with wheels disabled.
 #[test]
 fn find_links_no_binary() -> Result<()> {
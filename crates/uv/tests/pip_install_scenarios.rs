@@ -4729,11 +4729,11 @@ fn transitive_package_only_yanked_in_range_opt_in() {
 
     ----- stderr -----
     Resolved 2 packages in [TIME]
+    warning: package-b==1.0.0 is yanked (reason: "Yanked for testing").
     Downloaded 2 packages in [TIME]
     Installed 2 packages in [TIME]
      + package-a==0.1.0
      + package-b==1.0.0
-    warning: package-b==1.0.0 is yanked (reason: "Yanked for testing").
     "###);
 
     // Since the user included a dependency on `b` with an exact specifier, the yanked
@@ -4860,12 +4860,12 @@ fn transitive_yanked_and_unyanked_dependency_opt_in() {
 
     ----- stderr -----
     Resolved 3 packages in [TIME]
+    warning: package-c==2.0.0 is yanked (reason: "Yanked for testing").
     Downloaded 3 packages in [TIME]
     Installed 3 packages in [TIME]
      + package-a==1.0.0
      + package-b==1.0.0
      + package-c==2.0.0
-    warning: package-c==2.0.0 is yanked (reason: "Yanked for testing").
     "###);
 
     // Since the user explicitly selected the yanked version of `c`, it can be
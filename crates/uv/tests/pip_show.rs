@@ -221,6 +221,59 @@ fn show_found_single_package() -> Result<()> {
     Ok(())
 }
 
+/// Show the files installed for a package, as listed in its `RECORD`.
+#[test]
+fn show_files() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(context.filters(), install_command(&context)
+        .arg("tqdm==1000.0.0")
+        .arg("--no-index")
+        .arg("--find-links")
+        .arg(context.workspace_root.join("scripts/links/")), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + tqdm==1000.0.0
+    "###
+    );
+
+    uv_snapshot!(context.filters(), Command::new(get_bin())
+        .arg("pip")
+        .arg("show")
+        .arg("tqdm")
+        .arg("--files")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Name: tqdm
+    Version: 1000.0.0
+    Location: [SITE_PACKAGES]/
+    Requires:
+    Required-by:
+    Files:
+      tqdm-1000.0.0.dist-info/INSTALLER
+      tqdm-1000.0.0.dist-info/METADATA
+      tqdm-1000.0.0.dist-info/RECORD
+      tqdm-1000.0.0.dist-info/REQUESTED
+      tqdm-1000.0.0.dist-info/WHEEL
+      tqdm/__init__.py
+
+    ----- stderr -----
+    "###
+    );
+}
+
 #[test]
 fn show_found_multiple_packages() -> Result<()> {
     let context = TestContext::new("3.12");
@@ -553,3 +606,61 @@ fn show_required_by_multiple() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn why_required_by_multiple() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.touch()?;
+    requirements_txt.write_str(indoc! {r"
+        anyio==4.0.0
+        requests==2.31.0
+    "
+    })?;
+
+    uv_snapshot!(install_command(&context)
+        .arg("-r")
+        .arg("requirements.txt")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 7 packages in [TIME]
+    Downloaded 7 packages in [TIME]
+    Installed 7 packages in [TIME]
+     + anyio==4.0.0
+     + certifi==2024.2.2
+     + charset-normalizer==3.3.2
+     + idna==3.6
+     + requests==2.31.0
+     + sniffio==1.3.1
+     + urllib3==2.2.1
+    "###
+    );
+
+    // idna is required by anyio and requests, neither of which are required by anything else.
+    uv_snapshot!(context.filters(), Command::new(get_bin())
+        .arg("pip")
+        .arg("why")
+        .arg("idna")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    idna==3.6
+    ├── anyio==4.0.0 (requires idna>=2.8)
+    └── requests==2.31.0 (requires idna<4,>=2.5)
+
+    ----- stderr -----
+    "###
+    );
+
+    Ok(())
+}
@@ -250,6 +250,91 @@ fn install_hardlink() -> Result<()> {
     Ok(())
 }
 
+/// Install a package into a virtual environment using clone (i.e., copy-on-write) semantics.
+///
+/// If the filesystem doesn't support copy-on-write, the installation should fall back to
+/// hard-linking (or copying) without any user-facing warning.
+#[test]
+fn install_clone() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.touch()?;
+    requirements_txt.write_str("MarkupSafe==2.1.3")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--link-mode")
+        .arg("clone")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + markupsafe==2.1.3
+    "###
+    );
+
+    context.assert_command("import markupsafe").success();
+
+    // Removing the cache shouldn't invalidate the virtual environment.
+    fs::remove_dir_all(context.cache_dir.path())?;
+
+    context.assert_command("import markupsafe").success();
+
+    Ok(())
+}
+
+/// Install a package into a virtual environment using symlink semantics.
+#[test]
+#[cfg(unix)]
+fn install_symlink() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.touch()?;
+    requirements_txt.write_str("MarkupSafe==2.1.3")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--link-mode")
+        .arg("symlink")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + markupsafe==2.1.3
+    "###
+    );
+
+    context.assert_command("import markupsafe").success();
+
+    // The installed files should link into the cache.
+    assert!(context
+        .site_packages()
+        .join("markupsafe")
+        .join("__init__.py")
+        .symlink_metadata()?
+        .file_type()
+        .is_symlink());
+
+    // Unlike the other link modes, removing the cache invalidates the virtual environment.
+    fs::remove_dir_all(context.cache_dir.path())?;
+
+    context.assert_command("import markupsafe").failure();
+
+    Ok(())
+}
+
 /// Install multiple packages into a virtual environment.
 #[test]
 fn install_many() -> Result<()> {
@@ -2154,181 +2239,411 @@ fn refresh_package() -> Result<()> {
     Ok(())
 }
 
+/// Verify that we can revalidate cached data, rather than discarding it.
 #[test]
-#[cfg(feature = "maturin")]
-fn sync_editable() -> Result<()> {
+fn revalidate() -> Result<()> {
     let context = TestContext::new("3.12");
 
     let requirements_txt = context.temp_dir.child("requirements.txt");
-    requirements_txt.write_str(&indoc::formatdoc! {r"
-        boltons==23.1.1
-        -e {workspace_root}/scripts/packages/maturin_editable
-        numpy==1.26.2
-            # via poetry-editable
-        -e file://{workspace_root}/scripts/packages/poetry_editable
-        ",
-        workspace_root = context.workspace_root.simplified_display(),
-    })?;
+    requirements_txt.touch()?;
+    requirements_txt.write_str("MarkupSafe==2.1.3\ntomli==2.0.1")?;
 
-    // Install the editable packages.
-    uv_snapshot!(context.filters(), command(&context)
-        .arg(requirements_txt.path()), @r###"
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--strict"), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
 
     ----- stderr -----
-    Built 2 editables in [TIME]
     Resolved 2 packages in [TIME]
     Downloaded 2 packages in [TIME]
-    Installed 4 packages in [TIME]
-     + boltons==23.1.1
-     + maturin-editable==0.1.0 (from file://[WORKSPACE]/scripts/packages/maturin_editable)
-     + numpy==1.26.2
-     + poetry-editable==0.1.0 (from file://[WORKSPACE]/scripts/packages/poetry_editable)
+    Installed 2 packages in [TIME]
+     + markupsafe==2.1.3
+     + tomli==2.0.1
     "###
     );
 
-    // Reinstall the editable packages.
-    uv_snapshot!(context.filters(), command(&context)
-        .arg(requirements_txt.path())
-        .arg("--reinstall-package")
-        .arg("poetry-editable"), @r###"
+    context.assert_command("import markupsafe").success();
+    context.assert_command("import tomli").success();
+
+    // Re-run the installation with `--revalidate`. The cached wheels are revalidated against the
+    // index, so they're reported as downloaded, rather than installed directly from the cache,
+    // even though the index reports them as unchanged.
+    let parent = context.temp_dir.child("parent");
+    parent.create_dir_all()?;
+    let venv = create_venv(&parent, &context.cache_dir, "3.12");
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--revalidate")
+        .arg("--strict")
+        .env("VIRTUAL_ENV", venv.as_os_str()), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
 
     ----- stderr -----
-    Built 1 editable in [TIME]
-    Uninstalled 1 package in [TIME]
-    Installed 1 package in [TIME]
-     - poetry-editable==0.1.0 (from file://[WORKSPACE]/scripts/packages/poetry_editable)
-     + poetry-editable==0.1.0 (from file://[WORKSPACE]/scripts/packages/poetry_editable)
+    Resolved 2 packages in [TIME]
+    Downloaded 2 packages in [TIME]
+    Installed 2 packages in [TIME]
+     + markupsafe==2.1.3
+     + tomli==2.0.1
     "###
     );
 
-    // Make sure we have the right base case.
-    let python_source_file = context
-        .workspace_root
-        .join("scripts/packages/maturin_editable/python/maturin_editable/__init__.py");
-    let python_version_1 = indoc::indoc! {r"
-        from .maturin_editable import *
+    check_command(&venv, "import markupsafe", &context.temp_dir);
+    check_command(&venv, "import tomli", &context.temp_dir);
 
-        version = 1
-   "};
-    fs_err::write(&python_source_file, python_version_1)?;
+    Ok(())
+}
 
-    let check_installed = indoc::indoc! {r#"
-        from maturin_editable import sum_as_string, version
+/// Install packages with the network filesystem strategy, which copies unzipped wheels into place
+/// rather than symlinking them from the archive bucket.
+#[test]
+fn cache_fs_mode_network() -> Result<()> {
+    let context = TestContext::new("3.12");
 
-        assert version == 1, version
-        assert sum_as_string(1, 2) == "3", sum_as_string(1, 2)
-   "#};
-    context.assert_command(check_installed).success();
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("MarkupSafe==2.1.3\ntomli==2.0.1")?;
 
-    // Edit the sources.
-    let python_version_2 = indoc::indoc! {r"
-        from .maturin_editable import *
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--cache-fs-mode")
+        .arg("network")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
 
-        version = 2
-   "};
-    fs_err::write(&python_source_file, python_version_2)?;
+    ----- stderr -----
+    Resolved 2 packages in [TIME]
+    Downloaded 2 packages in [TIME]
+    Installed 2 packages in [TIME]
+     + markupsafe==2.1.3
+     + tomli==2.0.1
+    "###
+    );
 
-    let check_installed = indoc::indoc! {r#"
-        from maturin_editable import sum_as_string, version
-        from pathlib import Path
+    context.assert_command("import markupsafe").success();
+    context.assert_command("import tomli").success();
 
-        assert version == 2, version
-        assert sum_as_string(1, 2) == "3", sum_as_string(1, 2)
-   "#};
-    context.assert_command(check_installed).success();
+    // No entries should have been persisted to the archive bucket.
+    let archive = context.cache_dir.child("archive-v0");
+    assert!(fs_err::read_dir(archive.path()).map_or(true, |mut entries| entries.next().is_none()));
 
-    // Don't create a git diff.
-    fs_err::write(&python_source_file, python_version_1)?;
+    // Create a new virtual environment.
+    let venv = create_venv(&context.temp_dir, &context.cache_dir, "3.12");
 
-    uv_snapshot!(context.filters(), command(&context)
-        .arg(requirements_txt.path()), @r###"
+    // Reinstall. The copied wheels should be reused from the cache, without a download.
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--cache-fs-mode")
+        .arg("network")
+        .arg("--strict")
+        .env("VIRTUAL_ENV", venv.as_os_str()), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
 
     ----- stderr -----
-    Audited 4 packages in [TIME]
+    Installed 2 packages in [TIME]
+     + markupsafe==2.1.3
+     + tomli==2.0.1
     "###
     );
 
+    check_command(&venv, "import markupsafe", &context.temp_dir);
+    check_command(&venv, "import tomli", &context.temp_dir);
+
     Ok(())
 }
 
+/// Re-install a direct URL dependency with `--refresh-url`, which should only re-download the
+/// matching URL.
 #[test]
-fn sync_editable_and_registry() -> Result<()> {
+fn refresh_url() -> Result<()> {
     let context = TestContext::new("3.12");
 
-    // Copy the black test editable into the "current" directory
-    copy_dir_all(
-        context
-            .workspace_root
-            .join("scripts/packages/black_editable"),
-        context.temp_dir.join("black_editable"),
-    )?;
-
-    // Install the registry-based version of Black.
     let requirements_txt = context.temp_dir.child("requirements.txt");
-    requirements_txt.write_str(indoc::indoc! {r"
-        black==24.1.0
-        "
-    })?;
+    requirements_txt.write_str("MarkupSafe==2.1.3\ntomli @ https://files.pythonhosted.org/packages/97/75/10a9ebee3fd790d20926a90a2547f0bf78f371b2f13aa822c759680ca7b9/tomli-2.0.1-py3-none-any.whl")?;
 
-    uv_snapshot!(context.filters(), command(&context)
-        .arg(requirements_txt.path())
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
         .arg("--strict"), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
 
     ----- stderr -----
-    Resolved 1 package in [TIME]
-    Downloaded 1 package in [TIME]
-    Installed 1 package in [TIME]
-     + black==24.1.0
-    warning: The package `black` requires `click>=8.0.0`, but it's not installed.
-    warning: The package `black` requires `mypy-extensions>=0.4.3`, but it's not installed.
-    warning: The package `black` requires `packaging>=22.0`, but it's not installed.
-    warning: The package `black` requires `pathspec>=0.9.0`, but it's not installed.
-    warning: The package `black` requires `platformdirs>=2`, but it's not installed.
+    Resolved 2 packages in [TIME]
+    Downloaded 2 packages in [TIME]
+    Installed 2 packages in [TIME]
+     + markupsafe==2.1.3
+     + tomli==2.0.1 (from https://files.pythonhosted.org/packages/97/75/10a9ebee3fd790d20926a90a2547f0bf78f371b2f13aa822c759680ca7b9/tomli-2.0.1-py3-none-any.whl)
     "###
     );
 
-    // Install the editable version of Black. This should remove the registry-based version.
-    // Use the `file:` syntax for extra coverage.
-    let requirements_txt = context.temp_dir.child("requirements.txt");
-    requirements_txt.write_str(indoc::indoc! {r"
-        -e file:./black_editable
-        "
-    })?;
+    // Re-run the installation into a new environment with `--refresh-url`. Ensure that we only
+    // re-download the URL dependency.
+    let parent = context.temp_dir.child("parent");
+    parent.create_dir_all()?;
+    let venv = create_venv(&parent, &context.cache_dir, "3.12");
 
-    uv_snapshot!(context.filters(), command(&context)
-        .arg(requirements_txt.path()), @r###"
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--refresh-url")
+        .arg("https://files.pythonhosted.org/packages/97/75/10a9ebee3fd790d20926a90a2547f0bf78f371b2f13aa822c759680ca7b9/tomli-2.0.1-py3-none-any.whl")
+        .arg("--strict")
+        .env("VIRTUAL_ENV", venv.as_os_str()), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
 
     ----- stderr -----
-    Built 1 editable in [TIME]
-    Uninstalled 1 package in [TIME]
-    Installed 1 package in [TIME]
-     - black==24.1.0
-     + black==0.1.0 (from file://[TEMP_DIR]/black_editable)
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 2 packages in [TIME]
+     + markupsafe==2.1.3
+     + tomli==2.0.1 (from https://files.pythonhosted.org/packages/97/75/10a9ebee3fd790d20926a90a2547f0bf78f371b2f13aa822c759680ca7b9/tomli-2.0.1-py3-none-any.whl)
     "###
     );
 
-    // Re-install the registry-based version of Black. This should be a no-op, since we have a
-    // version of Black installed (the editable version) that satisfies the requirements.
-    let requirements_txt = context.temp_dir.child("requirements.txt");
-    requirements_txt.write_str(indoc::indoc! {r"
-        black
-        "
-    })?;
+    Ok(())
+}
+
+/// Re-install with both `--refresh-package` and `--refresh-url`, which should re-download the
+/// matching package and the matching URL.
+#[test]
+fn refresh_package_and_url() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("MarkupSafe==2.1.3\niniconfig==2.0.0\ntomli @ https://files.pythonhosted.org/packages/97/75/10a9ebee3fd790d20926a90a2547f0bf78f371b2f13aa822c759680ca7b9/tomli-2.0.1-py3-none-any.whl")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    Downloaded 3 packages in [TIME]
+    Installed 3 packages in [TIME]
+     + iniconfig==2.0.0
+     + markupsafe==2.1.3
+     + tomli==2.0.1 (from https://files.pythonhosted.org/packages/97/75/10a9ebee3fd790d20926a90a2547f0bf78f371b2f13aa822c759680ca7b9/tomli-2.0.1-py3-none-any.whl)
+    "###
+    );
+
+    // Re-run the installation into a new environment with both flags. Ensure that we re-download
+    // the refreshed package and the URL dependency, but not `iniconfig`.
+    let parent = context.temp_dir.child("parent");
+    parent.create_dir_all()?;
+    let venv = create_venv(&parent, &context.cache_dir, "3.12");
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--refresh-package")
+        .arg("markupsafe")
+        .arg("--refresh-url")
+        .arg("https://files.pythonhosted.org/packages/97/75/10a9ebee3fd790d20926a90a2547f0bf78f371b2f13aa822c759680ca7b9/tomli-2.0.1-py3-none-any.whl")
+        .arg("--strict")
+        .env("VIRTUAL_ENV", venv.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 2 packages in [TIME]
+    Downloaded 2 packages in [TIME]
+    Installed 3 packages in [TIME]
+     + iniconfig==2.0.0
+     + markupsafe==2.1.3
+     + tomli==2.0.1 (from https://files.pythonhosted.org/packages/97/75/10a9ebee3fd790d20926a90a2547f0bf78f371b2f13aa822c759680ca7b9/tomli-2.0.1-py3-none-any.whl)
+    "###
+    );
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "maturin")]
+fn sync_editable() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(&indoc::formatdoc! {r"
+        boltons==23.1.1
+        -e {workspace_root}/scripts/packages/maturin_editable
+        numpy==1.26.2
+            # via poetry-editable
+        -e file://{workspace_root}/scripts/packages/poetry_editable
+        ",
+        workspace_root = context.workspace_root.simplified_display(),
+    })?;
+
+    // Install the editable packages.
+    uv_snapshot!(context.filters(), command(&context)
+        .arg(requirements_txt.path()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Built 2 editables in [TIME]
+    Resolved 2 packages in [TIME]
+    Downloaded 2 packages in [TIME]
+    Installed 4 packages in [TIME]
+     + boltons==23.1.1
+     + maturin-editable==0.1.0 (from file://[WORKSPACE]/scripts/packages/maturin_editable)
+     + numpy==1.26.2
+     + poetry-editable==0.1.0 (from file://[WORKSPACE]/scripts/packages/poetry_editable)
+    "###
+    );
+
+    // Reinstall the editable packages.
+    uv_snapshot!(context.filters(), command(&context)
+        .arg(requirements_txt.path())
+        .arg("--reinstall-package")
+        .arg("poetry-editable"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Built 1 editable in [TIME]
+    Uninstalled 1 package in [TIME]
+    Installed 1 package in [TIME]
+     - poetry-editable==0.1.0 (from file://[WORKSPACE]/scripts/packages/poetry_editable)
+     + poetry-editable==0.1.0 (from file://[WORKSPACE]/scripts/packages/poetry_editable)
+    "###
+    );
+
+    // Make sure we have the right base case.
+    let python_source_file = context
+        .workspace_root
+        .join("scripts/packages/maturin_editable/python/maturin_editable/__init__.py");
+    let python_version_1 = indoc::indoc! {r"
+        from .maturin_editable import *
+
+        version = 1
+   "};
+    fs_err::write(&python_source_file, python_version_1)?;
+
+    let check_installed = indoc::indoc! {r#"
+        from maturin_editable import sum_as_string, version
+
+        assert version == 1, version
+        assert sum_as_string(1, 2) == "3", sum_as_string(1, 2)
+   "#};
+    context.assert_command(check_installed).success();
+
+    // Edit the sources.
+    let python_version_2 = indoc::indoc! {r"
+        from .maturin_editable import *
+
+        version = 2
+   "};
+    fs_err::write(&python_source_file, python_version_2)?;
+
+    let check_installed = indoc::indoc! {r#"
+        from maturin_editable import sum_as_string, version
+        from pathlib import Path
+
+        assert version == 2, version
+        assert sum_as_string(1, 2) == "3", sum_as_string(1, 2)
+   "#};
+    context.assert_command(check_installed).success();
+
+    // Don't create a git diff.
+    fs_err::write(&python_source_file, python_version_1)?;
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg(requirements_txt.path()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Audited 4 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+#[test]
+fn sync_editable_and_registry() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Copy the black test editable into the "current" directory
+    copy_dir_all(
+        context
+            .workspace_root
+            .join("scripts/packages/black_editable"),
+        context.temp_dir.join("black_editable"),
+    )?;
+
+    // Install the registry-based version of Black.
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(indoc::indoc! {r"
+        black==24.1.0
+        "
+    })?;
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg(requirements_txt.path())
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + black==24.1.0
+    warning: The package `black` requires `click>=8.0.0`, but it's not installed.
+    warning: The package `black` requires `mypy-extensions>=0.4.3`, but it's not installed.
+    warning: The package `black` requires `packaging>=22.0`, but it's not installed.
+    warning: The package `black` requires `pathspec>=0.9.0`, but it's not installed.
+    warning: The package `black` requires `platformdirs>=2`, but it's not installed.
+    "###
+    );
+
+    // Install the editable version of Black. This should remove the registry-based version.
+    // Use the `file:` syntax for extra coverage.
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(indoc::indoc! {r"
+        -e file:./black_editable
+        "
+    })?;
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg(requirements_txt.path()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Built 1 editable in [TIME]
+    Uninstalled 1 package in [TIME]
+    Installed 1 package in [TIME]
+     - black==24.1.0
+     + black==0.1.0 (from file://[TEMP_DIR]/black_editable)
+    "###
+    );
+
+    // Re-install the registry-based version of Black. This should be a no-op, since we have a
+    // version of Black installed (the editable version) that satisfies the requirements.
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(indoc::indoc! {r"
+        black
+        "
+    })?;
 
     uv_snapshot!(context.filters(), command(&context)
         .arg(requirements_txt.path()), @r###"
@@ -2484,18 +2799,41 @@ fn find_links() -> Result<()> {
     Ok(())
 }
 
-/// Sync using `--find-links` with `--no-index`, which should accept the local wheel.
+/// Sync with `--exclude`, which should neither install nor remove the excluded package.
 #[test]
-fn find_links_no_index_match() -> Result<()> {
+fn exclude() -> Result<()> {
     let context = TestContext::new("3.12");
 
     let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("validation==1.0.0")?;
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("requirements.txt")
+        .arg("--no-index")
+        .arg("--find-links")
+        .arg(context.workspace_root.join("scripts/links/")), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + validation==1.0.0
+    "###
+    );
+
+    // The installed `validation` should be left in place, rather than upgraded or removed.
     requirements_txt.write_str(indoc! {r"
         tqdm==1000.0.0
+        validation==2.0.0
     "})?;
 
     uv_snapshot!(context.filters(), command(&context)
         .arg("requirements.txt")
+        .arg("--exclude")
+        .arg("validation")
         .arg("--no-index")
         .arg("--find-links")
         .arg(context.workspace_root.join("scripts/links/")), @r###"
@@ -2514,9 +2852,9 @@ fn find_links_no_index_match() -> Result<()> {
     Ok(())
 }
 
-/// Sync using `--find-links` with `--offline`, which should accept the local wheel.
+/// Sync using `--find-links` with `--no-index`, which should accept the local wheel.
 #[test]
-fn find_links_offline_match() -> Result<()> {
+fn find_links_no_index_match() -> Result<()> {
     let context = TestContext::new("3.12");
 
     let requirements_txt = context.temp_dir.child("requirements.txt");
@@ -2526,7 +2864,7 @@ fn find_links_offline_match() -> Result<()> {
 
     uv_snapshot!(context.filters(), command(&context)
         .arg("requirements.txt")
-        .arg("--offline")
+        .arg("--no-index")
         .arg("--find-links")
         .arg(context.workspace_root.join("scripts/links/")), @r###"
     success: true
@@ -2544,68 +2882,63 @@ fn find_links_offline_match() -> Result<()> {
     Ok(())
 }
 
-/// Sync using `--find-links` with `--offline`, which should fail to find `numpy`.
+/// Sync a requirement with `--hash`, which should verify the distribution against the hashes.
 #[test]
-fn find_links_offline_no_match() -> Result<()> {
+fn require_hashes() -> Result<()> {
     let context = TestContext::new("3.12");
 
+    // Every requirement must be pinned.
     let requirements_txt = context.temp_dir.child("requirements.txt");
     requirements_txt.write_str(indoc! {r"
-        numpy
-        tqdm==1000.0.0
+        tqdm>=1000.0.0 --hash=sha256:a34996d4bd5abb2336e14ff0a2d22b92cfd0f0ed344e6883041ce01953276a13
     "})?;
 
     uv_snapshot!(context.filters(), command(&context)
         .arg("requirements.txt")
-        .arg("--offline")
+        .arg("--no-index")
         .arg("--find-links")
         .arg(context.workspace_root.join("scripts/links/")), @r###"
     success: false
-    exit_code: 1
+    exit_code: 2
     ----- stdout -----
 
     ----- stderr -----
-      × No solution found when resolving dependencies:
-      ╰─▶ Because numpy was not found in the cache and you require numpy, we can conclude that the requirements are unsatisfiable.
-
-          hint: Packages were unavailable because the network was disabled
+    error: In `--require-hashes` mode, all requirements must be pinned upfront with `==`, but found: `tqdm>=1000.0.0`
     "###
     );
 
-    Ok(())
-}
-
-/// Sync using `--find-links` with a local directory. Ensure that cached wheels are reused.
-#[test]
-fn find_links_cache() -> Result<()> {
-    let context = TestContext::new("3.12");
-
-    let requirements_txt = context.temp_dir.child("requirements.txt");
+    // A mismatched hash should be rejected.
     requirements_txt.write_str(indoc! {r"
-        tqdm
+        tqdm==1000.0.0 --hash=sha256:0000000000000000000000000000000000000000000000000000000000000000
     "})?;
 
-    // Install `tqdm`.
     uv_snapshot!(context.filters(), command(&context)
         .arg("requirements.txt")
+        .arg("--no-index")
         .arg("--find-links")
         .arg(context.workspace_root.join("scripts/links/")), @r###"
-    success: true
-    exit_code: 0
+    success: false
+    exit_code: 2
     ----- stdout -----
 
     ----- stderr -----
     Resolved 1 package in [TIME]
-    Downloaded 1 package in [TIME]
-    Installed 1 package in [TIME]
-     + tqdm==1000.0.0
+    error: Failed to download distributions
+      Caused by: Failed to fetch wheel: tqdm==1000.0.0
+      Caused by: Hash mismatch for file://[WORKSPACE]/scripts/links/tqdm-1000.0.0-py3-none-any.whl: expected one of the sha256 hashes provided via `--hash`, but the file has sha256 `a34996d4bd5abb2336e14ff0a2d22b92cfd0f0ed344e6883041ce01953276a13`
     "###
     );
 
-    // Reinstall `tqdm` with `--reinstall`. Ensure that the wheel is reused.
+    // A matching hash should be accepted.
+    requirements_txt.write_str(indoc! {r"
+        tqdm==1000.0.0 \
+            --hash=sha256:0000000000000000000000000000000000000000000000000000000000000000 \
+            --hash=sha256:a34996d4bd5abb2336e14ff0a2d22b92cfd0f0ed344e6883041ce01953276a13
+    "})?;
+
     uv_snapshot!(context.filters(), command(&context)
         .arg("requirements.txt")
-        .arg("--reinstall")
+        .arg("--no-index")
         .arg("--find-links")
         .arg(context.workspace_root.join("scripts/links/")), @r###"
     success: true
@@ -2613,9 +2946,9 @@ fn find_links_cache() -> Result<()> {
     ----- stdout -----
 
     ----- stderr -----
-    Uninstalled 1 package in [TIME]
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
     Installed 1 package in [TIME]
-     - tqdm==1000.0.0
      + tqdm==1000.0.0
     "###
     );
@@ -2623,32 +2956,23 @@ fn find_links_cache() -> Result<()> {
     Ok(())
 }
 
-/// Install without network access via the `--offline` flag.
+/// Write a JSON report of the installed and removed packages with `--report`.
 #[test]
-fn offline() -> Result<()> {
+fn install_report() -> Result<()> {
     let context = TestContext::new("3.12");
-    let requirements_in = context.temp_dir.child("requirements.in");
-    requirements_in.write_str("black==23.10.1")?;
-
-    // Install with `--offline` with an empty cache.
-    uv_snapshot!(command(&context)
-        .arg("requirements.in")
-        .arg("--offline"), @r###"
-    success: false
-    exit_code: 1
-    ----- stdout -----
-
-    ----- stderr -----
-      × No solution found when resolving dependencies:
-      ╰─▶ Because black==23.10.1 was not found in the cache and you require black==23.10.1, we can conclude that the requirements are unsatisfiable.
 
-          hint: Packages were unavailable because the network was disabled
-    "###
-    );
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("tqdm==1000.0.0")?;
 
-    // Populate the cache.
-    uv_snapshot!(command(&context)
-        .arg("requirements.in"), @r###"
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("requirements.txt")
+        .arg("--no-index")
+        .arg("--find-links")
+        .arg(context.workspace_root.join("scripts/links/"))
+        .arg("--link-mode")
+        .arg("copy")
+        .arg("--report")
+        .arg("report.json"), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
@@ -2657,39 +2981,70 @@ fn offline() -> Result<()> {
     Resolved 1 package in [TIME]
     Downloaded 1 package in [TIME]
     Installed 1 package in [TIME]
-     + black==23.10.1
+     + tqdm==1000.0.0
     "###
     );
 
-    // Install with `--offline` with a populated cache.
-    let venv = create_venv(&context.temp_dir, &context.cache_dir, "3.12");
-
-    uv_snapshot!(command(&context)
-        .arg("requirements.in")
-        .arg("--offline")
-        .env("VIRTUAL_ENV", venv.as_os_str()), @r###"
+    let report: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(context.temp_dir.child("report.json"))?)?;
+    assert_eq!(report["link_mode"], "copy");
+    assert_eq!(report["installed"][0]["name"], "tqdm");
+    assert_eq!(report["installed"][0]["version"], "1000.0.0");
+    assert_eq!(report["installed"][0]["cached"], false);
+    assert!(report["installed"][0]["url"]
+        .as_str()
+        .is_some_and(|url| url.ends_with("/scripts/links/tqdm-1000.0.0-py3-none-any.whl")));
+    assert_eq!(report["removed"], serde_json::json!([]));
+
+    // Reinstalling should report the removal, too.
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("requirements.txt")
+        .arg("--no-index")
+        .arg("--find-links")
+        .arg(context.workspace_root.join("scripts/links/"))
+        .arg("--link-mode")
+        .arg("copy")
+        .arg("--reinstall")
+        .arg("--report")
+        .arg("report.json"), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
 
     ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Uninstalled 1 package in [TIME]
     Installed 1 package in [TIME]
-     + black==23.10.1
+     - tqdm==1000.0.0
+     + tqdm==1000.0.0
     "###
     );
 
+    let report: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(context.temp_dir.child("report.json"))?)?;
+    assert_eq!(report["installed"][0]["name"], "tqdm");
+    assert_eq!(
+        report["removed"],
+        serde_json::json!([{ "name": "tqdm", "version": "1000.0.0" }])
+    );
+
     Ok(())
 }
 
-/// Sync with a repeated `anyio` requirement. The second requirement should be ignored.
+/// Report the hash of a wheel downloaded from a direct URL, which is computed as it's downloaded,
+/// since direct URLs don't provide any hashes.
 #[test]
-fn repeat_requirement() -> Result<()> {
+fn install_report_direct_url() -> Result<()> {
     let context = TestContext::new("3.12");
-    let requirements_in = context.temp_dir.child("requirements.in");
-    requirements_in.write_str("anyio\nanyio")?;
 
-    uv_snapshot!(command(&context)
-        .arg("requirements.in"), @r###"
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("tomli @ https://files.pythonhosted.org/packages/97/75/10a9ebee3fd790d20926a90a2547f0bf78f371b2f13aa822c759680ca7b9/tomli-2.0.1-py3-none-any.whl")?;
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("requirements.txt")
+        .arg("--report")
+        .arg("report.json"), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
@@ -2698,45 +3053,92 @@ fn repeat_requirement() -> Result<()> {
     Resolved 1 package in [TIME]
     Downloaded 1 package in [TIME]
     Installed 1 package in [TIME]
-     + anyio==4.3.0
-    "###);
+     + tomli==2.0.1 (from https://files.pythonhosted.org/packages/97/75/10a9ebee3fd790d20926a90a2547f0bf78f371b2f13aa822c759680ca7b9/tomli-2.0.1-py3-none-any.whl)
+    "###
+    );
+
+    let report: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(context.temp_dir.child("report.json"))?)?;
+    assert_eq!(report["installed"][0]["name"], "tomli");
+    assert_eq!(report["installed"][0]["cached"], false);
+    assert_eq!(
+        report["installed"][0]["sha256"],
+        "939de3e7a6161af0c887ef91b7d41a53e7c5a1ca976325f429cb46ea9bc30ecc"
+    );
 
     Ok(())
 }
 
-/// Sync with a repeated, but conflicting `anyio` requirement. The second requirement should cause
-/// an error.
+/// Record the hashes of remote and local archives in `direct_url.json`, computing them if the URL
+/// doesn't include one.
 #[test]
-fn conflicting_requirement() -> Result<()> {
+fn direct_url_json_archive_hashes() -> Result<()> {
     let context = TestContext::new("3.12");
-    let requirements_in = context.temp_dir.child("requirements.in");
-    requirements_in.write_str("anyio\nanyio==4.0.0")?;
 
-    uv_snapshot!(command(&context)
-        .arg("requirements.in"), @r###"
-    success: false
-    exit_code: 2
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(&format!(
+        "tomli @ https://files.pythonhosted.org/packages/97/75/10a9ebee3fd790d20926a90a2547f0bf78f371b2f13aa822c759680ca7b9/tomli-2.0.1-py3-none-any.whl\ntqdm @ {}",
+        Url::from_file_path(
+            context
+                .workspace_root
+                .join("scripts/links/tqdm-1000.0.0-py3-none-any.whl")
+        )
+        .unwrap()
+    ))?;
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("requirements.txt"), @r###"
+    success: true
+    exit_code: 0
     ----- stdout -----
 
     ----- stderr -----
-    error: Failed to determine installation plan
-      Caused by: Detected duplicate package in requirements: anyio
-    "###);
+    Resolved 2 packages in [TIME]
+    Downloaded 2 packages in [TIME]
+    Installed 2 packages in [TIME]
+     + tomli==2.0.1 (from https://files.pythonhosted.org/packages/97/75/10a9ebee3fd790d20926a90a2547f0bf78f371b2f13aa822c759680ca7b9/tomli-2.0.1-py3-none-any.whl)
+     + tqdm==1000.0.0 (from file://[WORKSPACE]/scripts/links/tqdm-1000.0.0-py3-none-any.whl)
+    "###
+    );
+
+    let direct_url: serde_json::Value = serde_json::from_str(&fs::read_to_string(
+        context
+            .site_packages()
+            .join("tomli-2.0.1.dist-info")
+            .join("direct_url.json"),
+    )?)?;
+    assert_eq!(
+        direct_url["archive_info"]["hashes"]["sha256"],
+        "939de3e7a6161af0c887ef91b7d41a53e7c5a1ca976325f429cb46ea9bc30ecc"
+    );
+
+    let direct_url: serde_json::Value = serde_json::from_str(&fs::read_to_string(
+        context
+            .site_packages()
+            .join("tqdm-1000.0.0.dist-info")
+            .join("direct_url.json"),
+    )?)?;
+    assert!(direct_url["archive_info"]["hashes"]["sha256"].is_string());
 
     Ok(())
 }
 
-/// Don't preserve the mtime from .tar.gz files, it may be the unix epoch (1970-01-01), while Python's zip
-/// implementation can't handle files with an mtime older than 1980.
-/// See also <https://github.com/alexcrichton/tar-rs/issues/349>.
+/// Run a post-install hook with the JSON report of the changes made to the environment.
 #[test]
-fn tar_dont_preserve_mtime() -> Result<()> {
+#[cfg(unix)]
+fn post_install_hook() -> Result<()> {
     let context = TestContext::new("3.12");
+
     let requirements_txt = context.temp_dir.child("requirements.txt");
-    requirements_txt.write_str("tomli @ https://files.pythonhosted.org/packages/c0/3f/d7af728f075fb08564c5949a9c95e44352e23dee646869fa104a3b2060a3/tomli-2.0.1.tar.gz")?;
+    requirements_txt.write_str("tqdm==1000.0.0")?;
 
-    uv_snapshot!(command(&context)
-        .arg("requirements.txt"), @r###"
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("requirements.txt")
+        .arg("--no-index")
+        .arg("--find-links")
+        .arg(context.workspace_root.join("scripts/links/"))
+        .arg("--post-install-hook")
+        .arg("cat > hook.json && echo \"$UV_HOOK_ENVIRONMENT\" > environment.txt"), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
@@ -2745,21 +3147,28 @@ fn tar_dont_preserve_mtime() -> Result<()> {
     Resolved 1 package in [TIME]
     Downloaded 1 package in [TIME]
     Installed 1 package in [TIME]
-     + tomli==2.0.1 (from https://files.pythonhosted.org/packages/c0/3f/d7af728f075fb08564c5949a9c95e44352e23dee646869fa104a3b2060a3/tomli-2.0.1.tar.gz)
-    "###);
-
-    Ok(())
-}
+     + tqdm==1000.0.0
+    "###
+    );
 
-/// Avoid creating a file with 000 permissions
-#[test]
-fn set_read_permissions() -> Result<()> {
-    let context = TestContext::new("3.12");
-    let requirements_in = context.temp_dir.child("requirements.in");
-    requirements_in.write_str("databricks==0.2")?;
+    let report: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(context.temp_dir.child("hook.json"))?)?;
+    assert_eq!(report["installed"][0]["name"], "tqdm");
+    assert_eq!(report["installed"][0]["version"], "1000.0.0");
+    assert!(
+        Path::new(fs::read_to_string(context.temp_dir.child("environment.txt"))?.trim())
+            .ends_with(".venv")
+    );
 
-    uv_snapshot!(command(&context)
-        .arg("requirements.in"), @r###"
+    // A failing hook is reported as a warning, after the changes to the environment.
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("requirements.txt")
+        .arg("--no-index")
+        .arg("--find-links")
+        .arg(context.workspace_root.join("scripts/links/"))
+        .arg("--reinstall")
+        .arg("--post-install-hook")
+        .arg("exit 3"), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
@@ -2767,142 +3176,139 @@ fn set_read_permissions() -> Result<()> {
     ----- stderr -----
     Resolved 1 package in [TIME]
     Downloaded 1 package in [TIME]
+    Uninstalled 1 package in [TIME]
     Installed 1 package in [TIME]
-     + databricks==0.2
-    "###);
+     - tqdm==1000.0.0
+     + tqdm==1000.0.0
+    warning: Post-install hook failed with exit status: 3: `exit 3`
+    "###
+    );
 
     Ok(())
 }
 
-/// Test special case to generate versioned pip launchers.
-/// <https://github.com/pypa/pip/blob/3898741e29b7279e7bffe044ecfbe20f6a438b1e/src/pip/_internal/operations/install/wheel.py#L283>
-/// <https://github.com/astral-sh/uv/issues/1593>
+/// Sync with limited download and install concurrency.
 #[test]
-fn pip_entrypoints() -> Result<()> {
+fn concurrency() -> Result<()> {
     let context = TestContext::new("3.12");
 
-    // TODO(konstin): Remove git dep when the next pip version is released.
-    for pip_requirement in ["pip==24.0", "pip @ git+https://github.com/pypa/pip"] {
-        let requirements_txt = context.temp_dir.child("requirements.txt");
-        requirements_txt.touch()?;
-        requirements_txt.write_str(pip_requirement)?;
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(indoc! {r"
+        anyio==4.0.0
+        idna==3.6
+        sniffio==1.3.1
+    "})?;
 
-        command(&context)
-            .arg("requirements.txt")
-            .arg("--strict")
-            .output()
-            .expect("Failed to install pip");
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--concurrent-downloads")
+        .arg("1")
+        .arg("--concurrent-installs")
+        .arg("1")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
 
-        let bin_dir = context.venv.join(if cfg!(unix) {
-            "bin"
-        } else if cfg!(windows) {
-            "Scripts"
-        } else {
-            unimplemented!("Only Windows and Unix are supported")
-        });
-        // Pip 24.0 contains a pip3.10 launcher.
-        // https://inspector.pypi.io/project/pip/24.0/packages/8a/6a/19e9fe04fca059ccf770861c7d5721ab4c2aebc539889e97c7977528a53b/pip-24.0-py3-none-any.whl/pip-24.0.dist-info/entry_points.txt
-        ChildPath::new(bin_dir.join(format!("pip3.10{EXE_SUFFIX}")))
-            .assert(predicates::path::missing());
-        ChildPath::new(bin_dir.join(format!("pip3.12{EXE_SUFFIX}")))
-            .assert(predicates::path::exists());
-    }
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    Downloaded 3 packages in [TIME]
+    Installed 3 packages in [TIME]
+     + anyio==4.0.0
+     + idna==3.6
+     + sniffio==1.3.1
+    "###
+    );
+
+    context.assert_command("import anyio").success();
 
     Ok(())
 }
 
+/// Sync an unnamed path requirement with `--hash`, which should be matched to the requirement by
+/// its URL.
 #[test]
-fn invalidate_on_change() -> Result<()> {
+fn require_hashes_unnamed() -> Result<()> {
     let context = TestContext::new("3.12");
 
-    // Create an editable package.
-    let editable_dir = context.temp_dir.child("editable");
-    editable_dir.create_dir_all()?;
-    let pyproject_toml = editable_dir.child("pyproject.toml");
-    pyproject_toml.write_str(
-        r#"[project]
-name = "example"
-version = "0.0.0"
-dependencies = [
-  "anyio==4.0.0"
-]
-requires-python = ">=3.8"
-"#,
-    )?;
-
-    // Write to a requirements file.
-    let requirements_in = context.temp_dir.child("requirements.in");
-    requirements_in.write_str(&format!("-e {}", editable_dir.path().display()))?;
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(&format!(
+        "{} --hash=sha256:a34996d4bd5abb2336e14ff0a2d22b92cfd0f0ed344e6883041ce01953276a13",
+        context
+            .workspace_root
+            .join("scripts/links/tqdm-1000.0.0-py3-none-any.whl")
+            .display()
+    ))?;
 
     uv_snapshot!(context.filters(), command(&context)
-        .arg("requirements.in"), @r###"
+        .arg("requirements.txt")
+        .arg("--require-hashes"), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
 
     ----- stderr -----
-    Built 1 editable in [TIME]
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
     Installed 1 package in [TIME]
-     + example==0.0.0 (from file://[TEMP_DIR]/editable)
+     + tqdm==1000.0.0 (from file://[WORKSPACE]/scripts/links/tqdm-1000.0.0-py3-none-any.whl)
     "###
     );
 
-    // Re-installing should be a no-op.
-    uv_snapshot!(context.filters(), command(&context)
-        .arg("requirements.in"), @r###"
-    success: true
-    exit_code: 0
-    ----- stdout -----
+    Ok(())
+}
 
-    ----- stderr -----
-    Audited 1 package in [TIME]
-    "###
-    );
+/// Hashes for the same package across multiple requirements files should be merged, rather than
+/// the last file replacing the hashes from earlier files.
+#[test]
+fn require_hashes_multiple_files() -> Result<()> {
+    let context = TestContext::new("3.12");
 
-    // Modify the editable package.
-    pyproject_toml.write_str(
-        r#"[project]
-name = "example"
-version = "0.0.0"
-dependencies = [
-  "anyio==3.7.1"
-]
-requires-python = ">=3.8"
-"#,
-    )?;
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(indoc! {r"
+        tqdm==1000.0.0 --hash=sha256:a34996d4bd5abb2336e14ff0a2d22b92cfd0f0ed344e6883041ce01953276a13
+    "})?;
+    let other_txt = context.temp_dir.child("other.txt");
+    other_txt.write_str(indoc! {r"
+        tqdm==1000.0.0 --hash=sha256:0000000000000000000000000000000000000000000000000000000000000000
+    "})?;
 
-    // Re-installing should update the package.
     uv_snapshot!(context.filters(), command(&context)
-        .arg("requirements.in"), @r###"
+        .arg("requirements.txt")
+        .arg("other.txt")
+        .arg("--no-index")
+        .arg("--find-links")
+        .arg(context.workspace_root.join("scripts/links/")), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
 
     ----- stderr -----
-    Built 1 editable in [TIME]
-    Uninstalled 1 package in [TIME]
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
     Installed 1 package in [TIME]
-     - example==0.0.0 (from file://[TEMP_DIR]/editable)
-     + example==0.0.0 (from file://[TEMP_DIR]/editable)
+     + tqdm==1000.0.0
     "###
     );
 
     Ok(())
 }
 
-/// Install with bytecode compilation.
+/// Sync using `--find-links` with `--offline`, which should accept the local wheel.
 #[test]
-fn compile() -> Result<()> {
+fn find_links_offline_match() -> Result<()> {
     let context = TestContext::new("3.12");
 
     let requirements_txt = context.temp_dir.child("requirements.txt");
-    requirements_txt.touch()?;
-    requirements_txt.write_str("MarkupSafe==2.1.3")?;
+    requirements_txt.write_str(indoc! {r"
+        tqdm==1000.0.0
+    "})?;
 
-    uv_snapshot!(command(&context)
+    uv_snapshot!(context.filters(), command(&context)
         .arg("requirements.txt")
-        .arg("--compile")
-        .arg("--strict"), @r###"
+        .arg("--offline")
+        .arg("--find-links")
+        .arg(context.workspace_root.join("scripts/links/")), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
@@ -2911,119 +3317,159 @@ fn compile() -> Result<()> {
     Resolved 1 package in [TIME]
     Downloaded 1 package in [TIME]
     Installed 1 package in [TIME]
-    Bytecode compiled 3 files in [TIME]
-     + markupsafe==2.1.3
+     + tqdm==1000.0.0
     "###
     );
 
-    assert!(context
-        .site_packages()
-        .join("markupsafe")
-        .join("__pycache__")
-        .join("__init__.cpython-312.pyc")
-        .exists());
+    Ok(())
+}
 
-    context.assert_command("import markupsafe").success();
+/// Sync using `--find-links` with `--offline`, which should fail to find `numpy`.
+#[test]
+fn find_links_offline_no_match() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(indoc! {r"
+        numpy
+        tqdm==1000.0.0
+    "})?;
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("requirements.txt")
+        .arg("--offline")
+        .arg("--find-links")
+        .arg(context.workspace_root.join("scripts/links/")), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+      × No solution found when resolving dependencies:
+      ╰─▶ Because numpy was not found in the cache and you require numpy, we can conclude that the requirements are unsatisfiable.
+
+          hint: Packages were unavailable because the network was disabled
+    "###
+    );
 
     Ok(())
 }
 
-/// Test that the `PYC_INVALIDATION_MODE` option is recognized and that the error handling works.
+/// Sync using `--find-links` with a local directory. Ensure that cached wheels are reused.
 #[test]
-fn compile_invalid_pyc_invalidation_mode() -> Result<()> {
+fn find_links_cache() -> Result<()> {
     let context = TestContext::new("3.12");
 
     let requirements_txt = context.temp_dir.child("requirements.txt");
-    requirements_txt.write_str("MarkupSafe==2.1.3")?;
-
-    let filters: Vec<_> = context
-        .filters()
-        .into_iter()
-        .chain([
-            // The first file can vary so we capture it here
-            (
-                r#"\[SITE_PACKAGES\].*\.py", received: "#,
-                r#"[SITE_PACKAGES]/[FILE].py", received: "#,
-            ),
-        ])
-        .collect();
+    requirements_txt.write_str(indoc! {r"
+        tqdm
+    "})?;
 
-    uv_snapshot!(filters, command(&context)
+    // Install `tqdm`.
+    uv_snapshot!(context.filters(), command(&context)
         .arg("requirements.txt")
-        .arg("--compile")
-        .arg("--strict")
-        .env("PYC_INVALIDATION_MODE", "bogus"), @r###"
-    success: false
-    exit_code: 2
+        .arg("--find-links")
+        .arg(context.workspace_root.join("scripts/links/")), @r###"
+    success: true
+    exit_code: 0
     ----- stdout -----
 
     ----- stderr -----
     Resolved 1 package in [TIME]
     Downloaded 1 package in [TIME]
     Installed 1 package in [TIME]
-    error: Failed to bytecode-compile Python file in: [SITE_PACKAGES]/
-      Caused by: Python process stderr:
-    Invalid value for PYC_INVALIDATION_MODE "bogus", valid are "TIMESTAMP", "CHECKED_HASH", "UNCHECKED_HASH":
-      Caused by: Bytecode compilation failed, expected "[SITE_PACKAGES]/[FILE].py", received: ""
+     + tqdm==1000.0.0
+    "###
+    );
+
+    // Reinstall `tqdm` with `--reinstall`. Ensure that the wheel is reused.
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("requirements.txt")
+        .arg("--reinstall")
+        .arg("--find-links")
+        .arg(context.workspace_root.join("scripts/links/")), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Uninstalled 1 package in [TIME]
+    Installed 1 package in [TIME]
+     - tqdm==1000.0.0
+     + tqdm==1000.0.0
     "###
     );
 
     Ok(())
 }
 
-/// Raise an error when an editable's `Requires-Python` constraint is not met.
+/// Install without network access via the `--offline` flag.
 #[test]
-fn requires_python_editable() -> Result<()> {
+fn offline() -> Result<()> {
     let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("black==23.10.1")?;
 
-    // Create an editable package with a `Requires-Python` constraint that is not met.
-    let editable_dir = context.temp_dir.child("editable");
-    editable_dir.create_dir_all()?;
-    let pyproject_toml = editable_dir.child("pyproject.toml");
-    pyproject_toml.write_str(
-        r#"[project]
-name = "example"
-version = "0.0.0"
-dependencies = [
-  "anyio==4.0.0"
-]
-requires-python = "<=3.5"
-"#,
-    )?;
+    // Install with `--offline` with an empty cache.
+    uv_snapshot!(command(&context)
+        .arg("requirements.in")
+        .arg("--offline"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
 
-    // Write to a requirements file.
-    let requirements_in = context.temp_dir.child("requirements.in");
-    requirements_in.write_str(&format!("-e {}", editable_dir.path().display()))?;
+    ----- stderr -----
+      × No solution found when resolving dependencies:
+      ╰─▶ Because black==23.10.1 was not found in the cache and you require black==23.10.1, we can conclude that the requirements are unsatisfiable.
+
+          hint: Packages were unavailable because the network was disabled
+    "###
+    );
 
+    // Populate the cache.
     uv_snapshot!(command(&context)
         .arg("requirements.in"), @r###"
-    success: false
-    exit_code: 2
+    success: true
+    exit_code: 0
     ----- stdout -----
 
     ----- stderr -----
-    error: Editable `example` requires Python <=3.5, but 3.12.1 is installed
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + black==23.10.1
+    "###
+    );
+
+    // Install with `--offline` with a populated cache.
+    let venv = create_venv(&context.temp_dir, &context.cache_dir, "3.12");
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.in")
+        .arg("--offline")
+        .env("VIRTUAL_ENV", venv.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Installed 1 package in [TIME]
+     + black==23.10.1
     "###
     );
 
     Ok(())
 }
 
-/// Install packages from an index that "doesn't support" zip file streaming (by way of using
-/// data descriptors).
+/// Sync with a repeated `anyio` requirement. The second requirement should be ignored.
 #[test]
-fn no_stream() -> Result<()> {
+fn repeat_requirement() -> Result<()> {
     let context = TestContext::new("3.12");
-
-    // Write to a requirements file.
-    let requirements_txt = context.temp_dir.child("requirements.txt");
-    requirements_txt
-        .write_str("hashb_foxglove_protocolbuffers_python==25.3.0.1.20240226043130+465630478360")?;
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio\nanyio")?;
 
     uv_snapshot!(command(&context)
-        .arg("requirements.txt")
-        .arg("--index-url")
-        .arg("https://buf.build/gen/python"), @r###"
+        .arg("requirements.in"), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
@@ -3032,49 +3478,799 @@ fn no_stream() -> Result<()> {
     Resolved 1 package in [TIME]
     Downloaded 1 package in [TIME]
     Installed 1 package in [TIME]
-     + hashb-foxglove-protocolbuffers-python==25.3.0.1.20240226043130+465630478360
-    "###
-    );
+     + anyio==4.3.0
+    "###);
 
     Ok(())
 }
 
-/// Raise an error when a direct URL dependency's `Requires-Python` constraint is not met.
+/// Sync with a repeated, but conflicting `anyio` requirement. The second requirement should cause
+/// an error.
 #[test]
-fn requires_python_direct_url() -> Result<()> {
+fn conflicting_requirement() -> Result<()> {
     let context = TestContext::new("3.12");
-
-    // Create an editable package with a `Requires-Python` constraint that is not met.
-    let editable_dir = context.temp_dir.child("editable");
-    editable_dir.create_dir_all()?;
-    let pyproject_toml = editable_dir.child("pyproject.toml");
-    pyproject_toml.write_str(
-        r#"[project]
-name = "example"
-version = "0.0.0"
-dependencies = [
-  "anyio==4.0.0"
-]
-requires-python = "<=3.5"
-"#,
-    )?;
-
-    // Write to a requirements file.
     let requirements_in = context.temp_dir.child("requirements.in");
-    requirements_in.write_str(&format!("example @ {}", editable_dir.path().display()))?;
+    requirements_in.write_str("anyio\nanyio==4.0.0")?;
 
-    uv_snapshot!(context.filters(), command(&context)
+    uv_snapshot!(command(&context)
         .arg("requirements.in"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 2
     ----- stdout -----
 
     ----- stderr -----
-      × No solution found when resolving dependencies:
-      ╰─▶ Because the current Python version (3.12.1) does not satisfy Python<=3.5 and example==0.0.0 depends on Python<=3.5, we can conclude that example==0.0.0 cannot be used.
-          And because only example==0.0.0 is available and you require example, we can conclude that the requirements are unsatisfiable.
-    "###
-    );
+    error: Failed to determine installation plan
+      Caused by: Detected duplicate package in requirements: anyio
+    "###);
+
+    Ok(())
+}
+
+/// Don't preserve the mtime from .tar.gz files, it may be the unix epoch (1970-01-01), while Python's zip
+/// implementation can't handle files with an mtime older than 1980.
+/// See also <https://github.com/alexcrichton/tar-rs/issues/349>.
+#[test]
+fn tar_dont_preserve_mtime() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("tomli @ https://files.pythonhosted.org/packages/c0/3f/d7af728f075fb08564c5949a9c95e44352e23dee646869fa104a3b2060a3/tomli-2.0.1.tar.gz")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + tomli==2.0.1 (from https://files.pythonhosted.org/packages/c0/3f/d7af728f075fb08564c5949a9c95e44352e23dee646869fa104a3b2060a3/tomli-2.0.1.tar.gz)
+    "###);
+
+    Ok(())
+}
+
+/// Avoid creating a file with 000 permissions
+#[test]
+fn set_read_permissions() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("databricks==0.2")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + databricks==0.2
+    "###);
+
+    Ok(())
+}
+
+/// Test special case to generate versioned pip launchers.
+/// <https://github.com/pypa/pip/blob/3898741e29b7279e7bffe044ecfbe20f6a438b1e/src/pip/_internal/operations/install/wheel.py#L283>
+/// <https://github.com/astral-sh/uv/issues/1593>
+#[test]
+fn pip_entrypoints() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // TODO(konstin): Remove git dep when the next pip version is released.
+    for pip_requirement in ["pip==24.0", "pip @ git+https://github.com/pypa/pip"] {
+        let requirements_txt = context.temp_dir.child("requirements.txt");
+        requirements_txt.touch()?;
+        requirements_txt.write_str(pip_requirement)?;
+
+        command(&context)
+            .arg("requirements.txt")
+            .arg("--strict")
+            .output()
+            .expect("Failed to install pip");
+
+        let bin_dir = context.venv.join(if cfg!(unix) {
+            "bin"
+        } else if cfg!(windows) {
+            "Scripts"
+        } else {
+            unimplemented!("Only Windows and Unix are supported")
+        });
+        // Pip 24.0 contains a pip3.10 launcher.
+        // https://inspector.pypi.io/project/pip/24.0/packages/8a/6a/19e9fe04fca059ccf770861c7d5721ab4c2aebc539889e97c7977528a53b/pip-24.0-py3-none-any.whl/pip-24.0.dist-info/entry_points.txt
+        ChildPath::new(bin_dir.join(format!("pip3.10{EXE_SUFFIX}")))
+            .assert(predicates::path::missing());
+        ChildPath::new(bin_dir.join(format!("pip3.12{EXE_SUFFIX}")))
+            .assert(predicates::path::exists());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn invalidate_on_change() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Create an editable package.
+    let editable_dir = context.temp_dir.child("editable");
+    editable_dir.create_dir_all()?;
+    let pyproject_toml = editable_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"[project]
+name = "example"
+version = "0.0.0"
+dependencies = [
+  "anyio==4.0.0"
+]
+requires-python = ">=3.8"
+"#,
+    )?;
+
+    // Write to a requirements file.
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str(&format!("-e {}", editable_dir.path().display()))?;
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Built 1 editable in [TIME]
+    Installed 1 package in [TIME]
+     + example==0.0.0 (from file://[TEMP_DIR]/editable)
+    "###
+    );
+
+    // Re-installing should be a no-op.
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Audited 1 package in [TIME]
+    "###
+    );
+
+    // Modify the editable package.
+    pyproject_toml.write_str(
+        r#"[project]
+name = "example"
+version = "0.0.0"
+dependencies = [
+  "anyio==3.7.1"
+]
+requires-python = ">=3.8"
+"#,
+    )?;
+
+    // Re-installing should update the package.
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Built 1 editable in [TIME]
+    Uninstalled 1 package in [TIME]
+    Installed 1 package in [TIME]
+     - example==0.0.0 (from file://[TEMP_DIR]/editable)
+     + example==0.0.0 (from file://[TEMP_DIR]/editable)
+    "###
+    );
+
+    Ok(())
+}
+
+/// Install with bytecode compilation.
+#[test]
+fn compile() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.touch()?;
+    requirements_txt.write_str("MarkupSafe==2.1.3")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--compile")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+    Bytecode compiled 3 files in [TIME]
+     + markupsafe==2.1.3
+    "###
+    );
+
+    assert!(context
+        .site_packages()
+        .join("markupsafe")
+        .join("__pycache__")
+        .join("__init__.cpython-312.pyc")
+        .exists());
+
+    // The compiled files should be tracked in the RECORD.
+    let record = fs::read_to_string(
+        context
+            .site_packages()
+            .join("MarkupSafe-2.1.3.dist-info")
+            .join("RECORD"),
+    )?;
+    assert!(record.contains("markupsafe/__pycache__/__init__.cpython-312.pyc,sha256="));
+
+    context.assert_command("import markupsafe").success();
+
+    Ok(())
+}
+
+/// Test that the `PYC_INVALIDATION_MODE` option is recognized and that the error handling works.
+#[test]
+fn compile_invalid_pyc_invalidation_mode() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("MarkupSafe==2.1.3")?;
+
+    let filters: Vec<_> = context
+        .filters()
+        .into_iter()
+        .chain([
+            // The first file can vary so we capture it here
+            (
+                r#"\[SITE_PACKAGES\].*\.py", received: "#,
+                r#"[SITE_PACKAGES]/[FILE].py", received: "#,
+            ),
+        ])
+        .collect();
+
+    uv_snapshot!(filters, command(&context)
+        .arg("requirements.txt")
+        .arg("--compile")
+        .arg("--strict")
+        .env("PYC_INVALIDATION_MODE", "bogus"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+    error: Failed to bytecode-compile Python file in: [SITE_PACKAGES]/
+      Caused by: Python process stderr:
+    Invalid value for PYC_INVALIDATION_MODE "bogus", valid are "TIMESTAMP", "CHECKED_HASH", "UNCHECKED_HASH":
+      Caused by: Bytecode compilation failed, expected "[SITE_PACKAGES]/[FILE].py", received: ""
+    "###
+    );
+
+    Ok(())
+}
+
+/// Raise an error when an editable's `Requires-Python` constraint is not met.
+#[test]
+fn requires_python_editable() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Create an editable package with a `Requires-Python` constraint that is not met.
+    let editable_dir = context.temp_dir.child("editable");
+    editable_dir.create_dir_all()?;
+    let pyproject_toml = editable_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"[project]
+name = "example"
+version = "0.0.0"
+dependencies = [
+  "anyio==4.0.0"
+]
+requires-python = "<=3.5"
+"#,
+    )?;
+
+    // Write to a requirements file.
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str(&format!("-e {}", editable_dir.path().display()))?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.in"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Editable `example` requires Python <=3.5, but 3.12.1 is installed
+    "###
+    );
+
+    Ok(())
+}
+
+/// Install packages from an index that "doesn't support" zip file streaming (by way of using
+/// data descriptors).
+#[test]
+fn no_stream() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Write to a requirements file.
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt
+        .write_str("hashb_foxglove_protocolbuffers_python==25.3.0.1.20240226043130+465630478360")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--index-url")
+        .arg("https://buf.build/gen/python"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + hashb-foxglove-protocolbuffers-python==25.3.0.1.20240226043130+465630478360
+    "###
+    );
+
+    Ok(())
+}
+
+/// Raise an error when a direct URL dependency's `Requires-Python` constraint is not met.
+#[test]
+fn requires_python_direct_url() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Create an editable package with a `Requires-Python` constraint that is not met.
+    let editable_dir = context.temp_dir.child("editable");
+    editable_dir.create_dir_all()?;
+    let pyproject_toml = editable_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"[project]
+name = "example"
+version = "0.0.0"
+dependencies = [
+  "anyio==4.0.0"
+]
+requires-python = "<=3.5"
+"#,
+    )?;
+
+    // Write to a requirements file.
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str(&format!("example @ {}", editable_dir.path().display()))?;
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("requirements.in"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+      × No solution found when resolving dependencies:
+      ╰─▶ Because the current Python version (3.12.1) does not satisfy Python<=3.5 and example==0.0.0 depends on Python<=3.5, we can conclude that example==0.0.0 cannot be used.
+          And because only example==0.0.0 is available and you require example, we can conclude that the requirements are unsatisfiable.
+    "###
+    );
+
+    Ok(())
+}
+
+/// Install from a `uv.lock` written by `pip compile`, without re-resolving.
+#[test]
+fn sync_lockfile() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==3.7.0")?;
+
+    context
+        .compile()
+        .arg("requirements.in")
+        .arg("--output-file")
+        .arg("uv.lock")
+        .assert()
+        .success();
+
+    uv_snapshot!(command(&context)
+        .arg("uv.lock")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Downloaded 3 packages in [TIME]
+    Installed 3 packages in [TIME]
+     + anyio==3.7.0
+     + idna==3.6
+     + sniffio==1.3.1
+    "###
+    );
+
+    context.assert_command("import anyio").success();
+
+    Ok(())
+}
+
+/// Report the changes that a sync would make, without modifying the environment.
+#[test]
+fn dry_run() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("tqdm==1000.0.0")?;
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("requirements.txt")
+        .arg("--no-index")
+        .arg("--find-links")
+        .arg(context.workspace_root.join("scripts/links/"))
+        .arg("--dry-run"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Would download 1 package
+    Would install 1 package
+     + tqdm==1000.0.0
+    "###
+    );
+
+    assert!(!context
+        .site_packages()
+        .join("tqdm-1000.0.0.dist-info")
+        .exists());
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("requirements.txt")
+        .arg("--no-index")
+        .arg("--find-links")
+        .arg(context.workspace_root.join("scripts/links/")), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + tqdm==1000.0.0
+    "###
+    );
+
+    // Switching to a source distribution would require a build.
+    requirements_txt.write_str("tqdm==999.0.0")?;
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("requirements.txt")
+        .arg("--no-index")
+        .arg("--find-links")
+        .arg(context.workspace_root.join("scripts/links/"))
+        .arg("--dry-run"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Would download 1 package
+    Would build 1 package
+    Would uninstall 1 package
+    Would install 1 package
+     - tqdm==1000.0.0
+     + tqdm==999.0.0
+    "###
+    );
+
+    assert!(context
+        .site_packages()
+        .join("tqdm-1000.0.0.dist-info")
+        .exists());
+
+    Ok(())
+}
+
+/// Report an editable that a sync would install, without building it.
+#[test]
+fn dry_run_editable() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(&indoc::formatdoc! {r"
+        -e file://{workspace_root}/scripts/packages/poetry_editable
+        ",
+        workspace_root = context.workspace_root.simplified_display(),
+    })?;
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("requirements.txt")
+        .arg("--dry-run"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Would build 1 package
+    Would install 1 package
+     + -e file://[WORKSPACE]/scripts/packages/poetry_editable
+    "###
+    );
+
+    assert!(!context
+        .site_packages()
+        .join("poetry_editable-0.1.0.dist-info")
+        .exists());
+    assert!(!context.cache_dir.child("built-wheels-v2").exists());
+
+    Ok(())
+}
+
+/// In a virtual environment with access to the system site packages, a matching system package
+/// should satisfy a requirement without being uninstalled, and a mismatched system package should
+/// be shadowed by an installation into the environment. Malformed system packages are ignored.
+#[test]
+#[cfg(unix)]
+fn system_site_packages() -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let context = TestContext::new("3.12");
+
+    // Populate a stand-in for the system site packages.
+    let system = context.temp_dir.child("system-site-packages");
+    let dist_info = system.child("validation-1.0.0.dist-info");
+    dist_info
+        .child("METADATA")
+        .write_str("Metadata-Version: 2.1\nName: validation\nVersion: 1.0.0\n")?;
+    dist_info.child("INSTALLER").write_str("pip\n")?;
+    dist_info.child("RECORD").touch()?;
+    system
+        .child("broken-not-a-version.dist-info")
+        .create_dir_all()?;
+
+    // Expose the system site packages to the environment, by way of an interpreter that reports
+    // them as the base installation's `site-packages`.
+    let pyvenv_cfg = context.venv.join("pyvenv.cfg");
+    let contents = fs_err::read_to_string(&pyvenv_cfg)?.replace(
+        "include-system-site-packages = false",
+        "include-system-site-packages = true",
+    );
+    fs_err::write(&pyvenv_cfg, contents)?;
+    let python = context.temp_dir.child("python");
+    python.write_str(&format!(
+        "#!/bin/sh\n{} \"$@\" | sed 's#\"base_site_packages\": \\[[^]]*\\]#\"base_site_packages\": [\"{}\"]#'\n",
+        venv_to_interpreter(&context.venv).display(),
+        system.display()
+    ))?;
+    fs_err::set_permissions(&python, std::fs::Permissions::from_mode(0o755))?;
+
+    // The system package satisfies the requirement.
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("validation==1.0.0")?;
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("requirements.txt")
+        .arg("--python")
+        .arg(python.path())
+        .arg("--no-index")
+        .arg("--find-links")
+        .arg(context.workspace_root.join("scripts/links/")), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Audited 1 package in [TIME]
+    "###
+    );
+
+    assert!(!context
+        .site_packages()
+        .join("validation-1.0.0.dist-info")
+        .exists());
+
+    // A different version is installed into the environment, shadowing the system package.
+    requirements_txt.write_str("validation==2.0.0")?;
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("requirements.txt")
+        .arg("--python")
+        .arg(python.path())
+        .arg("--no-index")
+        .arg("--find-links")
+        .arg(context.workspace_root.join("scripts/links/")), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + validation==2.0.0
+    "###
+    );
+
+    assert!(context
+        .site_packages()
+        .join("validation-2.0.0.dist-info")
+        .exists());
+
+    // The system package is never modified.
+    dist_info.assert(predicates::path::is_dir());
+
+    Ok(())
+}
+
+/// Packages installed by Conda into a Conda environment are preserved, rather than removed as
+/// extraneous, as Conda tracks them separately.
+#[test]
+fn sync_conda_env_preserves_conda_packages() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("validation==1.0.0\ntqdm==1000.0.0")?;
+
+    command(&context)
+        .arg("requirements.txt")
+        .arg("--no-index")
+        .arg("--find-links")
+        .arg(context.workspace_root.join("scripts/links/"))
+        .assert()
+        .success();
+
+    // Mark the environment as a Conda environment, and `validation` as having been installed by
+    // Conda.
+    fs_err::create_dir_all(context.venv.join("conda-meta"))?;
+    fs_err::write(
+        context
+            .site_packages()
+            .join("validation-1.0.0.dist-info")
+            .join("INSTALLER"),
+        "conda\n",
+    )?;
+
+    // `validation` is no longer required, but is left in place.
+    requirements_txt.write_str("tqdm==1000.0.0")?;
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("requirements.txt")
+        .arg("--no-index")
+        .arg("--find-links")
+        .arg(context.workspace_root.join("scripts/links/")), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Audited 1 package in [TIME]
+    "###
+    );
+
+    assert!(context
+        .site_packages()
+        .join("validation-1.0.0.dist-info")
+        .exists());
+
+    // Outside of a Conda environment, `validation` is removed.
+    fs_err::remove_dir_all(context.venv.join("conda-meta"))?;
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("requirements.txt")
+        .arg("--no-index")
+        .arg("--find-links")
+        .arg(context.workspace_root.join("scripts/links/")), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Uninstalled 1 package in [TIME]
+     - validation==1.0.0
+    "###
+    );
+
+    Ok(())
+}
+
+/// Install into an environment given by its root directory, rather than its interpreter.
+#[test]
+#[cfg(unix)]
+fn sync_python_environment_directory() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let parent = context.temp_dir.child("parent");
+    parent.create_dir_all()?;
+    let venv = create_venv(&parent, &context.cache_dir, "3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("validation==1.0.0")?;
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("requirements.txt")
+        .arg("--python")
+        .arg(&venv)
+        .arg("--no-index")
+        .arg("--find-links")
+        .arg(context.workspace_root.join("scripts/links/")), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + validation==1.0.0
+    "###
+    );
+
+    // The package is installed into the given environment, rather than the active one.
+    assert!(venv
+        .join("lib")
+        .join("python3.12")
+        .join("site-packages")
+        .join("validation-1.0.0.dist-info")
+        .exists());
+    assert!(!context
+        .site_packages()
+        .join("validation-1.0.0.dist-info")
+        .exists());
+
+    Ok(())
+}
+
+/// Count lookups of unzipped wheels in the cache with `--cache-metrics`.
+#[test]
+fn cache_metrics_wheel_index() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("iniconfig==2.0.0")?;
+    let metrics_json = context.temp_dir.child("metrics.json");
+
+    let read_metrics = || -> Result<serde_json::Value> {
+        Ok(serde_json::from_str(&fs::read_to_string(
+            metrics_json.path(),
+        )?)?)
+    };
+
+    // With an empty cache, the wheel isn't in the index.
+    command(&context)
+        .arg("requirements.txt")
+        .arg("--cache-metrics")
+        .arg(metrics_json.path())
+        .assert()
+        .success();
+    let metrics = read_metrics()?;
+    assert_eq!(metrics["hits"], 0);
+    assert!(metrics["misses"].as_u64().unwrap() > 0);
+
+    // Installing into a new virtual environment is served by the unzipped wheel in the cache,
+    // without any network requests.
+    let venv = create_venv(&context.temp_dir, &context.cache_dir, "3.12");
+    command(&context)
+        .arg("requirements.txt")
+        .arg("--cache-metrics")
+        .arg(metrics_json.path())
+        .env("VIRTUAL_ENV", venv.as_os_str())
+        .assert()
+        .success();
+    let metrics = read_metrics()?;
+    assert_eq!(metrics["hits"], 1);
+    assert_eq!(metrics["misses"], 0);
 
     Ok(())
 }
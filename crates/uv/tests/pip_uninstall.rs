@@ -426,3 +426,44 @@ fn uninstall_duplicate() -> Result<()> {
 
     Ok(())
 }
+
+/// Warn when uninstalling a package that was installed by another tool, like `conda`.
+#[test]
+fn uninstall_foreign_installer() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("tqdm==1000.0.0")?;
+
+    sync_command(&context)
+        .arg(requirements_txt.path())
+        .arg("--no-index")
+        .arg("--find-links")
+        .arg(context.workspace_root.join("scripts/links/"))
+        .assert()
+        .success();
+
+    // Mark the package as having been installed by `conda`.
+    fs_err::write(
+        context
+            .site_packages()
+            .join("tqdm-1000.0.0.dist-info")
+            .join("INSTALLER"),
+        "conda\n",
+    )?;
+
+    uv_snapshot!(uninstall_command(&context)
+        .arg("tqdm"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: Uninstalling tqdm==1000.0.0, which was installed by `conda`. This may leave `conda` in an inconsistent state.
+    Uninstalled 1 package in [TIME]
+     - tqdm==1000.0.0
+    "###
+    );
+
+    Ok(())
+}
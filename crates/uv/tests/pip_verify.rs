@@ -0,0 +1,136 @@
+use std::process::Command;
+
+use anyhow::Result;
+
+use common::uv_snapshot;
+
+use crate::common::{get_bin, TestContext, EXCLUDE_NEWER};
+
+mod common;
+
+/// Create a `pip install` command with options shared across scenarios.
+fn install_command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("pip")
+        .arg("install")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir);
+
+    if cfg!(all(windows, debug_assertions)) {
+        // TODO(konstin): Reduce stack usage in debug mode enough that the tests pass with the
+        // default windows stack of 1MB
+        command.env("UV_STACK_SIZE", (2 * 1024 * 1024).to_string());
+    }
+
+    command
+}
+
+/// Create a `pip verify` command with options shared across scenarios.
+fn verify_command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("pip")
+        .arg("verify")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir);
+
+    command
+}
+
+/// Verify an environment in which the installed files are unchanged.
+#[test]
+fn verify_unchanged() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(context.filters(), install_command(&context)
+        .arg("tqdm==1000.0.0")
+        .arg("--no-index")
+        .arg("--find-links")
+        .arg(context.workspace_root.join("scripts/links/")), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + tqdm==1000.0.0
+    "###
+    );
+
+    uv_snapshot!(context.filters(), verify_command(&context), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Verified 1 package in [TIME]
+    All installed files match their RECORD
+    "###
+    );
+}
+
+/// Verify an environment in which installed files were modified, removed, or added.
+#[test]
+fn verify_modified() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(context.filters(), install_command(&context)
+        .arg("tqdm==1000.0.0")
+        .arg("--no-index")
+        .arg("--find-links")
+        .arg(context.workspace_root.join("scripts/links/")), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + tqdm==1000.0.0
+    "###
+    );
+
+    let site_packages = context.site_packages();
+    fs_err::write(
+        site_packages.join("tqdm/__init__.py"),
+        "raise ImportError\n",
+    )?;
+    fs_err::remove_file(site_packages.join("tqdm-1000.0.0.dist-info/WHEEL"))?;
+    fs_err::write(site_packages.join("tqdm/patch.py"), "")?;
+
+    // Bytecode isn't reported as extra.
+    fs_err::create_dir_all(site_packages.join("tqdm/__pycache__"))?;
+    fs_err::write(
+        site_packages.join("tqdm/__pycache__/__init__.cpython-312.pyc"),
+        "",
+    )?;
+
+    uv_snapshot!(context.filters(), verify_command(&context), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    tqdm==1000.0.0
+      missing: tqdm-1000.0.0.dist-info/WHEEL
+      modified: tqdm/__init__.py
+      extra: tqdm/patch.py
+
+    ----- stderr -----
+    Verified 1 package in [TIME]
+    Found 3 issues
+    "###
+    );
+
+    Ok(())
+}
@@ -0,0 +1,142 @@
+#![cfg(all(feature = "python", unix))]
+
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+
+use common::uv_snapshot;
+use uv_interpreter::PythonTarget;
+
+use crate::common::{create_venv, get_bin, TestContext};
+
+mod common;
+
+/// Create a `python find` command with options shared across scenarios.
+fn command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("python")
+        .arg("find")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir);
+    command
+}
+
+/// By default, `python find` should prefer the active virtual environment.
+#[test]
+fn find_virtualenv() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(context.filters(), command(&context)
+        .env("VIRTUAL_ENV", context.venv.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    [VENV]/bin/python
+
+    ----- stderr -----
+    "###);
+}
+
+/// `python find` should fail for a request that can't be satisfied.
+#[test]
+fn find_missing() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("python-does-not-exist"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to locate Python interpreter at `python-does-not-exist`
+    "###);
+}
+
+/// `--python-sources` should control the order of discovery, and `--only-python-sources` should
+/// exclude any other sources.
+#[test]
+fn find_python_sources() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Activate a Python 3.11 environment, distinct from the Python 3.12 `.venv`.
+    let active = context.temp_dir.child("active");
+    active.create_dir_all()?;
+    let active = fs_err::canonicalize(create_venv(&active, &context.cache_dir, "3.11"))?;
+    let dot_venv = fs_err::canonicalize(&context.venv)?;
+
+    // The active virtual environment retains its priority unless it's listed.
+    let output = command(&context)
+        .arg("--python-sources")
+        .arg("dot-venv")
+        .env("VIRTUAL_ENV", active.as_os_str())
+        .output()?;
+    assert!(output.status.success());
+    assert!(Path::new(String::from_utf8(output.stdout)?.trim_end()).starts_with(&active));
+
+    // Prefer the `.venv` over the active virtual environment.
+    let output = command(&context)
+        .arg("--python-sources")
+        .arg("dot-venv,virtual-env")
+        .env("VIRTUAL_ENV", active.as_os_str())
+        .output()?;
+    assert!(output.status.success());
+    assert!(Path::new(String::from_utf8(output.stdout)?.trim_end()).starts_with(dot_venv));
+
+    // Exclude every source that could provide an interpreter.
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("--python-sources")
+        .arg("pyenv")
+        .arg("--only-python-sources")
+        .env("VIRTUAL_ENV", active.as_os_str())
+        .env("UV_TEST_PYTHON_PATH", context.venv.join("bin").as_os_str()), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: No versions of Python could be found. Is Python installed?
+    "###);
+
+    Ok(())
+}
+
+/// `python find --json` should describe the selected interpreter's target environment, in the
+/// format accepted by `pip compile --python-env`.
+#[test]
+fn find_json() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let output = command(&context)
+        .arg("--json")
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .output()?;
+    assert!(output.status.success());
+
+    let target_json = context.temp_dir.child("target.json");
+    target_json.write_binary(&output.stdout)?;
+    let target = PythonTarget::from_path(target_json.path())?;
+
+    assert_eq!(target.markers.python_version.to_string(), "3.12");
+    assert!(target.tags.iter().any(|tag| tag == "py3-none-any"));
+    target.tags()?;
+
+    // The installation details are those of the virtual environment.
+    let purelib = target
+        .sysconfig_paths
+        .get("purelib")
+        .expect("`purelib` to be included in the sysconfig paths");
+    assert!(fs_err::canonicalize(purelib)?.starts_with(fs_err::canonicalize(&context.venv)?));
+    let soabi = target.soabi.expect("CPython to define `SOABI`");
+    assert!(soabi.starts_with("cpython-312"));
+    assert!(target
+        .ext_suffix
+        .is_some_and(|ext_suffix| ext_suffix.contains(&soabi)));
+
+    Ok(())
+}
@@ -0,0 +1,45 @@
+#![cfg(all(feature = "python", unix))]
+
+use std::process::Command;
+
+use anyhow::Result;
+
+use common::uv_snapshot;
+
+use crate::common::{create_bin_with_executables, get_bin, TestContext};
+
+mod common;
+
+/// `python list` should report every interpreter in `PATH`, in order, once each.
+#[test]
+fn list_path() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let bin = create_bin_with_executables(&context.temp_dir, &["3.11", "3.12"])?;
+
+    let filters = [(
+        r"(cpython-3\.\d+)\.\d+-\S+\s+\S+ \(path\)",
+        "$1.[X]-[PLATFORM]  [PYTHON] (path)",
+    )]
+    .into_iter()
+    .chain(context.filters())
+    .collect::<Vec<_>>();
+
+    uv_snapshot!(filters, Command::new(get_bin())
+        .arg("python")
+        .arg("list")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("UV_TEST_PYTHON_PATH", bin)
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    cpython-3.11.[X]-[PLATFORM]  [PYTHON] (path)
+    cpython-3.12.[X]-[PLATFORM]  [PYTHON] (path)
+
+    ----- stderr -----
+    "###);
+
+    Ok(())
+}
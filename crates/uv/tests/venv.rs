@@ -8,6 +8,7 @@ use assert_cmd::prelude::*;
 use assert_fs::fixture::ChildPath;
 use assert_fs::prelude::*;
 use fs_err::PathExt;
+use predicates::prelude::*;
 use uv_fs::Simplified;
 
 use crate::common::{
@@ -116,6 +117,43 @@ fn create_venv() {
     context.venv.assert(predicates::path::is_dir());
 }
 
+#[test]
+#[cfg(unix)]
+fn create_venv_copies() -> Result<()> {
+    let context = VenvTestContext::new(&["3.12"]);
+
+    uv_snapshot!(context.filters(), context.venv_command()
+        .arg(context.venv.as_os_str())
+        .arg("--copies")
+        .arg("--python")
+        .arg("3.12"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Using Python 3.12.1 interpreter at: [PATH]
+    Creating virtualenv at: .venv
+    Activate with: source .venv/bin/activate
+    "###
+    );
+
+    // The executables should be regular files, rather than symlinks.
+    for name in ["python", "python3", "python3.12"] {
+        let executable = context.venv.join("bin").join(name);
+        assert!(fs_err::symlink_metadata(&executable)?.is_file());
+    }
+
+    // The environment should still be usable.
+    Command::new(context.venv.join("bin").join("python"))
+        .arg("-c")
+        .arg("import sys; assert sys.prefix != sys.base_prefix")
+        .assert()
+        .success();
+
+    Ok(())
+}
+
 #[test]
 fn create_venv_defaults_to_cwd() {
     let context = VenvTestContext::new(&["3.12"]);
@@ -276,6 +314,226 @@ fn create_venv_python_patch() {
     context.venv.assert(predicates::path::is_dir());
 }
 
+/// Without `--python`, a `.python-version` file in the current directory or any parent directory
+/// should select the interpreter.
+#[test]
+fn create_venv_python_version_file() -> Result<()> {
+    let context = VenvTestContext::new(&["3.12", "3.10"]);
+    context
+        .temp_dir
+        .child(".python-version")
+        .write_str("3.10\n")?;
+    let project = context.temp_dir.child("project");
+    project.create_dir_all()?;
+
+    uv_snapshot!(context.filters(), context.venv_command()
+        .arg(context.venv.as_os_str())
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&project), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Using Python 3.10.13 interpreter at: [PATH]
+    Creating virtualenv at: .venv
+    Activate with: source .venv/bin/activate
+    "###
+    );
+
+    context.venv.assert(predicates::path::is_dir());
+
+    // A pinned Python that isn't installed is an error, rather than falling back to the default.
+    context
+        .temp_dir
+        .child(".python-version")
+        .write_str("3.15\n")?;
+    let filters = &[(r"requested by `.+`", "requested by `[PATH]`")];
+    uv_snapshot!(filters, context.venv_command()
+        .arg(context.venv.as_os_str())
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&project), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+      × No Python 3.15 found, as requested by `[PATH]`. Is Python 3.15 installed?
+    "###
+    );
+
+    Ok(())
+}
+
+/// After the base interpreter is replaced by a new patch release, `--upgrade` should re-point the
+/// environment at it in-place.
+#[test]
+#[cfg(unix)]
+fn upgrade_venv() -> Result<()> {
+    let context = VenvTestContext::new(&["3.12"]);
+
+    uv_snapshot!(context.filters(), context.venv_command()
+        .arg(context.venv.as_os_str())
+        .arg("--python")
+        .arg("3.12"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Using Python 3.12.1 interpreter at: [PATH]
+    Creating virtualenv at: .venv
+    Activate with: source .venv/bin/activate
+    "###
+    );
+
+    // Simulate an environment created by a since-removed Python 3.12.0.
+    let python = context.venv.join("bin").join("python");
+    fs_err::remove_file(&python)?;
+    fs_err::os::unix::fs::symlink(context.temp_dir.join("missing").join("python3.12"), &python)?;
+    let pyvenv_cfg = context.venv.child("pyvenv.cfg");
+    let missing = context.temp_dir.join("missing").join("python3.12");
+    let mut content = fs_err::read_to_string(&pyvenv_cfg)?
+        .replace("version_info = 3.12.1", "version_info = 3.12.0");
+    // As written by the standard library's `venv`.
+    content.push_str(&format!(
+        "executable = {}\ncommand = {} -m venv {}\n",
+        missing.display(),
+        missing.display(),
+        context.venv.display()
+    ));
+    pyvenv_cfg.write_str(&content)?;
+    context.venv.child("marker.txt").touch()?;
+
+    uv_snapshot!(context.filters(), context.venv_command()
+        .arg(context.venv.as_os_str())
+        .arg("--upgrade"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Using Python 3.12.1 interpreter at: [PATH]
+    Upgrading virtualenv at: .venv
+    Activate with: source .venv/bin/activate
+    "###
+    );
+
+    // The environment should be upgraded in-place, rather than recreated.
+    context
+        .venv
+        .child("marker.txt")
+        .assert(predicates::path::exists());
+    pyvenv_cfg.assert(predicates::str::contains("version_info = 3.12.1"));
+    pyvenv_cfg.assert(predicates::str::contains("missing").not());
+    pyvenv_cfg.assert(predicates::str::contains(format!(
+        " -m venv {}",
+        context.venv.display()
+    )));
+    Command::new(&python)
+        .arg("-c")
+        .arg("import sys; assert sys.prefix != sys.base_prefix")
+        .assert()
+        .success();
+
+    Ok(())
+}
+
+/// Upgrading an environment with seed packages should retain them, rather than reinstalling them.
+#[test]
+#[cfg(unix)]
+fn upgrade_venv_seed() -> Result<()> {
+    let context = VenvTestContext::new(&["3.12"]);
+
+    uv_snapshot!(context.filters(), context.venv_command()
+        .arg(context.venv.as_os_str())
+        .arg("--seed")
+        .arg("--python")
+        .arg("3.12"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Using Python 3.12.1 interpreter at: [PATH]
+    Creating virtualenv at: .venv
+     + pip==24.0
+    Activate with: source .venv/bin/activate
+    "###
+    );
+
+    // Simulate an environment created by a since-removed Python 3.12.0.
+    let python = context.venv.join("bin").join("python");
+    fs_err::remove_file(&python)?;
+    fs_err::os::unix::fs::symlink(context.temp_dir.join("missing").join("python3.12"), &python)?;
+
+    // The seed packages are validated against the new interpreter, but not reinstalled.
+    uv_snapshot!(context.filters(), context.venv_command()
+        .arg(context.venv.as_os_str())
+        .arg("--upgrade")
+        .arg("--offline"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Using Python 3.12.1 interpreter at: [PATH]
+    Upgrading virtualenv at: .venv
+    Activate with: source .venv/bin/activate
+    "###
+    );
+
+    Command::new(&python)
+        .arg("-m")
+        .arg("pip")
+        .arg("--version")
+        .assert()
+        .success();
+
+    Ok(())
+}
+
+/// Upgrading to a different minor version would require recreating `site-packages`.
+#[test]
+fn upgrade_venv_minor_version() {
+    let context = VenvTestContext::new(&["3.12", "3.10"]);
+
+    uv_snapshot!(context.filters(), context.venv_command()
+        .arg(context.venv.as_os_str())
+        .arg("--python")
+        .arg("3.12"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Using Python 3.12.1 interpreter at: [PATH]
+    Creating virtualenv at: .venv
+    Activate with: source .venv/bin/activate
+    "###
+    );
+
+    uv_snapshot!(context.filters(), context.venv_command()
+        .arg(context.venv.as_os_str())
+        .arg("--upgrade")
+        .arg("--python")
+        .arg("3.10")
+        .env("UV_NO_WRAP", "1"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    Using Python 3.10.13 interpreter at: [PATH]
+    Upgrading virtualenv at: .venv
+    uv::venv::upgrade
+
+      × Failed to upgrade virtualenv
+      ╰─▶ Can't upgrade a Python 3.12.1 virtualenv to Python 3.10.13 in-place; only patch upgrades are supported
+    "###
+    );
+}
+
 #[test]
 fn file_exists() -> Result<()> {
     let context = VenvTestContext::new(&["3.12"]);